@@ -0,0 +1,568 @@
+//! Archive listing and extraction (zip, tar, tar.gz, tar.zst).
+//!
+//! # Overview
+//! Reads archives natively so tools never shell out to `tar`/`unzip` with
+//! platform-dependent flags. The format is detected from the magic bytes, not
+//! the extension: zip, or tar either plain or wrapped in gzip or zstd.
+//!
+//! Extraction is hardened against hostile archives:
+//! - Entries with absolute paths, drive prefixes, or `..` components are
+//!   skipped and reported instead of written.
+//! - Nothing is written through a symlink: an existing symlink at a target is
+//!   replaced, and entries below a symlinked directory are skipped.
+//! - Symlinks are created only when their target stays inside the destination
+//!   (and only on Unix), and `..` may only lead the target, so it cannot climb
+//!   back out through another extracted link. Hard links only go to regular
+//!   files inside the destination, never to symlinks.
+//!
+//! Both operations run on the worker pool and check for cancellation between
+//! entries.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.listArchive("artifact.tar.gz")
+//! // JS: // -> [{ path: "pkg/bin/tool", kind: "file", size: 123456, mode: 493 }, ...]
+//! // JS: await native.extractArchive("artifact.zip", "out", { include: ["bin/**"], stripComponents: 1 })
+//! ```
+
+use std::{
+	fs::{self, File},
+	io::{self, BufRead, BufReader, Read, Seek},
+	path::{Path, PathBuf},
+};
+
+use flate2::read::MultiGzDecoder;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{compress, glob::build_glob_pattern, task};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `listArchive`.
+#[napi(object)]
+pub struct ListArchiveOptions<'env> {
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// An entry of an archive.
+#[napi(object)]
+pub struct ArchiveEntry {
+	/// Path inside the archive, with forward slashes.
+	pub path:        String,
+	/// "file", "dir", "symlink", "hardlink", or "other".
+	pub kind:        String,
+	/// Uncompressed size in bytes.
+	pub size:        f64,
+	/// Unix permission bits, when recorded.
+	pub mode:        Option<u32>,
+	/// Link target for symlinks and hard links.
+	#[napi(js_name = "linkTarget")]
+	pub link_target: Option<String>,
+}
+
+/// Options for `extractArchive`.
+#[napi(object)]
+pub struct ExtractArchiveOptions<'env> {
+	/// Globs selecting entries to extract, matched against paths after
+	/// `stripComponents` (default: all). Patterns without a `/` match names
+	/// at any depth.
+	pub include:          Option<Vec<String>>,
+	/// Leading path components to drop from each entry (default: 0).
+	#[napi(js_name = "stripComponents")]
+	pub strip_components: Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:           Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:       Option<u32>,
+}
+
+/// Result of `extractArchive`.
+#[napi(object)]
+pub struct ExtractArchiveResult {
+	/// Files, symlinks, and hard links written.
+	pub files:   u32,
+	/// Bytes of file content written.
+	pub bytes:   f64,
+	/// Archive paths skipped because they would escape the destination.
+	pub skipped: Vec<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Reading
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+	File,
+	Dir,
+	Symlink,
+	HardLink,
+	Other,
+}
+
+impl Kind {
+	const fn name(self) -> &'static str {
+		match self {
+			Self::File => "file",
+			Self::Dir => "dir",
+			Self::Symlink => "symlink",
+			Self::HardLink => "hardlink",
+			Self::Other => "other",
+		}
+	}
+}
+
+struct Meta {
+	path: String,
+	kind: Kind,
+	size: u64,
+	mode: Option<u32>,
+	/// Link target; for zip symlinks it is the entry content instead.
+	link: Option<String>,
+}
+
+fn archive_error(path: &Path, err: impl std::fmt::Display) -> Error {
+	Error::from_reason(format!("Failed to read archive {}: {err}", path.display()))
+}
+
+fn lossy(bytes: &[u8]) -> String {
+	String::from_utf8_lossy(bytes).replace('\\', "/")
+}
+
+/// Call `visit` for every entry in order, with a reader over its content.
+fn for_each_entry(
+	path: &Path,
+	ct: &task::CancelToken,
+	mut visit: impl FnMut(Meta, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+	let file = File::open(path).map_err(|err| archive_error(path, err))?;
+	let mut reader = BufReader::new(file);
+	let head = reader.fill_buf().map_err(|err| archive_error(path, err))?;
+	if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+		return for_each_zip_entry(path, reader, ct, visit);
+	}
+	let decoded: Box<dyn Read> = match compress::detect_algorithm(head) {
+		Some(compress::Algorithm::Gzip) => Box::new(MultiGzDecoder::new(reader)),
+		Some(compress::Algorithm::Zstd) => {
+			Box::new(zstd::Decoder::with_buffer(reader).map_err(|err| archive_error(path, err))?)
+		},
+		None => Box::new(reader),
+	};
+	let mut archive = tar::Archive::new(decoded);
+	for entry in archive.entries().map_err(|err| archive_error(path, err))? {
+		ct.heartbeat()?;
+		let mut entry = entry.map_err(|err| archive_error(path, err))?;
+		let header = entry.header();
+		let entry_type = header.entry_type();
+		let kind = if entry_type.is_file() {
+			Kind::File
+		} else if entry_type.is_dir() {
+			Kind::Dir
+		} else if entry_type.is_symlink() {
+			Kind::Symlink
+		} else if entry_type.is_hard_link() {
+			Kind::HardLink
+		} else if entry_type.is_pax_global_extensions() {
+			continue;
+		} else {
+			Kind::Other
+		};
+		let meta = Meta {
+			path: lossy(&entry.path_bytes()),
+			kind,
+			size: entry.size(),
+			mode: header.mode().ok(),
+			link: entry.link_name_bytes().map(|link| lossy(&link)),
+		};
+		visit(meta, &mut entry)?;
+	}
+	Ok(())
+}
+
+fn for_each_zip_entry(
+	path: &Path,
+	reader: impl Read + Seek,
+	ct: &task::CancelToken,
+	mut visit: impl FnMut(Meta, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+	let mut archive = zip::ZipArchive::new(reader).map_err(|err| archive_error(path, err))?;
+	for index in 0..archive.len() {
+		ct.heartbeat()?;
+		let mut entry = archive.by_index(index).map_err(|err| archive_error(path, err))?;
+		let kind = if entry.is_symlink() {
+			Kind::Symlink
+		} else if entry.is_dir() {
+			Kind::Dir
+		} else {
+			Kind::File
+		};
+		let meta = Meta {
+			path: lossy(entry.name_raw()),
+			kind,
+			size: entry.size(),
+			mode: entry.unix_mode().map(|mode| mode & 0o7777),
+			link: None,
+		};
+		visit(meta, &mut entry)?;
+	}
+	Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Extraction
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn compile_include(patterns: &[String]) -> Result<GlobSet> {
+	let mut builder = GlobSetBuilder::new();
+	for pattern in patterns {
+		let glob = Glob::new(&build_glob_pattern(pattern))
+			.map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))?;
+		builder.add(glob);
+	}
+	builder
+		.build()
+		.map_err(|err| Error::from_reason(format!("Failed to build glob matcher: {err}")))
+}
+
+/// Split an archive path into plain components, or `None` if it is absolute
+/// or climbs with `..`.
+fn safe_components(path: &str) -> Option<Vec<&str>> {
+	if path.starts_with('/') || path.as_bytes().get(1) == Some(&b':') {
+		return None;
+	}
+	let mut parts = Vec::new();
+	for part in path.split('/') {
+		match part {
+			"" | "." => {},
+			".." => return None,
+			part => parts.push(part),
+		}
+	}
+	Some(parts)
+}
+
+/// Whether a symlink at `depth` components below the destination, pointing
+/// at `target`, resolves inside the destination.
+///
+/// `..` may only lead the target: once it descends, any component could be a
+/// symlink created by another entry (`x/y -> ..`), which makes a later `..`
+/// climb somewhere other than the text says. Leading `..` components climb
+/// through the link's own parents, which are real directories.
+fn link_stays_inside(depth: usize, target: &str) -> bool {
+	let target = target.replace('\\', "/");
+	if target.starts_with('/') || target.as_bytes().get(1) == Some(&b':') {
+		return false;
+	}
+	let mut depth = depth;
+	let mut descended = false;
+	for part in target.split('/') {
+		match part {
+			"" | "." => {},
+			".." if descended => return false,
+			".." => {
+				let Some(parent) = depth.checked_sub(1) else {
+					return false;
+				};
+				depth = parent;
+			},
+			_ => descended = true,
+		}
+	}
+	true
+}
+
+struct Extractor {
+	dest:    PathBuf,
+	include: Option<GlobSet>,
+	strip:   usize,
+	result:  ExtractArchiveResult,
+}
+
+impl Extractor {
+	/// Resolve where an entry goes, making sure no existing directory on the
+	/// way is a symlink. `None` means the entry is skipped.
+	fn target(&self, parts: &[&str]) -> Option<PathBuf> {
+		let mut path = self.dest.clone();
+		for (i, part) in parts.iter().enumerate() {
+			path.push(part);
+			let Ok(meta) = fs::symlink_metadata(&path) else {
+				break;
+			};
+			if meta.file_type().is_symlink() && i + 1 < parts.len() {
+				return None;
+			}
+		}
+		Some(self.dest.join(parts.iter().collect::<PathBuf>()))
+	}
+
+	fn skip(&mut self, path: String) {
+		self.result.skipped.push(path);
+	}
+
+	fn extract(&mut self, meta: Meta, reader: &mut dyn Read) -> Result<()> {
+		let Some(parts) = safe_components(&meta.path) else {
+			self.skip(meta.path);
+			return Ok(());
+		};
+		if parts.len() <= self.strip {
+			return Ok(());
+		}
+		let parts = &parts[self.strip..];
+		if let Some(include) = &self.include
+			&& !include.is_match(parts.join("/"))
+		{
+			return Ok(());
+		}
+		let Some(target) = self.target(parts) else {
+			self.skip(meta.path);
+			return Ok(());
+		};
+		let write_error = |err: io::Error| {
+			Error::from_reason(format!("Failed to extract {}: {err}", target.display()))
+		};
+
+		if meta.kind == Kind::Dir {
+			return fs::create_dir_all(&target).map_err(write_error);
+		}
+		if let Some(parent) = target.parent() {
+			fs::create_dir_all(parent).map_err(write_error)?;
+		}
+		// Never write through an existing symlink.
+		if fs::symlink_metadata(&target).is_ok_and(|existing| existing.file_type().is_symlink()) {
+			fs::remove_file(&target).map_err(write_error)?;
+		}
+		match meta.kind {
+			Kind::File => {
+				let mut file = File::create(&target).map_err(write_error)?;
+				let written = io::copy(reader, &mut file).map_err(write_error)?;
+				#[cfg(unix)]
+				if let Some(mode) = meta.mode {
+					use std::os::unix::fs::PermissionsExt;
+					let perms = fs::Permissions::from_mode(mode & 0o777);
+					fs::set_permissions(&target, perms).map_err(write_error)?;
+				}
+				self.result.bytes += written as f64;
+			},
+			Kind::Symlink => {
+				let link = if let Some(link) = meta.link {
+					link
+				} else {
+					let mut link = String::new();
+					reader.read_to_string(&mut link).map_err(write_error)?;
+					link
+				};
+				if !cfg!(unix) || !link_stays_inside(parts.len() - 1, &link) {
+					self.skip(meta.path);
+					return Ok(());
+				}
+				#[cfg(unix)]
+				std::os::unix::fs::symlink(&link, &target).map_err(write_error)?;
+			},
+			Kind::HardLink => {
+				let source = meta
+					.link
+					.as_deref()
+					.and_then(safe_components)
+					.filter(|source| source.len() > self.strip)
+					.and_then(|source| self.target(&source[self.strip..]));
+				// Check the entry itself, not what it points to: a hard link to a
+				// symlink would link whatever the symlink names.
+				let Some(source) = source.filter(|source| {
+					fs::symlink_metadata(source).is_ok_and(|meta| meta.file_type().is_file())
+				}) else {
+					self.skip(meta.path);
+					return Ok(());
+				};
+				fs::hard_link(&source, &target).map_err(write_error)?;
+			},
+			Kind::Dir | Kind::Other => return Ok(()),
+		}
+		self.result.files += 1;
+		Ok(())
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// List the entries of a zip or tar (optionally gzip/zstd) archive.
+///
+/// # Errors
+/// Returns an error if the file cannot be read, is not a supported archive,
+/// or the operation is cancelled.
+#[napi(js_name = "listArchive")]
+pub fn list_archive(
+	path: String,
+	options: Option<ListArchiveOptions<'_>>,
+) -> task::Async<Vec<ArchiveEntry>> {
+	let (signal, timeout_ms) = match options {
+		Some(opts) => (opts.signal, opts.timeout_ms),
+		None => (None, None),
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("archive.list", ct, move |ct| {
+		let mut entries = Vec::new();
+		for_each_entry(Path::new(&path), &ct, |meta, _| {
+			entries.push(ArchiveEntry {
+				path:        meta.path,
+				kind:        meta.kind.name().to_string(),
+				size:        meta.size as f64,
+				mode:        meta.mode,
+				link_target: meta.link,
+			});
+			Ok(())
+		})?;
+		Ok(entries)
+	})
+}
+
+/// Extract a zip or tar (optionally gzip/zstd) archive into `dest`.
+///
+/// # Arguments
+/// - `path`: Archive file.
+/// - `dest`: Destination directory, created if missing.
+/// - `options`: Entry filter, leading components to strip, cancellation.
+///
+/// # Returns
+/// Counts of written entries and bytes, and entries skipped as unsafe.
+///
+/// # Errors
+/// Returns an error for an invalid glob, an unreadable archive, a failed
+/// write, or cancellation. Entries written before the error remain.
+#[napi(js_name = "extractArchive")]
+pub fn extract_archive(
+	path: String,
+	dest: String,
+	options: Option<ExtractArchiveOptions<'_>>,
+) -> Result<task::Async<ExtractArchiveResult>> {
+	let (include, strip, signal, timeout_ms) = match options {
+		Some(opts) => (opts.include, opts.strip_components, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let include = include.as_deref().map(compile_include).transpose()?;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("archive.extract", ct, move |ct| {
+		let dest = PathBuf::from(dest);
+		fs::create_dir_all(&dest)
+			.map_err(|err| Error::from_reason(format!("Failed to create {}: {err}", dest.display())))?;
+		// Resolve symlinks in the destination itself once, so the checks
+		// below only see links created by (or planted in) the extraction.
+		let dest = dest.canonicalize().unwrap_or(dest);
+		let mut extractor = Extractor {
+			dest,
+			include,
+			strip: strip.unwrap_or(0) as usize,
+			result: ExtractArchiveResult { files: 0, bytes: 0.0, skipped: Vec::new() },
+		};
+		for_each_entry(Path::new(&path), &ct, |meta, reader| extractor.extract(meta, reader))?;
+		Ok(extractor.result)
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::ScratchDir;
+
+	/// A scratch directory with an empty `dest` to extract into.
+	fn scratch(name: &str) -> ScratchDir {
+		let root = ScratchDir::new(&format!("archive-{name}"));
+		fs::create_dir(root.join("dest")).unwrap();
+		root
+	}
+
+	fn extractor(dest: PathBuf) -> Extractor {
+		Extractor {
+			dest,
+			include: None,
+			strip: 0,
+			result: ExtractArchiveResult { files: 0, bytes: 0.0, skipped: Vec::new() },
+		}
+	}
+
+	fn add(extractor: &mut Extractor, path: &str, kind: Kind, link: Option<&str>, data: &[u8]) {
+		let meta = Meta {
+			path: path.to_string(),
+			kind,
+			size: data.len() as u64,
+			mode: None,
+			link: link.map(ToString::to_string),
+		};
+		extractor.extract(meta, &mut &data[..]).unwrap();
+	}
+
+	#[test]
+	fn test_safe_components() {
+		assert_eq!(safe_components("a/./b/"), Some(vec!["a", "b"]));
+		assert_eq!(safe_components("a/../b"), None);
+		assert_eq!(safe_components("/etc/passwd"), None);
+		assert_eq!(safe_components("C:/Windows"), None);
+	}
+
+	#[test]
+	fn test_link_stays_inside() {
+		assert!(link_stays_inside(1, ".."));
+		assert!(link_stays_inside(2, "../../a/b"));
+		assert!(link_stays_inside(0, "./a"));
+		assert!(!link_stays_inside(0, ".."));
+		assert!(!link_stays_inside(1, "../../a"));
+		assert!(!link_stays_inside(0, "/etc"));
+		assert!(!link_stays_inside(0, "C:\\Windows"));
+		// Climbing after descending could go through another extracted link.
+		assert!(!link_stays_inside(0, "x/y/../.."));
+		assert!(!link_stays_inside(3, "a/../b"));
+	}
+
+	#[test]
+	fn test_extract_skips_escaping_entries() {
+		let root = scratch("escape");
+		let mut extractor = extractor(root.join("dest"));
+		add(&mut extractor, "../evil", Kind::File, None, b"x");
+		add(&mut extractor, "/abs", Kind::File, None, b"x");
+		add(&mut extractor, "ok/file.txt", Kind::File, None, b"hello");
+		add(&mut extractor, "ok/link", Kind::HardLink, Some("ok/file.txt"), b"");
+		add(&mut extractor, "ok/up", Kind::HardLink, Some("../outside"), b"");
+
+		assert!(!root.join("evil").exists());
+		assert_eq!(fs::read(root.join("dest/ok/link")).unwrap(), b"hello");
+		assert_eq!(extractor.result.files, 2);
+		assert_eq!(extractor.result.bytes, 5.0);
+		assert_eq!(extractor.result.skipped, ["../evil", "/abs", "ok/up"]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_extract_symlink_traversal() {
+		let root = scratch("symlink");
+		fs::write(root.join("secret"), "outside").unwrap();
+		let mut extractor = extractor(root.join("dest"));
+		// Each link alone looks contained; chained they would reach `root`.
+		add(&mut extractor, "x/y", Kind::Symlink, Some(".."), b"");
+		add(&mut extractor, "z", Kind::Symlink, Some("x/y/../.."), b"");
+		add(&mut extractor, "abs", Kind::Symlink, Some("/etc"), b"");
+		// Nothing is written below an extracted symlink.
+		add(&mut extractor, "x/y/dropped", Kind::File, None, b"x");
+		// Hard links never target a symlink.
+		add(&mut extractor, "up", Kind::Symlink, Some("../secret"), b"");
+		add(&mut extractor, "hard", Kind::HardLink, Some("x/y"), b"");
+
+		let dest = root.join("dest");
+		assert_eq!(fs::read_link(dest.join("x/y")).unwrap(), Path::new(".."));
+		assert!(fs::symlink_metadata(dest.join("z")).is_err());
+		assert!(fs::symlink_metadata(dest.join("abs")).is_err());
+		assert!(fs::symlink_metadata(dest.join("up")).is_err());
+		assert!(fs::symlink_metadata(dest.join("hard")).is_err());
+		assert!(!dest.join("dropped").exists());
+		assert_eq!(extractor.result.skipped, ["z", "abs", "x/y/dropped", "up", "hard"]);
+	}
+}
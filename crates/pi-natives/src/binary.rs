@@ -0,0 +1,231 @@
+//! Binary content detection and hexdump rendering.
+//!
+//! # Overview
+//! [`is_binary`] inspects a leading sample for NUL bytes and control-character
+//! density, treating UTF-16 text as text. `hexdump` renders byte ranges as
+//! offset / hex / ASCII columns, optionally colored by byte class so previews
+//! of binary files stay readable in the terminal.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.isBinary("image.png") // -> true
+//! // JS: native.hexdump(buffer, { length: 64, width: 8, color: false })
+//! // -> ["00000000  89 50 4e 47 0d 0a 1a 0a  │.PNG....│", ...]
+//! ```
+
+use std::{fmt::Write as _, fs::File, io::Read, path::Path};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::encoding::{self, Encoding};
+
+/// Bytes inspected by binary detection.
+const SAMPLE_SIZE: usize = 8 * 1024;
+
+/// Default bytes per hexdump line.
+const DEFAULT_WIDTH: usize = 16;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `hexdump`.
+#[napi(object)]
+pub struct HexdumpOptions {
+	/// First byte to render (default: 0).
+	pub offset: Option<u32>,
+	/// Number of bytes to render (default: rest of the buffer).
+	pub length: Option<u32>,
+	/// Bytes per line (default: 16).
+	pub width:  Option<u32>,
+	/// Color bytes by class with ANSI escapes (default: true).
+	pub color:  Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Detection
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Heuristically decide whether `sample` is binary content.
+///
+/// NUL bytes mark content as binary unless it looks like UTF-16 text; text
+/// that is not UTF-8 is binary when more than 10% of it is control bytes.
+pub fn is_binary(sample: &[u8]) -> bool {
+	let sample = &sample[..sample.len().min(SAMPLE_SIZE)];
+	let (encoding, _) = encoding::detect(sample);
+	match encoding {
+		Encoding::Utf16Le | Encoding::Utf16Be => false,
+		_ if sample.contains(&0) => true,
+		Encoding::Utf8 => false,
+		Encoding::Latin1 => {
+			let controls = sample
+				.iter()
+				.filter(|&&b| b < 0x20 && !matches!(b, b'\t' | b'\n' | b'\r' | 0x0c | 0x1b | 0x08))
+				.count();
+			controls * 10 > sample.len()
+		},
+	}
+}
+
+/// Read the leading bytes of `path` that [`is_binary`] inspects.
+pub fn read_sample(path: &Path) -> Result<Vec<u8>> {
+	let file = File::open(path)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", path.display())))?;
+	let mut sample = Vec::with_capacity(SAMPLE_SIZE);
+	file
+		.take(SAMPLE_SIZE as u64)
+		.read_to_end(&mut sample)
+		.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+	Ok(sample)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Hexdump
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ByteClass {
+	Null,
+	Printable,
+	Whitespace,
+	Control,
+	NonAscii,
+}
+
+impl ByteClass {
+	const fn of(byte: u8) -> Self {
+		match byte {
+			0 => Self::Null,
+			b'\t' | b'\n' | b'\r' | b' ' | 0x0b | 0x0c => Self::Whitespace,
+			0x21..=0x7e => Self::Printable,
+			0x80.. => Self::NonAscii,
+			_ => Self::Control,
+		}
+	}
+
+	const fn color(self) -> &'static str {
+		match self {
+			Self::Null => "\x1b[90m",
+			Self::Printable => "\x1b[36m",
+			Self::Whitespace => "\x1b[32m",
+			Self::Control => "\x1b[35m",
+			Self::NonAscii => "\x1b[33m",
+		}
+	}
+
+	const fn glyph(self, byte: u8) -> char {
+		match self {
+			Self::Printable => byte as char,
+			Self::Whitespace if byte == b' ' => ' ',
+			_ => '.',
+		}
+	}
+}
+
+/// Tracks the active color so escapes are only written on class changes.
+struct Painter {
+	enabled: bool,
+	current: Option<ByteClass>,
+}
+
+impl Painter {
+	fn paint(&mut self, out: &mut String, class: ByteClass) {
+		if self.enabled && self.current != Some(class) {
+			out.push_str(class.color());
+			self.current = Some(class);
+		}
+	}
+
+	fn reset(&mut self, out: &mut String) {
+		if self.enabled && self.current.take().is_some() {
+			out.push_str("\x1b[0m");
+		}
+	}
+}
+
+fn hexdump_line(out: &mut String, offset: usize, row: &[u8], width: usize, color: bool) {
+	let mut painter = Painter { enabled: color, current: None };
+	if color {
+		out.push_str("\x1b[2m");
+	}
+	let _ = write!(out, "{offset:08x}");
+	if color {
+		out.push_str("\x1b[0m");
+	}
+	out.push_str("  ");
+	for col in 0..width {
+		if col > 0 {
+			out.push(' ');
+			if col % 8 == 0 {
+				out.push(' ');
+			}
+		}
+		if let Some(&byte) = row.get(col) {
+			painter.paint(out, ByteClass::of(byte));
+			let _ = write!(out, "{byte:02x}");
+		} else {
+			painter.reset(out);
+			out.push_str("  ");
+		}
+	}
+	painter.reset(out);
+	out.push_str("  │");
+	for &byte in row {
+		let class = ByteClass::of(byte);
+		painter.paint(out, class);
+		out.push(class.glyph(byte));
+	}
+	painter.reset(out);
+	for _ in row.len()..width {
+		out.push(' ');
+	}
+	out.push('│');
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Check whether a file or buffer holds binary content.
+///
+/// # Arguments
+/// - `input`: File path, or the content itself.
+///
+/// # Errors
+/// Returns an error if a path cannot be read.
+#[napi(js_name = "isBinary")]
+pub fn is_binary_content(input: Either<String, Uint8Array>) -> Result<bool> {
+	match input {
+		Either::A(path) => Ok(is_binary(&read_sample(Path::new(&path))?)),
+		Either::B(buffer) => Ok(is_binary(buffer.as_ref())),
+	}
+}
+
+/// Render a byte range as hexdump lines.
+///
+/// # Returns
+/// One line per `width` bytes with the absolute offset, hex bytes, and an
+/// ASCII column.
+#[napi]
+pub fn hexdump(buffer: Uint8Array, options: Option<HexdumpOptions>) -> Vec<String> {
+	let bytes = buffer.as_ref();
+	let (offset, length, width, color) = match options {
+		Some(opts) => (opts.offset, opts.length, opts.width, opts.color),
+		None => (None, None, None, None),
+	};
+	let start = offset.map_or(0, |offset| offset as usize).min(bytes.len());
+	let end = length.map_or(bytes.len(), |len| start.saturating_add(len as usize)).min(bytes.len());
+	let width = width.map_or(DEFAULT_WIDTH, |width| width as usize).clamp(1, 256);
+	let color = color.unwrap_or(true);
+
+	bytes[start..end]
+		.chunks(width)
+		.enumerate()
+		.map(|(i, row)| {
+			let mut line = String::with_capacity(width * 4 + 16);
+			hexdump_line(&mut line, start + i * width, row, width, color);
+			line
+		})
+		.collect()
+}
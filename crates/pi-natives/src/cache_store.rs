@@ -0,0 +1,668 @@
+//! Content-addressed disk cache.
+//!
+//! # Overview
+//! `CacheStore` maps string keys to byte blobs kept under a cache directory,
+//! so expensive results (highlighted renders, tokenization, index shards)
+//! survive restarts. Blobs are stored once per content under their BLAKE3
+//! digest (`blobs/ab/cdef…`), so keys with identical values share storage.
+//!
+//! The key index lives in memory and is persisted as an append-only journal
+//! (`journal`), replayed on open and compacted by `evict()` or when it grows
+//! well past the live entry count. Entries may carry a TTL; expired entries
+//! read as misses. When the referenced blobs exceed `maxBytes`, the least
+//! recently used entries are dropped until the store fits again; a value
+//! larger than `maxBytes` on its own is rejected.
+//!
+//! All disk work runs on the worker pool. A directory should be owned by one
+//! store at a time.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const cache = await native.CacheStore.open(".cache/render", { maxBytes: 64 << 20 })
+//! // JS: await cache.put("file.ts:v3", bytes, { ttl: 3_600_000 })
+//! // JS: await cache.get("file.ts:v3") // -> Uint8Array | null
+//! ```
+
+use std::{
+	collections::HashMap,
+	fs::{self, File, OpenOptions},
+	io::{BufRead, BufReader, ErrorKind, Write},
+	path::{Path, PathBuf},
+	sync::{
+		Arc,
+		atomic::{AtomicU32, AtomicU64, Ordering},
+	},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::Mutex;
+
+use crate::{hash, task};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `CacheStore.open`.
+#[napi(object)]
+pub struct CacheStoreOptions {
+	/// Size bound for stored blobs in bytes (default: 256 MiB).
+	#[napi(js_name = "maxBytes")]
+	pub max_bytes: Option<f64>,
+}
+
+/// Options for `CacheStore.put`.
+#[napi(object)]
+pub struct CachePutOptions {
+	/// Time to live in milliseconds (default: no expiry).
+	pub ttl: Option<f64>,
+}
+
+/// Result of `CacheStore.evict`.
+#[napi(object)]
+pub struct CacheEvictResult {
+	/// Entries removed (expired or over the size bound).
+	pub removed:     u32,
+	/// Bytes of blob storage freed.
+	#[napi(js_name = "freedBytes")]
+	pub freed_bytes: f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Store
+// ═══════════════════════════════════════════════════════════════════════════
+
+const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+const JOURNAL: &str = "journal";
+const BLOBS: &str = "blobs";
+
+fn now_ms() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> Error {
+	Error::from_reason(format!("Failed to {action} {}: {err}", path.display()))
+}
+
+struct Entry {
+	blob:     String,
+	size:     u64,
+	/// Expiry time in ms since the epoch.
+	expires:  Option<u64>,
+	/// Last read or write in ms since the epoch.
+	accessed: u64,
+}
+
+impl Entry {
+	fn expired(&self, now: u64) -> bool {
+		self.expires.is_some_and(|expires| expires <= now)
+	}
+}
+
+struct Blob {
+	refs: usize,
+	size: u64,
+}
+
+/// Journal records, one per line:
+/// - `P <key> <blob> <size> <expires|-> <accessed>`: entry written.
+/// - `A <key> <accessed>`: entry read.
+/// - `D <key>`: entry removed.
+///
+/// Keys are stored as BLAKE3 digests, so records never need escaping. Keys
+/// and blobs that are not digests are rejected, since blobs name files.
+enum Record<'a> {
+	Put(&'a str, Entry),
+	Access(&'a str, u64),
+	Delete(&'a str),
+}
+
+/// `field` if it is a lowercase hex BLAKE3 digest.
+fn digest(field: Option<&str>) -> Option<&str> {
+	field.filter(|field| {
+		field.len() == 64
+			&& field
+				.bytes()
+				.all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+	})
+}
+
+fn parse_record(line: &str) -> Option<Record<'_>> {
+	let mut fields = line.split(' ');
+	let record = match fields.next()? {
+		"P" => {
+			let key = digest(fields.next())?;
+			let blob = digest(fields.next())?.to_string();
+			let size = fields.next()?.parse().ok()?;
+			let expires = match fields.next()? {
+				"-" => None,
+				expires => Some(expires.parse().ok()?),
+			};
+			let accessed = fields.next()?.parse().ok()?;
+			Record::Put(key, Entry { blob, size, expires, accessed })
+		},
+		"A" => Record::Access(digest(fields.next())?, fields.next()?.parse().ok()?),
+		"D" => Record::Delete(digest(fields.next())?),
+		_ => return None,
+	};
+	fields.next().is_none().then_some(record)
+}
+
+fn put_record(key: &str, entry: &Entry) -> String {
+	let expires = entry.expires.map_or_else(|| "-".to_string(), |expires| expires.to_string());
+	format!("P {key} {} {} {expires} {}\n", entry.blob, entry.size, entry.accessed)
+}
+
+/// Entry count and size, readable without waiting for the store's lock.
+#[derive(Default)]
+struct Stats {
+	entries: AtomicU32,
+	bytes:   AtomicU64,
+}
+
+struct Store {
+	dir:           PathBuf,
+	max_bytes:     u64,
+	entries:       HashMap<String, Entry>,
+	blobs:         HashMap<String, Blob>,
+	/// Total size of referenced blobs.
+	bytes:         u64,
+	journal:       File,
+	journal_lines: usize,
+	stats:         Arc<Stats>,
+}
+
+impl Store {
+	fn open(dir: PathBuf, max_bytes: u64) -> Result<Self> {
+		fs::create_dir_all(dir.join(BLOBS)).map_err(|err| io_error("create", &dir, err))?;
+		let journal_path = dir.join(JOURNAL);
+		let mut entries = HashMap::new();
+		let mut journal_lines = 0;
+		match File::open(&journal_path) {
+			Ok(file) => {
+				for line in BufReader::new(file).lines() {
+					let line = line.map_err(|err| io_error("read", &journal_path, err))?;
+					journal_lines += 1;
+					// A torn last line from a crash is skipped.
+					match parse_record(&line) {
+						Some(Record::Put(key, entry)) => {
+							entries.insert(key.to_string(), entry);
+						},
+						Some(Record::Access(key, accessed)) => {
+							if let Some(entry) = entries.get_mut(key) {
+								entry.accessed = accessed;
+							}
+						},
+						Some(Record::Delete(key)) => {
+							entries.remove(key);
+						},
+						None => {},
+					}
+				}
+			},
+			Err(err) if err.kind() == ErrorKind::NotFound => {},
+			Err(err) => return Err(io_error("open", &journal_path, err)),
+		}
+
+		let mut blobs: HashMap<String, Blob> = HashMap::new();
+		let mut bytes = 0;
+		for entry in entries.values() {
+			let blob = blobs.entry(entry.blob.clone()).or_insert_with(|| {
+				bytes += entry.size;
+				Blob { refs: 0, size: entry.size }
+			});
+			blob.refs += 1;
+		}
+		let journal = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&journal_path)
+			.map_err(|err| io_error("open", &journal_path, err))?;
+		let mut store = Self {
+			dir,
+			max_bytes,
+			entries,
+			blobs,
+			bytes,
+			journal,
+			journal_lines,
+			stats: Arc::default(),
+		};
+		store.publish();
+		if store.journal_lines > store.entries.len() * 2 + 64 {
+			store.compact()?;
+		}
+		Ok(store)
+	}
+
+	/// Mirror the entry count and size into `stats`.
+	fn publish(&self) {
+		self
+			.stats
+			.entries
+			.store(self.entries.len().min(u32::MAX as usize) as u32, Ordering::Relaxed);
+		self.stats.bytes.store(self.bytes, Ordering::Relaxed);
+	}
+
+	fn blob_path(&self, blob: &str) -> PathBuf {
+		self.dir.join(BLOBS).join(&blob[..2]).join(&blob[2..])
+	}
+
+	fn append(&mut self, record: &str) -> Result<()> {
+		self.journal_lines += 1;
+		self
+			.journal
+			.write_all(record.as_bytes())
+			.map_err(|err| io_error("write", &self.dir.join(JOURNAL), err))
+	}
+
+	/// Drop one reference to `blob`, deleting the file when none remain.
+	/// Returns the bytes freed.
+	fn release(&mut self, blob: &str) -> u64 {
+		let Some(entry) = self.blobs.get_mut(blob) else {
+			return 0;
+		};
+		entry.refs -= 1;
+		if entry.refs > 0 {
+			return 0;
+		}
+		let size = entry.size;
+		self.blobs.remove(blob);
+		self.bytes -= size;
+		let _ = fs::remove_file(self.blob_path(blob));
+		size
+	}
+
+	/// Remove an entry without journaling it. Returns the bytes freed.
+	fn forget(&mut self, key: &str) -> Option<u64> {
+		let entry = self.entries.remove(key)?;
+		let freed = self.release(&entry.blob);
+		self.publish();
+		Some(freed)
+	}
+
+	fn remove(&mut self, key: &str) -> Result<Option<u64>> {
+		let freed = self.forget(key);
+		if freed.is_some() {
+			self.append(&format!("D {key}\n"))?;
+		}
+		Ok(freed)
+	}
+
+	fn put(&mut self, key: &str, data: &[u8], ttl: Option<u64>) -> Result<String> {
+		if data.len() as u64 > self.max_bytes {
+			return Err(Error::from_reason(format!(
+				"Value of {} bytes exceeds maxBytes ({})",
+				data.len(),
+				self.max_bytes
+			)));
+		}
+		let blob = hash::hash_bytes(data, hash::Algorithm::Blake3);
+		let path = self.blob_path(&blob);
+		if !self.blobs.contains_key(&blob) || !path.exists() {
+			let parent = path.parent().expect("blob path has a parent");
+			fs::create_dir_all(parent).map_err(|err| io_error("create", parent, err))?;
+			let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+			fs::write(&temp, data)
+				.and_then(|()| fs::rename(&temp, &path))
+				.map_err(|err| {
+					let _ = fs::remove_file(&temp);
+					io_error("write", &path, err)
+				})?;
+		}
+
+		let now = now_ms();
+		let entry = Entry {
+			blob:     blob.clone(),
+			size:     data.len() as u64,
+			expires:  ttl.map(|ttl| now.saturating_add(ttl)),
+			accessed: now,
+		};
+		// Reference the new blob before releasing the old one, so rewriting a
+		// key with the same content keeps the file.
+		let shared = self
+			.blobs
+			.entry(blob.clone())
+			.or_insert(Blob { refs: 0, size: entry.size });
+		shared.refs += 1;
+		if shared.refs == 1 {
+			self.bytes += entry.size;
+		}
+		self.forget(key);
+		self.append(&put_record(key, &entry))?;
+		self.entries.insert(key.to_string(), entry);
+		self.publish();
+		if self.bytes > self.max_bytes {
+			self.shrink(Some(key))?;
+		}
+		Ok(blob)
+	}
+
+	fn get(&mut self, key: &str) -> Result<Option<Vec<u8>>> {
+		let now = now_ms();
+		let Some(entry) = self.entries.get(key) else {
+			return Ok(None);
+		};
+		if entry.expired(now) {
+			self.remove(key)?;
+			return Ok(None);
+		}
+		let path = self.blob_path(&entry.blob);
+		let data = match fs::read(&path) {
+			Ok(data) => data,
+			// Deleted behind our back: treat as a miss.
+			Err(err) if err.kind() == ErrorKind::NotFound => {
+				self.remove(key)?;
+				return Ok(None);
+			},
+			Err(err) => return Err(io_error("read", &path, err)),
+		};
+		if let Some(entry) = self.entries.get_mut(key) {
+			entry.accessed = now;
+		}
+		self.append(&format!("A {key} {now}\n"))?;
+		Ok(Some(data))
+	}
+
+	/// Drop least recently used entries other than `keep` until the blobs fit
+	/// `max_bytes`. Returns the entries removed and bytes freed.
+	fn shrink(&mut self, keep: Option<&str>) -> Result<(u32, u64)> {
+		let mut removed = 0;
+		let mut freed = 0;
+		if self.bytes <= self.max_bytes {
+			return Ok((removed, freed));
+		}
+		let mut by_age: Vec<(u64, String)> = self
+			.entries
+			.iter()
+			.filter(|(key, _)| keep != Some(key.as_str()))
+			.map(|(key, entry)| (entry.accessed, key.clone()))
+			.collect();
+		by_age.sort_unstable();
+		for (_, key) in by_age {
+			if self.bytes <= self.max_bytes {
+				break;
+			}
+			freed += self.remove(&key)?.unwrap_or(0);
+			removed += 1;
+		}
+		Ok((removed, freed))
+	}
+
+	fn evict(&mut self) -> Result<CacheEvictResult> {
+		let now = now_ms();
+		let expired: Vec<String> = self
+			.entries
+			.iter()
+			.filter(|(_, entry)| entry.expired(now))
+			.map(|(key, _)| key.clone())
+			.collect();
+		let mut removed = expired.len() as u32;
+		let mut freed = 0;
+		for key in &expired {
+			freed += self.forget(key).unwrap_or(0);
+		}
+		let (shrunk, shrunk_bytes) = self.shrink(None)?;
+		removed += shrunk;
+		freed += shrunk_bytes;
+		freed += self.sweep();
+		self.compact()?;
+		Ok(CacheEvictResult { removed, freed_bytes: freed as f64 })
+	}
+
+	/// Delete blob files no entry references (left behind by a crash between
+	/// writing a blob and journaling its entry). Returns the bytes freed.
+	fn sweep(&self) -> u64 {
+		let mut freed = 0;
+		let Ok(shards) = fs::read_dir(self.dir.join(BLOBS)) else {
+			return 0;
+		};
+		for shard in shards.flatten() {
+			let prefix = shard.file_name().to_string_lossy().into_owned();
+			let Ok(files) = fs::read_dir(shard.path()) else {
+				continue;
+			};
+			for file in files.flatten() {
+				let name = format!("{prefix}{}", file.file_name().to_string_lossy());
+				if self.blobs.contains_key(&name) {
+					continue;
+				}
+				let size = file.metadata().map_or(0, |meta| meta.len());
+				if fs::remove_file(file.path()).is_ok() {
+					freed += size;
+				}
+			}
+		}
+		freed
+	}
+
+	/// Rewrite the journal with one record per live entry.
+	fn compact(&mut self) -> Result<()> {
+		let path = self.dir.join(JOURNAL);
+		let temp = self.dir.join(format!("{JOURNAL}.{}.tmp", std::process::id()));
+		let mut records = String::new();
+		for (key, entry) in &self.entries {
+			records.push_str(&put_record(key, entry));
+		}
+		fs::write(&temp, records)
+			.and_then(|()| fs::rename(&temp, &path))
+			.map_err(|err| {
+				let _ = fs::remove_file(&temp);
+				io_error("write", &path, err)
+			})?;
+		self.journal = OpenOptions::new()
+			.append(true)
+			.open(&path)
+			.map_err(|err| io_error("open", &path, err))?;
+		self.journal_lines = self.entries.len();
+		Ok(())
+	}
+}
+
+fn key_digest(key: &str) -> String {
+	hash::hash_bytes(key.as_bytes(), hash::Algorithm::Blake3)
+}
+
+fn parse_non_negative(value: Option<f64>, name: &str) -> Result<Option<u64>> {
+	match value {
+		Some(ms) if !ms.is_finite() || ms < 0.0 => {
+			Err(Error::from_reason(format!("{name} must be a non-negative number, got {ms}")))
+		},
+		Some(ms) => Ok(Some(ms as u64)),
+		None => Ok(None),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Content-addressed blob cache persisted in a directory.
+#[napi]
+pub struct CacheStore {
+	store: Arc<Mutex<Store>>,
+	stats: Arc<Stats>,
+}
+
+#[napi]
+impl CacheStore {
+	/// Open (or create) a cache in `dir`, replaying its journal.
+	///
+	/// # Errors
+	/// Returns an error if the directory or journal cannot be created or
+	/// read.
+	#[napi(js_name = "open")]
+	#[allow(clippy::use_self, reason = "napi expands the return type outside the impl")]
+	pub fn open(dir: String, options: Option<CacheStoreOptions>) -> Result<task::Async<CacheStore>> {
+		let max_bytes = parse_non_negative(options.and_then(|opts| opts.max_bytes), "maxBytes")?;
+		let max_bytes = max_bytes.unwrap_or(DEFAULT_MAX_BYTES);
+		Ok(task::blocking("cache_store.open", (), move |_| {
+			let store = Store::open(PathBuf::from(dir), max_bytes)?;
+			let stats = Arc::clone(&store.stats);
+			Ok(Self { store: Arc::new(Mutex::new(store)), stats })
+		}))
+	}
+
+	/// Store `data` under `key`, replacing any previous value.
+	///
+	/// # Returns
+	/// The BLAKE3 digest the blob is stored under.
+	///
+	/// # Errors
+	/// Returns an error for an invalid TTL, a value larger than `maxBytes`, or
+	/// if the blob cannot be written.
+	#[napi]
+	pub fn put(
+		&self,
+		key: String,
+		data: Either<String, Uint8Array>,
+		options: Option<CachePutOptions>,
+	) -> Result<task::Async<String>> {
+		let ttl = parse_non_negative(options.and_then(|opts| opts.ttl), "ttl")?;
+		let bytes = match data {
+			Either::A(text) => text.into_bytes(),
+			Either::B(buffer) => buffer.as_ref().to_vec(),
+		};
+		let store = Arc::clone(&self.store);
+		Ok(task::blocking("cache_store.put", (), move |_| {
+			store.lock().put(&key_digest(&key), &bytes, ttl)
+		}))
+	}
+
+	/// Read the value stored under `key`.
+	///
+	/// # Returns
+	/// The bytes, or `null` when the key is missing or expired.
+	#[napi]
+	pub fn get(&self, key: String) -> task::Async<Option<Uint8Array>> {
+		let store = Arc::clone(&self.store);
+		task::blocking("cache_store.get", (), move |_| {
+			Ok(store.lock().get(&key_digest(&key))?.map(Uint8Array::from))
+		})
+	}
+
+	/// Remove the value stored under `key`.
+	///
+	/// # Returns
+	/// Whether the key was present.
+	#[napi]
+	pub fn delete(&self, key: String) -> task::Async<bool> {
+		let store = Arc::clone(&self.store);
+		task::blocking("cache_store.delete", (), move |_| {
+			Ok(store.lock().remove(&key_digest(&key))?.is_some())
+		})
+	}
+
+	/// Drop expired entries and least recently used entries over `maxBytes`,
+	/// delete orphaned blobs, and compact the journal.
+	#[napi]
+	pub fn evict(&self) -> task::Async<CacheEvictResult> {
+		let store = Arc::clone(&self.store);
+		task::blocking("cache_store.evict", (), move |_| store.lock().evict())
+	}
+
+	/// Number of entries, including expired ones not yet evicted.
+	#[napi(getter)]
+	pub fn count(&self) -> u32 {
+		self.stats.entries.load(Ordering::Relaxed)
+	}
+
+	/// Bytes of blob storage in use.
+	#[napi(getter)]
+	pub fn size(&self) -> f64 {
+		self.stats.bytes.load(Ordering::Relaxed) as f64
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::ScratchDir;
+
+	fn blob_files(dir: &Path) -> usize {
+		fs::read_dir(dir.join(BLOBS))
+			.unwrap()
+			.map(|shard| fs::read_dir(shard.unwrap().path()).unwrap().count())
+			.sum()
+	}
+
+	#[test]
+	fn test_put_shares_blobs_and_survives_reopen() {
+		let dir = ScratchDir::new("cache-store-reopen");
+		let (a, b) = (key_digest("a"), key_digest("b"));
+		let mut store = Store::open(dir.to_path_buf(), 1 << 20).unwrap();
+		store.put(&a, b"same", None).unwrap();
+		store.put(&b, b"same", None).unwrap();
+		assert_eq!((store.entries.len(), store.bytes), (2, 4));
+		assert_eq!(blob_files(&dir), 1);
+
+		store.remove(&a).unwrap();
+		assert_eq!(blob_files(&dir), 1);
+		drop(store);
+
+		let mut store = Store::open(dir.to_path_buf(), 1 << 20).unwrap();
+		assert_eq!(store.get(&a).unwrap(), None);
+		assert_eq!(store.get(&b).unwrap().as_deref(), Some(&b"same"[..]));
+		assert_eq!(store.stats.entries.load(Ordering::Relaxed), 1);
+	}
+
+	#[test]
+	fn test_journal_rejects_records_that_are_not_digests() {
+		let dir = ScratchDir::new("cache-store-journal");
+		let key = key_digest("k");
+		fs::write(
+			dir.join(JOURNAL),
+			format!("P {key} ../../../etc/passwd 10 - 0\nP ../x {key} 10 - 0\nP {key} {key} 10\n"),
+		)
+		.unwrap();
+		let store = Store::open(dir.to_path_buf(), 1 << 20).unwrap();
+		assert!(store.entries.is_empty());
+		assert_eq!(store.bytes, 0);
+	}
+
+	#[test]
+	fn test_put_rejects_oversized_values_and_keeps_the_new_entry() {
+		let dir = ScratchDir::new("cache-store-bounds");
+		let (a, b) = (key_digest("a"), key_digest("b"));
+		let mut store = Store::open(dir.to_path_buf(), 10).unwrap();
+		store.put(&a, b"aaaaaa", None).unwrap();
+		assert!(store.put(&a, &[0; 11], None).is_err());
+		assert_eq!(store.get(&a).unwrap().as_deref(), Some(&b"aaaaaa"[..]));
+
+		// `b` is the most recent write, so `a` is evicted to make room even
+		// though it was just read.
+		store.put(&b, b"bbbbbb", None).unwrap();
+		assert_eq!(store.get(&a).unwrap(), None);
+		assert_eq!(store.get(&b).unwrap().as_deref(), Some(&b"bbbbbb"[..]));
+		assert_eq!(store.stats.bytes.load(Ordering::Relaxed), 6);
+		assert_eq!(blob_files(&dir), 1);
+	}
+
+	#[test]
+	fn test_evict_drops_expired_entries_and_orphan_blobs() {
+		let dir = ScratchDir::new("cache-store-evict");
+		let (a, b) = (key_digest("a"), key_digest("b"));
+		let mut store = Store::open(dir.to_path_buf(), 1 << 20).unwrap();
+		store.put(&a, b"expiring", Some(0)).unwrap();
+		store.put(&b, b"kept", None).unwrap();
+		let orphan = key_digest("orphan");
+		fs::create_dir_all(dir.join(BLOBS).join(&orphan[..2])).unwrap();
+		fs::write(store.blob_path(&orphan), b"orphan").unwrap();
+
+		let result = store.evict().unwrap();
+		assert_eq!(result.removed, 1);
+		assert_eq!(result.freed_bytes, 14.0);
+		assert_eq!(blob_files(&dir), 1);
+		assert_eq!(
+			fs::read_to_string(dir.join(JOURNAL))
+				.unwrap()
+				.lines()
+				.count(),
+			1
+		);
+	}
+}
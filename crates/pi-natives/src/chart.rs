@@ -0,0 +1,230 @@
+//! Mini-chart rendering with block characters.
+//!
+//! # Overview
+//! Renders numeric series as single-line sparklines (`▁▂▃▄▅▆▇█`) and labelled
+//! horizontal bar charts with eighth-block precision, for compact status UI
+//! such as token usage and latency history. Series longer than the target
+//! width are averaged into buckets. Non-finite values render as gaps.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.renderSparkline([1, 5, 3, 8, 2], { color: "36" }) // -> "\x1b[36m▁▅▃█▂\x1b[0m"
+//! // JS: native.renderBars([120, 45], ["input", "output"], 30) // -> ["input  ██████████████████ 120", ...]
+//! ```
+
+use std::fmt::Write;
+
+use napi_derive::napi;
+
+use crate::text::visible_width;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `renderSparkline`.
+#[napi(object)]
+pub struct SparklineOptions {
+	/// Maximum number of characters; longer series are averaged into buckets.
+	pub width: Option<u32>,
+	/// Value mapped to the lowest block (default: series minimum).
+	pub min:   Option<f64>,
+	/// Value mapped to the full block (default: series maximum).
+	pub max:   Option<f64>,
+	/// SGR parameters applied to the line (e.g. `"36"` or `"38;5;208"`).
+	pub color: Option<String>,
+}
+
+/// Options for `renderBars`.
+#[napi(object)]
+pub struct BarsOptions {
+	/// SGR parameters applied to the bars (e.g. `"32"`).
+	pub color:       Option<String>,
+	/// Omit the value after each bar (default: false).
+	#[napi(js_name = "hideValues")]
+	pub hide_values: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Rendering
+// ═══════════════════════════════════════════════════════════════════════════
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Partial blocks for 1/8 through 7/8 of a cell.
+const EIGHTHS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Average `values` into at most `width` buckets, ignoring non-finite values
+/// (a bucket with none is NaN).
+fn downsample(values: &[f64], width: usize) -> Vec<f64> {
+	if width == 0 || values.len() <= width {
+		return values.to_vec();
+	}
+	(0..width)
+		.map(|bucket| {
+			let start = bucket * values.len() / width;
+			let end = ((bucket + 1) * values.len() / width).max(start + 1);
+			let finite: Vec<f64> = values[start..end]
+				.iter()
+				.copied()
+				.filter(|v| v.is_finite())
+				.collect();
+			if finite.is_empty() {
+				f64::NAN
+			} else {
+				finite.iter().sum::<f64>() / finite.len() as f64
+			}
+		})
+		.collect()
+}
+
+fn styled(body: &str, color: Option<&str>) -> String {
+	match color {
+		Some(color) if !body.is_empty() => format!("\x1b[{color}m{body}\x1b[0m"),
+		_ => body.to_string(),
+	}
+}
+
+/// Render a series as one line of block characters.
+pub fn sparkline(
+	values: &[f64],
+	width: Option<usize>,
+	min: Option<f64>,
+	max: Option<f64>,
+) -> String {
+	let values = downsample(values, width.unwrap_or(0));
+	let finite = || values.iter().copied().filter(|v| v.is_finite());
+	let lo = min.unwrap_or_else(|| finite().fold(f64::INFINITY, f64::min));
+	let hi = max.unwrap_or_else(|| finite().fold(f64::NEG_INFINITY, f64::max));
+	let span = hi - lo;
+	values
+		.iter()
+		.map(|&value| {
+			if !value.is_finite() {
+				' '
+			} else if span <= 0.0 || !span.is_finite() {
+				SPARKS[SPARKS.len() / 2]
+			} else {
+				let level = ((value - lo) / span * (SPARKS.len() - 1) as f64).round();
+				SPARKS[level.clamp(0.0, (SPARKS.len() - 1) as f64) as usize]
+			}
+		})
+		.collect()
+}
+
+/// Format a bar value compactly: up to two decimals, trailing zeros removed.
+fn format_value(value: f64) -> String {
+	let text = format!("{value:.2}");
+	text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// A bar of `cells` character cells (fractional part in eighths).
+fn bar(cells: f64) -> String {
+	let eighths = (cells * 8.0).round().max(0.0) as usize;
+	let mut out: String = std::iter::repeat_n('█', eighths / 8).collect();
+	if !eighths.is_multiple_of(8) {
+		out.push(EIGHTHS[eighths % 8 - 1]);
+	}
+	out
+}
+
+/// Render labelled horizontal bars, one line per value, scaled so the largest
+/// value fills the bar area of a `width`-column line.
+pub fn bars(
+	values: &[f64],
+	labels: &[String],
+	width: usize,
+	color: Option<&str>,
+	show_values: bool,
+) -> Vec<String> {
+	let label_width = labels.iter().map(|label| visible_width(label)).max().unwrap_or(0);
+	let texts: Vec<String> = values
+		.iter()
+		.map(|&value| {
+			if show_values && value.is_finite() {
+				format_value(value)
+			} else {
+				String::new()
+			}
+		})
+		.collect();
+	let value_width = texts.iter().map(String::len).max().unwrap_or(0);
+	let reserved =
+		label_width + usize::from(label_width > 0) + value_width + usize::from(value_width > 0);
+	let area = width.saturating_sub(reserved).max(1);
+	let peak = values
+		.iter()
+		.copied()
+		.filter(|v| v.is_finite())
+		.fold(0.0, f64::max);
+
+	values
+		.iter()
+		.zip(texts)
+		.enumerate()
+		.map(|(i, (&value, text))| {
+			let mut line = String::new();
+			if label_width > 0 {
+				let label = labels.get(i).map_or("", String::as_str);
+				let pad = label_width - visible_width(label);
+				let _ = write!(line, "{label}{:pad$} ", "");
+			}
+			let cells = if peak > 0.0 && value.is_finite() {
+				value.max(0.0) / peak * area as f64
+			} else {
+				0.0
+			};
+			let body = bar(cells);
+			let body_width = body.chars().count();
+			line.push_str(&styled(&body, color));
+			if !text.is_empty() {
+				let pad = area - body_width.min(area);
+				let _ = write!(line, "{:pad$} {text}", "");
+			}
+			line
+		})
+		.collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Render a numeric series as a one-line sparkline.
+///
+/// # Arguments
+/// - `values`: Series to plot; non-finite values render as gaps.
+/// - `options`: Width limit, value range, and color.
+#[napi(js_name = "renderSparkline")]
+pub fn render_sparkline(values: Vec<f64>, options: Option<SparklineOptions>) -> String {
+	let (width, min, max, color) = match options {
+		Some(opts) => (opts.width, opts.min, opts.max, opts.color),
+		None => (None, None, None, None),
+	};
+	let line = sparkline(&values, width.map(|w| w as usize), min, max);
+	styled(&line, color.as_deref())
+}
+
+/// Render labelled horizontal bars.
+///
+/// # Arguments
+/// - `values`: Bar values; negative and non-finite values render empty.
+/// - `labels`: Label for each bar, padded to a common width.
+/// - `width`: Total line width in columns.
+/// - `options`: Bar color and value display.
+///
+/// # Returns
+/// One line per value.
+#[napi(js_name = "renderBars")]
+pub fn render_bars(
+	values: Vec<f64>,
+	labels: Vec<String>,
+	width: u32,
+	options: Option<BarsOptions>,
+) -> Vec<String> {
+	let (color, hide_values) = match options {
+		Some(opts) => (opts.color, opts.hide_values.unwrap_or(false)),
+		None => (None, false),
+	};
+	bars(&values, &labels, width as usize, color.as_deref(), !hide_values)
+}
@@ -0,0 +1,408 @@
+//! Token-aware file chunking for context building.
+//!
+//! # Overview
+//! Splits a file into line-aligned chunks of at most `maxTokens` BPE tokens.
+//! Split points are ranked by structure: for source code, lines that start a
+//! top-level definition rank highest, then members nested one or two levels
+//! deep (via tree-sitter); for Markdown, headings rank by level ahead of other
+//! blocks; blank lines are the weakest preference everywhere. Each chunk is cut
+//! at the best-ranked split point that keeps it at least half full.
+//!
+//! Doc comments and attributes directly above a definition stay attached to
+//! it. A single line longer than `maxTokens` becomes a chunk of its own.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.chunkFile("src/lib.rs", { maxTokens: 800, overlap: 64 })
+//! ```
+
+use std::path::Path;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use pulldown_cmark::{Event, Parser as MarkdownParser, Tag};
+use tiktoken_rs::CoreBPE;
+use tree_sitter::{Node, Parser};
+
+use crate::{markdown, symbols::Language, task, tokens};
+
+/// Default chunk budget in tokens.
+const DEFAULT_MAX_TOKENS: u32 = 512;
+/// Deepest definition nesting level that still produces split points.
+const MAX_LEVEL: u8 = 3;
+/// Rank of a split point after a blank line (lower ranks are preferred).
+const BLANK_RANK: u8 = MAX_LEVEL + 1;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `chunkFile`.
+#[napi(object)]
+pub struct ChunkFileOptions<'env> {
+	/// Maximum tokens per chunk (default: 512).
+	#[napi(js_name = "maxTokens")]
+	pub max_tokens:         Option<u32>,
+	/// Tokens of trailing context repeated at the start of the next chunk
+	/// (default: 0). Rounded down to whole lines.
+	pub overlap:            Option<u32>,
+	/// Prefer splitting on definition, heading, and paragraph boundaries
+	/// (default: true). When false, chunks are packed line by line.
+	#[napi(js_name = "respectBoundaries")]
+	pub respect_boundaries: Option<bool>,
+	/// Token encoding: `"o200k_base"` (default), `"cl100k_base"`,
+	/// `"p50k_base"`, or `"r50k_base"`.
+	pub encoding:           Option<String>,
+	/// Structure to split on: a language accepted by `extractSymbols`,
+	/// "markdown", or "text". Inferred from the extension when omitted.
+	pub language:           Option<String>,
+	/// Abort signal for cancelling the operation.
+	pub signal:             Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:         Option<u32>,
+}
+
+/// A contiguous, line-aligned slice of a file.
+#[napi(object)]
+pub struct FileChunk {
+	/// Chunk text.
+	pub text:       String,
+	/// First line (1-based).
+	#[napi(js_name = "startLine")]
+	pub start_line: u32,
+	/// Last line (1-based, inclusive).
+	#[napi(js_name = "endLine")]
+	pub end_line:   u32,
+	/// Start byte offset in the file (UTF-8).
+	#[napi(js_name = "startByte")]
+	pub start_byte: u32,
+	/// End byte offset in the file (UTF-8, exclusive).
+	#[napi(js_name = "endByte")]
+	pub end_byte:   u32,
+	/// Number of BPE tokens in `text`.
+	pub tokens:     u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Structure
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// How split points are found in a file.
+#[derive(Clone, Copy)]
+enum Structure {
+	Code(Language),
+	Markdown,
+	Text,
+}
+
+impl Structure {
+	fn from_name(name: &str) -> Option<Self> {
+		match name.trim().to_ascii_lowercase().as_str() {
+			"markdown" | "md" | "mdx" => Some(Self::Markdown),
+			"text" | "txt" | "plain" => Some(Self::Text),
+			other => Language::from_name(other).map(Self::Code),
+		}
+	}
+
+	fn from_path(path: &Path) -> Self {
+		path
+			.extension()
+			.and_then(|ext| ext.to_str())
+			.and_then(Self::from_name)
+			.unwrap_or(Self::Text)
+	}
+}
+
+/// Line table for a source text.
+struct Lines<'a> {
+	source: &'a str,
+	/// Byte offset where each line starts, plus a final entry at `source.len()`.
+	starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+	fn new(source: &'a str) -> Self {
+		let mut starts = vec![0];
+		starts.extend(source.match_indices('\n').map(|(idx, _)| idx + 1));
+		if starts.last() != Some(&source.len()) {
+			starts.push(source.len());
+		}
+		Self { source, starts }
+	}
+
+	const fn len(&self) -> usize {
+		self.starts.len() - 1
+	}
+
+	fn text(&self, line: usize) -> &'a str {
+		&self.source[self.starts[line]..self.starts[line + 1]]
+	}
+
+	/// Line containing byte `offset`.
+	fn line_of(&self, offset: usize) -> usize {
+		self
+			.starts
+			.partition_point(|&start| start <= offset)
+			.saturating_sub(1)
+	}
+
+	/// Whether `offset` is the first non-whitespace byte of its line.
+	fn starts_line(&self, offset: usize) -> bool {
+		let line_start = self.starts[self.line_of(offset)];
+		self.source[line_start..offset].trim().is_empty()
+	}
+}
+
+/// Record a split point before `line`, keeping the best rank seen.
+fn mark(ranks: &mut [Option<u8>], line: usize, rank: u8) {
+	if line == 0 || line >= ranks.len() {
+		return;
+	}
+	let slot = &mut ranks[line];
+	*slot = Some(slot.map_or(rank, |current| current.min(rank)));
+}
+
+fn mark_blank_lines(lines: &Lines<'_>, ranks: &mut [Option<u8>], rank: u8) {
+	for line in 1..lines.len() {
+		if lines.text(line - 1).trim().is_empty() && !lines.text(line).trim().is_empty() {
+			mark(ranks, line, rank);
+		}
+	}
+}
+
+/// Whether `node` is a comment or attribute that documents the next sibling.
+fn is_leading_trivia(node: Node<'_>) -> bool {
+	let kind = node.kind();
+	kind.contains("comment") || kind == "attribute_item" || kind == "decorator"
+}
+
+fn mark_definitions(node: Node<'_>, level: u8, lines: &Lines<'_>, ranks: &mut [Option<u8>]) {
+	let mut cursor = node.walk();
+	let mut prev: Option<Node<'_>> = None;
+	for child in node.named_children(&mut cursor) {
+		let start = child.start_byte();
+		let line = child.start_position().row;
+		let begins_line = lines.starts_line(start);
+		// Leading comments and attributes keep the split point above them.
+		let attached =
+			prev.is_some_and(|prev| is_leading_trivia(prev) && prev.end_position().row + 1 >= line);
+		if begins_line && !attached {
+			mark(ranks, line, level);
+		}
+		if child.end_position().row > line && level < MAX_LEVEL {
+			let next = if begins_line { level + 1 } else { level };
+			mark_definitions(child, next, lines, ranks);
+		}
+		prev = Some(child);
+	}
+}
+
+fn mark_markdown(lines: &Lines<'_>, ranks: &mut [Option<u8>]) {
+	let mut depth = 0usize;
+	let parser = MarkdownParser::new_ext(lines.source, markdown::parser_options());
+	for (event, range) in parser.into_offset_iter() {
+		match event {
+			Event::Start(tag) => {
+				let rank = match (depth, tag) {
+					(0, Tag::Heading { level, .. }) => Some((level as u8 - 1).min(MAX_LEVEL)),
+					(0, _) => Some(MAX_LEVEL),
+					// Items of a top-level list rank with paragraph breaks.
+					(1, Tag::Item) => Some(BLANK_RANK),
+					_ => None,
+				};
+				if let Some(rank) = rank {
+					mark(ranks, lines.line_of(range.start), rank);
+				}
+				depth += 1;
+			},
+			Event::End(_) => depth = depth.saturating_sub(1),
+			Event::Rule if depth == 0 => mark(ranks, lines.line_of(range.start), MAX_LEVEL),
+			_ => {},
+		}
+	}
+}
+
+/// Rank of the split point before each line, or `None` where splitting is
+/// not preferred.
+fn split_ranks(lines: &Lines<'_>, structure: Structure) -> Vec<Option<u8>> {
+	let mut ranks = vec![None; lines.len()];
+	match structure {
+		Structure::Code(language) => {
+			let mut parser = Parser::new();
+			let tree = parser
+				.set_language(&language.grammar())
+				.ok()
+				.and_then(|()| parser.parse(lines.source, None));
+			// Files the grammar cannot handle still split on blank lines.
+			if let Some(tree) = tree {
+				mark_definitions(tree.root_node(), 0, lines, &mut ranks);
+			}
+			mark_blank_lines(lines, &mut ranks, BLANK_RANK);
+		},
+		Structure::Markdown => {
+			mark_markdown(lines, &mut ranks);
+			mark_blank_lines(lines, &mut ranks, BLANK_RANK);
+		},
+		Structure::Text => mark_blank_lines(lines, &mut ranks, 0),
+	}
+	ranks
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Packing
+// ═══════════════════════════════════════════════════════════════════════════
+
+struct ChunkConfig {
+	max_tokens:         usize,
+	overlap:            usize,
+	respect_boundaries: bool,
+	structure:          Structure,
+}
+
+/// Pick where to end a chunk that starts at `start` and overflows at `limit`.
+fn choose_split(
+	start: usize,
+	limit: usize,
+	prefix: &[usize],
+	ranks: &[Option<u8>],
+	config: &ChunkConfig,
+) -> usize {
+	if !config.respect_boundaries {
+		return limit;
+	}
+	let half = config.max_tokens / 2;
+	let candidates = || (start + 1..=limit).filter_map(|line| ranks[line].map(|rank| (line, rank)));
+	// Best rank first, then the latest line among equals.
+	let best = |iter: &mut dyn Iterator<Item = (usize, u8)>| {
+		iter
+			.min_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))
+			.map(|(line, _)| line)
+	};
+	// Without a split point past the halfway mark, take the last one rather than
+	// emit a sliver of a chunk.
+	best(&mut candidates().filter(|&(line, _)| prefix[line] - prefix[start] >= half))
+		.or_else(|| candidates().next_back().map(|(line, _)| line))
+		.unwrap_or(limit)
+}
+
+fn chunk_source(
+	source: &str,
+	bpe: &CoreBPE,
+	config: &ChunkConfig,
+	ct: &task::CancelToken,
+) -> Result<Vec<FileChunk>> {
+	let lines = Lines::new(source);
+	let count = lines.len();
+	let mut prefix = Vec::with_capacity(count + 1);
+	prefix.push(0usize);
+	for line in 0..count {
+		if line % 1024 == 0 {
+			ct.heartbeat()?;
+		}
+		let tokens = bpe.encode_ordinary(lines.text(line)).len();
+		prefix.push(prefix[line] + tokens);
+	}
+	let ranks = if config.respect_boundaries {
+		split_ranks(&lines, config.structure)
+	} else {
+		Vec::new()
+	};
+
+	let mut chunks = Vec::new();
+	let mut start = 0;
+	while start < count {
+		ct.heartbeat()?;
+		// First line that does not fit, or `count` when the rest fits.
+		let limit = (start + 1..=count)
+			.find(|&end| prefix[end] - prefix[start] > config.max_tokens)
+			.map_or(count, |end| end - 1);
+		let end = if limit == count {
+			count
+		} else if limit == start {
+			// A single line over budget.
+			start + 1
+		} else {
+			choose_split(start, limit, &prefix, &ranks, config)
+		};
+
+		let (start_byte, end_byte) = (lines.starts[start], lines.starts[end]);
+		let text = source[start_byte..end_byte].to_string();
+		let tokens = bpe.encode_ordinary(&text).len();
+		chunks.push(FileChunk {
+			text,
+			start_line: (start + 1) as u32,
+			end_line: end as u32,
+			start_byte: start_byte as u32,
+			end_byte: end_byte as u32,
+			tokens: tokens.min(u32::MAX as usize) as u32,
+		});
+		if end == count {
+			break;
+		}
+
+		// Back up over whole trailing lines that fit the overlap budget, always
+		// leaving the next chunk ahead of this one's start.
+		let mut next = end;
+		while next > start + 1 && prefix[end] - prefix[next - 1] <= config.overlap {
+			next -= 1;
+		}
+		start = next;
+	}
+	Ok(chunks)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Split a file into token-bounded chunks on structural boundaries.
+///
+/// # Returns
+/// Chunks in file order. Without overlap they tile the file exactly; with
+/// overlap each chunk repeats the tail of the previous one.
+///
+/// # Errors
+/// Returns an error for a zero `maxTokens`, an unknown encoding or language,
+/// an unreadable file, or cancellation.
+#[napi(js_name = "chunkFile")]
+pub fn chunk_file(
+	path: String,
+	options: Option<ChunkFileOptions<'_>>,
+) -> Result<task::Async<Vec<FileChunk>>> {
+	let (max_tokens, overlap, respect_boundaries, encoding, language, signal, timeout_ms) =
+		match options {
+			Some(opts) => (
+				opts.max_tokens,
+				opts.overlap,
+				opts.respect_boundaries,
+				opts.encoding,
+				opts.language,
+				opts.signal,
+				opts.timeout_ms,
+			),
+			None => (None, None, None, None, None, None, None),
+		};
+	let max_tokens = max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+	if max_tokens == 0 {
+		return Err(Error::from_reason("maxTokens must be greater than 0"));
+	}
+	let encoder = tokens::resolve_encoding(encoding.as_deref())?;
+	let structure = match language.as_deref() {
+		Some(name) => Structure::from_name(name)
+			.ok_or_else(|| Error::from_reason(format!("Unsupported language: {name}")))?,
+		None => Structure::from_path(Path::new(&path)),
+	};
+	let config = ChunkConfig {
+		max_tokens: max_tokens as usize,
+		overlap: overlap.unwrap_or(0) as usize,
+		respect_boundaries: respect_boundaries.unwrap_or(true),
+		structure,
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("chunk_file", ct, move |ct| {
+		let bytes = std::fs::read(&path)
+			.map_err(|err| Error::from_reason(format!("Failed to read {path}: {err}")))?;
+		chunk_source(&String::from_utf8_lossy(&bytes), encoder(), &config, &ct)
+	}))
+}
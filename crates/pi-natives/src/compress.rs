@@ -0,0 +1,270 @@
+//! zstd and gzip compression.
+//!
+//! # Overview
+//! Compresses and decompresses buffers and files on the worker pool, so
+//! archiving session history or packing large context bundles never blocks
+//! the event loop. Decompression detects the format from the magic bytes
+//! and accepts concatenated frames/members, as produced by appending to an
+//! archive. File variants stream through fixed-size buffers and write the
+//! output via a temp file renamed into place, so a failed run never leaves a
+//! truncated destination.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const packed = await native.compress(bytes, { algo: "zstd", level: 9 })
+//! // JS: const bytes2 = await native.decompress(packed)
+//! // JS: await native.compressFile("session.jsonl", "session.jsonl.gz", { algo: "gzip" })
+//! ```
+
+use std::{
+	fs::{self, File},
+	io::{self, BufRead, BufReader, BufWriter, Read, Write},
+	path::{Path, PathBuf},
+};
+
+use flate2::{Compression, read::MultiGzDecoder, write::GzEncoder};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `compress` and `compressFile`.
+#[napi(object)]
+pub struct CompressOptions {
+	/// Format: "zstd" (default) or "gzip".
+	pub algo:  Option<String>,
+	/// Compression level: 1-22 for zstd (default 3), 0-9 for gzip (default 6).
+	pub level: Option<i32>,
+}
+
+/// Result of `compressFile` and `decompressFile`.
+#[napi(object)]
+pub struct CompressFileResult {
+	/// Bytes read from the source file.
+	#[napi(js_name = "bytesIn")]
+	pub bytes_in:  f64,
+	/// Bytes written to the destination file.
+	#[napi(js_name = "bytesOut")]
+	pub bytes_out: f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Codecs
+// ═══════════════════════════════════════════════════════════════════════════
+
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Buffer size for streaming file variants.
+const STREAM_BUFFER: usize = 256 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+	Zstd,
+	Gzip,
+}
+
+/// Parse an algorithm name; `None` selects zstd.
+pub fn parse_algorithm(name: Option<&str>) -> Result<Algorithm> {
+	match name.map(str::trim) {
+		None | Some("" | "zstd") => Ok(Algorithm::Zstd),
+		Some("gzip" | "gz") => Ok(Algorithm::Gzip),
+		Some(other) => Err(Error::from_reason(format!("Unknown compression algorithm: {other}"))),
+	}
+}
+
+/// Resolve a level for `algorithm`, rejecting values outside its range.
+fn resolve_level(algorithm: Algorithm, level: Option<i32>) -> Result<i32> {
+	let (default, range) = match algorithm {
+		Algorithm::Zstd => (3, 1..=22),
+		Algorithm::Gzip => (6, 0..=9),
+	};
+	match level {
+		None => Ok(default),
+		Some(level) if range.contains(&level) => Ok(level),
+		Some(level) => Err(Error::from_reason(format!(
+			"Invalid {algorithm:?} level {level}: expected {}-{}",
+			range.start(),
+			range.end()
+		))),
+	}
+}
+
+/// Detect the format from the leading magic bytes.
+pub fn detect_algorithm(data: &[u8]) -> Option<Algorithm> {
+	if data.starts_with(&ZSTD_MAGIC) {
+		Some(Algorithm::Zstd)
+	} else if data.starts_with(&GZIP_MAGIC) {
+		Some(Algorithm::Gzip)
+	} else {
+		None
+	}
+}
+
+/// Copy `reader` into `writer`, compressing with `algorithm`.
+pub fn compress_stream(
+	reader: impl Read,
+	writer: impl Write,
+	algorithm: Algorithm,
+	level: i32,
+) -> io::Result<()> {
+	let mut reader = reader;
+	match algorithm {
+		Algorithm::Zstd => zstd::stream::copy_encode(reader, writer, level),
+		Algorithm::Gzip => {
+			let mut encoder = GzEncoder::new(writer, Compression::new(level as u32));
+			io::copy(&mut reader, &mut encoder)?;
+			encoder.finish()?;
+			Ok(())
+		},
+	}
+}
+
+/// Copy `reader` into `writer`, decompressing the format its magic bytes
+/// announce.
+pub fn decompress_stream(reader: impl Read, writer: impl Write) -> io::Result<()> {
+	let mut reader = BufReader::with_capacity(STREAM_BUFFER, reader);
+	let mut writer = writer;
+	match detect_algorithm(reader.fill_buf()?) {
+		Some(Algorithm::Zstd) => zstd::stream::copy_decode(reader, writer),
+		Some(Algorithm::Gzip) => {
+			io::copy(&mut MultiGzDecoder::new(reader), &mut writer)?;
+			Ok(())
+		},
+		None => Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			"unrecognized format (expected zstd or gzip data)",
+		)),
+	}
+}
+
+fn codec_error(action: &str, err: io::Error) -> Error {
+	Error::from_reason(format!("Failed to {action}: {err}"))
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+	let name = path.file_name().map_or_else(Default::default, |name| name.to_string_lossy());
+	path.with_file_name(format!(".{name}.{}.compress.tmp", std::process::id()))
+}
+
+/// Stream `src` through `transform` into `dst` via a temp file.
+fn transform_file(
+	src: &Path,
+	dst: &Path,
+	action: &str,
+	transform: impl FnOnce(File, &mut BufWriter<File>) -> io::Result<()>,
+) -> Result<CompressFileResult> {
+	let input = File::open(src)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", src.display())))?;
+	let bytes_in = input.metadata().map_or(0, |meta| meta.len());
+	let temp = temp_path(dst);
+	let written = File::create(&temp).and_then(|output| {
+		let mut writer = BufWriter::with_capacity(STREAM_BUFFER, output);
+		transform(input, &mut writer)?;
+		writer.into_inner().map_err(io::IntoInnerError::into_error)?.sync_all()
+	});
+	let renamed = written.and_then(|()| fs::rename(&temp, dst));
+	if let Err(err) = renamed {
+		let _ = fs::remove_file(&temp);
+		return Err(Error::from_reason(format!(
+			"Failed to {action} {} to {}: {err}",
+			src.display(),
+			dst.display()
+		)));
+	}
+	let bytes_out = fs::metadata(dst).map_or(0, |meta| meta.len());
+	Ok(CompressFileResult { bytes_in: bytes_in as f64, bytes_out: bytes_out as f64 })
+}
+
+fn to_bytes(data: Either<String, Uint8Array>) -> Vec<u8> {
+	match data {
+		Either::A(text) => text.into_bytes(),
+		Either::B(buffer) => buffer.as_ref().to_vec(),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Compress a buffer or string (as UTF-8) on the worker pool.
+///
+/// # Errors
+/// Returns an error for an unknown algorithm or an out-of-range level.
+#[napi]
+pub fn compress(
+	data: Either<String, Uint8Array>,
+	options: Option<CompressOptions>,
+) -> Result<task::Async<Vec<u8>>> {
+	let (algo, level) = match options {
+		Some(opts) => (opts.algo, opts.level),
+		None => (None, None),
+	};
+	let algorithm = parse_algorithm(algo.as_deref())?;
+	let level = resolve_level(algorithm, level)?;
+	let bytes = to_bytes(data);
+	Ok(task::blocking("compress", (), move |_| {
+		let mut out = Vec::with_capacity(bytes.len() / 2);
+		compress_stream(bytes.as_slice(), &mut out, algorithm, level)
+			.map_err(|err| codec_error("compress", err))?;
+		Ok(out)
+	}))
+}
+
+/// Decompress zstd or gzip data on the worker pool, detecting the format.
+///
+/// # Errors
+/// Returns an error if the data is not zstd or gzip, or is corrupt.
+#[napi]
+pub fn decompress(data: Uint8Array) -> task::Async<Vec<u8>> {
+	let bytes = data.as_ref().to_vec();
+	task::blocking("decompress", (), move |_| {
+		let mut out = Vec::with_capacity(bytes.len().saturating_mul(4));
+		decompress_stream(bytes.as_slice(), &mut out).map_err(|err| codec_error("decompress", err))?;
+		Ok(out)
+	})
+}
+
+/// Compress `src` into `dst`, streaming on the worker pool.
+///
+/// # Errors
+/// Returns an error for invalid options or if either file cannot be
+/// accessed.
+#[napi(js_name = "compressFile")]
+pub fn compress_file(
+	src: String,
+	dst: String,
+	options: Option<CompressOptions>,
+) -> Result<task::Async<CompressFileResult>> {
+	let (algo, level) = match options {
+		Some(opts) => (opts.algo, opts.level),
+		None => (None, None),
+	};
+	let algorithm = parse_algorithm(algo.as_deref())?;
+	let level = resolve_level(algorithm, level)?;
+	Ok(task::blocking("compress.file", (), move |_| {
+		transform_file(Path::new(&src), Path::new(&dst), "compress", |input, output| {
+			let input = BufReader::with_capacity(STREAM_BUFFER, input);
+			compress_stream(input, output, algorithm, level)
+		})
+	}))
+}
+
+/// Decompress `src` (zstd or gzip, detected) into `dst`, streaming on the
+/// worker pool.
+///
+/// # Errors
+/// Returns an error if the source is not zstd or gzip, is corrupt, or
+/// either file cannot be accessed.
+#[napi(js_name = "decompressFile")]
+pub fn decompress_file(src: String, dst: String) -> task::Async<CompressFileResult> {
+	task::blocking("decompress.file", (), move |_| {
+		transform_file(Path::new(&src), Path::new(&dst), "decompress", |input, output| {
+			decompress_stream(input, output)
+		})
+	})
+}
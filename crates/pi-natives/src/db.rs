@@ -0,0 +1,375 @@
+//! Embedded `SQLite` storage.
+//!
+//! # Overview
+//! `openDb` opens (or creates) a `SQLite` database on the worker pool and
+//! returns a `Database` handle whose `exec`, `query`, and `transaction`
+//! methods run there too, so state that used to live in hand-rolled JSON
+//! files gets atomic, crash-safe writes.
+//!
+//! Databases open in WAL mode with `synchronous=NORMAL` and foreign keys on.
+//! Schema changes are ordered SQL scripts: migration `i` upgrades
+//! `PRAGMA user_version` from `i` to `i + 1`, each in its own transaction, so
+//! a crash mid-upgrade resumes at the first unapplied migration.
+//!
+//! Values cross the boundary as `null`, numbers, strings, or bytes. Integers
+//! are returned as JS numbers, so values beyond 2^53 lose precision.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const db = await native.openDb(".pi/state.db", { migrations: ["CREATE TABLE kv (k TEXT PRIMARY KEY, v TEXT)"] })
+//! // JS: await db.exec("INSERT OR REPLACE INTO kv VALUES (?, ?)", ["theme", "dark"])
+//! // JS: await db.query("SELECT v FROM kv WHERE k = ?", ["theme"]) // -> { columns: ["v"], rows: [["dark"]] }
+//! ```
+
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::Mutex;
+use rusqlite::{Connection, OpenFlags, params_from_iter, types::Value};
+
+use crate::task;
+
+/// Default time to wait on a locked database.
+const DEFAULT_BUSY_TIMEOUT_MS: u32 = 5_000;
+
+/// A value read from a column: `null`, number, text, or blob.
+pub type SqlValue = Option<Either3<f64, String, Uint8Array>>;
+
+/// A value bound to a `?` parameter: `null`, number, text, blob, or boolean.
+pub type SqlParam = Option<Either4<f64, String, Uint8Array, bool>>;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `openDb`.
+#[napi(object)]
+pub struct DbOpenOptions {
+	/// Open without write access; the file must exist (default: false).
+	pub readonly:        Option<bool>,
+	/// Milliseconds to wait on a locked database before failing
+	/// (default: 5000).
+	#[napi(js_name = "busyTimeoutMs")]
+	pub busy_timeout_ms: Option<u32>,
+	/// Schema migrations to apply on open; see `Database.migrate`.
+	pub migrations:      Option<Vec<String>>,
+}
+
+/// One statement of a `Database.transaction` batch.
+#[napi(object)]
+pub struct DbStatement {
+	/// SQL text of a single statement.
+	pub sql:    String,
+	/// Values for the statement's `?` parameters.
+	pub params: Option<Vec<SqlParam>>,
+}
+
+/// Outcome of a write.
+#[napi(object)]
+pub struct DbExecResult {
+	/// Rows inserted, updated, or deleted.
+	pub changes:           u32,
+	/// Rowid of the most recent successful insert on this connection.
+	#[napi(js_name = "lastInsertRowid")]
+	pub last_insert_rowid: f64,
+}
+
+/// Rows returned by a query.
+#[napi(object)]
+pub struct DbQueryResult {
+	/// Column names in select order.
+	pub columns: Vec<String>,
+	/// Row values, one entry per column.
+	pub rows:    Vec<Vec<SqlValue>>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Conversion
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn sql_error(err: rusqlite::Error) -> Error {
+	Error::from_reason(format!("SQLite error: {err}"))
+}
+
+/// Convert JS parameters into owned `SQLite` values; whole numbers bind as
+/// integers.
+fn bind_params(params: Option<Vec<SqlParam>>) -> Vec<Value> {
+	params
+		.unwrap_or_default()
+		.into_iter()
+		.map(|param| match param {
+			None => Value::Null,
+			Some(Either4::A(number)) if number.fract() == 0.0 && number.abs() < 9.2e18 => {
+				Value::Integer(number as i64)
+			},
+			Some(Either4::A(number)) => Value::Real(number),
+			Some(Either4::B(text)) => Value::Text(text),
+			Some(Either4::C(bytes)) => Value::Blob(bytes.to_vec()),
+			Some(Either4::D(flag)) => Value::Integer(i64::from(flag)),
+		})
+		.collect()
+}
+
+fn to_js(value: Value) -> SqlValue {
+	match value {
+		Value::Null => None,
+		Value::Integer(number) => Some(Either3::A(number as f64)),
+		Value::Real(number) => Some(Either3::A(number)),
+		Value::Text(text) => Some(Either3::B(text)),
+		Value::Blob(bytes) => Some(Either3::C(Uint8Array::from(bytes))),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Connection
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn open_connection(path: &str, readonly: bool, busy_timeout_ms: u32) -> Result<Connection> {
+	let flags = if readonly {
+		OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX
+	} else {
+		OpenFlags::default()
+	};
+	if !readonly
+		&& let Some(parent) = PathBuf::from(path)
+			.parent()
+			.filter(|parent| !parent.as_os_str().is_empty())
+	{
+		std::fs::create_dir_all(parent).map_err(|err| {
+			Error::from_reason(format!("Failed to create {}: {err}", parent.display()))
+		})?;
+	}
+	let conn = Connection::open_with_flags(path, flags)
+		.map_err(|err| Error::from_reason(format!("Failed to open {path}: {err}")))?;
+	conn
+		.busy_timeout(Duration::from_millis(u64::from(busy_timeout_ms)))
+		.map_err(sql_error)?;
+	if !readonly {
+		conn
+			.pragma_update_and_check(None, "journal_mode", "WAL", |_| Ok(()))
+			.map_err(sql_error)?;
+		conn
+			.pragma_update(None, "synchronous", "NORMAL")
+			.map_err(sql_error)?;
+	}
+	conn
+		.pragma_update(None, "foreign_keys", "ON")
+		.map_err(sql_error)?;
+	Ok(conn)
+}
+
+fn execute(conn: &Connection, sql: &str, params: &[Value]) -> Result<()> {
+	conn
+		.execute(sql, params_from_iter(params))
+		.map(drop)
+		.map_err(sql_error)
+}
+
+fn exec_result(conn: &Connection, changes: u64) -> DbExecResult {
+	DbExecResult {
+		changes:           changes.min(u64::from(u32::MAX)) as u32,
+		last_insert_rowid: conn.last_insert_rowid() as f64,
+	}
+}
+
+fn query(conn: &Connection, sql: &str, params: &[Value]) -> Result<DbQueryResult> {
+	let mut stmt = conn.prepare(sql).map_err(sql_error)?;
+	let columns: Vec<String> = stmt
+		.column_names()
+		.into_iter()
+		.map(str::to_string)
+		.collect();
+	let mut rows = stmt.query(params_from_iter(params)).map_err(sql_error)?;
+	let mut out = Vec::new();
+	while let Some(row) = rows.next().map_err(sql_error)? {
+		let values = (0..columns.len())
+			.map(|idx| row.get::<_, Value>(idx).map(to_js))
+			.collect::<rusqlite::Result<Vec<_>>>()
+			.map_err(sql_error)?;
+		out.push(values);
+	}
+	Ok(DbQueryResult { columns, rows: out })
+}
+
+/// Apply the migrations past the stored `user_version`.
+fn migrate(conn: &mut Connection, migrations: &[String]) -> Result<u32> {
+	let current: u32 = conn
+		.pragma_query_value(None, "user_version", |row| row.get(0))
+		.map_err(sql_error)?;
+	if current as usize > migrations.len() {
+		return Err(Error::from_reason(format!(
+			"Database schema version {current} is newer than the {} known migrations",
+			migrations.len()
+		)));
+	}
+	for (idx, script) in migrations.iter().enumerate().skip(current as usize) {
+		let version = idx as u32 + 1;
+		let tx = conn.transaction().map_err(sql_error)?;
+		tx.execute_batch(script)
+			.map_err(|err| Error::from_reason(format!("Migration {version} failed: {err}")))?;
+		tx.pragma_update(None, "user_version", version)
+			.map_err(sql_error)?;
+		tx.commit().map_err(sql_error)?;
+	}
+	Ok(migrations.len() as u32)
+}
+
+type SharedConnection = Arc<Mutex<Option<Connection>>>;
+
+/// Run `f` on the open connection, or fail if the database was closed.
+fn with_connection<T>(
+	conn: &SharedConnection,
+	f: impl FnOnce(&mut Connection) -> Result<T>,
+) -> Result<T> {
+	let mut guard = conn.lock();
+	let conn = guard
+		.as_mut()
+		.ok_or_else(|| Error::from_reason("Database is closed"))?;
+	f(conn)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Handle to an open `SQLite` database. Create one with `openDb`.
+#[napi]
+pub struct Database {
+	conn: SharedConnection,
+	path: String,
+}
+
+#[napi]
+impl Database {
+	/// Run SQL that returns no rows. Without `params`, `sql` may hold several
+	/// `;`-separated statements.
+	///
+	/// # Errors
+	/// Returns an error if the database is closed or the SQL fails.
+	#[napi]
+	pub fn exec(&self, sql: String, params: Option<Vec<SqlParam>>) -> task::Async<DbExecResult> {
+		let batch = params.is_none();
+		let params = bind_params(params);
+		let conn = Arc::clone(&self.conn);
+		task::blocking("db.exec", (), move |_| {
+			with_connection(&conn, |conn| {
+				let before = conn.total_changes();
+				if batch {
+					conn.execute_batch(&sql).map_err(sql_error)?;
+				} else {
+					execute(conn, &sql, &params)?;
+				}
+				Ok(exec_result(conn, conn.total_changes() - before))
+			})
+		})
+	}
+
+	/// Run a single statement and collect every row it returns.
+	///
+	/// # Errors
+	/// Returns an error if the database is closed or the SQL fails.
+	#[napi]
+	pub fn query(&self, sql: String, params: Option<Vec<SqlParam>>) -> task::Async<DbQueryResult> {
+		let params = bind_params(params);
+		let conn = Arc::clone(&self.conn);
+		task::blocking("db.query", (), move |_| {
+			with_connection(&conn, |conn| query(conn, &sql, &params))
+		})
+	}
+
+	/// Run `statements` in one transaction; if any fails, none take effect.
+	///
+	/// # Errors
+	/// Returns an error naming the failing statement, or if the database is
+	/// closed.
+	#[napi]
+	pub fn transaction(&self, statements: Vec<DbStatement>) -> task::Async<DbExecResult> {
+		let statements: Vec<(String, Vec<Value>)> = statements
+			.into_iter()
+			.map(|stmt| (stmt.sql, bind_params(stmt.params)))
+			.collect();
+		let conn = Arc::clone(&self.conn);
+		task::blocking("db.transaction", (), move |_| {
+			with_connection(&conn, |conn| {
+				let before = conn.total_changes();
+				let tx = conn.transaction().map_err(sql_error)?;
+				for (idx, (sql, params)) in statements.iter().enumerate() {
+					tx.execute(sql, params_from_iter(params)).map_err(|err| {
+						Error::from_reason(format!("Statement {} failed: {err}", idx + 1))
+					})?;
+				}
+				tx.commit().map_err(sql_error)?;
+				Ok(exec_result(conn, conn.total_changes() - before))
+			})
+		})
+	}
+
+	/// Apply the migrations past the database's `PRAGMA user_version`.
+	/// Migration `i` upgrades version `i` to `i + 1`; each runs in its own
+	/// transaction.
+	///
+	/// # Returns
+	/// The resulting schema version (`migrations.length`).
+	///
+	/// # Errors
+	/// Returns an error if a migration fails (earlier ones stay applied), the
+	/// stored version is newer than `migrations`, or the database is closed.
+	#[napi]
+	pub fn migrate(&self, migrations: Vec<String>) -> task::Async<u32> {
+		let conn = Arc::clone(&self.conn);
+		task::blocking("db.migrate", (), move |_| {
+			with_connection(&conn, |conn| migrate(conn, &migrations))
+		})
+	}
+
+	/// Close the database. Later calls fail; closing twice is a no-op.
+	///
+	/// # Errors
+	/// Returns an error if `SQLite` cannot finalize the connection.
+	#[napi]
+	pub fn close(&self) -> task::Async<()> {
+		let conn = Arc::clone(&self.conn);
+		task::blocking("db.close", (), move |_| {
+			let conn = conn.lock().take();
+			conn.map_or(Ok(()), |conn| conn.close().map_err(|(_, err)| sql_error(err)))
+		})
+	}
+
+	/// Path the database was opened with.
+	#[napi(getter)]
+	pub fn path(&self) -> String {
+		self.path.clone()
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Open (or create) the `SQLite` database at `path` and apply `migrations`.
+///
+/// # Arguments
+/// - `path`: Database file; parent directories are created. `":memory:"` opens
+///   a private in-memory database.
+///
+/// # Errors
+/// Returns an error if the file cannot be opened or a migration fails.
+#[napi(js_name = "openDb")]
+pub fn open_db(path: String, options: Option<DbOpenOptions>) -> task::Async<Database> {
+	let (readonly, busy_timeout_ms, migrations) = match options {
+		Some(opts) => (opts.readonly, opts.busy_timeout_ms, opts.migrations),
+		None => (None, None, None),
+	};
+	task::blocking("db.open", (), move |_| {
+		let mut conn = open_connection(
+			&path,
+			readonly.unwrap_or(false),
+			busy_timeout_ms.unwrap_or(DEFAULT_BUSY_TIMEOUT_MS),
+		)?;
+		if let Some(migrations) = migrations {
+			migrate(&mut conn, &migrations)?;
+		}
+		Ok(Database { conn: Arc::new(Mutex::new(Some(conn))), path })
+	})
+}
@@ -0,0 +1,511 @@
+//! Line-oriented text diffing exported via N-API.
+//!
+//! # Overview
+//! Computes edit scripts with imara-diff (Myers or Histogram), then groups the
+//! changes into unified-diff style hunks with surrounding context lines and
+//! 1-indexed line numbers on both sides. Replaced line pairs can be refined
+//! into word- or character-level changed ranges for inline highlighting, and
+//! short texts can be diffed word by word.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.diffLines("a\nb\n", "a\nc\n", { algorithm: "histogram", context: 3 })
+//! // JS: native.diffWords("let x = 1", "let y = 1")
+//! // -> [{ kind: 0, text: "let " }, { kind: 2, text: "x" }, { kind: 1, text: "y" }, { kind: 0, text: " = 1" }]
+//! ```
+
+use std::ops::Range;
+
+use imara_diff::{Algorithm, Diff, InternedInput, Interner, Token};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+const DEFAULT_CONTEXT: u32 = 3;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Kind of a single line inside a diff hunk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[napi]
+pub enum DiffLineKind {
+	/// Line present on both sides.
+	Context = 0,
+	/// Line only present in the new text.
+	Added   = 1,
+	/// Line only present in the old text.
+	Removed = 2,
+}
+
+/// Options for `diffLines`.
+#[napi(object)]
+#[derive(Default)]
+pub struct DiffOptions<'env> {
+	/// Diff algorithm: "histogram" (default), "myers", or "myersMinimal".
+	pub algorithm:  Option<String>,
+	/// Number of unchanged context lines around each change (default: 3).
+	pub context:    Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// A single line of a diff hunk.
+#[derive(Clone)]
+#[napi(object)]
+pub struct DiffLine {
+	/// Whether the line is context, added, or removed.
+	pub kind:     DiffLineKind,
+	/// Line content without its line terminator.
+	pub content:  String,
+	/// 1-indexed line number in the old text (absent for added lines).
+	#[napi(js_name = "oldLine")]
+	pub old_line: Option<u32>,
+	/// 1-indexed line number in the new text (absent for removed lines).
+	#[napi(js_name = "newLine")]
+	pub new_line: Option<u32>,
+}
+
+/// A group of nearby changes with surrounding context.
+#[derive(Clone)]
+#[napi(object)]
+pub struct DiffHunk {
+	/// 1-indexed start line in the old text (line before the hunk when
+	/// `oldLines` is 0, matching unified diff headers).
+	#[napi(js_name = "oldStart")]
+	pub old_start: u32,
+	/// Number of old-text lines covered by the hunk.
+	#[napi(js_name = "oldLines")]
+	pub old_lines: u32,
+	/// 1-indexed start line in the new text (line before the hunk when
+	/// `newLines` is 0).
+	#[napi(js_name = "newStart")]
+	pub new_start: u32,
+	/// Number of new-text lines covered by the hunk.
+	#[napi(js_name = "newLines")]
+	pub new_lines: u32,
+	/// Lines of the hunk in display order.
+	pub lines:     Vec<DiffLine>,
+}
+
+/// Result of a line diff.
+#[napi(object)]
+pub struct DiffResult {
+	/// Hunks in ascending line order (empty when the texts are identical).
+	pub hunks:   Vec<DiffHunk>,
+	/// Total number of added lines.
+	pub added:   u32,
+	/// Total number of removed lines.
+	pub removed: u32,
+}
+
+/// Options for `refineHunk`.
+#[napi(object)]
+#[derive(Default)]
+pub struct RefineOptions {
+	/// Token granularity: "word" (default) or "char".
+	pub granularity: Option<String>,
+}
+
+/// A changed span inside a line, in UTF-16 code units.
+#[derive(Clone, Copy)]
+#[napi(object)]
+pub struct ChangedRange {
+	/// Start offset (inclusive).
+	pub start: u32,
+	/// End offset (exclusive).
+	pub end:   u32,
+}
+
+/// Intra-line changes for one replaced line pair.
+#[napi(object)]
+pub struct LineRefinement {
+	/// Changed spans in the old line.
+	#[napi(js_name = "oldRanges")]
+	pub old_ranges: Vec<ChangedRange>,
+	/// Changed spans in the new line.
+	#[napi(js_name = "newRanges")]
+	pub new_ranges: Vec<ChangedRange>,
+	/// Fraction of tokens shared by both lines (0 = disjoint, 1 = identical).
+	pub similarity: f64,
+}
+
+/// A run of text in a word diff.
+#[napi(object)]
+pub struct WordDiffPart {
+	/// Whether the text is shared, added, or removed.
+	pub kind: DiffLineKind,
+	/// The text of the run.
+	pub text: String,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Hunk grouping
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn clamp_u32(value: usize) -> u32 {
+	value.min(u32::MAX as usize) as u32
+}
+
+/// Parse an algorithm name; `None` selects Histogram.
+pub fn parse_algorithm(name: Option<&str>) -> Result<Algorithm> {
+	match name.map(str::trim) {
+		None | Some("" | "histogram") => Ok(Algorithm::Histogram),
+		Some("myers") => Ok(Algorithm::Myers),
+		Some("myersMinimal" | "minimal") => Ok(Algorithm::MyersMinimal),
+		Some(other) => Err(Error::from_reason(format!("Unknown diff algorithm: {other}"))),
+	}
+}
+
+/// Strip a trailing `\n` / `\r\n` from a line token.
+#[inline]
+pub fn trim_line_ending(line: &str) -> &str {
+	let line = line.strip_suffix('\n').unwrap_or(line);
+	line.strip_suffix('\r').unwrap_or(line)
+}
+
+/// Old and new line ranges of one change.
+pub type LineChange = (Range<usize>, Range<usize>);
+
+/// Compute the raw changed ranges between two texts, split into lines.
+///
+/// Returns the old lines, new lines, and `(old_range, new_range)` pairs in
+/// ascending order. Lines keep their terminators.
+pub fn changed_ranges<'a>(
+	old_text: &'a str,
+	new_text: &'a str,
+	algorithm: Algorithm,
+) -> (Vec<&'a str>, Vec<&'a str>, Vec<LineChange>) {
+	let input = InternedInput::new(old_text, new_text);
+	let mut diff = Diff::compute(algorithm, &input);
+	diff.postprocess_lines(&input);
+
+	let old_lines: Vec<&str> = input.before.iter().map(|&t| input.interner[t]).collect();
+	let new_lines: Vec<&str> = input.after.iter().map(|&t| input.interner[t]).collect();
+	let ranges = diff
+		.hunks()
+		.map(|h| {
+			(h.before.start as usize..h.before.end as usize, h.after.start as usize..h.after.end as usize)
+		})
+		.collect();
+	(old_lines, new_lines, ranges)
+}
+
+/// Group raw changed ranges into hunks with `context` lines around them.
+pub fn build_hunks(
+	old_lines: &[&str],
+	new_lines: &[&str],
+	ranges: &[LineChange],
+	context: usize,
+) -> Vec<DiffHunk> {
+	let mut hunks = Vec::new();
+	let mut i = 0usize;
+
+	while i < ranges.len() {
+		// Extend the group while the gap between changes fits in shared context.
+		let mut j = i;
+		while j + 1 < ranges.len()
+			&& ranges[j + 1].0.start.saturating_sub(ranges[j].0.end) <= context * 2
+		{
+			j += 1;
+		}
+
+		let (first_old, first_new) = &ranges[i];
+		let (last_old, last_new) = &ranges[j];
+		let lead = context.min(first_old.start).min(first_new.start);
+		let trail = context
+			.min(old_lines.len() - last_old.end)
+			.min(new_lines.len() - last_new.end);
+
+		let old_start = first_old.start - lead;
+		let new_start = first_new.start - lead;
+		let old_end = last_old.end + trail;
+		let new_end = last_new.end + trail;
+
+		let mut lines = Vec::with_capacity((old_end - old_start) + (new_end - new_start));
+		let mut old_pos = old_start;
+		let mut new_pos = new_start;
+		for (old_range, new_range) in &ranges[i..=j] {
+			while old_pos < old_range.start {
+				lines.push(context_line(old_lines, old_pos, new_pos));
+				old_pos += 1;
+				new_pos += 1;
+			}
+			for idx in old_range.clone() {
+				lines.push(DiffLine {
+					kind:     DiffLineKind::Removed,
+					content:  trim_line_ending(old_lines[idx]).to_string(),
+					old_line: Some(clamp_u32(idx + 1)),
+					new_line: None,
+				});
+			}
+			for idx in new_range.clone() {
+				lines.push(DiffLine {
+					kind:     DiffLineKind::Added,
+					content:  trim_line_ending(new_lines[idx]).to_string(),
+					old_line: None,
+					new_line: Some(clamp_u32(idx + 1)),
+				});
+			}
+			old_pos = old_range.end;
+			new_pos = new_range.end;
+		}
+		while old_pos < old_end {
+			lines.push(context_line(old_lines, old_pos, new_pos));
+			old_pos += 1;
+			new_pos += 1;
+		}
+
+		let old_count = old_end - old_start;
+		let new_count = new_end - new_start;
+		hunks.push(DiffHunk {
+			old_start: clamp_u32(if old_count == 0 { old_start } else { old_start + 1 }),
+			old_lines: clamp_u32(old_count),
+			new_start: clamp_u32(if new_count == 0 { new_start } else { new_start + 1 }),
+			new_lines: clamp_u32(new_count),
+			lines,
+		});
+		i = j + 1;
+	}
+
+	hunks
+}
+
+fn context_line(old_lines: &[&str], old_idx: usize, new_idx: usize) -> DiffLine {
+	DiffLine {
+		kind:     DiffLineKind::Context,
+		content:  trim_line_ending(old_lines[old_idx]).to_string(),
+		old_line: Some(clamp_u32(old_idx + 1)),
+		new_line: Some(clamp_u32(new_idx + 1)),
+	}
+}
+
+/// Diff two texts line by line and group the result into hunks.
+pub fn diff_texts(
+	old_text: &str,
+	new_text: &str,
+	algorithm: Algorithm,
+	context: usize,
+) -> DiffResult {
+	let (old_lines, new_lines, ranges) = changed_ranges(old_text, new_text, algorithm);
+	let removed = ranges.iter().map(|(old, _)| old.len()).sum();
+	let added = ranges.iter().map(|(_, new)| new.len()).sum();
+	let hunks = build_hunks(&old_lines, &new_lines, &ranges, context);
+	DiffResult { hunks, added: clamp_u32(added), removed: clamp_u32(removed) }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Intra-line refinement
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+	Word,
+	Space,
+	Other,
+}
+
+#[inline]
+fn char_class(ch: char) -> CharClass {
+	if ch.is_alphanumeric() || ch == '_' {
+		CharClass::Word
+	} else if ch.is_whitespace() {
+		CharClass::Space
+	} else {
+		CharClass::Other
+	}
+}
+
+/// Split a line into diff tokens and their UTF-16 end offsets.
+///
+/// Word mode groups identifier runs and whitespace runs; every other character
+/// is its own token. Char mode emits one token per character.
+fn tokenize_line(line: &str, by_char: bool) -> (Vec<&str>, Vec<u32>) {
+	let mut tokens = Vec::new();
+	let mut ends = Vec::new();
+	let mut utf16_pos = 0u32;
+	let mut start = 0usize;
+	let mut current: Option<CharClass> = None;
+
+	for (idx, ch) in line.char_indices() {
+		let class = char_class(ch);
+		let split = by_char || current != Some(class) || class == CharClass::Other;
+		if split && idx > start {
+			tokens.push(&line[start..idx]);
+			ends.push(utf16_pos);
+			start = idx;
+		}
+		current = Some(class);
+		utf16_pos += ch.len_utf16() as u32;
+	}
+	if start < line.len() {
+		tokens.push(&line[start..]);
+		ends.push(utf16_pos);
+	}
+	(tokens, ends)
+}
+
+fn push_changed_range(out: &mut Vec<ChangedRange>, ends: &[u32], tokens: Range<u32>) {
+	if tokens.is_empty() {
+		return;
+	}
+	let start = if tokens.start == 0 {
+		0
+	} else {
+		ends[tokens.start as usize - 1]
+	};
+	let end = ends[tokens.end as usize - 1];
+	match out.last_mut() {
+		Some(last) if last.end == start => last.end = end,
+		_ => out.push(ChangedRange { start, end }),
+	}
+}
+
+/// Diff two token sequences.
+fn diff_tokens(old_tokens: &[&str], new_tokens: &[&str]) -> Diff {
+	let mut interner = Interner::new(old_tokens.len() + new_tokens.len());
+	let before: Vec<Token> = old_tokens.iter().map(|&t| interner.intern(t)).collect();
+	let after: Vec<Token> = new_tokens.iter().map(|&t| interner.intern(t)).collect();
+
+	// Myers: histogram's heuristics degrade on small, highly repetitive tokens.
+	let mut diff = Diff::default();
+	diff.compute_with(Algorithm::Myers, &before, &after, interner.num_tokens());
+	diff
+}
+
+/// Compute changed spans between two versions of a single line.
+pub fn refine_line_pair(old_line: &str, new_line: &str, by_char: bool) -> LineRefinement {
+	let (old_tokens, old_ends) = tokenize_line(old_line, by_char);
+	let (new_tokens, new_ends) = tokenize_line(new_line, by_char);
+	let diff = diff_tokens(&old_tokens, &new_tokens);
+
+	let mut old_ranges = Vec::new();
+	let mut new_ranges = Vec::new();
+	for hunk in diff.hunks() {
+		push_changed_range(&mut old_ranges, &old_ends, hunk.before);
+		push_changed_range(&mut new_ranges, &new_ends, hunk.after);
+	}
+
+	let total = old_tokens.len() + new_tokens.len();
+	let changed = (diff.count_removals() + diff.count_additions()) as usize;
+	let similarity = if total == 0 {
+		1.0
+	} else {
+		(total - changed) as f64 / total as f64
+	};
+	LineRefinement { old_ranges, new_ranges, similarity }
+}
+
+fn push_word_part(parts: &mut Vec<WordDiffPart>, kind: DiffLineKind, tokens: &[&str]) {
+	if tokens.is_empty() {
+		return;
+	}
+	match parts.last_mut() {
+		Some(last) if last.kind == kind => last.text.extend(tokens.iter().copied()),
+		_ => parts.push(WordDiffPart { kind, text: tokens.concat() }),
+	}
+}
+
+/// Diff two texts word by word, using the word tokens of `refineHunk`.
+///
+/// Returns runs covering both texts in order: shared runs once, and removed
+/// runs before the added runs that replace them.
+pub fn diff_word_parts(old_text: &str, new_text: &str) -> Vec<WordDiffPart> {
+	let (old_tokens, _) = tokenize_line(old_text, false);
+	let (new_tokens, _) = tokenize_line(new_text, false);
+	let diff = diff_tokens(&old_tokens, &new_tokens);
+
+	let mut parts = Vec::new();
+	let mut old_pos = 0usize;
+	for hunk in diff.hunks() {
+		let before = hunk.before.start as usize..hunk.before.end as usize;
+		let after = hunk.after.start as usize..hunk.after.end as usize;
+		push_word_part(&mut parts, DiffLineKind::Context, &old_tokens[old_pos..before.start]);
+		push_word_part(&mut parts, DiffLineKind::Removed, &old_tokens[before.clone()]);
+		push_word_part(&mut parts, DiffLineKind::Added, &new_tokens[after]);
+		old_pos = before.end;
+	}
+	push_word_part(&mut parts, DiffLineKind::Context, &old_tokens[old_pos..]);
+	parts
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Diff two texts line by line.
+///
+/// # Arguments
+/// - `old_text`: Original text.
+/// - `new_text`: Updated text.
+/// - `options`: Algorithm, context-line, and cancellation settings.
+///
+/// # Returns
+/// Hunks with per-line kinds and line numbers, plus added/removed totals.
+///
+/// # Errors
+/// Returns an error when the algorithm name is unknown, or on cancellation or
+/// timeout.
+#[napi(js_name = "diffLines")]
+pub fn diff_lines(
+	old_text: String,
+	new_text: String,
+	options: Option<DiffOptions<'_>>,
+) -> Result<task::Async<DiffResult>> {
+	let DiffOptions { algorithm, context, signal, timeout_ms } = options.unwrap_or_default();
+	let algorithm = parse_algorithm(algorithm.as_deref())?;
+	let context = context.unwrap_or(DEFAULT_CONTEXT) as usize;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("diff_lines", ct, move |ct| {
+		ct.heartbeat()?;
+		let result = diff_texts(&old_text, &new_text, algorithm, context);
+		ct.heartbeat()?;
+		Ok(result)
+	}))
+}
+
+/// Refine replaced line pairs into word- or character-level changed ranges.
+///
+/// Lines are paired by index; extra lines on either side are whole-line
+/// additions/removals and get no refinement entry.
+///
+/// # Returns
+/// One refinement per paired line, with ranges in UTF-16 code units.
+///
+/// # Errors
+/// Returns an error when the granularity is unknown.
+#[napi(js_name = "refineHunk")]
+pub fn refine_hunk(
+	old_lines: Vec<String>,
+	new_lines: Vec<String>,
+	options: Option<RefineOptions>,
+) -> Result<Vec<LineRefinement>> {
+	let by_char = match options.unwrap_or_default().granularity.as_deref() {
+		None | Some("word") => false,
+		Some("char") => true,
+		Some(other) => return Err(Error::from_reason(format!("Unknown granularity: {other}"))),
+	};
+	Ok(old_lines
+		.iter()
+		.zip(&new_lines)
+		.map(|(old, new)| refine_line_pair(old, new, by_char))
+		.collect())
+}
+
+/// Diff two texts word by word.
+///
+/// Identifier runs and whitespace runs are single tokens; other characters
+/// are tokens of their own. Suited to short texts such as a replaced line or
+/// a paragraph; use `diffLines` for whole files.
+///
+/// # Returns
+/// Shared, removed, and added runs covering both texts in order.
+#[napi(js_name = "diffWords")]
+pub fn diff_words(old_text: String, new_text: String) -> Vec<WordDiffPart> {
+	diff_word_parts(&old_text, &new_text)
+}
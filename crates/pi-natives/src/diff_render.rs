@@ -0,0 +1,469 @@
+//! Styled, width-fitted rendering of diff hunks.
+//!
+//! # Overview
+//! `renderDiff` turns hunks from `diffLines` (or `gitDiff`) into terminal rows
+//! ready to paint: a line-number gutter, a `+`/`-` sign column, syntax
+//! highlighted content, and word-level emphasis on the changed parts of
+//! replaced line pairs. Every row is padded to exactly `width` cells so line
+//! backgrounds fill the pane; long lines are wrapped onto continuation rows or
+//! truncated with an ellipsis.
+//!
+//! Each hunk is highlighted twice, once along the old side and once along the
+//! new side, so multi-line constructs such as block comments and template
+//! strings stay correct on both sides of a change. Styles are raw SGR
+//! sequences; every style change resets and re-applies the row's base style,
+//! so backgrounds survive syntax colors and emphasis.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const { hunks } = native.diffLines(before, after)
+//! // JS: native.renderDiff(hunks, { width: 100, syntaxLanguage: "ts", colors: theme.syntax })
+//! // JS: // -> [{ text: "\x1b[0m…", kind: "header", ... }, { text, kind: "removed", oldLine: 12, ... }]
+//! ```
+
+use std::ops::Range;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+	diff::{self, DiffHunk, DiffLineKind},
+	highlight::{self, HighlightColors, LineHighlighter},
+	text,
+};
+
+const RESET: &str = "\x1b[0m";
+/// Replaced line pairs less similar than this are not emphasized word by
+/// word; the whole line is the change.
+const MIN_REFINE_SIMILARITY: f64 = 0.5;
+/// Palette index meaning "no syntax color".
+const NO_COLOR: usize = usize::MAX;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `renderDiff`.
+#[napi(object)]
+pub struct RenderDiffOptions {
+	/// Width of every rendered row in terminal cells.
+	pub width:            u32,
+	/// Language for syntax highlighting, e.g. "ts" or "rust" (default: none).
+	#[napi(js_name = "syntaxLanguage")]
+	pub syntax_language:  Option<String>,
+	/// Show old and new line numbers in a gutter (default: true).
+	#[napi(js_name = "lineNumbers")]
+	pub line_numbers:     Option<bool>,
+	/// Wrap long lines onto continuation rows instead of truncating them
+	/// (default: false).
+	pub wrap:             Option<bool>,
+	/// Emit a `@@ -a,b +c,d @@` row before each hunk (default: true).
+	#[napi(js_name = "hunkHeaders")]
+	pub hunk_headers:     Option<bool>,
+	/// Emphasize changed words within replaced line pairs (default: true).
+	pub refine:           Option<bool>,
+	/// Syntax colors (default: content is not syntax highlighted).
+	pub colors:           Option<HighlightColors>,
+	/// SGR style for added rows, e.g. a background (default: green text).
+	#[napi(js_name = "addedStyle")]
+	pub added_style:      Option<String>,
+	/// SGR style for removed rows (default: red text).
+	#[napi(js_name = "removedStyle")]
+	pub removed_style:    Option<String>,
+	/// SGR style for context rows (default: none).
+	#[napi(js_name = "contextStyle")]
+	pub context_style:    Option<String>,
+	/// SGR style added to changed words in added rows (default: bold).
+	#[napi(js_name = "addedEmphasis")]
+	pub added_emphasis:   Option<String>,
+	/// SGR style added to changed words in removed rows (default: bold).
+	#[napi(js_name = "removedEmphasis")]
+	pub removed_emphasis: Option<String>,
+	/// SGR style for the line-number gutter and hunk headers (default: dim).
+	#[napi(js_name = "gutterStyle")]
+	pub gutter_style:     Option<String>,
+}
+
+/// One rendered row.
+#[napi(object)]
+pub struct RenderedDiffRow {
+	/// Styled row text, exactly `width` cells wide.
+	pub text:         String,
+	/// "header", "context", "added", or "removed".
+	pub kind:         String,
+	/// Line number in the old text (unset for headers and added lines).
+	#[napi(js_name = "oldLine")]
+	pub old_line:     Option<u32>,
+	/// Line number in the new text (unset for headers and removed lines).
+	#[napi(js_name = "newLine")]
+	pub new_line:     Option<u32>,
+	/// Whether the row continues a wrapped line.
+	pub continuation: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Styling
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Resolved styles and layout settings.
+struct Layout<'a> {
+	palette:       Option<[&'a str; 11]>,
+	context:       &'a str,
+	added:         &'a str,
+	removed:       &'a str,
+	added_emph:    &'a str,
+	removed_emph:  &'a str,
+	gutter:        &'a str,
+	/// Digits per line-number column, or `None` without line numbers.
+	number_width:  Option<usize>,
+	/// Cells available for line content.
+	content_width: usize,
+	wrap:          bool,
+}
+
+impl Layout<'_> {
+	const fn base_style(&self, kind: DiffLineKind) -> &str {
+		match kind {
+			DiffLineKind::Context => self.context,
+			DiffLineKind::Added => self.added,
+			DiffLineKind::Removed => self.removed,
+		}
+	}
+
+	const fn emphasis_style(&self, kind: DiffLineKind) -> &str {
+		match kind {
+			DiffLineKind::Added => self.added_emph,
+			_ => self.removed_emph,
+		}
+	}
+
+	fn color(&self, index: usize) -> &str {
+		self
+			.palette
+			.and_then(|palette| palette.get(index).copied())
+			.unwrap_or("")
+	}
+
+	fn gutter_cells(&self) -> usize {
+		// "<old> <new> " then "<sign> ".
+		self.number_width.map_or(0, |digits| digits * 2 + 2) + 2
+	}
+}
+
+/// A grapheme with its style key: palette index and emphasis.
+struct Cell<'a> {
+	text:       &'a str,
+	width:      usize,
+	color:      usize,
+	emphasized: bool,
+}
+
+/// Convert UTF-16 ranges within `line` to byte ranges.
+fn byte_ranges(line: &str, ranges: &[diff::ChangedRange]) -> Vec<Range<usize>> {
+	let mut offsets = Vec::with_capacity(line.len() + 1);
+	for (byte, ch) in line.char_indices() {
+		for _ in 0..ch.len_utf16() {
+			offsets.push(byte);
+		}
+	}
+	offsets.push(line.len());
+	let at = |unit: u32| offsets.get(unit as usize).copied().unwrap_or(line.len());
+	ranges
+		.iter()
+		.map(|range| at(range.start)..at(range.end))
+		.collect()
+}
+
+/// Split a line into styled cells. Tabs become spaces and other control
+/// characters are dropped so rows keep their measured width.
+fn cells<'a>(
+	line: &'a str,
+	runs: &[(Range<usize>, usize)],
+	emphasis: &[Range<usize>],
+) -> Vec<Cell<'a>> {
+	let mut out = Vec::with_capacity(line.len());
+	let mut run = 0;
+	let mut emph = 0;
+	for (offset, grapheme) in line.grapheme_indices(true) {
+		while run < runs.len() && runs[run].0.end <= offset {
+			run += 1;
+		}
+		while emph < emphasis.len() && emphasis[emph].end <= offset {
+			emph += 1;
+		}
+		let color = runs.get(run).map_or(NO_COLOR, |(_, color)| *color);
+		let emphasized = emphasis
+			.get(emph)
+			.is_some_and(|range| range.start <= offset);
+		if grapheme == "\t" {
+			for _ in 0..text::TAB_WIDTH {
+				out.push(Cell { text: " ", width: 1, color, emphasized });
+			}
+			continue;
+		}
+		if grapheme.chars().all(char::is_control) {
+			continue;
+		}
+		out.push(Cell {
+			text: grapheme,
+			width: text::grapheme_width_str(grapheme),
+			color,
+			emphasized,
+		});
+	}
+	out
+}
+
+/// Break cells into rows of at most `width` cells: wrapped onto several rows,
+/// or cut to one row ending in an ellipsis.
+fn fit<'c, 'a>(cells: &'c [Cell<'a>], width: usize, wrap: bool) -> Vec<(&'c [Cell<'a>], bool)> {
+	let mut rows = Vec::new();
+	let mut start = 0;
+	let mut used = 0;
+	for (index, cell) in cells.iter().enumerate() {
+		if used + cell.width <= width || index == start {
+			used += cell.width;
+			continue;
+		}
+		if !wrap {
+			// Back off until the ellipsis fits too.
+			let mut end = index;
+			while end > start && used + 1 > width {
+				end -= 1;
+				used -= cells[end].width;
+			}
+			return vec![(&cells[start..end], true)];
+		}
+		rows.push((&cells[start..index], false));
+		start = index;
+		used = cell.width;
+	}
+	rows.push((&cells[start..], false));
+	rows
+}
+
+/// Append `cells` in `base` style, padded to `width` cells, with an optional
+/// trailing ellipsis.
+fn paint(
+	out: &mut String,
+	layout: &Layout<'_>,
+	kind: DiffLineKind,
+	cells: &[Cell<'_>],
+	ellipsis: bool,
+	width: usize,
+) {
+	let base = layout.base_style(kind);
+	let mut current = None;
+	let mut used = 0;
+	for cell in cells {
+		let key = (cell.color, cell.emphasized);
+		if current != Some(key) {
+			out.push_str(RESET);
+			out.push_str(base);
+			if cell.emphasized {
+				out.push_str(layout.emphasis_style(kind));
+			}
+			out.push_str(layout.color(cell.color));
+			current = Some(key);
+		}
+		out.push_str(cell.text);
+		used += cell.width;
+	}
+	out.push_str(RESET);
+	out.push_str(base);
+	if ellipsis {
+		out.push('…');
+		used += 1;
+	}
+	out.push_str(&" ".repeat(width.saturating_sub(used)));
+	out.push_str(RESET);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Rendering
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Changed-word byte ranges for each line of a hunk, from pairing every run
+/// of removed lines with the added lines that follow it.
+fn refine_hunk_lines(hunk: &DiffHunk) -> Vec<Vec<Range<usize>>> {
+	let lines = &hunk.lines;
+	let mut emphasis = vec![Vec::new(); lines.len()];
+	let mut index = 0;
+	while index < lines.len() {
+		let removed_start = index;
+		while index < lines.len() && lines[index].kind == DiffLineKind::Removed {
+			index += 1;
+		}
+		let added_start = index;
+		while index < lines.len() && lines[index].kind == DiffLineKind::Added {
+			index += 1;
+		}
+		if removed_start == added_start || added_start == index {
+			index = index.max(removed_start + 1);
+			continue;
+		}
+		for (old, new) in (removed_start..added_start).zip(added_start..index) {
+			let refinement = diff::refine_line_pair(&lines[old].content, &lines[new].content, false);
+			if refinement.similarity >= MIN_REFINE_SIMILARITY {
+				emphasis[old] = byte_ranges(&lines[old].content, &refinement.old_ranges);
+				emphasis[new] = byte_ranges(&lines[new].content, &refinement.new_ranges);
+			}
+		}
+	}
+	emphasis
+}
+
+fn render_header(hunk: &DiffHunk, layout: &Layout<'_>, width: usize) -> RenderedDiffRow {
+	let label = format!(
+		"@@ -{},{} +{},{} @@",
+		hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+	);
+	let header_cells = cells(&label, &[], &[]);
+	let (row, ellipsis) = fit(&header_cells, width, false).swap_remove(0);
+	let mut text = String::new();
+	let header = Layout { context: layout.gutter, ..*layout };
+	paint(&mut text, &header, DiffLineKind::Context, row, ellipsis, width);
+	RenderedDiffRow {
+		text,
+		kind: "header".to_string(),
+		old_line: None,
+		new_line: None,
+		continuation: false,
+	}
+}
+
+fn render_hunk(
+	hunk: &DiffHunk,
+	layout: &Layout<'_>,
+	language: Option<&str>,
+	refine: bool,
+	rows: &mut Vec<RenderedDiffRow>,
+) {
+	// Separate highlighters keep each side's parser state consistent.
+	let mut old_side = layout.palette.map(|_| LineHighlighter::new(language));
+	let mut new_side = layout.palette.map(|_| LineHighlighter::new(language));
+	let emphasis = if refine {
+		refine_hunk_lines(hunk)
+	} else {
+		vec![Vec::new(); hunk.lines.len()]
+	};
+
+	for (line, emphasis) in hunk.lines.iter().zip(&emphasis) {
+		// Syntaxes expect lines with terminators.
+		let terminated = format!("{}\n", line.content);
+		let highlight = |side: &mut Option<LineHighlighter>| {
+			side
+				.as_mut()
+				.map(|highlighter| highlighter.runs(&terminated))
+		};
+		let runs = match line.kind {
+			DiffLineKind::Context => {
+				highlight(&mut old_side);
+				highlight(&mut new_side)
+			},
+			DiffLineKind::Added => highlight(&mut new_side),
+			DiffLineKind::Removed => highlight(&mut old_side),
+		}
+		.unwrap_or_default();
+
+		let line_cells = cells(&line.content, &runs, emphasis);
+		let (sign, kind) = match line.kind {
+			DiffLineKind::Context => (' ', "context"),
+			DiffLineKind::Added => ('+', "added"),
+			DiffLineKind::Removed => ('-', "removed"),
+		};
+		let fitted = fit(&line_cells, layout.content_width, layout.wrap);
+		for (index, (row, ellipsis)) in fitted.into_iter().enumerate() {
+			let continuation = index > 0;
+			let mut text = String::new();
+			if let Some(digits) = layout.number_width {
+				let number = |value: Option<u32>| match value {
+					Some(value) if !continuation => format!("{value:>digits$}"),
+					_ => " ".repeat(digits),
+				};
+				text.push_str(RESET);
+				text.push_str(layout.gutter);
+				text.push_str(&number(line.old_line));
+				text.push(' ');
+				text.push_str(&number(line.new_line));
+				text.push(' ');
+			}
+			text.push_str(RESET);
+			text.push_str(layout.base_style(line.kind));
+			text.push(if continuation { ' ' } else { sign });
+			text.push(' ');
+			paint(&mut text, layout, line.kind, row, ellipsis, layout.content_width);
+			rows.push(RenderedDiffRow {
+				text,
+				kind: kind.to_string(),
+				old_line: line.old_line,
+				new_line: line.new_line,
+				continuation,
+			});
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Render diff hunks as styled terminal rows of a fixed width.
+///
+/// # Arguments
+/// - `hunks`: Hunks from `diffLines` or `gitDiff`.
+/// - `options`: Width, highlighting language and colors, gutter, wrapping, and
+///   row styles.
+///
+/// # Returns
+/// Rows in display order, each exactly `width` cells wide.
+///
+/// # Errors
+/// Returns an error when `width` leaves no room for content after the gutter.
+#[napi(js_name = "renderDiff")]
+pub fn render_diff(
+	hunks: Vec<DiffHunk>,
+	options: RenderDiffOptions,
+) -> Result<Vec<RenderedDiffRow>> {
+	let width = options.width as usize;
+	let number_width = options.line_numbers.unwrap_or(true).then(|| {
+		let max = hunks
+			.iter()
+			.map(|hunk| (hunk.old_start + hunk.old_lines).max(hunk.new_start + hunk.new_lines))
+			.max()
+			.unwrap_or(1);
+		max.max(1).ilog10() as usize + 1
+	});
+	let mut layout = Layout {
+		palette: options.colors.as_ref().map(highlight::palette),
+		context: options.context_style.as_deref().unwrap_or(""),
+		added: options.added_style.as_deref().unwrap_or("\x1b[32m"),
+		removed: options.removed_style.as_deref().unwrap_or("\x1b[31m"),
+		added_emph: options.added_emphasis.as_deref().unwrap_or("\x1b[1m"),
+		removed_emph: options.removed_emphasis.as_deref().unwrap_or("\x1b[1m"),
+		gutter: options.gutter_style.as_deref().unwrap_or("\x1b[2m"),
+		number_width,
+		content_width: 0,
+		wrap: options.wrap.unwrap_or(false),
+	};
+	let gutter = layout.gutter_cells();
+	if width <= gutter {
+		return Err(Error::from_reason(format!(
+			"Width {width} leaves no room for content after the {gutter}-cell gutter"
+		)));
+	}
+	layout.content_width = width - gutter;
+
+	let language = options.syntax_language.as_deref();
+	let refine = options.refine.unwrap_or(true);
+	let headers = options.hunk_headers.unwrap_or(true);
+	let mut rows = Vec::new();
+	for hunk in &hunks {
+		if headers {
+			rows.push(render_header(hunk, &layout, width));
+		}
+		render_hunk(hunk, &layout, language, refine, &mut rows);
+	}
+	Ok(rows)
+}
@@ -0,0 +1,370 @@
+//! Validated string-replacement file edits.
+//!
+//! # Overview
+//! `applyEdit` replaces an exact span of text in a file: `oldText` must occur
+//! exactly once, or `occurrence` picks one of several matches, or
+//! `replaceAll` rewrites every match. The file's dominant line ending is
+//! applied to both texts first, so LF edits work on CRLF files. The file is
+//! rewritten atomically and the change is returned as line hunks.
+//!
+//! When `oldText` is missing, the result lists the regions of the file that
+//! resemble it most. Regions are scored line by line with a bigram Dice
+//! coefficient over whitespace-stripped text, so stale indentation or a
+//! renamed identifier still points at the right place.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.applyEdit("src/app.ts", { oldText: "let x = 1;", newText: "let x = 2;" })
+//! // JS: // -> { status: "applied", matches: 1, replacements: 1, hunks: [...], candidates: [] }
+//! ```
+
+use std::{fs, path::Path};
+
+use imara_diff::Algorithm;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::{binary, diff, eol, file_filter, task, write};
+
+/// Maximum number of near-miss regions reported.
+const MAX_CANDIDATES: usize = 3;
+/// Regions scoring below this similarity are not reported.
+const MIN_SIMILARITY: f64 = 0.5;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `applyEdit`.
+#[napi(object)]
+pub struct ApplyEditOptions {
+	/// Exact text to replace.
+	#[napi(js_name = "oldText")]
+	pub old_text:    String,
+	/// Replacement text.
+	#[napi(js_name = "newText")]
+	pub new_text:    String,
+	/// Replace only this match, 1-indexed (default: `oldText` must be
+	/// unique).
+	pub occurrence:  Option<u32>,
+	/// Replace every match (default: false).
+	#[napi(js_name = "replaceAll")]
+	pub replace_all: Option<bool>,
+	/// Compute the result without writing the file (default: false).
+	#[napi(js_name = "dryRun")]
+	pub dry_run:     Option<bool>,
+	/// Unchanged context lines around each hunk (default: 3).
+	pub context:     Option<u32>,
+}
+
+/// A region of the file resembling `oldText`.
+#[napi(object)]
+pub struct EditCandidate {
+	/// First line of the region, 1-indexed.
+	#[napi(js_name = "startLine")]
+	pub start_line: u32,
+	/// Last line of the region, 1-indexed and inclusive.
+	#[napi(js_name = "endLine")]
+	pub end_line:   u32,
+	/// Text of the region.
+	pub text:       String,
+	/// Similarity to `oldText`, from 0 to 1.
+	pub similarity: f64,
+}
+
+/// Result of `applyEdit`.
+#[napi(object)]
+pub struct ApplyEditResult {
+	/// "applied", "dryRun", "notFound" (no such text or occurrence), or
+	/// "ambiguous" (several matches and no `occurrence`).
+	pub status:       String,
+	/// Number of matches of `oldText` in the file.
+	pub matches:      u32,
+	/// Number of matches replaced (or that would be, for dry runs).
+	pub replacements: u32,
+	/// Line hunks describing the change; empty unless applied or dry run.
+	pub hunks:        Vec<diff::DiffHunk>,
+	/// Number of added lines.
+	pub added:        u32,
+	/// Number of removed lines.
+	pub removed:      u32,
+	/// Closest regions when `oldText` was not found, best first.
+	pub candidates:   Vec<EditCandidate>,
+}
+
+impl ApplyEditResult {
+	fn unapplied(status: &str, matches: usize, candidates: Vec<EditCandidate>) -> Self {
+		Self {
+			status: status.to_string(),
+			matches: clamp_u32(matches),
+			replacements: 0,
+			hunks: Vec::new(),
+			added: 0,
+			removed: 0,
+			candidates,
+		}
+	}
+}
+
+fn clamp_u32(value: usize) -> u32 {
+	u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Near-miss search
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Sorted character bigrams of a line with all whitespace removed.
+fn bigrams(line: &str) -> Vec<(char, char)> {
+	let chars: Vec<char> = line.chars().filter(|ch| !ch.is_whitespace()).collect();
+	let mut grams: Vec<(char, char)> = chars.windows(2).map(|pair| (pair[0], pair[1])).collect();
+	if grams.is_empty()
+		&& let Some(&ch) = chars.first()
+	{
+		grams.push((ch, ch));
+	}
+	grams.sort_unstable();
+	grams
+}
+
+/// Dice coefficient of two sorted bigram multisets.
+fn dice(a: &[(char, char)], b: &[(char, char)]) -> f64 {
+	if a.is_empty() && b.is_empty() {
+		return 1.0;
+	}
+	let (mut i, mut j, mut common) = (0, 0, 0usize);
+	while i < a.len() && j < b.len() {
+		match a[i].cmp(&b[j]) {
+			std::cmp::Ordering::Less => i += 1,
+			std::cmp::Ordering::Greater => j += 1,
+			std::cmp::Ordering::Equal => {
+				common += 1;
+				i += 1;
+				j += 1;
+			},
+		}
+	}
+	(2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+/// Find the non-overlapping line windows most similar to `needle`.
+fn closest_regions(content: &str, needle: &str) -> Vec<EditCandidate> {
+	let lines: Vec<&str> = content.lines().collect();
+	let wanted: Vec<Vec<(char, char)>> = needle.lines().map(bigrams).collect();
+	if lines.is_empty() || wanted.is_empty() {
+		return Vec::new();
+	}
+	let have: Vec<Vec<(char, char)>> = lines.par_iter().map(|line| bigrams(line)).collect();
+	let span = wanted.len().min(lines.len());
+	let mut scored: Vec<(usize, f64)> = (0..=lines.len() - span)
+		.into_par_iter()
+		.map(|start| {
+			let total: f64 = wanted
+				.iter()
+				.zip(&have[start..start + span])
+				.map(|(want, got)| dice(want, got))
+				.sum();
+			(start, total / wanted.len() as f64)
+		})
+		.filter(|&(_, score)| score >= MIN_SIMILARITY)
+		.collect();
+	scored.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+	let mut picked: Vec<(usize, f64)> = Vec::new();
+	for (start, score) in scored {
+		if picked.len() == MAX_CANDIDATES {
+			break;
+		}
+		if picked
+			.iter()
+			.all(|&(other, _)| start + span <= other || other + span <= start)
+		{
+			picked.push((start, score));
+		}
+	}
+	picked
+		.into_iter()
+		.map(|(start, score)| EditCandidate {
+			start_line: clamp_u32(start + 1),
+			end_line:   clamp_u32(start + span),
+			text:       lines[start..start + span].join("\n"),
+			similarity: (score * 1000.0).round() / 1000.0,
+		})
+		.collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Editing
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn run_edit(path: &Path, options: &ApplyEditOptions) -> Result<ApplyEditResult> {
+	if options.old_text.is_empty() {
+		return Err(Error::from_reason("oldText must not be empty"));
+	}
+	let bytes = fs::read(path)
+		.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+	if binary::is_binary(&bytes) {
+		return Err(Error::from_reason(format!("{} is a binary file", path.display())));
+	}
+	let content = String::from_utf8(bytes)
+		.map_err(|_| Error::from_reason(format!("{} is not valid UTF-8", path.display())))?;
+	let old_text = eol::preserve_eol(&content, &options.old_text);
+	let new_text = eol::preserve_eol(&content, &options.new_text);
+
+	let starts: Vec<usize> = content
+		.match_indices(old_text.as_ref())
+		.map(|(start, _)| start)
+		.collect();
+	if starts.is_empty() {
+		return Ok(ApplyEditResult::unapplied(
+			"notFound",
+			0,
+			closest_regions(&content, &options.old_text),
+		));
+	}
+	let selected: Vec<usize> = if options.replace_all.unwrap_or(false) {
+		starts.clone()
+	} else {
+		match options.occurrence {
+			Some(nth) => match starts.get((nth as usize).wrapping_sub(1)) {
+				Some(&start) => vec![start],
+				None => return Ok(ApplyEditResult::unapplied("notFound", starts.len(), Vec::new())),
+			},
+			None if starts.len() > 1 => {
+				return Ok(ApplyEditResult::unapplied("ambiguous", starts.len(), Vec::new()));
+			},
+			None => starts.clone(),
+		}
+	};
+
+	let mut updated = String::with_capacity(content.len() + new_text.len() * selected.len());
+	let mut cursor = 0;
+	for &start in &selected {
+		updated.push_str(&content[cursor..start]);
+		updated.push_str(&new_text);
+		cursor = start + old_text.len();
+	}
+	updated.push_str(&content[cursor..]);
+
+	let context = options.context.unwrap_or(3) as usize;
+	let result = diff::diff_texts(&content, &updated, Algorithm::Histogram, context);
+	let dry_run = options.dry_run.unwrap_or(false);
+	if !dry_run && updated != content {
+		write::write_atomic(path, updated.as_bytes(), &write::AtomicWrite::default())?;
+	}
+	Ok(ApplyEditResult {
+		status:       if dry_run { "dryRun" } else { "applied" }.to_string(),
+		matches:      clamp_u32(starts.len()),
+		replacements: clamp_u32(selected.len()),
+		hunks:        result.hunks,
+		added:        result.added,
+		removed:      result.removed,
+		candidates:   Vec::new(),
+	})
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Replace exact text in a file after verifying it matches.
+///
+/// # Arguments
+/// - `path`: File to edit.
+/// - `options`: Text to replace, replacement, and match selection.
+///
+/// # Returns
+/// The outcome with line hunks, or the closest regions when `oldText` is
+/// missing. A missing or ambiguous match leaves the file untouched.
+///
+/// # Errors
+/// Returns an error when `oldText` is empty or the file cannot be read, is
+/// binary or not UTF-8, or cannot be written.
+#[napi(js_name = "applyEdit")]
+pub fn apply_edit(path: String, options: ApplyEditOptions) -> task::Async<ApplyEditResult> {
+	task::blocking("edit.apply", (), move |_| {
+		let path = file_filter::resolve_search_path(&path)?;
+		run_edit(&path, &options)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::path::PathBuf;
+
+	use super::*;
+	use crate::test_util::ScratchDir;
+
+	/// `file.txt` holding `content`, in a directory removed with the guard.
+	fn scratch(name: &str, content: &str) -> (ScratchDir, PathBuf) {
+		let root = ScratchDir::new(&format!("edit-{name}"));
+		let path = root.join("file.txt");
+		fs::write(&path, content).unwrap();
+		(root, path)
+	}
+
+	fn edit(old_text: &str, new_text: &str) -> ApplyEditOptions {
+		ApplyEditOptions {
+			old_text:    old_text.to_string(),
+			new_text:    new_text.to_string(),
+			occurrence:  None,
+			replace_all: None,
+			dry_run:     None,
+			context:     None,
+		}
+	}
+
+	#[test]
+	fn test_edit_applies_lf_text_to_crlf_file() {
+		let (_root, path) = scratch("crlf", "fn a() {\r\n\tlet x = 1;\r\n}\r\n");
+		let result = run_edit(&path, &edit("\tlet x = 1;\n}", "\tlet x = 2;\n\tx\n}")).unwrap();
+		assert_eq!(result.status, "applied");
+		assert_eq!((result.added, result.removed), (2, 1));
+		assert_eq!(fs::read_to_string(&path).unwrap(), "fn a() {\r\n\tlet x = 2;\r\n\tx\r\n}\r\n");
+	}
+
+	#[test]
+	fn test_edit_selects_among_repeated_matches() {
+		let content = "x = 1\ny = 2\nx = 1\n";
+		let (_root, path) = scratch("repeated", content);
+
+		let result = run_edit(&path, &edit("x = 1", "x = 3")).unwrap();
+		assert_eq!((result.status.as_str(), result.matches), ("ambiguous", 2));
+		assert_eq!(fs::read_to_string(&path).unwrap(), content);
+
+		let options = ApplyEditOptions { occurrence: Some(3), ..edit("x = 1", "x = 3") };
+		assert_eq!(run_edit(&path, &options).unwrap().status, "notFound");
+
+		let options = ApplyEditOptions { occurrence: Some(2), ..edit("x = 1", "x = 3") };
+		assert_eq!(run_edit(&path, &options).unwrap().replacements, 1);
+		assert_eq!(fs::read_to_string(&path).unwrap(), "x = 1\ny = 2\nx = 3\n");
+
+		let options = ApplyEditOptions { replace_all: Some(true), ..edit("= ", "== ") };
+		assert_eq!(run_edit(&path, &options).unwrap().replacements, 3);
+		assert_eq!(fs::read_to_string(&path).unwrap(), "x == 1\ny == 2\nx == 3\n");
+	}
+
+	#[test]
+	fn test_edit_dry_run_leaves_file_untouched() {
+		let (_root, path) = scratch("dry", "a\nb\n");
+		let options = ApplyEditOptions { dry_run: Some(true), ..edit("b", "c") };
+		let result = run_edit(&path, &options).unwrap();
+		assert_eq!(result.status, "dryRun");
+		assert_eq!(result.hunks.len(), 1);
+		assert_eq!(fs::read_to_string(&path).unwrap(), "a\nb\n");
+	}
+
+	#[test]
+	fn test_edit_not_found_suggests_closest_region() {
+		let content = "fn main() {\n    let total = compute(a, b);\n    println!(\"{total}\");\n}\n";
+		let (_root, path) = scratch("missing", content);
+		let result = run_edit(&path, &edit("\tlet total = compute(a, c);\n", "")).unwrap();
+		assert_eq!((result.status.as_str(), result.matches), ("notFound", 0));
+		let best = &result.candidates[0];
+		assert_eq!((best.start_line, best.end_line), (2, 2));
+		assert_eq!(best.text, "    let total = compute(a, b);");
+		assert!(best.similarity > 0.9 && best.similarity < 1.0);
+		assert_eq!(fs::read_to_string(&path).unwrap(), content);
+	}
+}
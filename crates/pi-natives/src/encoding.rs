@@ -0,0 +1,191 @@
+//! Text encoding detection and transcoding to UTF-8.
+//!
+//! # Overview
+//! Detects UTF-8, UTF-16LE/BE, and Latin-1 content from byte-order marks or,
+//! without one, from the distribution of NUL bytes and UTF-8 validity of a
+//! leading sample. Decoding strips the BOM and converts to UTF-8, replacing
+//! invalid sequences with U+FFFD.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.detectEncoding(buffer) // -> { encoding: "utf-16le", bom: true }
+//! // JS: const { text, encoding } = await native.readFileText("build.log", { transcode: true })
+//! ```
+
+use std::path::PathBuf;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+/// Bytes inspected when guessing an encoding without a BOM.
+const SAMPLE_SIZE: usize = 64 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detected encoding of a byte buffer.
+#[napi(object)]
+pub struct DetectedEncoding {
+	/// `"utf-8"`, `"utf-16le"`, `"utf-16be"`, or `"latin1"`.
+	pub encoding: String,
+	/// Whether the content starts with a byte-order mark.
+	pub bom:      bool,
+}
+
+/// Options for `readFileText`.
+#[napi(object)]
+pub struct ReadFileTextOptions<'env> {
+	/// Convert detected non-UTF-8 content to UTF-8 (default: true). When
+	/// false, content is decoded as UTF-8 with invalid sequences replaced.
+	pub transcode:  Option<bool>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Decoded file contents.
+#[napi(object)]
+pub struct FileText {
+	/// File contents as UTF-8, without a BOM.
+	pub text:     String,
+	/// Encoding detected on disk.
+	pub encoding: String,
+	/// Whether the file started with a byte-order mark.
+	pub bom:      bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Detection
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Text encoding recognized by [`detect`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+	Utf8,
+	Utf16Le,
+	Utf16Be,
+	Latin1,
+}
+
+impl Encoding {
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Utf8 => "utf-8",
+			Self::Utf16Le => "utf-16le",
+			Self::Utf16Be => "utf-16be",
+			Self::Latin1 => "latin1",
+		}
+	}
+}
+
+/// Detect the encoding of `bytes`, returning it with the BOM length.
+pub fn detect(bytes: &[u8]) -> (Encoding, usize) {
+	if bytes.starts_with(&[0xef, 0xbb, 0xbf]) {
+		return (Encoding::Utf8, 3);
+	}
+	if bytes.starts_with(&[0xff, 0xfe]) {
+		return (Encoding::Utf16Le, 2);
+	}
+	if bytes.starts_with(&[0xfe, 0xff]) {
+		return (Encoding::Utf16Be, 2);
+	}
+
+	let sample = &bytes[..bytes.len().min(SAMPLE_SIZE)];
+	// BOM-less UTF-16 text is mostly ASCII, so one byte of each unit is NUL.
+	let (mut even_nuls, mut odd_nuls) = (0usize, 0usize);
+	for pair in sample.as_chunks::<2>().0 {
+		even_nuls += usize::from(pair[0] == 0);
+		odd_nuls += usize::from(pair[1] == 0);
+	}
+	let units = sample.len() / 2;
+	if units > 0 {
+		if odd_nuls * 10 >= units * 3 && even_nuls * 10 < units {
+			return (Encoding::Utf16Le, 0);
+		}
+		if even_nuls * 10 >= units * 3 && odd_nuls * 10 < units {
+			return (Encoding::Utf16Be, 0);
+		}
+	}
+
+	match std::str::from_utf8(sample) {
+		Ok(_) => (Encoding::Utf8, 0),
+		// A multi-byte sequence cut off by the sample boundary is still UTF-8.
+		Err(err) if err.error_len().is_none() && sample.len() < bytes.len() => (Encoding::Utf8, 0),
+		Err(_) => (Encoding::Latin1, 0),
+	}
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> String {
+	let units = bytes.as_chunks::<2>().0.iter().map(|&pair| to_unit(pair));
+	let mut text: String = char::decode_utf16(units)
+		.map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+		.collect();
+	if bytes.len() % 2 == 1 {
+		text.push(char::REPLACEMENT_CHARACTER);
+	}
+	text
+}
+
+fn decode(bytes: Vec<u8>, encoding: Encoding, bom_len: usize) -> String {
+	match encoding {
+		Encoding::Utf8 => match String::from_utf8(bytes) {
+			Ok(mut text) => {
+				text.drain(..bom_len);
+				text
+			},
+			Err(err) => String::from_utf8_lossy(&err.as_bytes()[bom_len..]).into_owned(),
+		},
+		Encoding::Utf16Le => decode_utf16(&bytes[bom_len..], u16::from_le_bytes),
+		Encoding::Utf16Be => decode_utf16(&bytes[bom_len..], u16::from_be_bytes),
+		Encoding::Latin1 => bytes[bom_len..].iter().map(|&b| char::from(b)).collect(),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect the text encoding of a buffer.
+#[napi(js_name = "detectEncoding")]
+pub fn detect_encoding(buffer: Uint8Array) -> DetectedEncoding {
+	let (encoding, bom_len) = detect(buffer.as_ref());
+	DetectedEncoding { encoding: encoding.name().to_string(), bom: bom_len > 0 }
+}
+
+/// Read a file as text, detecting its encoding and converting it to UTF-8.
+///
+/// # Returns
+/// The decoded text with the encoding detected on disk.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or the operation is cancelled.
+#[napi(js_name = "readFileText")]
+pub fn read_file_text(
+	path: String,
+	options: Option<ReadFileTextOptions<'_>>,
+) -> task::Async<FileText> {
+	let (transcode, signal, timeout_ms) = match options {
+		Some(opts) => (opts.transcode, opts.signal, opts.timeout_ms),
+		None => (None, None, None),
+	};
+	let transcode = transcode.unwrap_or(true);
+	let path = PathBuf::from(path);
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("read_file_text", ct, move |ct| {
+		let bytes = std::fs::read(&path)
+			.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+		ct.heartbeat()?;
+		let (encoding, bom_len) = detect(&bytes);
+		let text = if transcode || encoding == Encoding::Utf8 {
+			decode(bytes, encoding, bom_len)
+		} else {
+			String::from_utf8_lossy(&bytes).into_owned()
+		};
+		Ok(FileText { text, encoding: encoding.name().to_string(), bom: bom_len > 0 })
+	})
+}
@@ -0,0 +1,374 @@
+//! Environment snapshots, diffs, and shell export parsing.
+//!
+//! # Overview
+//! Lets the shell tool persist environment changes across tool calls: take a
+//! snapshot before running a script, read the exported variables after it
+//! (the output of `export -p`), and diff the two to get the variables the
+//! script added, changed, or removed.
+//!
+//! `parseShellExports` reads `export -p` output from bash (`declare -x`),
+//! dash, and zsh, as well as plain `NAME=value` lines. Values are unquoted
+//! with shell rules (single, double, and `$'...'` quotes, backslash escapes),
+//! so multi-line values survive. Statements that do not export a value, such
+//! as arrays or names exported without one, are ignored.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const before = native.snapshotEnv();
+//! // JS: const out = await shell.run({ command: `source ./env.sh >/dev/null; export -p` });
+//! // JS: const diff = native.diffEnv(before, native.parseShellExports(out.output));
+//! // JS: // -> { added: [{ name: "VIRTUAL_ENV", value: "/repo/.venv" }], changed: [...], removed: [] }
+//! ```
+
+use std::{collections::HashMap, iter::Peekable, str::Chars};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Variables shells update on their own, ignored by default when diffing.
+const DEFAULT_IGNORE: &[&str] = &["_", "SHLVL"];
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `diffEnv`.
+#[napi(object)]
+pub struct DiffEnvOptions {
+	/// Variable names to leave out of the diff (default: `_` and `SHLVL`).
+	pub ignore: Option<Vec<String>>,
+}
+
+/// A variable present only in the later environment.
+#[napi(object)]
+pub struct EnvVar {
+	/// Variable name.
+	pub name:  String,
+	/// Value in the later environment.
+	pub value: String,
+}
+
+/// A variable whose value differs between the two environments.
+#[napi(object)]
+pub struct EnvChange {
+	/// Variable name.
+	pub name:   String,
+	/// Value in the earlier environment.
+	pub before: String,
+	/// Value in the later environment.
+	pub after:  String,
+}
+
+/// Differences between two environments, each list sorted by name.
+#[napi(object)]
+pub struct EnvDiff {
+	/// Variables set only in `after`.
+	pub added:   Vec<EnvVar>,
+	/// Variables set in both with different values.
+	pub changed: Vec<EnvChange>,
+	/// Names of variables set only in `before`.
+	pub removed: Vec<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Diffing
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn diff(
+	before: &HashMap<String, String>,
+	after: &HashMap<String, String>,
+	ignore: &[String],
+) -> EnvDiff {
+	let ignored = |name: &str| ignore.iter().any(|ignored| ignored == name);
+	let mut added = Vec::new();
+	let mut changed = Vec::new();
+	for (name, value) in after {
+		if ignored(name) {
+			continue;
+		}
+		match before.get(name) {
+			None => added.push(EnvVar { name: name.clone(), value: value.clone() }),
+			Some(old) if old != value => changed.push(EnvChange {
+				name:   name.clone(),
+				before: old.clone(),
+				after:  value.clone(),
+			}),
+			Some(_) => {},
+		}
+	}
+	let mut removed: Vec<String> = before
+		.keys()
+		.filter(|name| !ignored(name) && !after.contains_key(*name))
+		.cloned()
+		.collect();
+	added.sort_by(|a, b| a.name.cmp(&b.name));
+	changed.sort_by(|a, b| a.name.cmp(&b.name));
+	removed.sort();
+	EnvDiff { added, changed, removed }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Parsing
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn unterminated(quote: &str) -> Error {
+	Error::from_reason(format!("Unterminated {quote} quote in shell exports"))
+}
+
+/// Decode the body of a `$'...'` quote, after the opening quote. Octal and
+/// `\x` escapes are bytes (bash writes non-ASCII as octal UTF-8), so the
+/// result is decoded as UTF-8 at the end.
+fn ansi_c_quote(chars: &mut Peekable<Chars<'_>>, word: &mut String) -> Result<()> {
+	let push = |bytes: &mut Vec<u8>, ch: char| {
+		bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+	};
+	let mut bytes = Vec::new();
+	loop {
+		let ch = chars.next().ok_or_else(|| unterminated("$'"))?;
+		if ch == '\'' {
+			break;
+		}
+		if ch != '\\' {
+			push(&mut bytes, ch);
+			continue;
+		}
+		let escape = chars.next().ok_or_else(|| unterminated("$'"))?;
+		let byte = match escape {
+			'a' => 0x07,
+			'b' => 0x08,
+			'e' | 'E' => 0x1b,
+			'f' => 0x0c,
+			'n' => b'\n',
+			'r' => b'\r',
+			't' => b'\t',
+			'v' => 0x0b,
+			'\\' | '\'' | '"' | '?' => escape as u8,
+			'0'..='7' => {
+				let mut code = escape.to_digit(8).unwrap_or_default();
+				for _ in 0..2 {
+					let Some(digit) = chars.peek().and_then(|ch| ch.to_digit(8)) else {
+						break;
+					};
+					code = code * 8 + digit;
+					chars.next();
+				}
+				code as u8
+			},
+			'x' | 'u' | 'U' => {
+				let max_digits = match escape {
+					'x' => 2,
+					'u' => 4,
+					_ => 8,
+				};
+				let mut code = 0;
+				let mut digits = 0;
+				while digits < max_digits
+					&& let Some(digit) = chars.peek().and_then(|ch| ch.to_digit(16))
+				{
+					code = code * 16 + digit;
+					chars.next();
+					digits += 1;
+				}
+				if digits == 0 {
+					bytes.push(b'\\');
+					push(&mut bytes, escape);
+				} else if escape == 'x' {
+					bytes.push(code as u8);
+				} else {
+					push(&mut bytes, char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+				}
+				continue;
+			},
+			_ => {
+				bytes.push(b'\\');
+				push(&mut bytes, escape);
+				continue;
+			},
+		};
+		bytes.push(byte);
+	}
+	word.push_str(&String::from_utf8_lossy(&bytes));
+	Ok(())
+}
+
+/// Split `text` into statements of unquoted words, ending statements at
+/// unquoted newlines and semicolons.
+fn statements(text: &str) -> Result<Vec<Vec<String>>> {
+	let mut statements = Vec::new();
+	let mut words = Vec::new();
+	let mut word = String::new();
+	// Distinguishes an empty quoted word (`''`) from no word at all.
+	let mut in_word = false;
+	let mut chars = text.chars().peekable();
+	while let Some(ch) = chars.next() {
+		match ch {
+			' ' | '\t' | '\r' | '\n' | ';' => {
+				if in_word {
+					words.push(std::mem::take(&mut word));
+					in_word = false;
+				}
+				if matches!(ch, '\n' | ';') && !words.is_empty() {
+					statements.push(std::mem::take(&mut words));
+				}
+			},
+			'#' if !in_word => while chars.next_if(|&ch| ch != '\n').is_some() {},
+			'\\' => match chars.next() {
+				Some('\n') | None => {},
+				Some(next) => {
+					word.push(next);
+					in_word = true;
+				},
+			},
+			'\'' => {
+				in_word = true;
+				loop {
+					match chars.next().ok_or_else(|| unterminated("single"))? {
+						'\'' => break,
+						ch => word.push(ch),
+					}
+				}
+			},
+			'"' => {
+				in_word = true;
+				loop {
+					match chars.next().ok_or_else(|| unterminated("double"))? {
+						'"' => break,
+						'\\' => match chars.next().ok_or_else(|| unterminated("double"))? {
+							'\n' => {},
+							next @ ('$' | '`' | '"' | '\\') => word.push(next),
+							next => {
+								word.push('\\');
+								word.push(next);
+							},
+						},
+						ch => word.push(ch),
+					}
+				}
+			},
+			'$' if chars.next_if_eq(&'\'').is_some() => {
+				in_word = true;
+				ansi_c_quote(&mut chars, &mut word)?;
+			},
+			ch => {
+				word.push(ch);
+				in_word = true;
+			},
+		}
+	}
+	if in_word {
+		words.push(word);
+	}
+	if !words.is_empty() {
+		statements.push(words);
+	}
+	Ok(statements)
+}
+
+/// Split `NAME=value`, requiring a valid shell variable name.
+fn assignment(word: &str) -> Option<(&str, &str)> {
+	let (name, value) = word.split_once('=')?;
+	let mut chars = name.chars();
+	let valid = chars
+		.next()
+		.is_some_and(|ch| ch.is_ascii_alphabetic() || ch == '_')
+		&& chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+	valid.then_some((name, value))
+}
+
+/// Assignments a statement exports, or none if it exports nothing.
+fn exported(words: &[String]) -> Vec<(&str, &str)> {
+	let (command, rest) = match words.split_first() {
+		Some((command, rest)) if matches!(command.as_str(), "export" | "declare" | "typeset") => {
+			(command.as_str(), rest)
+		},
+		// Plain `NAME=value` lines, unless they prefix a command.
+		_ => {
+			let assignments: Vec<_> = words.iter().filter_map(|word| assignment(word)).collect();
+			return if assignments.len() == words.len() {
+				assignments
+			} else {
+				Vec::new()
+			};
+		},
+	};
+	let flags: String = rest
+		.iter()
+		.take_while(|word| word.starts_with('-'))
+		.flat_map(|word| word.chars().skip(1))
+		.collect();
+	// Arrays are never exported; `export -n` unexports; `declare` without
+	// `-x` only sets a shell variable.
+	if flags.contains(['a', 'A', 'n', 'f', 'F']) || (command != "export" && !flags.contains('x')) {
+		return Vec::new();
+	}
+	rest
+		.iter()
+		.skip_while(|word| word.starts_with('-'))
+		.filter_map(|word| assignment(word))
+		.collect()
+}
+
+fn parse_exports(text: &str) -> Result<HashMap<String, String>> {
+	let mut vars = HashMap::new();
+	for words in statements(text)? {
+		for (name, value) in exported(&words) {
+			vars.insert(name.to_string(), value.to_string());
+		}
+	}
+	Ok(vars)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Capture the environment of the current process.
+///
+/// # Returns
+/// Variable names mapped to values; values that are not valid UTF-8 are
+/// converted lossily.
+#[napi(js_name = "snapshotEnv")]
+pub fn snapshot_env() -> HashMap<String, String> {
+	std::env::vars_os()
+		.map(|(name, value)| {
+			(name.to_string_lossy().into_owned(), value.to_string_lossy().into_owned())
+		})
+		.collect()
+}
+
+/// Compare two environments.
+///
+/// # Arguments
+/// - `before`: Earlier environment, e.g. from `snapshotEnv`.
+/// - `after`: Later environment, e.g. from `parseShellExports`.
+/// - `options`: Names to ignore.
+///
+/// # Returns
+/// Added, changed, and removed variables, each sorted by name.
+#[napi(js_name = "diffEnv")]
+#[allow(clippy::implicit_hasher, reason = "napi converts JS objects into the default `HashMap`")]
+pub fn diff_env(
+	before: HashMap<String, String>,
+	after: HashMap<String, String>,
+	options: Option<DiffEnvOptions>,
+) -> EnvDiff {
+	let ignore = options
+		.and_then(|opts| opts.ignore)
+		.unwrap_or_else(|| DEFAULT_IGNORE.iter().map(ToString::to_string).collect());
+	diff(&before, &after, &ignore)
+}
+
+/// Parse exported variables from `export -p` output or `NAME=value` lines.
+///
+/// # Arguments
+/// - `text`: Shell output; later assignments to a name win.
+///
+/// # Returns
+/// Exported variable names mapped to their unquoted values.
+///
+/// # Errors
+/// Returns an error for unterminated quotes.
+#[napi(js_name = "parseShellExports")]
+pub fn parse_shell_exports(text: String) -> Result<HashMap<String, String>> {
+	parse_exports(&text)
+}
@@ -0,0 +1,197 @@
+//! Line-ending detection and normalization.
+//!
+//! # Overview
+//! Counts LF, CRLF, and lone CR terminators to find a text's dominant line
+//! ending, and rewrites text to a single target ending. `applyEdit`,
+//! `replaceInFiles`, and `writeFileAtomic` (with `preserveEol`) use
+//! [`preserve_eol`] so edits keep the original file's convention instead of
+//! converting CRLF files to LF.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.detectEol("a\r\nb\r\n") // -> { eol: "crlf", lf: 0, crlf: 2, cr: 0, mixed: false }
+//! // JS: native.normalizeEol("a\nb\r\n", "lf") // -> "a\nb\n"
+//! ```
+
+use std::borrow::Cow;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Line-ending statistics for a text.
+#[napi(object)]
+pub struct EolInfo {
+	/// Dominant line ending: `"lf"`, `"crlf"`, or `"cr"` (`"lf"` when the text
+	/// has none).
+	pub eol:   String,
+	/// Number of LF terminators not preceded by CR.
+	pub lf:    u32,
+	/// Number of CRLF terminators.
+	pub crlf:  u32,
+	/// Number of CR terminators not followed by LF.
+	pub cr:    u32,
+	/// Whether more than one kind of terminator occurs.
+	pub mixed: bool,
+}
+
+/// A line terminator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Eol {
+	Lf,
+	CrLf,
+	Cr,
+}
+
+impl Eol {
+	/// Parse a JS-facing name (`"lf"`, `"crlf"`, or `"cr"`).
+	///
+	/// # Errors
+	/// Returns an error for unknown names.
+	pub fn parse(name: &str) -> Result<Self> {
+		match name {
+			"lf" | "\n" => Ok(Self::Lf),
+			"crlf" | "\r\n" => Ok(Self::CrLf),
+			"cr" | "\r" => Ok(Self::Cr),
+			other => Err(Error::from_reason(format!("Unknown line ending: {other}"))),
+		}
+	}
+
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Lf => "lf",
+			Self::CrLf => "crlf",
+			Self::Cr => "cr",
+		}
+	}
+
+	pub const fn as_str(self) -> &'static str {
+		match self {
+			Self::Lf => "\n",
+			Self::CrLf => "\r\n",
+			Self::Cr => "\r",
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Detection and normalization
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Terminator counts for a text.
+#[derive(Default, Clone, Copy)]
+pub struct EolCounts {
+	pub lf:   usize,
+	pub crlf: usize,
+	pub cr:   usize,
+}
+
+impl EolCounts {
+	/// Dominant terminator, or `None` when the text has no line endings. Ties
+	/// prefer LF, then CRLF.
+	pub const fn dominant(self) -> Option<Eol> {
+		if self.lf + self.crlf + self.cr == 0 {
+			None
+		} else if self.lf >= self.crlf && self.lf >= self.cr {
+			Some(Eol::Lf)
+		} else if self.crlf >= self.cr {
+			Some(Eol::CrLf)
+		} else {
+			Some(Eol::Cr)
+		}
+	}
+
+	pub fn mixed(self) -> bool {
+		u8::from(self.lf > 0) + u8::from(self.crlf > 0) + u8::from(self.cr > 0) > 1
+	}
+}
+
+/// Count line terminators in `text`.
+pub fn count_eols(text: &str) -> EolCounts {
+	let bytes = text.as_bytes();
+	let mut counts = EolCounts::default();
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			b'\r' if bytes.get(i + 1) == Some(&b'\n') => {
+				counts.crlf += 1;
+				i += 1;
+			},
+			b'\r' => counts.cr += 1,
+			b'\n' => counts.lf += 1,
+			_ => {},
+		}
+		i += 1;
+	}
+	counts
+}
+
+/// Rewrite every line terminator in `text` to `target`, borrowing when
+/// nothing changes.
+pub fn normalize(text: &str, target: Eol) -> Cow<'_, str> {
+	let counts = count_eols(text);
+	let already = match target {
+		Eol::Lf => counts.crlf + counts.cr == 0,
+		Eol::CrLf => counts.lf + counts.cr == 0,
+		Eol::Cr => counts.lf + counts.crlf == 0,
+	};
+	if already {
+		return Cow::Borrowed(text);
+	}
+	let mut out = String::with_capacity(text.len() + counts.lf);
+	let mut rest = text;
+	while let Some(pos) = rest.find(['\r', '\n']) {
+		out.push_str(&rest[..pos]);
+		out.push_str(target.as_str());
+		let skip = if rest[pos..].starts_with("\r\n") { 2 } else { 1 };
+		rest = &rest[pos + skip..];
+	}
+	out.push_str(rest);
+	Cow::Owned(out)
+}
+
+/// Convert `updated` to the dominant line ending of `original`.
+///
+/// Text without line endings in `original` is returned unchanged, so new
+/// files keep whatever the caller wrote.
+pub fn preserve_eol<'a>(original: &str, updated: &'a str) -> Cow<'a, str> {
+	match count_eols(original).dominant() {
+		Some(eol) => normalize(updated, eol),
+		None => Cow::Borrowed(updated),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect the dominant line ending of a text.
+#[napi(js_name = "detectEol")]
+pub fn detect_eol(text: String) -> EolInfo {
+	let counts = count_eols(&text);
+	let clamp = |n: usize| n.min(u32::MAX as usize) as u32;
+	EolInfo {
+		eol:   counts.dominant().unwrap_or(Eol::Lf).name().to_string(),
+		lf:    clamp(counts.lf),
+		crlf:  clamp(counts.crlf),
+		cr:    clamp(counts.cr),
+		mixed: counts.mixed(),
+	}
+}
+
+/// Rewrite every line ending in a text to `target` (`"lf"`, `"crlf"`, or
+/// `"cr"`).
+///
+/// # Errors
+/// Returns an error for an unknown target.
+#[napi(js_name = "normalizeEol")]
+pub fn normalize_eol(text: String, target: String) -> Result<String> {
+	let target = Eol::parse(&target)?;
+	Ok(match normalize(&text, target) {
+		Cow::Borrowed(_) => text,
+		Cow::Owned(out) => out,
+	})
+}
@@ -0,0 +1,157 @@
+//! File selection shared by the filesystem-walking APIs.
+//!
+//! # Overview
+//! Resolves search roots against the current directory and narrows scanned
+//! entries to regular files by filename glob and file type, so `grep`,
+//! `replaceInFiles`, `countLines`, and snapshots select files the same way.
+
+use std::path::{Path, PathBuf};
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use napi::bindgen_prelude::*;
+
+use crate::{fs_cache, glob::build_glob_pattern};
+
+/// File type filter resolved from a `type` option.
+pub enum TypeFilter {
+	Known { exts: &'static [&'static str], names: &'static [&'static str] },
+	Custom(String),
+}
+
+impl TypeFilter {
+	fn match_ext(&self, ext: &str) -> bool {
+		match self {
+			Self::Known { exts, .. } => exts.iter().any(|e| ext.eq_ignore_ascii_case(e)),
+			Self::Custom(custom_ext) => ext.eq_ignore_ascii_case(custom_ext),
+		}
+	}
+
+	fn match_name(&self, name: &str) -> bool {
+		match self {
+			Self::Known { names, .. } => names.iter().any(|n| name.eq_ignore_ascii_case(n)),
+			Self::Custom(ext) => ext.eq_ignore_ascii_case(name),
+		}
+	}
+}
+
+/// A file selected for searching.
+pub struct FileEntry {
+	/// Absolute path.
+	pub path:          PathBuf,
+	/// Path relative to the search root, using forward slashes.
+	pub relative_path: String,
+}
+
+/// Resolve a file or directory path against the current directory.
+pub fn resolve_search_path(path: &str) -> Result<PathBuf> {
+	let candidate = PathBuf::from(path);
+	if candidate.is_absolute() {
+		return Ok(candidate);
+	}
+	let cwd = std::env::current_dir()
+		.map_err(|err| Error::from_reason(format!("Failed to resolve cwd: {err}")))?;
+	Ok(cwd.join(candidate))
+}
+
+/// Compile a filename glob, matching basenames anywhere when it has no `/`.
+pub fn compile_glob(glob: Option<&str>) -> Result<Option<GlobSet>> {
+	let Some(glob) = glob.map(str::trim).filter(|value| !value.is_empty()) else {
+		return Ok(None);
+	};
+	let mut builder = GlobSetBuilder::new();
+	let pattern = build_glob_pattern(glob);
+	let glob = Glob::new(&pattern)
+		.map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))?;
+	builder.add(glob);
+	builder
+		.build()
+		.map(Some)
+		.map_err(|err| Error::from_reason(format!("Failed to build glob matcher: {err}")))
+}
+
+/// Resolve a file type name (e.g. "ts", "rust") or bare extension.
+pub fn resolve_type_filter(type_name: Option<&str>) -> Option<TypeFilter> {
+	let normalized = type_name
+		.map(str::trim)
+		.filter(|value| !value.is_empty())
+		.map(|value| value.trim_start_matches('.').to_lowercase())?;
+
+	let (exts, names): (&[&str], &[&str]) = match normalized.as_str() {
+		"js" | "javascript" => (&["js", "jsx", "mjs", "cjs"], &[]),
+		"ts" | "typescript" => (&["ts", "tsx", "mts", "cts"], &[]),
+		"json" => (&["json", "jsonc", "json5"], &[]),
+		"yaml" | "yml" => (&["yaml", "yml"], &[]),
+		"toml" => (&["toml"], &[]),
+		"md" | "markdown" => (&["md", "markdown", "mdx"], &[]),
+		"py" | "python" => (&["py", "pyi"], &[]),
+		"rs" | "rust" => (&["rs"], &[]),
+		"go" => (&["go"], &[]),
+		"java" => (&["java"], &[]),
+		"kt" | "kotlin" => (&["kt", "kts"], &[]),
+		"c" => (&["c", "h"], &[]),
+		"cpp" | "cxx" => (&["cpp", "cc", "cxx", "hpp", "hxx", "hh"], &[]),
+		"cs" | "csharp" => (&["cs", "csx"], &[]),
+		"php" => (&["php", "phtml"], &[]),
+		"rb" | "ruby" => (&["rb", "rake", "gemspec"], &[]),
+		"sh" | "bash" => (&["sh", "bash", "zsh"], &[]),
+		"zsh" => (&["zsh"], &[]),
+		"fish" => (&["fish"], &[]),
+		"html" => (&["html", "htm"], &[]),
+		"css" => (&["css"], &[]),
+		"scss" => (&["scss"], &[]),
+		"sass" => (&["sass"], &[]),
+		"less" => (&["less"], &[]),
+		"xml" => (&["xml"], &[]),
+		"docker" | "dockerfile" => (&[], &["dockerfile"]),
+		"make" | "makefile" => (&[], &["makefile"]),
+		_ => {
+			return Some(TypeFilter::Custom(normalized));
+		},
+	};
+
+	Some(TypeFilter::Known { exts, names })
+}
+
+/// Check whether `path` matches a resolved type filter.
+pub fn matches_type_filter(path: &Path, filter: &TypeFilter) -> bool {
+	let base_name = path
+		.file_name()
+		.and_then(|name| name.to_str())
+		.unwrap_or("");
+	if filter.match_name(base_name) {
+		return true;
+	}
+	let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+	if ext.is_empty() {
+		return false;
+	}
+	filter.match_ext(ext)
+}
+
+/// Select regular files from scanned entries by glob and type filter.
+pub fn collect_files(
+	root: &Path,
+	scanned_entries: &[fs_cache::GlobMatch],
+	glob_set: Option<&GlobSet>,
+	type_filter: Option<&TypeFilter>,
+) -> Vec<FileEntry> {
+	let mut entries = Vec::new();
+	for entry in scanned_entries {
+		if entry.file_type != fs_cache::FileType::File {
+			continue;
+		}
+		if let Some(glob_set) = glob_set
+			&& !glob_set.is_match(Path::new(&entry.path))
+		{
+			continue;
+		}
+		let path = root.join(&entry.path);
+		if let Some(filter) = type_filter
+			&& !matches_type_filter(&path, filter)
+		{
+			continue;
+		}
+		entries.push(FileEntry { path, relative_path: entry.path.clone() });
+	}
+	entries
+}
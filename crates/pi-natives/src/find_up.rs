@@ -0,0 +1,144 @@
+//! Upward discovery of config files.
+//!
+//! # Overview
+//! `findUp` walks from a starting directory to its ancestors and checks each
+//! one for a list of names (`package.json`, `.editorconfig`, or nested paths
+//! like `.pi/config.json`), replacing a chain of `fs.exists` round trips from
+//! JS with one native call.
+//!
+//! The walk follows the path as written rather than its resolved target, so
+//! a project opened through a symlink finds the configs around the link.
+//! Candidates are checked through symlinks, and dangling links do not count.
+//! The walk ends at `stopAt` (inclusive), or at the filesystem root.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.findUp({ names: [".pi/config.json", "package.json"], from: "/repo/pkg/src" })
+//! // JS: // -> ["/repo/pkg/package.json"]
+//! // JS: native.findUp({ names: [".editorconfig"], from: "/repo/pkg/src", multiple: true })
+//! // JS: // -> ["/repo/pkg/.editorconfig", "/repo/.editorconfig"]
+//! ```
+
+use std::{
+	env, fs,
+	path::{Component, Path, PathBuf},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `findUp`.
+#[napi(object)]
+pub struct FindUpOptions {
+	/// Names to look for in each directory, in priority order; may contain
+	/// separators (e.g. `.pi/config.json`).
+	pub names:    Vec<String>,
+	/// Starting directory, or a file whose directory is used (default:
+	/// process cwd).
+	pub from:     Option<String>,
+	/// Last directory to search (default: filesystem root).
+	#[napi(js_name = "stopAt")]
+	pub stop_at:  Option<String>,
+	/// Return every hit instead of only the nearest (default: false).
+	pub multiple: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Walking
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Make `path` absolute against `cwd` and resolve `.` and `..` lexically,
+/// without following symlinks.
+fn normalize(path: &Path, cwd: &Path) -> PathBuf {
+	let mut out = PathBuf::new();
+	for component in cwd.join(path).components() {
+		match component {
+			Component::CurDir => {},
+			Component::ParentDir => {
+				out.pop();
+			},
+			other => out.push(other),
+		}
+	}
+	out
+}
+
+/// Whether `dir` is the stop boundary, either as written or once symlinks
+/// are resolved.
+fn is_stop(dir: &Path, stop: &Path, stop_resolved: Option<&Path>, lexical: bool) -> bool {
+	if lexical {
+		return dir == stop;
+	}
+	dir == stop
+		|| stop_resolved.is_some_and(|stop| fs::canonicalize(dir).is_ok_and(|dir| dir == stop))
+}
+
+fn find_up(names: &[String], from: &Path, stop: Option<&Path>, multiple: bool) -> Vec<PathBuf> {
+	let start = if fs::metadata(from).is_ok_and(|meta| meta.is_file()) {
+		from.parent().unwrap_or(from)
+	} else {
+		from
+	};
+	// Compare resolved paths only when the boundary is not an ancestor as
+	// written, e.g. a canonical `stopAt` with a start reached through a link.
+	let lexical = stop.is_none_or(|stop| start.starts_with(stop));
+	let stop_resolved = stop
+		.filter(|_| !lexical)
+		.and_then(|stop| fs::canonicalize(stop).ok());
+
+	let mut found = Vec::new();
+	for dir in start.ancestors() {
+		for name in names {
+			let candidate = dir.join(name);
+			if fs::metadata(&candidate).is_ok() {
+				found.push(candidate);
+				if !multiple {
+					return found;
+				}
+			}
+		}
+		if stop.is_some_and(|stop| is_stop(dir, stop, stop_resolved.as_deref(), lexical)) {
+			break;
+		}
+	}
+	found
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Find config files in a directory and its ancestors.
+///
+/// # Arguments
+/// - `options`: Names to look for, start and stop directories, and whether to
+///   collect every hit.
+///
+/// # Returns
+/// Absolute paths, nearest directory first and in `names` order within a
+/// directory; at most one unless `multiple` is set.
+///
+/// # Errors
+/// Returns an error if the current directory cannot be read.
+#[napi(js_name = "findUp")]
+pub fn find_up_napi(options: FindUpOptions) -> Result<Vec<String>> {
+	let cwd = env::current_dir()
+		.map_err(|err| Error::from_reason(format!("Failed to read current directory: {err}")))?;
+	let from = normalize(Path::new(options.from.as_deref().unwrap_or(".")), &cwd);
+	let stop = options
+		.stop_at
+		.map(|stop| normalize(Path::new(&stop), &cwd));
+	let names: Vec<String> = options
+		.names
+		.into_iter()
+		.filter(|name| !name.is_empty())
+		.collect();
+	Ok(find_up(&names, &from, stop.as_deref(), options.multiple.unwrap_or(false))
+		.into_iter()
+		.map(|path| path.to_string_lossy().into_owned())
+		.collect())
+}
@@ -13,14 +13,15 @@
 use std::{
 	borrow::Cow,
 	path::{Path, PathBuf},
-	sync::LazyLock,
+	sync::{Arc, LazyLock},
 	time::{Duration, Instant},
 };
 
-use dashmap::DashMap;
-use ignore::WalkBuilder;
+use dashmap::{DashMap, DashSet};
+use ignore::{WalkBuilder, WalkState};
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use parking_lot::Mutex;
 
 use crate::task;
 
@@ -44,13 +45,25 @@ pub enum FileType {
 #[napi(object)]
 pub struct GlobMatch {
 	/// Relative path from the search root, using forward slashes.
-	pub path:      String,
+	pub path:        String,
 	/// Resolved filesystem type for the match.
 	#[napi(js_name = "fileType")]
-	pub file_type: FileType,
+	pub file_type:   FileType,
 	/// Modification time in milliseconds since Unix epoch (from
 	/// `symlink_metadata`).
-	pub mtime:     Option<f64>,
+	pub mtime:       Option<f64>,
+	/// File size in bytes; only set for files when metadata is requested.
+	pub size:        Option<f64>,
+	/// Number of path components below the search root; only set when
+	/// metadata is requested.
+	pub depth:       Option<u32>,
+	/// Whether the first block of a file looks binary; only set for readable
+	/// files when metadata is requested.
+	#[napi(js_name = "isBinary")]
+	pub is_binary:   Option<bool>,
+	/// Type of a symlink's target; unset for other entries and dangling links.
+	#[napi(js_name = "targetType")]
+	pub target_type: Option<FileType>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -186,6 +199,31 @@ pub fn should_skip_path(path: &Path, mentions_node_modules: bool) -> bool {
 	false
 }
 
+/// Type of whatever `path` resolves to, following symlinks.
+fn resolved_file_type(path: &Path) -> Option<FileType> {
+	let metadata = std::fs::metadata(path).ok()?;
+	Some(if metadata.is_dir() {
+		FileType::Dir
+	} else {
+		FileType::File
+	})
+}
+
+/// Identity of the directory at `path`, following symlinks.
+#[cfg(unix)]
+fn dir_id(path: &Path) -> Option<(u64, u64)> {
+	use std::os::unix::fs::MetadataExt;
+
+	let metadata = std::fs::metadata(path).ok()?;
+	Some((metadata.dev(), metadata.ino()))
+}
+
+/// Identity of the directory at `path`, following symlinks.
+#[cfg(not(unix))]
+fn dir_id(path: &Path) -> Option<PathBuf> {
+	std::fs::canonicalize(path).ok()
+}
+
 pub fn classify_file_type(path: &Path) -> Option<(FileType, Option<f64>)> {
 	let metadata = std::fs::symlink_metadata(path).ok()?;
 	let file_type = metadata.file_type();
@@ -237,43 +275,180 @@ pub fn build_walker(root: &Path, include_hidden: bool, use_gitignore: bool) -> W
 	builder
 }
 
-/// Scans filesystem entries and records normalized relative paths with file
-/// metadata.
+/// Predicate over a directory's root-relative path deciding whether a walk
+/// enters it.
+pub type DescendFilter = dyn Fn(&str) -> bool + Send + Sync;
+
+/// Which parts of the tree a walk enters, beyond visibility and ignore rules.
+#[derive(Default)]
+pub struct WalkScope {
+	/// Walk into symlinked directories. Each directory is entered once, so a
+	/// directory reached again, through a link or directly, is skipped and
+	/// cycles terminate.
+	pub follow_symlinks: bool,
+	/// Deepest level to walk, counting the root's children as 1.
+	pub max_depth:       Option<usize>,
+	/// Called with each directory's root-relative path; returning `false`
+	/// skips the directory and everything under it.
+	pub descend:         Option<Arc<DescendFilter>>,
+}
+
+/// Applies `scope` to `builder`.
+fn apply_scope(builder: &mut WalkBuilder, root: &Path, scope: &WalkScope) {
+	builder.max_depth(scope.max_depth);
+	if !scope.follow_symlinks && scope.descend.is_none() {
+		return;
+	}
+
+	let visited = scope.follow_symlinks.then(|| {
+		let visited = DashSet::new();
+		if let Some(id) = dir_id(root) {
+			visited.insert(id);
+		}
+		visited
+	});
+	let descend = scope.descend.clone();
+	let root = root.to_path_buf();
+	builder
+		.follow_links(scope.follow_symlinks)
+		.filter_entry(move |entry| {
+			let is_dir = entry.file_type().is_some_and(|ty| ty.is_dir());
+			if !is_dir {
+				return true;
+			}
+			if let Some(descend) = &descend
+				&& !descend(&normalize_relative_path(&root, entry.path()))
+			{
+				return false;
+			}
+			// Every directory entered is recorded, so a real directory first
+			// reached through a link is not walked a second time.
+			visited
+				.as_ref()
+				.is_none_or(|visited| dir_id(entry.path()).is_none_or(|id| visited.insert(id)))
+		});
+}
+
+/// Walks filesystem entries, passing each normalized relative path with file
+/// metadata to `visit` until it returns `false`.
 ///
-/// Always stores `node_modules` entries; caller-side filtering handles
-/// exclusion.
-fn collect_entries(
+/// Always visits `node_modules` entries; caller-side filtering handles
+/// exclusion. Only the parts of the tree `scope` allows are walked.
+pub fn walk_entries(
 	root: &Path,
 	include_hidden: bool,
 	use_gitignore: bool,
+	scope: &WalkScope,
 	ct: &task::CancelToken,
-) -> Result<Vec<GlobMatch>> {
-	let builder = build_walker(root, include_hidden, use_gitignore);
-	let mut entries = Vec::new();
+	mut visit: impl FnMut(GlobMatch) -> bool,
+) -> Result<()> {
+	let mut builder = build_walker(root, include_hidden, use_gitignore);
+	apply_scope(&mut builder, root, scope);
 
 	for entry in builder.build() {
 		ct.heartbeat()?;
 
 		let Ok(entry) = entry else { continue };
-		let path = entry.path();
-		if should_skip_path(path, true) {
-			// The cache always stores node_modules; caller-side filtering is applied later.
+		let Some(entry) = entry_match(root, entry.path()) else {
 			continue;
-		}
+		};
 
-		let relative = normalize_relative_path(root, path);
-		if relative.is_empty() {
-			// Ignore the synthetic root entry ("" relative path).
-			continue;
+		if !visit(entry) {
+			break;
 		}
+	}
 
-		let Some((file_type, mtime)) = classify_file_type(path) else {
-			continue;
-		};
+	Ok(())
+}
+
+/// Like [`walk_entries`], but spreads the walk over `threads` workers.
+///
+/// A `threads` of 0 picks a count from the available parallelism. `visit` is
+/// called from all workers, in no particular order. With one thread this is
+/// [`walk_entries`].
+pub fn walk_entries_parallel(
+	root: &Path,
+	include_hidden: bool,
+	use_gitignore: bool,
+	scope: &WalkScope,
+	threads: usize,
+	ct: &task::CancelToken,
+	visit: impl Fn(GlobMatch) -> bool + Sync,
+) -> Result<()> {
+	if threads == 1 {
+		return walk_entries(root, include_hidden, use_gitignore, scope, ct, visit);
+	}
+
+	let mut builder = build_walker(root, include_hidden, use_gitignore);
+	apply_scope(&mut builder, root, scope);
+	let error = Mutex::new(None);
+	builder.threads(threads).build_parallel().run(|| {
+		Box::new(|entry| {
+			if let Err(err) = ct.heartbeat() {
+				error.lock().get_or_insert(err);
+				return WalkState::Quit;
+			}
+
+			let Ok(entry) = entry else {
+				return WalkState::Continue;
+			};
+			let Some(entry) = entry_match(root, entry.path()) else {
+				return WalkState::Continue;
+			};
+			if visit(entry) {
+				WalkState::Continue
+			} else {
+				WalkState::Quit
+			}
+		})
+	});
 
-		entries.push(GlobMatch { path: relative.into_owned(), file_type, mtime });
+	error.into_inner().map_or(Ok(()), Err)
+}
+
+/// Builds the entry for a walked `path`, or `None` when it is skipped.
+fn entry_match(root: &Path, path: &Path) -> Option<GlobMatch> {
+	if should_skip_path(path, true) {
+		// The cache always stores node_modules; caller-side filtering is applied later.
+		return None;
+	}
+
+	let relative = normalize_relative_path(root, path);
+	if relative.is_empty() {
+		// Ignore the synthetic root entry ("" relative path).
+		return None;
 	}
 
+	let (file_type, mtime) = classify_file_type(path)?;
+	let target_type = if file_type == FileType::Symlink {
+		resolved_file_type(path)
+	} else {
+		None
+	};
+	Some(GlobMatch {
+		path: relative.into_owned(),
+		file_type,
+		mtime,
+		size: None,
+		depth: None,
+		is_binary: None,
+		target_type,
+	})
+}
+
+/// Scans filesystem entries and records normalized relative paths with file
+/// metadata.
+fn collect_entries(
+	root: &Path,
+	include_hidden: bool,
+	use_gitignore: bool,
+	ct: &task::CancelToken,
+) -> Result<Vec<GlobMatch>> {
+	let mut entries = Vec::new();
+	walk_entries(root, include_hidden, use_gitignore, &WalkScope::default(), ct, |entry| {
+		entries.push(entry);
+		true
+	})?;
 	Ok(entries)
 }
 
@@ -345,6 +520,14 @@ pub fn force_rescan(
 	Ok(entries)
 }
 
+/// Drop the cached scan for `root`, so the next cached query rescans.
+///
+/// Uncached walks call this as [`force_rescan`] without `store` would: they
+/// see the current tree, so an older scan of the same root may be stale.
+pub fn drop_scan(root: &Path, include_hidden: bool, use_gitignore: bool) {
+	FS_CACHE.remove(&CacheKey { root: root.to_path_buf(), include_hidden, use_gitignore });
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Invalidation
 // ═══════════════════════════════════════════════════════════════════════════
@@ -402,3 +585,33 @@ pub fn invalidate_fs_scan_cache(path: Option<String>) {
 		None => invalidate_all(),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[test]
+	fn test_follow_symlinks_enters_each_directory_once() {
+		let root = std::env::temp_dir().join(format!("pi-fs-cache-links-{}", std::process::id()));
+		let _ = std::fs::remove_dir_all(&root);
+		std::fs::create_dir_all(root.join("real")).unwrap();
+		std::fs::write(root.join("real/file.txt"), "x").unwrap();
+		// Sorts before `real`, so the walk reaches the directory through it first.
+		std::os::unix::fs::symlink("real", root.join("link")).unwrap();
+		// A cycle back to the root.
+		std::os::unix::fs::symlink("..", root.join("real/up")).unwrap();
+
+		let scope = WalkScope { follow_symlinks: true, ..WalkScope::default() };
+		let mut files = Vec::new();
+		walk_entries(&root, true, false, &scope, &task::CancelToken::default(), |entry| {
+			if entry.path.ends_with("file.txt") {
+				files.push(entry.path);
+			}
+			true
+		})
+		.unwrap();
+		assert_eq!(files.len(), 1, "walked twice: {files:?}");
+		std::fs::remove_dir_all(&root).unwrap();
+	}
+}
@@ -0,0 +1,488 @@
+//! Fuzzy scoring and "did you mean" suggestions.
+//!
+//! # Overview
+//! `fuzzyScore` implements an fzf-v2 style Smith-Waterman scorer: every query
+//! character must match in order, matches earn boundary/camelCase bonuses,
+//! consecutive runs are rewarded, and gaps are penalized. The optimal
+//! alignment is recovered so callers can highlight the matched characters,
+//! either as individual positions or, with `spans`, merged into `[start, end)`
+//! runs ready for a picker to render.
+//!
+//! Candidates are scored in parallel; matching is smart-case (case-sensitive
+//! only when the query contains an uppercase character).
+//!
+//! `suggest` ranks candidates by Damerau-Levenshtein (optimal string
+//! alignment) distance, abandoning a candidate as soon as every cell of a DP
+//! row exceeds the distance budget.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.fuzzyScore("gco", ["git checkout", "go"], { limit: 10 })
+//! // JS: native.fuzzyScore("gch", ["git checkout"], { spans: true })
+//! // -> [{ index: 0, score: 83, positions: [0, 4, 5], spans: [{ start: 0, end: 1 }, { start: 4, end: 6 }] }]
+//! // JS: native.suggest("gerp", ["grep", "git"]) // [{ value: "grep", distance: 1 }]
+//! ```
+
+use napi_derive::napi;
+use rayon::prelude::*;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `fuzzyScore`.
+#[napi(object)]
+#[derive(Default)]
+pub struct FuzzyScoreOptions {
+	/// Maximum number of results to return (default: all matches).
+	pub limit: Option<u32>,
+	/// Also report the matched characters merged into highlight spans
+	/// (default: false).
+	pub spans: Option<bool>,
+}
+
+/// A scored candidate.
+#[napi(object)]
+pub struct FuzzyScoreMatch {
+	/// Index of the candidate in the input array.
+	pub index:     u32,
+	/// Match quality score (higher is better).
+	pub score:     i32,
+	/// UTF-16 offsets of the matched characters in the candidate.
+	pub positions: Vec<u32>,
+	/// Matched character runs in the candidate, in order; only set when
+	/// `spans` is requested.
+	pub spans:     Option<Vec<FuzzySpan>>,
+}
+
+/// A run of consecutive matched characters.
+#[napi(object)]
+pub struct FuzzySpan {
+	/// UTF-16 offset of the first matched character.
+	pub start: u32,
+	/// UTF-16 offset just past the last matched character.
+	pub end:   u32,
+}
+
+/// Options for `suggest`.
+#[napi(object)]
+#[derive(Default)]
+pub struct SuggestOptions {
+	/// Maximum edit distance to accept (default: 2, or a third of the input
+	/// length for longer inputs).
+	#[napi(js_name = "maxDistance")]
+	pub max_distance: Option<u32>,
+}
+
+/// A candidate close to the input.
+#[napi(object)]
+pub struct Suggestion {
+	/// The candidate string.
+	pub value:    String,
+	/// Edit distance from the input (case-insensitive).
+	pub distance: u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Scoring constants (mirroring fzf's algo v2)
+// ═══════════════════════════════════════════════════════════════════════════
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+const BONUS_CAMEL_123: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+/// Cells never reachable by a valid alignment.
+const UNREACHABLE: i32 = i32::MIN / 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum CharClass {
+	White,
+	NonWord,
+	Delimiter,
+	Lower,
+	Upper,
+	Letter,
+	Number,
+}
+
+fn char_class(ch: char) -> CharClass {
+	if ch.is_lowercase() {
+		CharClass::Lower
+	} else if ch.is_uppercase() {
+		CharClass::Upper
+	} else if ch.is_numeric() {
+		CharClass::Number
+	} else if ch.is_alphabetic() {
+		CharClass::Letter
+	} else if ch.is_whitespace() {
+		CharClass::White
+	} else if matches!(ch, '/' | ',' | ':' | ';' | '|') {
+		CharClass::Delimiter
+	} else {
+		CharClass::NonWord
+	}
+}
+
+/// Bonus for matching a character of class `cur` that follows class `prev`.
+const fn bonus_for(prev: CharClass, cur: CharClass) -> i32 {
+	let cur_is_word = matches!(
+		cur,
+		CharClass::Lower | CharClass::Upper | CharClass::Letter | CharClass::Number
+	);
+	if cur_is_word {
+		match prev {
+			CharClass::White => return BONUS_BOUNDARY_WHITE,
+			CharClass::Delimiter => return BONUS_BOUNDARY_DELIMITER,
+			CharClass::NonWord => return BONUS_BOUNDARY,
+			_ => {},
+		}
+	}
+	match (prev, cur) {
+		(CharClass::Lower, CharClass::Upper) => BONUS_CAMEL_123,
+		(p, CharClass::Number) if !matches!(p, CharClass::Number) => BONUS_CAMEL_123,
+		(_, CharClass::NonWord | CharClass::Delimiter) => BONUS_NON_WORD,
+		(_, CharClass::White) => BONUS_BOUNDARY_WHITE,
+		_ => 0,
+	}
+}
+
+fn fold_char(ch: char, case_sensitive: bool) -> char {
+	if case_sensitive {
+		ch
+	} else {
+		ch.to_lowercase().next().unwrap_or(ch)
+	}
+}
+
+/// Back-pointer for recovering the optimal alignment.
+#[derive(Clone, Copy)]
+enum Step {
+	None,
+	Consecutive,
+	Gap(u32),
+}
+
+/// Score `query` against `target`, returning the score and the char indices of
+/// the optimal alignment, or `None` when `query` is not a subsequence.
+fn score_candidate(
+	query: &[char],
+	target: &str,
+	case_sensitive: bool,
+) -> Option<(i32, Vec<usize>)> {
+	let chars: Vec<char> = target.chars().collect();
+	let n = chars.len();
+	let m = query.len();
+	if m > n {
+		return None;
+	}
+
+	// Cheap subsequence pre-check before the quadratic pass.
+	let folded: Vec<char> = chars.iter().map(|&ch| fold_char(ch, case_sensitive)).collect();
+	let mut qi = 0;
+	for &ch in &folded {
+		if qi < m && ch == query[qi] {
+			qi += 1;
+		}
+	}
+	if qi < m {
+		return None;
+	}
+
+	let mut bonus = Vec::with_capacity(n);
+	let mut prev = CharClass::White;
+	for &ch in &chars {
+		let class = char_class(ch);
+		bonus.push(bonus_for(prev, class));
+		prev = class;
+	}
+
+	// score[i * n + j]: best score with query[i] matched at target[j].
+	// chunk[i * n + j]: bonus at the start of the consecutive run ending there.
+	let mut score = vec![UNREACHABLE; m * n];
+	let mut chunk = vec![0i32; m * n];
+	let mut steps = vec![Step::None; m * n];
+
+	for j in 0..n {
+		if folded[j] == query[0] {
+			score[j] = SCORE_MATCH + bonus[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+			chunk[j] = bonus[j];
+		}
+	}
+
+	for (i, &q) in query.iter().enumerate().skip(1) {
+		let row = i * n;
+		let prev_row = row - n;
+		// Best `score[i-1][k] + gap penalty` for k < j - 1, with its source k.
+		let mut gap_best = UNREACHABLE;
+		let mut gap_from = 0u32;
+		for j in i..n {
+			if j >= 2 {
+				let from_prev = score[prev_row + j - 2] + SCORE_GAP_START;
+				let extended = gap_best + SCORE_GAP_EXTENSION;
+				if from_prev >= extended {
+					gap_best = from_prev;
+					gap_from = (j - 2) as u32;
+				} else {
+					gap_best = extended;
+				}
+			}
+			if folded[j] != q {
+				continue;
+			}
+
+			let diag = score[prev_row + j - 1];
+			let consecutive = if diag > UNREACHABLE {
+				let run_bonus = bonus[j].max(chunk[prev_row + j - 1]).max(BONUS_CONSECUTIVE);
+				diag + SCORE_MATCH + run_bonus
+			} else {
+				UNREACHABLE
+			};
+			let gapped = if gap_best > UNREACHABLE {
+				gap_best + SCORE_MATCH + bonus[j]
+			} else {
+				UNREACHABLE
+			};
+
+			if consecutive >= gapped && consecutive > UNREACHABLE {
+				score[row + j] = consecutive;
+				chunk[row + j] = chunk[prev_row + j - 1];
+				steps[row + j] = Step::Consecutive;
+			} else if gapped > UNREACHABLE {
+				score[row + j] = gapped;
+				chunk[row + j] = bonus[j];
+				steps[row + j] = Step::Gap(gap_from);
+			}
+		}
+	}
+
+	let last_row = (m - 1) * n;
+	let (mut j, best) = (m - 1..n)
+		.map(|j| (j, score[last_row + j]))
+		.max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))?;
+	if best <= UNREACHABLE {
+		return None;
+	}
+
+	let mut positions = vec![0usize; m];
+	for i in (0..m).rev() {
+		positions[i] = j;
+		match steps[i * n + j] {
+			Step::Consecutive => j -= 1,
+			Step::Gap(from) => j = from as usize,
+			Step::None => {},
+		}
+	}
+	Some((best, positions))
+}
+
+/// Fold `query` for smart-case matching.
+///
+/// # Returns
+/// The query characters and whether matching is case-sensitive (the query
+/// contains an uppercase character).
+pub fn prepare_query(query: &str) -> (Vec<char>, bool) {
+	let case_sensitive = query.chars().any(char::is_uppercase);
+	(
+		query
+			.chars()
+			.map(|ch| fold_char(ch, case_sensitive))
+			.collect(),
+		case_sensitive,
+	)
+}
+
+/// Score `target` against a query from [`prepare_query`], or `None` when the
+/// query is not a subsequence of it.
+pub fn score(query: &[char], target: &str, case_sensitive: bool) -> Option<i32> {
+	if query.is_empty() {
+		return Some(0);
+	}
+	score_candidate(query, target, case_sensitive).map(|(score, _)| score)
+}
+
+/// Convert char indices into UTF-16 offsets within `target`.
+fn to_utf16_positions(target: &str, char_positions: &[usize]) -> Vec<u32> {
+	let mut out = Vec::with_capacity(char_positions.len());
+	let mut wanted = char_positions.iter().peekable();
+	let mut offset = 0u32;
+	for (index, ch) in target.chars().enumerate() {
+		let Some(&&next) = wanted.peek() else { break };
+		if index == next {
+			out.push(offset);
+			wanted.next();
+		}
+		offset += ch.len_utf16() as u32;
+	}
+	out
+}
+
+/// Convert char indices into merged UTF-16 `[start, end)` spans within
+/// `target`.
+fn to_utf16_spans(target: &str, char_positions: &[usize]) -> Vec<FuzzySpan> {
+	let mut spans: Vec<FuzzySpan> = Vec::new();
+	let mut wanted = char_positions.iter().peekable();
+	let mut offset = 0u32;
+	for (index, ch) in target.chars().enumerate() {
+		let Some(&&next) = wanted.peek() else { break };
+		let end = offset + ch.len_utf16() as u32;
+		if index == next {
+			match spans.last_mut() {
+				Some(span) if span.end == offset => span.end = end,
+				_ => spans.push(FuzzySpan { start: offset, end }),
+			}
+			wanted.next();
+		}
+		offset = end;
+	}
+	spans
+}
+
+/// Score `candidates` in parallel and rank the matches by score (descending),
+/// then by candidate length and input order, keeping at most `limit`.
+fn rank(query: &str, candidates: &[String], limit: usize) -> Vec<(usize, i32, Vec<usize>)> {
+	let (query, case_sensitive) = prepare_query(query);
+	if query.is_empty() {
+		return (0..candidates.len().min(limit))
+			.map(|index| (index, 0, Vec::new()))
+			.collect();
+	}
+
+	let mut matches: Vec<(usize, i32, Vec<usize>)> = candidates
+		.par_iter()
+		.enumerate()
+		.filter_map(|(index, candidate)| {
+			score_candidate(&query, candidate, case_sensitive)
+				.map(|(score, positions)| (index, score, positions))
+		})
+		.collect();
+
+	matches.sort_by(|a, b| {
+		b.1.cmp(&a.1)
+			.then_with(|| candidates[a.0].len().cmp(&candidates[b.0].len()))
+			.then_with(|| a.0.cmp(&b.0))
+	});
+	matches.truncate(limit);
+	matches
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Edit distance
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Optimal string alignment distance between `a` and `b`, or `None` once it is
+/// certain to exceed `max`.
+fn bounded_osa_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+	if a.len().abs_diff(b.len()) > max {
+		return None;
+	}
+	if a.is_empty() || b.is_empty() {
+		return Some(a.len().max(b.len()));
+	}
+
+	let width = b.len() + 1;
+	let mut prev2 = vec![0usize; width];
+	let mut prev: Vec<usize> = (0..width).collect();
+	let mut cur = vec![0usize; width];
+	for i in 1..=a.len() {
+		cur[0] = i;
+		let mut row_min = cur[0];
+		for j in 1..width {
+			let cost = usize::from(a[i - 1] != b[j - 1]);
+			let mut value = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+			if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+				value = value.min(prev2[j - 2] + 1);
+			}
+			cur[j] = value;
+			row_min = row_min.min(value);
+		}
+		if row_min > max {
+			return None;
+		}
+		std::mem::swap(&mut prev2, &mut prev);
+		std::mem::swap(&mut prev, &mut cur);
+	}
+	let distance = prev[b.len()];
+	(distance <= max).then_some(distance)
+}
+
+fn lowercase_chars(value: &str) -> Vec<char> {
+	value.chars().flat_map(char::to_lowercase).collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Fuzzy-score `candidates` against `query`.
+///
+/// # Returns
+/// Matching candidates sorted by score (descending), then by candidate length
+/// and input order, with highlight spans when `spans` is set. An empty query
+/// matches everything with score 0.
+#[napi(js_name = "fuzzyScore")]
+pub fn fuzzy_score(
+	query: String,
+	candidates: Vec<String>,
+	options: Option<FuzzyScoreOptions>,
+) -> Vec<FuzzyScoreMatch> {
+	let options = options.unwrap_or_default();
+	let limit = options.limit.map_or(usize::MAX, |limit| limit as usize);
+	let with_spans = options.spans.unwrap_or(false);
+	rank(&query, &candidates, limit)
+		.into_iter()
+		.map(|(index, score, positions)| {
+			let candidate = &candidates[index];
+			FuzzyScoreMatch {
+				index: index as u32,
+				score,
+				positions: to_utf16_positions(candidate, &positions),
+				spans: with_spans.then(|| to_utf16_spans(candidate, &positions)),
+			}
+		})
+		.collect()
+}
+
+/// Suggest candidates within a small edit distance of `input`.
+///
+/// # Returns
+/// Candidates sorted by distance, then by input order. Exact
+/// (case-insensitive) matches are included with distance 0.
+#[napi]
+pub fn suggest(
+	input: String,
+	candidates: Vec<String>,
+	options: Option<SuggestOptions>,
+) -> Vec<Suggestion> {
+	let options = options.unwrap_or_default();
+	let input = lowercase_chars(&input);
+	let max_distance = options
+		.max_distance
+		.map_or_else(|| (input.len() / 3).max(2), |max| max as usize);
+
+	let mut found: Vec<(usize, usize)> = candidates
+		.par_iter()
+		.enumerate()
+		.filter_map(|(index, candidate)| {
+			bounded_osa_distance(&input, &lowercase_chars(candidate), max_distance)
+				.map(|distance| (index, distance))
+		})
+		.collect();
+	found.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+	let mut candidates: Vec<Option<String>> = candidates.into_iter().map(Some).collect();
+	found
+		.into_iter()
+		.filter_map(|(index, distance)| {
+			candidates[index]
+				.take()
+				.map(|value| Suggestion { value, distance: distance as u32 })
+		})
+		.collect()
+}
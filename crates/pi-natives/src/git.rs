@@ -0,0 +1,992 @@
+//! Native git queries via libgit2.
+//!
+//! # Overview
+//! Reads repositories directly from the object database, index, and working
+//! tree instead of spawning `git`. Diffs are returned as the same hunk and line
+//! structures as the text diff API, so the UI renders both with one code path.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.gitDiff({ repoPath: ".", paths: ["src"], staged: false, contextLines: 3 })
+//! // JS: await native.gitLog({ path: "src/lib.rs", maxCount: 10 })
+//! // JS: await native.gitRepoInfo(".") // -> { branch: "main", ahead: 1, behind: 0, ... }
+//! // JS: await native.gitStageHunks("src/lib.rs", [diff.files[0].hunkIds[1]])
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use git2::{
+	BranchType, Delta, DiffFindOptions, DiffOptions, ErrorCode, IndexEntry, IndexTime, ObjectType,
+	Oid, Patch, Repository, RepositoryState, Sort, Tree,
+};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{diff, task};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `gitDiff`.
+#[napi(object)]
+pub struct GitDiffOptions<'env> {
+	/// Path inside the repository (default: current directory).
+	#[napi(js_name = "repoPath")]
+	pub repo_path:     Option<String>,
+	/// Limit the diff to these pathspecs, relative to the repository root.
+	pub paths:         Option<Vec<String>>,
+	/// Diff the index against HEAD instead of the working tree against the
+	/// index (default: false).
+	pub staged:        Option<bool>,
+	/// Unchanged context lines around each hunk (default: 3).
+	#[napi(js_name = "contextLines")]
+	pub context_lines: Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:        Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:    Option<u32>,
+}
+
+/// Diff of a single file.
+#[napi(object)]
+pub struct GitFileDiff {
+	/// Path relative to the repository root (the new path for renames).
+	pub path:     String,
+	/// Previous path for renames and copies.
+	#[napi(js_name = "oldPath")]
+	pub old_path: Option<String>,
+	/// "added", "deleted", "modified", "renamed", "copied", "typechange", or
+	/// "untracked".
+	pub status:   String,
+	/// Whether either side is binary (binary files have no hunks).
+	pub binary:   bool,
+	/// Hunks in ascending line order.
+	pub hunks:    Vec<diff::DiffHunk>,
+	/// Stable identifier of each hunk, for `gitStageHunks`/`gitUnstageHunks`.
+	#[napi(js_name = "hunkIds")]
+	pub hunk_ids: Vec<String>,
+	/// Number of added lines.
+	pub added:    u32,
+	/// Number of removed lines.
+	pub removed:  u32,
+}
+
+/// Result of `gitDiff`.
+#[napi(object)]
+pub struct GitDiffResult {
+	/// Changed files in path order.
+	pub files:   Vec<GitFileDiff>,
+	/// Total number of added lines.
+	pub added:   u32,
+	/// Total number of removed lines.
+	pub removed: u32,
+}
+
+/// Options for `gitLog`.
+#[napi(object)]
+pub struct GitLogOptions<'env> {
+	/// File or directory whose history to list. Absolute and cwd-relative paths
+	/// inside the repository are accepted, as are repository-relative ones.
+	pub path:          String,
+	/// Path inside the repository (default: the parent of `path`).
+	#[napi(js_name = "repoPath")]
+	pub repo_path:     Option<String>,
+	/// Maximum number of commits to return (default: 20).
+	#[napi(js_name = "maxCount")]
+	pub max_count:     Option<u32>,
+	/// Include each commit's diff for `path` (default: false).
+	pub patch:         Option<bool>,
+	/// Unchanged context lines around each patch hunk (default: 3).
+	#[napi(js_name = "contextLines")]
+	pub context_lines: Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:        Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:    Option<u32>,
+}
+
+/// A commit touching the requested path.
+#[napi(object)]
+pub struct GitCommit {
+	/// Full commit hash.
+	pub hash:    String,
+	/// Author name.
+	pub author:  String,
+	/// Author email.
+	pub email:   String,
+	/// Author date in milliseconds since the Unix epoch.
+	pub date:    f64,
+	/// First line of the commit message.
+	pub subject: String,
+	/// Changes to the requested path, when `patch` is set.
+	pub patch:   Option<Vec<GitFileDiff>>,
+}
+
+/// Repository location, branch, and state.
+#[napi(object)]
+pub struct GitRepoInfo {
+	/// Working tree root (the git directory for bare repositories).
+	pub root:      String,
+	/// Path of the git directory.
+	#[napi(js_name = "gitDir")]
+	pub git_dir:   String,
+	/// Current branch name; absent when HEAD is detached.
+	pub branch:    Option<String>,
+	/// Commit hash HEAD points at; absent on an unborn branch.
+	pub head:      Option<String>,
+	/// Whether HEAD is detached.
+	pub detached:  bool,
+	/// Upstream branch (e.g. "origin/main"), if configured.
+	pub upstream:  Option<String>,
+	/// Commits on the branch that are not on its upstream.
+	pub ahead:     u32,
+	/// Commits on the upstream that are not on the branch.
+	pub behind:    u32,
+	/// In-progress operation: "clean", "merge", "revert", "cherryPick",
+	/// "bisect", "rebase", "rebaseInteractive", "rebaseMerge", or "applyMailbox".
+	pub state:     String,
+	/// Whether this is a linked worktree (`git worktree add`).
+	pub worktree:  bool,
+	/// Whether this repository is a submodule of another.
+	pub submodule: bool,
+	/// Whether the repository is bare.
+	pub bare:      bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Helpers
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn git_error(err: git2::Error) -> Error {
+	Error::from_reason(format!("Git error: {}", err.message()))
+}
+
+fn clamp_u32(value: usize) -> u32 {
+	value.min(u32::MAX as usize) as u32
+}
+
+/// Discover the repository containing `path` (default: current directory).
+pub fn open_repo(path: Option<&str>) -> Result<Repository> {
+	Repository::discover(path.unwrap_or(".")).map_err(git_error)
+}
+
+fn path_string(path: &Path) -> String {
+	path.to_string_lossy().replace('\\', "/")
+}
+
+const fn status_name(status: Delta) -> &'static str {
+	match status {
+		Delta::Added => "added",
+		Delta::Deleted => "deleted",
+		Delta::Renamed => "renamed",
+		Delta::Copied => "copied",
+		Delta::Typechange => "typechange",
+		Delta::Untracked => "untracked",
+		_ => "modified",
+	}
+}
+
+/// Convert a libgit2 patch into diff API hunks.
+pub fn patch_hunks(patch: &Patch<'_>) -> Result<Vec<diff::DiffHunk>> {
+	let mut hunks = Vec::with_capacity(patch.num_hunks());
+	for hunk_idx in 0..patch.num_hunks() {
+		let (header, line_count) = patch.hunk(hunk_idx).map_err(git_error)?;
+		let mut lines = Vec::with_capacity(line_count);
+		for line_idx in 0..line_count {
+			let line = patch.line_in_hunk(hunk_idx, line_idx).map_err(git_error)?;
+			let kind = match line.origin() {
+				' ' => diff::DiffLineKind::Context,
+				'+' => diff::DiffLineKind::Added,
+				'-' => diff::DiffLineKind::Removed,
+				// "\ No newline at end of file" markers.
+				_ => continue,
+			};
+			let content = String::from_utf8_lossy(line.content());
+			lines.push(diff::DiffLine {
+				kind,
+				content: diff::trim_line_ending(&content).to_string(),
+				old_line: line.old_lineno(),
+				new_line: line.new_lineno(),
+			});
+		}
+		hunks.push(diff::DiffHunk {
+			old_start: header.old_start(),
+			old_lines: header.old_lines(),
+			new_start: header.new_start(),
+			new_lines: header.new_lines(),
+			lines,
+		});
+	}
+	Ok(hunks)
+}
+
+/// Content-derived hunk identifier: stable for an unchanged diff, and
+/// different as soon as the hunk's position or lines change.
+fn hunk_id(path: &str, hunk: &diff::DiffHunk) -> String {
+	let mut key = format!(
+		"{path}\0{},{},{},{}\0",
+		hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines
+	);
+	for line in &hunk.lines {
+		key.push(match line.kind {
+			diff::DiffLineKind::Context => ' ',
+			diff::DiffLineKind::Added => '+',
+			diff::DiffLineKind::Removed => '-',
+		});
+		key.push_str(&line.content);
+		key.push('\n');
+	}
+	let oid = Oid::hash_object(ObjectType::Blob, key.as_bytes()).unwrap_or_else(|_| Oid::zero());
+	oid.to_string()[..12].to_string()
+}
+
+/// Convert every delta of a diff into file diffs.
+pub fn collect_file_diffs(
+	changes: &git2::Diff<'_>,
+	ct: &task::CancelToken,
+) -> Result<Vec<GitFileDiff>> {
+	let mut files = Vec::with_capacity(changes.deltas().len());
+	for idx in 0..changes.deltas().len() {
+		ct.heartbeat()?;
+		if let Some(file) = file_diff(changes, idx)? {
+			files.push(file);
+		}
+	}
+	Ok(files)
+}
+
+/// Convert the delta at `idx` into a file diff.
+fn file_diff(changes: &git2::Diff<'_>, idx: usize) -> Result<Option<GitFileDiff>> {
+	let Some(delta) = changes.get_delta(idx) else {
+		return Ok(None);
+	};
+	let path = delta
+		.new_file()
+		.path()
+		.or_else(|| delta.old_file().path())
+		.map(path_string);
+	let old_path = delta.old_file().path().map(path_string);
+	let renamed = matches!(delta.status(), Delta::Renamed | Delta::Copied);
+	let mut file = GitFileDiff {
+		path:     path.unwrap_or_default(),
+		old_path: if renamed { old_path } else { None },
+		status:   status_name(delta.status()).to_string(),
+		binary:   delta.flags().is_binary(),
+		hunks:    Vec::new(),
+		hunk_ids: Vec::new(),
+		added:    0,
+		removed:  0,
+	};
+	if let Some(patch) = Patch::from_diff(changes, idx).map_err(git_error)? {
+		let (_, added, removed) = patch.line_stats().map_err(git_error)?;
+		// Binary detection happens while loading content for the patch.
+		file.binary |= patch.delta().flags().is_binary();
+		file.hunks = patch_hunks(&patch)?;
+		file.hunk_ids = file.hunks.iter().map(|hunk| hunk_id(&file.path, hunk)).collect();
+		file.added = clamp_u32(added);
+		file.removed = clamp_u32(removed);
+	}
+	Ok(Some(file))
+}
+
+fn git_diff_sync(
+	repo_path: Option<&str>,
+	paths: &[String],
+	staged: bool,
+	context_lines: u32,
+	ct: &task::CancelToken,
+) -> Result<GitDiffResult> {
+	let repo = open_repo(repo_path)?;
+	let mut opts = DiffOptions::new();
+	opts.context_lines(context_lines).ignore_submodules(true);
+	for path in paths {
+		opts.pathspec(path);
+	}
+	let mut changes = if staged {
+		let head = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+		repo.diff_tree_to_index(head.as_ref(), None, Some(&mut opts))
+	} else {
+		opts
+			.include_untracked(true)
+			.recurse_untracked_dirs(true)
+			.show_untracked_content(true);
+		repo.diff_index_to_workdir(None, Some(&mut opts))
+	}
+	.map_err(git_error)?;
+	changes
+		.find_similar(Some(DiffFindOptions::new().renames(true)))
+		.map_err(git_error)?;
+	ct.heartbeat()?;
+
+	let files = collect_file_diffs(&changes, ct)?;
+	let added = files.iter().fold(0u32, |sum, file| sum.saturating_add(file.added));
+	let removed = files.iter().fold(0u32, |sum, file| sum.saturating_add(file.removed));
+	Ok(GitDiffResult { files, added, removed })
+}
+
+/// Resolve `path` to a repository-relative path, accepting absolute,
+/// cwd-relative, and repository-relative inputs.
+fn repo_relative(repo: &Repository, path: &str) -> PathBuf {
+	let workdir = repo.workdir().and_then(|dir| std::fs::canonicalize(dir).ok());
+	if let (Some(workdir), Some(absolute)) = (workdir, canonical(Path::new(path)))
+		&& let Ok(relative) = absolute.strip_prefix(&workdir)
+	{
+		return relative.to_path_buf();
+	}
+	PathBuf::from(path)
+}
+
+/// Canonical form of `path`; a deleted file resolves through its directory.
+fn canonical(path: &Path) -> Option<PathBuf> {
+	std::fs::canonicalize(path).ok().or_else(|| {
+		let parent = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+		let parent = std::fs::canonicalize(parent.unwrap_or_else(|| Path::new("."))).ok()?;
+		Some(parent.join(path.file_name()?))
+	})
+}
+
+/// Open `repo_path`, or the repository containing `path` when absent.
+fn open_repo_for(path: &str, repo_path: Option<&str>) -> Result<Repository> {
+	if let Some(repo_path) = repo_path {
+		return open_repo(Some(repo_path));
+	}
+	let start = Path::new(path);
+	let dir = if start.is_dir() { Some(start) } else { start.parent() };
+	let dir = dir.filter(|dir| !dir.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+	open_repo(Some(&dir.to_string_lossy()))
+}
+
+fn git_log_sync(
+	path: &str,
+	repo_path: Option<&str>,
+	max_count: usize,
+	patch: bool,
+	context_lines: u32,
+	ct: &task::CancelToken,
+) -> Result<Vec<GitCommit>> {
+	let repo = open_repo_for(path, repo_path)?;
+	let relative = repo_relative(&repo, path);
+	let id_at = |tree: &Tree<'_>| tree.get_path(&relative).ok().map(|entry| entry.id());
+
+	let mut walk = repo.revwalk().map_err(git_error)?;
+	walk.set_sorting(Sort::TIME).map_err(git_error)?;
+	if walk.push_head().is_err() {
+		// Unborn branch: no history yet.
+		return Ok(Vec::new());
+	}
+
+	let mut commits = Vec::new();
+	for oid in walk {
+		if commits.len() >= max_count {
+			break;
+		}
+		ct.heartbeat()?;
+		let commit = repo.find_commit(oid.map_err(git_error)?).map_err(git_error)?;
+		let tree = commit.tree().map_err(git_error)?;
+		let current = id_at(&tree);
+		let parents = commit
+			.parents()
+			.map(|parent| parent.tree().map_err(git_error))
+			.collect::<Result<Vec<_>>>()?;
+		// Like `git log <path>`, skip commits identical to any parent at `path`.
+		let touched = if parents.is_empty() {
+			current.is_some()
+		} else {
+			parents.iter().all(|parent| id_at(parent) != current)
+		};
+		if !touched {
+			continue;
+		}
+
+		let patch = if patch {
+			let mut opts = DiffOptions::new();
+			opts.context_lines(context_lines).pathspec(&relative);
+			let changes = repo
+				.diff_tree_to_tree(parents.first(), Some(&tree), Some(&mut opts))
+				.map_err(git_error)?;
+			Some(collect_file_diffs(&changes, ct)?)
+		} else {
+			None
+		};
+		let author = commit.author();
+		commits.push(GitCommit {
+			hash: commit.id().to_string(),
+			author: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+			email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+			date: author.when().seconds() as f64 * 1000.0,
+			subject: commit.summary().unwrap_or_default().to_string(),
+			patch,
+		});
+	}
+	Ok(commits)
+}
+
+const fn state_name(state: RepositoryState) -> &'static str {
+	match state {
+		RepositoryState::Clean => "clean",
+		RepositoryState::Merge => "merge",
+		RepositoryState::Revert | RepositoryState::RevertSequence => "revert",
+		RepositoryState::CherryPick | RepositoryState::CherryPickSequence => "cherryPick",
+		RepositoryState::Bisect => "bisect",
+		RepositoryState::Rebase | RepositoryState::ApplyMailboxOrRebase => "rebase",
+		RepositoryState::RebaseInteractive => "rebaseInteractive",
+		RepositoryState::RebaseMerge => "rebaseMerge",
+		RepositoryState::ApplyMailbox => "applyMailbox",
+	}
+}
+
+fn repo_info_sync(path: Option<&str>, ct: &task::CancelToken) -> Result<Option<GitRepoInfo>> {
+	let repo = match Repository::discover(path.unwrap_or(".")) {
+		Ok(repo) => repo,
+		Err(err) if err.code() == ErrorCode::NotFound => return Ok(None),
+		Err(err) => return Err(git_error(err)),
+	};
+	ct.heartbeat()?;
+	let git_dir = repo.path();
+	let root = repo.workdir().unwrap_or(git_dir);
+	let detached = repo.head_detached().unwrap_or(false);
+	let head = repo.head().ok();
+	let head_id = head.as_ref().and_then(|head| head.target());
+
+	// Unborn branches have no resolvable HEAD, so read the symbolic target.
+	let branch = if detached {
+		None
+	} else if let Some(head) = &head {
+		head.shorthand().map(str::to_string)
+	} else {
+		repo
+			.find_reference("HEAD")
+			.ok()
+			.and_then(|head| head.symbolic_target().map(str::to_string))
+			.map(|target| target.trim_start_matches("refs/heads/").to_string())
+	};
+
+	let mut upstream = None;
+	let (mut ahead, mut behind) = (0, 0);
+	if let Some(name) = &branch
+		&& let Ok(local) = repo.find_branch(name, BranchType::Local)
+		&& let Ok(remote) = local.upstream()
+	{
+		upstream = remote.name().ok().flatten().map(str::to_string);
+		// Counting walks history between the two tips, which can be long.
+		ct.heartbeat()?;
+		if let (Some(local_id), Some(remote_id)) = (local.get().target(), remote.get().target())
+			&& let Ok((a, b)) = repo.graph_ahead_behind(local_id, remote_id)
+		{
+			(ahead, behind) = (clamp_u32(a), clamp_u32(b));
+		}
+	}
+
+	// Submodule git dirs live under the superproject's `.git/modules/`.
+	let components: Vec<_> = git_dir.components().map(|c| c.as_os_str()).collect();
+	let submodule = components.windows(2).any(|pair| pair[0] == ".git" && pair[1] == "modules");
+
+	Ok(Some(GitRepoInfo {
+		root: path_string(root).trim_end_matches('/').to_string(),
+		git_dir: path_string(git_dir).trim_end_matches('/').to_string(),
+		branch,
+		head: head_id.map(|id| id.to_string()),
+		detached,
+		upstream,
+		ahead,
+		behind,
+		state: state_name(repo.state()).to_string(),
+		worktree: repo.is_worktree(),
+		submodule,
+		bare: repo.is_bare(),
+	}))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Hunk staging
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A selected hunk as line ranges: `(start, len)` in the text being rewritten
+/// and in the text supplying the replacement lines.
+struct HunkRange {
+	base:   (u32, u32),
+	source: (u32, u32),
+}
+
+/// 0-indexed line where a unified-diff range starts (ranges with zero lines
+/// name the line before the insertion point).
+const fn range_start((start, len): (u32, u32)) -> usize {
+	if len == 0 { start as usize } else { start.saturating_sub(1) as usize }
+}
+
+/// Replace each hunk's base lines with its source lines.
+fn splice_hunks(base: &[u8], source: &[u8], ranges: &[HunkRange]) -> Vec<u8> {
+	let base_lines: Vec<&[u8]> = base.split_inclusive(|&b| b == b'\n').collect();
+	let source_lines: Vec<&[u8]> = source.split_inclusive(|&b| b == b'\n').collect();
+	let mut out = Vec::with_capacity(base.len().max(source.len()));
+	let mut cursor = 0;
+	for range in ranges {
+		let start = range_start(range.base).min(base_lines.len());
+		let src_start = range_start(range.source).min(source_lines.len());
+		let src_end = (src_start + range.source.1 as usize).min(source_lines.len());
+		out.extend(base_lines[cursor.min(start)..start].concat());
+		out.extend(source_lines[src_start..src_end].concat());
+		cursor = (start + range.base.1 as usize).min(base_lines.len());
+	}
+	out.extend(base_lines[cursor..].concat());
+	out
+}
+
+fn blob_content(repo: &Repository, id: Oid) -> Result<Vec<u8>> {
+	Ok(repo.find_blob(id).map_err(git_error)?.content().to_vec())
+}
+
+fn new_index_entry(path: &[u8], mode: u32) -> IndexEntry {
+	IndexEntry {
+		ctime: IndexTime::new(0, 0),
+		mtime: IndexTime::new(0, 0),
+		dev: 0,
+		ino: 0,
+		mode,
+		uid: 0,
+		gid: 0,
+		file_size: 0,
+		id: Oid::zero(),
+		flags: 0,
+		flags_extended: 0,
+		path: path.to_vec(),
+	}
+}
+
+/// Stage (`unstage == false`) or unstage the hunks of `path` named by
+/// `hunk_ids`, returning how many were applied.
+fn update_hunks_sync(
+	path: &str,
+	hunk_ids: &[String],
+	repo_path: Option<&str>,
+	context_lines: u32,
+	unstage: bool,
+	ct: &task::CancelToken,
+) -> Result<u32> {
+	let repo = open_repo_for(path, repo_path)?;
+	let relative = repo_relative(&repo, path);
+	let rel_str = path_string(&relative);
+	let mut index = repo.index().map_err(git_error)?;
+	let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+
+	// The same diff as `gitDiff`, so its hunk ids match.
+	let mut opts = DiffOptions::new();
+	opts.context_lines(context_lines).ignore_submodules(true);
+	let mut changes = if unstage {
+		// No pathspec: renames pair up only with both paths in the diff.
+		repo.diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut opts))
+	} else {
+		// Untracked files are never paired into renames, so one path suffices.
+		opts
+			.pathspec(&relative)
+			.disable_pathspec_match(true)
+			.include_untracked(true)
+			.recurse_untracked_dirs(true)
+			.show_untracked_content(true);
+		repo.diff_index_to_workdir(Some(&index), Some(&mut opts))
+	}
+	.map_err(git_error)?;
+	changes
+		.find_similar(Some(DiffFindOptions::new().renames(true)))
+		.map_err(git_error)?;
+	ct.heartbeat()?;
+	let file = (0..changes.deltas().len())
+		.find(|&idx| {
+			changes.get_delta(idx).is_some_and(|delta| {
+				let path = delta.new_file().path().or_else(|| delta.old_file().path());
+				path.map(path_string).as_deref() == Some(rel_str.as_str())
+			})
+		})
+		.map(|idx| file_diff(&changes, idx))
+		.transpose()?
+		.flatten()
+		.ok_or_else(|| Error::from_reason(format!("No changes to {rel_str}")))?;
+
+	let mut ranges = Vec::with_capacity(hunk_ids.len());
+	for id in hunk_ids {
+		let pos = file.hunk_ids.iter().position(|candidate| candidate == id).ok_or_else(|| {
+			Error::from_reason(format!("Hunk {id} not found in {rel_str}; the diff has changed"))
+		})?;
+		let hunk = &file.hunks[pos];
+		let (old, new) = ((hunk.old_start, hunk.old_lines), (hunk.new_start, hunk.new_lines));
+		// Staging rewrites the index (old side) with working-tree lines;
+		// unstaging rewrites the index (new side) with HEAD lines.
+		ranges.push(if unstage {
+			HunkRange { base: new, source: old }
+		} else {
+			HunkRange { base: old, source: new }
+		});
+	}
+	ranges.sort_by_key(|range| range.base.0);
+	ranges.dedup_by_key(|range| range.base);
+	// Last point to give up before the index is rewritten.
+	ct.heartbeat()?;
+	let applied = clamp_u32(ranges.len());
+	let all_hunks = ranges.len() == file.hunks.len();
+
+	let index_entry = index.get_path(&relative, 0);
+	// HEAD holds a staged rename under its old path.
+	let head_path = file.old_path.as_deref().map_or_else(|| relative.clone(), PathBuf::from);
+	let head_entry = head_tree.as_ref().and_then(|tree| tree.get_path(&head_path).ok());
+	let index_content = match &index_entry {
+		Some(entry) => blob_content(&repo, entry.id)?,
+		None => Vec::new(),
+	};
+	let (content, template) = if unstage {
+		let head_content = match &head_entry {
+			Some(entry) => blob_content(&repo, entry.id())?,
+			None => Vec::new(),
+		};
+		if head_entry.is_none() && all_hunks {
+			// Unstaging every hunk of a newly added file removes it from the index.
+			index.remove_path(&relative).map_err(git_error)?;
+			index.write().map_err(git_error)?;
+			return Ok(applied);
+		}
+		let mode = head_entry.as_ref().map_or(0o100_644, |entry| entry.filemode() as u32);
+		let template = index_entry.unwrap_or_else(|| new_index_entry(rel_str.as_bytes(), mode));
+		(splice_hunks(&index_content, &head_content, &ranges), template)
+	} else {
+		let workdir = repo
+			.workdir()
+			.ok_or_else(|| Error::from_reason("Cannot stage hunks in a bare repository"))?;
+		let absolute = workdir.join(&relative);
+		let Ok(work_content) = std::fs::read(&absolute) else {
+			if all_hunks {
+				// Staging every hunk of a deleted file stages the deletion.
+				index.remove_path(&relative).map_err(git_error)?;
+				index.write().map_err(git_error)?;
+				return Ok(applied);
+			}
+			return Err(Error::from_reason(format!("Failed to read {}", absolute.display())));
+		};
+		let mode = executable_mode(&absolute);
+		let template = index_entry.unwrap_or_else(|| new_index_entry(rel_str.as_bytes(), mode));
+		(splice_hunks(&index_content, &work_content, &ranges), template)
+	};
+
+	// Clear cached stat data so git rehashes the working-tree file.
+	let mut entry = template;
+	entry.ctime = IndexTime::new(0, 0);
+	entry.mtime = IndexTime::new(0, 0);
+	entry.file_size = clamp_u32(content.len());
+	index.add_frombuffer(&entry, &content).map_err(git_error)?;
+	index.write().map_err(git_error)?;
+	Ok(applied)
+}
+
+#[cfg(unix)]
+fn executable_mode(path: &Path) -> u32 {
+	use std::os::unix::fs::PermissionsExt;
+	let executable =
+		std::fs::metadata(path).is_ok_and(|meta| meta.permissions().mode() & 0o111 != 0);
+	if executable { 0o100_755 } else { 0o100_644 }
+}
+
+#[cfg(not(unix))]
+fn executable_mode(_path: &Path) -> u32 {
+	0o100_644
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Diff the working tree (or the index with `staged`) into structured hunks.
+///
+/// Untracked files are included as additions in working-tree diffs, and
+/// renames are detected.
+///
+/// # Errors
+/// Returns an error if no repository is found, libgit2 fails, or the operation
+/// is cancelled.
+#[napi(js_name = "gitDiff")]
+pub fn git_diff(options: Option<GitDiffOptions<'_>>) -> task::Async<GitDiffResult> {
+	let (repo_path, paths, staged, context_lines, signal, timeout_ms) = match options {
+		Some(opts) => (
+			opts.repo_path,
+			opts.paths,
+			opts.staged,
+			opts.context_lines,
+			opts.signal,
+			opts.timeout_ms,
+		),
+		None => (None, None, None, None, None, None),
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("git_diff", ct, move |ct| {
+		git_diff_sync(
+			repo_path.as_deref(),
+			&paths.unwrap_or_default(),
+			staged.unwrap_or(false),
+			context_lines.unwrap_or(3),
+			&ct,
+		)
+	})
+}
+
+/// List recent commits touching a file or directory, newest first.
+///
+/// # Errors
+/// Returns an error if no repository is found, libgit2 fails, or the operation
+/// is cancelled.
+#[napi(js_name = "gitLog")]
+pub fn git_log(options: GitLogOptions<'_>) -> task::Async<Vec<GitCommit>> {
+	let GitLogOptions { path, repo_path, max_count, patch, context_lines, signal, timeout_ms } =
+		options;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("git_log", ct, move |ct| {
+		git_log_sync(
+			&path,
+			repo_path.as_deref(),
+			max_count.unwrap_or(20) as usize,
+			patch.unwrap_or(false),
+			context_lines.unwrap_or(3),
+			&ct,
+		)
+	})
+}
+
+/// Options for `gitRepoInfo`.
+#[napi(object)]
+pub struct GitRepoInfoOptions<'env> {
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Describe the repository containing `path` (default: current directory).
+///
+/// # Returns
+/// Root, branch, upstream tracking, and in-progress state, or `null` when
+/// `path` is not inside a repository.
+///
+/// # Errors
+/// Returns an error if the repository cannot be read, or the operation is
+/// cancelled or times out.
+#[napi(js_name = "gitRepoInfo")]
+pub fn git_repo_info(
+	path: Option<String>,
+	options: Option<GitRepoInfoOptions<'_>>,
+) -> task::Async<Option<GitRepoInfo>> {
+	let (signal, timeout_ms) = options.map_or((None, None), |opts| (opts.signal, opts.timeout_ms));
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("git_repo_info", ct, move |ct| repo_info_sync(path.as_deref(), &ct))
+}
+
+/// Options for `gitStageHunks` and `gitUnstageHunks`.
+#[napi(object)]
+pub struct GitHunkOptions<'env> {
+	/// Path inside the repository (default: the parent of `path`).
+	#[napi(js_name = "repoPath")]
+	pub repo_path:     Option<String>,
+	/// Context lines used when the hunk ids were computed (default: 3).
+	#[napi(js_name = "contextLines")]
+	pub context_lines: Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:        Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:    Option<u32>,
+}
+
+fn run_hunk_update(
+	tag: &'static str,
+	path: String,
+	hunk_ids: Vec<String>,
+	options: Option<GitHunkOptions<'_>>,
+	unstage: bool,
+) -> task::Async<u32> {
+	let (repo_path, context_lines, signal, timeout_ms) = match options {
+		Some(opts) => (opts.repo_path, opts.context_lines, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking(tag, ct, move |ct| {
+		update_hunks_sync(
+			&path,
+			&hunk_ids,
+			repo_path.as_deref(),
+			context_lines.unwrap_or(3),
+			unstage,
+			&ct,
+		)
+	})
+}
+
+/// Stage individual working-tree hunks of a file, identified by the
+/// `hunkIds` of a `gitDiff` result.
+///
+/// # Returns
+/// The number of hunks staged.
+///
+/// # Errors
+/// Returns an error if a hunk id no longer matches the current diff, the index
+/// cannot be written, or the operation is cancelled or times out.
+#[napi(js_name = "gitStageHunks")]
+pub fn git_stage_hunks(
+	path: String,
+	hunk_ids: Vec<String>,
+	options: Option<GitHunkOptions<'_>>,
+) -> task::Async<u32> {
+	run_hunk_update("git_stage_hunks", path, hunk_ids, options, false)
+}
+
+/// Unstage individual hunks of a file, identified by the `hunkIds` of a staged
+/// `gitDiff` result.
+///
+/// # Returns
+/// The number of hunks unstaged.
+///
+/// # Errors
+/// Returns an error if a hunk id no longer matches the current diff, the index
+/// cannot be written, or the operation is cancelled or times out.
+#[napi(js_name = "gitUnstageHunks")]
+pub fn git_unstage_hunks(
+	path: String,
+	hunk_ids: Vec<String>,
+	options: Option<GitHunkOptions<'_>>,
+) -> task::Async<u32> {
+	run_hunk_update("git_unstage_hunks", path, hunk_ids, options, true)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::ScratchDir;
+
+	const ORIGINAL: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+	const CHANGED: &str = "one\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\ntwelve\n";
+
+	/// A repository with `file.txt` committed as [`ORIGINAL`] and changed in
+	/// the working tree to [`CHANGED`], two hunks apart.
+	fn scratch(name: &str) -> (ScratchDir, Repository) {
+		let root = ScratchDir::new(&format!("git-{name}"));
+		let repo = Repository::init(&root).unwrap();
+		std::fs::write(root.join("file.txt"), ORIGINAL).unwrap();
+		let mut index = repo.index().unwrap();
+		index.add_path(Path::new("file.txt")).unwrap();
+		index.write().unwrap();
+		let tree_id = index.write_tree().unwrap();
+		let signature = git2::Signature::now("test", "test@example.com").unwrap();
+		let tree = repo.find_tree(tree_id).unwrap();
+		repo
+			.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+			.unwrap();
+		drop(tree);
+		std::fs::write(root.join("file.txt"), CHANGED).unwrap();
+		(root, repo)
+	}
+
+	fn hunk_ids(root: &Path, path: &str, staged: bool) -> Vec<String> {
+		let root = root.to_string_lossy();
+		let diff = git_diff_sync(Some(&root), &[], staged, 3, &task::CancelToken::default()).unwrap();
+		diff
+			.files
+			.into_iter()
+			.filter(|file| file.path == path)
+			.flat_map(|file| file.hunk_ids)
+			.collect()
+	}
+
+	fn indexed(repo: &Repository, path: &str) -> Option<String> {
+		let mut index = repo.index().unwrap();
+		index.read(true).unwrap();
+		let entry = index.get_path(Path::new(path), 0)?;
+		Some(String::from_utf8(blob_content(repo, entry.id).unwrap()).unwrap())
+	}
+
+	fn update(root: &Path, path: &str, ids: &[String], unstage: bool) -> Result<u32> {
+		let path = root.join(path);
+		update_hunks_sync(
+			&path.to_string_lossy(),
+			ids,
+			None,
+			3,
+			unstage,
+			&task::CancelToken::default(),
+		)
+	}
+
+	#[test]
+	fn test_stage_and_unstage_one_hunk() {
+		let (root, repo) = scratch("partial");
+		let ids = hunk_ids(&root, "file.txt", false);
+		assert_eq!(ids.len(), 2);
+
+		assert_eq!(update(&root, "file.txt", &ids[1..], false).unwrap(), 1);
+		assert_eq!(
+			indexed(&repo, "file.txt").unwrap(),
+			"1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\ntwelve\n"
+		);
+		// The other hunk is still unstaged, and keeps its id.
+		assert_eq!(hunk_ids(&root, "file.txt", false), &ids[..1]);
+
+		let staged = hunk_ids(&root, "file.txt", true);
+		assert_eq!(staged.len(), 1);
+		assert_eq!(update(&root, "file.txt", &staged, true).unwrap(), 1);
+		assert_eq!(indexed(&repo, "file.txt").unwrap(), ORIGINAL);
+		assert_eq!(std::fs::read_to_string(root.join("file.txt")).unwrap(), CHANGED);
+	}
+
+	#[test]
+	fn test_stage_rejects_stale_hunk_ids() {
+		let (root, repo) = scratch("stale");
+		let ids = hunk_ids(&root, "file.txt", false);
+		std::fs::write(root.join("file.txt"), "ONE\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\ntwelve\n")
+			.unwrap();
+
+		let err = update(&root, "file.txt", &ids[..1], false).unwrap_err();
+		assert!(err.reason.contains("the diff has changed"), "{}", err.reason);
+		assert_eq!(indexed(&repo, "file.txt").unwrap(), ORIGINAL);
+	}
+
+	#[test]
+	fn test_stage_all_hunks_of_deleted_file() {
+		let (root, repo) = scratch("deleted");
+		std::fs::remove_file(root.join("file.txt")).unwrap();
+		let ids = hunk_ids(&root, "file.txt", false);
+
+		assert_eq!(update(&root, "file.txt", &ids, false).unwrap(), 1);
+		assert_eq!(indexed(&repo, "file.txt"), None);
+	}
+
+	#[test]
+	fn test_stage_file_in_new_directory() {
+		let (root, repo) = scratch("newdir");
+		std::fs::create_dir_all(root.join("newdir")).unwrap();
+		std::fs::write(root.join("newdir/f.txt"), "a\nb\n").unwrap();
+		let ids = hunk_ids(&root, "newdir/f.txt", false);
+		assert_eq!(ids.len(), 1);
+
+		assert_eq!(update(&root, "newdir/f.txt", &ids, false).unwrap(), 1);
+		assert_eq!(indexed(&repo, "newdir/f.txt").as_deref(), Some("a\nb\n"));
+	}
+
+	#[test]
+	fn test_unstage_hunk_of_staged_rename() {
+		let (root, repo) = scratch("rename");
+		// `git mv file.txt renamed.txt`, with both edits staged.
+		std::fs::rename(root.join("file.txt"), root.join("renamed.txt")).unwrap();
+		let mut index = repo.index().unwrap();
+		index.remove_path(Path::new("file.txt")).unwrap();
+		index.add_path(Path::new("renamed.txt")).unwrap();
+		index.write().unwrap();
+		let ids = hunk_ids(&root, "renamed.txt", true);
+		assert_eq!(ids.len(), 2);
+
+		assert_eq!(update(&root, "renamed.txt", &ids[..1], true).unwrap(), 1);
+		assert_eq!(
+			indexed(&repo, "renamed.txt").as_deref(),
+			Some("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\ntwelve\n")
+		);
+		// The rename itself stays staged.
+		assert_eq!(indexed(&repo, "file.txt"), None);
+		assert_eq!(hunk_ids(&root, "renamed.txt", true), &ids[1..]);
+	}
+}
@@ -6,15 +6,37 @@
 //! glob matching plus optional file-type filtering, and optionally streams each
 //! accepted match through a callback.
 //!
+//! `pattern` and `patterns` are combined: an entry matches if any of them
+//! does and no exclusion does. Exclusions come from `exclude` and from
+//! `patterns` entries starting with `!`; all globs are compiled into one
+//! `GlobSet`, so a single walk answers every pattern.
+//!
+//! Uncached walks filter entries on the walker and send matches over a
+//! channel, so `maxResults` stops the walk early. They use one thread in path
+//! order by default; with more `threads`, matches arrive in no particular
+//! order unless `sortByPath` is set.
+//!
+//! `globStream` walks on a background thread instead and hands matches out in
+//! batches the caller pulls with `next`, so huge trees never flood the event
+//! loop. The walk reads ahead into a bounded buffer and blocks when it fills.
+//!
 //! The walker always skips `.git`, and skips `node_modules` unless explicitly
 //! requested.
 //!
 //! # Example
 //! ```ignore
 //! // JS: await native.glob({ pattern: "*.rs", path: "." })
+//! // JS: const stream = native.globStream({ pattern: "*.rs", path: "." });
+//! // JS: for (let batch; (batch = await stream.next(500)).length > 0; ) render(batch);
 //! ```
 
-use std::path::Path;
+use std::{
+	fmt::Write as _,
+	path::{Path, PathBuf},
+	sync::{Arc, mpsc as std_mpsc},
+	thread,
+	time::Duration,
+};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use napi::{
@@ -22,16 +44,23 @@ use napi::{
 	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
 };
 use napi_derive::napi;
+use parking_lot::{Condvar, Mutex};
+use tokio::sync::mpsc;
 
 // Re-export entry types so existing `glob::FileType` / `glob::GlobMatch` paths still work.
 pub use crate::fs_cache::{FileType, GlobMatch};
-use crate::{fs_cache, task};
+use crate::{binary, fs_cache, task};
 
 /// Input options for `glob`, including traversal, filtering, and cancellation.
-#[napi(object)]
+#[napi(object, object_to_js = false)]
 pub struct GlobOptions<'env> {
 	/// Glob pattern to match (e.g., "*.ts").
-	pub pattern:              String,
+	pub pattern:              Option<String>,
+	/// More patterns to match; entries starting with `!` are exclusions.
+	pub patterns:             Option<Vec<String>>,
+	/// Patterns to skip, even when another pattern matches. An excluded
+	/// directory excludes everything under it.
+	pub exclude:              Option<Vec<String>>,
 	/// Directory to search.
 	pub path:                 String,
 	/// Filter by file type: "file", "dir", or "symlink".
@@ -49,6 +78,35 @@ pub struct GlobOptions<'env> {
 	/// Sort results by mtime (most recent first) before applying limit.
 	#[napi(js_name = "sortByMtime")]
 	pub sort_by_mtime:        Option<bool>,
+	/// Sort results by path before applying limit, so a parallel walk returns
+	/// the same matches in the same order every time.
+	#[napi(js_name = "sortByPath")]
+	pub sort_by_path:         Option<bool>,
+	/// Number of walker threads; 0 picks one from the available parallelism
+	/// (default: 1). A single thread walks in path order; more return matches
+	/// in the order found. Ignored when `cache` is set.
+	pub threads:              Option<u32>,
+	/// Walk into symlinked directories (default: false). Each directory is
+	/// entered once, so cycles terminate and a directory reached again, through
+	/// a link or directly, is left out. `fileType` then also matches a link by
+	/// its target's type. Always walks without the cache.
+	#[napi(js_name = "followSymlinks")]
+	pub follow_symlinks:      Option<bool>,
+	/// Deepest level to return and walk, counting the root's children as 1.
+	#[napi(js_name = "maxDepth")]
+	pub max_depth:            Option<u32>,
+	/// Shallowest level to return; shallower entries are still walked.
+	#[napi(js_name = "minDepth")]
+	pub min_depth:            Option<u32>,
+	/// Called with each directory's root-relative path before walking into
+	/// it; returning `false` skips the directory and everything under it.
+	/// Always walks without the cache.
+	#[napi(js_name = "shouldDescend", ts_type = "(path: string) => boolean")]
+	pub should_descend:       Option<DescendCallback>,
+	/// Fill in `size`, `depth`, and `isBinary` on each match (default: false).
+	/// Sniffing for binaries reads the first block of every matched file.
+	#[napi(js_name = "withMetadata")]
+	pub with_metadata:        Option<bool>,
 	/// Include `node_modules` entries when the pattern does not explicitly
 	/// mention them.
 	#[napi(js_name = "includeNodeModules")]
@@ -69,47 +127,208 @@ pub struct GlobResult {
 	pub total_matches: u32,
 }
 
-fn build_glob_pattern(glob: &str) -> String {
-	let normalized = if cfg!(windows) && glob.contains('\\') {
+/// How `glob` interprets a pattern.
+#[napi(object)]
+pub struct GlobExplanation {
+	/// Pattern as given.
+	pub input:                 String,
+	/// Pattern after rewriting, as matched against root-relative paths.
+	pub pattern:               String,
+	/// Regular expression source the pattern compiles to, usable as a JS
+	/// `RegExp`.
+	pub regex:                 String,
+	/// Whether backslashes were converted to `/` (Windows only).
+	#[napi(js_name = "normalizedSeparators")]
+	pub normalized_separators: bool,
+	/// Whether `**/` was prepended because the pattern has no `/`.
+	#[napi(js_name = "anyDirectory")]
+	pub any_directory:         bool,
+	/// Human-readable notes on the rewrites and matching rules that apply.
+	pub notes:                 Vec<String>,
+}
+
+/// How a user pattern is rewritten before compilation.
+struct GlobRewrite {
+	/// Pattern handed to globset.
+	pattern:               String,
+	/// Backslashes were converted to `/` (Windows only).
+	normalized_separators: bool,
+	/// `**/` was prepended so the pattern matches in any directory.
+	any_directory:         bool,
+}
+
+fn rewrite_glob(glob: &str) -> GlobRewrite {
+	let normalized_separators = cfg!(windows) && glob.contains('\\');
+	let normalized = if normalized_separators {
 		std::borrow::Cow::Owned(glob.replace('\\', "/"))
 	} else {
 		std::borrow::Cow::Borrowed(glob)
 	};
-	if normalized.contains('/') || normalized.starts_with("**") {
-		normalized.into_owned()
-	} else {
+	let any_directory = !normalized.contains('/') && !normalized.starts_with("**");
+	let pattern = if any_directory {
 		format!("**/{normalized}")
+	} else {
+		normalized.into_owned()
+	};
+	GlobRewrite { pattern, normalized_separators, any_directory }
+}
+
+/// Rewrite a user glob the way `glob` does: backslashes become `/` on
+/// Windows, and patterns without `/` match in any directory.
+pub fn build_glob_pattern(glob: &str) -> String {
+	rewrite_glob(glob).pattern
+}
+
+/// Include and exclude globs compiled into one set.
+struct GlobFilter {
+	set:           GlobSet,
+	/// Globs at or past this index in `set` are exclusions.
+	exclude_start: usize,
+}
+
+impl GlobFilter {
+	fn is_match(&self, path: &str) -> bool {
+		if self.exclude_start == self.set.len() {
+			return self.set.is_match(path);
+		}
+		let matches = self.set.matches(path);
+		matches.iter().any(|&index| index < self.exclude_start)
+			&& !matches.iter().any(|&index| index >= self.exclude_start)
 	}
 }
 
-fn compile_glob(glob: &str) -> Result<GlobSet> {
+fn parse_glob(pattern: &str) -> Result<Glob> {
+	Glob::new(pattern).map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))
+}
+
+/// Compile include patterns (`!`-prefixed ones are exclusions) and exclusions
+/// into a single filter. With no include patterns, everything is included.
+fn compile_filter(patterns: &[String], exclude: &[String]) -> Result<GlobFilter> {
+	let (negated, includes): (Vec<&str>, Vec<&str>) = patterns
+		.iter()
+		.map(String::as_str)
+		.partition(|pattern| pattern.starts_with('!'));
+	let includes = if includes.is_empty() {
+		vec!["*"]
+	} else {
+		includes
+	};
 	let mut builder = GlobSetBuilder::new();
-	let pattern = build_glob_pattern(glob);
-	let glob = Glob::new(&pattern)
-		.map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))?;
-	builder.add(glob);
-	builder
+	for pattern in &includes {
+		builder.add(parse_glob(&build_glob_pattern(pattern))?);
+	}
+	let excluded = negated
+		.iter()
+		.map(|pattern| &pattern[1..])
+		.filter(|pattern| !pattern.is_empty())
+		.chain(exclude.iter().map(String::as_str));
+	for pattern in excluded {
+		let pattern = build_glob_pattern(pattern.trim_end_matches('/'));
+		builder.add(parse_glob(&pattern)?);
+		builder.add(parse_glob(&format!("{pattern}/**"))?);
+	}
+	let set = builder
 		.build()
-		.map_err(|err| Error::from_reason(format!("Failed to build glob matcher: {err}")))
+		.map_err(|err| Error::from_reason(format!("Failed to build glob matcher: {err}")))?;
+	Ok(GlobFilter { set, exclude_start: includes.len() })
 }
 
+/// JS predicate deciding whether a directory is walked.
+type DescendCallback = ThreadsafeFunction<String, bool, String, Status, false>;
+
+/// Matches a `globStream` walk reads ahead before blocking on the consumer.
+const STREAM_BUFFER: usize = 1024;
+
+/// How often a paused walk rechecks its abort signal and timeout.
+const STREAM_PAUSE_POLL: Duration = Duration::from_millis(50);
+
 /// Internal runtime config for a single glob execution.
 struct GlobConfig {
-	root:                  std::path::PathBuf,
-	pattern:               String,
+	root:                  PathBuf,
+	patterns:              Vec<String>,
+	exclude:               Vec<String>,
 	include_hidden:        bool,
 	file_type_filter:      Option<FileType>,
 	max_results:           usize,
 	use_gitignore:         bool,
 	mentions_node_modules: bool,
 	sort_by_mtime:         bool,
+	sort_by_path:          bool,
+	threads:               usize,
+	with_metadata:         bool,
+	min_depth:             usize,
+	scope:                 fs_cache::WalkScope,
 	use_cache:             bool,
 }
 
+impl GlobConfig {
+	/// Whether results are sorted, so every candidate must be collected before
+	/// applying the limit.
+	const fn sorted(&self) -> bool {
+		self.sort_by_mtime || self.sort_by_path
+	}
+}
+
+/// Ask `callback` whether to walk into the directory at `path`, blocking until
+/// it answers. Walks into it when the call fails or returns a non-boolean.
+fn ask_descend(callback: &DescendCallback, path: &str) -> bool {
+	let (sender, receiver) = std_mpsc::sync_channel(1);
+	let status = callback.call_with_return_value(
+		path.to_string(),
+		ThreadsafeFunctionCallMode::Blocking,
+		move |result: Result<bool>, _env| {
+			let _ = sender.send(result.unwrap_or(true));
+			Ok(())
+		},
+	);
+	status != Status::Ok || receiver.recv().unwrap_or(true)
+}
+
+/// Number of components in a root-relative path.
+fn path_depth(path: &str) -> usize {
+	path.bytes().filter(|&byte| byte == b'/').count() + 1
+}
+
+/// Whether `entry` passes the `node_modules` policy, depth range, glob, and
+/// file-type filter. When following symlinks, a link also passes as its
+/// target's type.
+fn accepts(entry: &GlobMatch, filter: &GlobFilter, config: &GlobConfig) -> bool {
+	let depth = path_depth(&entry.path);
+	// Apply post-scan node_modules policy before glob matching.
+	!fs_cache::should_skip_path(Path::new(&entry.path), config.mentions_node_modules)
+		&& depth >= config.min_depth
+		&& config
+			.scope
+			.max_depth
+			.is_none_or(|max_depth| depth <= max_depth)
+		&& filter.is_match(&entry.path)
+		&& config.file_type_filter.is_none_or(|filter| {
+			filter == entry.file_type
+				|| (config.scope.follow_symlinks && Some(filter) == entry.target_type)
+		})
+}
+
+/// Fill in size, depth, and the binary hint for an accepted `entry` when
+/// `config.with_metadata` is set.
+fn with_metadata(mut entry: GlobMatch, config: &GlobConfig) -> GlobMatch {
+	if !config.with_metadata {
+		return entry;
+	}
+	entry.depth = Some(path_depth(&entry.path) as u32);
+	if entry.file_type == FileType::File {
+		let path = config.root.join(&entry.path);
+		entry.size = std::fs::metadata(&path).ok().map(|meta| meta.len() as f64);
+		entry.is_binary = binary::read_sample(&path)
+			.ok()
+			.map(|sample| binary::is_binary(&sample));
+	}
+	entry
+}
+
 /// Filter and collect matching entries from a pre-scanned list.
 fn filter_entries(
 	entries: &[GlobMatch],
-	glob_set: &GlobSet,
+	filter: &GlobFilter,
 	config: &GlobConfig,
 	on_match: Option<&ThreadsafeFunction<GlobMatch>>,
 	ct: &task::CancelToken,
@@ -121,32 +340,73 @@ fn filter_entries(
 
 	for entry in entries {
 		ct.heartbeat()?;
-		if fs_cache::should_skip_path(Path::new(&entry.path), config.mentions_node_modules) {
-			// Apply post-scan node_modules policy before glob matching.
-			continue;
-		}
-		if !glob_set.is_match(&entry.path) {
-			continue;
-		}
-		if config
-			.file_type_filter
-			.is_some_and(|filter| filter != entry.file_type)
-		{
+		if !accepts(entry, filter, config) {
 			continue;
 		}
+		let entry = with_metadata(entry.clone(), config);
 		if let Some(callback) = on_match {
 			callback.call(Ok(entry.clone()), ThreadsafeFunctionCallMode::NonBlocking);
 		}
 
-		matches.push(entry.clone());
-		// Only early-break when not sorting; mtime sort requires full candidate set.
-		if !config.sort_by_mtime && matches.len() >= config.max_results {
+		matches.push(entry);
+		// Only early-break when not sorting; sorting requires full candidate set.
+		if !config.sorted() && matches.len() >= config.max_results {
 			break;
 		}
 	}
 	Ok(matches)
 }
 
+/// Walk `config.root` without the cache, filtering on the walker threads and
+/// collecting matches through a channel until the walk ends or the limit is
+/// reached.
+fn walk_matches(
+	config: &GlobConfig,
+	filter: &GlobFilter,
+	on_match: Option<&ThreadsafeFunction<GlobMatch>>,
+	ct: &task::CancelToken,
+) -> Result<Vec<GlobMatch>> {
+	let limit = if config.sorted() {
+		usize::MAX
+	} else {
+		config.max_results
+	};
+	let (sender, receiver) = std_mpsc::channel();
+	thread::scope(|scope| {
+		let walker = scope.spawn(move || {
+			fs_cache::walk_entries_parallel(
+				&config.root,
+				config.include_hidden,
+				config.use_gitignore,
+				&config.scope,
+				config.threads,
+				ct,
+				|entry| {
+					// Sending fails once the receiver is gone at the limit.
+					!accepts(&entry, filter, config) || sender.send(with_metadata(entry, config)).is_ok()
+				},
+			)
+		});
+
+		let mut matches = Vec::new();
+		for entry in &receiver {
+			if let Some(callback) = on_match {
+				callback.call(Ok(entry.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+			}
+			matches.push(entry);
+			if matches.len() >= limit {
+				break;
+			}
+		}
+		drop(receiver);
+
+		walker
+			.join()
+			.unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+		Ok(matches)
+	})
+}
+
 /// Executes matching/filtering over scanned entries and optionally streams each
 /// hit.
 fn run_glob(
@@ -154,7 +414,7 @@ fn run_glob(
 	on_match: Option<&ThreadsafeFunction<GlobMatch>>,
 	ct: task::CancelToken,
 ) -> Result<GlobResult> {
-	let glob_set = compile_glob(&config.pattern)?;
+	let filter = compile_filter(&config.patterns, &config.exclude)?;
 	if config.max_results == 0 {
 		return Ok(GlobResult { matches: Vec::new(), total_matches: 0 });
 	}
@@ -162,7 +422,7 @@ fn run_glob(
 	let mut matches = if config.use_cache {
 		let scan =
 			fs_cache::get_or_scan(&config.root, config.include_hidden, config.use_gitignore, &ct)?;
-		let mut matches = filter_entries(&scan.entries, &glob_set, &config, on_match, &ct)?;
+		let mut matches = filter_entries(&scan.entries, &filter, &config, on_match, &ct)?;
 		// Empty-result recheck: if we got zero matches from a cached scan that's old
 		// enough, force a rescan and try once more before returning empty.
 		if matches.is_empty() && scan.cache_age_ms >= fs_cache::empty_recheck_ms() {
@@ -173,22 +433,21 @@ fn run_glob(
 				true,
 				&ct,
 			)?;
-			matches = filter_entries(&fresh, &glob_set, &config, on_match, &ct)?;
+			matches = filter_entries(&fresh, &filter, &config, on_match, &ct)?;
 		}
 		matches
 	} else {
-		let fresh = fs_cache::force_rescan(
-			&config.root,
-			config.include_hidden,
-			config.use_gitignore,
-			false,
-			&ct,
-		)?;
-		filter_entries(&fresh, &glob_set, &config, on_match, &ct)?
+		fs_cache::drop_scan(&config.root, config.include_hidden, config.use_gitignore);
+		walk_matches(&config, &filter, on_match, &ct)?
 	};
 
+	if config.sorted() {
+		// Parallel walks finish in any order; sorting by path first also gives
+		// equal mtimes a stable order.
+		matches.sort_by(|a, b| Path::new(&a.path).cmp(Path::new(&b.path)));
+	}
 	if config.sort_by_mtime {
-		// Sorting mode: rank by mtime descending, then apply max-results truncation.
+		// Rank by mtime descending; the stable sort keeps ties in path order.
 		matches.sort_by(|a, b| {
 			let a_mtime = a.mtime.unwrap_or(0.0);
 			let b_mtime = b.mtime.unwrap_or(0.0);
@@ -196,6 +455,8 @@ fn run_glob(
 				.partial_cmp(&a_mtime)
 				.unwrap_or(std::cmp::Ordering::Equal)
 		});
+	}
+	if config.sorted() {
 		matches.truncate(config.max_results);
 	}
 	let total_matches = matches.len().min(u32::MAX as usize) as u32;
@@ -207,8 +468,10 @@ fn run_glob(
 /// Resolves the search root, scans entries, applies glob and optional file-type
 /// filters, and optionally streams each accepted match through `on_match`.
 ///
-/// If `sortByMtime` is enabled, all matching entries are collected, sorted by
-/// descending mtime, then truncated to `maxResults`.
+/// If `sortByMtime` or `sortByPath` is enabled, all matching entries are
+/// collected, sorted, then truncated to `maxResults`. Otherwise an uncached
+/// walk returns matches in path order, or in the order they are found when
+/// `threads` is not 1.
 ///
 /// # Errors
 /// Returns an error when the search path cannot be resolved, the path is not a
@@ -221,42 +484,370 @@ pub fn glob(
 		ThreadsafeFunction<GlobMatch>,
 	>,
 ) -> task::Async<GlobResult> {
+	let (path, config, ct) = split_options(options);
+	task::blocking("glob", ct, move |ct| {
+		run_glob(config(fs_cache::resolve_search_path(&path)?), on_match.as_ref(), ct)
+	})
+}
+
+/// Split `options` into the search path, a config builder taking the resolved
+/// root, and the cancel token.
+fn split_options(
+	options: GlobOptions<'_>,
+) -> (String, impl FnOnce(PathBuf) -> GlobConfig + Send + 'static, task::CancelToken) {
 	let GlobOptions {
 		pattern,
+		patterns,
+		exclude,
 		path,
 		file_type,
 		hidden,
 		max_results,
 		gitignore,
 		sort_by_mtime,
+		sort_by_path,
+		threads,
+		with_metadata,
+		follow_symlinks,
+		max_depth,
+		min_depth,
+		should_descend,
 		cache,
 		include_node_modules,
 		timeout_ms,
 		signal,
 	} = options;
 
-	let pattern = pattern.trim();
-	let pattern = if pattern.is_empty() { "*" } else { pattern };
-	let pattern = pattern.to_string();
+	let patterns: Vec<String> = pattern
+		.into_iter()
+		.chain(patterns.unwrap_or_default())
+		.map(|pattern| pattern.trim().to_string())
+		.filter(|pattern| !pattern.is_empty())
+		.collect();
 
 	let ct = task::CancelToken::new(timeout_ms, signal);
-
-	task::blocking("glob", ct, move |ct| {
-		run_glob(
-			GlobConfig {
-				root: fs_cache::resolve_search_path(&path)?,
-				include_hidden: hidden.unwrap_or(false),
-				file_type_filter: file_type,
-				max_results: max_results.map_or(usize::MAX, |value| value as usize),
-				use_gitignore: gitignore.unwrap_or(true),
-				mentions_node_modules: include_node_modules
-					.unwrap_or_else(|| pattern.contains("node_modules")),
-				sort_by_mtime: sort_by_mtime.unwrap_or(false),
-				use_cache: cache.unwrap_or(false),
-				pattern,
+	let config = move |root| {
+		let mentions_node_modules = include_node_modules.unwrap_or_else(|| {
+			patterns
+				.iter()
+				.any(|pattern| !pattern.starts_with('!') && pattern.contains("node_modules"))
+		});
+		// Cached scans cover the whole tree; following links or pruning needs a
+		// fresh walk.
+		let use_cache =
+			cache.unwrap_or(false) && follow_symlinks != Some(true) && should_descend.is_none();
+		let descend = should_descend.map(|callback| {
+			Arc::new(move |path: &str| {
+				// No JS call where the node_modules policy drops entries.
+				fs_cache::should_skip_path(Path::new(path), mentions_node_modules)
+					|| ask_descend(&callback, path)
+			}) as Arc<fs_cache::DescendFilter>
+		});
+		GlobConfig {
+			root,
+			include_hidden: hidden.unwrap_or(false),
+			file_type_filter: file_type,
+			max_results: max_results.map_or(usize::MAX, |value| value as usize),
+			use_gitignore: gitignore.unwrap_or(true),
+			mentions_node_modules,
+			sort_by_mtime: sort_by_mtime.unwrap_or(false),
+			sort_by_path: sort_by_path.unwrap_or(false),
+			threads: threads.unwrap_or(1) as usize,
+			with_metadata: with_metadata.unwrap_or(false),
+			min_depth: min_depth.unwrap_or(0) as usize,
+			scope: fs_cache::WalkScope {
+				follow_symlinks: follow_symlinks.unwrap_or(false),
+				max_depth: max_depth.map(|depth| depth as usize),
+				descend,
 			},
-			on_match.as_ref(),
-			ct,
-		)
+			use_cache,
+			patterns,
+			exclude: exclude.unwrap_or_default(),
+		}
+	};
+	(path, config, ct)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Streaming
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Default)]
+struct StreamState {
+	paused:    bool,
+	cancelled: bool,
+	/// Error that ended the walk early, reported once the buffer drains.
+	error:     Option<String>,
+}
+
+/// State shared between a `GlobStream` and its walker thread.
+#[derive(Default)]
+struct StreamShared {
+	state:   Mutex<StreamState>,
+	resumed: Condvar,
+}
+
+impl StreamShared {
+	/// Block while paused; returns `false` once the stream is cancelled.
+	fn wait_while_paused(&self, ct: &task::CancelToken) -> bool {
+		let mut state = self.state.lock();
+		while state.paused && !state.cancelled && !ct.aborted() {
+			self.resumed.wait_for(&mut state, STREAM_PAUSE_POLL);
+		}
+		!state.cancelled
+	}
+}
+
+/// Walk `config.root`, sending accepted entries until the walk ends, the
+/// limit is reached, or the stream is cancelled.
+fn stream_walk(
+	config: &GlobConfig,
+	filter: &GlobFilter,
+	shared: &StreamShared,
+	sender: &mpsc::Sender<GlobMatch>,
+	ct: &task::CancelToken,
+) -> Result<()> {
+	let mut sent = 0;
+	fs_cache::walk_entries(
+		&config.root,
+		config.include_hidden,
+		config.use_gitignore,
+		&config.scope,
+		ct,
+		|entry| {
+			if !shared.wait_while_paused(ct) {
+				return false;
+			}
+			if !accepts(&entry, filter, config) {
+				return true;
+			}
+			// Fails once the stream is cancelled or dropped.
+			if sender.blocking_send(with_metadata(entry, config)).is_err() {
+				return false;
+			}
+			sent += 1;
+			sent < config.max_results
+		},
+	)
+}
+
+/// Glob matches produced by a background walk and pulled in batches.
+#[napi]
+pub struct GlobStream {
+	shared:   Arc<StreamShared>,
+	receiver: Arc<tokio::sync::Mutex<Option<mpsc::Receiver<GlobMatch>>>>,
+}
+
+impl Drop for GlobStream {
+	fn drop(&mut self) {
+		// Let a paused walker thread exit instead of waiting forever.
+		self.cancel();
+	}
+}
+
+#[napi]
+impl GlobStream {
+	/// Resolve with up to `batchSize` matches, waiting until at least one is
+	/// available. Resolves with an empty array once the walk is finished or
+	/// cancelled, or rejects if it was aborted or timed out.
+	#[napi]
+	pub fn next<'env>(
+		&self,
+		env: &'env Env,
+		batch_size: u32,
+	) -> Result<PromiseRaw<'env, Vec<GlobMatch>>> {
+		let shared = Arc::clone(&self.shared);
+		let receiver = Arc::clone(&self.receiver);
+		let batch_size = batch_size.max(1) as usize;
+		task::future(env, "glob.next", async move {
+			let mut receiver = receiver.lock().await;
+			let Some(rx) = receiver.as_mut() else {
+				return Ok(Vec::new());
+			};
+			let mut batch = Vec::new();
+			if rx.recv_many(&mut batch, batch_size).await == 0 {
+				// The walk is over; drop the channel and report how it ended.
+				*receiver = None;
+				let error = shared.state.lock().error.take();
+				if let Some(error) = error {
+					return Err(Error::from_reason(error));
+				}
+			}
+			Ok(batch)
+		})
+	}
+
+	/// Stop walking once the current entry is handled. Matches already
+	/// buffered can still be pulled with `next`.
+	#[napi]
+	pub fn pause(&self) {
+		self.shared.state.lock().paused = true;
+	}
+
+	/// Continue a paused walk.
+	#[napi]
+	pub fn resume(&self) {
+		self.shared.state.lock().paused = false;
+		self.shared.resumed.notify_all();
+	}
+
+	/// Stop the walk and discard buffered matches; later `next` calls resolve
+	/// with an empty array.
+	#[napi]
+	pub fn cancel(&self) {
+		self.shared.state.lock().cancelled = true;
+		self.shared.resumed.notify_all();
+		// A pending `next` holds the receiver; the walker sees the flag and
+		// closes the channel, which ends that call instead.
+		if let Ok(mut receiver) = self.receiver.try_lock() {
+			*receiver = None;
+		}
+	}
+}
+
+/// Start walking for glob matches in the background.
+///
+/// Takes the same options as `glob`, except that `sortByMtime` needs every
+/// match up front and is rejected, and `cache` and `threads` are ignored since
+/// the walk is always fresh and in path order.
+///
+/// # Errors
+/// Returns an error when the search path cannot be resolved, the path is not a
+/// directory, the glob pattern is invalid, `sortByMtime` is set, or the walker
+/// thread cannot start.
+#[napi(js_name = "globStream")]
+pub fn glob_stream(options: GlobOptions<'_>) -> Result<GlobStream> {
+	if options.sort_by_mtime == Some(true) {
+		return Err(Error::from_reason("globStream does not support sortByMtime"));
+	}
+	let (path, config, ct) = split_options(options);
+	let config = config(fs_cache::resolve_search_path(&path)?);
+	let filter = compile_filter(&config.patterns, &config.exclude)?;
+	let shared = Arc::new(StreamShared::default());
+	let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+	let walker_shared = Arc::clone(&shared);
+	std::thread::Builder::new()
+		.name("pi-glob-stream".to_string())
+		.spawn(move || {
+			if config.max_results == 0 {
+				return;
+			}
+			if let Err(err) = stream_walk(&config, &filter, &walker_shared, &sender, &ct) {
+				walker_shared.state.lock().error = Some(err.reason.clone());
+			}
+		})
+		.map_err(|err| Error::from_reason(format!("Failed to start glob walker: {err}")))?;
+	Ok(GlobStream { shared, receiver: Arc::new(tokio::sync::Mutex::new(Some(receiver))) })
+}
+
+/// Rewrite globset's byte-mode regex as JS `RegExp` source: drop the `(?-u)`
+/// flag and turn the `\xNN` UTF-8 byte escapes it writes for non-ASCII
+/// characters back into `\uXXXX` escapes of their UTF-16 code units.
+fn to_js_regex(regex: &str) -> String {
+	let regex = regex.trim_start_matches("(?-u)");
+	let mut out = String::with_capacity(regex.len());
+	let mut bytes = Vec::new();
+	let flush = |out: &mut String, bytes: &mut Vec<u8>| {
+		for unit in String::from_utf8_lossy(bytes).encode_utf16() {
+			let _ = write!(out, "\\u{unit:04x}");
+		}
+		bytes.clear();
+	};
+	let mut rest = regex;
+	while let Some(ch) = rest.chars().next() {
+		if ch != '\\' {
+			flush(&mut out, &mut bytes);
+			out.push(ch);
+			rest = &rest[ch.len_utf8()..];
+			continue;
+		}
+		if let Some(hex) = rest.strip_prefix("\\x").and_then(|hex| hex.get(..2))
+			&& let Ok(byte) = u8::from_str_radix(hex, 16)
+			&& byte >= 0x80
+		{
+			bytes.push(byte);
+			rest = &rest[4..];
+			continue;
+		}
+		// Copy any other escape whole, so `\\` never starts a byte escape.
+		flush(&mut out, &mut bytes);
+		let len = 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+		out.push_str(&rest[..len]);
+		rest = &rest[len..];
+	}
+	flush(&mut out, &mut bytes);
+	out
+}
+
+fn compile_regex(pattern: &str) -> Result<String> {
+	Glob::new(pattern)
+		.map(|glob| to_js_regex(glob.regex()))
+		.map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))
+}
+
+/// Compile a glob pattern to the regular expression `glob` matches paths
+/// with, after the same rewriting, as JS `RegExp` source (non-ASCII characters
+/// are written as `\uXXXX` escapes).
+///
+/// # Errors
+/// Returns an error when the pattern is invalid.
+#[napi(js_name = "globToRegex")]
+pub fn glob_to_regex(pattern: String) -> Result<String> {
+	compile_regex(&build_glob_pattern(&pattern))
+}
+
+/// Explain how `glob` rewrites and matches a pattern.
+///
+/// # Errors
+/// Returns an error when the pattern is invalid.
+#[napi(js_name = "explainGlob")]
+pub fn explain_glob(pattern: String) -> Result<GlobExplanation> {
+	let rewrite = rewrite_glob(&pattern);
+	let regex = compile_regex(&rewrite.pattern)?;
+	let mut notes = Vec::new();
+	if rewrite.normalized_separators {
+		notes.push("Backslashes were converted to `/` path separators".to_string());
+	} else if pattern.contains('\\') {
+		notes.push("`\\` escapes the character after it; use `/` as the path separator".to_string());
+	}
+	if rewrite.any_directory {
+		notes.push(
+			"The pattern has no `/`, so `**/` was prepended to match it in any directory".to_string(),
+		);
+	} else {
+		notes.push(
+			"The pattern is matched against the whole path relative to the search root".to_string(),
+		);
+	}
+	if pattern.replace("**", "").contains(['*', '?']) {
+		notes.push("`*` and `?` also match `/`, so they can span directories".to_string());
+	}
+	if rewrite.pattern.contains('{') {
+		notes.push("`{a,b}` matches any one of the comma-separated alternatives".to_string());
+	}
+	if rewrite.pattern.contains('[') {
+		notes.push("`[...]` matches one character from the set; `[!...]` negates it".to_string());
+	}
+	Ok(GlobExplanation {
+		input: pattern,
+		pattern: rewrite.pattern,
+		regex,
+		normalized_separators: rewrite.normalized_separators,
+		any_directory: rewrite.any_directory,
+		notes,
 	})
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_regex_escapes_non_ascii_as_utf16() {
+		assert_eq!(compile_regex("café/*.md").unwrap(), r"^caf\u00e9/.*\.md$");
+		assert_eq!(compile_regex("[é-ü]").unwrap(), r"^[\u00e9-\u00fc]$");
+		assert_eq!(compile_regex("😀").unwrap(), r"^\ud83d\ude00$");
+		// An escaped backslash followed by `xc3` stays literal text.
+		assert_eq!(to_js_regex(r"(?-u)^\\xc3$"), r"^\\xc3$");
+	}
+}
@@ -1,8 +1,9 @@
 //! Ripgrep-backed search exported via N-API.
 //!
-//! Provides two layers:
+//! Provides three layers:
 //! - `search()` for in-memory content search.
 //! - `grep()` for filesystem search with glob/type filtering.
+//! - `countMatches()` for match totals without per-match results.
 //!
 //! The filesystem search matches the previous JS wrapper behavior, including
 //! global offsets, optional match limits, and per-file match summaries.
@@ -10,10 +11,8 @@
 use std::{
 	fs::File,
 	io::{self, Cursor, Read},
-	path::{Path, PathBuf},
 };
 
-use globset::{Glob, GlobSet, GlobSetBuilder};
 use grep_matcher::Matcher;
 use grep_regex::RegexMatcherBuilder;
 use grep_searcher::{
@@ -28,7 +27,13 @@ use napi_derive::napi;
 use rayon::prelude::*;
 use smallvec::SmallVec;
 
-use crate::{fs_cache, task};
+use crate::{
+	file_filter::{
+		FileEntry, collect_files, compile_glob, matches_type_filter, resolve_search_path,
+		resolve_type_filter,
+	},
+	fs_cache, task,
+};
 
 const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
 
@@ -36,6 +41,10 @@ const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
 enum OutputMode {
 	Content,
 	Count,
+	/// Stop at the first match in each file.
+	FilesWithMatches,
+	/// Totals only, without per-file entries.
+	Total,
 }
 
 /// Options for searching file content.
@@ -46,8 +55,14 @@ pub struct SearchOptions {
 	/// Case-insensitive search.
 	#[napi(js_name = "ignoreCase")]
 	pub ignore_case:    Option<bool>,
-	/// Enable multiline matching.
+	/// Let matches span lines; `^`/`$` still match at line boundaries.
 	pub multiline:      Option<bool>,
+	/// Treat the pattern as a literal string.
+	#[napi(js_name = "fixedStrings")]
+	pub fixed_strings:  Option<bool>,
+	/// Only match whole words.
+	#[napi(js_name = "wordBoundary")]
+	pub word_boundary:  Option<bool>,
 	/// Maximum number of matches to return.
 	#[napi(js_name = "maxCount")]
 	pub max_count:      Option<u32>,
@@ -83,8 +98,14 @@ pub struct GrepOptions<'env> {
 	/// Case-insensitive search.
 	#[napi(js_name = "ignoreCase")]
 	pub ignore_case:    Option<bool>,
-	/// Enable multiline matching.
+	/// Let matches span lines; `^`/`$` still match at line boundaries.
 	pub multiline:      Option<bool>,
+	/// Treat the pattern as a literal string.
+	#[napi(js_name = "fixedStrings")]
+	pub fixed_strings:  Option<bool>,
+	/// Only match whole words.
+	#[napi(js_name = "wordBoundary")]
+	pub word_boundary:  Option<bool>,
 	/// Include hidden files (default: true).
 	pub hidden:         Option<bool>,
 	/// Enable shared filesystem scan cache (default: false).
@@ -105,7 +126,9 @@ pub struct GrepOptions<'env> {
 	/// Truncate lines longer than this (characters).
 	#[napi(js_name = "maxColumns")]
 	pub max_columns:    Option<u32>,
-	/// Output mode (content, filesWithMatches, or count).
+	/// Output mode (content, filesWithMatches, or count). `filesWithMatches`
+	/// stops reading each file at its first match, so totals count one match
+	/// per file.
 	pub mode:           Option<String>,
 	/// Abort signal for cancelling the operation.
 	pub signal:         Option<Unknown<'env>>,
@@ -140,6 +163,11 @@ pub struct Match {
 	pub context_after:  Option<Vec<ContextLine>>,
 	/// Whether the line was truncated.
 	pub truncated:      Option<bool>,
+	/// 1-indexed column of the first match on the line, in UTF-16 code units.
+	pub column:         Option<u32>,
+	/// Text of the first match on the line.
+	#[napi(js_name = "matchText")]
+	pub match_text:     Option<String>,
 }
 
 /// Result of searching content.
@@ -176,17 +204,70 @@ pub struct GrepMatch {
 	pub context_after:  Option<Vec<ContextLine>>,
 	/// Whether the line was truncated.
 	pub truncated:      Option<bool>,
+	/// 1-indexed column of the first match on the line, in UTF-16 code units
+	/// (content mode only).
+	pub column:         Option<u32>,
+	/// Text of the first match on the line (content mode only).
+	#[napi(js_name = "matchText")]
+	pub match_text:     Option<String>,
 	/// Per-file match count (count mode only).
 	#[napi(js_name = "matchCount")]
 	pub match_count:    Option<u32>,
 }
 
+/// Options for counting matches in files.
+#[napi(object)]
+#[derive(Default)]
+pub struct CountMatchesOptions<'env> {
+	/// Glob filter for filenames (e.g., "*.ts").
+	pub glob:          Option<String>,
+	/// Filter by file type (e.g., "js", "py", "rust").
+	#[napi(js_name = "type")]
+	pub type_filter:   Option<String>,
+	/// Case-insensitive search.
+	#[napi(js_name = "ignoreCase")]
+	pub ignore_case:   Option<bool>,
+	/// Let matches span lines; `^`/`$` still match at line boundaries.
+	pub multiline:     Option<bool>,
+	/// Treat the pattern as a literal string.
+	#[napi(js_name = "fixedStrings")]
+	pub fixed_strings: Option<bool>,
+	/// Only match whole words.
+	#[napi(js_name = "wordBoundary")]
+	pub word_boundary: Option<bool>,
+	/// Include hidden files (default: true).
+	pub hidden:        Option<bool>,
+	/// Enable shared filesystem scan cache (default: false).
+	pub cache:         Option<bool>,
+	/// Abort signal for cancelling the operation.
+	pub signal:        Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:    Option<u32>,
+}
+
+/// Match totals across files.
+#[napi(object)]
+pub struct CountMatchesResult {
+	/// Matching lines across all files; a line with several occurrences
+	/// counts once.
+	#[napi(js_name = "totalMatches")]
+	pub total_matches:      u32,
+	/// Number of files with at least one match.
+	#[napi(js_name = "filesWithMatches")]
+	pub files_with_matches: u32,
+	/// Number of files searched.
+	#[napi(js_name = "filesSearched")]
+	pub files_searched:     u32,
+}
+
 /// Result of searching files.
 #[napi(object)]
 pub struct GrepResult {
 	/// Matches or per-file counts, depending on output mode.
 	pub matches:            Vec<GrepMatch>,
-	/// Total matches across all files.
+	/// Matching lines across all files; a line with several occurrences
+	/// counts once. In `filesWithMatches` mode each file counts once.
 	#[napi(js_name = "totalMatches")]
 	pub total_matches:      u32,
 	/// Number of files with at least one match.
@@ -200,28 +281,8 @@ pub struct GrepResult {
 	pub limit_reached:      Option<bool>,
 }
 
-enum TypeFilter {
-	Known { exts: &'static [&'static str], names: &'static [&'static str] },
-	Custom(String),
-}
-
-impl TypeFilter {
-	fn match_ext(&self, ext: &str) -> bool {
-		match self {
-			Self::Known { exts, .. } => exts.iter().any(|e| ext.eq_ignore_ascii_case(e)),
-			Self::Custom(custom_ext) => ext.eq_ignore_ascii_case(custom_ext),
-		}
-	}
-
-	fn match_name(&self, name: &str) -> bool {
-		match self {
-			Self::Known { names, .. } => names.iter().any(|n| name.eq_ignore_ascii_case(n)),
-			Self::Custom(ext) => ext.eq_ignore_ascii_case(name),
-		}
-	}
-}
-
-struct MatchCollector {
+struct MatchCollector<'m> {
+	matcher:         &'m grep_regex::RegexMatcher,
 	matches:         Vec<CollectedMatch>,
 	match_count:     u64,
 	collected_count: u64,
@@ -232,6 +293,8 @@ struct MatchCollector {
 	context_before:  SmallVec<[ContextLine; 8]>,
 	max_columns:     Option<usize>,
 	collect_matches: bool,
+	/// Stop after the first match.
+	first_only:      bool,
 }
 
 struct CollectedMatch {
@@ -240,6 +303,8 @@ struct CollectedMatch {
 	context_before: SmallVec<[ContextLine; 8]>,
 	context_after:  SmallVec<[ContextLine; 8]>,
 	truncated:      bool,
+	column:         Option<u32>,
+	match_text:     Option<String>,
 }
 
 struct SearchResultInternal {
@@ -249,25 +314,23 @@ struct SearchResultInternal {
 	limit_reached: bool,
 }
 
-struct FileEntry {
-	path:          PathBuf,
-	relative_path: String,
-}
-
 struct FileSearchResult {
 	relative_path: String,
 	matches:       Vec<CollectedMatch>,
 	match_count:   u64,
 }
 
-impl MatchCollector {
+impl<'m> MatchCollector<'m> {
 	fn new(
+		matcher: &'m grep_regex::RegexMatcher,
 		max_count: Option<u64>,
 		offset: u64,
 		max_columns: Option<usize>,
 		collect_matches: bool,
+		first_only: bool,
 	) -> Self {
 		Self {
+			matcher,
 			matches: Vec::new(),
 			match_count: 0,
 			collected_count: 0,
@@ -278,6 +341,7 @@ impl MatchCollector {
 			context_before: SmallVec::new(),
 			max_columns,
 			collect_matches,
+			first_only,
 		}
 	}
 
@@ -292,6 +356,18 @@ impl MatchCollector {
 			_ => (line.to_string(), false),
 		}
 	}
+
+	/// Locate the first match in `bytes`, returning its 1-indexed UTF-16
+	/// column and its text.
+	fn first_match(&self, bytes: &[u8]) -> (Option<u32>, Option<String>) {
+		let Ok(Some(found)) = self.matcher.find(bytes) else {
+			return (None, None);
+		};
+		let prefix = String::from_utf8_lossy(&bytes[..found.start()]);
+		let column = prefix.encode_utf16().count() + 1;
+		let text = String::from_utf8_lossy(&bytes[found]);
+		(Some(clamp_u32(column as u64)), Some(self.truncate_line(&text).0))
+	}
 }
 
 fn bytes_to_trimmed_string(bytes: &[u8]) -> String {
@@ -301,7 +377,7 @@ fn bytes_to_trimmed_string(bytes: &[u8]) -> String {
 	}
 }
 
-impl Sink for MatchCollector {
+impl Sink for MatchCollector<'_> {
 	type Error = io::Error;
 
 	fn matched(
@@ -327,6 +403,7 @@ impl Sink for MatchCollector {
 			let raw_line = bytes_to_trimmed_string(mat.bytes());
 			let (line, truncated) = self.truncate_line(&raw_line);
 			let line_number = mat.line_number().unwrap_or(0);
+			let (column, match_text) = self.first_match(mat.bytes());
 
 			self.matches.push(CollectedMatch {
 				line_number,
@@ -334,6 +411,8 @@ impl Sink for MatchCollector {
 				context_before: std::mem::take(&mut self.context_before),
 				context_after: SmallVec::new(),
 				truncated,
+				column,
+				match_text,
 			});
 		} else {
 			self.context_before.clear();
@@ -348,7 +427,7 @@ impl Sink for MatchCollector {
 			self.limit_reached = true;
 		}
 
-		Ok(true)
+		Ok(!self.first_only)
 	}
 
 	fn context(
@@ -390,102 +469,12 @@ fn clamp_u32(value: u64) -> u32 {
 
 fn parse_output_mode(mode: Option<&str>) -> OutputMode {
 	match mode {
-		Some("count" | "filesWithMatches") => OutputMode::Count,
+		Some("count") => OutputMode::Count,
+		Some("filesWithMatches") => OutputMode::FilesWithMatches,
 		_ => OutputMode::Content,
 	}
 }
 
-fn resolve_search_path(path: &str) -> Result<PathBuf> {
-	let candidate = PathBuf::from(path);
-	if candidate.is_absolute() {
-		return Ok(candidate);
-	}
-	let cwd = std::env::current_dir()
-		.map_err(|err| Error::from_reason(format!("Failed to resolve cwd: {err}")))?;
-	Ok(cwd.join(candidate))
-}
-
-fn build_glob_pattern(glob: &str) -> String {
-	let normalized = glob.replace('\\', "/");
-	if normalized.contains('/') || normalized.starts_with("**/") {
-		normalized
-	} else {
-		format!("**/{normalized}")
-	}
-}
-
-fn compile_glob(glob: Option<&str>) -> Result<Option<GlobSet>> {
-	let Some(glob) = glob.map(str::trim).filter(|value| !value.is_empty()) else {
-		return Ok(None);
-	};
-	let mut builder = GlobSetBuilder::new();
-	let pattern = build_glob_pattern(glob);
-	let glob = Glob::new(&pattern)
-		.map_err(|err| Error::from_reason(format!("Invalid glob pattern: {err}")))?;
-	builder.add(glob);
-	builder
-		.build()
-		.map(Some)
-		.map_err(|err| Error::from_reason(format!("Failed to build glob matcher: {err}")))
-}
-
-fn resolve_type_filter(type_name: Option<&str>) -> Option<TypeFilter> {
-	let normalized = type_name
-		.map(str::trim)
-		.filter(|value| !value.is_empty())
-		.map(|value| value.trim_start_matches('.').to_lowercase())?;
-
-	let (exts, names): (&[&str], &[&str]) = match normalized.as_str() {
-		"js" | "javascript" => (&["js", "jsx", "mjs", "cjs"], &[]),
-		"ts" | "typescript" => (&["ts", "tsx", "mts", "cts"], &[]),
-		"json" => (&["json", "jsonc", "json5"], &[]),
-		"yaml" | "yml" => (&["yaml", "yml"], &[]),
-		"toml" => (&["toml"], &[]),
-		"md" | "markdown" => (&["md", "markdown", "mdx"], &[]),
-		"py" | "python" => (&["py", "pyi"], &[]),
-		"rs" | "rust" => (&["rs"], &[]),
-		"go" => (&["go"], &[]),
-		"java" => (&["java"], &[]),
-		"kt" | "kotlin" => (&["kt", "kts"], &[]),
-		"c" => (&["c", "h"], &[]),
-		"cpp" | "cxx" => (&["cpp", "cc", "cxx", "hpp", "hxx", "hh"], &[]),
-		"cs" | "csharp" => (&["cs", "csx"], &[]),
-		"php" => (&["php", "phtml"], &[]),
-		"rb" | "ruby" => (&["rb", "rake", "gemspec"], &[]),
-		"sh" | "bash" => (&["sh", "bash", "zsh"], &[]),
-		"zsh" => (&["zsh"], &[]),
-		"fish" => (&["fish"], &[]),
-		"html" => (&["html", "htm"], &[]),
-		"css" => (&["css"], &[]),
-		"scss" => (&["scss"], &[]),
-		"sass" => (&["sass"], &[]),
-		"less" => (&["less"], &[]),
-		"xml" => (&["xml"], &[]),
-		"docker" | "dockerfile" => (&[], &["dockerfile"]),
-		"make" | "makefile" => (&[], &["makefile"]),
-		_ => {
-			return Some(TypeFilter::Custom(normalized));
-		},
-	};
-
-	Some(TypeFilter::Known { exts, names })
-}
-
-fn matches_type_filter(path: &Path, filter: &TypeFilter) -> bool {
-	let base_name = path
-		.file_name()
-		.and_then(|name| name.to_str())
-		.unwrap_or("");
-	if filter.match_name(base_name) {
-		return true;
-	}
-	let ext = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
-	if ext.is_empty() {
-		return false;
-	}
-	filter.match_ext(ext)
-}
-
 fn resolve_context(
 	context: Option<u32>,
 	context_before: Option<u32>,
@@ -499,10 +488,11 @@ fn resolve_context(
 	}
 }
 
-fn build_searcher(before_context: u32, after_context: u32) -> Searcher {
+fn build_searcher(before_context: u32, after_context: u32, multiline: bool) -> Searcher {
 	SearcherBuilder::new()
 		.binary_detection(BinaryDetection::quit(b'\x00'))
 		.line_number(true)
+		.multi_line(multiline)
 		.before_context(before_context as usize)
 		.after_context(after_context as usize)
 		.build()
@@ -512,6 +502,7 @@ fn build_searcher(before_context: u32, after_context: u32) -> Searcher {
 struct SearchParams {
 	context_before: u32,
 	context_after:  u32,
+	multiline:      bool,
 	max_columns:    Option<u32>,
 	mode:           OutputMode,
 	max_count:      Option<u64>,
@@ -543,12 +534,15 @@ fn run_search_reader<R: Read>(
 		} else {
 			0
 		},
+		params.multiline,
 	);
 	let mut collector = MatchCollector::new(
+		matcher,
 		params.max_count,
 		params.offset,
 		params.max_columns.map(|v| v as usize),
 		params.mode == OutputMode::Content,
+		params.mode == OutputMode::FilesWithMatches,
 	);
 	searcher.search_reader(matcher, reader, &mut collector)?;
 	Ok(SearchResultInternal {
@@ -576,6 +570,8 @@ fn to_public_match(matched: CollectedMatch) -> Match {
 		context_before,
 		context_after,
 		truncated: if matched.truncated { Some(true) } else { None },
+		column: matched.column,
+		match_text: matched.match_text,
 	}
 }
 
@@ -597,10 +593,33 @@ fn to_grep_match(path: &str, matched: CollectedMatch) -> GrepMatch {
 		context_before,
 		context_after,
 		truncated: if matched.truncated { Some(true) } else { None },
+		column: matched.column,
+		match_text: matched.match_text,
 		match_count: None,
 	}
 }
 
+/// Per-file entry for the count and filesWithMatches modes; None when only
+/// totals are wanted.
+fn summary_match(path: &str, mode: OutputMode, match_count: u64) -> Option<GrepMatch> {
+	let count = match mode {
+		OutputMode::Content | OutputMode::Total => return None,
+		OutputMode::Count => Some(clamp_u32(match_count)),
+		OutputMode::FilesWithMatches => None,
+	};
+	Some(GrepMatch {
+		path:           path.to_string(),
+		line_number:    0,
+		line:           String::new(),
+		context_before: None,
+		context_after:  None,
+		truncated:      None,
+		column:         None,
+		match_text:     None,
+		match_count:    count,
+	})
+}
+
 const fn empty_search_result(error: Option<String>) -> SearchResult {
 	SearchResult { matches: Vec::new(), match_count: 0, limit_reached: false, error }
 }
@@ -613,6 +632,8 @@ struct GrepConfig {
 	type_filter:    Option<String>,
 	ignore_case:    Option<bool>,
 	multiline:      Option<bool>,
+	fixed_strings:  Option<bool>,
+	word_boundary:  Option<bool>,
 	hidden:         Option<bool>,
 	cache:          Option<bool>,
 	max_count:      Option<u32>,
@@ -621,58 +642,52 @@ struct GrepConfig {
 	context_after:  Option<u32>,
 	context:        Option<u32>,
 	max_columns:    Option<u32>,
-	mode:           Option<String>,
+	mode:           OutputMode,
 }
 
-fn collect_files(
-	root: &Path,
-	scanned_entries: &[fs_cache::GlobMatch],
-	glob_set: Option<&GlobSet>,
-	type_filter: Option<&TypeFilter>,
-) -> Vec<FileEntry> {
-	let mut entries = Vec::new();
-	for entry in scanned_entries {
-		if entry.file_type != fs_cache::FileType::File {
-			continue;
-		}
-		if let Some(glob_set) = glob_set
-			&& !glob_set.is_match(Path::new(&entry.path))
-		{
-			continue;
-		}
-		let path = root.join(&entry.path);
-		if let Some(filter) = type_filter
-			&& !matches_type_filter(&path, filter)
-		{
-			continue;
+/// How a pattern is interpreted.
+#[derive(Clone, Copy, Default)]
+struct PatternFlags {
+	ignore_case:   bool,
+	multiline:     bool,
+	fixed_strings: bool,
+	word:          bool,
+}
+
+impl PatternFlags {
+	fn new(
+		ignore_case: Option<bool>,
+		multiline: Option<bool>,
+		fixed_strings: Option<bool>,
+		word: Option<bool>,
+	) -> Self {
+		Self {
+			ignore_case:   ignore_case.unwrap_or(false),
+			multiline:     multiline.unwrap_or(false),
+			fixed_strings: fixed_strings.unwrap_or(false),
+			word:          word.unwrap_or(false),
 		}
-		entries.push(FileEntry { path, relative_path: entry.path.clone() });
 	}
-	entries
 }
 
-fn build_matcher(
-	pattern: &str,
-	ignore_case: bool,
-	multiline: bool,
-) -> Result<grep_regex::RegexMatcher> {
+fn build_matcher(pattern: &str, flags: PatternFlags) -> Result<grep_regex::RegexMatcher> {
 	RegexMatcherBuilder::new()
-		.case_insensitive(ignore_case)
-		.multi_line(multiline)
+		.case_insensitive(flags.ignore_case)
+		.multi_line(flags.multiline)
+		.fixed_strings(flags.fixed_strings)
+		.word(flags.word)
 		.build(pattern)
 		.map_err(|err| Error::from_reason(format!("Regex error: {err}")))
 }
 
+/// Search every file without limits; `params.max_count` and `params.offset`
+/// are ignored.
 fn run_parallel_search(
 	entries: &[FileEntry],
 	matcher: &grep_regex::RegexMatcher,
-	context_before: u32,
-	context_after: u32,
-	max_columns: Option<u32>,
-	mode: OutputMode,
+	params: SearchParams,
 ) -> Vec<FileSearchResult> {
-	let params =
-		SearchParams { context_before, context_after, max_columns, mode, max_count: None, offset: 0 };
+	let params = SearchParams { max_count: None, offset: 0, ..params };
 	let mut results: Vec<FileSearchResult> = entries
 		.par_iter()
 		.filter_map(|entry| {
@@ -744,16 +759,8 @@ fn run_sequential_search(
 					matches.push(to_grep_match(&entry.relative_path, matched));
 				}
 			},
-			OutputMode::Count => {
-				matches.push(GrepMatch {
-					path:           entry.relative_path.clone(),
-					line_number:    0,
-					line:           String::new(),
-					context_before: None,
-					context_after:  None,
-					truncated:      None,
-					match_count:    Some(clamp_u32(search.match_count)),
-				});
+			OutputMode::Count | OutputMode::FilesWithMatches | OutputMode::Total => {
+				matches.extend(summary_match(&entry.relative_path, mode, search.match_count));
 			},
 		}
 
@@ -766,10 +773,14 @@ fn run_sequential_search(
 }
 
 fn search_sync(content: &[u8], options: SearchOptions) -> SearchResult {
-	let ignore_case = options.ignore_case.unwrap_or(false);
-	let multiline = options.multiline.unwrap_or(false);
+	let flags = PatternFlags::new(
+		options.ignore_case,
+		options.multiline,
+		options.fixed_strings,
+		options.word_boundary,
+	);
 	let mode = parse_output_mode(options.mode.as_deref());
-	let matcher = match build_matcher(&options.pattern, ignore_case, multiline) {
+	let matcher = match build_matcher(&options.pattern, flags) {
 		Ok(matcher) => matcher,
 		Err(err) => return empty_search_result(Some(err.to_string())),
 	};
@@ -779,8 +790,15 @@ fn search_sync(content: &[u8], options: SearchOptions) -> SearchResult {
 	let max_columns = options.max_columns;
 	let max_count = options.max_count.map(u64::from);
 	let offset = options.offset.unwrap_or(0) as u64;
-	let params =
-		SearchParams { context_before, context_after, max_columns, mode, max_count, offset };
+	let params = SearchParams {
+		context_before,
+		context_after,
+		multiline: flags.multiline,
+		max_columns,
+		mode,
+		max_count,
+		offset,
+	};
 
 	let result = match run_search(&matcher, content, params) {
 		Ok(result) => result,
@@ -803,10 +821,14 @@ fn grep_sync(
 	let search_path = resolve_search_path(&options.path)?;
 	let metadata = std::fs::metadata(&search_path)
 		.map_err(|err| Error::from_reason(format!("Path not found: {err}")))?;
-	let ignore_case = options.ignore_case.unwrap_or(false);
-	let multiline = options.multiline.unwrap_or(false);
-	let output_mode = parse_output_mode(options.mode.as_deref());
-	let matcher = build_matcher(&options.pattern, ignore_case, multiline)?;
+	let flags = PatternFlags::new(
+		options.ignore_case,
+		options.multiline,
+		options.fixed_strings,
+		options.word_boundary,
+	);
+	let output_mode = options.mode;
+	let matcher = build_matcher(&options.pattern, flags)?;
 
 	let (context_before, context_after) =
 		resolve_context(options.context, options.context_before, options.context_after);
@@ -850,6 +872,7 @@ fn grep_sync(
 		let params = SearchParams {
 			context_before,
 			context_after,
+			multiline: flags.multiline,
 			max_columns,
 			mode: output_mode,
 			max_count,
@@ -876,16 +899,8 @@ fn grep_sync(
 					matches.push(to_grep_match(&path_string, matched));
 				}
 			},
-			OutputMode::Count => {
-				matches.push(GrepMatch {
-					path:           path_string,
-					line_number:    0,
-					line:           String::new(),
-					context_before: None,
-					context_after:  None,
-					truncated:      None,
-					match_count:    Some(clamp_u32(search.match_count)),
-				});
+			OutputMode::Count | OutputMode::FilesWithMatches | OutputMode::Total => {
+				matches.extend(summary_match(&path_string, output_mode, search.match_count));
 			},
 		}
 
@@ -926,16 +941,18 @@ fn grep_sync(
 		});
 	}
 
+	let params = SearchParams {
+		context_before,
+		context_after,
+		multiline: flags.multiline,
+		max_columns,
+		mode: output_mode,
+		max_count,
+		offset,
+	};
 	let allow_parallel = max_count.is_none() && offset == 0;
 	if allow_parallel {
-		let results = run_parallel_search(
-			&entries,
-			&matcher,
-			context_before,
-			context_after,
-			max_columns,
-			output_mode,
-		);
+		let results = run_parallel_search(&entries, &matcher, params);
 		let mut matches = Vec::new();
 		let mut total_matches = 0u64;
 		let mut files_with_matches = 0u32;
@@ -958,15 +975,11 @@ fn grep_sync(
 						matches.push(grep_match);
 					}
 				},
-				OutputMode::Count => {
-					let grep_match = GrepMatch {
-						path:           result.relative_path.clone(),
-						line_number:    0,
-						line:           String::new(),
-						context_before: None,
-						context_after:  None,
-						truncated:      None,
-						match_count:    Some(clamp_u32(result.match_count)),
+				OutputMode::Count | OutputMode::FilesWithMatches | OutputMode::Total => {
+					let Some(grep_match) =
+						summary_match(&result.relative_path, output_mode, result.match_count)
+					else {
+						continue;
 					};
 					if let Some(callback) = on_match {
 						callback.call(Ok(grep_match.clone()), ThreadsafeFunctionCallMode::NonBlocking);
@@ -986,14 +999,7 @@ fn grep_sync(
 	}
 
 	let (matches, total_matches, files_with_matches, files_searched, limit_reached) =
-		run_sequential_search(&entries, &matcher, SearchParams {
-			context_before,
-			context_after,
-			max_columns,
-			mode: output_mode,
-			max_count,
-			offset,
-		});
+		run_sequential_search(&entries, &matcher, params);
 
 	// Fire callbacks for sequential search results
 	if let Some(callback) = on_match {
@@ -1076,7 +1082,8 @@ pub fn has_match(
 		},
 	};
 
-	let matcher = build_matcher(pattern_ref, ignore_case, multiline)?;
+	let flags = PatternFlags { ignore_case, multiline, ..PatternFlags::default() };
+	let matcher = build_matcher(pattern_ref, flags)?;
 	Ok(matcher.is_match(content_slice).unwrap_or(false))
 }
 
@@ -1102,6 +1109,8 @@ pub fn grep(
 		type_filter,
 		ignore_case,
 		multiline,
+		fixed_strings,
+		word_boundary,
 		hidden,
 		cache,
 		max_count,
@@ -1122,6 +1131,8 @@ pub fn grep(
 		type_filter,
 		ignore_case,
 		multiline,
+		fixed_strings,
+		word_boundary,
 		hidden,
 		cache,
 		max_count,
@@ -1130,9 +1141,69 @@ pub fn grep(
 		context_after,
 		context,
 		max_columns,
-		mode,
+		mode: parse_output_mode(mode.as_deref()),
 	};
 
 	let ct = task::CancelToken::new(timeout_ms, signal);
 	task::blocking("grep", ct, move |ct| grep_sync(config, on_match.as_ref(), ct))
 }
+
+/// Count matches in files without building per-match or per-file results.
+///
+/// # Arguments
+/// - `pattern`: Regex pattern to search for.
+/// - `path`: Directory or file to search.
+/// - `options`: Regex settings, filters, and cancellation.
+///
+/// # Returns
+/// Match totals across all searched files. Like ripgrep's `--count`, these
+/// count matching lines, not occurrences.
+#[napi(js_name = "countMatches")]
+pub fn count_matches(
+	pattern: String,
+	path: String,
+	options: Option<CountMatchesOptions<'_>>,
+) -> task::Async<CountMatchesResult> {
+	let CountMatchesOptions {
+		glob,
+		type_filter,
+		ignore_case,
+		multiline,
+		fixed_strings,
+		word_boundary,
+		hidden,
+		cache,
+		signal,
+		timeout_ms,
+	} = options.unwrap_or_default();
+
+	let config = GrepConfig {
+		pattern,
+		path,
+		glob,
+		type_filter,
+		ignore_case,
+		multiline,
+		fixed_strings,
+		word_boundary,
+		hidden,
+		cache,
+		max_count: None,
+		offset: None,
+		context_before: None,
+		context_after: None,
+		context: None,
+		max_columns: None,
+		mode: OutputMode::Total,
+	};
+
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("count_matches", ct, move |ct| {
+		let result = grep_sync(config, None, ct)?;
+		Ok(CountMatchesResult {
+			total_matches:      result.total_matches,
+			files_with_matches: result.files_with_matches,
+			files_searched:     result.files_searched,
+		})
+	})
+}
@@ -0,0 +1,314 @@
+//! Fast content hashing.
+//!
+//! # Overview
+//! Hashes buffers and files on the worker pool with either BLAKE3
+//! (cryptographic, 256-bit, for content addressing) or XXH3/XXH64 (fast
+//! non-cryptographic fingerprints for change detection). Digests are
+//! lowercase hex.
+//!
+//! Files are streamed in fixed-size chunks, so memory stays flat regardless
+//! of size. Once a BLAKE3 input reaches [`PARALLEL_THRESHOLD`] its chunks are
+//! hashed with the multithreaded tree mode on the rayon pool.
+//!
+//! `detectChanges` checks many files against previously recorded state: size
+//! and mtime are compared first, and a file is hashed only when its mtime
+//! moved, so unchanged trees cost one `stat` per file.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.hashBuffer("hello")
+//! // JS: // -> "ea8f163db38682925e4491c5e58d4bb3506ef8c14eb78a86e908c5624a67200f"
+//! // JS: await native.hashFile("dist/app.js", "xxh3")
+//! // JS: await native.detectChanges([{ path: "src/a.ts", expectedHash, expectedMtime }])
+//! ```
+
+use std::{fs::File, io::Read, path::Path, time::UNIX_EPOCH};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rayon::prelude::*;
+use xxhash_rust::{xxh3::Xxh3, xxh64::Xxh64};
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Hashing
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Input size from which BLAKE3 hashes with multiple threads; below it the
+/// coordination costs more than it saves.
+pub const PARALLEL_THRESHOLD: usize = 1024 * 1024;
+
+/// Read size when streaming files.
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Supported hash algorithms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+	/// BLAKE3, 256-bit digest.
+	Blake3,
+	/// XXH3, 64-bit digest.
+	Xxh3,
+	/// XXH3, 128-bit digest.
+	Xxh3_128,
+	/// XXH64, 64-bit digest.
+	Xxh64,
+}
+
+/// Parse an algorithm name; `None` selects BLAKE3.
+pub fn parse_algorithm(name: Option<&str>) -> Result<Algorithm> {
+	match name.map(str::trim) {
+		None | Some("" | "blake3") => Ok(Algorithm::Blake3),
+		Some("xxh3" | "xxh3-64") => Ok(Algorithm::Xxh3),
+		Some("xxh3-128" | "xxh128") => Ok(Algorithm::Xxh3_128),
+		Some("xxh64") => Ok(Algorithm::Xxh64),
+		Some(other) => Err(Error::from_reason(format!("Unknown hash algorithm: {other}"))),
+	}
+}
+
+/// Incremental hasher over any supported algorithm.
+pub enum Hasher {
+	Blake3(Box<blake3::Hasher>),
+	Xxh3(Box<Xxh3>),
+	Xxh3_128(Box<Xxh3>),
+	Xxh64(Xxh64),
+}
+
+impl Hasher {
+	pub fn new(algorithm: Algorithm) -> Self {
+		match algorithm {
+			Algorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+			Algorithm::Xxh3 => Self::Xxh3(Box::new(Xxh3::new())),
+			Algorithm::Xxh3_128 => Self::Xxh3_128(Box::new(Xxh3::new())),
+			Algorithm::Xxh64 => Self::Xxh64(Xxh64::new(0)),
+		}
+	}
+
+	/// Feed `data`, hashing large BLAKE3 inputs in parallel.
+	pub fn update(&mut self, data: &[u8]) {
+		match self {
+			Self::Blake3(hasher) if data.len() >= PARALLEL_THRESHOLD => {
+				hasher.update_rayon(data);
+			},
+			Self::Blake3(hasher) => {
+				hasher.update(data);
+			},
+			Self::Xxh3(hasher) | Self::Xxh3_128(hasher) => hasher.update(data),
+			Self::Xxh64(hasher) => hasher.update(data),
+		}
+	}
+
+	/// Lowercase hex digest.
+	pub fn finish(&self) -> String {
+		match self {
+			Self::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+			Self::Xxh3(hasher) => format!("{:016x}", hasher.digest()),
+			Self::Xxh3_128(hasher) => format!("{:032x}", hasher.digest128()),
+			Self::Xxh64(hasher) => format!("{:016x}", hasher.digest()),
+		}
+	}
+}
+
+/// Hash a byte slice.
+pub fn hash_bytes(data: &[u8], algorithm: Algorithm) -> String {
+	let mut hasher = Hasher::new(algorithm);
+	hasher.update(data);
+	hasher.finish()
+}
+
+/// Hash a file's contents in chunks, checking for cancellation between them.
+pub fn hash_path(path: &Path, algorithm: Algorithm, ct: &task::CancelToken) -> Result<String> {
+	let read_error =
+		|err: std::io::Error| Error::from_reason(format!("Failed to read {}: {err}", path.display()));
+	let mut file = File::open(path)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", path.display())))?;
+	let len = file.metadata().map_err(read_error)?.len();
+	// Parallel BLAKE3 only pays off with large updates, so read big files in
+	// threshold-sized pieces.
+	let chunk = if algorithm == Algorithm::Blake3 && len >= PARALLEL_THRESHOLD as u64 {
+		PARALLEL_THRESHOLD * 8
+	} else {
+		CHUNK_SIZE
+	};
+	let mut buf = vec![0u8; chunk];
+	let mut hasher = Hasher::new(algorithm);
+	loop {
+		ct.heartbeat()?;
+		let mut filled = 0;
+		while filled < buf.len() {
+			match file.read(&mut buf[filled..]) {
+				Ok(0) => break,
+				Ok(n) => filled += n,
+				Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {},
+				Err(err) => return Err(read_error(err)),
+			}
+		}
+		if filled == 0 {
+			break;
+		}
+		hasher.update(&buf[..filled]);
+		if filled < buf.len() {
+			break;
+		}
+	}
+	Ok(hasher.finish())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Change detection
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A file and the state it was last seen in.
+#[napi(object)]
+pub struct ChangeEntry {
+	/// File to check.
+	pub path:           String,
+	/// Digest of the content when last seen, in the requested algorithm.
+	#[napi(js_name = "expectedHash")]
+	pub expected_hash:  Option<String>,
+	/// Modification time in milliseconds since the Unix epoch when last seen.
+	#[napi(js_name = "expectedMtime")]
+	pub expected_mtime: Option<f64>,
+	/// Size in bytes when last seen.
+	#[napi(js_name = "expectedSize")]
+	pub expected_size:  Option<f64>,
+}
+
+/// Options for `detectChanges`.
+#[napi(object)]
+pub struct DetectChangesOptions<'env> {
+	/// Algorithm of `expectedHash`: "blake3" (default), "xxh3", "xxh3-128", or
+	/// "xxh64".
+	pub algorithm:  Option<String>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Whether `entry` no longer matches the file on disk.
+///
+/// A missing file or a size mismatch is a change. A matching mtime proves
+/// the file unchanged without reading it; otherwise the content is hashed
+/// against `expected_hash`. With neither an mtime nor a hash to compare, only
+/// existence and size are checked.
+fn has_changed(entry: &ChangeEntry, algorithm: Algorithm, ct: &task::CancelToken) -> Result<bool> {
+	let path = Path::new(&entry.path);
+	let Ok(metadata) = std::fs::metadata(path) else {
+		return Ok(true);
+	};
+	if !metadata.is_file()
+		|| entry
+			.expected_size
+			.is_some_and(|size| size as u64 != metadata.len())
+	{
+		return Ok(true);
+	}
+	// Compare whole milliseconds so integral and fractional mtimes (as in
+	// `fs.Stats.mtimeMs`) agree.
+	let mtime = metadata
+		.modified()
+		.ok()
+		.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+		.map(|duration| duration.as_millis());
+	if let (Some(expected), Some(mtime)) = (entry.expected_mtime, mtime)
+		&& expected as u128 == mtime
+	{
+		return Ok(false);
+	}
+	let Some(expected) = &entry.expected_hash else {
+		return Ok(entry.expected_mtime.is_some());
+	};
+	if let Ok(actual) = hash_path(path, algorithm, ct) {
+		Ok(!actual.eq_ignore_ascii_case(expected))
+	} else {
+		// Surface cancellation; any other read failure counts as a change.
+		ct.heartbeat()?;
+		Ok(true)
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Hash a buffer or string (hashed as UTF-8) on the worker pool.
+///
+/// # Arguments
+/// - `data`: Bytes or text to hash.
+/// - `algo`: `"blake3"` (default), `"xxh3"`, `"xxh3-128"`, or `"xxh64"`.
+///
+/// # Returns
+/// The lowercase hex digest.
+///
+/// # Errors
+/// Returns an error for an unknown algorithm.
+#[napi(js_name = "hashBuffer")]
+pub fn hash_buffer(
+	data: Either<String, Uint8Array>,
+	algo: Option<String>,
+) -> Result<task::Async<String>> {
+	let algorithm = parse_algorithm(algo.as_deref())?;
+	let bytes = match data {
+		Either::A(text) => text.into_bytes(),
+		Either::B(buffer) => buffer.as_ref().to_vec(),
+	};
+	Ok(task::blocking("hash.buffer", (), move |_| Ok(hash_bytes(&bytes, algorithm))))
+}
+
+/// Hash a file's contents on the worker pool, streaming it in chunks.
+///
+/// # Arguments
+/// - `path`: File to hash.
+/// - `algo`: `"blake3"` (default), `"xxh3"`, `"xxh3-128"`, or `"xxh64"`.
+///
+/// # Returns
+/// The lowercase hex digest.
+///
+/// # Errors
+/// Returns an error for an unknown algorithm or an unreadable file.
+#[napi(js_name = "hashFile")]
+pub fn hash_file(path: String, algo: Option<String>) -> Result<task::Async<String>> {
+	let algorithm = parse_algorithm(algo.as_deref())?;
+	Ok(task::blocking("hash.file", (), move |ct| hash_path(Path::new(&path), algorithm, &ct)))
+}
+
+/// Find the files that changed since they were last seen.
+///
+/// Entries are checked in parallel. Size and mtime are compared first; files
+/// are hashed only when their mtime moved, and unreadable or missing files
+/// count as changed.
+///
+/// # Returns
+/// Paths of changed entries, in input order.
+///
+/// # Errors
+/// Returns an error for an unknown algorithm or when the operation is
+/// cancelled or times out.
+#[napi(js_name = "detectChanges")]
+pub fn detect_changes(
+	entries: Vec<ChangeEntry>,
+	options: Option<DetectChangesOptions<'_>>,
+) -> Result<task::Async<Vec<String>>> {
+	let (algorithm, signal, timeout_ms) = match options {
+		Some(opts) => (opts.algorithm, opts.signal, opts.timeout_ms),
+		None => (None, None, None),
+	};
+	let algorithm = parse_algorithm(algorithm.as_deref())?;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("hash.detect_changes", ct, move |ct| {
+		let changed = entries
+			.par_iter()
+			.map(|entry| {
+				ct.heartbeat()?;
+				has_changed(entry, algorithm, &ct)
+			})
+			.collect::<Result<Vec<_>>>()?;
+		Ok(entries
+			.into_iter()
+			.zip(changed)
+			.filter_map(|(entry, changed)| changed.then_some(entry.path))
+			.collect())
+	}))
+}
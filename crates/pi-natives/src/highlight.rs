@@ -5,7 +5,7 @@
 //! - comment, keyword, function, variable, string, number, type, operator,
 //!   punctuation, inserted, deleted
 
-use std::{cell::RefCell, collections::HashMap, sync::OnceLock};
+use std::{cell::RefCell, collections::HashMap, ops::Range, sync::OnceLock};
 
 use napi_derive::napi;
 use syntect::parsing::{ParseState, Scope, ScopeStack, ScopeStackOp, SyntaxReference, SyntaxSet};
@@ -349,94 +349,101 @@ fn find_syntax<'a>(ss: &'a SyntaxSet, lang: &str) -> Option<&'a SyntaxReference>
 		.or_else(|| ss.find_syntax_by_token(alias))
 }
 
-/// Highlight code and return ANSI-colored lines.
-///
-/// # Arguments
-/// * `code` - The source code to highlight
-/// * `lang` - Language identifier (e.g., "rust", "typescript", "python")
-/// * `colors` - Theme colors as ANSI escape sequences
-///
-/// # Returns
-/// Highlighted code with ANSI color codes, or the original code if highlighting
-/// fails.
-#[napi(js_name = "highlightCode")]
-pub fn highlight_code(code: String, lang: Option<String>, colors: HighlightColors) -> String {
-	let inserted = colors.inserted.as_deref().unwrap_or("");
-	let deleted = colors.deleted.as_deref().unwrap_or("");
-
-	// Color palette as array for quick indexing
-	let palette = [
-		colors.comment.as_str(),     // 0
-		colors.keyword.as_str(),     // 1
-		colors.function.as_str(),    // 2
-		colors.variable.as_str(),    // 3
-		colors.string.as_str(),      // 4
-		colors.number.as_str(),      // 5
-		colors.r#type.as_str(),      // 6
-		colors.operator.as_str(),    // 7
-		colors.punctuation.as_str(), // 8
-		inserted,                    // 9
-		deleted,                     // 10
-	];
+/// Palette indexed by the color categories of `scope_to_color_index`.
+pub fn palette(colors: &HighlightColors) -> [&str; 11] {
+	[
+		colors.comment.as_str(),                  // 0
+		colors.keyword.as_str(),                  // 1
+		colors.function.as_str(),                 // 2
+		colors.variable.as_str(),                 // 3
+		colors.string.as_str(),                   // 4
+		colors.number.as_str(),                   // 5
+		colors.r#type.as_str(),                   // 6
+		colors.operator.as_str(),                 // 7
+		colors.punctuation.as_str(),              // 8
+		colors.inserted.as_deref().unwrap_or(""), // 9
+		colors.deleted.as_deref().unwrap_or(""),  // 10
+	]
+}
 
-	let ss = get_syntax_set();
+/// Highlights consecutive lines, carrying parser state from one line to the
+/// next.
+pub struct LineHighlighter {
+	parse_state: ParseState,
+	scope_stack: ScopeStack,
+}
 
-	// Find syntax for the language
-	let syntax = match &lang {
-		Some(l) => find_syntax(ss, l),
-		None => None,
+impl LineHighlighter {
+	/// Create a highlighter for a language, falling back to plain text.
+	pub fn new(lang: Option<&str>) -> Self {
+		let ss = get_syntax_set();
+		let syntax = lang
+			.and_then(|l| find_syntax(ss, l))
+			.unwrap_or_else(|| ss.find_syntax_plain_text());
+		Self { parse_state: ParseState::new(syntax), scope_stack: ScopeStack::new() }
 	}
-	.unwrap_or_else(|| ss.find_syntax_plain_text());
-
-	let mut parse_state = ParseState::new(syntax);
-	let mut scope_stack = ScopeStack::new();
-	let mut result = String::with_capacity(code.len() * 2);
 
-	for line in syntect::util::LinesWithEndings::from(code.as_str()) {
-		let Ok(ops) = parse_state.parse_line(line, ss) else {
-			// Parse error - append unhighlighted line and continue
-			result.push_str(line);
-			continue;
+	/// Split a line (including its terminator) into byte ranges tagged with a
+	/// palette index, or `usize::MAX` for uncolored text.
+	pub fn runs(&mut self, line: &str) -> Vec<(Range<usize>, usize)> {
+		let ss = get_syntax_set();
+		let Ok(ops) = self.parse_state.parse_line(line, ss) else {
+			// Parse error - leave the line unhighlighted
+			return vec![(0..line.len(), usize::MAX)];
 		};
 
+		let mut runs = Vec::with_capacity(ops.len() + 1);
 		let mut prev_end = 0;
 		for (offset, op) in ops {
 			let offset = offset.min(line.len());
 
-			// Output text BEFORE this operation using current scope
+			// Text BEFORE this operation uses the current scope
 			if offset > prev_end {
-				let text = &line[prev_end..offset];
-				let color_idx = scope_to_color_index(&scope_stack);
-
-				if color_idx < palette.len() && !palette[color_idx].is_empty() {
-					result.push_str(palette[color_idx]);
-					result.push_str(text);
-					result.push_str("\x1b[39m");
-				} else {
-					result.push_str(text);
-				}
+				runs.push((prev_end..offset, scope_to_color_index(&self.scope_stack)));
 			}
 			prev_end = offset;
 
 			// Now apply scope operation for NEXT segment
 			match op {
 				ScopeStackOp::Push(scope) => {
-					scope_stack.push(scope);
+					self.scope_stack.push(scope);
 				},
 				ScopeStackOp::Pop(count) => {
 					for _ in 0..count {
-						scope_stack.pop();
+						self.scope_stack.pop();
 					}
 				},
 				ScopeStackOp::Restore | ScopeStackOp::Clear(_) | ScopeStackOp::Noop => {},
 			}
 		}
 
-		// Output remaining text with current scope
+		// Remaining text uses the current scope
 		if prev_end < line.len() {
-			let text = &line[prev_end..];
-			let color_idx = scope_to_color_index(&scope_stack);
+			runs.push((prev_end..line.len(), scope_to_color_index(&self.scope_stack)));
+		}
+		runs
+	}
+}
+
+/// Highlight code and return ANSI-colored lines.
+///
+/// # Arguments
+/// * `code` - The source code to highlight
+/// * `lang` - Language identifier (e.g., "rust", "typescript", "python")
+/// * `colors` - Theme colors as ANSI escape sequences
+///
+/// # Returns
+/// Highlighted code with ANSI color codes, or the original code if highlighting
+/// fails.
+#[napi(js_name = "highlightCode")]
+pub fn highlight_code(code: String, lang: Option<String>, colors: HighlightColors) -> String {
+	let palette = palette(&colors);
+	let mut highlighter = LineHighlighter::new(lang.as_deref());
+	let mut result = String::with_capacity(code.len() * 2);
 
+	for line in syntect::util::LinesWithEndings::from(code.as_str()) {
+		for (range, color_idx) in highlighter.runs(line) {
+			let text = &line[range];
 			if color_idx < palette.len() && !palette[color_idx].is_empty() {
 				result.push_str(palette[color_idx]);
 				result.push_str(text);
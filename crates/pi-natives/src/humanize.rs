@@ -0,0 +1,238 @@
+//! Human-readable sizes, durations, and relative times.
+//!
+//! # Overview
+//! Formatters for status columns in list views: file sizes (`1.5MB`), task
+//! durations (`3m 05s`), and timestamps relative to now (`3m ago`). Each takes
+//! an optional `width` that pads the result to a fixed column (right-aligned
+//! by default), so a column of sizes or ages lines up without measuring in JS.
+//! Values wider than `width` are returned unpadded rather than truncated.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.formatBytes(1536) // -> "1.5KB"
+//! // JS: native.formatBytes(1_500_000, { si: true, width: 7 }) // -> "  1.5MB"
+//! // JS: native.formatDuration(185_000) // -> "3m 05s"
+//! // JS: native.formatDuration(185_000, { style: "clock" }) // -> "3:05"
+//! // JS: native.formatRelativeTime(Date.now() - 180_000) // -> "3m ago"
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+const BYTE_UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `formatBytes`.
+#[napi(object)]
+pub struct FormatBytesOptions {
+	/// Use powers of 1000 instead of 1024 (default: false).
+	pub si:       Option<bool>,
+	/// Digits after the decimal point for KB and above (default: 1).
+	pub decimals: Option<u32>,
+	/// Pad to this many columns.
+	pub width:    Option<u32>,
+	/// Padding side when `width` is set: "left" or "right" (default: "right").
+	pub align:    Option<String>,
+}
+
+/// Options for `formatDuration`.
+#[napi(object)]
+pub struct FormatDurationOptions {
+	/// "compact" (`3m 05s`) or "clock" (`3:05`) (default: "compact").
+	pub style: Option<String>,
+	/// Pad to this many columns.
+	pub width: Option<u32>,
+	/// Padding side when `width` is set: "left" or "right" (default: "right").
+	pub align: Option<String>,
+}
+
+/// Options for `formatRelativeTime`.
+#[napi(object)]
+pub struct FormatRelativeTimeOptions {
+	/// Reference time in milliseconds since the Unix epoch (default: now).
+	pub now:   Option<f64>,
+	/// Pad to this many columns.
+	pub width: Option<u32>,
+	/// Padding side when `width` is set: "left" or "right" (default: "right").
+	pub align: Option<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Formatting
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Format a byte count with the largest unit that keeps the value at least 1.
+pub fn format_bytes(bytes: f64, si: bool, decimals: usize) -> String {
+	let base = if si { 1000.0 } else { 1024.0 };
+	let sign = if bytes < 0.0 { "-" } else { "" };
+	let scale = 10f64.powi(decimals as i32);
+	// Compare the value as it will print, so 1023.99KB becomes 1.0MB rather
+	// than 1024.0KB.
+	let shown = |value: f64, unit: usize| {
+		if unit == 0 {
+			value.round()
+		} else {
+			(value * scale).round() / scale
+		}
+	};
+	let mut value = bytes.abs();
+	let mut unit = 0;
+	while unit < BYTE_UNITS.len() - 1 && shown(value, unit) >= base {
+		value /= base;
+		unit += 1;
+	}
+	if unit == 0 {
+		return format!("{sign}{value:.0}B");
+	}
+	format!("{sign}{value:.decimals$}{}", BYTE_UNITS[unit])
+}
+
+/// Format a duration as `850ms`, `4.2s`, `3m 05s`, `2h 05m`, or `3d 04h`.
+fn format_compact(ms: f64) -> String {
+	if ms.round() < 1000.0 {
+		return format!("{}ms", ms.round() as u64);
+	}
+	let tenths = (ms / 100.0).round() as u64;
+	if tenths < 600 {
+		return format!("{}.{}s", tenths / 10, tenths % 10);
+	}
+	// At least a minute, since 59.95s rounds up to it above.
+	let secs = ((ms / 1000.0) as u64).max(60);
+	if secs < 3600 {
+		format!("{}m {:02}s", secs / 60, secs % 60)
+	} else if secs < 86400 {
+		format!("{}h {:02}m", secs / 3600, secs / 60 % 60)
+	} else {
+		format!("{}d {:02}h", secs / 86400, secs / 3600 % 24)
+	}
+}
+
+/// Format a duration as `m:ss` or `h:mm:ss`.
+fn format_clock(ms: f64) -> String {
+	let secs = (ms / 1000.0) as u64;
+	if secs < 3600 {
+		format!("{}:{:02}", secs / 60, secs % 60)
+	} else {
+		format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+	}
+}
+
+/// Format the distance from `now` to `timestamp` as `3m ago` or `in 3m`.
+fn format_relative(timestamp: f64, now: f64) -> String {
+	const UNITS: [(&str, f64); 7] = [
+		("s", 1.0),
+		("m", 60.0),
+		("h", 3600.0),
+		("d", 86400.0),
+		("w", 7.0 * 86400.0),
+		("mo", 30.0 * 86400.0),
+		("y", 365.0 * 86400.0),
+	];
+	let delta = (now - timestamp) / 1000.0;
+	let secs = delta.abs();
+	if secs < 5.0 {
+		return "just now".to_string();
+	}
+	let mut unit = 0;
+	while unit + 1 < UNITS.len() && secs >= UNITS[unit + 1].1 {
+		unit += 1;
+	}
+	let (suffix, size) = UNITS[unit];
+	let count = (secs / size).floor() as u64;
+	if delta >= 0.0 {
+		format!("{count}{suffix} ago")
+	} else {
+		format!("in {count}{suffix}")
+	}
+}
+
+/// Pad `text` to `width` columns on the side `align` names.
+fn pad(text: String, width: Option<u32>, align: Option<&str>) -> Result<String> {
+	let right = match align {
+		None | Some("right") => true,
+		Some("left") => false,
+		Some(other) => return Err(Error::from_reason(format!("Unknown alignment: {other}"))),
+	};
+	let width = width.unwrap_or(0) as usize;
+	Ok(if right {
+		format!("{text:>width$}")
+	} else {
+		format!("{text:<width$}")
+	})
+}
+
+fn now_ms() -> f64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Format a byte count, e.g. `512B`, `1.5KB`, or `3.2GB`.
+///
+/// # Arguments
+/// - `bytes`: Byte count.
+/// - `options`: Unit base, decimals, and column width.
+///
+/// # Errors
+/// Returns an error for an unknown alignment.
+#[napi(js_name = "formatBytes")]
+pub fn format_bytes_napi(bytes: f64, options: Option<FormatBytesOptions>) -> Result<String> {
+	let options = options.unwrap_or(FormatBytesOptions {
+		si:       None,
+		decimals: None,
+		width:    None,
+		align:    None,
+	});
+	let text =
+		format_bytes(bytes, options.si.unwrap_or(false), options.decimals.unwrap_or(1) as usize);
+	pad(text, options.width, options.align.as_deref())
+}
+
+/// Format a duration, e.g. `850ms`, `4.2s`, `3m 05s`, or `2h 05m`.
+///
+/// # Arguments
+/// - `ms`: Duration in milliseconds; negative values are formatted as zero.
+/// - `options`: Style and column width.
+///
+/// # Errors
+/// Returns an error for an unknown style or alignment.
+#[napi(js_name = "formatDuration")]
+pub fn format_duration(ms: f64, options: Option<FormatDurationOptions>) -> Result<String> {
+	let options = options.unwrap_or(FormatDurationOptions { style: None, width: None, align: None });
+	let ms = ms.max(0.0);
+	let text = match options.style.as_deref() {
+		None | Some("compact") => format_compact(ms),
+		Some("clock") => format_clock(ms),
+		Some(other) => return Err(Error::from_reason(format!("Unknown duration style: {other}"))),
+	};
+	pad(text, options.width, options.align.as_deref())
+}
+
+/// Format a timestamp relative to now, e.g. `just now`, `3m ago`, `2d ago`,
+/// or `in 5m`.
+///
+/// # Arguments
+/// - `timestamp`: Milliseconds since the Unix epoch.
+/// - `options`: Reference time and column width.
+///
+/// # Errors
+/// Returns an error for an unknown alignment.
+#[napi(js_name = "formatRelativeTime")]
+pub fn format_relative_time(
+	timestamp: f64,
+	options: Option<FormatRelativeTimeOptions>,
+) -> Result<String> {
+	let options =
+		options.unwrap_or(FormatRelativeTimeOptions { now: None, width: None, align: None });
+	let now = options.now.unwrap_or_else(now_ms);
+	pad(format_relative(timestamp, now), options.width, options.align.as_deref())
+}
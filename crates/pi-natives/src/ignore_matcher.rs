@@ -0,0 +1,426 @@
+//! Standalone ignore checks with the walker's rules.
+//!
+//! # Overview
+//! `IgnoreMatcher` answers "would `glob` skip this path?" for arbitrary paths
+//! under a root without walking the tree. It applies the same rules as the
+//! walker in [`fs_cache`]: `.git` and `node_modules` are skipped, hidden
+//! entries are skipped unless requested, and ignore files are honored with
+//! the walker's precedence:
+//!
+//! - `.ignore` files, nearest directory first;
+//! - `.gitignore` files, nearest directory first, including those above the
+//!   root up to the repository root;
+//! - `.git/info/exclude` of the repository;
+//! - the global git excludes file (`core.excludesFile`).
+//!
+//! Git rules only apply inside a repository, as in the walker. A directory
+//! that is ignored hides everything under it, and a whitelist rule (`!.env`)
+//! makes a hidden entry visible.
+//!
+//! Rule files are read on first use and cached; call `reload()` after they
+//! change.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const matcher = new native.IgnoreMatcher("/repo");
+//! // JS: matcher.isIgnored("dist/bundle.js") // -> true
+//! // JS: matcher.isIgnored("/repo/src/deleted.ts", false) // -> false
+//! ```
+
+use std::{
+	collections::HashMap,
+	ffi::OsStr,
+	path::{Path, PathBuf},
+};
+
+use ignore::{
+	Match,
+	gitignore::{Gitignore, GitignoreBuilder},
+};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::fs_cache;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for creating an `IgnoreMatcher`.
+#[napi(object)]
+pub struct IgnoreMatcherOptions {
+	/// Treat hidden files as visible (default: false).
+	pub hidden:               Option<bool>,
+	/// Respect .gitignore, .ignore, and git exclude files (default: true).
+	pub gitignore:            Option<bool>,
+	/// Treat `node_modules` entries as visible (default: false).
+	#[napi(js_name = "includeNodeModules")]
+	pub include_node_modules: Option<bool>,
+}
+
+/// Ignore files found in one directory.
+struct DirRules {
+	/// `.ignore`.
+	ignore:    Option<Gitignore>,
+	/// `.gitignore`.
+	gitignore: Option<Gitignore>,
+	/// `.git/info/exclude`, for the repository root only.
+	exclude:   Option<Gitignore>,
+}
+
+/// Decides whether paths under a root are ignored, as the `glob` walker
+/// would.
+#[napi]
+pub struct IgnoreMatcher {
+	root:                 PathBuf,
+	include_hidden:       bool,
+	use_gitignore:        bool,
+	include_node_modules: bool,
+	/// Nearest directory at or above the root holding `.git`.
+	repo_root:            Option<PathBuf>,
+	/// Global git excludes, matched relative to the repository root.
+	global:               Option<Gitignore>,
+	/// Per-directory rules, loaded on first use.
+	rules:                HashMap<PathBuf, DirRules>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Matching
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Load the ignore file at `path`, if it exists and parses.
+fn load_rules(dir: &Path, path: &Path) -> Option<Gitignore> {
+	if !path.is_file() {
+		return None;
+	}
+	let mut builder = GitignoreBuilder::new(dir);
+	if builder.add(path).is_some() {
+		return None;
+	}
+	builder.build().ok()
+}
+
+/// First decisive match of `rules` along `dirs`.
+fn first_match<'a>(
+	dirs: &[&'a DirRules],
+	rules: impl Fn(&'a DirRules) -> Option<&'a Gitignore>,
+	path: &Path,
+	is_dir: bool,
+) -> Match<()> {
+	dirs
+		.iter()
+		.filter_map(|dir| rules(dir))
+		.map(|matcher| matcher.matched(path, is_dir).map(|_| ()))
+		.find(|decision| !decision.is_none())
+		.unwrap_or(Match::None)
+}
+
+impl IgnoreMatcher {
+	/// Create a matcher for `root`, which should already be resolved.
+	pub fn with_settings(
+		root: PathBuf,
+		include_hidden: bool,
+		use_gitignore: bool,
+		include_node_modules: bool,
+	) -> Self {
+		let repo_root = root
+			.ancestors()
+			.find(|dir| dir.join(".git").exists())
+			.map(Path::to_path_buf);
+		let global = repo_root
+			.as_ref()
+			.filter(|_| use_gitignore)
+			.map(|repo_root| GitignoreBuilder::new(repo_root).build_global().0)
+			.filter(|global| !global.is_empty());
+		Self {
+			root,
+			include_hidden,
+			use_gitignore,
+			include_node_modules,
+			repo_root,
+			global,
+			rules: HashMap::new(),
+		}
+	}
+
+	/// Drop cached rules for `dir`, so edited ignore files take effect.
+	pub fn forget(&mut self, dir: &Path) {
+		self.rules.remove(dir);
+	}
+
+	/// Whether `path`, an absolute path under the root, is ignored. Paths
+	/// outside the root are never ignored.
+	pub fn is_ignored_path(&mut self, path: &Path, is_dir: bool) -> bool {
+		let Ok(relative) = path.strip_prefix(&self.root) else {
+			return false;
+		};
+		let mut current = self.root.clone();
+		let mut components = relative.components().peekable();
+		while let Some(component) = components.next() {
+			current.push(component);
+			// Ancestors of `path` are directories; the walker would prune them.
+			let entry_is_dir = components.peek().is_some() || is_dir;
+			if self.entry_ignored(&current, entry_is_dir) {
+				return true;
+			}
+		}
+		false
+	}
+
+	/// Whether the walker skips the entry at `path`, given that its parents
+	/// are not skipped.
+	fn entry_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+		let name = path.file_name().unwrap_or_default();
+		if name == ".git" || (!self.include_node_modules && name == "node_modules") {
+			return true;
+		}
+
+		let decision = if self.use_gitignore {
+			self.rules_match(path, is_dir)
+		} else {
+			Match::None
+		};
+		match decision {
+			Match::Ignore(()) => true,
+			// A whitelisted entry stays visible even when hidden.
+			Match::Whitelist(()) => false,
+			Match::None => !self.include_hidden && is_hidden(name),
+		}
+	}
+
+	/// Decision of the ignore files that apply to `path`.
+	fn rules_match(&mut self, path: &Path, is_dir: bool) -> Match<()> {
+		// Rules above the root apply inside a repository, up to its root.
+		let top = self.repo_root.as_deref().unwrap_or(&self.root);
+		let dirs: Vec<PathBuf> = path
+			.ancestors()
+			.skip(1)
+			.take_while(|dir| dir.starts_with(top))
+			.map(Path::to_path_buf)
+			.collect();
+		for dir in &dirs {
+			if !self.rules.contains_key(dir) {
+				let rules = self.load_dir(dir);
+				self.rules.insert(dir.clone(), rules);
+			}
+		}
+		let dirs: Vec<&DirRules> = dirs.iter().map(|dir| &self.rules[dir]).collect();
+
+		let ignore = first_match(&dirs, |rules| rules.ignore.as_ref(), path, is_dir);
+		if !ignore.is_none() || self.repo_root.is_none() {
+			return ignore;
+		}
+		let gitignore = first_match(&dirs, |rules| rules.gitignore.as_ref(), path, is_dir);
+		if !gitignore.is_none() {
+			return gitignore;
+		}
+		let exclude = first_match(&dirs, |rules| rules.exclude.as_ref(), path, is_dir);
+		if !exclude.is_none() {
+			return exclude;
+		}
+		self
+			.global
+			.as_ref()
+			.map_or(Match::None, |global| global.matched(path, is_dir).map(|_| ()))
+	}
+
+	/// Read the ignore files in `dir`.
+	fn load_dir(&self, dir: &Path) -> DirRules {
+		let in_repo = self.repo_root.is_some();
+		let is_repo_root = self.repo_root.as_deref() == Some(dir);
+		DirRules {
+			ignore:    load_rules(dir, &dir.join(".ignore")),
+			gitignore: if in_repo {
+				load_rules(dir, &dir.join(".gitignore"))
+			} else {
+				None
+			},
+			exclude:   if is_repo_root {
+				load_rules(dir, &dir.join(".git").join("info").join("exclude"))
+			} else {
+				None
+			},
+		}
+	}
+}
+
+fn is_hidden(name: &OsStr) -> bool {
+	name.to_str().is_some_and(|name| name.starts_with('.'))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[napi]
+impl IgnoreMatcher {
+	/// Create a matcher for paths under `root`.
+	///
+	/// # Errors
+	/// Returns an error when `root` cannot be resolved or is not a directory.
+	#[napi(constructor)]
+	pub fn new(root: String, options: Option<IgnoreMatcherOptions>) -> Result<Self> {
+		let (hidden, gitignore, include_node_modules) = match options {
+			Some(opts) => (opts.hidden, opts.gitignore, opts.include_node_modules),
+			None => (None, None, None),
+		};
+		Ok(Self::with_settings(
+			fs_cache::resolve_search_path(&root)?,
+			hidden.unwrap_or(false),
+			gitignore.unwrap_or(true),
+			include_node_modules.unwrap_or(false),
+		))
+	}
+
+	/// Check whether `glob` would skip `path`.
+	///
+	/// # Arguments
+	/// - `path`: Path relative to the root, or absolute.
+	/// - `is_dir`: Whether the path is a directory (default: checked on disk, so
+	///   pass it for paths that no longer exist).
+	///
+	/// # Returns
+	/// `true` when the path or one of its parents is ignored; paths outside
+	/// the root are never ignored.
+	#[napi(js_name = "isIgnored")]
+	pub fn is_ignored(&mut self, path: String, is_dir: Option<bool>) -> bool {
+		let mut path = self.root.join(path);
+		if !path.starts_with(&self.root)
+			&& let Ok(resolved) = std::fs::canonicalize(&path)
+		{
+			// The root is resolved; resolve the path too before giving up.
+			path = resolved;
+		}
+		let is_dir = is_dir.unwrap_or_else(|| path.is_dir());
+		self.is_ignored_path(&path, is_dir)
+	}
+
+	/// Forget cached ignore files, so edits to them take effect.
+	#[napi]
+	pub fn reload(&mut self) {
+		self.rules.clear();
+	}
+
+	/// Resolved root directory.
+	#[napi(getter)]
+	pub fn root(&self) -> String {
+		self.root.to_string_lossy().into_owned()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::ScratchDir;
+
+	fn scratch(name: &str, git: bool) -> ScratchDir {
+		let root = ScratchDir::new(&format!("ignore-{name}"));
+		if git {
+			std::fs::create_dir_all(root.join(".git/info")).unwrap();
+		}
+		root
+	}
+
+	fn write(root: &Path, path: &str, contents: &str) {
+		let path = root.join(path);
+		std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+		std::fs::write(path, contents).unwrap();
+	}
+
+	fn matcher(root: &Path) -> IgnoreMatcher {
+		IgnoreMatcher::with_settings(root.to_path_buf(), false, true, false)
+	}
+
+	#[test]
+	fn test_ignored_directory_hides_its_contents() {
+		let root = scratch("dirs", true);
+		write(&root, ".gitignore", "dist/\n*.log\n");
+		let mut matcher = matcher(&root);
+		assert!(matcher.is_ignored_path(&root.join("dist"), true));
+		assert!(matcher.is_ignored_path(&root.join("dist/app.js"), false));
+		assert!(matcher.is_ignored_path(&root.join("src/debug.log"), false));
+		assert!(!matcher.is_ignored_path(&root.join("src/main.rs"), false));
+		// `dist/` only matches directories.
+		assert!(!matcher.is_ignored_path(&root.join("dist"), false));
+		assert!(matcher.is_ignored_path(&root.join(".git/config"), false));
+		assert!(matcher.is_ignored_path(&root.join("node_modules/pkg/index.js"), false));
+		assert!(!matcher.is_ignored_path(Path::new("/elsewhere/dist/app.js"), false));
+	}
+
+	#[test]
+	fn test_whitelist_makes_hidden_entry_visible() {
+		let root = scratch("hidden", true);
+		write(&root, ".gitignore", "!.env\n");
+		let mut matcher = matcher(&root);
+		assert!(matcher.is_ignored_path(&root.join(".cache"), false));
+		assert!(matcher.is_ignored_path(&root.join(".config/app.toml"), false));
+		assert!(!matcher.is_ignored_path(&root.join(".env"), false));
+
+		let mut shown = IgnoreMatcher::with_settings(root.to_path_buf(), true, true, true);
+		assert!(!shown.is_ignored_path(&root.join(".cache"), false));
+		assert!(!shown.is_ignored_path(&root.join("node_modules/pkg/index.js"), false));
+		assert!(shown.is_ignored_path(&root.join(".git/config"), false));
+	}
+
+	#[test]
+	fn test_precedence_of_ignore_files() {
+		let root = scratch("precedence", true);
+		write(&root, ".git/info/exclude", "*.tmp\nexcluded.txt\n");
+		write(&root, ".gitignore", "*.log\n!excluded.txt\n");
+		write(&root, ".ignore", "!keep.log\n");
+		write(&root, "sub/.gitignore", "!trace.log\n*.tmp\n!scratch.tmp\n");
+		let mut matcher = matcher(&root);
+		// `.ignore` overrides `.gitignore`.
+		assert!(!matcher.is_ignored_path(&root.join("keep.log"), false));
+		assert!(matcher.is_ignored_path(&root.join("other.log"), false));
+		// The nearest `.gitignore` wins.
+		assert!(!matcher.is_ignored_path(&root.join("sub/trace.log"), false));
+		assert!(matcher.is_ignored_path(&root.join("sub/other.log"), false));
+		// `.gitignore` overrides `.git/info/exclude`.
+		assert!(!matcher.is_ignored_path(&root.join("excluded.txt"), false));
+		assert!(matcher.is_ignored_path(&root.join("a.tmp"), false));
+		assert!(!matcher.is_ignored_path(&root.join("sub/scratch.tmp"), false));
+	}
+
+	#[test]
+	fn test_rules_above_root_apply_inside_repository() {
+		let repo = scratch("parents", true);
+		write(&repo, ".gitignore", "*.log\n");
+		write(&repo, "pkg/src/main.rs", "");
+		let root = repo.join("pkg");
+		let mut matcher = matcher(&root);
+		assert!(matcher.is_ignored_path(&root.join("src/debug.log"), false));
+		assert!(!matcher.is_ignored_path(&root.join("src/main.rs"), false));
+	}
+
+	#[test]
+	fn test_git_rules_need_a_repository() {
+		let root = scratch("norepo", false);
+		write(&root, ".gitignore", "*.log\n");
+		write(&root, ".ignore", "*.tmp\n");
+		let mut matcher = matcher(&root);
+		assert!(!matcher.is_ignored_path(&root.join("debug.log"), false));
+		assert!(matcher.is_ignored_path(&root.join("a.tmp"), false));
+
+		let mut unfiltered = IgnoreMatcher::with_settings(root.to_path_buf(), false, false, false);
+		assert!(!unfiltered.is_ignored_path(&root.join("a.tmp"), false));
+	}
+
+	#[test]
+	fn test_rules_are_cached_until_forgotten() {
+		let root = scratch("reload", true);
+		write(&root, ".gitignore", "*.log\n");
+		let mut matcher = matcher(&root);
+		assert!(matcher.is_ignored_path(&root.join("debug.log"), false));
+
+		write(&root, ".gitignore", "*.tmp\n");
+		assert!(matcher.is_ignored_path(&root.join("debug.log"), false));
+		matcher.forget(&root);
+		assert!(!matcher.is_ignored_path(&root.join("debug.log"), false));
+		assert!(matcher.is_ignored_path(&root.join("a.tmp"), false));
+
+		write(&root, ".gitignore", "");
+		matcher.reload();
+		assert!(!matcher.is_ignored_path(&root.join("a.tmp"), false));
+	}
+}
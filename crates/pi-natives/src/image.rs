@@ -149,3 +149,119 @@ fn encode_image(img: &DynamicImage, format: u8, quality: u8) -> Result<Vec<u8>>
 		_ => Err(Error::from_reason(format!("Invalid image format: {format}"))),
 	}
 }
+
+/// Terminal cell size in pixels.
+#[napi(object)]
+pub struct CellPixels {
+	/// Cell width in pixels.
+	pub width:  u32,
+	/// Cell height in pixels.
+	pub height: u32,
+}
+
+/// Options for `prepareImage`.
+#[napi(object)]
+pub struct PrepareImageOptions {
+	/// Maximum width in terminal columns.
+	#[napi(js_name = "maxCols")]
+	pub max_cols:    Option<u32>,
+	/// Maximum height in terminal rows.
+	#[napi(js_name = "maxRows")]
+	pub max_rows:    Option<u32>,
+	/// Cell size in pixels (default: 8x16).
+	#[napi(js_name = "cellPixels")]
+	pub cell_pixels: Option<CellPixels>,
+}
+
+/// Decoded image scaled to fit a cell box.
+#[napi(object)]
+pub struct PreparedImage {
+	/// RGBA pixel data, 4 bytes per pixel, row-major.
+	pub data:            Uint8Array,
+	/// Scaled width in pixels.
+	pub width:           u32,
+	/// Scaled height in pixels.
+	pub height:          u32,
+	/// Columns covered by the scaled image.
+	pub cols:            u32,
+	/// Rows covered by the scaled image.
+	pub rows:            u32,
+	/// Original width in pixels.
+	#[napi(js_name = "originalWidth")]
+	pub original_width:  u32,
+	/// Original height in pixels.
+	#[napi(js_name = "originalHeight")]
+	pub original_height: u32,
+}
+
+/// Largest size with the image's aspect ratio that fits `max_w`x`max_h`,
+/// never upscaling.
+fn fit_size(width: u32, height: u32, max_w: Option<u32>, max_h: Option<u32>) -> (u32, u32) {
+	let scale_w = max_w.map_or(1.0, |max| f64::from(max) / f64::from(width));
+	let scale_h = max_h.map_or(1.0, |max| f64::from(max) / f64::from(height));
+	let scale = scale_w.min(scale_h).min(1.0);
+	let scaled = |len: u32| ((f64::from(len) * scale).round() as u32).max(1);
+	(scaled(width), scaled(height))
+}
+
+/// Decode an image and downscale it to fit a terminal cell box.
+///
+/// # Arguments
+/// - `input`: File path or encoded bytes (PNG, JPEG, GIF, WebP). Only the
+///   first frame of an animated GIF is used.
+/// - `options`: Cell box limits and cell size.
+///
+/// # Returns
+/// RGBA pixels with the scaled size and the cells they cover.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or the image cannot be decoded.
+#[napi(js_name = "prepareImage")]
+pub fn prepare_image(
+	input: Either<String, Uint8Array>,
+	options: Option<PrepareImageOptions>,
+) -> task::Async<PreparedImage> {
+	let source = match input {
+		Either::A(path) => Either::A(path),
+		Either::B(bytes) => Either::B(bytes.to_vec()),
+	};
+	let (max_cols, max_rows, cell) = match options {
+		Some(opts) => (opts.max_cols, opts.max_rows, opts.cell_pixels),
+		None => (None, None, None),
+	};
+	let (cell_w, cell_h) = cell.map_or((8, 16), |cell| (cell.width.max(1), cell.height.max(1)));
+	task::blocking("image.prepare", (), move |_| {
+		let bytes = match source {
+			Either::A(path) => std::fs::read(&path)
+				.map_err(|e| Error::from_reason(format!("Failed to read {path}: {e}")))?,
+			Either::B(bytes) => bytes,
+		};
+		let img = ImageReader::new(Cursor::new(bytes))
+			.with_guessed_format()
+			.map_err(|e| Error::from_reason(format!("Failed to detect image format: {e}")))?
+			.decode()
+			.map_err(|e| Error::from_reason(format!("Failed to decode image: {e}")))?;
+
+		let (original_width, original_height) = (img.width(), img.height());
+		let (width, height) = fit_size(
+			original_width,
+			original_height,
+			max_cols.map(|cols| cols.saturating_mul(cell_w)),
+			max_rows.map(|rows| rows.saturating_mul(cell_h)),
+		);
+		let img = if (width, height) == (original_width, original_height) {
+			img
+		} else {
+			img.resize_exact(width, height, FilterType::Lanczos3)
+		};
+		Ok(PreparedImage {
+			data: Uint8Array::from(img.into_rgba8().into_raw()),
+			width,
+			height,
+			cols: width.div_ceil(cell_w),
+			rows: height.div_ceil(cell_h),
+			original_width,
+			original_height,
+		})
+	})
+}
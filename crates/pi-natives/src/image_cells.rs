@@ -0,0 +1,339 @@
+//! Character-cell image rendering for terminals without graphics protocols.
+//!
+//! # Overview
+//! Converts RGBA pixels into rows of styled characters:
+//! - `halfblock`: each cell shows two vertically stacked pixels with `▀`
+//!   (foreground = top, background = bottom), so the image should be scaled to
+//!   `cols x rows*2` pixels first.
+//! - `braille`: each cell is a 2x4 dot grid; a dot is lit when its pixel is
+//!   brighter than the image's mean luminance, and the cell takes the average
+//!   color of its lit pixels. Scale to `cols*2 x rows*4` pixels first.
+//!
+//! Colors are emitted as truecolor SGR or mapped to the xterm 256-color or
+//! 16-color palettes. Pixels with alpha below 128 are left unpainted.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const img = await native.prepareImage("logo.png", { maxCols: 40, maxRows: 20, cellPixels: { width: 1, height: 2 } })
+//! // JS: native.renderImageCells(img.data, img.width, img.height, { mode: "halfblock" }).forEach(r => console.log(r))
+//! ```
+
+use std::fmt::Write;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `renderImageCells`.
+#[napi(object)]
+pub struct RenderImageCellsOptions {
+	/// `"halfblock"` (default) or `"braille"`.
+	pub mode:  Option<String>,
+	/// Color depth: `"truecolor"` (default), `"256"`, or `"16"`.
+	pub color: Option<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Colors
+// ═══════════════════════════════════════════════════════════════════════════
+
+type Rgb = [u8; 3];
+
+const ALPHA_CUTOFF: u8 = 128;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColorDepth {
+	TrueColor,
+	Ansi256,
+	Ansi16,
+}
+
+impl ColorDepth {
+	fn parse(name: Option<&str>) -> Result<Self> {
+		match name {
+			None | Some("truecolor") => Ok(Self::TrueColor),
+			Some("256") => Ok(Self::Ansi256),
+			Some("16") => Ok(Self::Ansi16),
+			Some(other) => Err(Error::from_reason(format!("Unknown color depth: {other}"))),
+		}
+	}
+}
+
+/// The 16 ANSI colors as rendered by xterm.
+const ANSI16: [Rgb; 16] = [
+	[0, 0, 0],
+	[205, 0, 0],
+	[0, 205, 0],
+	[205, 205, 0],
+	[0, 0, 238],
+	[205, 0, 205],
+	[0, 205, 205],
+	[229, 229, 229],
+	[127, 127, 127],
+	[255, 0, 0],
+	[0, 255, 0],
+	[255, 255, 0],
+	[92, 92, 255],
+	[255, 0, 255],
+	[0, 255, 255],
+	[255, 255, 255],
+];
+
+fn distance(a: Rgb, b: Rgb) -> i32 {
+	(0..3)
+		.map(|i| (i32::from(a[i]) - i32::from(b[i])).pow(2))
+		.sum()
+}
+
+/// Nearest xterm 256-color index, choosing between the 6x6x6 cube and the
+/// gray ramp.
+fn to_ansi256(rgb: Rgb) -> u8 {
+	const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+	let cube_index = |v: u8| match v {
+		0..=47 => 0,
+		48..=114 => 1,
+		_ => (v - 35) / 40,
+	};
+	let [r, g, b] = rgb.map(cube_index);
+	let cube = [LEVELS[r as usize], LEVELS[g as usize], LEVELS[b as usize]];
+	let mean = (u32::from(rgb[0]) + u32::from(rgb[1]) + u32::from(rgb[2])) / 3;
+	let gray_index = if mean > 238 { 23 } else { (mean.saturating_sub(3) / 10) as u8 };
+	let level = 8 + 10 * gray_index;
+	if distance([level; 3], rgb) < distance(cube, rgb) {
+		232 + gray_index
+	} else {
+		16 + 36 * r + 6 * g + b
+	}
+}
+
+fn to_ansi16(rgb: Rgb) -> u8 {
+	(0..16u8)
+		.min_by_key(|&i| distance(ANSI16[usize::from(i)], rgb))
+		.unwrap_or(0)
+}
+
+/// Append the SGR parameters selecting `rgb` as foreground or background.
+fn push_color(out: &mut String, depth: ColorDepth, rgb: Rgb, background: bool) {
+	let _ = match depth {
+		ColorDepth::TrueColor => {
+			let base = if background { 48 } else { 38 };
+			write!(out, "{base};2;{};{};{}", rgb[0], rgb[1], rgb[2])
+		},
+		ColorDepth::Ansi256 => {
+			let base = if background { 48 } else { 38 };
+			write!(out, "{base};5;{}", to_ansi256(rgb))
+		},
+		ColorDepth::Ansi16 => {
+			let index = to_ansi16(rgb);
+			let base = match (background, index < 8) {
+				(false, true) => 30,
+				(false, false) => 90 - 8,
+				(true, true) => 40,
+				(true, false) => 100 - 8,
+			};
+			write!(out, "{}", base + index)
+		},
+	};
+}
+
+/// Builds one row of styled cells, emitting SGR only when colors change.
+struct RowWriter {
+	depth: ColorDepth,
+	out:   String,
+	fg:    Option<Rgb>,
+	bg:    Option<Rgb>,
+	dirty: bool,
+}
+
+impl RowWriter {
+	const fn new(depth: ColorDepth) -> Self {
+		Self { depth, out: String::new(), fg: None, bg: None, dirty: false }
+	}
+
+	fn cell(&mut self, ch: char, fg: Option<Rgb>, bg: Option<Rgb>) {
+		// Compare in the output palette so equal mapped colors are not re-sent.
+		let fg_key = fg.map(|rgb| self.quantize(rgb));
+		let bg_key = bg.map(|rgb| self.quantize(rgb));
+		if fg_key != self.fg || bg_key != self.bg {
+			if (self.fg.is_some() && fg_key.is_none()) || (self.bg.is_some() && bg_key.is_none()) {
+				self.out.push_str("\x1b[0m");
+				self.fg = None;
+				self.bg = None;
+			}
+			let mut params = String::new();
+			if let Some(rgb) = fg.filter(|_| fg_key != self.fg) {
+				push_color(&mut params, self.depth, rgb, false);
+			}
+			if let Some(rgb) = bg.filter(|_| bg_key != self.bg) {
+				if !params.is_empty() {
+					params.push(';');
+				}
+				push_color(&mut params, self.depth, rgb, true);
+			}
+			if !params.is_empty() {
+				let _ = write!(self.out, "\x1b[{params}m");
+				self.dirty = true;
+			}
+			self.fg = fg_key;
+			self.bg = bg_key;
+		}
+		self.out.push(ch);
+	}
+
+	fn quantize(&self, rgb: Rgb) -> Rgb {
+		match self.depth {
+			ColorDepth::TrueColor => rgb,
+			ColorDepth::Ansi256 => [to_ansi256(rgb), 0, 0],
+			ColorDepth::Ansi16 => ANSI16[usize::from(to_ansi16(rgb))],
+		}
+	}
+
+	fn finish(mut self) -> String {
+		if self.dirty && (self.fg.is_some() || self.bg.is_some()) {
+			self.out.push_str("\x1b[0m");
+		}
+		self.out
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Rendering
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// RGBA image view with transparent-aware pixel access.
+struct Pixels<'a> {
+	rgba:   &'a [u8],
+	width:  usize,
+	height: usize,
+}
+
+impl Pixels<'_> {
+	/// Opaque pixel color, or `None` if out of bounds or transparent.
+	fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+		if x >= self.width || y >= self.height {
+			return None;
+		}
+		let i = (y * self.width + x) * 4;
+		let px = &self.rgba[i..i + 4];
+		(px[3] >= ALPHA_CUTOFF).then(|| [px[0], px[1], px[2]])
+	}
+}
+
+fn luminance(rgb: Rgb) -> u32 {
+	(299 * u32::from(rgb[0]) + 587 * u32::from(rgb[1]) + 114 * u32::from(rgb[2])) / 1000
+}
+
+fn render_halfblock(pixels: &Pixels<'_>, depth: ColorDepth) -> Vec<String> {
+	(0..pixels.height.div_ceil(2))
+		.map(|row| {
+			let mut writer = RowWriter::new(depth);
+			for x in 0..pixels.width {
+				let top = pixels.get(x, row * 2);
+				let bottom = pixels.get(x, row * 2 + 1);
+				match (top, bottom) {
+					(Some(_), _) => writer.cell('▀', top, bottom),
+					(None, Some(_)) => writer.cell('▄', bottom, None),
+					(None, None) => writer.cell(' ', None, None),
+				}
+			}
+			writer.finish()
+		})
+		.collect()
+}
+
+/// Braille dot bits indexed by `[y][x]` within a 2x4 cell.
+const BRAILLE_BITS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+fn render_braille(pixels: &Pixels<'_>, depth: ColorDepth) -> Vec<String> {
+	let mut total = 0u64;
+	let mut opaque = 0u64;
+	for y in 0..pixels.height {
+		for x in 0..pixels.width {
+			if let Some(rgb) = pixels.get(x, y) {
+				total += u64::from(luminance(rgb));
+				opaque += 1;
+			}
+		}
+	}
+	let threshold = total.checked_div(opaque).unwrap_or(0) as u32;
+
+	(0..pixels.height.div_ceil(4))
+		.map(|row| {
+			let mut writer = RowWriter::new(depth);
+			for col in 0..pixels.width.div_ceil(2) {
+				let mut bits = 0;
+				let mut sum = [0u32; 3];
+				let mut lit = 0;
+				for (dy, row_bits) in BRAILLE_BITS.iter().enumerate() {
+					for (dx, bit) in row_bits.iter().enumerate() {
+						let Some(rgb) = pixels.get(col * 2 + dx, row * 4 + dy) else {
+							continue;
+						};
+						if luminance(rgb) >= threshold {
+							bits |= bit;
+							lit += 1;
+							for (acc, value) in sum.iter_mut().zip(rgb) {
+								*acc += u32::from(value);
+							}
+						}
+					}
+				}
+				if lit == 0 {
+					writer.cell(' ', None, None);
+				} else {
+					let color = sum.map(|acc| (acc / lit) as u8);
+					let ch = char::from_u32(0x2800 + bits).unwrap_or(' ');
+					writer.cell(ch, Some(color), None);
+				}
+			}
+			writer.finish()
+		})
+		.collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Render an RGBA image as rows of styled characters.
+///
+/// # Arguments
+/// - `rgba`: Pixel data, 4 bytes per pixel, row-major.
+/// - `width`, `height`: Image dimensions in pixels.
+/// - `options`: Cell mode and color depth.
+///
+/// # Returns
+/// One string per terminal row, each ending with an SGR reset if colored.
+///
+/// # Errors
+/// Returns an error for an unknown mode or color depth, or if the buffer
+/// length does not match the dimensions.
+#[napi(js_name = "renderImageCells")]
+pub fn render_image_cells(
+	rgba: Uint8Array,
+	width: u32,
+	height: u32,
+	options: Option<RenderImageCellsOptions>,
+) -> Result<Vec<String>> {
+	let (mode, color) = match options {
+		Some(opts) => (opts.mode, opts.color),
+		None => (None, None),
+	};
+	let depth = ColorDepth::parse(color.as_deref())?;
+	let (width, height) = (width as usize, height as usize);
+	if width.checked_mul(height).and_then(|n| n.checked_mul(4)) != Some(rgba.len()) {
+		return Err(Error::from_reason(format!(
+			"RGBA buffer has {} bytes, expected {width}x{height}x4",
+			rgba.len()
+		)));
+	}
+	let pixels = Pixels { rgba: rgba.as_ref(), width, height };
+	match mode.as_deref() {
+		None | Some("halfblock") => Ok(render_halfblock(&pixels, depth)),
+		Some("braille") => Ok(render_braille(&pixels, depth)),
+		Some(other) => Err(Error::from_reason(format!("Unknown render mode: {other}"))),
+	}
+}
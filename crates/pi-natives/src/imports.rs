@@ -0,0 +1,700 @@
+//! Import extraction and file-level dependency graphs.
+//!
+//! # Overview
+//! Finds the import statements of a source file with tree-sitter (the same
+//! languages as symbol extraction) and resolves each one to the file it
+//! refers to, so the context selector can pull in the files related to the
+//! one being edited.
+//!
+//! Resolution is static and filesystem-based:
+//! - JavaScript/TypeScript: relative specifiers, trying the usual extensions,
+//!   `.js` -> `.ts` rewrites, and `index.*` files. Bare specifiers (packages)
+//!   stay unresolved.
+//! - Python: relative imports, and absolute imports found under the package
+//!   root (and the graph root or `src/`).
+//! - Rust: `mod foo;` and `use crate::`/`self::`/`super::` paths, resolved to
+//!   the deepest module file that exists.
+//! - Go: imports under the module path from `go.mod`, resolved to the package
+//!   directory.
+//!
+//! `buildImportGraph` walks a workspace and links every file to the files it
+//! imports and is imported by.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.extractImports("src/cli.ts")
+//! // JS: // -> [{ specifier: "./args", kind: "import", line: 1, resolved: "src/args.ts" }]
+//! // JS: const graph = await native.buildImportGraph(cwd)
+//! ```
+
+use std::{
+	collections::{BTreeSet, HashMap},
+	fs,
+	path::{Component, Path, PathBuf},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rayon::prelude::*;
+use tree_sitter::{Node, Parser};
+
+use crate::{
+	fs_cache::{build_walker, normalize_relative_path, should_skip_path},
+	symbols::Language,
+	task,
+};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// An import statement found in a source file.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct ImportRef {
+	/// Module as written (`./args`, `os.path`, `crate::fs::read`, `fmt`).
+	pub specifier: String,
+	/// Kind: "import", "export" (re-export), "require", "dynamic"
+	/// (`import()`), "use", "mod", or "extern".
+	pub kind:      String,
+	/// Line of the statement (1-based).
+	pub line:      u32,
+	/// Resolved file (a directory for Go packages), or `null` for external or
+	/// unresolvable modules.
+	pub resolved:  Option<String>,
+}
+
+/// Options for `buildImportGraph`.
+#[napi(object)]
+pub struct ImportGraphOptions<'env> {
+	/// Include hidden files and directories (default: false).
+	pub hidden:     Option<bool>,
+	/// Respect .gitignore and related ignore files (default: true).
+	pub gitignore:  Option<bool>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// A file in an import graph. Paths are relative to the graph root with
+/// forward slashes.
+#[napi(object)]
+pub struct ImportGraphNode {
+	/// The file.
+	pub path:        String,
+	/// Files this file imports.
+	pub imports:     Vec<String>,
+	/// Files that import this file.
+	#[napi(js_name = "importedBy")]
+	pub imported_by: Vec<String>,
+	/// Specifiers that did not resolve to a file in the graph (packages,
+	/// standard library, missing files).
+	pub external:    Vec<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Extraction
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Skip files larger than this when building a graph.
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+const JS_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// An import before resolution.
+struct RawImport {
+	specifier: String,
+	kind:      &'static str,
+	line:      u32,
+	/// Python `from M import a, b`: the imported names, which may be
+	/// submodules of `M`.
+	names:     Vec<String>,
+}
+
+fn text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+	source.get(node.byte_range()).unwrap_or_default()
+}
+
+/// Content of a string literal node (quotes removed).
+fn string_content(node: Node<'_>, source: &str) -> String {
+	text(node, source)
+		.trim_matches(|ch| matches!(ch, '"' | '\'' | '`'))
+		.to_string()
+}
+
+fn first_string_argument(call: Node<'_>, source: &str) -> Option<String> {
+	let argument = call.child_by_field_name("arguments")?.named_child(0)?;
+	(argument.kind() == "string").then(|| string_content(argument, source))
+}
+
+/// Flatten a Rust use tree into `::`-separated paths.
+fn flatten_use(node: Node<'_>, prefix: &str, source: &str, out: &mut Vec<String>) {
+	let join = |path: &str| {
+		let path: String = path.split_whitespace().collect();
+		if prefix.is_empty() {
+			path
+		} else {
+			format!("{prefix}::{path}")
+		}
+	};
+	match node.kind() {
+		"scoped_use_list" => {
+			let prefix = node
+				.child_by_field_name("path")
+				.map_or_else(|| prefix.to_string(), |path| join(text(path, source)));
+			if let Some(list) = node.child_by_field_name("list") {
+				flatten_use(list, &prefix, source, out);
+			}
+		},
+		"use_list" => {
+			let mut cursor = node.walk();
+			for child in node.named_children(&mut cursor) {
+				flatten_use(child, prefix, source, out);
+			}
+		},
+		"use_as_clause" => {
+			if let Some(path) = node.child_by_field_name("path") {
+				flatten_use(path, prefix, source, out);
+			}
+		},
+		"use_wildcard" => {
+			if let Some(path) = node.named_child(0) {
+				flatten_use(path, prefix, source, out);
+			}
+		},
+		_ => {
+			let path = join(text(node, source));
+			out.push(path.strip_suffix("::self").unwrap_or(&path).to_string());
+		},
+	}
+}
+
+fn python_names(node: Node<'_>, source: &str) -> Vec<String> {
+	let mut cursor = node.walk();
+	node
+		.children_by_field_name("name", &mut cursor)
+		.filter_map(|name| match name.kind() {
+			"aliased_import" => name.child_by_field_name("name"),
+			_ => Some(name),
+		})
+		.map(|name| text(name, source).to_string())
+		.collect()
+}
+
+/// Collect the import statements of a parsed file.
+fn collect_imports(node: Node<'_>, language: Language, source: &str, out: &mut Vec<RawImport>) {
+	let line = node.start_position().row as u32 + 1;
+	let mut push = |specifier: String, kind: &'static str, names: Vec<String>| {
+		if !specifier.is_empty() {
+			out.push(RawImport { specifier, kind, line, names });
+		}
+	};
+	let mut descend = true;
+	match (language, node.kind()) {
+		(Language::TypeScript | Language::Tsx | Language::JavaScript, kind) => match kind {
+			"import_statement" | "export_statement" => {
+				let clause = node
+					.named_children(&mut node.walk())
+					.find(|child| child.kind() == "import_require_clause");
+				let source_node = node
+					.child_by_field_name("source")
+					.or_else(|| clause.and_then(|clause| clause.child_by_field_name("source")));
+				if let Some(source_node) = source_node {
+					let kind = if kind == "export_statement" {
+						"export"
+					} else {
+						"import"
+					};
+					push(string_content(source_node, source), kind, Vec::new());
+				}
+			},
+			"call_expression" => {
+				let function = node.child_by_field_name("function");
+				match function.map(|function| (function.kind(), text(function, source))) {
+					Some(("import", _)) => {
+						push(
+							first_string_argument(node, source).unwrap_or_default(),
+							"dynamic",
+							Vec::new(),
+						);
+					},
+					Some(("identifier", "require")) => {
+						push(
+							first_string_argument(node, source).unwrap_or_default(),
+							"require",
+							Vec::new(),
+						);
+					},
+					_ => {},
+				}
+			},
+			_ => {},
+		},
+		(Language::Python, "import_statement") => {
+			for name in python_names(node, source) {
+				push(name, "import", Vec::new());
+			}
+			descend = false;
+		},
+		(Language::Python, "import_from_statement") => {
+			if let Some(module) = node.child_by_field_name("module_name") {
+				push(text(module, source).to_string(), "import", python_names(node, source));
+			}
+			descend = false;
+		},
+		(Language::Rust, "mod_item") if node.child_by_field_name("body").is_none() => {
+			if let Some(name) = node.child_by_field_name("name") {
+				push(text(name, source).to_string(), "mod", Vec::new());
+			}
+		},
+		(Language::Rust, "use_declaration") => {
+			let mut paths = Vec::new();
+			if let Some(argument) = node.child_by_field_name("argument") {
+				flatten_use(argument, "", source, &mut paths);
+			}
+			for path in paths {
+				push(path, "use", Vec::new());
+			}
+			descend = false;
+		},
+		(Language::Rust, "extern_crate_declaration") => {
+			if let Some(name) = node.child_by_field_name("name") {
+				push(text(name, source).to_string(), "extern", Vec::new());
+			}
+			descend = false;
+		},
+		(Language::Go, "import_spec") => {
+			if let Some(path) = node.child_by_field_name("path") {
+				push(string_content(path, source), "import", Vec::new());
+			}
+			descend = false;
+		},
+		_ => {},
+	}
+	if descend {
+		let mut cursor = node.walk();
+		for child in node.named_children(&mut cursor) {
+			collect_imports(child, language, source, out);
+		}
+	}
+}
+
+fn parse_imports(source: &str, language: Language) -> Result<Vec<RawImport>> {
+	let mut parser = Parser::new();
+	parser
+		.set_language(&language.grammar())
+		.map_err(|err| Error::from_reason(format!("Failed to load {language:?} grammar: {err}")))?;
+	let tree = parser
+		.parse(source, None)
+		.ok_or_else(|| Error::from_reason(format!("Failed to parse {language:?} source")))?;
+	let mut imports = Vec::new();
+	collect_imports(tree.root_node(), language, source, &mut imports);
+	Ok(imports)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Resolution
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Remove `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+	let mut out = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {},
+			Component::ParentDir => {
+				if !out.pop() {
+					out.push("..");
+				}
+			},
+			other => out.push(other),
+		}
+	}
+	out
+}
+
+fn parent_dir(file: &Path) -> &Path {
+	file.parent().unwrap_or_else(|| Path::new(""))
+}
+
+fn existing_file(path: PathBuf) -> Option<PathBuf> {
+	path.is_file().then_some(path)
+}
+
+fn resolve_js(file: &Path, specifier: &str) -> Option<PathBuf> {
+	if !(specifier.starts_with("./")
+		|| specifier.starts_with("../")
+		|| matches!(specifier, "." | ".."))
+	{
+		return None;
+	}
+	let base = normalize(&parent_dir(file).join(specifier));
+	let name = base.file_name()?.to_string_lossy().into_owned();
+	let with_ext = |ext: &str| base.with_file_name(format!("{name}.{ext}"));
+	if let Some(found) = existing_file(base.clone()) {
+		return Some(found);
+	}
+	// TypeScript sources import their compiled names (`./a.js` -> `./a.ts`).
+	let rewrites =
+		[("js", &["ts", "tsx"][..]), ("jsx", &["tsx"]), ("mjs", &["mts"]), ("cjs", &["cts"])];
+	for (compiled, sources) in rewrites {
+		if let Some(stem) = name.strip_suffix(&format!(".{compiled}")) {
+			for ext in sources {
+				if let Some(found) = existing_file(base.with_file_name(format!("{stem}.{ext}"))) {
+					return Some(found);
+				}
+			}
+		}
+	}
+	JS_EXTENSIONS
+		.iter()
+		.find_map(|ext| existing_file(with_ext(ext)))
+		.or_else(|| {
+			JS_EXTENSIONS
+				.iter()
+				.find_map(|ext| existing_file(base.join(format!("index.{ext}"))))
+		})
+}
+
+fn python_module(base: &Path, dotted: &str) -> Option<PathBuf> {
+	let mut path = base.to_path_buf();
+	for part in dotted.split('.').filter(|part| !part.is_empty()) {
+		path.push(part);
+	}
+	existing_file(path.with_extension("py")).or_else(|| existing_file(path.join("__init__.py")))
+}
+
+/// Directory above the outermost package containing `file`.
+fn python_package_root(file: &Path) -> PathBuf {
+	let mut dir = parent_dir(file);
+	while dir.join("__init__.py").is_file()
+		&& let Some(parent) = dir.parent()
+	{
+		dir = parent;
+	}
+	dir.to_path_buf()
+}
+
+fn resolve_python(file: &Path, specifier: &str, roots: &[PathBuf]) -> Option<PathBuf> {
+	let dots = specifier.len() - specifier.trim_start_matches('.').len();
+	if dots > 0 {
+		let mut base = parent_dir(file).to_path_buf();
+		for _ in 1..dots {
+			base = normalize(&base.join(".."));
+		}
+		let rest = &specifier[dots..];
+		return if rest.is_empty() {
+			existing_file(base.join("__init__.py"))
+		} else {
+			python_module(&base, rest)
+		};
+	}
+	std::iter::once(python_package_root(file))
+		.chain(roots.iter().cloned())
+		.find_map(|root| python_module(&root, specifier))
+}
+
+/// Directory holding the submodules of the module defined by `file`.
+fn rust_module_dir(file: &Path) -> PathBuf {
+	let dir = parent_dir(file);
+	match file.file_stem().and_then(|stem| stem.to_str()) {
+		Some("mod" | "lib" | "main") | None => dir.to_path_buf(),
+		Some(stem) => dir.join(stem),
+	}
+}
+
+/// Source file of the module whose submodules live in `dir`.
+fn rust_module_file(dir: &Path) -> Option<PathBuf> {
+	existing_file(dir.with_extension("rs"))
+		.or_else(|| existing_file(dir.join("mod.rs")))
+		.or_else(|| existing_file(dir.join("lib.rs")))
+		.or_else(|| existing_file(dir.join("main.rs")))
+}
+
+/// `src` directory of the crate containing `file`.
+fn rust_crate_src(file: &Path) -> Option<PathBuf> {
+	file
+		.ancestors()
+		.skip(1)
+		.find(|dir| dir.join("Cargo.toml").is_file())
+		.map(|dir| dir.join("src"))
+}
+
+fn resolve_rust(file: &Path, specifier: &str, kind: &str) -> Option<PathBuf> {
+	let module_dir = rust_module_dir(file);
+	if kind == "mod" {
+		return existing_file(module_dir.join(format!("{specifier}.rs")))
+			.or_else(|| existing_file(module_dir.join(specifier).join("mod.rs")));
+	}
+	if kind == "extern" {
+		return None;
+	}
+	let mut segments = specifier
+		.split("::")
+		.filter(|segment| *segment != "*")
+		.peekable();
+	let (base, anchored) = match segments.peek().copied() {
+		Some("crate") => {
+			segments.next();
+			(rust_crate_src(file)?, true)
+		},
+		Some("self") => {
+			segments.next();
+			(module_dir, true)
+		},
+		Some("super") => {
+			let mut base = module_dir;
+			while segments.next_if_eq(&"super").is_some() {
+				base = base.parent()?.to_path_buf();
+			}
+			(base, true)
+		},
+		// `use foo::bar` names a crate unless `foo` is a submodule here.
+		_ => (module_dir, false),
+	};
+	let segments: Vec<&str> = segments.collect();
+	// The deepest prefix naming a module file; later segments are items.
+	(usize::from(!anchored)..=segments.len())
+		.rev()
+		.find_map(|depth| {
+			let dir = segments[..depth]
+				.iter()
+				.fold(base.clone(), |dir, segment| dir.join(segment));
+			rust_module_file(&dir)
+		})
+}
+
+/// Module path and directory from the nearest `go.mod` above `file`.
+fn go_module(file: &Path) -> Option<(String, PathBuf)> {
+	let dir = file
+		.ancestors()
+		.skip(1)
+		.find(|dir| dir.join("go.mod").is_file())?;
+	let manifest = fs::read_to_string(dir.join("go.mod")).ok()?;
+	let module = manifest
+		.lines()
+		.find_map(|line| line.trim().strip_prefix("module "))?
+		.trim()
+		.trim_matches('"');
+	Some((module.to_string(), dir.to_path_buf()))
+}
+
+fn resolve_go(file: &Path, specifier: &str) -> Option<PathBuf> {
+	let (module, dir) = go_module(file)?;
+	let rest = specifier.strip_prefix(&module)?;
+	let package = if rest.is_empty() {
+		dir
+	} else {
+		dir.join(rest.strip_prefix('/')?)
+	};
+	package.is_dir().then_some(package)
+}
+
+/// Parse and resolve the imports of `file`. `roots` are extra directories
+/// searched for absolute Python imports.
+fn imports_of(file: &Path, language: Language, roots: &[PathBuf]) -> Result<Vec<ImportRef>> {
+	let bytes = fs::read(file)
+		.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", file.display())))?;
+	let source = String::from_utf8_lossy(&bytes);
+	let mut refs = Vec::new();
+	for raw in parse_imports(&source, language)? {
+		let resolve = |specifier: &str| match language {
+			Language::TypeScript | Language::Tsx | Language::JavaScript => resolve_js(file, specifier),
+			Language::Python => resolve_python(file, specifier, roots),
+			Language::Rust => resolve_rust(file, specifier, raw.kind),
+			Language::Go => resolve_go(file, specifier),
+		};
+		let mut whole_module = raw.names.is_empty();
+		// `from pkg import a, b` depends on submodules `pkg.a` and `pkg.b`
+		// when they exist, and on `pkg` itself otherwise.
+		for name in &raw.names {
+			let separator = if raw.specifier.ends_with('.') {
+				""
+			} else {
+				"."
+			};
+			let specifier = format!("{}{separator}{name}", raw.specifier);
+			match resolve(&specifier) {
+				Some(path) => refs.push(ImportRef {
+					specifier,
+					kind: raw.kind.to_string(),
+					line: raw.line,
+					resolved: Some(path.to_string_lossy().into_owned()),
+				}),
+				None => whole_module = true,
+			}
+		}
+		if whole_module {
+			let resolved = resolve(&raw.specifier).filter(|path| path != file);
+			refs.push(ImportRef {
+				specifier: raw.specifier,
+				kind:      raw.kind.to_string(),
+				line:      raw.line,
+				resolved:  resolved.map(|path| path.to_string_lossy().into_owned()),
+			});
+		}
+	}
+	Ok(refs)
+}
+
+fn resolve_language(language: Option<&str>, path: &str) -> Result<Language> {
+	match language {
+		Some(name) => Language::from_name(name)
+			.ok_or_else(|| Error::from_reason(format!("Unsupported language: {name}"))),
+		None => Language::from_path(Path::new(path)).ok_or_else(|| {
+			Error::from_reason(format!("Cannot infer language for {path}; pass a language"))
+		}),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Graph
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn build_graph(
+	root: &Path,
+	hidden: bool,
+	gitignore: bool,
+	ct: &task::CancelToken,
+) -> Result<Vec<ImportGraphNode>> {
+	let mut files: Vec<(String, Language)> = Vec::new();
+	for entry in build_walker(root, hidden, gitignore).build() {
+		ct.heartbeat()?;
+		let Ok(entry) = entry else { continue };
+		let path = entry.path();
+		let relative = normalize_relative_path(root, path);
+		let Some(language) = Language::from_path(path) else {
+			continue;
+		};
+		if !entry.file_type().is_some_and(|kind| kind.is_file())
+			|| should_skip_path(Path::new(relative.as_ref()), false)
+			|| entry
+				.metadata()
+				.is_ok_and(|meta| meta.len() > MAX_FILE_SIZE)
+		{
+			continue;
+		}
+		files.push((relative.into_owned(), language));
+	}
+
+	let roots = [root.to_path_buf(), root.join("src")];
+	let imports: Vec<Vec<ImportRef>> = files
+		.par_iter()
+		.map(|(relative, language)| {
+			ct.heartbeat()?;
+			Ok(imports_of(&root.join(relative), *language, &roots).unwrap_or_default())
+		})
+		.collect::<Result<_>>()?;
+
+	let index: HashMap<&str, usize> = files
+		.iter()
+		.enumerate()
+		.map(|(index, (path, _))| (path.as_str(), index))
+		.collect();
+	// Go packages are directories; an import links to every file in them.
+	let mut packages: HashMap<&str, Vec<usize>> = HashMap::new();
+	for (index, (path, language)) in files.iter().enumerate() {
+		if *language == Language::Go && !path.ends_with("_test.go") {
+			let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+			packages.entry(dir).or_default().push(index);
+		}
+	}
+
+	let mut edges: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); files.len()];
+	let mut external: Vec<BTreeSet<String>> = vec![BTreeSet::new(); files.len()];
+	for (from, refs) in imports.into_iter().enumerate() {
+		for import in refs {
+			let targets = import.resolved.as_deref().and_then(|resolved| {
+				let relative = Path::new(resolved).strip_prefix(root).ok()?;
+				let relative = normalize_relative_path(Path::new(""), relative);
+				match index.get(relative.as_ref()) {
+					Some(&target) => Some(vec![target]),
+					None => packages.get(relative.as_ref()).cloned(),
+				}
+			});
+			match targets {
+				Some(targets) => edges[from].extend(targets.into_iter().filter(|&to| to != from)),
+				None => {
+					external[from].insert(import.specifier);
+				},
+			}
+		}
+	}
+
+	let mut imported_by: Vec<Vec<String>> = vec![Vec::new(); files.len()];
+	for (from, targets) in edges.iter().enumerate() {
+		for &to in targets {
+			imported_by[to].push(files[from].0.clone());
+		}
+	}
+	Ok(files
+		.iter()
+		.zip(edges)
+		.zip(external)
+		.zip(imported_by)
+		.map(|((((path, _), targets), external), mut imported_by)| {
+			imported_by.sort();
+			ImportGraphNode {
+				path: path.clone(),
+				imports: targets.into_iter().map(|to| files[to].0.clone()).collect(),
+				imported_by,
+				external: external.into_iter().collect(),
+			}
+		})
+		.collect())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Extract and resolve the imports of a source file.
+///
+/// # Arguments
+/// - `path`: Source file; resolved paths are built from it, so they are
+///   relative when it is.
+/// - `language`: Language name; inferred from the extension when omitted.
+///
+/// # Returns
+/// Imports in source order. Python `from pkg import a` yields `pkg.a` when
+/// that is a submodule.
+///
+/// # Errors
+/// Returns an error if the language is unsupported or the file cannot be
+/// read.
+#[napi(js_name = "extractImports")]
+pub fn extract_imports(
+	path: String,
+	language: Option<String>,
+) -> Result<task::Async<Vec<ImportRef>>> {
+	let language = resolve_language(language.as_deref(), &path)?;
+	Ok(task::blocking("imports.extract", (), move |_| imports_of(Path::new(&path), language, &[])))
+}
+
+/// Build the file-level import graph of a workspace.
+///
+/// # Returns
+/// One node per supported source file, sorted by path, with resolved
+/// imports, reverse edges, and unresolved specifiers.
+///
+/// # Errors
+/// Returns an error if the root cannot be read or the walk is cancelled.
+#[napi(js_name = "buildImportGraph")]
+pub fn build_import_graph(
+	root: String,
+	options: Option<ImportGraphOptions<'_>>,
+) -> task::Async<Vec<ImportGraphNode>> {
+	let (hidden, gitignore, signal, timeout_ms) = match options {
+		Some(opts) => (opts.hidden, opts.gitignore, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("imports.graph", ct, move |ct| {
+		let root = PathBuf::from(root);
+		let root = fs::canonicalize(&root)
+			.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", root.display())))?;
+		build_graph(&root, hidden.unwrap_or(false), gitignore.unwrap_or(true), &ct)
+	})
+}
@@ -0,0 +1,163 @@
+//! Indentation style detection.
+//!
+//! # Overview
+//! `detectIndent` reports whether a text indents with tabs or spaces and how
+//! wide one level is, so generated edits can match the file. Tabs and spaces
+//! are counted per indented line; the width of a space level is the most
+//! common change in indentation between consecutive non-blank lines, so
+//! aligned continuation lines and deep nesting do not skew it. Block comment
+//! continuation lines (` * ...`) are ignored.
+//!
+//! Confidence is the share of indented lines using the chosen style, scaled
+//! for spaces by the share of indentation changes that are whole levels. The
+//! result also carries the text's line ending and whether it ends with one.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.detectIndent("fn a() {\n    b();\n}\n")
+//! // JS: // -> { style: "spaces", width: 4, indent: "    ", confidence: 1, eol: "lf", mixedEol: false, finalNewline: true }
+//! ```
+
+use std::collections::HashMap;
+
+use napi_derive::napi;
+
+use crate::eol::{self, Eol};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Indentation and line-ending style of a text.
+#[napi(object)]
+pub struct IndentInfo {
+	/// `"tabs"`, `"spaces"`, or `"unknown"` when no line is indented.
+	pub style:         String,
+	/// Characters per indentation level: 1 for tabs, 0 when unknown.
+	pub width:         u32,
+	/// One level of indentation, e.g. `"\t"` or `"  "` (empty when unknown).
+	pub indent:        String,
+	/// How consistently the text follows the detected style, from 0 to 1.
+	pub confidence:    f64,
+	/// Dominant line ending: `"lf"`, `"crlf"`, or `"cr"` (`"lf"` when the text
+	/// has none).
+	pub eol:           String,
+	/// Whether more than one kind of line ending occurs.
+	#[napi(js_name = "mixedEol")]
+	pub mixed_eol:     bool,
+	/// Whether the text ends with a line ending.
+	#[napi(js_name = "finalNewline")]
+	pub final_newline: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Detection
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Indentation tallies for a text.
+#[derive(Default)]
+struct IndentStats {
+	/// Lines indented with a leading tab.
+	tabs:   usize,
+	/// Lines indented with a leading space.
+	spaces: usize,
+	/// Occurrences of each nonzero change in space indentation between
+	/// consecutive lines.
+	deltas: HashMap<usize, usize>,
+}
+
+fn collect_stats(text: &str) -> IndentStats {
+	let mut stats = IndentStats::default();
+	// Space indentation of the previous counted line; `None` after a tab line.
+	let mut previous = Some(0);
+	for line in text.lines() {
+		let body = line.trim_start_matches([' ', '\t']);
+		if body.is_empty() {
+			continue;
+		}
+		let leading = &line[..line.len() - body.len()];
+		if leading.starts_with('\t') {
+			stats.tabs += 1;
+			previous = None;
+			continue;
+		}
+		let width = leading.len() - leading.trim_start_matches(' ').len();
+		if width % 2 == 1 && body.starts_with('*') {
+			continue;
+		}
+		if width > 0 {
+			stats.spaces += 1;
+		}
+		if let Some(before) = previous {
+			let delta = width.abs_diff(before);
+			if delta > 0 {
+				*stats.deltas.entry(delta).or_default() += 1;
+			}
+		}
+		previous = Some(width);
+	}
+	stats
+}
+
+/// Most frequent indentation change, preferring the smaller on ties.
+fn level_width(deltas: &HashMap<usize, usize>) -> Option<usize> {
+	deltas
+		.iter()
+		.max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))
+		.map(|(&delta, _)| delta)
+}
+
+fn round(value: f64) -> f64 {
+	(value * 1000.0).round() / 1000.0
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect the indentation style and line ending of a text.
+///
+/// # Arguments
+/// - `text`: Source text, typically a whole file.
+///
+/// # Returns
+/// Tabs or spaces with the level width and confidence, plus line-ending and
+/// final-newline information.
+#[napi(js_name = "detectIndent")]
+pub fn detect_indent(text: String) -> IndentInfo {
+	let counts = eol::count_eols(&text);
+	let stats = collect_stats(&text);
+	let indented = stats.tabs + stats.spaces;
+	let (style, width, confidence) = if indented == 0 {
+		("unknown", 0, 0.0)
+	} else if stats.tabs >= stats.spaces {
+		("tabs", 1, stats.tabs as f64 / indented as f64)
+	} else {
+		let width = level_width(&stats.deltas).unwrap_or(1);
+		let total: usize = stats.deltas.values().sum();
+		let whole: usize = stats
+			.deltas
+			.iter()
+			.filter(|&(&delta, _)| delta % width == 0)
+			.map(|(_, &count)| count)
+			.sum();
+		let consistency = if total == 0 {
+			1.0
+		} else {
+			whole as f64 / total as f64
+		};
+		("spaces", width, stats.spaces as f64 / indented as f64 * consistency)
+	};
+	IndentInfo {
+		style:         style.to_string(),
+		width:         u32::try_from(width).unwrap_or(u32::MAX),
+		indent:        match style {
+			"tabs" => "\t".to_string(),
+			_ => " ".repeat(width),
+		},
+		confidence:    round(confidence),
+		eol:           counts.dominant().unwrap_or(Eol::Lf).name().to_string(),
+		mixed_eol:     counts.mixed(),
+		final_newline: text.ends_with(['\n', '\r']),
+	}
+}
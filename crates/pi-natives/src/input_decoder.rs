@@ -0,0 +1,308 @@
+//! Incremental tokenizer for raw terminal input.
+//!
+//! # Overview
+//! [`InputDecoder`] consumes stdin chunks as they arrive and returns typed
+//! events: keys, mouse reports, bracketed paste start/text/end, focus changes,
+//! and unrecognized escape sequences (terminal replies and the like). Escape
+//! sequences and UTF-8 characters split across chunks are held until the rest
+//! arrives, so JS needs no buffering of its own.
+//!
+//! A trailing lone ESC is ambiguous: it is either the Escape key or the start
+//! of a sequence still in flight. It stays buffered (`hasPending` is true)
+//! until more input arrives or the caller gives up waiting and calls
+//! `flush()`.
+//!
+//! Pasted text is emitted as it arrives, in one or more `paste` events
+//! between `paste-start` and `paste-end`, so large pastes aren't held in
+//! memory twice.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const decoder = new native.InputDecoder();
+//! // JS: decoder.feed("a\x1b[<0;3;4M\x1b") // -> [{ kind: "key", ... }, { kind: "mouse", ... }]
+//! // JS: decoder.hasPending // -> true
+//! // JS: decoder.flush() // -> [{ kind: "key", data: "\x1b", key: { key: "escape", ... } }]
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{
+	keys::{KeyEvent, parse_key_event_inner},
+	mouse::{MouseEvent, parse_mouse_prefix},
+};
+
+const PASTE_START: &[u8] = b"\x1b[200~";
+const PASTE_END: &[u8] = b"\x1b[201~";
+const FOCUS_IN: &[u8] = b"\x1b[I";
+const FOCUS_OUT: &[u8] = b"\x1b[O";
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A decoded input event.
+#[napi(object)]
+pub struct InputEvent {
+	/// Event type: `"key"`, `"mouse"`, `"paste-start"`, `"paste"`,
+	/// `"paste-end"`, `"focus"`, or `"unknown"`.
+	pub kind:    String,
+	/// Raw input for the event, or the pasted text for `"paste"` events.
+	pub data:    String,
+	/// Decoded key for `"key"` events.
+	pub key:     Option<KeyEvent>,
+	/// Decoded report for `"mouse"` events.
+	pub mouse:   Option<MouseEvent>,
+	/// Whether the terminal gained focus, for `"focus"` events.
+	pub focused: Option<bool>,
+}
+
+impl InputEvent {
+	fn new(kind: &str, data: String) -> Self {
+		Self { kind: kind.to_string(), data, key: None, mouse: None, focused: None }
+	}
+
+	/// Classify one complete sequence.
+	fn from_sequence(seq: &[u8]) -> Self {
+		let data = String::from_utf8_lossy(seq).into_owned();
+		match seq {
+			PASTE_START => return Self::new("paste-start", data),
+			FOCUS_IN | FOCUS_OUT => {
+				let focused = seq == FOCUS_IN;
+				return Self { focused: Some(focused), ..Self::new("focus", data) };
+			},
+			_ => {},
+		}
+		if let Some((report, len)) = parse_mouse_prefix(&data)
+			&& len == data.len()
+		{
+			return Self { mouse: Some(report.into()), ..Self::new("mouse", data) };
+		}
+		match parse_key_event_inner(&data) {
+			Some(key) => Self { key: Some(key), ..Self::new("key", data) },
+			None => Self::new("unknown", data),
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Tokenizer
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Default)]
+struct Decoder {
+	/// Bytes not yet emitted.
+	buffer:   Vec<u8>,
+	/// Whether we are between the bracketed paste markers.
+	in_paste: bool,
+}
+
+impl Decoder {
+	fn feed(&mut self, chunk: &[u8], out: &mut Vec<InputEvent>) {
+		self.buffer.extend_from_slice(chunk);
+		let mut pos = 0;
+		while pos < self.buffer.len() {
+			if self.in_paste {
+				pos = self.paste(pos, out);
+				if self.in_paste {
+					break;
+				}
+				continue;
+			}
+			let Some(len) = sequence_len(&self.buffer[pos..]) else {
+				break;
+			};
+			let seq = &self.buffer[pos..pos + len];
+			self.in_paste = seq == PASTE_START;
+			out.push(InputEvent::from_sequence(seq));
+			pos += len;
+		}
+		self.buffer.drain(..pos);
+	}
+
+	/// Emit pasted text from `pos`, returning the position after what was
+	/// consumed. Bytes that may begin the end marker or a UTF-8 character stay
+	/// buffered.
+	fn paste(&mut self, pos: usize, out: &mut Vec<InputEvent>) -> usize {
+		let rest = &self.buffer[pos..];
+		if let Some(end) = rest.windows(PASTE_END.len()).position(|w| w == PASTE_END) {
+			if end > 0 {
+				out.push(InputEvent::new("paste", String::from_utf8_lossy(&rest[..end]).into_owned()));
+			}
+			out.push(InputEvent::new("paste-end", String::from_utf8_lossy(PASTE_END).into_owned()));
+			self.in_paste = false;
+			return pos + end + PASTE_END.len();
+		}
+
+		let held = (1..PASTE_END.len())
+			.rev()
+			.find(|&n| rest.ends_with(&PASTE_END[..n]))
+			.unwrap_or(0);
+		let text = &rest[..rest.len() - held];
+		let len = match std::str::from_utf8(text) {
+			Err(err) if err.error_len().is_none() => err.valid_up_to(),
+			_ => text.len(),
+		};
+		if len > 0 {
+			out.push(InputEvent::new("paste", String::from_utf8_lossy(&text[..len]).into_owned()));
+		}
+		pos + len
+	}
+}
+
+/// Length of the sequence at the start of `bytes`, or None when it is not
+/// complete yet.
+fn sequence_len(bytes: &[u8]) -> Option<usize> {
+	if bytes[0] != 0x1b {
+		return char_len(bytes);
+	}
+	match *bytes.get(1)? {
+		b'[' => csi_len(bytes),
+		// SS3: ESC O plus one byte
+		b'O' => (bytes.len() >= 3).then_some(3),
+		// OSC, DCS, APC, PM, SOS: terminated by ST (OSC also by BEL)
+		intro @ (b']' | b'P' | b'_' | b'^' | b'X') => {
+			let body = &bytes[2..];
+			let st = body.windows(2).position(|w| w == b"\x1b\\").map(|i| i + 2);
+			let bel = (intro == b']')
+				.then(|| body.iter().position(|&b| b == 0x07).map(|i| i + 1))
+				.flatten();
+			let end = match (st, bel) {
+				(Some(st), Some(bel)) => st.min(bel),
+				(st, bel) => st.or(bel)?,
+			};
+			Some(2 + end)
+		},
+		// ESC ESC: the first one is a lone Escape
+		0x1b => Some(1),
+		// Alt-prefixed character
+		_ => char_len(&bytes[1..]).map(|len| len + 1),
+	}
+}
+
+/// Length of a CSI sequence starting at `bytes` (`ESC [`).
+fn csi_len(bytes: &[u8]) -> Option<usize> {
+	match *bytes.get(2)? {
+		// X10 mouse: CSI M plus three characters
+		b'M' => {
+			let mut len = 3;
+			for _ in 0..3 {
+				len += char_len(bytes.get(len..).filter(|rest| !rest.is_empty())?)?;
+			}
+			Some(len)
+		},
+		// Linux console function keys: CSI [ letter
+		b'[' => (bytes.len() >= 4).then_some(4),
+		_ => {
+			for (i, &b) in bytes.iter().enumerate().skip(2) {
+				match b {
+					// rxvt modified keys end in `$` (e.g. CSI 2 $)
+					b'$' if i > 2 && bytes[2..i].iter().all(u8::is_ascii_digit) => {
+						return Some(i + 1);
+					},
+					0x20..=0x3f => {},
+					0x40..=0x7e => return Some(i + 1),
+					// Malformed: end the sequence before the offending byte.
+					_ => return Some(i),
+				}
+			}
+			None
+		},
+	}
+}
+
+/// Length of the UTF-8 character at the start of `bytes`, or None when it is
+/// cut off. Invalid bytes count as one-byte characters.
+fn char_len(bytes: &[u8]) -> Option<usize> {
+	let width = match bytes[0] {
+		0xc2..=0xdf => 2,
+		0xe0..=0xef => 3,
+		0xf0..=0xf4 => 4,
+		_ => return Some(1),
+	};
+	let available = bytes.len().min(width);
+	if !bytes[1..available]
+		.iter()
+		.all(|&b| (0x80..=0xbf).contains(&b))
+	{
+		return Some(1);
+	}
+	if available < width {
+		return None;
+	}
+	let valid = std::str::from_utf8(&bytes[..width]).is_ok();
+	Some(if valid { width } else { 1 })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Stateful decoder turning raw terminal input into typed events.
+#[napi]
+pub struct InputDecoder {
+	decoder: Decoder,
+}
+
+#[napi]
+impl InputDecoder {
+	#[napi(constructor)]
+	pub fn new() -> Self {
+		Self { decoder: Decoder::default() }
+	}
+
+	/// Feed the next chunk of input; strings are processed as UTF-8.
+	/// Sequences split across calls are held until complete.
+	///
+	/// # Returns
+	/// Events completed by this chunk, in input order.
+	#[napi]
+	pub fn feed(&mut self, chunk: Either<String, Uint8Array>) -> Vec<InputEvent> {
+		let mut events = Vec::new();
+		match chunk {
+			Either::A(text) => self.decoder.feed(text.as_bytes(), &mut events),
+			Either::B(bytes) => self.decoder.feed(&bytes, &mut events),
+		}
+		events
+	}
+
+	/// Emit buffered input as a single event without waiting for the rest of
+	/// the sequence, e.g. a lone ESC after an escape timeout. Does nothing
+	/// inside a bracketed paste.
+	///
+	/// # Returns
+	/// The flushed event, or nothing when no input is buffered.
+	#[napi]
+	pub fn flush(&mut self) -> Vec<InputEvent> {
+		if self.decoder.in_paste || self.decoder.buffer.is_empty() {
+			return Vec::new();
+		}
+		let buffer = std::mem::take(&mut self.decoder.buffer);
+		vec![InputEvent::from_sequence(&buffer)]
+	}
+
+	/// Whether input is buffered waiting for the rest of a sequence (outside
+	/// a bracketed paste).
+	#[napi(getter, js_name = "hasPending")]
+	pub const fn has_pending(&self) -> bool {
+		!self.decoder.in_paste && !self.decoder.buffer.is_empty()
+	}
+
+	/// Whether the decoder is inside a bracketed paste.
+	#[napi(getter, js_name = "inPaste")]
+	pub const fn in_paste(&self) -> bool {
+		self.decoder.in_paste
+	}
+
+	/// Discard buffered input and paste state.
+	#[napi]
+	pub fn reset(&mut self) {
+		self.decoder = Decoder::default();
+	}
+}
+
+impl Default for InputDecoder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,469 @@
+//! Incremental JSON parsing and repair for streamed LLM output.
+//!
+//! # Overview
+//! [`JsonStream`] consumes JSON text in arbitrary chunks with a byte-level
+//! state machine. Every value that closes inside a chunk is reported with its
+//! JSON Pointer path and raw text, and at any point the partial document can
+//! be repaired into valid JSON: open strings are closed, truncated literals are
+//! completed or dropped, dangling keys and trailing commas are removed, and
+//! open containers are closed.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const s = new native.JsonStream();
+//! // JS: s.push('{"path": "a.ts", "edits": [{"old');
+//! // JS: s.repair(); // '{"path": "a.ts", "edits": [{}]}'
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A value that finished parsing during a `push`.
+#[napi(object)]
+pub struct JsonStreamValue {
+	/// JSON Pointer to the value (`""` for the root, e.g. `/edits/0/old`).
+	pub path: String,
+	/// Raw JSON text of the value.
+	pub raw:  String,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// State machine
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Container {
+	Object,
+	Array,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Expect {
+	/// Object just opened: a key or `}`.
+	KeyOrEnd,
+	/// After a comma in an object.
+	Key,
+	/// After a key.
+	Colon,
+	/// After a colon, or after a comma in an array.
+	Value,
+	/// Array just opened: a value or `]`.
+	ValueOrEnd,
+	/// After a member value.
+	CommaOrEnd,
+}
+
+struct Frame {
+	container:    Container,
+	expect:       Expect,
+	/// Byte offset of the opening bracket.
+	start:        usize,
+	/// Truncation point dropping the member in progress (its leading comma,
+	/// or just past the opening bracket for the first member).
+	member_start: usize,
+	key:          String,
+	index:        u32,
+}
+
+#[derive(Clone, Copy, Default)]
+enum Lex {
+	#[default]
+	Idle,
+	Str { start: usize, is_key: bool, escape: bool, unicode: u8 },
+	Literal { start: usize },
+}
+
+#[derive(Default)]
+struct Parser {
+	buffer:    String,
+	frames:    Vec<Frame>,
+	lex:       Lex,
+	root_done: bool,
+	error:     Option<String>,
+}
+
+const fn is_delimiter(byte: u8) -> bool {
+	matches!(byte, b',' | b']' | b'}' | b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn is_valid_number(text: &str) -> bool {
+	let bytes = text.as_bytes();
+	let mut i = usize::from(bytes.first() == Some(&b'-'));
+	let int_start = i;
+	while i < bytes.len() && bytes[i].is_ascii_digit() {
+		i += 1;
+	}
+	let int_len = i - int_start;
+	if int_len == 0 || (int_len > 1 && bytes[int_start] == b'0') {
+		return false;
+	}
+	if i < bytes.len() && bytes[i] == b'.' {
+		i += 1;
+		let frac_start = i;
+		while i < bytes.len() && bytes[i].is_ascii_digit() {
+			i += 1;
+		}
+		if i == frac_start {
+			return false;
+		}
+	}
+	if i < bytes.len() && matches!(bytes[i], b'e' | b'E') {
+		i += 1;
+		if i < bytes.len() && matches!(bytes[i], b'+' | b'-') {
+			i += 1;
+		}
+		let exp_start = i;
+		while i < bytes.len() && bytes[i].is_ascii_digit() {
+			i += 1;
+		}
+		if i == exp_start {
+			return false;
+		}
+	}
+	i == bytes.len()
+}
+
+fn is_valid_literal(text: &str) -> bool {
+	matches!(text, "true" | "false" | "null") || is_valid_number(text)
+}
+
+/// Complete a truncated literal into a valid one, if possible.
+fn complete_literal(text: &str) -> Option<String> {
+	for word in ["true", "false", "null"] {
+		if word.starts_with(text) {
+			return Some(word.to_string());
+		}
+	}
+	let trimmed = text.trim_end_matches(['.', 'e', 'E', '+', '-']);
+	is_valid_number(trimmed).then(|| trimmed.to_string())
+}
+
+/// Decode a JSON string literal (including quotes) into its value.
+fn unescape(raw: &str) -> String {
+	let inner = &raw[1..raw.len() - 1];
+	let mut out = String::with_capacity(inner.len());
+	let mut chars = inner.chars();
+	while let Some(ch) = chars.next() {
+		if ch != '\\' {
+			out.push(ch);
+			continue;
+		}
+		match chars.next() {
+			Some('n') => out.push('\n'),
+			Some('t') => out.push('\t'),
+			Some('r') => out.push('\r'),
+			Some('b') => out.push('\u{8}'),
+			Some('f') => out.push('\u{c}'),
+			Some('u') => {
+				let hex: String = chars.by_ref().take(4).collect();
+				let unit = u16::from_str_radix(&hex, 16).unwrap_or(0xfffd);
+				// Surrogate pairs arrive as two escapes; decode them together.
+				if (0xd800..0xdc00).contains(&unit) {
+					let rest = chars.as_str();
+					if let Some(low) = rest
+						.strip_prefix("\\u")
+						.and_then(|r| r.get(..4))
+						.and_then(|h| u16::from_str_radix(h, 16).ok())
+						.filter(|low| (0xdc00..0xe000).contains(low))
+					{
+						chars = rest[6..].chars();
+						out.extend(char::decode_utf16([unit, low]).map(|r| r.unwrap_or('\u{fffd}')));
+						continue;
+					}
+				}
+				out.push(char::from_u32(u32::from(unit)).unwrap_or('\u{fffd}'));
+			},
+			Some(other) => out.push(other),
+			None => {},
+		}
+	}
+	out
+}
+
+/// Append a JSON Pointer segment, escaping `~` and `/`.
+fn push_pointer_segment(out: &mut String, segment: &str) {
+	out.push('/');
+	for ch in segment.chars() {
+		match ch {
+			'~' => out.push_str("~0"),
+			'/' => out.push_str("~1"),
+			_ => out.push(ch),
+		}
+	}
+}
+
+impl Parser {
+	fn pointer(&self) -> String {
+		let mut out = String::new();
+		for frame in &self.frames {
+			match frame.container {
+				Container::Object => push_pointer_segment(&mut out, &frame.key),
+				Container::Array => push_pointer_segment(&mut out, &frame.index.to_string()),
+			}
+		}
+		out
+	}
+
+	fn fail(&mut self, pos: usize, what: &str) -> Error {
+		let message = format!("Invalid JSON at byte {pos}: {what}");
+		self.error = Some(message.clone());
+		Error::from_reason(message)
+	}
+
+	fn complete_value(&mut self, start: usize, end: usize, out: &mut Vec<JsonStreamValue>) {
+		out.push(JsonStreamValue { path: self.pointer(), raw: self.buffer[start..end].to_string() });
+		match self.frames.last_mut() {
+			Some(frame) => frame.expect = Expect::CommaOrEnd,
+			None => self.root_done = true,
+		}
+	}
+
+	fn close_container(&mut self, pos: usize, out: &mut Vec<JsonStreamValue>) {
+		if let Some(frame) = self.frames.pop() {
+			self.complete_value(frame.start, pos + 1, out);
+		}
+	}
+
+	fn begin_value(&mut self, pos: usize, byte: u8) -> Result<()> {
+		let container = match byte {
+			b'{' => Container::Object,
+			b'[' => Container::Array,
+			b'"' => {
+				self.lex = Lex::Str { start: pos, is_key: false, escape: false, unicode: 0 };
+				return Ok(());
+			},
+			b'-' | b'0'..=b'9' | b't' | b'f' | b'n' => {
+				self.lex = Lex::Literal { start: pos };
+				return Ok(());
+			},
+			_ => return Err(self.fail(pos, "expected a value")),
+		};
+		let expect = if container == Container::Object {
+			Expect::KeyOrEnd
+		} else {
+			Expect::ValueOrEnd
+		};
+		self.frames.push(Frame {
+			container,
+			expect,
+			start: pos,
+			member_start: pos + 1,
+			key: String::new(),
+			index: 0,
+		});
+		Ok(())
+	}
+
+	fn step_idle(&mut self, pos: usize, byte: u8, out: &mut Vec<JsonStreamValue>) -> Result<()> {
+		if byte.is_ascii_whitespace() {
+			return Ok(());
+		}
+		let Some(frame) = self.frames.last_mut() else {
+			if self.root_done {
+				return Err(self.fail(pos, "trailing data after the root value"));
+			}
+			return self.begin_value(pos, byte);
+		};
+		match (frame.expect, byte) {
+			(Expect::KeyOrEnd | Expect::Key, b'"') => {
+				self.lex = Lex::Str { start: pos, is_key: true, escape: false, unicode: 0 };
+			},
+			(Expect::KeyOrEnd, b'}') | (Expect::ValueOrEnd, b']') => self.close_container(pos, out),
+			(Expect::Colon, b':') => frame.expect = Expect::Value,
+			(Expect::Value | Expect::ValueOrEnd, _) => return self.begin_value(pos, byte),
+			(Expect::CommaOrEnd, b',') => {
+				frame.member_start = pos;
+				if frame.container == Container::Object {
+					frame.expect = Expect::Key;
+				} else {
+					frame.index += 1;
+					frame.expect = Expect::Value;
+				}
+			},
+			(Expect::CommaOrEnd, b'}') if frame.container == Container::Object => {
+				self.close_container(pos, out);
+			},
+			(Expect::CommaOrEnd, b']') if frame.container == Container::Array => {
+				self.close_container(pos, out);
+			},
+			_ => return Err(self.fail(pos, "unexpected character")),
+		}
+		Ok(())
+	}
+
+	fn push(&mut self, chunk: &str) -> Result<Vec<JsonStreamValue>> {
+		if let Some(error) = &self.error {
+			return Err(Error::from_reason(error.clone()));
+		}
+		let offset = self.buffer.len();
+		self.buffer.push_str(chunk);
+		let mut out = Vec::new();
+		for (i, &byte) in chunk.as_bytes().iter().enumerate() {
+			let pos = offset + i;
+			match self.lex {
+				Lex::Str { start, is_key, escape, unicode } => {
+					if unicode > 0 {
+						if !byte.is_ascii_hexdigit() {
+							return Err(self.fail(pos, "invalid unicode escape"));
+						}
+						self.lex = Lex::Str { start, is_key, escape, unicode: unicode - 1 };
+					} else if escape {
+						let unicode = if byte == b'u' { 4 } else { 0 };
+						self.lex = Lex::Str { start, is_key, escape: false, unicode };
+					} else if byte == b'\\' {
+						self.lex = Lex::Str { start, is_key, escape: true, unicode };
+					} else if byte == b'"' {
+						self.lex = Lex::Idle;
+						if is_key {
+							let key = unescape(&self.buffer[start..=pos]);
+							if let Some(frame) = self.frames.last_mut() {
+								frame.key = key;
+								frame.expect = Expect::Colon;
+							}
+						} else {
+							self.complete_value(start, pos + 1, &mut out);
+						}
+					}
+				},
+				Lex::Literal { start } => {
+					if is_delimiter(byte) {
+						self.lex = Lex::Idle;
+						if !is_valid_literal(&self.buffer[start..pos]) {
+							return Err(self.fail(start, "invalid literal"));
+						}
+						self.complete_value(start, pos, &mut out);
+						self.step_idle(pos, byte, &mut out)?;
+					} else if !byte.is_ascii_alphanumeric() && !matches!(byte, b'.' | b'+' | b'-') {
+						return Err(self.fail(pos, "invalid literal"));
+					}
+				},
+				Lex::Idle => self.step_idle(pos, byte, &mut out)?,
+			}
+		}
+		Ok(out)
+	}
+
+	fn finish_root_literal(&mut self) -> Option<JsonStreamValue> {
+		let Lex::Literal { start } = self.lex else { return None };
+		if !self.frames.is_empty() || !is_valid_literal(&self.buffer[start..]) {
+			return None;
+		}
+		self.lex = Lex::Idle;
+		let mut out = Vec::with_capacity(1);
+		self.complete_value(start, self.buffer.len(), &mut out);
+		out.pop()
+	}
+
+	fn repair(&self) -> Option<String> {
+		if self.root_done {
+			return Some(self.buffer.trim().to_string());
+		}
+		let mut out = self.buffer.clone();
+		let mut drop_member = false;
+		match self.lex {
+			Lex::Str { is_key: true, .. } => drop_member = true,
+			Lex::Str { is_key: false, escape, unicode, .. } => {
+				if unicode > 0 {
+					out.truncate(out.len() - 2 - (4 - unicode as usize));
+				} else if escape {
+					out.pop();
+				}
+				out.push('"');
+			},
+			Lex::Literal { start } => match complete_literal(&out[start..]) {
+				Some(literal) => {
+					out.truncate(start);
+					out.push_str(&literal);
+				},
+				None if self.frames.is_empty() => return None,
+				None => drop_member = true,
+			},
+			Lex::Idle => {
+				let frame = self.frames.last()?;
+				drop_member = matches!(frame.expect, Expect::Key | Expect::Colon | Expect::Value);
+			},
+		}
+		if drop_member && let Some(frame) = self.frames.last() {
+			out.truncate(frame.member_start);
+		}
+		for frame in self.frames.iter().rev() {
+			out.push(if frame.container == Container::Object { '}' } else { ']' });
+		}
+		Some(out)
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Incremental JSON parser for streamed text such as tool-call arguments.
+#[napi]
+pub struct JsonStream {
+	parser: Parser,
+}
+
+#[napi]
+impl JsonStream {
+	#[napi(constructor)]
+	pub fn new() -> Self {
+		Self { parser: Parser::default() }
+	}
+
+	/// Feed the next chunk of JSON text.
+	///
+	/// # Returns
+	/// Values (scalars and containers) that closed within this chunk, innermost
+	/// first.
+	///
+	/// # Errors
+	/// Returns an error when the text is not valid JSON; the stream stays
+	/// failed until `reset()`.
+	#[napi]
+	pub fn push(&mut self, chunk: String) -> Result<Vec<JsonStreamValue>> {
+		self.parser.push(&chunk)
+	}
+
+	/// Signal end of input, completing a trailing root-level number.
+	///
+	/// # Returns
+	/// The root value if it completed on this call.
+	#[napi]
+	pub fn end(&mut self) -> Option<JsonStreamValue> {
+		self.parser.finish_root_literal()
+	}
+
+	/// Best-effort valid JSON for the text received so far.
+	///
+	/// # Returns
+	/// `null` when nothing salvageable has been received yet (or after an error).
+	#[napi]
+	pub fn repair(&self) -> Option<String> {
+		if self.parser.error.is_some() {
+			return None;
+		}
+		self.parser.repair()
+	}
+
+	/// Whether the root value has been fully received.
+	#[napi(getter)]
+	pub const fn done(&self) -> bool {
+		self.parser.root_done
+	}
+
+	/// Discard all received text and state.
+	#[napi]
+	pub fn reset(&mut self) {
+		self.parser = Parser::default();
+	}
+}
+
+impl Default for JsonStream {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,231 @@
+//! Indexed JSONL files for session history.
+//!
+//! # Overview
+//! [`JsonlFile`] scans a JSONL file once to record the byte range of every
+//! non-blank line, then serves record counts and range reads by seeking
+//! directly to the requested records. Appends extend the index in place, and
+//! `refresh()` picks up records written by other processes.
+//!
+//! Records are exchanged as raw JSON text; callers parse only the ranges they
+//! display.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const log = await native.openJsonl("session.jsonl");
+//! // JS: const last = await log.readRange(log.count() - 50, log.count());
+//! ```
+
+use std::{
+	fs::{File, OpenOptions},
+	io::{BufReader, Read, Seek, SeekFrom, Write},
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::Mutex;
+
+use crate::task;
+
+/// Bytes read per chunk while indexing.
+const SCAN_CHUNK: usize = 256 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Index
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Default)]
+struct Index {
+	/// Byte range (start, end) of each non-blank line, excluding the newline.
+	records:      Vec<(u64, u64)>,
+	/// Offset up to which the file has been scanned.
+	scanned:      u64,
+	/// Start of the trailing line that has no newline yet.
+	partial_from: Option<u64>,
+}
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> Error {
+	Error::from_reason(format!("Failed to {action} {}: {err}", path.display()))
+}
+
+impl Index {
+	/// Scan from the last scanned offset to EOF, indexing complete lines.
+	fn extend(&mut self, path: &Path, ct: &task::CancelToken) -> Result<()> {
+		let mut file = File::open(path).map_err(|err| io_error("open", path, err))?;
+		let len = file.metadata().map_err(|err| io_error("stat", path, err))?.len();
+		if len < self.scanned {
+			// Truncated or replaced: start over.
+			*self = Self::default();
+		}
+		let resume = self.partial_from.unwrap_or(self.scanned);
+		file
+			.seek(SeekFrom::Start(resume))
+			.map_err(|err| io_error("seek", path, err))?;
+
+		let mut reader = BufReader::with_capacity(SCAN_CHUNK, file);
+		let mut chunk = vec![0u8; SCAN_CHUNK];
+		let mut line_start = resume;
+		let mut line_blank = true;
+		let mut offset = resume;
+		loop {
+			ct.heartbeat()?;
+			let read = reader.read(&mut chunk).map_err(|err| io_error("read", path, err))?;
+			if read == 0 {
+				break;
+			}
+			for &byte in &chunk[..read] {
+				if byte == b'\n' {
+					if !line_blank {
+						self.records.push((line_start, offset));
+					}
+					line_start = offset + 1;
+					line_blank = true;
+				} else if !byte.is_ascii_whitespace() {
+					line_blank = false;
+				}
+				offset += 1;
+			}
+		}
+		self.scanned = offset;
+		self.partial_from = (!line_blank).then_some(line_start);
+		Ok(())
+	}
+}
+
+fn read_records(
+	path: &Path,
+	ranges: &[(u64, u64)],
+	ct: &task::CancelToken,
+) -> Result<Vec<String>> {
+	let (Some(first), Some(last)) = (ranges.first(), ranges.last()) else {
+		return Ok(Vec::new());
+	};
+	let mut file = File::open(path).map_err(|err| io_error("open", path, err))?;
+	file
+		.seek(SeekFrom::Start(first.0))
+		.map_err(|err| io_error("seek", path, err))?;
+	let mut bytes = vec![0u8; (last.1 - first.0) as usize];
+	file
+		.read_exact(&mut bytes)
+		.map_err(|err| io_error("read", path, err))?;
+
+	let mut out = Vec::with_capacity(ranges.len());
+	for &(start, end) in ranges {
+		ct.heartbeat()?;
+		let slice = &bytes[(start - first.0) as usize..(end - first.0) as usize];
+		let line = String::from_utf8_lossy(slice);
+		out.push(line.trim_end_matches('\r').to_string());
+	}
+	Ok(out)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Handle to an indexed JSONL file.
+#[napi]
+pub struct JsonlFile {
+	path:  PathBuf,
+	index: Arc<Mutex<Index>>,
+}
+
+#[napi]
+impl JsonlFile {
+	/// Number of indexed records.
+	#[napi]
+	pub fn count(&self) -> u32 {
+		self.index.lock().records.len().min(u32::MAX as usize) as u32
+	}
+
+	/// Read records `[start, end)` as raw JSON text.
+	///
+	/// Out-of-range bounds are clamped.
+	#[napi(js_name = "readRange")]
+	pub fn read_range(&self, start: u32, end: u32) -> task::Async<Vec<String>> {
+		let ranges = {
+			let index = self.index.lock();
+			let len = index.records.len();
+			let end = (end as usize).min(len);
+			let start = (start as usize).min(end);
+			index.records[start..end].to_vec()
+		};
+		let path = self.path.clone();
+		task::blocking("jsonl.read_range", (), move |ct| read_records(&path, &ranges, &ct))
+	}
+
+	/// Append records (one JSON document each) and index them.
+	///
+	/// # Returns
+	/// The new record count.
+	///
+	/// # Errors
+	/// Returns an error if a record contains a newline or the write fails.
+	#[napi]
+	pub fn append(&self, records: Vec<String>) -> Result<task::Async<u32>> {
+		if let Some(pos) = records.iter().position(|record| record.contains('\n')) {
+			return Err(Error::from_reason(format!("Record {pos} contains a newline")));
+		}
+		let path = self.path.clone();
+		let index = Arc::clone(&self.index);
+		Ok(task::blocking("jsonl.append", (), move |ct| {
+			let mut index = index.lock();
+			// Catch up on external writes so offsets stay correct.
+			index.extend(&path, &ct)?;
+			let mut file = OpenOptions::new()
+				.append(true)
+				.open(&path)
+				.map_err(|err| io_error("open", &path, err))?;
+			let mut payload = String::new();
+			if index.partial_from.is_some() {
+				// Never glue the first record onto an unterminated line.
+				payload.push('\n');
+			}
+			for record in &records {
+				payload.push_str(record);
+				payload.push('\n');
+			}
+			file
+				.write_all(payload.as_bytes())
+				.map_err(|err| io_error("write", &path, err))?;
+			index.extend(&path, &ct)?;
+			Ok(index.records.len().min(u32::MAX as usize) as u32)
+		}))
+	}
+
+	/// Index records appended to the file since it was opened or last
+	/// refreshed. A truncated file is re-indexed from scratch.
+	///
+	/// # Returns
+	/// The new record count.
+	#[napi]
+	pub fn refresh(&self) -> task::Async<u32> {
+		let path = self.path.clone();
+		let index = Arc::clone(&self.index);
+		task::blocking("jsonl.refresh", (), move |ct| {
+			let mut index = index.lock();
+			index.extend(&path, &ct)?;
+			Ok(index.records.len().min(u32::MAX as usize) as u32)
+		})
+	}
+}
+
+/// Open (creating if missing) and index a JSONL file.
+///
+/// # Errors
+/// Returns an error if the file cannot be created or read.
+#[napi(js_name = "openJsonl")]
+pub fn open_jsonl(path: String) -> task::Async<JsonlFile> {
+	let path = PathBuf::from(path);
+	task::blocking("jsonl.open", (), move |ct| {
+		OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&path)
+			.map_err(|err| io_error("open", &path, err))?;
+		let mut index = Index::default();
+		index.extend(&path, &ct)?;
+		Ok(JsonlFile { path, index: Arc::new(Mutex::new(index)) })
+	})
+}
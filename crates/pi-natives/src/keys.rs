@@ -8,6 +8,7 @@
 //! ```ignore
 //! // JS: native.matchesKittySequence("\x1b[65;5u", 65, 4) -> true
 //! // JS: native.parseKey("\x1b[65;5u", false) -> "ctrl+a"
+//! // JS: native.parseKeyEvent("\x1b[97;5u") -> { key: "a", codepoint: 97, modifiers: 4, ... }
 //! ```
 
 use std::borrow::Cow;
@@ -109,6 +110,27 @@ pub struct ParsedKittyResult {
 	pub event_type:      Option<u32>,
 }
 
+/// Structured key event decoded from Kitty, modifyOtherKeys or legacy input.
+#[napi(object)]
+pub struct KeyEvent {
+	/// Key name without modifiers (e.g. "a", "enter", "up", "f5"), when known.
+	pub key:             Option<String>,
+	/// Unicode or Kitty functional codepoint; absent for keys reported by name
+	/// only (arrows, function keys, home/end, ...).
+	pub codepoint:       Option<u32>,
+	/// Shifted key codepoint reported by the sequence.
+	pub shifted_key:     Option<u32>,
+	/// Base layout key codepoint reported by the sequence.
+	pub base_layout_key: Option<u32>,
+	/// Modifier bitmask (1 = shift, 2 = alt, 4 = ctrl, 8 = super, 16 = hyper,
+	/// 32 = meta, 64 = caps lock, 128 = num lock).
+	pub modifiers:       u32,
+	/// Event type (1 = press, 2 = repeat, 3 = release).
+	pub event_type:      u32,
+	/// Text the key inserts, if any.
+	pub text:            Option<String>,
+}
+
 /// Perfect hash map for legacy sequences - O(1) lookup
 static LEGACY_SEQUENCES: phf::Map<&'static [u8], &'static str> = phf_map! {
 	// Arrow keys (SS3 and CSI)
@@ -238,9 +260,7 @@ pub fn matches_kitty_sequence(
 		&& base == expected_codepoint
 	{
 		let cp = parsed.codepoint;
-		let is_ascii_letter = u8::try_from(cp)
-			.ok()
-			.is_some_and(|b| b.is_ascii_alphabetic());
+		let is_ascii_letter = u8::try_from(cp).is_ok_and(|b| b.is_ascii_alphabetic());
 		let is_known_symbol = is_symbol_key(cp);
 		if !is_ascii_letter && !is_known_symbol {
 			return true;
@@ -330,6 +350,15 @@ pub fn parse_kitty_sequence_napi(data: String) -> Option<ParsedKittyResult> {
 	})
 }
 
+/// Parse terminal input into a structured key event.
+///
+/// Accepts Kitty, xterm modifyOtherKeys and legacy sequences. Returns None
+/// when the input is not a single recognized key.
+#[napi(js_name = "parseKeyEvent")]
+pub fn parse_key_event(data: String) -> Option<KeyEvent> {
+	parse_key_event_inner(&data)
+}
+
 // =============================================================================
 // Key Matching
 // =============================================================================
@@ -365,23 +394,17 @@ fn parse_key_id(key_id: &str) -> Option<ParsedKeyId<'_>> {
 		};
 
 		match c0 {
-			b'c' | b'C' => {
-				if p.eq_ignore_ascii_case("ctrl") {
-					modifier |= MOD_CTRL;
-					continue;
-				}
+			b'c' | b'C' if p.eq_ignore_ascii_case("ctrl") => {
+				modifier |= MOD_CTRL;
+				continue;
 			},
-			b's' | b'S' => {
-				if p.eq_ignore_ascii_case("shift") {
-					modifier |= MOD_SHIFT;
-					continue;
-				}
+			b's' | b'S' if p.eq_ignore_ascii_case("shift") => {
+				modifier |= MOD_SHIFT;
+				continue;
 			},
-			b'a' | b'A' => {
-				if p.eq_ignore_ascii_case("alt") {
-					modifier |= MOD_ALT;
-					continue;
-				}
+			b'a' | b'A' if p.eq_ignore_ascii_case("alt") => {
+				modifier |= MOD_ALT;
+				continue;
 			},
 			_ => {},
 		}
@@ -489,9 +512,8 @@ fn matches_key_inner(bytes: &[u8], key_id: &str, kitty_protocol_active: bool) ->
 		if let Some(base) = parsed_base
 			&& base == codepoint
 		{
-			let is_ascii_letter = u8::try_from(parsed_codepoint)
-				.ok()
-				.is_some_and(|b| b.is_ascii_alphabetic());
+			let is_ascii_letter =
+				u8::try_from(parsed_codepoint).is_ok_and(|b| b.is_ascii_alphabetic());
 			let is_known_symbol = is_symbol_key(parsed_codepoint);
 			if !is_ascii_letter && !is_known_symbol {
 				return true;
@@ -941,6 +963,149 @@ fn parse_esc_pair(code: u8, kitty_protocol_active: bool) -> Option<Cow<'static,
 	None
 }
 
+// =============================================================================
+// Key Events
+// =============================================================================
+
+const EVENT_PRESS: u32 = 1;
+const EVENT_RELEASE: u32 = 3;
+
+/// Private use range Kitty assigns to functional keys (keypad, modifiers, ...).
+const KITTY_FUNCTIONAL_KEYS: std::ops::RangeInclusive<u32> = 57344..=63743;
+
+/// Decode one complete key sequence; shared with the input decoder.
+pub fn parse_key_event_inner(data: &str) -> Option<KeyEvent> {
+	let bytes = data.as_bytes();
+
+	if bytes.first() == Some(&0x1b) && !LEGACY_SEQUENCES.contains_key(bytes) {
+		if let Some((modifiers, keycode)) = parse_modify_other_keys(bytes) {
+			let codepoint = u32::try_from(keycode).ok();
+			return Some(KeyEvent {
+				key: Some(format_key_name(keycode)?.to_owned()),
+				codepoint,
+				shifted_key: None,
+				base_layout_key: None,
+				modifiers,
+				event_type: EVENT_PRESS,
+				text: infer_text(codepoint, modifiers),
+			});
+		}
+		if let Some(parsed) = parse_kitty_sequence(bytes) {
+			return Some(kitty_key_event(bytes, &parsed));
+		}
+	}
+
+	let Some(id) = parse_key_inner(bytes, false) else {
+		// A lone non-ASCII character typed without any protocol encoding.
+		let mut chars = data.chars();
+		let ch = chars.next()?;
+		if chars.next().is_some() || ch.is_control() {
+			return None;
+		}
+		return Some(KeyEvent {
+			key:             Some(ch.to_string()),
+			codepoint:       Some(u32::from(ch)),
+			shifted_key:     None,
+			base_layout_key: None,
+			modifiers:       0,
+			event_type:      EVENT_PRESS,
+			text:            Some(ch.to_string()),
+		});
+	};
+	let ParsedKeyId { key, modifier } = parse_key_id(&id)?;
+	let text = match *bytes {
+		[b @ 0x20..=0x7e] => Some(char::from(b).to_string()),
+		_ => None,
+	};
+
+	Some(KeyEvent {
+		key: Some(key.to_owned()),
+		codepoint: key_name_codepoint(key),
+		shifted_key: None,
+		base_layout_key: None,
+		modifiers: modifier,
+		event_type: EVENT_PRESS,
+		text,
+	})
+}
+
+fn kitty_key_event(bytes: &[u8], parsed: &ParsedKittySequence) -> KeyEvent {
+	let codepoint = u32::try_from(parsed.codepoint).ok();
+	let key = match format_kitty_key(parsed) {
+		Some(id) => parse_key_id(&id).map(|p| p.key.to_owned()),
+		None => codepoint
+			.filter(|cp| !KITTY_FUNCTIONAL_KEYS.contains(cp))
+			.and_then(char::from_u32)
+			.filter(|ch| !ch.is_control())
+			.map(String::from),
+	};
+	let shifted_key = parsed.shifted_key.and_then(|cp| u32::try_from(cp).ok());
+	let event_type = parsed.event_type.unwrap_or(EVENT_PRESS);
+
+	let text = if event_type == EVENT_RELEASE {
+		None
+	} else if let Some(text) = kitty_text(bytes) {
+		Some(text)
+	} else if parsed.modifier & MOD_SHIFT != 0 {
+		infer_text(shifted_key, parsed.modifier)
+	} else {
+		infer_text(codepoint, parsed.modifier)
+	};
+
+	KeyEvent {
+		key,
+		codepoint,
+		shifted_key,
+		base_layout_key: parsed.base_layout_key.and_then(|cp| u32::try_from(cp).ok()),
+		modifiers: parsed.modifier,
+		event_type,
+		text,
+	}
+}
+
+/// Decode the text-as-codepoints field of a CSI u sequence.
+fn kitty_text(bytes: &[u8]) -> Option<String> {
+	let body = bytes.strip_prefix(b"\x1b[")?.strip_suffix(b"u")?;
+	let field = body.split(|&b| b == b';').nth(2)?;
+	let text: String = field
+		.split(|&b| b == b':')
+		.filter_map(|digits| std::str::from_utf8(digits).ok()?.parse::<u32>().ok())
+		.filter_map(char::from_u32)
+		.filter(|ch| !ch.is_control())
+		.collect();
+	(!text.is_empty()).then_some(text)
+}
+
+/// Text typed by a printable key when no modifier other than shift (or a lock)
+/// is held.
+fn infer_text(codepoint: Option<u32>, modifiers: u32) -> Option<String> {
+	if modifiers & !(MOD_SHIFT | LOCK_MASK) != 0 {
+		return None;
+	}
+	codepoint
+		.filter(|cp| !KITTY_FUNCTIONAL_KEYS.contains(cp))
+		.and_then(char::from_u32)
+		.filter(|ch| !ch.is_control())
+		.map(String::from)
+}
+
+/// Codepoint for a legacy key name, if the key has one.
+fn key_name_codepoint(key: &str) -> Option<u32> {
+	let codepoint = match key {
+		"escape" => CP_ESCAPE,
+		"tab" => CP_TAB,
+		"enter" => CP_ENTER,
+		"space" => CP_SPACE,
+		"backspace" => CP_BACKSPACE,
+		_ => {
+			let mut chars = key.chars();
+			let ch = chars.next()?;
+			return chars.next().is_none().then_some(u32::from(ch));
+		},
+	};
+	u32::try_from(codepoint).ok()
+}
+
 // =============================================================================
 // Kitty Protocol Parsing
 // =============================================================================
@@ -1180,9 +1345,7 @@ fn format_kitty_key(parsed: &ParsedKittySequence) -> Option<Cow<'static, str>> {
 	let effective_mod = parsed.modifier & !LOCK_MASK;
 	let effective_codepoint = {
 		let cp = parsed.codepoint;
-		let is_ascii_letter = u8::try_from(cp)
-			.ok()
-			.is_some_and(|b| b.is_ascii_alphabetic());
+		let is_ascii_letter = u8::try_from(cp).is_ok_and(|b| b.is_ascii_alphabetic());
 		let is_known_symbol = is_symbol_key(cp);
 		if is_ascii_letter || is_known_symbol {
 			cp
@@ -2,8 +2,9 @@
 //!
 //! # Overview
 //! High-performance primitives for clipboard access, grep, file discovery,
-//! ANSI-aware text measurement, syntax highlighting, HTML-to-Markdown
-//! conversion, and image processing.
+//! fuzzy scoring, text diffing and merging, ANSI-aware text measurement,
+//! syntax highlighting, token counting, HTML-to-Markdown conversion, and image
+//! processing.
 //!
 //! # Example
 //! ```ignore
@@ -21,19 +22,83 @@
 #![allow(clippy::trailing_empty_array, reason = "generated by napi macro")]
 #![allow(clippy::trivially_copy_pass_by_ref, reason = "napi env idiom")]
 
+pub mod archive;
+pub mod binary;
+pub mod cache_store;
+pub mod chart;
+pub mod chunk;
 pub mod clipboard;
+pub mod compress;
+pub mod db;
+pub mod diff;
+pub mod diff_render;
+pub mod edit;
+pub mod encoding;
+pub mod env_diff;
+pub mod eol;
 pub mod fd;
+pub mod file_filter;
+pub mod find_up;
 pub mod fs_cache;
+pub mod fuzzy;
+pub mod git;
 pub mod glob;
 pub mod grep;
+pub mod hash;
 pub mod highlight;
 pub mod html;
+pub mod humanize;
+pub mod ignore_matcher;
 pub mod image;
+pub mod image_cells;
+pub mod imports;
+pub mod indent;
+pub mod input_decoder;
+pub mod json_stream;
+pub mod jsonl;
 pub mod keys;
+pub mod line_count;
+pub mod line_index;
+pub mod links;
+pub mod log_normalize;
+pub mod lru;
+pub mod markdown;
+pub mod merge;
+pub mod mouse;
+pub mod multi_match;
+pub mod natural;
+pub mod offset_map;
+pub mod patch;
 pub mod prof;
 pub mod ps;
 pub mod pty;
+pub mod raw_input;
+pub mod read;
+pub mod redact;
+pub mod replace;
+pub mod safe_regex;
+pub mod screenshot;
 pub mod shell;
+pub mod shlex;
+pub mod sixel;
+pub mod snapshot;
+pub mod sse;
+pub mod symbol_index;
+pub mod symbols;
 pub mod system_info;
+pub mod tail;
 pub mod task;
+pub mod temp;
+pub mod term_screen;
+pub mod terminal;
+#[cfg(test)]
+mod test_util;
 pub mod text;
+pub mod text_buffer;
+pub mod tokens;
+pub mod transcript;
+pub mod vector_index;
+pub mod watch;
+pub mod which;
+pub mod words;
+pub mod write;
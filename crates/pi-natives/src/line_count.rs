@@ -0,0 +1,392 @@
+//! Language-aware line statistics.
+//!
+//! # Overview
+//! Walks a directory with the same walker and glob filter as `grep`, detects
+//! each file's language from its name or extension, and classifies every line
+//! as code, comment, or blank. Files are counted in parallel and the totals
+//! are aggregated per language, cloc-style.
+//!
+//! Classification is lexical: a line with any code outside comments and
+//! strings is code, a line holding only comment text is a comment, and a
+//! whitespace-only line is blank. Strings are tracked within a line so that
+//! comment markers inside literals are not mistaken for comments.
+//!
+//! Files with an unknown language, binary files, and files larger than 8 MiB
+//! are skipped.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.countLines({ path: ".", glob: "src/**" })
+//! ```
+
+use std::{collections::HashMap, fs, path::Path, sync::LazyLock};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::{binary, file_filter, fs_cache, task};
+
+/// Files larger than this are never counted.
+const MAX_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `countLines`.
+#[napi(object)]
+pub struct CountLinesOptions<'env> {
+	/// Directory or file to count (default: current directory).
+	pub path:       Option<String>,
+	/// Glob filter for filenames (e.g., "*.ts").
+	pub glob:       Option<String>,
+	/// Include hidden files (default: false).
+	pub hidden:     Option<bool>,
+	/// Respect .gitignore files (default: true).
+	pub gitignore:  Option<bool>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Line counts for one language.
+#[derive(Clone, Debug)]
+#[napi(object)]
+pub struct LanguageLineCount {
+	/// Language name (e.g., "Rust", "TypeScript").
+	pub language: String,
+	/// Number of files counted.
+	pub files:    u32,
+	/// Lines containing code.
+	pub code:     u32,
+	/// Lines containing only comments.
+	pub comment:  u32,
+	/// Whitespace-only lines.
+	pub blank:    u32,
+}
+
+/// Result of `countLines`.
+#[napi(object)]
+pub struct CountLinesResult {
+	/// Per-language counts, sorted by code lines (most first).
+	pub languages: Vec<LanguageLineCount>,
+	/// Total files counted.
+	pub files:     u32,
+	/// Total code lines.
+	pub code:      u32,
+	/// Total comment lines.
+	pub comment:   u32,
+	/// Total blank lines.
+	pub blank:     u32,
+	/// Files skipped as binary, oversized, unreadable, or of unknown language.
+	pub skipped:   u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Languages
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Lexical description of a language's comments and string literals.
+struct LanguageSpec {
+	name:           &'static str,
+	extensions:     &'static [&'static str],
+	filenames:      &'static [&'static str],
+	line_comments:  &'static [&'static str],
+	block_comments: &'static [(&'static str, &'static str)],
+	quotes:         &'static [char],
+}
+
+const C_BLOCK: &[(&str, &str)] = &[("/*", "*/")];
+const MARKUP_BLOCK: &[(&str, &str)] = &[("<!--", "-->")];
+
+macro_rules! lang {
+	($name:literal, [$($ext:literal),*], [$($file:literal),*], [$($line:literal),*], $block:expr, [$($quote:literal),*]) => {
+		LanguageSpec {
+			name:           $name,
+			extensions:     &[$($ext),*],
+			filenames:      &[$($file),*],
+			line_comments:  &[$($line),*],
+			block_comments: $block,
+			quotes:         &[$($quote),*],
+		}
+	};
+}
+
+static LANGUAGES: &[LanguageSpec] = &[
+	lang!("Rust", ["rs"], [], ["//"], C_BLOCK, ['"']),
+	lang!("TypeScript", ["ts", "mts", "cts", "tsx"], [], ["//"], C_BLOCK, ['"', '\'', '`']),
+	lang!("JavaScript", ["js", "mjs", "cjs", "jsx"], [], ["//"], C_BLOCK, ['"', '\'', '`']),
+	lang!("Python", ["py", "pyi"], [], ["#"], &[], ['"', '\'']),
+	lang!("Go", ["go"], [], ["//"], C_BLOCK, ['"', '\'', '`']),
+	lang!("C", ["c", "h"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("C++", ["cc", "cpp", "cxx", "hh", "hpp", "hxx"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("C#", ["cs"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("Java", ["java"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("Kotlin", ["kt", "kts"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("Swift", ["swift"], [], ["//"], C_BLOCK, ['"']),
+	lang!("Scala", ["scala", "sc"], [], ["//"], C_BLOCK, ['"']),
+	lang!("Dart", ["dart"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("Zig", ["zig"], [], ["//"], &[], ['"', '\'']),
+	lang!("PHP", ["php"], [], ["//", "#"], C_BLOCK, ['"', '\'']),
+	lang!("Ruby", ["rb", "rake", "gemspec"], ["Rakefile", "Gemfile"], ["#"], &[], ['"', '\'']),
+	lang!("Perl", ["pl", "pm"], [], ["#"], &[], ['"', '\'']),
+	lang!("Lua", ["lua"], [], ["--"], &[("--[[", "]]")], ['"', '\'']),
+	lang!("Haskell", ["hs"], [], ["--"], &[("{-", "-}")], ['"']),
+	lang!("OCaml", ["ml", "mli"], [], [], &[("(*", "*)")], ['"']),
+	lang!("Elixir", ["ex", "exs"], [], ["#"], &[], ['"']),
+	lang!("Clojure", ["clj", "cljs", "cljc", "edn"], [], [";"], &[], ['"']),
+	lang!("Julia", ["jl"], [], ["#"], &[("#=", "=#")], ['"']),
+	lang!("R", ["r"], [], ["#"], &[], ['"', '\'']),
+	lang!("Shell", ["sh", "bash", "zsh", "fish"], [], ["#"], &[], ['"', '\'']),
+	lang!("PowerShell", ["ps1", "psm1"], [], ["#"], &[("<#", "#>")], ['"', '\'']),
+	lang!("SQL", ["sql"], [], ["--"], C_BLOCK, ['\'']),
+	lang!("HTML", ["html", "htm"], [], [], MARKUP_BLOCK, []),
+	lang!("XML", ["xml", "xsd", "xsl", "plist"], [], [], MARKUP_BLOCK, []),
+	lang!("Vue", ["vue"], [], ["//"], &[("<!--", "-->"), ("/*", "*/")], []),
+	lang!("Svelte", ["svelte"], [], ["//"], &[("<!--", "-->"), ("/*", "*/")], []),
+	lang!("CSS", ["css"], [], [], C_BLOCK, ['"', '\'']),
+	lang!("SCSS", ["scss", "less"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("Markdown", ["md", "markdown", "mdx"], [], [], MARKUP_BLOCK, []),
+	lang!("JSON", ["json", "jsonc", "json5"], [], ["//"], C_BLOCK, ['"']),
+	lang!("YAML", ["yml", "yaml"], [], ["#"], &[], ['"', '\'']),
+	lang!("TOML", ["toml"], [], ["#"], &[], ['"', '\'']),
+	lang!("Protobuf", ["proto"], [], ["//"], C_BLOCK, ['"', '\'']),
+	lang!("GraphQL", ["graphql", "gql"], [], ["#"], &[], ['"']),
+	lang!("Terraform", ["tf", "tfvars", "hcl"], [], ["#", "//"], C_BLOCK, ['"']),
+	lang!("Nix", ["nix"], [], ["#"], C_BLOCK, ['"']),
+	lang!("Makefile", ["mk", "mak"], ["Makefile", "makefile", "GNUmakefile"], ["#"], &[], []),
+	lang!("Dockerfile", ["dockerfile"], ["Dockerfile", "Containerfile"], ["#"], &[], []),
+	lang!("CMake", ["cmake"], ["CMakeLists.txt"], ["#"], &[], ['"']),
+];
+
+/// Map from a filename or extension to an index into [`LANGUAGES`].
+type NameTable = HashMap<&'static str, usize>;
+
+/// Lookup tables from exact filename and lowercase extension to a language.
+static LOOKUP: LazyLock<(NameTable, NameTable)> = LazyLock::new(|| {
+	let mut filenames = HashMap::new();
+	let mut extensions = HashMap::new();
+	for (idx, spec) in LANGUAGES.iter().enumerate() {
+		for name in spec.filenames {
+			filenames.entry(*name).or_insert(idx);
+		}
+		for ext in spec.extensions {
+			extensions.entry(*ext).or_insert(idx);
+		}
+	}
+	(filenames, extensions)
+});
+
+fn detect_language(path: &Path) -> Option<usize> {
+	let (filenames, extensions) = &*LOOKUP;
+	let name = path.file_name()?.to_str()?;
+	if let Some(&idx) = filenames.get(name) {
+		return Some(idx);
+	}
+	let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+	extensions.get(ext.as_str()).copied()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Counting
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, Default)]
+struct Counts {
+	code:    u32,
+	comment: u32,
+	blank:   u32,
+}
+
+/// Classify every line of `text` according to `spec`.
+fn count_text(text: &str, spec: &LanguageSpec) -> Counts {
+	let mut counts = Counts::default();
+	// Closing delimiter of the block comment that is still open, if any.
+	let mut open_block: Option<&str> = None;
+
+	for line in text.lines() {
+		if line.trim().is_empty() {
+			counts.blank += 1;
+			continue;
+		}
+		let mut has_code = false;
+		let mut has_comment = false;
+		let mut quote: Option<char> = None;
+		let mut rest = line;
+
+		'scan: while let Some(ch) = rest.chars().next() {
+			if let Some(end) = open_block {
+				has_comment = true;
+				if let Some(after) = rest.strip_prefix(end) {
+					open_block = None;
+					rest = after;
+				} else {
+					rest = &rest[ch.len_utf8()..];
+				}
+				continue;
+			}
+			if let Some(q) = quote {
+				if ch == '\\' {
+					let mut chars = rest.chars();
+					chars.next();
+					chars.next();
+					rest = chars.as_str();
+					continue;
+				}
+				if ch == q {
+					quote = None;
+				}
+				rest = &rest[ch.len_utf8()..];
+				continue;
+			}
+			if ch.is_whitespace() {
+				rest = &rest[ch.len_utf8()..];
+				continue;
+			}
+			// Block openers are checked first so `--[[` wins over `--`.
+			for &(start, end) in spec.block_comments {
+				if let Some(after) = rest.strip_prefix(start) {
+					has_comment = true;
+					open_block = Some(end);
+					rest = after;
+					continue 'scan;
+				}
+			}
+			if spec
+				.line_comments
+				.iter()
+				.any(|marker| rest.starts_with(marker))
+			{
+				has_comment = true;
+				break;
+			}
+			if spec.quotes.contains(&ch) {
+				quote = Some(ch);
+			}
+			has_code = true;
+			rest = &rest[ch.len_utf8()..];
+		}
+
+		if has_code {
+			counts.code += 1;
+		} else if has_comment {
+			counts.comment += 1;
+		} else {
+			counts.blank += 1;
+		}
+	}
+	counts
+}
+
+/// Count one file, returning its language index and line counts.
+fn count_file(path: &Path) -> Option<(usize, Counts)> {
+	let language = detect_language(path)?;
+	let metadata = fs::metadata(path).ok()?;
+	if metadata.len() > MAX_FILE_BYTES {
+		return None;
+	}
+	let bytes = fs::read(path).ok()?;
+	if binary::is_binary(&bytes) {
+		return None;
+	}
+	let text = String::from_utf8_lossy(&bytes);
+	Some((language, count_text(&text, &LANGUAGES[language])))
+}
+
+fn run_count(
+	root: &Path,
+	glob: Option<&str>,
+	include_hidden: bool,
+	use_gitignore: bool,
+	ct: &task::CancelToken,
+) -> Result<CountLinesResult> {
+	let glob_set = file_filter::compile_glob(glob)?;
+	let metadata =
+		fs::metadata(root).map_err(|err| Error::from_reason(format!("Path not found: {err}")))?;
+	let paths = if metadata.is_file() {
+		vec![root.to_path_buf()]
+	} else {
+		let scanned = fs_cache::force_rescan(root, include_hidden, use_gitignore, false, ct)?;
+		file_filter::collect_files(root, &scanned, glob_set.as_ref(), None)
+			.into_iter()
+			.map(|entry| entry.path)
+			.collect()
+	};
+	ct.heartbeat()?;
+
+	let counted = paths
+		.par_iter()
+		.map(|path| {
+			ct.heartbeat()?;
+			Ok(count_file(path))
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	let mut per_language: HashMap<usize, LanguageLineCount> = HashMap::new();
+	let mut skipped = 0u32;
+	for entry in counted {
+		let Some((language, counts)) = entry else {
+			skipped = skipped.saturating_add(1);
+			continue;
+		};
+		let total = per_language
+			.entry(language)
+			.or_insert_with(|| LanguageLineCount {
+				language: LANGUAGES[language].name.to_string(),
+				files:    0,
+				code:     0,
+				comment:  0,
+				blank:    0,
+			});
+		total.files = total.files.saturating_add(1);
+		total.code = total.code.saturating_add(counts.code);
+		total.comment = total.comment.saturating_add(counts.comment);
+		total.blank = total.blank.saturating_add(counts.blank);
+	}
+
+	let mut languages: Vec<LanguageLineCount> = per_language.into_values().collect();
+	languages.sort_by(|a, b| {
+		b.code
+			.cmp(&a.code)
+			.then_with(|| a.language.cmp(&b.language))
+	});
+	let sum = |field: fn(&LanguageLineCount) -> u32| {
+		languages
+			.iter()
+			.fold(0u32, |acc, lang| acc.saturating_add(field(lang)))
+	};
+	Ok(CountLinesResult {
+		files: sum(|lang| lang.files),
+		code: sum(|lang| lang.code),
+		comment: sum(|lang| lang.comment),
+		blank: sum(|lang| lang.blank),
+		skipped,
+		languages,
+	})
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Count code, comment, and blank lines per language under a directory.
+///
+/// Files are selected like `grep` (gitignore-aware walk plus optional glob)
+/// and counted in parallel. A `path` naming a single file counts just that
+/// file.
+///
+/// # Errors
+/// Returns an error when the path does not exist, the glob is invalid, or the
+/// operation is cancelled or times out.
+#[napi(js_name = "countLines")]
+pub fn count_lines(options: CountLinesOptions<'_>) -> task::Async<CountLinesResult> {
+	let CountLinesOptions { path, glob, hidden, gitignore, signal, timeout_ms } = options;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("line_count", ct, move |ct| {
+		let root = file_filter::resolve_search_path(path.as_deref().unwrap_or("."))?;
+		run_count(&root, glob.as_deref(), hidden.unwrap_or(false), gitignore.unwrap_or(true), &ct)
+	})
+}
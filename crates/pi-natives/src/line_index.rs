@@ -0,0 +1,132 @@
+//! Newline-offset index over a file or buffer.
+//!
+//! # Overview
+//! [`LineIndex`] records the byte offset at which every line starts, so line
+//! and offset lookups are O(1) and O(log n) respectively. Lines are 1-indexed
+//! and offsets are UTF-8 byte offsets. A trailing newline does not start an
+//! extra (empty) line.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const index = await native.LineIndex.fromFile("src/lib.rs");
+//! // JS: index.lineOfOffset(1024); // -> 37
+//! ```
+
+use std::{
+	fs::File,
+	io::Read,
+	path::{Path, PathBuf},
+};
+
+use bstr::ByteSlice;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+/// Bytes read per chunk while indexing files.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/// Line-start index for a file or buffer.
+#[napi]
+pub struct LineIndex {
+	/// Byte offset where each line starts; always begins with 0.
+	starts: Vec<u64>,
+	/// Total length in bytes.
+	len:    u64,
+}
+
+impl LineIndex {
+	fn new() -> Self {
+		Self { starts: vec![0], len: 0 }
+	}
+
+	fn extend(&mut self, chunk: &[u8]) {
+		let base = self.len;
+		self
+			.starts
+			.extend(chunk.find_iter(b"\n").map(|pos| base + pos as u64 + 1));
+		self.len += chunk.len() as u64;
+	}
+
+	fn line_count_usize(&self) -> usize {
+		if self.len == 0 {
+			0
+		} else if self.starts.last() == Some(&self.len) {
+			self.starts.len() - 1
+		} else {
+			self.starts.len()
+		}
+	}
+}
+
+fn index_file(path: &Path, ct: &task::CancelToken) -> Result<LineIndex> {
+	let mut file = File::open(path)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", path.display())))?;
+	let mut index = LineIndex::new();
+	let mut chunk = vec![0u8; CHUNK_SIZE];
+	loop {
+		ct.heartbeat()?;
+		let read = file
+			.read(&mut chunk)
+			.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+		if read == 0 {
+			return Ok(index);
+		}
+		index.extend(&chunk[..read]);
+	}
+}
+
+#[napi]
+impl LineIndex {
+	/// Index a file on the worker pool.
+	#[napi(js_name = "fromFile")]
+	#[allow(clippy::use_self, reason = "napi expands the return type outside the impl")]
+	pub fn from_file(path: String) -> task::Async<LineIndex> {
+		let path = PathBuf::from(path);
+		task::blocking("line_index.file", (), move |ct| index_file(&path, &ct))
+	}
+
+	/// Index an in-memory buffer synchronously.
+	#[napi(factory, js_name = "fromBuffer")]
+	pub fn from_buffer(buffer: Uint8Array) -> Self {
+		let mut index = Self::new();
+		index.extend(buffer.as_ref());
+		index
+	}
+
+	/// Number of lines (0 for empty input).
+	#[napi(js_name = "lineCount")]
+	pub fn line_count(&self) -> u32 {
+		self.line_count_usize().min(u32::MAX as usize) as u32
+	}
+
+	/// Total indexed length in bytes.
+	#[napi(getter, js_name = "byteLength")]
+	pub const fn byte_length(&self) -> f64 {
+		self.len as f64
+	}
+
+	/// Byte offset where 1-indexed `line` starts, or `null` when out of range.
+	#[napi(js_name = "offsetOfLine")]
+	pub fn offset_of_line(&self, line: u32) -> Option<f64> {
+		let line = line as usize;
+		if line == 0 || line > self.line_count_usize() {
+			return None;
+		}
+		Some(self.starts[line - 1] as f64)
+	}
+
+	/// 1-indexed line containing byte `offset`, or `null` when out of range.
+	///
+	/// An offset equal to the byte length maps to the last line.
+	#[napi(js_name = "lineOfOffset")]
+	pub fn line_of_offset(&self, offset: f64) -> Option<u32> {
+		if !(0.0..=self.len as f64).contains(&offset) || self.line_count_usize() == 0 {
+			return None;
+		}
+		let offset = offset as u64;
+		let line = self.starts.partition_point(|&start| start <= offset);
+		Some(line.min(self.line_count_usize()).min(u32::MAX as usize) as u32)
+	}
+}
@@ -0,0 +1,478 @@
+//! URL and file-reference detection in terminal output.
+//!
+//! # Overview
+//! Scans output line by line for clickable targets:
+//! - URLs (`http`, `https`, `ftp`, `file`), with trailing punctuation and
+//!   unbalanced closing brackets trimmed.
+//! - File references as compilers, linters, and stack traces print them:
+//!   `path:line`, `path:line:col`, `path(line,col)`, and Python's
+//!   `File "path", line N`. Paths containing a separator are also linked
+//!   without a position.
+//!
+//! Every file reference is checked against the filesystem (relative paths
+//! resolve against `cwd`, `~/` against the home directory), so words that
+//! merely look like paths are dropped. ANSI escapes are ignored and ranges
+//! are display columns, ready for hit-testing clicks in a rendered pane.
+//!
+//! In the other direction, `makeFileLink` and `hyperlink` emit OSC 8
+//! hyperlinks so printed file references are clickable: `file://` URIs or
+//! editor URIs (`vscode://file/...:line:col` and friends), percent-encoded,
+//! with a stable `id` so terminals highlight every line of a link together.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.detectLinks("error: src/main.rs:10:5 see https://docs.rs", { cwd: root })
+//! // JS: // -> [{ kind: "file", row: 0, start: 7, end: 23, path: "/repo/src/main.rs", line: 10, column: 5, ... },
+//! // JS: //     { kind: "url", row: 0, start: 28, end: 43, url: "https://docs.rs", ... }]
+//! // JS: native.makeFileLink("src/main.rs", 10, 5, { scheme: "vscode", cwd: root })
+//! // JS: // -> "\x1b]8;id=...;vscode://file/repo/src/main.rs:10:5\x1b\\src/main.rs:10:5\x1b]8;;\x1b\\"
+//! ```
+
+use std::{
+	collections::HashMap,
+	env,
+	fmt::Write as _,
+	path::{Path, PathBuf},
+	sync::LazyLock,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use regex::{Captures, Regex};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::text::visible_width;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `detectLinks`.
+#[napi(object)]
+pub struct DetectLinksOptions {
+	/// Directory relative paths resolve against (default: process cwd).
+	pub cwd: Option<String>,
+}
+
+/// A link found in the text.
+#[napi(object)]
+pub struct DetectedLink {
+	/// `"url"` or `"file"`.
+	pub kind:   String,
+	/// Zero-based line of the input containing the link.
+	pub row:    u32,
+	/// Start display column within the line (ANSI escapes excluded).
+	pub start:  u32,
+	/// End display column (exclusive).
+	pub end:    u32,
+	/// The link as it appears in the text.
+	pub text:   String,
+	/// Target URL, for `"url"` links.
+	pub url:    Option<String>,
+	/// Absolute path of the existing file or directory, for `"file"` links.
+	pub path:   Option<String>,
+	/// 1-based line number from the reference, if any.
+	pub line:   Option<u32>,
+	/// 1-based column number from the reference, if any.
+	pub column: Option<u32>,
+}
+
+/// Options for `makeFileLink`.
+#[napi(object)]
+pub struct FileLinkOptions {
+	/// `"file"` (default) or an editor: `"vscode"`, `"vscode-insiders"`,
+	/// `"vscodium"`, `"cursor"`, `"windsurf"`, `"zed"`, `"idea"`, or `"subl"`.
+	pub scheme: Option<String>,
+	/// Visible text, which may be styled (default: `path:line:col` as given).
+	pub text:   Option<String>,
+	/// Link id (default: derived from the URI).
+	pub id:     Option<String>,
+	/// Directory relative paths resolve against (default: process cwd).
+	pub cwd:    Option<String>,
+}
+
+/// Options for `hyperlink`.
+#[napi(object)]
+pub struct HyperlinkOptions {
+	/// Link id (default: derived from the URI).
+	pub id: Option<String>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Scanning
+// ═══════════════════════════════════════════════════════════════════════════
+
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(
+		r#"(?x)
+		(?P<url>\b(?:https?|ftp|file)://[^\s<>"'`]+)
+		| File\ "(?P<pypath>[^"]+)",\ line\ (?P<pyline>\d+)
+		| (?P<path>(?:[A-Za-z]:[\\/])?[\w.~@+\-/\\]*[\w@+\-])
+		  (?: :(?P<line>\d+)(?::(?P<col>\d+))?
+		    | \((?P<pline>\d+)(?:,\s*(?P<pcol>\d+))?\) )?
+		"#,
+	)
+	.expect("link pattern is valid")
+});
+
+/// Remove ANSI escape sequences (CSI, OSC, and two-byte escapes).
+fn strip_ansi(line: &str) -> String {
+	let mut out = String::with_capacity(line.len());
+	let mut chars = line.chars().peekable();
+	while let Some(ch) = chars.next() {
+		if ch != '\x1b' {
+			out.push(ch);
+			continue;
+		}
+		match chars.next() {
+			Some('[') => {
+				// Parameters and intermediates, then a final byte in @..~.
+				for c in chars.by_ref() {
+					if ('@'..='~').contains(&c) {
+						break;
+					}
+				}
+			},
+			Some(']') => {
+				// OSC runs until BEL or ST (ESC \).
+				while let Some(c) = chars.next() {
+					if c == '\x07' {
+						break;
+					}
+					if c == '\x1b' && chars.peek() == Some(&'\\') {
+						chars.next();
+						break;
+					}
+				}
+			},
+			_ => {},
+		}
+	}
+	out
+}
+
+/// Trim sentence punctuation and closing brackets that have no opening
+/// partner inside the URL.
+fn trim_url(url: &str) -> &str {
+	let mut url = url;
+	loop {
+		let Some(last) = url.chars().last() else {
+			return url;
+		};
+		let unbalanced = |open: char, close: char| {
+			url.chars().filter(|&c| c == close).count() > url.chars().filter(|&c| c == open).count()
+		};
+		let trim = match last {
+			'.' | ',' | ';' | ':' | '!' | '?' | '\'' | '"' => true,
+			')' => unbalanced('(', ')'),
+			']' => unbalanced('[', ']'),
+			'}' => unbalanced('{', '}'),
+			_ => false,
+		};
+		if !trim {
+			return url;
+		}
+		url = &url[..url.len() - last.len_utf8()];
+	}
+}
+
+/// Resolves candidate paths, caching filesystem lookups.
+struct Resolver<'a> {
+	cwd:   &'a Path,
+	home:  Option<PathBuf>,
+	cache: HashMap<String, Option<PathBuf>>,
+}
+
+impl Resolver<'_> {
+	fn resolve(&mut self, raw: &str) -> Option<PathBuf> {
+		if let Some(hit) = self.cache.get(raw) {
+			return hit.clone();
+		}
+		let path = match raw.strip_prefix("~/") {
+			Some(rest) => self.home.as_ref().map(|home| home.join(rest)),
+			None => Some(self.cwd.join(raw)),
+		};
+		// Collecting components drops `.` segments.
+		let found = path
+			.map(|path| path.components().collect::<PathBuf>())
+			.filter(|path| path.exists());
+		self.cache.insert(raw.to_string(), found.clone());
+		found
+	}
+}
+
+fn number(captures: &Captures<'_>, name: &str) -> Option<u32> {
+	captures.name(name).and_then(|m| m.as_str().parse().ok())
+}
+
+/// Find links in one ANSI-free line.
+fn scan_line(line: &str, row: u32, resolver: &mut Resolver<'_>, out: &mut Vec<DetectedLink>) {
+	let column = |byte: usize| visible_width(&line[..byte]) as u32;
+	for captures in LINK.captures_iter(line) {
+		let Some(whole) = captures.get(0) else {
+			continue;
+		};
+		if let Some(url) = captures.name("url") {
+			let url = trim_url(url.as_str());
+			let start = whole.start();
+			out.push(DetectedLink {
+				kind:   "url".to_string(),
+				row,
+				start:  column(start),
+				end:    column(start + url.len()),
+				text:   url.to_string(),
+				url:    Some(url.to_string()),
+				path:   None,
+				line:   None,
+				column: None,
+			});
+			continue;
+		}
+
+		let (path, line_no, col_no, start) = if let Some(path) = captures.name("pypath") {
+			(path.as_str(), number(&captures, "pyline"), None, path.start() - 1)
+		} else if let Some(path) = captures.name("path") {
+			let line_no = number(&captures, "line").or_else(|| number(&captures, "pline"));
+			let col_no = number(&captures, "col").or_else(|| number(&captures, "pcol"));
+			(path.as_str(), line_no, col_no, path.start())
+		} else {
+			continue;
+		};
+		// Bare words are only linked with a position; bare paths need a separator.
+		if line_no.is_none() && !path.contains(['/', '\\']) {
+			continue;
+		}
+		let Some(resolved) = resolver.resolve(path) else {
+			continue;
+		};
+		out.push(DetectedLink {
+			kind:   "file".to_string(),
+			row,
+			start:  column(start),
+			end:    column(whole.end()),
+			text:   line[start..whole.end()].to_string(),
+			url:    None,
+			path:   Some(resolved.to_string_lossy().into_owned()),
+			line:   line_no,
+			column: col_no,
+		});
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Hyperlinks
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Editors that open `<scheme>://file<path>:<line>:<col>` URIs.
+const FILE_PATH_EDITORS: &[&str] =
+	&["vscode", "vscode-insiders", "vscodium", "cursor", "windsurf", "zed"];
+
+/// Percent-encode every byte outside `keep` and the unreserved set.
+fn percent_encode(text: &str, keep: &[u8]) -> String {
+	let mut out = String::with_capacity(text.len());
+	for &byte in text.as_bytes() {
+		if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) || keep.contains(&byte) {
+			out.push(byte as char);
+		} else {
+			let _ = write!(out, "%{byte:02X}");
+		}
+	}
+	out
+}
+
+/// Absolute path with forward slashes and a leading `/`, as URIs spell it.
+fn uri_path(path: &str, cwd: Option<String>) -> Result<String> {
+	let is_drive = |p: &str| {
+		let bytes = p.as_bytes();
+		bytes.len() >= 3
+			&& bytes[0].is_ascii_alphabetic()
+			&& bytes[1] == b':'
+			&& matches!(bytes[2], b'/' | b'\\')
+	};
+	let absolute = if is_drive(path) || Path::new(path).is_absolute() {
+		path.to_string()
+	} else if let Some(rest) = path.strip_prefix("~/") {
+		let home = env::home_dir().ok_or_else(|| Error::from_reason("Home directory is unknown"))?;
+		home.join(rest).to_string_lossy().into_owned()
+	} else {
+		let cwd = match cwd {
+			Some(cwd) => PathBuf::from(cwd),
+			None => env::current_dir().map_err(|err| {
+				Error::from_reason(format!("Failed to read current directory: {err}"))
+			})?,
+		};
+		// Collecting components drops `.` segments.
+		cwd.join(path)
+			.components()
+			.collect::<PathBuf>()
+			.to_string_lossy()
+			.into_owned()
+	};
+	let absolute = absolute.replace('\\', "/");
+	Ok(if absolute.starts_with('/') {
+		absolute
+	} else {
+		format!("/{absolute}")
+	})
+}
+
+fn file_link_uri(
+	scheme: &str,
+	path: &str,
+	line: Option<u32>,
+	column: Option<u32>,
+) -> Result<String> {
+	let encoded = percent_encode(path, b"/:@!$&'()*+,;=");
+	if scheme == "file" {
+		// File URIs have no standard way to carry a position.
+		return Ok(format!("file://{encoded}"));
+	}
+	let line = line.unwrap_or(1);
+	let column = column.unwrap_or(1);
+	if FILE_PATH_EDITORS.contains(&scheme) {
+		return Ok(format!("{scheme}://file{encoded}:{line}:{column}"));
+	}
+	let query = percent_encode(path, b"/:");
+	match scheme {
+		"idea" => Ok(format!("idea://open?file={query}&line={line}&column={column}")),
+		"subl" => Ok(format!("subl://open?url=file://{query}&line={line}&column={column}")),
+		_ => Err(Error::from_reason(format!("Unknown link scheme: {scheme}"))),
+	}
+}
+
+/// Remove OSC 8 sequences so wrapped text never nests links.
+fn strip_hyperlinks(text: &str) -> String {
+	let mut out = String::with_capacity(text.len());
+	let mut rest = text;
+	while let Some(start) = rest.find("\x1b]8;") {
+		out.push_str(&rest[..start]);
+		let body = &rest[start + 4..];
+		let end = match (body.find('\x07'), body.find("\x1b\\")) {
+			(Some(bel), Some(st)) if st < bel => st + 2,
+			(Some(bel), _) => bel + 1,
+			(None, Some(st)) => st + 2,
+			(None, None) => body.len(),
+		};
+		rest = &body[end..];
+	}
+	out.push_str(rest);
+	out
+}
+
+/// Wrap each line of `text` in an OSC 8 hyperlink to `uri`.
+fn osc8(text: &str, uri: &str, id: Option<String>) -> String {
+	// URIs must be printable ASCII; anything else would end the sequence early.
+	let mut safe = String::with_capacity(uri.len());
+	for byte in uri.bytes() {
+		if (0x21..=0x7e).contains(&byte) {
+			safe.push(byte as char);
+		} else {
+			let _ = write!(safe, "%{byte:02X}");
+		}
+	}
+	let uri = safe;
+	let id = id.map_or_else(
+		|| format!("{:016x}", xxh3_64(uri.as_bytes())),
+		|id| {
+			id.chars()
+				.filter(|c| c.is_ascii_graphic() && !matches!(c, ':' | ';'))
+				.collect()
+		},
+	);
+	strip_hyperlinks(text)
+		.split('\n')
+		.map(|line| {
+			if line.is_empty() {
+				String::new()
+			} else {
+				format!("\x1b]8;id={id};{uri}\x1b\\{line}\x1b]8;;\x1b\\")
+			}
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect URLs and file references in terminal output.
+///
+/// # Arguments
+/// - `text`: Output to scan; may contain ANSI escapes and multiple lines.
+/// - `options`: Directory for resolving relative paths.
+///
+/// # Returns
+/// Links in reading order. File links are included only when the path
+/// exists.
+///
+/// # Errors
+/// Returns an error if no `cwd` is given and the process cwd is unavailable.
+#[napi(js_name = "detectLinks")]
+pub fn detect_links(
+	text: String,
+	options: Option<DetectLinksOptions>,
+) -> Result<Vec<DetectedLink>> {
+	let cwd = match options.and_then(|opts| opts.cwd) {
+		Some(cwd) => PathBuf::from(cwd),
+		None => env::current_dir()
+			.map_err(|err| Error::from_reason(format!("Failed to read current directory: {err}")))?,
+	};
+	let mut resolver = Resolver { cwd: &cwd, home: env::home_dir(), cache: HashMap::new() };
+	let mut links = Vec::new();
+	for (row, line) in text.split('\n').enumerate() {
+		let line = line.strip_suffix('\r').unwrap_or(line);
+		let plain = if line.contains('\x1b') { strip_ansi(line) } else { line.to_string() };
+		scan_line(&plain, row as u32, &mut resolver, &mut links);
+	}
+	Ok(links)
+}
+
+/// Build a clickable OSC 8 hyperlink for a file reference.
+///
+/// # Arguments
+/// - `path`: File path; relative paths resolve against `cwd`, `~/` against the
+///   home directory.
+/// - `line`: 1-based line number, if any.
+/// - `column`: 1-based column number, if any.
+/// - `options`: URI scheme, visible text, link id, and base directory.
+///
+/// # Returns
+/// The visible text wrapped in an OSC 8 sequence. `file://` URIs carry no
+/// position; editor URIs default to line and column 1.
+///
+/// # Errors
+/// Returns an error for an unknown scheme, or when a relative path cannot be
+/// resolved.
+#[napi(js_name = "makeFileLink")]
+pub fn make_file_link(
+	path: String,
+	line: Option<u32>,
+	column: Option<u32>,
+	options: Option<FileLinkOptions>,
+) -> Result<String> {
+	let (scheme, text, id, cwd) = match options {
+		Some(opts) => (opts.scheme, opts.text, opts.id, opts.cwd),
+		None => (None, None, None, None),
+	};
+	let uri =
+		file_link_uri(scheme.as_deref().unwrap_or("file"), &uri_path(&path, cwd)?, line, column)?;
+	let text = text.unwrap_or_else(|| match (line, column) {
+		(Some(line), Some(column)) => format!("{path}:{line}:{column}"),
+		(Some(line), None) => format!("{path}:{line}"),
+		_ => path,
+	});
+	Ok(osc8(&text, &uri, id))
+}
+
+/// Wrap existing, possibly styled, text in an OSC 8 hyperlink.
+///
+/// # Arguments
+/// - `text`: Visible text; SGR styling is kept and links already inside it are
+///   removed. Each line is linked separately under the same id.
+/// - `uri`: Link target; bytes outside printable ASCII are percent-encoded.
+/// - `options`: Link id.
+#[napi(js_name = "hyperlink")]
+pub fn hyperlink(text: String, uri: String, options: Option<HyperlinkOptions>) -> String {
+	osc8(&text, &uri, options.and_then(|opts| opts.id))
+}
@@ -0,0 +1,159 @@
+//! Log cleanup before display or summarization.
+//!
+//! # Overview
+//! Build and test logs are mostly noise to a reader: every line carries a
+//! timestamp, daemons prefix a PID, progress bars redraw the same line with
+//! carriage returns, and retry loops print the same message hundreds of times.
+//! `normalizeLog` removes the volatile parts of each line and then collapses
+//! runs of identical lines into one line with a `(×N)` marker, so a log that
+//! differed only in timestamps shrinks to its distinct messages.
+//!
+//! Each line keeps only the text after its last carriage return (what a
+//! terminal would show) and loses trailing whitespace. Runs of blank lines
+//! collapse to a single blank line without a marker.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.normalizeLog([
+//! // JS:   "2024-05-01T10:00:00.123Z [worker] retrying",
+//! // JS:   "2024-05-01T10:00:01.456Z [worker] retrying",
+//! // JS:   "sshd[4242]: connection closed",
+//! // JS: ]) // -> ["[worker] retrying (×2)", "sshd: connection closed"]
+//! ```
+
+use std::sync::LazyLock;
+
+use napi_derive::napi;
+use regex::Regex;
+
+use crate::text::strip_ansi;
+
+/// Date-time forms: RFC 3339 / ISO 8601, Go's `log` package, syslog, and a
+/// bare time of day.
+const TIMESTAMP_FORMS: &str = r"(?:\b\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}(?::\d{2}(?:[.,]\d+)?)?(?:Z|\s?[+-]\d{2}:?\d{2}|\sUTC)?|\b\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}(?:[.,]\d+)?|\b(?:Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec) +\d{1,2} \d{2}:\d{2}:\d{2}|\b\d{1,2}:\d{2}:\d{2}(?:[.,]\d+)?(?: ?[AP]M)?\b)";
+
+/// A timestamp, bracketed or bare, or a kernel uptime stamp at line start,
+/// with the whitespace after it.
+static TIMESTAMP: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(&format!(r"(?:^\[\s*\d+\.\d+\]|\[\s*{TIMESTAMP_FORMS}\s*\]|{TIMESTAMP_FORMS})[ \t]*"))
+		.expect("timestamp pattern is valid")
+});
+
+/// `name[1234]:` as written by syslog.
+static SYSLOG_PID: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"\b([\w.-]+)\[\d+\]:").expect("syslog pid pattern is valid"));
+
+/// `pid=1234`, `PID: 1234`, `pid 1234`, and Node's `(node:1234)`.
+static PID: LazyLock<Regex> = LazyLock::new(|| {
+	Regex::new(r"(?i)\b(pid)(?:[=:]\s*|\s+)\d+\b|(\(node):\d+\b").expect("pid pattern is valid")
+});
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `normalizeLog`.
+#[napi(object)]
+pub struct NormalizeLogOptions {
+	/// Remove timestamps and process IDs (default: true).
+	#[napi(js_name = "stripTimestamps")]
+	pub strip_timestamps: Option<bool>,
+	/// Replace runs of identical lines with one line ending in `(×N)`
+	/// (default: true).
+	#[napi(js_name = "collapseRepeats")]
+	pub collapse_repeats: Option<bool>,
+	/// Remove ANSI escape sequences (default: true).
+	#[napi(js_name = "stripAnsi")]
+	pub strip_ansi:       Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Normalization
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn strip_volatile(line: &str) -> String {
+	if !line.bytes().any(|b| b.is_ascii_digit()) {
+		return line.to_string();
+	}
+	let line = TIMESTAMP.replace_all(line, "");
+	let line = SYSLOG_PID.replace_all(&line, "$1:");
+	let line = PID.replace_all(&line, "$1$2");
+	line.into_owned()
+}
+
+fn normalize_line(line: &str, strip_timestamps: bool, strip_escapes: bool) -> String {
+	let line = if strip_escapes {
+		strip_ansi(line)
+	} else {
+		line.to_string()
+	};
+	// Keep what a terminal would show after carriage-return redraws.
+	let line = line
+		.trim_end_matches('\r')
+		.rsplit('\r')
+		.next()
+		.unwrap_or_default();
+	let line = if strip_timestamps {
+		strip_volatile(line)
+	} else {
+		line.to_string()
+	};
+	line.trim_end().to_string()
+}
+
+fn push_run(out: &mut Vec<String>, line: String, count: usize) {
+	if count > 1 && !line.is_empty() {
+		out.push(format!("{line} (×{count})"));
+	} else {
+		out.push(line);
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Clean up log lines for display or summarization.
+///
+/// # Arguments
+/// - `lines`: Log lines; entries containing newlines are split, ignoring a
+///   trailing one.
+/// - `options`: Which cleanups to apply (all on by default).
+///
+/// # Returns
+/// Cleaned lines, with repeats collapsed when enabled.
+#[napi(js_name = "normalizeLog")]
+pub fn normalize_log(lines: Vec<String>, options: Option<NormalizeLogOptions>) -> Vec<String> {
+	let (strip_timestamps, collapse_repeats, strip_escapes) = match options {
+		Some(opts) => (
+			opts.strip_timestamps.unwrap_or(true),
+			opts.collapse_repeats.unwrap_or(true),
+			opts.strip_ansi.unwrap_or(true),
+		),
+		None => (true, true, true),
+	};
+	let mut out = Vec::with_capacity(lines.len());
+	let mut run: Option<(String, usize)> = None;
+	let split = lines
+		.iter()
+		.flat_map(|entry| entry.strip_suffix('\n').unwrap_or(entry).split('\n'));
+	for line in split {
+		let line = normalize_line(line, strip_timestamps, strip_escapes);
+		if !collapse_repeats {
+			out.push(line);
+			continue;
+		}
+		match &mut run {
+			Some((current, count)) if *current == line => *count += 1,
+			_ => {
+				if let Some((current, count)) = run.replace((line, 1)) {
+					push_run(&mut out, current, count);
+				}
+			},
+		}
+	}
+	if let Some((current, count)) = run {
+		push_run(&mut out, current, count);
+	}
+	out
+}
@@ -0,0 +1,304 @@
+//! Bounded in-process LRU map.
+//!
+//! # Overview
+//! `LruMap` holds string or byte values under string keys, bounded by entry
+//! count and by total bytes (key plus value, UTF-8 for strings). Inserting
+//! past either bound evicts the least recently used entries. Entries may
+//! expire after a TTL; expired entries read as misses and are dropped when
+//! touched or when `prune()` runs.
+//!
+//! Recency is an increasing tick per access, ordered in a `BTreeMap`, so
+//! reads, writes, and evictions are `O(log n)` without per-entry JS objects.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const lines = new native.LruMap({ maxBytes: 8 << 20, ttl: 60_000 })
+//! // JS: lines.set("README.md:120", rendered)
+//! // JS: lines.get("README.md:120") // -> string | Uint8Array | null
+//! ```
+
+use std::{
+	collections::{BTreeMap, HashMap},
+	time::{Duration, Instant},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for creating an `LruMap`.
+#[napi(object)]
+pub struct LruMapOptions {
+	/// Maximum number of entries (default: unlimited).
+	#[napi(js_name = "maxEntries")]
+	pub max_entries: Option<u32>,
+	/// Maximum total bytes of keys and values (default: 64 MiB).
+	#[napi(js_name = "maxBytes")]
+	pub max_bytes:   Option<f64>,
+	/// Default time to live in milliseconds (default: no expiry).
+	pub ttl:         Option<f64>,
+}
+
+/// Options for `LruMap.set`.
+#[napi(object)]
+pub struct LruSetOptions {
+	/// Time to live in milliseconds, overriding the map's default.
+	pub ttl: Option<f64>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Map
+// ═══════════════════════════════════════════════════════════════════════════
+
+const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+enum Value {
+	Text(String),
+	Bytes(Vec<u8>),
+}
+
+impl Value {
+	const fn len(&self) -> usize {
+		match self {
+			Self::Text(text) => text.len(),
+			Self::Bytes(bytes) => bytes.len(),
+		}
+	}
+
+	fn to_js(&self) -> Either<String, Uint8Array> {
+		match self {
+			Self::Text(text) => Either::A(text.clone()),
+			Self::Bytes(bytes) => Either::B(Uint8Array::from(bytes.clone())),
+		}
+	}
+}
+
+struct Slot {
+	value:   Value,
+	tick:    u64,
+	expires: Option<Instant>,
+}
+
+fn parse_ttl(ttl: Option<f64>) -> Result<Option<Duration>> {
+	match ttl {
+		Some(ms) if !ms.is_finite() || ms < 0.0 => {
+			Err(Error::from_reason(format!("ttl must be a non-negative number, got {ms}")))
+		},
+		Some(ms) => Ok(Some(Duration::try_from_secs_f64(ms / 1000.0).unwrap_or(Duration::MAX))),
+		None => Ok(None),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Bounded LRU map of string or byte values.
+#[napi]
+pub struct LruMap {
+	slots:       HashMap<String, Slot>,
+	/// Keys by last access tick, oldest first.
+	order:       BTreeMap<u64, String>,
+	tick:        u64,
+	bytes:       usize,
+	max_entries: usize,
+	max_bytes:   usize,
+	ttl:         Option<Duration>,
+}
+
+impl LruMap {
+	const fn cost(key: &str, value: &Value) -> usize {
+		key.len() + value.len()
+	}
+
+	const fn next_tick(&mut self) -> u64 {
+		self.tick += 1;
+		self.tick
+	}
+
+	fn remove_slot(&mut self, key: &str) -> Option<Slot> {
+		let slot = self.slots.remove(key)?;
+		self.order.remove(&slot.tick);
+		self.bytes -= Self::cost(key, &slot.value);
+		Some(slot)
+	}
+
+	/// Look up a live entry, dropping it if expired. `touch` marks it as
+	/// recently used.
+	fn live(&mut self, key: &str, touch: bool) -> Option<&Slot> {
+		let expired = self
+			.slots
+			.get(key)?
+			.expires
+			.is_some_and(|expires| expires <= Instant::now());
+		if expired {
+			self.remove_slot(key);
+			return None;
+		}
+		if touch {
+			let tick = self.next_tick();
+			let slot = self.slots.get_mut(key)?;
+			self.order.remove(&slot.tick);
+			self.order.insert(tick, key.to_string());
+			slot.tick = tick;
+		}
+		self.slots.get(key)
+	}
+
+	/// Evict least recently used entries until both bounds hold.
+	fn shrink(&mut self) {
+		while self.slots.len() > self.max_entries || self.bytes > self.max_bytes {
+			let Some((_, key)) = self.order.pop_first() else {
+				break;
+			};
+			if let Some(slot) = self.slots.remove(&key) {
+				self.bytes -= Self::cost(&key, &slot.value);
+			}
+		}
+	}
+}
+
+#[napi]
+impl LruMap {
+	/// Create an empty map.
+	///
+	/// # Errors
+	/// Returns an error for a negative or non-finite `maxBytes` or `ttl`.
+	#[napi(constructor)]
+	pub fn new(options: Option<LruMapOptions>) -> Result<Self> {
+		let (max_entries, max_bytes, ttl) = match options {
+			Some(opts) => (opts.max_entries, opts.max_bytes, opts.ttl),
+			None => (None, None, None),
+		};
+		let max_bytes = match max_bytes {
+			Some(max) if !max.is_finite() || max < 0.0 => {
+				return Err(Error::from_reason(format!(
+					"maxBytes must be a non-negative number, got {max}"
+				)));
+			},
+			Some(max) => max as usize,
+			None => DEFAULT_MAX_BYTES,
+		};
+		Ok(Self {
+			slots: HashMap::new(),
+			order: BTreeMap::new(),
+			tick: 0,
+			bytes: 0,
+			max_entries: max_entries.map_or(usize::MAX, |max| max as usize),
+			max_bytes,
+			ttl: parse_ttl(ttl)?,
+		})
+	}
+
+	/// Store `value` under `key` as the most recently used entry, evicting
+	/// older entries to stay within bounds.
+	///
+	/// # Returns
+	/// `false` when the entry alone exceeds `maxBytes` and was not stored; the
+	/// previous value under `key`, if any, is kept.
+	///
+	/// # Errors
+	/// Returns an error for a negative or non-finite `ttl`.
+	#[napi]
+	pub fn set(
+		&mut self,
+		key: String,
+		value: Either<String, Uint8Array>,
+		options: Option<LruSetOptions>,
+	) -> Result<bool> {
+		let ttl = match parse_ttl(options.and_then(|opts| opts.ttl))? {
+			Some(ttl) => Some(ttl),
+			None => self.ttl,
+		};
+		let value = match value {
+			Either::A(text) => Value::Text(text),
+			Either::B(bytes) => Value::Bytes(bytes.as_ref().to_vec()),
+		};
+		// A rejected value leaves any existing entry under `key` in place.
+		let cost = Self::cost(&key, &value);
+		if cost > self.max_bytes || self.max_entries == 0 {
+			return Ok(false);
+		}
+		self.remove_slot(&key);
+		let tick = self.next_tick();
+		let expires = ttl.and_then(|ttl| Instant::now().checked_add(ttl));
+		self.order.insert(tick, key.clone());
+		self.slots.insert(key, Slot { value, tick, expires });
+		self.bytes += cost;
+		self.shrink();
+		Ok(true)
+	}
+
+	/// Read the value under `key`, marking it as recently used.
+	///
+	/// # Returns
+	/// The value, or `null` when missing or expired.
+	#[napi]
+	pub fn get(&mut self, key: String) -> Option<Either<String, Uint8Array>> {
+		self.live(&key, true).map(|slot| slot.value.to_js())
+	}
+
+	/// Read the value under `key` without changing its recency.
+	#[napi]
+	pub fn peek(&mut self, key: String) -> Option<Either<String, Uint8Array>> {
+		self.live(&key, false).map(|slot| slot.value.to_js())
+	}
+
+	/// Whether a live entry exists under `key` (does not change recency).
+	#[napi]
+	pub fn has(&mut self, key: String) -> bool {
+		self.live(&key, false).is_some()
+	}
+
+	/// Remove the entry under `key`, returning whether it existed.
+	#[napi]
+	pub fn delete(&mut self, key: String) -> bool {
+		self.remove_slot(&key).is_some()
+	}
+
+	/// Remove all entries.
+	#[napi]
+	pub fn clear(&mut self) {
+		self.slots.clear();
+		self.order.clear();
+		self.bytes = 0;
+	}
+
+	/// Drop expired entries, returning how many were removed.
+	#[napi]
+	pub fn prune(&mut self) -> u32 {
+		let now = Instant::now();
+		let expired: Vec<String> = self
+			.slots
+			.iter()
+			.filter(|(_, slot)| slot.expires.is_some_and(|expires| expires <= now))
+			.map(|(key, _)| key.clone())
+			.collect();
+		for key in &expired {
+			self.remove_slot(key);
+		}
+		expired.len() as u32
+	}
+
+	/// Keys from least to most recently used, including expired entries not
+	/// yet pruned.
+	#[napi]
+	pub fn keys(&self) -> Vec<String> {
+		self.order.values().cloned().collect()
+	}
+
+	/// Number of entries, including expired ones not yet pruned.
+	#[napi(getter)]
+	pub fn size(&self) -> u32 {
+		self.slots.len() as u32
+	}
+
+	/// Total bytes of keys and values held.
+	#[napi(getter)]
+	pub const fn bytes(&self) -> f64 {
+		self.bytes as f64
+	}
+}
@@ -0,0 +1,239 @@
+//! Markdown block structure with source mapping.
+//!
+//! # Overview
+//! Splits Markdown into its top-level blocks (paragraphs, headings, code
+//! blocks, lists, tables, ...) using `pulldown-cmark`'s offset iterator, so
+//! callers can re-render a streaming message block by block and only redo the
+//! trailing block that is still growing.
+//!
+//! Fenced code blocks can also be extracted with their content and language,
+//! including whether the closing fence has arrived yet.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.parseMarkdownBlocks("# Title\n\n```rs\nfn main() {}\n```\n")
+//! ```
+
+use napi_derive::napi;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Kind of a top-level Markdown block.
+#[napi]
+pub enum MarkdownBlockKind {
+	/// Plain paragraph.
+	Paragraph          = 1,
+	/// ATX or setext heading.
+	Heading            = 2,
+	/// Fenced or indented code block.
+	CodeBlock          = 3,
+	/// Ordered or unordered list.
+	List               = 4,
+	/// GFM table.
+	Table              = 5,
+	/// Block quote.
+	BlockQuote         = 6,
+	/// Thematic break (`---`).
+	Rule               = 7,
+	/// Raw HTML block.
+	Html               = 8,
+	/// Footnote definition.
+	FootnoteDefinition = 9,
+	/// Anything else (definition lists, metadata blocks, math).
+	Other              = 10,
+}
+
+/// A top-level Markdown block and its source range.
+#[napi(object)]
+pub struct MarkdownBlock {
+	/// Block kind.
+	pub kind:     MarkdownBlockKind,
+	/// Start byte offset in the source (UTF-8).
+	pub start:    u32,
+	/// End byte offset in the source (UTF-8, exclusive).
+	pub end:      u32,
+	/// Heading level (1-6), for headings.
+	pub level:    Option<u32>,
+	/// Info-string language, for fenced code blocks with one.
+	pub language: Option<String>,
+	/// Whether the code block is fenced (vs indented), for code blocks.
+	pub fenced:   Option<bool>,
+	/// Whether the list is ordered, for lists.
+	pub ordered:  Option<bool>,
+}
+
+/// A fenced code block extracted from Markdown.
+#[napi(object)]
+pub struct CodeBlock {
+	/// First word of the info string (e.g. "ts"), if any.
+	pub language: Option<String>,
+	/// Full info string after the opening fence.
+	pub info:     String,
+	/// Code content without fences or container indentation.
+	pub content:  String,
+	/// Start byte offset of the opening fence (UTF-8).
+	pub start:    u32,
+	/// End byte offset of the block (UTF-8, exclusive).
+	pub end:      u32,
+	/// Whether the closing fence is present (false while still streaming).
+	pub closed:   bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Parsing
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Parser options shared by the Markdown utilities.
+pub fn parser_options() -> Options {
+	Options::ENABLE_TABLES
+		| Options::ENABLE_STRIKETHROUGH
+		| Options::ENABLE_TASKLISTS
+		| Options::ENABLE_FOOTNOTES
+}
+
+/// First word of a fenced code block info string, if any.
+pub fn info_language(info: &str) -> Option<String> {
+	info
+		.split(|ch: char| ch.is_whitespace() || ch == ',' || ch == '{')
+		.next()
+		.filter(|lang| !lang.is_empty())
+		.map(str::to_string)
+}
+
+impl MarkdownBlock {
+	const fn new(kind: MarkdownBlockKind, start: usize, end: usize) -> Self {
+		Self {
+			kind,
+			start: start as u32,
+			end: end as u32,
+			level: None,
+			language: None,
+			fenced: None,
+			ordered: None,
+		}
+	}
+}
+
+fn block_from_tag(tag: &Tag<'_>, start: usize, end: usize) -> MarkdownBlock {
+	let mut block = MarkdownBlock::new(MarkdownBlockKind::Other, start, end);
+	match tag {
+		Tag::Paragraph => block.kind = MarkdownBlockKind::Paragraph,
+		Tag::Heading { level, .. } => {
+			block.kind = MarkdownBlockKind::Heading;
+			block.level = Some(*level as u32);
+		},
+		Tag::CodeBlock(kind) => {
+			block.kind = MarkdownBlockKind::CodeBlock;
+			match kind {
+				CodeBlockKind::Fenced(info) => {
+					block.fenced = Some(true);
+					block.language = info_language(info);
+				},
+				CodeBlockKind::Indented => block.fenced = Some(false),
+			}
+		},
+		Tag::List(first) => {
+			block.kind = MarkdownBlockKind::List;
+			block.ordered = Some(first.is_some());
+		},
+		Tag::Table(_) => block.kind = MarkdownBlockKind::Table,
+		Tag::BlockQuote(_) => block.kind = MarkdownBlockKind::BlockQuote,
+		Tag::HtmlBlock => block.kind = MarkdownBlockKind::Html,
+		Tag::FootnoteDefinition(_) => block.kind = MarkdownBlockKind::FootnoteDefinition,
+		_ => {},
+	}
+	block
+}
+
+/// Strip container prefixes (indentation, block quote markers) from a line.
+fn strip_container_prefix(line: &str) -> &str {
+	line.trim_start_matches(['>', ' ', '\t'])
+}
+
+/// Whether the fenced block in `source` ends with a closing fence matching its
+/// opening fence.
+fn has_closing_fence(source: &str) -> bool {
+	let mut lines = source.lines();
+	let Some(opening) = lines.next().map(strip_container_prefix) else {
+		return false;
+	};
+	let Some(fence_char) = opening.chars().next().filter(|ch| matches!(ch, '`' | '~')) else {
+		return false;
+	};
+	let fence_len = opening.chars().take_while(|&ch| ch == fence_char).count();
+	lines.next_back().is_some_and(|last| {
+		let last = strip_container_prefix(last).trim_end();
+		last.len() >= fence_len && last.chars().all(|ch| ch == fence_char)
+	})
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Split Markdown into top-level blocks with byte ranges.
+///
+/// # Returns
+/// Blocks in source order. Ranges cover the block's full source, including
+/// fences and list markers.
+#[napi(js_name = "parseMarkdownBlocks")]
+pub fn parse_markdown_blocks(text: String) -> Vec<MarkdownBlock> {
+	let mut blocks = Vec::new();
+	let mut depth = 0usize;
+	for (event, range) in Parser::new_ext(&text, parser_options()).into_offset_iter() {
+		match event {
+			Event::Start(tag) => {
+				if depth == 0 {
+					blocks.push(block_from_tag(&tag, range.start, range.end));
+				}
+				depth += 1;
+			},
+			Event::End(_) => depth = depth.saturating_sub(1),
+			Event::Rule if depth == 0 => {
+				blocks.push(MarkdownBlock::new(MarkdownBlockKind::Rule, range.start, range.end));
+			},
+			_ => {},
+		}
+	}
+	blocks
+}
+
+/// Extract every fenced code block (at any nesting depth) from Markdown.
+///
+/// # Returns
+/// Blocks in source order; a block whose closing fence has not been seen yet
+/// (streaming output) runs to the end of the text with `closed: false`.
+#[napi(js_name = "extractCodeBlocks")]
+pub fn extract_code_blocks(markdown: String) -> Vec<CodeBlock> {
+	let mut blocks = Vec::new();
+	let mut current: Option<CodeBlock> = None;
+	for (event, range) in Parser::new_ext(&markdown, parser_options()).into_offset_iter() {
+		match event {
+			Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+				current = Some(CodeBlock {
+					language: info_language(&info),
+					info:     info.trim().to_string(),
+					content:  String::new(),
+					start:    range.start as u32,
+					end:      range.end as u32,
+					closed:   has_closing_fence(&markdown[range]),
+				});
+			},
+			Event::Text(text) => {
+				if let Some(block) = current.as_mut() {
+					block.content.push_str(&text);
+				}
+			},
+			Event::End(TagEnd::CodeBlock) => {
+				if let Some(block) = current.take() {
+					blocks.push(block);
+				}
+			},
+			_ => {},
+		}
+	}
+	blocks
+}
@@ -0,0 +1,296 @@
+//! Three-way text merge exported via N-API.
+//!
+//! # Overview
+//! Diffs `base -> ours` and `base -> theirs` with the [`diff`] engine, clusters
+//! overlapping (or touching) changes, and emits merged text. Clusters changed
+//! on only one side take that side; clusters changed identically on both
+//! sides are taken once; everything else becomes a conflict block with
+//! git-style markers and a structured conflict entry.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.merge3(base, ours, theirs, { style: "diff3" })
+//! ```
+
+use std::ops::Range;
+
+use imara_diff::Algorithm;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::diff;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `merge3`.
+#[napi(object)]
+#[derive(Default)]
+pub struct MergeOptions {
+	/// Conflict style: "merge" (default) or "diff3" (includes the base section).
+	pub style:        Option<String>,
+	/// Diff algorithm used against the base (default: "histogram").
+	pub algorithm:    Option<String>,
+	/// Label after `<<<<<<<` (default: "ours").
+	#[napi(js_name = "oursLabel")]
+	pub ours_label:   Option<String>,
+	/// Label after `|||||||` in diff3 style (default: "base").
+	#[napi(js_name = "baseLabel")]
+	pub base_label:   Option<String>,
+	/// Label after `>>>>>>>` (default: "theirs").
+	#[napi(js_name = "theirsLabel")]
+	pub theirs_label: Option<String>,
+}
+
+/// A conflicting region of a three-way merge.
+///
+/// Line ranges are 1-indexed and inclusive of `start`; a side whose `lines`
+/// count is 0 is an empty insertion point.
+#[napi(object)]
+pub struct MergeConflict {
+	/// First line of the conflict block (the `<<<<<<<` marker) in the output.
+	#[napi(js_name = "mergedStart")]
+	pub merged_start: u32,
+	/// Number of output lines occupied by the block, markers included.
+	#[napi(js_name = "mergedLines")]
+	pub merged_lines: u32,
+	/// Start line of the region in the base text.
+	#[napi(js_name = "baseStart")]
+	pub base_start:   u32,
+	/// Number of base lines in the region.
+	#[napi(js_name = "baseLines")]
+	pub base_lines:   u32,
+	/// Start line of the region in our text.
+	#[napi(js_name = "oursStart")]
+	pub ours_start:   u32,
+	/// Number of our lines in the region.
+	#[napi(js_name = "oursLines")]
+	pub ours_lines:   u32,
+	/// Start line of the region in their text.
+	#[napi(js_name = "theirsStart")]
+	pub theirs_start: u32,
+	/// Number of their lines in the region.
+	#[napi(js_name = "theirsLines")]
+	pub theirs_lines: u32,
+	/// Base content of the region.
+	pub base:         String,
+	/// Our content of the region.
+	pub ours:         String,
+	/// Their content of the region.
+	pub theirs:       String,
+}
+
+/// Result of a three-way merge.
+#[napi(object)]
+pub struct MergeResult {
+	/// Merged text, with conflict markers around unresolved regions.
+	pub merged:    String,
+	/// Unresolved regions in output order.
+	pub conflicts: Vec<MergeConflict>,
+	/// Whether the merge completed without conflicts.
+	pub clean:     bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Merge
+// ═══════════════════════════════════════════════════════════════════════════
+
+type Change = (Range<usize>, Range<usize>);
+
+struct Labels {
+	ours:   String,
+	base:   String,
+	theirs: String,
+	diff3:  bool,
+}
+
+fn clamp_u32(value: usize) -> u32 {
+	value.min(u32::MAX as usize) as u32
+}
+
+/// Cursor over one side's changes, tracking the cumulative line-count delta.
+struct SideCursor<'a> {
+	changes: &'a [Change],
+	next:    usize,
+	delta:   isize,
+}
+
+impl<'a> SideCursor<'a> {
+	const fn new(changes: &'a [Change]) -> Self {
+		Self { changes, next: 0, delta: 0 }
+	}
+
+	fn peek_base_start(&self) -> Option<usize> {
+		self.changes.get(self.next).map(|(base, _)| base.start)
+	}
+
+	/// Consume every change starting at or before `end`, widening `end` to
+	/// cover them. Returns whether anything was consumed.
+	fn absorb(&mut self, end: &mut usize, cluster_delta: &mut isize) -> bool {
+		let mut any = false;
+		while let Some((base, side)) = self.changes.get(self.next)
+			&& base.start <= *end
+		{
+			*end = (*end).max(base.end);
+			*cluster_delta += side.len() as isize - base.len() as isize;
+			self.next += 1;
+			any = true;
+		}
+		any
+	}
+}
+
+const fn map_range(range: &Range<usize>, delta_before: isize, delta_within: isize) -> Range<usize> {
+	let start = (range.start as isize + delta_before) as usize;
+	let end = (range.end as isize + delta_before + delta_within) as usize;
+	start..end
+}
+
+fn push_lines(out: &mut String, lines: &[&str]) {
+	for line in lines {
+		out.push_str(line);
+	}
+}
+
+fn push_marker(out: &mut String, marker: &str, label: &str) {
+	if !out.is_empty() && !out.ends_with('\n') {
+		out.push('\n');
+	}
+	out.push_str(marker);
+	if !label.is_empty() {
+		out.push(' ');
+		out.push_str(label);
+	}
+	out.push('\n');
+}
+
+fn merge_texts(
+	base: &str,
+	ours: &str,
+	theirs: &str,
+	algorithm: Algorithm,
+	labels: &Labels,
+) -> MergeResult {
+	let (base_lines, ours_lines, ours_changes) = diff::changed_ranges(base, ours, algorithm);
+	let (_, theirs_lines, theirs_changes) = diff::changed_ranges(base, theirs, algorithm);
+
+	let mut merged = String::with_capacity(base.len().max(ours.len()).max(theirs.len()));
+	let mut merged_line_count = 0usize;
+	let mut conflicts = Vec::new();
+	let mut a = SideCursor::new(&ours_changes);
+	let mut b = SideCursor::new(&theirs_changes);
+	let mut base_pos = 0usize;
+
+	loop {
+		let start = match (a.peek_base_start(), b.peek_base_start()) {
+			(None, None) => break,
+			(Some(x), None) | (None, Some(x)) => x,
+			(Some(x), Some(y)) => x.min(y),
+		};
+
+		// Copy the unchanged base run preceding the cluster.
+		push_lines(&mut merged, &base_lines[base_pos..start]);
+		merged_line_count += start - base_pos;
+
+		// Grow the cluster until neither side has a change touching it.
+		let mut end = start;
+		let (mut a_within, mut b_within) = (0isize, 0isize);
+		let (mut a_changed, mut b_changed) = (false, false);
+		loop {
+			let grew_a = a.absorb(&mut end, &mut a_within);
+			let grew_b = b.absorb(&mut end, &mut b_within);
+			a_changed |= grew_a;
+			b_changed |= grew_b;
+			if !grew_a && !grew_b {
+				break;
+			}
+		}
+
+		let base_range = start..end;
+		let ours_range = map_range(&base_range, a.delta, a_within);
+		let theirs_range = map_range(&base_range, b.delta, b_within);
+		a.delta += a_within;
+		b.delta += b_within;
+		let ours_slice = &ours_lines[ours_range.clone()];
+		let theirs_slice = &theirs_lines[theirs_range.clone()];
+
+		if !b_changed || (a_changed && ours_slice == theirs_slice) {
+			push_lines(&mut merged, ours_slice);
+			merged_line_count += ours_slice.len();
+		} else if !a_changed {
+			push_lines(&mut merged, theirs_slice);
+			merged_line_count += theirs_slice.len();
+		} else {
+			let base_slice = &base_lines[base_range.clone()];
+			let block_start = merged_line_count;
+			push_marker(&mut merged, "<<<<<<<", &labels.ours);
+			push_lines(&mut merged, ours_slice);
+			let mut block_lines = 2 + ours_slice.len() + theirs_slice.len();
+			if labels.diff3 {
+				push_marker(&mut merged, "|||||||", &labels.base);
+				push_lines(&mut merged, base_slice);
+				block_lines += 1 + base_slice.len();
+			}
+			push_marker(&mut merged, "=======", "");
+			push_lines(&mut merged, theirs_slice);
+			push_marker(&mut merged, ">>>>>>>", &labels.theirs);
+			block_lines += 1;
+			merged_line_count += block_lines;
+
+			conflicts.push(MergeConflict {
+				merged_start: clamp_u32(block_start + 1),
+				merged_lines: clamp_u32(block_lines),
+				base_start:   clamp_u32(base_range.start + 1),
+				base_lines:   clamp_u32(base_range.len()),
+				ours_start:   clamp_u32(ours_range.start + 1),
+				ours_lines:   clamp_u32(ours_range.len()),
+				theirs_start: clamp_u32(theirs_range.start + 1),
+				theirs_lines: clamp_u32(theirs_range.len()),
+				base:         base_slice.concat(),
+				ours:         ours_slice.concat(),
+				theirs:       theirs_slice.concat(),
+			});
+		}
+
+		base_pos = end;
+	}
+
+	push_lines(&mut merged, &base_lines[base_pos..]);
+	let clean = conflicts.is_empty();
+	MergeResult { merged, conflicts, clean }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Three-way merge of `ours` and `theirs` against their common `base`.
+///
+/// # Returns
+/// Merged text with conflict markers plus a structured list of conflicts.
+///
+/// # Errors
+/// Returns an error when the style or algorithm name is unknown.
+#[napi(js_name = "merge3")]
+pub fn merge3(
+	base: String,
+	ours: String,
+	theirs: String,
+	options: Option<MergeOptions>,
+) -> Result<MergeResult> {
+	let options = options.unwrap_or_default();
+	let algorithm = diff::parse_algorithm(options.algorithm.as_deref())?;
+	let diff3 = match options.style.as_deref() {
+		None | Some("merge") => false,
+		Some("diff3") => true,
+		Some(other) => return Err(Error::from_reason(format!("Unknown conflict style: {other}"))),
+	};
+	let labels = Labels {
+		ours: options.ours_label.unwrap_or_else(|| "ours".to_string()),
+		base: options.base_label.unwrap_or_else(|| "base".to_string()),
+		theirs: options.theirs_label.unwrap_or_else(|| "theirs".to_string()),
+		diff3,
+	};
+	Ok(merge_texts(&base, &ours, &theirs, algorithm, &labels))
+}
@@ -0,0 +1,221 @@
+//! Terminal mouse report decoding.
+//!
+//! # Overview
+//! Decodes SGR (mode 1006), urxvt (mode 1015), and X10/normal (mode 1000)
+//! mouse reports into button, position, modifier, and event-kind fields, so
+//! drag selection doesn't run regexes over every motion report in JS.
+//!
+//! X10 reports are read per character, which also covers the UTF-8 extended
+//! coordinates of mode 1005.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.parseMouseEvent("\x1b[<32;10;5M")
+//! // -> { button: 0, x: 10, y: 5, modifiers: 0, kind: "drag" }
+//! ```
+
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Modifier bits, matching the keyboard modifier mask used by `keys.rs`.
+const MOD_SHIFT: u32 = 1;
+const MOD_ALT: u32 = 2;
+const MOD_CTRL: u32 = 4;
+
+/// Button number for motion without a held button and for legacy releases,
+/// which don't say which button went up.
+pub const NO_BUTTON: u32 = 3;
+
+/// Decoded mouse report.
+#[napi(object)]
+pub struct MouseEvent {
+	/// Button: 0 left, 1 middle, 2 right, 3 none, 4-7 wheel
+	/// up/down/left/right, 8-11 extra buttons.
+	pub button:    u32,
+	/// 1-based column.
+	pub x:         u32,
+	/// 1-based row.
+	pub y:         u32,
+	/// Modifier bitmask (1 = shift, 2 = alt, 4 = ctrl).
+	pub modifiers: u32,
+	/// Event kind: `"press"`, `"release"`, `"drag"`, `"move"`, or `"wheel"`.
+	pub kind:      String,
+}
+
+/// Kind of mouse event.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MouseKind {
+	Press,
+	Release,
+	Drag,
+	Move,
+	Wheel,
+}
+
+impl MouseKind {
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Press => "press",
+			Self::Release => "release",
+			Self::Drag => "drag",
+			Self::Move => "move",
+			Self::Wheel => "wheel",
+		}
+	}
+}
+
+/// Mouse report decoded from terminal input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MouseReport {
+	pub button:    u32,
+	pub x:         u32,
+	pub y:         u32,
+	pub modifiers: u32,
+	pub kind:      MouseKind,
+}
+
+impl MouseReport {
+	/// Decode the button code shared by every encoding. `release` is set for
+	/// SGR reports terminated by `m`.
+	const fn from_code(code: u32, x: u32, y: u32, release: bool) -> Option<Self> {
+		if x == 0 || y == 0 {
+			return None;
+		}
+
+		let wheel = code & 64 != 0 && code & 128 == 0;
+		let extended = code & (64 | 128) != 0;
+		let low = code & 3;
+		let button = match code & (64 | 128) {
+			64 => low + 4,
+			128 => low + 8,
+			_ => low,
+		};
+
+		let mut modifiers = 0;
+		if code & 4 != 0 {
+			modifiers |= MOD_SHIFT;
+		}
+		if code & 8 != 0 {
+			modifiers |= MOD_ALT;
+		}
+		if code & 16 != 0 {
+			modifiers |= MOD_CTRL;
+		}
+
+		let kind = if release {
+			MouseKind::Release
+		} else if wheel {
+			MouseKind::Wheel
+		} else if code & 32 != 0 {
+			if !extended && low == NO_BUTTON {
+				MouseKind::Move
+			} else {
+				MouseKind::Drag
+			}
+		} else if !extended && low == NO_BUTTON {
+			MouseKind::Release
+		} else {
+			MouseKind::Press
+		};
+
+		Some(Self { button, x, y, modifiers, kind })
+	}
+}
+
+impl From<MouseReport> for MouseEvent {
+	fn from(report: MouseReport) -> Self {
+		Self {
+			button:    report.button,
+			x:         report.x,
+			y:         report.y,
+			modifiers: report.modifiers,
+			kind:      report.kind.name().to_string(),
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Parsing
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Decode a mouse report at the start of `data`, returning it with the number
+/// of bytes it spans.
+pub fn parse_mouse_prefix(data: &str) -> Option<(MouseReport, usize)> {
+	let rest = data.strip_prefix("\x1b[")?;
+
+	// SGR: CSI < code ; x ; y (M | m)
+	if let Some(sgr) = rest.strip_prefix('<') {
+		let ([code, x, y], end) = parse_fields(sgr.as_bytes())?;
+		let release = match sgr.as_bytes()[end] {
+			b'M' => false,
+			b'm' => true,
+			_ => return None,
+		};
+		let report = MouseReport::from_code(code, x, y, release)?;
+		return Some((report, 3 + end + 1));
+	}
+
+	// X10 / normal: CSI M followed by three characters offset by 32
+	if let Some(x10) = rest.strip_prefix('M') {
+		let mut values = [0u32; 3];
+		let mut len = 3;
+		let mut chars = x10.chars();
+		for value in &mut values {
+			let ch = chars.next()?;
+			*value = u32::from(ch).checked_sub(32)?;
+			len += ch.len_utf8();
+		}
+		let [code, x, y] = values;
+		return Some((MouseReport::from_code(code, x, y, false)?, len));
+	}
+
+	// urxvt: CSI code+32 ; x ; y M
+	let ([code, x, y], end) = parse_fields(rest.as_bytes())?;
+	if rest.as_bytes()[end] != b'M' {
+		return None;
+	}
+	let report = MouseReport::from_code(code.checked_sub(32)?, x, y, false)?;
+	Some((report, 2 + end + 1))
+}
+
+/// Parse `n;n;n`, returning the values and the index of the byte after them.
+/// Fails unless a terminator byte follows.
+fn parse_fields(bytes: &[u8]) -> Option<([u32; 3], usize)> {
+	let mut values = [0u32; 3];
+	let mut idx = 0;
+	for (i, value) in values.iter_mut().enumerate() {
+		if i > 0 {
+			if bytes.get(idx) != Some(&b';') {
+				return None;
+			}
+			idx += 1;
+		}
+		let start = idx;
+		while let Some(&b) = bytes.get(idx)
+			&& b.is_ascii_digit()
+		{
+			*value = value.checked_mul(10)?.checked_add(u32::from(b - b'0'))?;
+			idx += 1;
+		}
+		if idx == start {
+			return None;
+		}
+	}
+	(idx < bytes.len()).then_some((values, idx))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Decode a single SGR, urxvt, or X10 mouse report.
+///
+/// Returns None unless the whole input is one mouse report.
+#[napi(js_name = "parseMouseEvent")]
+pub fn parse_mouse_event(data: String) -> Option<MouseEvent> {
+	let (report, len) = parse_mouse_prefix(&data)?;
+	(len == data.len()).then(|| report.into())
+}
@@ -0,0 +1,348 @@
+//! Multi-pattern literal scanning with an Aho-Corasick automaton.
+//!
+//! # Overview
+//! [`MultiMatcher`] compiles a list of literal patterns (secret prefixes,
+//! watch keywords, error markers) into one automaton, so each scan is a single
+//! pass over the text no matter how many patterns there are. Matches carry
+//! the index of the pattern that produced them.
+//!
+//! By default matches are leftmost-longest and non-overlapping. `overlapping`
+//! reports every occurrence of every pattern, and `wholeWords` drops matches
+//! that touch a letter, digit, or underscore on either side. `push` scans a
+//! stream chunk by chunk, carrying the end of each chunk over so a pattern
+//! split across PTY reads is still found. Offsets are UTF-16 code units.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const m = new native.MultiMatcher(["error:", "warning:", "FAILED"], { caseInsensitive: true });
+//! // JS: m.scan("Warning: x\nerror: y") // -> [{ patternId: 1, start: 0, end: 8 }, { patternId: 0, start: 11, end: 17 }]
+//! // JS: pty.onChunk(chunk => classify(m.push(chunk)));
+//! ```
+
+use std::collections::HashSet;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `MultiMatcher`.
+#[napi(object)]
+pub struct MultiMatcherOptions {
+	/// Match ASCII letters case-insensitively (default: false).
+	#[napi(js_name = "caseInsensitive")]
+	pub case_insensitive: Option<bool>,
+	/// Only report matches not adjacent to word characters (default: false).
+	#[napi(js_name = "wholeWords")]
+	pub whole_words:      Option<bool>,
+	/// Report overlapping matches, including patterns inside longer ones
+	/// (default: false).
+	pub overlapping:      Option<bool>,
+}
+
+/// A pattern occurrence.
+#[napi(object)]
+pub struct MultiMatch {
+	/// Index of the pattern in the constructor's list.
+	#[napi(js_name = "patternId")]
+	pub pattern_id: u32,
+	/// Start offset in UTF-16 code units; for `push`, relative to the chunk
+	/// and negative when the match began in an earlier chunk.
+	pub start:      i32,
+	/// End offset in UTF-16 code units (exclusive).
+	pub end:        i32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Matching
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A match as byte offsets into the scanned haystack.
+struct ByteMatch {
+	pattern: u32,
+	start:   usize,
+	end:     usize,
+}
+
+/// Whether a word character sits just before (or after) byte offset `byte`.
+fn is_word_char(text: &str, byte: usize, before: bool) -> bool {
+	let ch = if before {
+		text[..byte].chars().next_back()
+	} else {
+		text[byte..].chars().next()
+	};
+	ch.is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+}
+
+struct Automaton {
+	ac:               AhoCorasick,
+	case_insensitive: bool,
+	whole_words:      bool,
+	overlapping:      bool,
+	/// Longest pattern in bytes.
+	max_len:          usize,
+	/// Proper prefixes of the patterns (ASCII-lowercased when matching is
+	/// case-insensitive), to spot a match still in progress at a chunk end.
+	prefixes:         HashSet<String>,
+}
+
+impl Automaton {
+	fn new(
+		patterns: &[String],
+		case_insensitive: bool,
+		whole_words: bool,
+		overlapping: bool,
+	) -> Result<Self> {
+		let kind = if whole_words || overlapping {
+			MatchKind::Standard
+		} else {
+			MatchKind::LeftmostLongest
+		};
+		let ac = AhoCorasickBuilder::new()
+			.ascii_case_insensitive(case_insensitive)
+			.match_kind(kind)
+			.build(patterns)
+			.map_err(|err| Error::from_reason(format!("Failed to build matcher: {err}")))?;
+		let mut prefixes = HashSet::new();
+		if !overlapping {
+			for pattern in patterns {
+				let pattern = if case_insensitive {
+					pattern.to_ascii_lowercase()
+				} else {
+					pattern.clone()
+				};
+				for (end, _) in pattern.char_indices().skip(1) {
+					prefixes.insert(pattern[..end].to_string());
+				}
+			}
+		}
+		Ok(Self {
+			ac,
+			case_insensitive,
+			whole_words,
+			overlapping,
+			max_len: patterns.iter().map(String::len).max().unwrap_or(0),
+			prefixes,
+		})
+	}
+
+	/// Earliest offset where the end of `text` is a proper prefix of some
+	/// pattern, i.e. where the next chunk could complete a longer match.
+	fn partial_start(&self, text: &str) -> Option<usize> {
+		if self.overlapping {
+			return None;
+		}
+		let from = text.len().saturating_sub(self.max_len.saturating_sub(1));
+		(from..text.len())
+			.filter(|&start| text.is_char_boundary(start))
+			.find(|&start| {
+				let tail = &text[start..];
+				if self.case_insensitive {
+					self.prefixes.contains(&tail.to_ascii_lowercase())
+				} else {
+					self.prefixes.contains(tail)
+				}
+			})
+	}
+
+	/// Whether a match in a text of `len` bytes can only be confirmed by more
+	/// input: its word boundary is unknown, or a longer match in progress
+	/// would replace it.
+	fn is_pending(&self, m: &ByteMatch, len: usize, partial: Option<usize>) -> bool {
+		(self.whole_words && m.end == len) || partial.is_some_and(|start| m.start >= start)
+	}
+
+	fn find(&self, text: &str) -> Vec<ByteMatch> {
+		let to_match = |m: aho_corasick::Match| ByteMatch {
+			pattern: m.pattern().as_u32(),
+			start:   m.start(),
+			end:     m.end(),
+		};
+		if !self.whole_words {
+			return if self.overlapping {
+				self.ac.find_overlapping_iter(text).map(to_match).collect()
+			} else {
+				self.ac.find_iter(text).map(to_match).collect()
+			};
+		}
+		// A leftmost match that fails the word check can hide a valid one, so
+		// consider every occurrence and then pick leftmost-longest.
+		let mut candidates: Vec<ByteMatch> = self
+			.ac
+			.find_overlapping_iter(text)
+			.map(to_match)
+			.filter(|m| !is_word_char(text, m.start, true) && !is_word_char(text, m.end, false))
+			.collect();
+		if self.overlapping {
+			return candidates;
+		}
+		candidates.sort_by(|a, b| a.start.cmp(&b.start).then(b.end.cmp(&a.end)));
+		let mut picked: Vec<ByteMatch> = Vec::new();
+		for m in candidates {
+			if picked.last().is_none_or(|last| m.start >= last.end) {
+				picked.push(m);
+			}
+		}
+		picked
+	}
+}
+
+/// Convert byte matches to UTF-16 offsets, moved by `shift` units.
+fn to_utf16(text: &str, matches: Vec<ByteMatch>, shift: i32) -> Vec<MultiMatch> {
+	let mut matches = matches;
+	matches.sort_by_key(|m| m.start);
+	let mut byte = 0;
+	let mut unit = 0;
+	matches
+		.into_iter()
+		.map(|m| {
+			unit += text[byte..m.start].encode_utf16().count();
+			byte = m.start;
+			let end = unit + text[m.start..m.end].encode_utf16().count();
+			MultiMatch {
+				pattern_id: m.pattern,
+				start:      unit as i32 + shift,
+				end:        end as i32 + shift,
+			}
+		})
+		.collect()
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Scanner for many literal patterns at once.
+#[napi]
+pub struct MultiMatcher {
+	automaton:     Automaton,
+	/// End of the previous `push` chunk, kept to find matches spanning chunks.
+	carry:         String,
+	/// UTF-16 offset of `carry` relative to the start of the last chunk.
+	carry_offset:  i32,
+	/// Byte offset in `carry` of a match possibly in progress.
+	carry_partial: Option<usize>,
+}
+
+#[napi]
+impl MultiMatcher {
+	/// Compile `patterns` into one automaton.
+	///
+	/// # Errors
+	/// Returns an error if a pattern is empty or the automaton is too large.
+	#[napi(constructor)]
+	pub fn new(patterns: Vec<String>, options: Option<MultiMatcherOptions>) -> Result<Self> {
+		if let Some(index) = patterns.iter().position(String::is_empty) {
+			return Err(Error::from_reason(format!("Pattern {index} is empty")));
+		}
+		let (case_insensitive, whole_words, overlapping) = match options {
+			Some(opts) => (
+				opts.case_insensitive.unwrap_or(false),
+				opts.whole_words.unwrap_or(false),
+				opts.overlapping.unwrap_or(false),
+			),
+			None => (false, false, false),
+		};
+		Ok(Self {
+			automaton:     Automaton::new(&patterns, case_insensitive, whole_words, overlapping)?,
+			carry:         String::new(),
+			carry_offset:  0,
+			carry_partial: None,
+		})
+	}
+
+	/// Number of compiled patterns.
+	#[napi(getter, js_name = "patternCount")]
+	pub fn pattern_count(&self) -> u32 {
+		self.automaton.ac.patterns_len() as u32
+	}
+
+	/// Find pattern occurrences in `text`.
+	///
+	/// # Returns
+	/// Matches ordered by start offset.
+	#[napi]
+	pub fn scan(&self, text: String) -> Vec<MultiMatch> {
+		to_utf16(&text, self.automaton.find(&text), 0)
+	}
+
+	/// Whether any pattern occurs in `text`.
+	#[napi(js_name = "isMatch")]
+	pub fn is_match(&self, text: String) -> bool {
+		if self.automaton.whole_words {
+			!self.automaton.find(&text).is_empty()
+		} else {
+			self.automaton.ac.is_match(&text)
+		}
+	}
+
+	/// Scan the next chunk of a stream. Matches that span the boundary with
+	/// the previous chunk are found, and each match is reported once: one
+	/// near the chunk's end that more input could still change (a longer
+	/// pattern in progress, or an unknown word boundary) waits for the next
+	/// chunk or `end`.
+	///
+	/// # Returns
+	/// Newly confirmed matches, with offsets relative to this chunk's start.
+	#[napi]
+	pub fn push(&mut self, chunk: String) -> Vec<MultiMatch> {
+		let automaton = &self.automaton;
+		let origin = self.carry.len();
+		let old_partial = self.carry_partial;
+		let mut text = std::mem::take(&mut self.carry);
+		text.push_str(&chunk);
+		let partial = automaton.partial_start(&text);
+		// Matches within the carried text were reported by the previous call
+		// unless they were pending then.
+		let matches: Vec<ByteMatch> = automaton
+			.find(&text)
+			.into_iter()
+			.filter(|m| {
+				let new = m.end > origin || automaton.is_pending(m, origin, old_partial);
+				new && !automaton.is_pending(m, text.len(), partial)
+			})
+			.collect();
+		let origin_units = text[..origin].encode_utf16().count() as i32;
+		let found = to_utf16(&text, matches, -origin_units);
+
+		// Keep enough to rescan a pending match along with the character
+		// before it.
+		let mut keep = text.len().saturating_sub(automaton.max_len + 1);
+		while !text.is_char_boundary(keep) {
+			keep -= 1;
+		}
+		self.carry_offset = text[..keep].encode_utf16().count() as i32 - origin_units;
+		self.carry_partial = partial.map(|start| start - keep);
+		self.carry = text.split_off(keep);
+		found
+	}
+
+	/// Signal end of the stream and forget the carried-over text.
+	///
+	/// # Returns
+	/// Matches that were pending at the end of the last chunk, with offsets
+	/// relative to that chunk's start.
+	#[napi]
+	pub fn end(&mut self) -> Vec<MultiMatch> {
+		let carry = std::mem::take(&mut self.carry);
+		let partial = self.carry_partial.take();
+		let matches = self
+			.automaton
+			.find(&carry)
+			.into_iter()
+			.filter(|m| self.automaton.is_pending(m, carry.len(), partial))
+			.collect();
+		to_utf16(&carry, matches, self.carry_offset)
+	}
+
+	/// Forget the carried-over end of the stream.
+	#[napi]
+	pub fn reset(&mut self) {
+		self.carry.clear();
+		self.carry_offset = 0;
+		self.carry_partial = None;
+	}
+}
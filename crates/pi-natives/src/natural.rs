@@ -0,0 +1,97 @@
+//! Natural-order string comparison.
+//!
+//! # Overview
+//! Orders strings the way people expect file lists to sort: runs of ASCII
+//! digits compare by numeric value (`file2` < `file10`), letters compare
+//! case-folded, and nothing depends on the process locale, so the tree view,
+//! glob results, and completion lists agree everywhere.
+//!
+//! Ties are broken deterministically: fewer leading zeros first (`7` <
+//! `007`), then plain code-point order (`File` < `file`). The result is a
+//! total order, so sorting is stable across runs.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.naturalSort(["file10.txt", "File2.txt", "file1.txt"])
+//! // JS: // -> ["file1.txt", "File2.txt", "file10.txt"]
+//! // JS: native.compareNatural("v1.10", "v1.9") // -> 1
+//! ```
+
+use std::cmp::Ordering;
+
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Comparison
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn digit_run_end(s: &str, start: usize) -> usize {
+	s.as_bytes()[start..]
+		.iter()
+		.position(|b| !b.is_ascii_digit())
+		.map_or(s.len(), |len| start + len)
+}
+
+/// Compare with numeric digit runs and case folding; when that ties, the
+/// first digit run with fewer leading zeros sorts first.
+fn compare_folded(a: &str, b: &str) -> Ordering {
+	let (mut i, mut j) = (0, 0);
+	let mut zeros = Ordering::Equal;
+	while i < a.len() && j < b.len() {
+		if a.as_bytes()[i].is_ascii_digit() && b.as_bytes()[j].is_ascii_digit() {
+			let (end_a, end_b) = (digit_run_end(a, i), digit_run_end(b, j));
+			let (run_a, run_b) = (&a[i..end_a], &b[j..end_b]);
+			let (num_a, num_b) = (run_a.trim_start_matches('0'), run_b.trim_start_matches('0'));
+			// Equal-length digit strings compare numerically as text.
+			let ord = num_a.len().cmp(&num_b.len()).then_with(|| num_a.cmp(num_b));
+			if ord != Ordering::Equal {
+				return ord;
+			}
+			if zeros == Ordering::Equal {
+				zeros = run_a.len().cmp(&run_b.len());
+			}
+			(i, j) = (end_a, end_b);
+			continue;
+		}
+		let ca = a[i..].chars().next().expect("index is a char boundary");
+		let cb = b[j..].chars().next().expect("index is a char boundary");
+		let ord = ca.to_lowercase().cmp(cb.to_lowercase());
+		if ord != Ordering::Equal {
+			return ord;
+		}
+		i += ca.len_utf8();
+		j += cb.len_utf8();
+	}
+	(a.len() - i).cmp(&(b.len() - j)).then(zeros)
+}
+
+/// Natural-order comparison: numeric digit runs, case-folded letters, and
+/// deterministic tie-breaking.
+pub fn compare_natural(a: &str, b: &str) -> Ordering {
+	compare_folded(a, b).then_with(|| a.cmp(b))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Compare two strings in natural order.
+///
+/// # Returns
+/// `-1`, `0`, or `1`, usable directly as an `Array.prototype.sort`
+/// comparator.
+#[napi(js_name = "compareNatural")]
+pub fn compare_natural_js(a: String, b: String) -> i32 {
+	compare_natural(&a, &b) as i32
+}
+
+/// Sort strings in natural order.
+///
+/// # Returns
+/// A new sorted array; the input is not modified.
+#[napi(js_name = "naturalSort")]
+pub fn natural_sort(strings: Vec<String>) -> Vec<String> {
+	let mut strings = strings;
+	strings.sort_by(|a, b| compare_natural(a, b));
+	strings
+}
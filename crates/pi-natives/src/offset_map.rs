@@ -0,0 +1,227 @@
+//! UTF-8 byte to UTF-16 offset conversion for JS interop.
+//!
+//! # Overview
+//! Several native APIs report UTF-8 byte offsets (`chunkFile`, symbol ranges,
+//! `LineIndex`), while JS strings are indexed in UTF-16 code units. The two
+//! agree only for ASCII, so slicing a JS string with a byte offset goes wrong
+//! after the first accented letter or emoji. [`Utf16OffsetMap`] converts in
+//! both directions for one text.
+//!
+//! The map keeps the text plus a checkpoint every 256 bytes, so a lookup is a
+//! binary search and a short scan. All-ASCII text skips both. Byte input that
+//! is not valid UTF-8 is measured the way `TextDecoder` decodes it: each
+//! invalid sequence becomes one U+FFFD.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const map = native.offsetMapUtf16(source);
+//! // JS: const sym = symbols[0];
+//! // JS: source.slice(map.toUtf16(sym.startByte), map.toUtf16(sym.endByte));
+//! // JS: map.columnToUtf16(3, 12) // byte column 12 on line 3 -> UTF-16 column
+//! ```
+
+use bstr::ByteSlice;
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+/// Bytes between checkpoints.
+const CHECKPOINT_INTERVAL: usize = 256;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Measuring
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Size of each character of `bytes` as `(UTF-8 bytes, UTF-16 units)`. An
+/// invalid sequence counts as one replacement character.
+fn units(bytes: &[u8]) -> impl Iterator<Item = (usize, usize)> + '_ {
+	bytes.utf8_chunks().flat_map(|chunk| {
+		let valid = chunk
+			.valid()
+			.chars()
+			.map(|ch| (ch.len_utf8(), ch.len_utf16()));
+		let invalid = (!chunk.invalid().is_empty()).then_some((chunk.invalid().len(), 1));
+		valid.chain(invalid)
+	})
+}
+
+/// A character boundary with its offset in both encodings.
+#[derive(Clone, Copy)]
+struct Checkpoint {
+	byte:  usize,
+	utf16: usize,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Offset conversion between UTF-8 bytes and UTF-16 code units for one text.
+#[napi]
+pub struct Utf16OffsetMap {
+	bytes:       Vec<u8>,
+	utf16_len:   usize,
+	/// Checkpoints in ascending order, starting at offset 0; empty for ASCII.
+	checkpoints: Vec<Checkpoint>,
+	/// Byte offset where each line starts.
+	line_starts: Vec<usize>,
+}
+
+impl Utf16OffsetMap {
+	fn new(bytes: Vec<u8>) -> Self {
+		let line_starts = std::iter::once(0)
+			.chain(bytes.find_iter(b"\n").map(|pos| pos + 1))
+			.collect();
+		if bytes.is_ascii() {
+			return Self { utf16_len: bytes.len(), bytes, checkpoints: Vec::new(), line_starts };
+		}
+		let mut checkpoints = vec![Checkpoint { byte: 0, utf16: 0 }];
+		let (mut byte, mut utf16) = (0, 0);
+		for (byte_len, utf16_len) in units(&bytes) {
+			byte += byte_len;
+			utf16 += utf16_len;
+			if byte - checkpoints[checkpoints.len() - 1].byte >= CHECKPOINT_INTERVAL {
+				checkpoints.push(Checkpoint { byte, utf16 });
+			}
+		}
+		Self { bytes, utf16_len: utf16, checkpoints, line_starts }
+	}
+
+	fn byte_to_utf16(&self, offset: usize) -> Result<usize> {
+		if offset > self.bytes.len() {
+			return Err(Error::from_reason(format!(
+				"Byte offset {offset} is out of range (length {})",
+				self.bytes.len()
+			)));
+		}
+		if self.checkpoints.is_empty() {
+			return Ok(offset);
+		}
+		let index = self.checkpoints.partition_point(|cp| cp.byte <= offset) - 1;
+		let Checkpoint { mut byte, mut utf16 } = self.checkpoints[index];
+		for (byte_len, utf16_len) in units(&self.bytes[byte..]) {
+			if byte + byte_len > offset {
+				break;
+			}
+			byte += byte_len;
+			utf16 += utf16_len;
+		}
+		Ok(utf16)
+	}
+
+	fn utf16_to_byte(&self, offset: usize) -> Result<usize> {
+		if offset > self.utf16_len {
+			return Err(Error::from_reason(format!(
+				"UTF-16 offset {offset} is out of range (length {})",
+				self.utf16_len
+			)));
+		}
+		if self.checkpoints.is_empty() {
+			return Ok(offset);
+		}
+		let index = self.checkpoints.partition_point(|cp| cp.utf16 <= offset) - 1;
+		let Checkpoint { mut byte, mut utf16 } = self.checkpoints[index];
+		for (byte_len, utf16_len) in units(&self.bytes[byte..]) {
+			if utf16 + utf16_len > offset {
+				break;
+			}
+			byte += byte_len;
+			utf16 += utf16_len;
+		}
+		Ok(byte)
+	}
+}
+
+#[napi]
+impl Utf16OffsetMap {
+	/// Length of the text in UTF-8 bytes.
+	#[napi(getter, js_name = "byteLength")]
+	pub const fn byte_length(&self) -> u32 {
+		self.bytes.len() as u32
+	}
+
+	/// Length of the text in UTF-16 code units, i.e. the JS string length.
+	#[napi(getter, js_name = "utf16Length")]
+	pub const fn utf16_length(&self) -> u32 {
+		self.utf16_len as u32
+	}
+
+	/// Whether the text is ASCII, so both offsets are always equal.
+	#[napi(getter, js_name = "isAscii")]
+	pub const fn is_ascii(&self) -> bool {
+		self.checkpoints.is_empty()
+	}
+
+	/// Convert a byte offset to a UTF-16 offset. An offset inside a character
+	/// maps to the start of that character.
+	///
+	/// # Errors
+	/// Returns an error if `offset` is past the end of the text.
+	#[napi(js_name = "toUtf16")]
+	pub fn to_utf16(&self, offset: u32) -> Result<u32> {
+		self
+			.byte_to_utf16(offset as usize)
+			.map(|offset| offset as u32)
+	}
+
+	/// Convert a UTF-16 offset to a byte offset. An offset between the halves
+	/// of a surrogate pair maps to the start of the pair.
+	///
+	/// # Errors
+	/// Returns an error if `offset` is past the end of the text.
+	#[napi(js_name = "toByte")]
+	pub fn to_byte(&self, offset: u32) -> Result<u32> {
+		self
+			.utf16_to_byte(offset as usize)
+			.map(|offset| offset as u32)
+	}
+
+	/// Convert many byte offsets in one call.
+	///
+	/// # Errors
+	/// Returns an error if any offset is past the end of the text.
+	#[napi(js_name = "toUtf16Many")]
+	pub fn to_utf16_many(&self, offsets: Vec<u32>) -> Result<Vec<u32>> {
+		offsets
+			.into_iter()
+			.map(|offset| self.to_utf16(offset))
+			.collect()
+	}
+
+	/// Convert a byte column on a line to a UTF-16 column on the same line.
+	///
+	/// # Arguments
+	/// - `line`: 1-indexed line number.
+	/// - `column`: 0-indexed byte column.
+	///
+	/// # Errors
+	/// Returns an error if the line does not exist or the column is past the
+	/// end of the text.
+	#[napi(js_name = "columnToUtf16")]
+	pub fn column_to_utf16(&self, line: u32, column: u32) -> Result<u32> {
+		let start = (line as usize)
+			.checked_sub(1)
+			.and_then(|index| self.line_starts.get(index))
+			.copied()
+			.ok_or_else(|| Error::from_reason(format!("Line {line} is out of range")))?;
+		let end = self.byte_to_utf16(start + column as usize)?;
+		Ok((end - self.byte_to_utf16(start)?) as u32)
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Build an offset map for `text`.
+///
+/// # Arguments
+/// - `text`: The text whose offsets will be converted; pass the same bytes the
+///   offsets were computed on.
+#[napi(js_name = "offsetMapUtf16")]
+pub fn offset_map_utf16(text: Either<String, Uint8Array>) -> Utf16OffsetMap {
+	let bytes = match text {
+		Either::A(text) => text.into_bytes(),
+		Either::B(bytes) => bytes.to_vec(),
+	};
+	Utf16OffsetMap::new(bytes)
+}
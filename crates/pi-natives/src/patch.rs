@@ -0,0 +1,822 @@
+//! Unified diff parsing and application with tolerant hunk matching.
+//!
+//! # Overview
+//! `parsePatch` splits a unified diff, possibly touching several files, into
+//! per-file hunks shaped like those of `diffLines`.
+//!
+//! `applyPatch` applies a single-file unified diff to text in memory. Each
+//! hunk's old side is located nearest to the line its header names, then the
+//! search widens outward, so hunks still land when earlier edits shifted the
+//! file (drift). Later hunks are offset by the drift of earlier ones.
+//!
+//! Matching escalates in stages and stops at the first that succeeds:
+//! 1. exact lines;
+//! 2. with `fuzz` N, up to N leading and trailing context lines may be dropped,
+//!    as in `patch --fuzz`;
+//! 3. with `tolerant`, lines compare with leading and trailing whitespace
+//!    ignored (again with fuzz).
+//!
+//! Every hunk reports the file lines it matched, the drift, the fuzz used,
+//! and which lines only matched by ignoring whitespace, so callers can show a
+//! confirmation diff whenever a patch did not apply cleanly. Hunks that match
+//! nowhere are skipped and reported with a conflict: the lines they expected
+//! next to the closest lines the file has, and where those first differ. The
+//! remaining hunks still apply.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.parsePatch(patchText)
+//! // JS: // -> [{ oldPath: "a/src/main.rs", newPath: "b/src/main.rs", hunks: [{ oldStart, lines, ... }] }]
+//! // JS: native.applyPatch(source, patchText, { tolerant: true, fuzz: 2 })
+//! // JS: // -> { text, applied: true, exact: false, hunks: [{ startLine, offset, fuzz, ... }] }
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{
+	diff::{DiffHunk, DiffLine, DiffLineKind},
+	eol,
+};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `applyPatch`.
+#[napi(object)]
+pub struct ApplyPatchOptions {
+	/// Context lines that may be dropped from each end of a hunk when it does
+	/// not match exactly (default: 0).
+	pub fuzz:       Option<u32>,
+	/// Compare lines ignoring leading and trailing whitespace when exact
+	/// matching fails (default: false).
+	pub tolerant:   Option<bool>,
+	/// Furthest a hunk may move from the line its header names (default:
+	/// unlimited).
+	#[napi(js_name = "maxOffset")]
+	pub max_offset: Option<u32>,
+}
+
+/// Why a hunk did not apply: its old side next to the closest file lines.
+#[napi(object)]
+pub struct PatchConflict {
+	/// Context and removed lines the hunk expected, in order.
+	pub expected:      Vec<String>,
+	/// File lines at the position matching the most expected lines (nearest
+	/// the header on ties); shorter than `expected` at the end of the file.
+	pub actual:        Vec<String>,
+	/// Line of the original text where `actual` starts, 1-indexed.
+	#[napi(js_name = "startLine")]
+	pub start_line:    u32,
+	/// First line of the original text, 1-indexed, that differs from what the
+	/// hunk expected.
+	#[napi(js_name = "mismatchLine")]
+	pub mismatch_line: u32,
+}
+
+/// How one hunk was applied.
+#[napi(object)]
+pub struct PatchHunkResult {
+	/// Start line of the hunk's old side as written in its header.
+	#[napi(js_name = "oldStart")]
+	pub old_start:        u32,
+	/// Whether the hunk was applied.
+	pub applied:          bool,
+	/// First matched line of the original text, 1-indexed (unset when not
+	/// applied; the line after which text was inserted for pure insertions).
+	#[napi(js_name = "startLine")]
+	pub start_line:       Option<u32>,
+	/// Last matched line of the original text, 1-indexed and inclusive.
+	#[napi(js_name = "endLine")]
+	pub end_line:         Option<u32>,
+	/// Lines between the matched position and the one the header names
+	/// (positive when the hunk moved down).
+	pub offset:           i32,
+	/// Context lines dropped from each end to make the hunk match.
+	pub fuzz:             u32,
+	/// Original lines, 1-indexed, that matched only with whitespace ignored.
+	#[napi(js_name = "whitespaceLines")]
+	pub whitespace_lines: Vec<u32>,
+	/// Expected and actual lines when the hunk did not apply.
+	pub conflict:         Option<PatchConflict>,
+}
+
+/// Result of `applyPatch`.
+#[napi(object)]
+pub struct ApplyPatchResult {
+	/// Patched text (hunks that did not apply are skipped).
+	pub text:    String,
+	/// Whether every hunk was applied.
+	pub applied: bool,
+	/// Whether every hunk applied at its stated position without fuzz or
+	/// whitespace tolerance.
+	pub exact:   bool,
+	/// Per-hunk outcome in patch order.
+	pub hunks:   Vec<PatchHunkResult>,
+}
+
+/// One file of a parsed patch.
+#[napi(object)]
+pub struct PatchFile {
+	/// Path from the `---` header as written (unset for `/dev/null` or when
+	/// the patch has no file headers).
+	#[napi(js_name = "oldPath")]
+	pub old_path:              Option<String>,
+	/// Path from the `+++` header as written (unset for `/dev/null` or when
+	/// the patch has no file headers).
+	#[napi(js_name = "newPath")]
+	pub new_path:              Option<String>,
+	/// Hunks in patch order, with line numbers from their headers.
+	pub hunks:                 Vec<DiffHunk>,
+	/// Whether the old file's last line has no trailing newline.
+	#[napi(js_name = "oldNoNewlineAtEnd")]
+	pub old_no_newline_at_end: bool,
+	/// Whether the new file's last line has no trailing newline.
+	#[napi(js_name = "newNoNewlineAtEnd")]
+	pub new_no_newline_at_end: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Parsing
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A line of a hunk body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HunkLine {
+	Context(String),
+	Remove(String),
+	Add(String),
+}
+
+/// A parsed unified diff hunk.
+#[derive(Clone, Debug, Default)]
+pub struct Hunk {
+	pub old_start:  u32,
+	pub old_lines:  u32,
+	pub new_start:  u32,
+	pub new_lines:  u32,
+	pub lines:      Vec<HunkLine>,
+	/// The old side's last line has no trailing newline.
+	pub old_no_eol: bool,
+	/// The new side's last line has no trailing newline.
+	pub new_no_eol: bool,
+}
+
+impl Hunk {
+	fn old_side(&self) -> Vec<&str> {
+		self
+			.lines
+			.iter()
+			.filter_map(|line| match line {
+				HunkLine::Context(text) | HunkLine::Remove(text) => Some(text.as_str()),
+				HunkLine::Add(_) => None,
+			})
+			.collect()
+	}
+
+	/// Convert to the `diffLines` hunk shape, numbering lines from the header.
+	fn to_diff_hunk(&self) -> DiffHunk {
+		let mut old_line = self.old_start;
+		let mut new_line = self.new_start;
+		let lines = self
+			.lines
+			.iter()
+			.map(|line| match line {
+				HunkLine::Context(text) => {
+					old_line += 1;
+					new_line += 1;
+					DiffLine {
+						kind:     DiffLineKind::Context,
+						content:  text.clone(),
+						old_line: Some(old_line - 1),
+						new_line: Some(new_line - 1),
+					}
+				},
+				HunkLine::Remove(text) => {
+					old_line += 1;
+					DiffLine {
+						kind:     DiffLineKind::Removed,
+						content:  text.clone(),
+						old_line: Some(old_line - 1),
+						new_line: None,
+					}
+				},
+				HunkLine::Add(text) => {
+					new_line += 1;
+					DiffLine {
+						kind:     DiffLineKind::Added,
+						content:  text.clone(),
+						old_line: None,
+						new_line: Some(new_line - 1),
+					}
+				},
+			})
+			.collect();
+		DiffHunk {
+			old_start: self.old_start,
+			old_lines: self.old_lines,
+			new_start: self.new_start,
+			new_lines: self.new_lines,
+			lines,
+		}
+	}
+
+	/// Number of context lines before the first change and after the last.
+	fn context_edges(&self) -> (usize, usize) {
+		let is_context = |line: &&HunkLine| matches!(line, HunkLine::Context(_));
+		let leading = self.lines.iter().take_while(is_context).count();
+		let trailing = if leading == self.lines.len() {
+			0
+		} else {
+			self.lines.iter().rev().take_while(is_context).count()
+		};
+		(leading, trailing)
+	}
+}
+
+/// Parse `-a,b +c,d` ranges from a hunk header.
+fn parse_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+	let rest = line.strip_prefix("@@ -")?;
+	let (ranges, _) = rest.split_once(" @@")?;
+	let (old, new) = ranges.split_once(" +")?;
+	let range = |text: &str| -> Option<(u32, u32)> {
+		match text.split_once(',') {
+			Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+			None => Some((text.parse().ok()?, 1)),
+		}
+	};
+	let (old_start, old_lines) = range(old)?;
+	let (new_start, new_lines) = range(new)?;
+	Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Hunks of one file in a patch.
+#[derive(Debug, Default)]
+pub struct FilePatch {
+	/// Path from the `---` header, if any.
+	pub old_path: Option<String>,
+	/// Path from the `+++` header, if any.
+	pub new_path: Option<String>,
+	pub hunks:    Vec<Hunk>,
+}
+
+/// Path named by a `---`/`+++` header: a trailing timestamp is dropped and
+/// `/dev/null` yields None.
+fn header_path(text: &str) -> Option<String> {
+	let path = text.split('\t').next().unwrap_or(text).trim_end();
+	(path != "/dev/null" && !path.is_empty()).then(|| path.to_string())
+}
+
+/// Parse a unified diff into per-file hunks. Hunks before any file header
+/// belong to a file without paths; other lines between hunks are ignored.
+///
+/// # Errors
+/// Returns an error for malformed hunk headers, hunk bodies whose line counts
+/// disagree with their headers, or patches without hunks.
+pub fn parse_files(patch: &str) -> Result<Vec<FilePatch>> {
+	let mut files: Vec<FilePatch> = Vec::new();
+	let mut remaining = (0u32, 0u32);
+	let mut last_side = None;
+	// Patch line of the current hunk's header, for errors.
+	let mut header_line = 0;
+	for (index, raw) in patch.lines().enumerate() {
+		let line = raw.strip_suffix('\r').unwrap_or(raw);
+		if remaining == (0, 0) {
+			if let Some(path) = line.strip_prefix("--- ") {
+				files.push(FilePatch { old_path: header_path(path), ..FilePatch::default() });
+				continue;
+			}
+			if let Some(path) = line.strip_prefix("+++ ")
+				&& let Some(file) = files.last_mut()
+				&& file.hunks.is_empty()
+			{
+				file.new_path = header_path(path);
+				continue;
+			}
+			if line.starts_with("@@") {
+				let (old_start, old_lines, new_start, new_lines) =
+					parse_header(line).ok_or_else(|| {
+						Error::from_reason(format!("Invalid hunk header on line {}: {line}", index + 1))
+					})?;
+				remaining = (old_lines, new_lines);
+				header_line = index + 1;
+				if files.is_empty() {
+					files.push(FilePatch::default());
+				}
+				if let Some(file) = files.last_mut() {
+					file.hunks.push(Hunk {
+						old_start,
+						old_lines,
+						new_start,
+						new_lines,
+						..Hunk::default()
+					});
+				}
+				last_side = None;
+				continue;
+			}
+			if line.starts_with('\\')
+				&& let Some(hunk) = files.last_mut().and_then(|file| file.hunks.last_mut())
+			{
+				mark_no_eol(hunk, last_side);
+			}
+			continue;
+		}
+		let Some(hunk) = files.last_mut().and_then(|file| file.hunks.last_mut()) else {
+			continue;
+		};
+		let marker = line.chars().next();
+		let text = marker.map_or("", |ch| &line[ch.len_utf8()..]).to_string();
+		match marker {
+			Some(' ') | None => {
+				remaining.0 = remaining.0.saturating_sub(1);
+				remaining.1 = remaining.1.saturating_sub(1);
+				hunk.lines.push(HunkLine::Context(text));
+				last_side = Some(' ');
+			},
+			Some('-') => {
+				remaining.0 = remaining.0.saturating_sub(1);
+				hunk.lines.push(HunkLine::Remove(text));
+				last_side = Some('-');
+			},
+			Some('+') => {
+				remaining.1 = remaining.1.saturating_sub(1);
+				hunk.lines.push(HunkLine::Add(text));
+				last_side = Some('+');
+			},
+			Some('\\') => mark_no_eol(hunk, last_side),
+			_ => {
+				return Err(Error::from_reason(format!(
+					"Hunk at line {header_line} is shorter than its header"
+				)));
+			},
+		}
+	}
+	if remaining != (0, 0) {
+		return Err(Error::from_reason("Patch ends in the middle of a hunk"));
+	}
+	if files.iter().all(|file| file.hunks.is_empty()) {
+		return Err(Error::from_reason("Patch contains no hunks"));
+	}
+	Ok(files)
+}
+
+/// Parse the hunks of a single-file unified diff, ignoring file headers.
+///
+/// # Errors
+/// Returns an error for malformed hunk headers, hunk bodies whose line counts
+/// disagree with their headers, patches without hunks, or patches touching
+/// more than one file.
+pub fn parse_hunks(patch: &str) -> Result<Vec<Hunk>> {
+	let mut files = parse_files(patch)?;
+	if files.len() > 1 {
+		return Err(Error::from_reason("Patch touches more than one file"));
+	}
+	Ok(files.pop().map_or_default(|file| file.hunks))
+}
+
+/// Apply a `\ No newline at end of file` marker to the side of the line
+/// before it.
+const fn mark_no_eol(hunk: &mut Hunk, last_side: Option<char>) {
+	match last_side {
+		Some('-') => hunk.old_no_eol = true,
+		Some('+') => hunk.new_no_eol = true,
+		Some(' ') => {
+			hunk.old_no_eol = true;
+			hunk.new_no_eol = true;
+		},
+		_ => {},
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Matching
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Where and how a hunk's old side matched.
+struct Placement {
+	/// Index of the first matched line.
+	start:      usize,
+	/// Fuzz level that matched.
+	fuzz:       usize,
+	/// Context lines dropped from the front of the hunk.
+	skip_front: usize,
+	/// Context lines dropped from the back of the hunk.
+	skip_back:  usize,
+	/// Matched line indices that needed whitespace tolerance.
+	whitespace: Vec<usize>,
+}
+
+/// Compare `pattern` against `lines` at `start`, returning the positions that
+/// only matched with whitespace ignored, or `None` on mismatch.
+fn match_at(
+	lines: &[String],
+	start: usize,
+	pattern: &[&str],
+	tolerant: bool,
+) -> Option<Vec<usize>> {
+	let window = lines.get(start..start + pattern.len())?;
+	let mut loose = Vec::new();
+	for (offset, (have, want)) in window.iter().zip(pattern).enumerate() {
+		if have == want {
+			continue;
+		}
+		if tolerant && have.trim() == want.trim() {
+			loose.push(start + offset);
+			continue;
+		}
+		return None;
+	}
+	Some(loose)
+}
+
+/// Candidate start positions within `[floor, last]`, nearest to `expected`
+/// first and at most `max_offset` away.
+fn positions(
+	expected: usize,
+	floor: usize,
+	last: usize,
+	max_offset: usize,
+) -> impl Iterator<Item = usize> {
+	let reach = expected
+		.abs_diff(floor)
+		.max(expected.abs_diff(last))
+		.min(max_offset);
+	(0..=reach).flat_map(move |distance| {
+		let after = expected
+			.checked_add(distance)
+			.filter(|pos| (floor..=last).contains(pos));
+		let before = expected
+			.checked_sub(distance)
+			.filter(|pos| distance > 0 && (floor..=last).contains(pos));
+		after.into_iter().chain(before)
+	})
+}
+
+fn place(
+	hunk: &Hunk,
+	lines: &[String],
+	expected: usize,
+	floor: usize,
+	options: &Settings,
+) -> Option<Placement> {
+	let old_side = hunk.old_side();
+	let (leading, trailing) = hunk.context_edges();
+	let modes: &[bool] = if options.tolerant {
+		&[false, true]
+	} else {
+		&[false]
+	};
+	for &tolerant in modes {
+		for fuzz in 0..=options.fuzz {
+			let skip_front = fuzz.min(leading);
+			let skip_back = fuzz.min(trailing);
+			if fuzz > 0 && skip_front == 0 && skip_back == 0 {
+				break;
+			}
+			let pattern = &old_side[skip_front..old_side.len() - skip_back];
+			// A hunk stripped of every old line would match anywhere.
+			if pattern.is_empty() && !old_side.is_empty() {
+				break;
+			}
+			if pattern.len() > lines.len().saturating_sub(floor) {
+				continue;
+			}
+			let last = lines.len() - pattern.len();
+			let target = expected + skip_front;
+			for start in positions(target, floor, last, options.max_offset) {
+				if let Some(whitespace) = match_at(lines, start, pattern, tolerant) {
+					return Some(Placement { start, fuzz, skip_front, skip_back, whitespace });
+				}
+			}
+		}
+	}
+	None
+}
+
+/// Describe why `hunk` matched nowhere, comparing its old side with the
+/// candidate position that has the most equal lines.
+fn conflict(
+	hunk: &Hunk,
+	lines: &[String],
+	expected: usize,
+	floor: usize,
+	options: &Settings,
+) -> PatchConflict {
+	let old_side = hunk.old_side();
+	let same =
+		|have: &String, want: &&str| have == want || (options.tolerant && have.trim() == want.trim());
+	let last = lines.len().saturating_sub(old_side.len()).max(floor);
+	let target = expected.clamp(floor, last);
+	let mut best = (target, 0);
+	for start in positions(target, floor, last, options.max_offset) {
+		let window = lines.get(start..).unwrap_or_default();
+		let score = window
+			.iter()
+			.zip(&old_side)
+			.filter(|(have, want)| same(have, want))
+			.count();
+		if score > best.1 {
+			best = (start, score);
+		}
+	}
+	let start = best.0;
+	let actual: Vec<String> = lines
+		.iter()
+		.skip(start)
+		.take(old_side.len())
+		.cloned()
+		.collect();
+	let mismatch = actual
+		.iter()
+		.zip(&old_side)
+		.position(|(have, want)| !same(have, want))
+		.unwrap_or(actual.len());
+	PatchConflict {
+		expected: old_side.iter().map(ToString::to_string).collect(),
+		actual,
+		start_line: clamp_u32(start + 1),
+		mismatch_line: clamp_u32(start + mismatch + 1),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Applying
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Matching settings for `apply_hunks`.
+pub struct Settings {
+	/// Context lines that may be dropped from each end of a hunk.
+	pub fuzz:       usize,
+	/// Retry with whitespace-insensitive line comparison.
+	pub tolerant:   bool,
+	/// Furthest a hunk may move from its expected position.
+	pub max_offset: usize,
+}
+
+fn clamp_u32(value: usize) -> u32 {
+	u32::try_from(value).unwrap_or(u32::MAX)
+}
+
+/// Apply parsed hunks to `content`.
+pub fn apply_hunks(content: &str, hunks: &[Hunk], settings: &Settings) -> ApplyPatchResult {
+	let eol = eol::count_eols(content).dominant().unwrap_or(eol::Eol::Lf);
+	let mut lines: Vec<String> = content
+		.split('\n')
+		.map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+		.collect();
+	let mut final_newline = content.is_empty() || content.ends_with('\n');
+	if final_newline || content.is_empty() {
+		lines.pop();
+	}
+
+	// Replacements in original coordinates, applied back to front at the end.
+	let mut edits: Vec<(usize, usize, Vec<String>)> = Vec::new();
+	let mut results = Vec::with_capacity(hunks.len());
+	let mut drift: isize = 0;
+	let mut floor = 0;
+	for hunk in hunks {
+		// Headers of pure insertions name the line before the insertion point.
+		let header_index = if hunk.old_lines == 0 {
+			hunk.old_start as usize
+		} else {
+			(hunk.old_start as usize).saturating_sub(1)
+		};
+		let expected = header_index.saturating_add_signed(drift);
+		let Some(placed) = place(hunk, &lines, expected, floor, settings) else {
+			results.push(PatchHunkResult {
+				old_start:        hunk.old_start,
+				applied:          false,
+				start_line:       None,
+				end_line:         None,
+				offset:           0,
+				fuzz:             0,
+				whitespace_lines: Vec::new(),
+				conflict:         Some(conflict(hunk, &lines, expected, floor, settings)),
+			});
+			continue;
+		};
+		let start = placed.start;
+		// Context keeps the file's text, so whitespace-tolerant matches and
+		// dropped context leave those lines as they are.
+		let mut replacement = Vec::new();
+		let mut end = start;
+		for line in &hunk.lines[placed.skip_front..hunk.lines.len() - placed.skip_back] {
+			match line {
+				HunkLine::Context(_) => {
+					replacement.push(lines[end].clone());
+					end += 1;
+				},
+				HunkLine::Remove(_) => end += 1,
+				HunkLine::Add(text) => replacement.push(text.clone()),
+			}
+		}
+		let matched = end - start;
+		let offset = start as isize - (header_index + placed.skip_front) as isize;
+		drift = offset;
+		floor = end;
+		if end == lines.len() && placed.skip_back == 0 && (hunk.old_no_eol || hunk.new_no_eol) {
+			final_newline = !hunk.new_no_eol;
+		}
+		results.push(PatchHunkResult {
+			old_start:        hunk.old_start,
+			applied:          true,
+			start_line:       Some(clamp_u32(if matched == 0 { start } else { start + 1 })),
+			end_line:         (matched > 0).then(|| clamp_u32(end)),
+			offset:           i32::try_from(offset).unwrap_or(i32::MAX),
+			fuzz:             clamp_u32(placed.fuzz),
+			whitespace_lines: placed
+				.whitespace
+				.iter()
+				.map(|&line| clamp_u32(line + 1))
+				.collect(),
+			conflict:         None,
+		});
+		edits.push((start, end, replacement));
+	}
+
+	for (start, end, replacement) in edits.into_iter().rev() {
+		lines.splice(start..end, replacement);
+	}
+	let mut text = lines.join(eol.as_str());
+	if final_newline && !lines.is_empty() {
+		text.push_str(eol.as_str());
+	}
+	let applied = results.iter().all(|hunk| hunk.applied);
+	let exact = applied
+		&& results
+			.iter()
+			.all(|hunk| hunk.offset == 0 && hunk.fuzz == 0 && hunk.whitespace_lines.is_empty());
+	ApplyPatchResult { text, applied, exact, hunks: results }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Apply a single-file unified diff to text.
+///
+/// # Arguments
+/// - `content`: Text to patch.
+/// - `patch`: Unified diff; file headers are optional.
+/// - `options`: Fuzz, whitespace tolerance, and maximum drift.
+///
+/// # Returns
+/// The patched text and how each hunk matched. Hunks that do not match are
+/// skipped and reported with `applied: false` and a `conflict`.
+///
+/// # Errors
+/// Returns an error if the patch is malformed or touches several files.
+#[napi(js_name = "applyPatch")]
+pub fn apply_patch(
+	content: String,
+	patch: String,
+	options: Option<ApplyPatchOptions>,
+) -> Result<ApplyPatchResult> {
+	let (fuzz, tolerant, max_offset) = match options {
+		Some(opts) => (opts.fuzz, opts.tolerant, opts.max_offset),
+		None => (None, None, None),
+	};
+	let settings = Settings {
+		fuzz:       fuzz.unwrap_or(0) as usize,
+		tolerant:   tolerant.unwrap_or(false),
+		max_offset: max_offset.map_or(usize::MAX, |max| max as usize),
+	};
+	let hunks = parse_hunks(&patch)?;
+	Ok(apply_hunks(&content, &hunks, &settings))
+}
+
+/// Parse a unified diff into per-file hunks.
+///
+/// # Arguments
+/// - `patch`: Unified diff touching one or more files; file headers are
+///   optional.
+///
+/// # Returns
+/// One entry per file in patch order, with hunks in the same shape as
+/// `diffLines`.
+///
+/// # Errors
+/// Returns an error if a hunk header is malformed, a hunk body disagrees with
+/// its header, or the patch has no hunks.
+#[napi(js_name = "parsePatch")]
+pub fn parse_patch(patch: String) -> Result<Vec<PatchFile>> {
+	Ok(parse_files(&patch)?
+		.into_iter()
+		.map(|file| PatchFile {
+			old_no_newline_at_end: file.hunks.iter().any(|hunk| hunk.old_no_eol),
+			new_no_newline_at_end: file.hunks.iter().any(|hunk| hunk.new_no_eol),
+			hunks:                 file.hunks.iter().map(Hunk::to_diff_hunk).collect(),
+			old_path:              file.old_path,
+			new_path:              file.new_path,
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const FILE: &str = "a\nb\nc\nd\ne\nf\ng\n";
+
+	fn apply(content: &str, patch: &str, fuzz: usize, tolerant: bool) -> ApplyPatchResult {
+		let settings = Settings { fuzz, tolerant, max_offset: usize::MAX };
+		apply_hunks(content, &parse_hunks(patch).unwrap(), &settings)
+	}
+
+	#[test]
+	fn test_apply_exact() {
+		let result = apply(FILE, "@@ -3,3 +3,3 @@\n c\n-d\n+D\n e\n", 0, false);
+		assert!(result.applied && result.exact);
+		assert_eq!(result.text, "a\nb\nc\nD\ne\nf\ng\n");
+		assert_eq!(result.hunks[0].start_line, Some(3));
+		assert_eq!(result.hunks[0].end_line, Some(5));
+	}
+
+	#[test]
+	fn test_apply_drift_carries_to_later_hunks() {
+		let content = format!("x\ny\n{FILE}");
+		let patch = "@@ -3,3 +3,3 @@\n c\n-d\n+D\n e\n@@ -6,2 +6,2 @@\n f\n-g\n+G\n";
+		let result = apply(&content, patch, 0, false);
+		assert!(result.applied && !result.exact);
+		assert_eq!(result.text, "x\ny\na\nb\nc\nD\ne\nf\nG\n");
+		let offsets: Vec<_> = result
+			.hunks
+			.iter()
+			.map(|hunk| (hunk.offset, hunk.start_line))
+			.collect();
+		assert_eq!(offsets, [(2, Some(5)), (2, Some(8))]);
+	}
+
+	#[test]
+	fn test_apply_fuzz_drops_mismatched_context() {
+		let patch = "@@ -3,3 +3,3 @@\n C\n-d\n+D\n e\n";
+		let result = apply(FILE, patch, 0, false);
+		assert!(!result.applied);
+		assert_eq!(result.text, FILE);
+
+		let result = apply(FILE, patch, 1, false);
+		assert!(result.applied && !result.exact);
+		assert_eq!(result.text, "a\nb\nc\nD\ne\nf\ng\n");
+		assert_eq!(result.hunks[0].fuzz, 1);
+		assert_eq!(result.hunks[0].offset, 0);
+	}
+
+	#[test]
+	fn test_apply_tolerant_keeps_file_whitespace() {
+		let content = "fn main() {\n\tlet x = 1;  \n\tprint(x);\n}\n";
+		let patch =
+			"@@ -1,3 +1,3 @@\n fn main() {\n-    let x = 1;\n+    let x = 2;\n     print(x);\n";
+		assert!(!apply(content, patch, 0, false).applied);
+
+		let result = apply(content, patch, 0, true);
+		assert!(result.applied && !result.exact);
+		// Context keeps the file's indentation; added lines are taken as written.
+		assert_eq!(result.text, "fn main() {\n    let x = 2;\n\tprint(x);\n}\n");
+		assert_eq!(result.hunks[0].whitespace_lines, [2, 3]);
+	}
+
+	#[test]
+	fn test_apply_reports_conflict_and_applies_the_rest() {
+		let patch = "@@ -2,3 +2,3 @@\n b\n-X\n+Y\n d\n@@ -6,2 +6,2 @@\n f\n-g\n+G\n";
+		let result = apply(FILE, patch, 0, false);
+		assert!(!result.applied);
+		assert_eq!(result.text, "a\nb\nc\nd\ne\nf\nG\n");
+		let conflict = result.hunks[0].conflict.as_ref().unwrap();
+		assert_eq!(conflict.expected, ["b", "X", "d"]);
+		assert_eq!(conflict.actual, ["b", "c", "d"]);
+		assert_eq!(conflict.start_line, 2);
+		assert_eq!(conflict.mismatch_line, 3);
+		assert!(result.hunks[1].applied);
+	}
+
+	#[test]
+	fn test_apply_keeps_crlf_and_missing_final_newline() {
+		let result =
+			apply("a\r\nb\r\nc", "@@ -3 +3 @@\n-c\n\\ No newline at end of file\n+C\n", 0, false);
+		assert!(result.applied);
+		assert_eq!(result.text, "a\r\nb\r\nC\r\n");
+	}
+
+	#[test]
+	fn test_parse_error_names_the_hunk_line() {
+		let patch =
+			"--- a/x\n+++ b/x\n@@ -1,1 +1,1 @@\n-a\n+b\n@@ -5,3 +5,3 @@\n e\n@@ -9 +9 @@\n-i\n+I\n";
+		let err = parse_files(patch).unwrap_err();
+		assert_eq!(err.reason, "Hunk at line 6 is shorter than its header");
+	}
+
+	#[test]
+	fn test_parse_files_splits_by_header() {
+		let patch =
+			"--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- /dev/null\n+++ b/y\n@@ -0,0 +1 @@\n+new\n";
+		let files = parse_files(patch).unwrap();
+		let paths: Vec<_> = files
+			.iter()
+			.map(|file| (file.old_path.as_deref(), file.new_path.as_deref()))
+			.collect();
+		assert_eq!(paths, [(Some("a/x"), Some("b/x")), (None, Some("b/y"))]);
+		assert_eq!(files[1].hunks[0].lines, [HunkLine::Add("new".to_string())]);
+	}
+}
@@ -2,7 +2,10 @@
 //!
 //! # Overview
 //! Provides a stateful PTY session that supports streaming output and stdin
-//! passthrough while a command is running.
+//! passthrough while a command is running. [`PtySession`] runs a shell command
+//! line and streams decoded text; [`Pty`] spawns a program with an argument
+//! vector and streams raw output bytes, for TUI programs that need exact
+//! terminal output.
 
 use std::{
 	collections::HashMap,
@@ -41,6 +44,24 @@ pub struct PtyStartOptions<'env> {
 	pub rows:       Option<u16>,
 }
 
+/// Options for spawning a program with [`Pty`].
+#[napi(object)]
+pub struct PtySpawnOptions<'env> {
+	/// PTY column count.
+	pub cols:       Option<u16>,
+	/// PTY row count.
+	pub rows:       Option<u16>,
+	/// Working directory for the program.
+	pub cwd:        Option<String>,
+	/// Environment variables for the program.
+	pub env:        Option<HashMap<String, String>>,
+	/// Timeout in milliseconds before cancelling.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+}
+
 /// Result of a PTY command run.
 #[napi(object)]
 pub struct PtyRunResult {
@@ -52,9 +73,17 @@ pub struct PtyRunResult {
 	pub timed_out: bool,
 }
 
+#[derive(Clone)]
+enum PtyProgram {
+	/// Command line run through `sh -lc`.
+	Shell(String),
+	/// Program executed directly with an argument vector.
+	Exec { cmd: String, args: Vec<String> },
+}
+
 #[derive(Clone)]
 struct PtyRunConfig {
-	command: String,
+	program: PtyProgram,
 	cwd:     Option<String>,
 	env:     Option<HashMap<String, String>>,
 	cols:    u16,
@@ -63,11 +92,18 @@ struct PtyRunConfig {
 
 enum ReaderEvent {
 	Chunk(String),
+	Bytes(Vec<u8>),
 	Done,
 }
 
+/// Where PTY output goes: decoded text chunks or raw bytes.
+enum OutputSink {
+	Text(Option<ThreadsafeFunction<String>>),
+	Bytes(Option<ThreadsafeFunction<Uint8Array>>),
+}
+
 enum ControlMessage {
-	Input(String),
+	Input(Vec<u8>),
 	Resize { cols: u16, rows: u16 },
 	Kill,
 }
@@ -106,55 +142,26 @@ impl PtySession {
 		>,
 	) -> Result<PromiseRaw<'env, PtyRunResult>> {
 		let run_config = PtyRunConfig {
-			command: options.command,
+			program: PtyProgram::Shell(options.command),
 			cwd:     options.cwd,
 			env:     options.env,
 			cols:    options.cols.unwrap_or(120).clamp(20, 400),
 			rows:    options.rows.unwrap_or(40).clamp(5, 200),
 		};
 		let ct = task::CancelToken::new(options.timeout_ms, options.signal);
-		let core = Arc::clone(&self.core);
-
-		// Register control channel synchronously so write()/kill() work immediately.
-		let (control_tx, control_rx) = mpsc::channel::<ControlMessage>();
-		{
-			let mut guard = core
-				.lock()
-				.map_err(|_| Error::from_reason("PTY session lock poisoned"))?;
-			if guard.is_some() {
-				return Err(Error::from_reason("PTY session already running"));
-			}
-			*guard = Some(PtySessionCore { control_tx });
-		}
-		task::future(env, "pty.start", async move {
-			let run_result =
-				tokio::task::spawn_blocking(move || run_pty_sync(run_config, on_chunk, control_rx, ct))
-					.await;
-
-			// Always clear core regardless of result
-			let mut guard = core
-				.lock()
-				.map_err(|_| Error::from_reason("PTY session lock poisoned"))?;
-			*guard = None;
-			drop(guard);
-
-			match run_result {
-				Ok(inner) => inner,
-				Err(err) => Err(Error::from_reason(format!("PTY execution task failed: {err}"))),
-			}
-		})
+		start_run(env, &self.core, "pty.start", run_config, OutputSink::Text(on_chunk), ct)
 	}
 
 	/// Write raw input bytes to PTY stdin.
 	#[napi]
 	pub fn write(&self, data: String) -> Result<()> {
-		self.send_control(ControlMessage::Input(data))
+		send_control(&self.core, ControlMessage::Input(data.into_bytes()))
 	}
 
 	/// Resize the active PTY.
 	#[napi]
 	pub fn resize(&self, cols: u16, rows: u16) -> Result<()> {
-		self.send_control(ControlMessage::Resize {
+		send_control(&self.core, ControlMessage::Resize {
 			cols: cols.clamp(20, 400),
 			rows: rows.clamp(5, 200),
 		})
@@ -163,29 +170,140 @@ impl PtySession {
 	/// Force-kill the active PTY command.
 	#[napi]
 	pub fn kill(&self) -> Result<()> {
-		self.send_control(ControlMessage::Kill)
+		send_control(&self.core, ControlMessage::Kill)
 	}
 }
 
-impl PtySession {
-	fn send_control(&self, message: ControlMessage) -> Result<()> {
-		let guard = self
-			.core
+/// Pseudo-terminal running a program with an argument vector and streaming
+/// raw output bytes.
+#[napi]
+pub struct Pty {
+	core: Arc<Mutex<Option<PtySessionCore>>>,
+}
+
+impl Default for Pty {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[napi]
+impl Pty {
+	#[napi(constructor)]
+	pub fn new() -> Self {
+		Self { core: Arc::new(Mutex::new(None)) }
+	}
+
+	/// Spawn `cmd` with `args` in a new PTY and stream output bytes via
+	/// callback until the program exits.
+	#[napi]
+	pub fn spawn<'env>(
+		&self,
+		env: &'env Env,
+		cmd: String,
+		args: Option<Vec<String>>,
+		options: Option<PtySpawnOptions<'env>>,
+		#[napi(ts_arg_type = "((data: Uint8Array) => void) | undefined | null")] on_data: Option<
+			ThreadsafeFunction<Uint8Array>,
+		>,
+	) -> Result<PromiseRaw<'env, PtyRunResult>> {
+		let (cols, rows, cwd, vars, timeout_ms, signal) = match options {
+			Some(opts) => (opts.cols, opts.rows, opts.cwd, opts.env, opts.timeout_ms, opts.signal),
+			None => (None, None, None, None, None, None),
+		};
+		let run_config = PtyRunConfig {
+			program: PtyProgram::Exec { cmd, args: args.unwrap_or_default() },
+			cwd,
+			env: vars,
+			cols: cols.unwrap_or(120).clamp(20, 400),
+			rows: rows.unwrap_or(40).clamp(5, 200),
+		};
+		let ct = task::CancelToken::new(timeout_ms, signal);
+		start_run(env, &self.core, "pty.spawn", run_config, OutputSink::Bytes(on_data), ct)
+	}
+
+	/// Write input to the program; strings are sent as UTF-8.
+	#[napi]
+	pub fn write(&self, data: Either<String, Uint8Array>) -> Result<()> {
+		let bytes = match data {
+			Either::A(text) => text.into_bytes(),
+			Either::B(bytes) => bytes.to_vec(),
+		};
+		send_control(&self.core, ControlMessage::Input(bytes))
+	}
+
+	/// Resize the PTY.
+	#[napi]
+	pub fn resize(&self, cols: u16, rows: u16) -> Result<()> {
+		send_control(&self.core, ControlMessage::Resize {
+			cols: cols.clamp(20, 400),
+			rows: rows.clamp(5, 200),
+		})
+	}
+
+	/// Force-kill the running program.
+	#[napi]
+	pub fn kill(&self) -> Result<()> {
+		send_control(&self.core, ControlMessage::Kill)
+	}
+}
+
+/// Register a run on `core` and drive it on the blocking pool.
+fn start_run<'env>(
+	env: &'env Env,
+	core: &Arc<Mutex<Option<PtySessionCore>>>,
+	tag: &'static str,
+	run_config: PtyRunConfig,
+	sink: OutputSink,
+	ct: task::CancelToken,
+) -> Result<PromiseRaw<'env, PtyRunResult>> {
+	let core = Arc::clone(core);
+
+	// Register control channel synchronously so write()/kill() work immediately.
+	let (control_tx, control_rx) = mpsc::channel::<ControlMessage>();
+	{
+		let mut guard = core
 			.lock()
 			.map_err(|_| Error::from_reason("PTY session lock poisoned"))?;
-		let core = guard
-			.as_ref()
-			.ok_or_else(|| Error::from_reason("PTY session is not running"))?;
-		core
-			.control_tx
-			.send(message)
-			.map_err(|_| Error::from_reason("PTY session is no longer available"))
+		if guard.is_some() {
+			return Err(Error::from_reason("PTY session already running"));
+		}
+		*guard = Some(PtySessionCore { control_tx });
 	}
+	task::future(env, tag, async move {
+		let run_result =
+			tokio::task::spawn_blocking(move || run_pty_sync(run_config, sink, control_rx, ct)).await;
+
+		// Always clear core regardless of result
+		let mut guard = core
+			.lock()
+			.map_err(|_| Error::from_reason("PTY session lock poisoned"))?;
+		*guard = None;
+		drop(guard);
+
+		match run_result {
+			Ok(inner) => inner,
+			Err(err) => Err(Error::from_reason(format!("PTY execution task failed: {err}"))),
+		}
+	})
+}
+
+fn send_control(core: &Mutex<Option<PtySessionCore>>, message: ControlMessage) -> Result<()> {
+	let guard = core
+		.lock()
+		.map_err(|_| Error::from_reason("PTY session lock poisoned"))?;
+	let core = guard
+		.as_ref()
+		.ok_or_else(|| Error::from_reason("PTY session is not running"))?;
+	core
+		.control_tx
+		.send(message)
+		.map_err(|_| Error::from_reason("PTY session is no longer available"))
 }
 
 fn run_pty_sync(
 	config: PtyRunConfig,
-	on_chunk: Option<ThreadsafeFunction<String>>,
+	sink: OutputSink,
 	control_rx: mpsc::Receiver<ControlMessage>,
 	ct: task::CancelToken,
 ) -> Result<PtyRunResult> {
@@ -199,9 +317,19 @@ fn run_pty_sync(
 		})
 		.map_err(|err| Error::from_reason(format!("Failed to open PTY: {err}")))?;
 
-	let mut cmd = CommandBuilder::new("sh");
-	cmd.arg("-lc");
-	cmd.arg(&config.command);
+	let mut cmd = match &config.program {
+		PtyProgram::Shell(command) => {
+			let mut cmd = CommandBuilder::new("sh");
+			cmd.arg("-lc");
+			cmd.arg(command);
+			cmd
+		},
+		PtyProgram::Exec { cmd: program, args } => {
+			let mut cmd = CommandBuilder::new(program);
+			cmd.args(args);
+			cmd
+		},
+	};
 	if let Some(cwd) = config.cwd.as_ref() {
 		cmd.cwd(cwd);
 	}
@@ -226,62 +354,12 @@ fn run_pty_sync(
 		.map_err(|err| Error::from_reason(format!("Failed to create PTY reader: {err}")))?;
 
 	let (reader_tx, reader_rx) = mpsc::channel::<ReaderEvent>();
+	let raw_output = matches!(sink, OutputSink::Bytes(_));
 	let reader_thread = std::thread::spawn(move || {
-		const REPLACEMENT: &str = "\u{FFFD}";
-		const BUF: usize = 4096;
-		let mut buf = [0u8; BUF + 4];
-		let mut it = 0;
-		loop {
-			match reader.read(&mut buf[it..BUF]) {
-				Ok(0) => {
-					break;
-				},
-				Ok(n) => {
-					it += n;
-					while it > 0 {
-						let pending = &buf[..it];
-						match str::from_utf8(pending) {
-							Ok(text) => {
-								let _ = reader_tx.send(ReaderEvent::Chunk(text.to_string()));
-								it = 0;
-								break;
-							},
-							Err(err) => {
-								let valid_up_to = err.valid_up_to();
-								if valid_up_to > 0 {
-									// SAFETY: [..valid_up_to] is guaranteed valid UTF-8 by valid_up_to().
-									let text = unsafe { str::from_utf8_unchecked(&pending[..valid_up_to]) };
-									let _ = reader_tx.send(ReaderEvent::Chunk(text.to_string()));
-									buf.copy_within(valid_up_to..it, 0);
-									it -= valid_up_to;
-								}
-								match err.error_len() {
-									Some(invalid_len) => {
-										let _ = reader_tx.send(ReaderEvent::Chunk(REPLACEMENT.to_string()));
-										buf.copy_within(invalid_len..it, 0);
-										it -= invalid_len;
-									},
-									None => {
-										break;
-									},
-								}
-							},
-						}
-					}
-				},
-				Err(_) => {
-					break;
-				},
-			}
-		}
-		for chunk in buf[..it].utf8_chunks() {
-			let valid = chunk.valid();
-			if !valid.is_empty() {
-				let _ = reader_tx.send(ReaderEvent::Chunk(valid.to_string()));
-			}
-			if !chunk.invalid().is_empty() {
-				let _ = reader_tx.send(ReaderEvent::Chunk(REPLACEMENT.to_string()));
-			}
+		if raw_output {
+			read_bytes(reader.as_mut(), &reader_tx);
+		} else {
+			read_text(reader.as_mut(), &reader_tx);
 		}
 		let _ = reader_tx.send(ReaderEvent::Done);
 	});
@@ -302,7 +380,7 @@ fn run_pty_sync(
 		loop {
 			match control_rx.try_recv() {
 				Ok(ControlMessage::Input(data)) => {
-					let _ = writer.write_all(data.as_bytes());
+					let _ = writer.write_all(&data);
 					let _ = writer.flush();
 				},
 				Ok(ControlMessage::Resize { cols, rows }) => {
@@ -319,7 +397,17 @@ fn run_pty_sync(
 
 		loop {
 			match reader_rx.try_recv() {
-				Ok(ReaderEvent::Chunk(chunk)) => emit_chunk(&chunk, on_chunk.as_ref()),
+				Ok(ReaderEvent::Chunk(chunk)) => {
+					if let OutputSink::Text(Some(callback)) = &sink {
+						emit_chunk(&chunk, Some(callback));
+					}
+				},
+				Ok(ReaderEvent::Bytes(bytes)) => {
+					if let OutputSink::Bytes(Some(callback)) = &sink {
+						let data = Uint8Array::from(bytes);
+						callback.call(Ok(data), ThreadsafeFunctionCallMode::NonBlocking);
+					}
+				},
 				Ok(ReaderEvent::Done) => {
 					reader_done = true;
 					break;
@@ -362,3 +450,76 @@ fn emit_chunk(text: &str, callback: Option<&ThreadsafeFunction<String>>) {
 		callback.call(Ok(text.to_string()), ThreadsafeFunctionCallMode::NonBlocking);
 	}
 }
+
+/// Forward raw output bytes as they arrive.
+fn read_bytes(reader: &mut dyn Read, reader_tx: &mpsc::Sender<ReaderEvent>) {
+	let mut buf = [0u8; 4096];
+	loop {
+		match reader.read(&mut buf) {
+			Ok(0) | Err(_) => break,
+			Ok(n) => {
+				let _ = reader_tx.send(ReaderEvent::Bytes(buf[..n].to_vec()));
+			},
+		}
+	}
+}
+
+/// Forward output as UTF-8 text, holding back sequences split across reads.
+fn read_text(reader: &mut dyn Read, reader_tx: &mpsc::Sender<ReaderEvent>) {
+	const REPLACEMENT: &str = "\u{FFFD}";
+	const BUF: usize = 4096;
+	let mut buf = [0u8; BUF + 4];
+	let mut it = 0;
+	loop {
+		match reader.read(&mut buf[it..BUF]) {
+			Ok(0) => {
+				break;
+			},
+			Ok(n) => {
+				it += n;
+				while it > 0 {
+					let pending = &buf[..it];
+					match str::from_utf8(pending) {
+						Ok(text) => {
+							let _ = reader_tx.send(ReaderEvent::Chunk(text.to_string()));
+							it = 0;
+							break;
+						},
+						Err(err) => {
+							let valid_up_to = err.valid_up_to();
+							if valid_up_to > 0 {
+								// SAFETY: [..valid_up_to] is guaranteed valid UTF-8 by valid_up_to().
+								let text = unsafe { str::from_utf8_unchecked(&pending[..valid_up_to]) };
+								let _ = reader_tx.send(ReaderEvent::Chunk(text.to_string()));
+								buf.copy_within(valid_up_to..it, 0);
+								it -= valid_up_to;
+							}
+							match err.error_len() {
+								Some(invalid_len) => {
+									let _ = reader_tx.send(ReaderEvent::Chunk(REPLACEMENT.to_string()));
+									buf.copy_within(invalid_len..it, 0);
+									it -= invalid_len;
+								},
+								None => {
+									break;
+								},
+							}
+						},
+					}
+				}
+			},
+			Err(_) => {
+				break;
+			},
+		}
+	}
+	for chunk in buf[..it].utf8_chunks() {
+		let valid = chunk.valid();
+		if !valid.is_empty() {
+			let _ = reader_tx.send(ReaderEvent::Chunk(valid.to_string()));
+		}
+		if !chunk.invalid().is_empty() {
+			let _ = reader_tx.send(ReaderEvent::Chunk(REPLACEMENT.to_string()));
+		}
+	}
+}
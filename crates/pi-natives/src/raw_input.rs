@@ -0,0 +1,241 @@
+//! Raw terminal input read natively from stdin.
+//!
+//! # Overview
+//! `openRawInput` puts the controlling terminal in raw mode (the same flags
+//! Node's `setRawMode(true)` uses), reads stdin on a dedicated thread, and
+//! delivers each read as a chunk through a threadsafe callback. Every chunk
+//! is stamped the moment `read` returns, before it queues behind JS work, so
+//! escape-sequence timeout heuristics (a lone ESC versus the start of an
+//! Alt or CSI sequence) can compare real arrival times instead of the
+//! jittery times Node's TTY stream reports.
+//!
+//! `vmin` and `vtime` map to the termios fields of the same name: a read
+//! returns once `vmin` bytes are available or `vtime` tenths of a second pass
+//! between bytes. Reading stops when the signal aborts, the timeout elapses,
+//! or stdin reaches end of file; the previous terminal settings are restored
+//! and the promise resolves with a summary.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.openRawInput({ vtime: 1, signal }, chunk => decoder.feed(chunk.data, chunk.timestamp))
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use napi::{
+	bindgen_prelude::*,
+	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `openRawInput`.
+#[napi(object)]
+pub struct RawInputOptions<'env> {
+	/// Minimum bytes a read waits for (default: 1). Values above 1 can delay
+	/// cancellation until more input arrives.
+	pub vmin:       Option<u8>,
+	/// Inter-byte timeout in tenths of a second (default: 0, no timeout).
+	pub vtime:      Option<u8>,
+	/// Abort signal that stops reading.
+	pub signal:     Option<Unknown<'env>>,
+	/// Stop reading after this many milliseconds.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Bytes returned by one read of stdin.
+#[napi(object)]
+pub struct RawInputChunk {
+	/// Raw input bytes.
+	pub data:      Uint8Array,
+	/// Milliseconds since the Unix epoch, with microsecond precision, taken
+	/// when the read returned.
+	pub timestamp: f64,
+}
+
+/// Summary returned when reading stops.
+#[napi(object)]
+pub struct RawInputResult {
+	/// Number of chunks delivered.
+	pub chunks: u32,
+	/// Total bytes delivered.
+	pub bytes:  f64,
+	/// Whether reading stopped because stdin reached end of file.
+	pub eof:    bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Reading
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn now_ms() -> f64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Terminal settings to restore when reading stops, however it stops.
+#[cfg(unix)]
+struct RawModeGuard {
+	fd:       libc::c_int,
+	original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawModeGuard {
+	fn enter(fd: libc::c_int, vmin: u8, vtime: u8) -> Result<Self> {
+		// SAFETY: `isatty` accepts any descriptor.
+		if unsafe { libc::isatty(fd) } != 1 {
+			return Err(Error::from_reason("stdin is not a terminal"));
+		}
+		// SAFETY: termios is plain old data; tcgetattr initializes it on success.
+		let mut original: libc::termios = unsafe { std::mem::zeroed() };
+		// SAFETY: `fd` is an open terminal descriptor.
+		if unsafe { libc::tcgetattr(fd, &raw mut original) } != 0 {
+			return Err(Error::from_reason(format!(
+				"Failed to read terminal settings: {}",
+				std::io::Error::last_os_error()
+			)));
+		}
+		let mut raw = original;
+		raw.c_iflag &= !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+		raw.c_oflag |= libc::ONLCR;
+		raw.c_cflag |= libc::CS8;
+		raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+		raw.c_cc[libc::VMIN] = vmin;
+		raw.c_cc[libc::VTIME] = vtime;
+		// SAFETY: `fd` is valid and `raw` is a fully initialized termios.
+		if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw const raw) } != 0 {
+			return Err(Error::from_reason(format!(
+				"Failed to enter raw mode: {}",
+				std::io::Error::last_os_error()
+			)));
+		}
+		Ok(Self { fd, original })
+	}
+}
+
+#[cfg(unix)]
+impl Drop for RawModeGuard {
+	fn drop(&mut self) {
+		// SAFETY: restores the settings read in `enter` on the same descriptor.
+		unsafe { libc::tcsetattr(self.fd, libc::TCSANOW, &raw const self.original) };
+	}
+}
+
+#[cfg(unix)]
+fn read_loop(
+	vmin: u8,
+	vtime: u8,
+	ct: &task::CancelToken,
+	on_input: &ThreadsafeFunction<RawInputChunk>,
+) -> Result<RawInputResult> {
+	use std::io::ErrorKind;
+
+	let fd = libc::STDIN_FILENO;
+	let _guard = RawModeGuard::enter(fd, vmin, vtime)?;
+	let mut summary = RawInputResult { chunks: 0, bytes: 0.0, eof: false };
+	let mut buf = [0u8; 4096];
+	while !ct.aborted() {
+		// Wake up regularly so cancellation is noticed without input.
+		let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+		// SAFETY: `pollfd` is a single valid entry.
+		let ready = unsafe { libc::poll(&raw mut pollfd, 1, 50) };
+		if ready < 0 {
+			let err = std::io::Error::last_os_error();
+			if err.kind() == ErrorKind::Interrupted {
+				continue;
+			}
+			return Err(Error::from_reason(format!("Failed to poll stdin: {err}")));
+		}
+		if ready == 0 {
+			continue;
+		}
+		// SAFETY: `buf` is writable for its full length.
+		let n = unsafe { libc::read(fd, buf.as_mut_ptr().cast(), buf.len()) };
+		let timestamp = now_ms();
+		if n < 0 {
+			let err = std::io::Error::last_os_error();
+			if matches!(err.kind(), ErrorKind::Interrupted | ErrorKind::WouldBlock) {
+				continue;
+			}
+			return Err(Error::from_reason(format!("Failed to read stdin: {err}")));
+		}
+		if n == 0 {
+			// With `vmin` 0 an empty read is a timeout; a hangup is end of file.
+			if vmin > 0 || pollfd.revents & (libc::POLLHUP | libc::POLLERR) != 0 {
+				summary.eof = true;
+				break;
+			}
+			continue;
+		}
+		let n = n as usize;
+		summary.chunks += 1;
+		summary.bytes += n as f64;
+		on_input.call(
+			Ok(RawInputChunk { data: Uint8Array::from(buf[..n].to_vec()), timestamp }),
+			ThreadsafeFunctionCallMode::NonBlocking,
+		);
+	}
+	Ok(summary)
+}
+
+#[cfg(not(unix))]
+fn read_loop(
+	_vmin: u8,
+	_vtime: u8,
+	_ct: &task::CancelToken,
+	_on_input: &ThreadsafeFunction<RawInputChunk>,
+) -> Result<RawInputResult> {
+	Err(Error::from_reason("Raw input is only supported on Unix"))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Put the terminal in raw mode and stream stdin chunks with arrival times.
+///
+/// # Arguments
+/// - `options`: termios read batching (`vmin`, `vtime`) and cancellation.
+/// - `on_input`: Callback receiving each chunk as it is read.
+///
+/// # Returns
+/// A summary once the signal aborts, the timeout elapses, or stdin ends.
+///
+/// # Errors
+/// Returns an error if stdin is not a terminal or cannot be read.
+#[napi(js_name = "openRawInput")]
+pub fn open_raw_input<'env>(
+	env: &'env Env,
+	options: Option<RawInputOptions<'env>>,
+	#[napi(ts_arg_type = "(chunk: RawInputChunk) => void")] on_input: ThreadsafeFunction<
+		RawInputChunk,
+	>,
+) -> Result<PromiseRaw<'env, RawInputResult>> {
+	let (vmin, vtime, signal, timeout_ms) = match options {
+		Some(opts) => (opts.vmin, opts.vtime, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let (vmin, vtime) = (vmin.unwrap_or(1), vtime.unwrap_or(0));
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+	// A dedicated thread, since reads can block for as long as the user idles.
+	std::thread::Builder::new()
+		.name("pi-raw-input".to_string())
+		.spawn(move || {
+			let _ = done_tx.send(read_loop(vmin, vtime, &ct, &on_input));
+		})
+		.map_err(|err| Error::from_reason(format!("Failed to start input thread: {err}")))?;
+	task::future(env, "raw_input", async move {
+		done_rx
+			.await
+			.map_err(|_| Error::from_reason("Raw input thread exited unexpectedly"))?
+	})
+}
@@ -0,0 +1,242 @@
+//! Bounded file reads by line range.
+//!
+//! # Overview
+//! Streams a file line by line on the worker pool, skipping lines before the
+//! requested range without buffering them and stopping once the range or the
+//! byte budget is exhausted. Lines that are not valid UTF-8 are decoded with a
+//! configurable fallback instead of failing the whole read.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.readFileSlice("big.log", { startLine: 100, endLine: 200, maxBytes: 65536 })
+//! ```
+
+use std::{
+	fs::File,
+	io::{BufRead, BufReader},
+	path::PathBuf,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{task, text};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `readFileSlice`.
+#[napi(object)]
+pub struct ReadFileSliceOptions<'env> {
+	/// First line to return, 1-indexed (default: 1).
+	#[napi(js_name = "startLine")]
+	pub start_line:        Option<u32>,
+	/// Last line to return, 1-indexed and inclusive (default: end of file).
+	#[napi(js_name = "endLine")]
+	pub end_line:          Option<u32>,
+	/// Maximum bytes of line content to return (default: unlimited).
+	#[napi(js_name = "maxBytes")]
+	pub max_bytes:         Option<u32>,
+	/// Decoding for lines that are not valid UTF-8: "lossy" (default, U+FFFD
+	/// replacement) or "latin1".
+	#[napi(js_name = "encodingFallback")]
+	pub encoding_fallback: Option<String>,
+	/// Abort signal for cancelling the operation.
+	pub signal:            Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:        Option<u32>,
+}
+
+/// Result of `readFileSlice`.
+#[napi(object)]
+pub struct ReadFileSliceResult {
+	/// Lines without their terminators.
+	pub lines:         Vec<String>,
+	/// Visible terminal width of each line.
+	pub widths:        Vec<u32>,
+	/// 1-indexed number of the first returned line.
+	#[napi(js_name = "startLine")]
+	pub start_line:    u32,
+	/// Whether `maxBytes` cut the read short.
+	pub truncated:     bool,
+	/// Whether the file has lines after the last returned one.
+	#[napi(js_name = "hasMore")]
+	pub has_more:      bool,
+	/// Whether any line needed the encoding fallback.
+	#[napi(js_name = "fallbackUsed")]
+	pub fallback_used: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Reading
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy)]
+enum Fallback {
+	Lossy,
+	Latin1,
+}
+
+fn parse_fallback(name: Option<&str>) -> Result<Fallback> {
+	match name {
+		None | Some("lossy") => Ok(Fallback::Lossy),
+		Some("latin1" | "iso-8859-1") => Ok(Fallback::Latin1),
+		Some(other) => Err(Error::from_reason(format!("Unknown encoding fallback: {other}"))),
+	}
+}
+
+/// Decode a line, returning whether the fallback was needed.
+fn decode_line(bytes: Vec<u8>, fallback: Fallback) -> (String, bool) {
+	match String::from_utf8(bytes) {
+		Ok(line) => (line, false),
+		Err(err) => {
+			let bytes = err.into_bytes();
+			let line = match fallback {
+				Fallback::Lossy => String::from_utf8_lossy(&bytes).into_owned(),
+				Fallback::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+			};
+			(line, true)
+		},
+	}
+}
+
+fn strip_terminator(line: &mut Vec<u8>) {
+	if line.last() == Some(&b'\n') {
+		line.pop();
+		if line.last() == Some(&b'\r') {
+			line.pop();
+		}
+	}
+}
+
+/// Truncate `line` to at most `max` bytes without splitting a UTF-8 sequence.
+fn truncate_at_char_boundary(line: &mut Vec<u8>, max: usize) {
+	let mut cut = max.min(line.len());
+	while cut > 0 && cut < line.len() && (line[cut] & 0xc0) == 0x80 {
+		cut -= 1;
+	}
+	line.truncate(cut);
+}
+
+struct SliceConfig {
+	path:       PathBuf,
+	start_line: usize,
+	end_line:   usize,
+	max_bytes:  usize,
+	fallback:   Fallback,
+}
+
+fn read_file_slice_sync(
+	config: SliceConfig,
+	ct: &task::CancelToken,
+) -> Result<ReadFileSliceResult> {
+	let path = &config.path;
+	let file = File::open(path)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", path.display())))?;
+	let mut reader = BufReader::with_capacity(64 * 1024, file);
+	let read_error =
+		|err: std::io::Error| Error::from_reason(format!("Failed to read {}: {err}", path.display()));
+
+	let mut line_no = 0usize;
+	let mut buf = Vec::new();
+	// Skip to the requested start line without decoding.
+	while line_no + 1 < config.start_line {
+		buf.clear();
+		if line_no % 4096 == 0 {
+			ct.heartbeat()?;
+		}
+		if reader.read_until(b'\n', &mut buf).map_err(read_error)? == 0 {
+			break;
+		}
+		line_no += 1;
+	}
+
+	let mut result = ReadFileSliceResult {
+		lines:         Vec::new(),
+		widths:        Vec::new(),
+		start_line:    config.start_line.min(u32::MAX as usize) as u32,
+		truncated:     false,
+		has_more:      false,
+		fallback_used: false,
+	};
+	let mut budget = config.max_bytes;
+	while line_no < config.end_line {
+		if line_no % 4096 == 0 {
+			ct.heartbeat()?;
+		}
+		let mut line = Vec::new();
+		if reader.read_until(b'\n', &mut line).map_err(read_error)? == 0 {
+			return Ok(result);
+		}
+		line_no += 1;
+		strip_terminator(&mut line);
+		if line.len() > budget {
+			result.truncated = true;
+			// Always return something: a partial first line beats an empty read.
+			if !result.lines.is_empty() {
+				result.has_more = true;
+				return Ok(result);
+			}
+			truncate_at_char_boundary(&mut line, budget);
+		}
+		budget -= line.len();
+		let (line, used_fallback) = decode_line(line, config.fallback);
+		result.fallback_used |= used_fallback;
+		result.widths.push(text::visible_width(&line).min(u32::MAX as usize) as u32);
+		result.lines.push(line);
+		if result.truncated {
+			break;
+		}
+	}
+
+	result.has_more = result.truncated || !reader.fill_buf().map_err(read_error)?.is_empty();
+	Ok(result)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Read a line range of a file with a byte budget.
+///
+/// # Returns
+/// The requested lines, their visible widths, and truncation flags.
+///
+/// # Errors
+/// Returns an error for an invalid range or fallback, unreadable files, or
+/// cancellation.
+#[napi(js_name = "readFileSlice")]
+pub fn read_file_slice(
+	path: String,
+	options: Option<ReadFileSliceOptions<'_>>,
+) -> Result<task::Async<ReadFileSliceResult>> {
+	let (start_line, end_line, max_bytes, fallback, signal, timeout_ms) = match options {
+		Some(opts) => (
+			opts.start_line,
+			opts.end_line,
+			opts.max_bytes,
+			opts.encoding_fallback,
+			opts.signal,
+			opts.timeout_ms,
+		),
+		None => (None, None, None, None, None, None),
+	};
+	let start_line = start_line.unwrap_or(1).max(1) as usize;
+	let end_line = end_line.map_or(usize::MAX, |line| line as usize);
+	if end_line < start_line {
+		return Err(Error::from_reason(format!(
+			"endLine ({end_line}) must not be before startLine ({start_line})"
+		)));
+	}
+	let config = SliceConfig {
+		path: PathBuf::from(path),
+		start_line,
+		end_line,
+		max_bytes: max_bytes.map_or(usize::MAX, |max| max as usize),
+		fallback: parse_fallback(fallback.as_deref())?,
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("read_file_slice", ct, move |ct| read_file_slice_sync(config, &ct)))
+}
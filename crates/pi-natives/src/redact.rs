@@ -0,0 +1,296 @@
+//! Secret detection and redaction.
+//!
+//! # Overview
+//! Scans text for credentials before it leaves the process (tool output,
+//! prompts, logs) and replaces them with a marker. Built-in rules cover
+//! private key blocks, cloud and developer-service API keys, JWTs, bearer
+//! tokens, credentials embedded in URLs, and `key = value` style assignments;
+//! extra rules can be passed as regexes. With `entropyCheck`, long
+//! random-looking tokens that no rule recognizes are redacted as well.
+//!
+//! Rules with a capture group redact only the group (the value, not the
+//! `password=` before it). Overlapping findings keep the earliest, longest
+//! one. A `RegexSet` prefilter skips rules that cannot match, so clean text
+//! costs one scan.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.redactSecrets("export OPENAI_API_KEY=sk-proj-abc...")
+//! // JS: // -> { text: "export OPENAI_API_KEY=[REDACTED:openai_api_key]", secrets: [{ kind: "openai_api_key", start: 22, end: 73 }] }
+//! ```
+
+use std::sync::LazyLock;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use regex::{Regex, RegexSet};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `redactSecrets`.
+#[napi(object)]
+pub struct RedactOptions {
+	/// Extra regexes to redact (reported as kind `"custom"`). A pattern with a
+	/// capture group redacts only the first group.
+	pub patterns:      Option<Vec<String>>,
+	/// Also redact long high-entropy tokens not matched by any rule
+	/// (default: false).
+	#[napi(js_name = "entropyCheck")]
+	pub entropy_check: Option<bool>,
+	/// Replacement text; `{kind}` expands to the finding's kind
+	/// (default: `"[REDACTED:{kind}]"`).
+	pub replacement:   Option<String>,
+}
+
+/// A secret found in the input.
+#[napi(object)]
+pub struct SecretMatch {
+	/// Rule that matched (e.g. `"aws_access_key"`, `"private_key"`,
+	/// `"high_entropy"`, `"custom"`).
+	pub kind:  String,
+	/// Start offset in the input, in UTF-16 code units.
+	pub start: u32,
+	/// End offset in the input, in UTF-16 code units (exclusive).
+	pub end:   u32,
+}
+
+/// Result of `redactSecrets`.
+#[napi(object)]
+pub struct RedactResult {
+	/// Input with every finding replaced.
+	pub text:    String,
+	/// Findings in input order.
+	pub secrets: Vec<SecretMatch>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Rules
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Built-in rules as `(kind, pattern)`, most specific first; on equal spans
+/// the earlier rule names the finding.
+const RULES: &[(&str, &str)] = &[
+	(
+		"private_key",
+		concat!(
+			r"(?s)-----BEGIN [A-Z0-9 ]*PRIVATE KEY(?: BLOCK)?-----.*?",
+			r"(?:-----END [A-Z0-9 ]*PRIVATE KEY(?: BLOCK)?-----|\z)",
+		),
+	),
+	("aws_access_key", r"\b(?:AKIA|ASIA|ABIA|ACCA)[0-9A-Z]{16}\b"),
+	(
+		"aws_secret_key",
+		concat!(
+			r#"(?i)aws.{0,20}?(?:secret|private).{0,20}?["'=:\s]+"#,
+			r"([A-Za-z0-9/+=]{40})(?:[^A-Za-z0-9/+=]|$)",
+		),
+	),
+	("github_token", r"\b(?:gh[pousr]_[A-Za-z0-9]{36,255}|github_pat_[A-Za-z0-9_]{22,255})\b"),
+	("gitlab_token", r"\bglpat-[A-Za-z0-9_-]{20,}"),
+	("slack_token", r"\bxox[abposr]-[A-Za-z0-9-]{10,}"),
+	("slack_webhook", r"https://hooks\.slack\.com/services/[A-Za-z0-9/_-]+"),
+	("anthropic_api_key", r"\bsk-ant-[A-Za-z0-9_-]{20,}"),
+	("openai_api_key", r"\bsk-(?:proj-|svcacct-|admin-)?[A-Za-z0-9_-]{20,}"),
+	("google_api_key", r"\bAIza[0-9A-Za-z_-]{35}"),
+	("stripe_key", r"\b[rsp]k_(?:live|test)_[0-9A-Za-z]{16,}"),
+	("npm_token", r"\bnpm_[A-Za-z0-9]{36}\b"),
+	("jwt", r"\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}"),
+	("bearer_token", r"(?i)\bbearer\s+([A-Za-z0-9._~+/-]{16,}=*)"),
+	("url_credentials", r"\b[a-zA-Z][a-zA-Z0-9+.-]*://[^/\s:@]+:([^/\s@]+)@"),
+	(
+		"generic_secret",
+		concat!(
+			r"(?i)(?:api[_-]?key|secret|token|passw(?:or)?d|access[_-]?key|auth)",
+			r#"["']?\s*[:=]\s*["']?([A-Za-z0-9_\-+/=.~]{8,})"#,
+		),
+	),
+];
+
+/// Kind of the `generic_secret` rule, whose values are checked with
+/// `looks_like_value`.
+const GENERIC_KIND: &str = "generic_secret";
+
+struct RuleSet {
+	set:   RegexSet,
+	rules: Vec<(&'static str, Regex)>,
+}
+
+static BUILTIN: LazyLock<RuleSet> = LazyLock::new(|| {
+	let patterns = RULES.iter().map(|(_, pattern)| *pattern);
+	RuleSet {
+		set:   RegexSet::new(patterns).expect("built-in secret rules are valid"),
+		rules: RULES
+			.iter()
+			.map(|&(kind, pattern)| {
+				(kind, Regex::new(pattern).expect("built-in secret rule is valid"))
+			})
+			.collect(),
+	}
+});
+
+/// Candidate tokens for the entropy check.
+static TOKEN: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"[A-Za-z0-9+/_-]{24,}={0,2}").expect("token pattern is valid"));
+
+/// Minimum Shannon entropy, in bits per character, of a high-entropy token.
+const ENTROPY_THRESHOLD: f64 = 3.5;
+
+fn shannon_entropy(token: &str) -> f64 {
+	let mut counts = [0u32; 256];
+	for byte in token.bytes() {
+		counts[usize::from(byte)] += 1;
+	}
+	let len = token.len() as f64;
+	counts
+		.iter()
+		.filter(|&&count| count > 0)
+		.map(|&count| {
+			let p = f64::from(count) / len;
+			-p * p.log2()
+		})
+		.sum()
+}
+
+/// Whether a token looks like generated key material: several digits, both
+/// letter cases well represented (camelCase identifiers are mostly lower
+/// case), high entropy, and not a plain hex digest (commit hashes,
+/// checksums).
+fn looks_random(token: &str) -> bool {
+	let count = |pred: fn(&u8) -> bool| token.bytes().filter(pred).count();
+	let (upper, lower) = (count(u8::is_ascii_uppercase), count(u8::is_ascii_lowercase));
+	let letters = upper + lower;
+	!token.bytes().all(|b| b.is_ascii_hexdigit())
+		&& count(u8::is_ascii_digit) >= 2
+		&& upper * 4 >= letters
+		&& lower * 4 >= letters
+		&& shannon_entropy(token) >= ENTROPY_THRESHOLD
+}
+
+/// Whether an assigned value is plausibly a credential rather than code: it
+/// mixes letters and digits and is not a member expression such as
+/// `process.env.API_KEY_V2`.
+fn looks_like_value(value: &str) -> bool {
+	let is_ident = |part: &str| {
+		part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+			&& part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')
+	};
+	let member_expression = value.contains('.') && value.split('.').all(is_ident);
+	value.bytes().any(|b| b.is_ascii_alphabetic())
+		&& value.bytes().any(|b| b.is_ascii_digit())
+		&& !member_expression
+}
+
+/// A finding as a byte range of the input.
+struct Finding {
+	start: usize,
+	end:   usize,
+	kind:  String,
+	rank:  usize,
+}
+
+/// Span to redact for a rule match: the first capture group if present.
+fn span(captures: &regex::Captures<'_>) -> (usize, usize) {
+	let group = captures.get(1).or_else(|| captures.get(0));
+	group.map_or((0, 0), |m| (m.start(), m.end()))
+}
+
+/// Collect findings from all rules, then drop overlaps keeping the earliest,
+/// longest, then highest-priority finding.
+fn find_secrets(text: &str, custom: &[Regex], entropy_check: bool) -> Vec<Finding> {
+	let mut found = Vec::new();
+	for index in &BUILTIN.set.matches(text) {
+		let (kind, regex) = &BUILTIN.rules[index];
+		for captures in regex.captures_iter(text) {
+			let (start, end) = span(&captures);
+			if *kind == GENERIC_KIND && !looks_like_value(&text[start..end]) {
+				continue;
+			}
+			found.push(Finding { start, end, kind: (*kind).to_string(), rank: index });
+		}
+	}
+	for regex in custom {
+		for captures in regex.captures_iter(text) {
+			let (start, end) = span(&captures);
+			found.push(Finding { start, end, kind: "custom".to_string(), rank: RULES.len() });
+		}
+	}
+	if entropy_check {
+		for token in TOKEN.find_iter(text) {
+			if looks_random(token.as_str()) {
+				found.push(Finding {
+					start: token.start(),
+					end:   token.end(),
+					kind:  "high_entropy".to_string(),
+					rank:  RULES.len() + 1,
+				});
+			}
+		}
+	}
+
+	found.retain(|finding| finding.end > finding.start);
+	found.sort_by(|a, b| {
+		a.start
+			.cmp(&b.start)
+			.then(b.end.cmp(&a.end))
+			.then(a.rank.cmp(&b.rank))
+	});
+	let mut kept: Vec<Finding> = Vec::with_capacity(found.len());
+	for finding in found {
+		if kept.last().is_none_or(|last| finding.start >= last.end) {
+			kept.push(finding);
+		}
+	}
+	kept
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect and redact secrets in text.
+///
+/// # Arguments
+/// - `text`: Text to scan.
+/// - `options`: Extra patterns, entropy check, and replacement text.
+///
+/// # Returns
+/// The redacted text and the location of each finding in the input.
+///
+/// # Errors
+/// Returns an error if a custom pattern is not a valid regex.
+#[napi(js_name = "redactSecrets")]
+pub fn redact_secrets(text: String, options: Option<RedactOptions>) -> Result<RedactResult> {
+	let (patterns, entropy_check, replacement) = match options {
+		Some(opts) => (opts.patterns, opts.entropy_check, opts.replacement),
+		None => (None, None, None),
+	};
+	let custom = patterns
+		.unwrap_or_default()
+		.iter()
+		.map(|pattern| {
+			Regex::new(pattern)
+				.map_err(|err| Error::from_reason(format!("Invalid pattern {pattern:?}: {err}")))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	let replacement = replacement.as_deref().unwrap_or("[REDACTED:{kind}]");
+
+	let findings = find_secrets(&text, &custom, entropy_check.unwrap_or(false));
+	let mut out = String::with_capacity(text.len());
+	let mut secrets = Vec::with_capacity(findings.len());
+	let mut last = 0;
+	// UTF-16 offset of byte `last`, advanced incrementally.
+	let mut utf16 = 0;
+	for finding in findings {
+		utf16 += text[last..finding.start].encode_utf16().count();
+		let start = utf16;
+		utf16 += text[finding.start..finding.end].encode_utf16().count();
+		out.push_str(&text[last..finding.start]);
+		out.push_str(&replacement.replace("{kind}", &finding.kind));
+		last = finding.end;
+		secrets.push(SecretMatch { kind: finding.kind, start: start as u32, end: utf16 as u32 });
+	}
+	out.push_str(&text[last..]);
+	Ok(RedactResult { text: out, secrets })
+}
@@ -0,0 +1,496 @@
+//! Workspace-wide search and replace.
+//!
+//! # Overview
+//! Selects files with the same walker, glob, and type filters as `grep`,
+//! rewrites every match in parallel, and reports per-file line hunks. In
+//! dry-run mode nothing is written. Otherwise all rewritten files are staged
+//! as temp files next to their targets and then renamed into place; if any
+//! rename fails, files already replaced are restored from their originals and
+//! backups written so far are removed. Files modified after they were read
+//! abort the replacement before anything is written.
+//!
+//! The replacement text takes each file's dominant line ending, so replacing
+//! with multi-line text keeps CRLF files CRLF. Binary files, files that are
+//! not UTF-8, and files larger than 4 MiB are skipped.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.replaceInFiles({ pattern: "oldName", replacement: "newName", dryRun: true })
+//! ```
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+use grep_matcher::{Captures, Matcher};
+use grep_regex::{RegexMatcher, RegexMatcherBuilder};
+use imara_diff::Algorithm;
+use napi::{
+	bindgen_prelude::*,
+	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::{binary, diff, eol, file_filter, fs_cache, task};
+
+/// Files larger than this are never rewritten.
+const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `replaceInFiles`.
+#[napi(object)]
+pub struct ReplaceOptions<'env> {
+	/// Text or regex to search for.
+	pub pattern:     String,
+	/// Replacement text. With `regex`, `$1`/`${name}` expand capture groups
+	/// and `$$` is a literal `$`.
+	pub replacement: String,
+	/// Directory or file to rewrite (default: current directory).
+	pub path:        Option<String>,
+	/// Glob filter for filenames (e.g., "*.ts").
+	pub glob:        Option<String>,
+	/// Filter by file type (e.g., "js", "py", "rust").
+	#[napi(js_name = "type")]
+	pub type_filter: Option<String>,
+	/// Treat `pattern` as a regex instead of literal text (default: false).
+	pub regex:       Option<bool>,
+	/// Case-insensitive matching.
+	#[napi(js_name = "ignoreCase")]
+	pub ignore_case: Option<bool>,
+	/// Include hidden files (default: true).
+	pub hidden:      Option<bool>,
+	/// Compute hunks without writing any file (default: false).
+	#[napi(js_name = "dryRun")]
+	pub dry_run:     Option<bool>,
+	/// Copy each original to `<file>.bak` before replacing it (default: false).
+	pub backup:      Option<bool>,
+	/// Unchanged context lines around each hunk (default: 3).
+	pub context:     Option<u32>,
+	/// Abort signal for cancelling the operation.
+	pub signal:      Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:  Option<u32>,
+}
+
+/// Changes made (or previewed) in one file.
+#[derive(Clone)]
+#[napi(object)]
+pub struct FileReplacement {
+	/// Path relative to the search root, using forward slashes.
+	pub path:         String,
+	/// Number of matches replaced.
+	pub replacements: u32,
+	/// Line hunks describing the change.
+	pub hunks:        Vec<diff::DiffHunk>,
+	/// Backup file written before replacing, if any.
+	#[napi(js_name = "backupPath")]
+	pub backup_path:  Option<String>,
+}
+
+/// Result of `replaceInFiles`.
+#[napi(object)]
+pub struct ReplaceResult {
+	/// Changed files, sorted by path.
+	pub files:              Vec<FileReplacement>,
+	/// Total matches replaced across all files.
+	#[napi(js_name = "totalReplacements")]
+	pub total_replacements: u32,
+	/// Number of files considered.
+	#[napi(js_name = "filesSearched")]
+	pub files_searched:     u32,
+	/// Whether changes were written to disk (false for dry runs).
+	pub applied:            bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Planning
+// ═══════════════════════════════════════════════════════════════════════════
+
+struct Replacer {
+	matcher:     RegexMatcher,
+	replacement: String,
+	expand:      bool,
+}
+
+impl Replacer {
+	/// Rewrite `haystack`, returning the new bytes and the match count.
+	fn apply(&self, haystack: &[u8], replacement: &[u8]) -> Result<(Vec<u8>, u32)> {
+		let mut caps = self
+			.matcher
+			.new_captures()
+			.map_err(|err| Error::from_reason(format!("Regex error: {err}")))?;
+		let mut out = Vec::with_capacity(haystack.len());
+		let mut count = 0u32;
+		self
+			.matcher
+			.replace_with_captures(haystack, &mut caps, &mut out, |caps, dst| {
+				count = count.saturating_add(1);
+				if self.expand {
+					caps.interpolate(
+						|name| self.matcher.capture_index(name),
+						haystack,
+						replacement,
+						dst,
+					);
+				} else {
+					dst.extend_from_slice(replacement);
+				}
+				true
+			})
+			.map_err(|err| Error::from_reason(format!("Replace failed: {err}")))?;
+		Ok((out, count))
+	}
+}
+
+struct PlannedFile {
+	path:     PathBuf,
+	/// Modification time and length when `original` was read.
+	stamp:    (Option<SystemTime>, u64),
+	original: Vec<u8>,
+	updated:  Vec<u8>,
+	report:   FileReplacement,
+}
+
+fn plan_file(
+	entry: &file_filter::FileEntry,
+	replacer: &Replacer,
+	context: usize,
+) -> Result<Option<PlannedFile>> {
+	let Ok(meta) = fs::metadata(&entry.path) else {
+		return Ok(None);
+	};
+	if meta.len() > MAX_FILE_BYTES {
+		return Ok(None);
+	}
+	let Ok(original) = fs::read(&entry.path) else {
+		return Ok(None);
+	};
+	if binary::is_binary(&original) || !replacer.matcher.is_match(&original).unwrap_or(false) {
+		return Ok(None);
+	}
+	let Ok(old_text) = std::str::from_utf8(&original) else {
+		return Ok(None);
+	};
+	let replacement = eol::preserve_eol(old_text, &replacer.replacement);
+	let (updated, replacements) = replacer.apply(&original, replacement.as_bytes())?;
+	if updated == original {
+		return Ok(None);
+	}
+	let new_text = String::from_utf8_lossy(&updated);
+	let hunks = diff::diff_texts(old_text, &new_text, Algorithm::Histogram, context).hunks;
+	Ok(Some(PlannedFile {
+		path: entry.path.clone(),
+		stamp: (meta.modified().ok(), meta.len()),
+		report: FileReplacement {
+			path: entry.relative_path.clone(),
+			replacements,
+			hunks,
+			backup_path: None,
+		},
+		original,
+		updated,
+	}))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Applying
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn temp_path(path: &Path) -> PathBuf {
+	let name = path
+		.file_name()
+		.map_or_else(Default::default, |name| name.to_string_lossy());
+	path.with_file_name(format!(".{name}.{}.replace.tmp", std::process::id()))
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+	let mut name = path.as_os_str().to_owned();
+	name.push(".bak");
+	PathBuf::from(name)
+}
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> Error {
+	Error::from_reason(format!("Failed to {action} {}: {err}", path.display()))
+}
+
+/// Whether `file` still has the modification time and length it was planned
+/// with.
+fn unchanged(file: &PlannedFile) -> bool {
+	fs::metadata(&file.path).is_ok_and(|meta| (meta.modified().ok(), meta.len()) == file.stamp)
+}
+
+/// Write every planned file via temp file + rename, restoring the originals of
+/// already-renamed files and removing backups if a later step fails.
+fn apply_all(planned: &mut [PlannedFile], backup: bool) -> Result<()> {
+	let mut staged = Vec::with_capacity(planned.len());
+	let cleanup = |paths: &[PathBuf]| {
+		for path in paths {
+			let _ = fs::remove_file(path);
+		}
+	};
+	for file in planned.iter() {
+		if !unchanged(file) {
+			cleanup(&staged);
+			return Err(Error::from_reason(format!(
+				"{} changed since it was read; nothing was replaced",
+				file.path.display()
+			)));
+		}
+		let temp = temp_path(&file.path);
+		let written = fs::write(&temp, &file.updated).and_then(|()| {
+			let perms = fs::metadata(&file.path)?.permissions();
+			fs::set_permissions(&temp, perms)
+		});
+		if let Err(err) = written {
+			let _ = fs::remove_file(&temp);
+			cleanup(&staged);
+			return Err(io_error("stage", &file.path, err));
+		}
+		staged.push(temp);
+	}
+
+	let mut backups = Vec::new();
+	let mut failure = None;
+	for (done, file) in planned.iter_mut().enumerate() {
+		let step = if backup {
+			let bak = backup_path(&file.path);
+			let written = fs::write(&bak, &file.original);
+			// A partial backup is removed with the rest on rollback.
+			backups.push(bak.clone());
+			written.map(|()| Some(bak))
+		} else {
+			Ok(None)
+		}
+		.and_then(|bak| fs::rename(&staged[done], &file.path).map(|()| bak));
+		match step {
+			Ok(bak) => {
+				file.report.backup_path = bak.map(|bak| bak.to_string_lossy().into_owned());
+			},
+			Err(err) => {
+				failure = Some((done, io_error("replace", &file.path, err)));
+				break;
+			},
+		}
+	}
+	if let Some((done, err)) = failure {
+		for restored in &mut planned[..done] {
+			let _ = fs::write(&restored.path, &restored.original);
+			restored.report.backup_path = None;
+		}
+		cleanup(&backups);
+		cleanup(&staged[done..]);
+		return Err(err);
+	}
+	Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Replace a pattern across files, or preview the change with `dryRun`.
+///
+/// # Arguments
+/// - `options`: Pattern, replacement, file filters, and write mode.
+/// - `on_file`: Optional callback invoked per changed (or previewed) file,
+///   after all files are written.
+///
+/// # Returns
+/// Per-file hunks and replacement counts.
+///
+/// # Errors
+/// Returns an error for invalid patterns or globs, missing paths, write
+/// failures (after rolling back), or cancellation.
+#[napi(js_name = "replaceInFiles")]
+pub fn replace_in_files(
+	options: ReplaceOptions<'_>,
+	#[napi(ts_arg_type = "((file: FileReplacement) => void) | undefined | null")] on_file: Option<
+		ThreadsafeFunction<FileReplacement>,
+	>,
+) -> task::Async<ReplaceResult> {
+	let ReplaceOptions {
+		pattern,
+		replacement,
+		path,
+		glob,
+		type_filter,
+		regex,
+		ignore_case,
+		hidden,
+		dry_run,
+		backup,
+		context,
+		signal,
+		timeout_ms,
+	} = options;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("replace_in_files", ct, move |ct| {
+		let regex = regex.unwrap_or(false);
+		let matcher = RegexMatcherBuilder::new()
+			.case_insensitive(ignore_case.unwrap_or(false))
+			.multi_line(true)
+			.fixed_strings(!regex)
+			.build(&pattern)
+			.map_err(|err| Error::from_reason(format!("Regex error: {err}")))?;
+		let replacer = Replacer { matcher, replacement, expand: regex };
+		let glob_set = file_filter::compile_glob(glob.as_deref())?;
+		let type_filter = file_filter::resolve_type_filter(type_filter.as_deref());
+		let root = file_filter::resolve_search_path(path.as_deref().unwrap_or("."))?;
+		let metadata =
+			fs::metadata(&root).map_err(|err| Error::from_reason(format!("Path not found: {err}")))?;
+
+		let entries = if metadata.is_file() {
+			let relative = root
+				.file_name()
+				.map(|name| name.to_string_lossy().into_owned());
+			vec![file_filter::FileEntry {
+				relative_path: relative.unwrap_or_default(),
+				path:          root.clone(),
+			}]
+		} else {
+			let scanned = fs_cache::force_rescan(&root, hidden.unwrap_or(true), true, false, &ct)?;
+			file_filter::collect_files(&root, &scanned, glob_set.as_ref(), type_filter.as_ref())
+		};
+		ct.heartbeat()?;
+
+		let context = context.unwrap_or(3) as usize;
+		let mut planned = entries
+			.par_iter()
+			.map(|entry| {
+				ct.heartbeat()?;
+				plan_file(entry, &replacer, context)
+			})
+			.collect::<Result<Vec<_>>>()?
+			.into_iter()
+			.flatten()
+			.collect::<Vec<_>>();
+		planned.sort_by(|a, b| a.report.path.cmp(&b.report.path));
+		ct.heartbeat()?;
+
+		let applied = !dry_run.unwrap_or(false);
+		if applied {
+			apply_all(&mut planned, backup.unwrap_or(false))?;
+			fs_cache::invalidate_path(&root);
+		}
+		// Reported only after every file is written, so a rollback never follows
+		// a reported success.
+		if let Some(callback) = &on_file {
+			for file in &planned {
+				callback.call(Ok(file.report.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+			}
+		}
+
+		let total = planned
+			.iter()
+			.fold(0u32, |sum, file| sum.saturating_add(file.report.replacements));
+		Ok(ReplaceResult {
+			files: planned.into_iter().map(|file| file.report).collect(),
+			total_replacements: total,
+			files_searched: entries.len().min(u32::MAX as usize) as u32,
+			applied,
+		})
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::{ScratchDir, dir_names};
+
+	fn plan(root: &Path, name: &str, pattern: &str, replacement: &str) -> PlannedFile {
+		let replacer = Replacer {
+			matcher:     RegexMatcher::new(pattern).unwrap(),
+			replacement: replacement.to_string(),
+			expand:      true,
+		};
+		let entry =
+			file_filter::FileEntry { path: root.join(name), relative_path: name.to_string() };
+		plan_file(&entry, &replacer, 3).unwrap().unwrap()
+	}
+
+	#[test]
+	fn test_plan_expands_captures_and_keeps_crlf() {
+		let root = ScratchDir::new("replace-plan");
+		fs::write(root.join("a.txt"), "let old_one = 1;\r\nlet old_two = 2;\r\n").unwrap();
+		let file = plan(&root, "a.txt", r"old_(\w+)", "new_$1\n// renamed");
+		assert_eq!(file.report.replacements, 2);
+		assert_eq!(
+			String::from_utf8(file.updated).unwrap(),
+			"let new_one\r\n// renamed = 1;\r\nlet new_two\r\n// renamed = 2;\r\n"
+		);
+	}
+
+	#[test]
+	fn test_apply_all_writes_backups() {
+		let root = ScratchDir::new("replace-apply");
+		fs::write(root.join("a.txt"), "foo\n").unwrap();
+		fs::write(root.join("b.txt"), "x foo\n").unwrap();
+		let mut planned = [plan(&root, "a.txt", "foo", "bar"), plan(&root, "b.txt", "foo", "bar")];
+
+		apply_all(&mut planned, true).unwrap();
+		assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "bar\n");
+		assert_eq!(fs::read_to_string(root.join("b.txt.bak")).unwrap(), "x foo\n");
+		assert!(
+			planned[0]
+				.report
+				.backup_path
+				.as_deref()
+				.is_some_and(|bak| bak.ends_with("a.txt.bak"))
+		);
+		assert_eq!(dir_names(&root), ["a.txt", "a.txt.bak", "b.txt", "b.txt.bak"]);
+	}
+
+	#[test]
+	fn test_apply_all_rolls_back_when_a_rename_fails() {
+		let root = ScratchDir::new("replace-rollback");
+		fs::write(root.join("a.txt"), "foo\n").unwrap();
+		let first = plan(&root, "a.txt", "foo", "bar");
+		// Renaming a file over a non-empty directory fails after `a.txt` is
+		// already replaced.
+		let dir = root.join("z");
+		fs::create_dir(&dir).unwrap();
+		fs::write(dir.join("keep"), "").unwrap();
+		let meta = fs::metadata(&dir).unwrap();
+		let second = PlannedFile {
+			path:     dir,
+			stamp:    (meta.modified().ok(), meta.len()),
+			original: Vec::new(),
+			updated:  b"bar\n".to_vec(),
+			report:   FileReplacement {
+				path:         "z".to_string(),
+				replacements: 1,
+				hunks:        Vec::new(),
+				backup_path:  None,
+			},
+		};
+		let mut planned = [first, second];
+
+		assert!(apply_all(&mut planned, true).is_err());
+		assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "foo\n");
+		assert!(planned.iter().all(|file| file.report.backup_path.is_none()));
+		// No backups or staged temp files are left behind.
+		assert_eq!(dir_names(&root), ["a.txt", "z"]);
+	}
+
+	#[test]
+	fn test_apply_all_rejects_files_changed_since_planning() {
+		let root = ScratchDir::new("replace-changed");
+		fs::write(root.join("a.txt"), "foo\n").unwrap();
+		fs::write(root.join("b.txt"), "foo\n").unwrap();
+		let mut planned = [plan(&root, "a.txt", "foo", "bar"), plan(&root, "b.txt", "foo", "bar")];
+		fs::write(root.join("b.txt"), "foo edited\n").unwrap();
+
+		let err = apply_all(&mut planned, false).unwrap_err();
+		assert!(err.reason.contains("changed since it was read"), "{}", err.reason);
+		assert_eq!(fs::read_to_string(root.join("a.txt")).unwrap(), "foo\n");
+		assert_eq!(fs::read_to_string(root.join("b.txt")).unwrap(), "foo edited\n");
+		assert_eq!(dir_names(&root), ["a.txt", "b.txt"]);
+	}
+}
@@ -0,0 +1,309 @@
+//! Linear-time regular expressions for user-supplied patterns.
+//!
+//! # Overview
+//! `compileRegex` compiles a pattern with the `regex` crate, whose matching
+//! time is linear in the input, so a hostile or careless pattern cannot hang
+//! the UI the way a backtracking JS `RegExp` can. The remaining costs are
+//! bounded by a budget: the compiled program size is capped at compile time,
+//! and `findAll`/`replaceAll` stop with an error once a time limit passes.
+//!
+//! Flags follow JS where they overlap (`i`, `m`, `s`, `u`, and `g`, which is
+//! implied), plus `x` for verbose patterns. Lookaround and backreferences are
+//! not supported and fail to compile. Offsets are UTF-16 code units and
+//! replacement strings use JS syntax (`$&`, `$1`, `$<name>`, `$$`).
+//!
+//! # Example
+//! ```ignore
+//! // JS: const re = native.compileRegex("(?<key>\\w+)=(\\d+)", "gi", { timeoutMs: 50 });
+//! // JS: re.findAll("a=1, b=2").matches // -> [{ start: 0, end: 3, text: "a=1", groups: ["a", "1"] }, ...]
+//! // JS: re.replaceAll("a=1", "$<key>: $2") // -> "a: 1"
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use regex::{Captures, Regex, RegexBuilder};
+
+use crate::task::CancelToken;
+
+const DEFAULT_SIZE_LIMIT: u32 = 1 << 20;
+const DEFAULT_TIMEOUT_MS: u32 = 250;
+const DEFAULT_MAX_MATCHES: u32 = 10_000;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Resource budget for `compileRegex`.
+#[napi(object)]
+pub struct RegexOptions {
+	/// Maximum compiled program size in bytes (default: 1 MiB).
+	#[napi(js_name = "sizeLimit")]
+	pub size_limit:  Option<u32>,
+	/// Time limit for each `findAll` or `replaceAll` call in milliseconds
+	/// (default: 250).
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:  Option<u32>,
+	/// Maximum matches `findAll` returns (default: 10000).
+	#[napi(js_name = "maxMatches")]
+	pub max_matches: Option<u32>,
+}
+
+/// One match found by `findAll`.
+#[napi(object)]
+pub struct RegexMatch {
+	/// Start offset in UTF-16 code units.
+	pub start:  u32,
+	/// End offset in UTF-16 code units (exclusive).
+	pub end:    u32,
+	/// Matched text.
+	pub text:   String,
+	/// Capture groups 1..n; `null` for groups that did not participate.
+	pub groups: Vec<Option<String>>,
+}
+
+/// Result of `findAll`.
+#[napi(object)]
+pub struct RegexFindResult {
+	/// Matches in order of position.
+	pub matches:       Vec<RegexMatch>,
+	/// Whether `maxMatches` stopped the search early.
+	#[napi(js_name = "limitReached")]
+	pub limit_reached: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Helpers
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn build(pattern: &str, flags: &str, size_limit: usize) -> Result<Regex> {
+	let mut builder = RegexBuilder::new(pattern);
+	builder.size_limit(size_limit).dfa_size_limit(size_limit);
+	for flag in flags.chars() {
+		match flag {
+			'i' => builder.case_insensitive(true),
+			'm' => builder.multi_line(true),
+			's' => builder.dot_matches_new_line(true),
+			'x' => builder.ignore_whitespace(true),
+			// Matching is always global and Unicode-aware.
+			'g' | 'u' => &mut builder,
+			_ => return Err(Error::from_reason(format!("Unsupported regex flag: {flag}"))),
+		};
+	}
+	builder.build().map_err(|err| match err {
+		regex::Error::CompiledTooBig(limit) => {
+			Error::from_reason(format!("Regex is too large: compiled size exceeds {limit} bytes"))
+		},
+		err => Error::from_reason(format!("Invalid regex: {err}")),
+	})
+}
+
+/// Converts ascending byte offsets of one string to UTF-16 offsets.
+struct Utf16Cursor<'a> {
+	text:  &'a str,
+	byte:  usize,
+	utf16: usize,
+}
+
+impl Utf16Cursor<'_> {
+	fn advance(&mut self, byte: usize) -> u32 {
+		self.utf16 += self.text[self.byte..byte].encode_utf16().count();
+		self.byte = byte;
+		self.utf16 as u32
+	}
+}
+
+/// Append `replacement` for one match, expanding JS `$` patterns. As in JS,
+/// `$<name>` is literal unless the regex has named groups.
+fn expand(replacement: &str, caps: &Captures<'_>, named: bool, text: &str, out: &mut String) {
+	let whole = caps.get(0).expect("group 0 always participates");
+	let mut rest = replacement;
+	while let Some(dollar) = rest.find('$') {
+		out.push_str(&rest[..dollar]);
+		let after = &rest[dollar + 1..];
+		let bytes = after.as_bytes();
+		let consumed = match bytes.first() {
+			Some(b'$') => {
+				out.push('$');
+				1
+			},
+			Some(b'&') => {
+				out.push_str(whole.as_str());
+				1
+			},
+			Some(b'`') => {
+				out.push_str(&text[..whole.start()]);
+				1
+			},
+			Some(b'\'') => {
+				out.push_str(&text[whole.end()..]);
+				1
+			},
+			Some(b'<') if named => {
+				if let Some(close) = after.find('>') {
+					if let Some(group) = caps.name(&after[1..close]) {
+						out.push_str(group.as_str());
+					}
+					close + 1
+				} else {
+					out.push('$');
+					0
+				}
+			},
+			Some(digit @ b'0'..=b'9') => {
+				// Two digits when they name an existing group, as in JS.
+				let one = usize::from(digit - b'0');
+				let two = bytes
+					.get(1)
+					.filter(|b| b.is_ascii_digit())
+					.map(|b| one * 10 + usize::from(b - b'0'))
+					.filter(|&index| index >= 1 && index < caps.len());
+				match (two, one) {
+					(Some(index), _) => {
+						out.push_str(caps.get(index).map_or("", |m| m.as_str()));
+						2
+					},
+					(None, index) if index >= 1 && index < caps.len() => {
+						out.push_str(caps.get(index).map_or("", |m| m.as_str()));
+						1
+					},
+					_ => {
+						out.push('$');
+						0
+					},
+				}
+			},
+			_ => {
+				out.push('$');
+				0
+			},
+		};
+		rest = &after[consumed..];
+	}
+	out.push_str(rest);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A compiled regular expression with a matching budget.
+#[napi]
+pub struct SafeRegex {
+	regex:       Regex,
+	flags:       String,
+	timeout_ms:  u32,
+	max_matches: u32,
+}
+
+#[napi]
+impl SafeRegex {
+	/// The pattern source.
+	#[napi(getter)]
+	pub fn source(&self) -> String {
+		self.regex.as_str().to_string()
+	}
+
+	/// The flags the regex was compiled with.
+	#[napi(getter)]
+	pub fn flags(&self) -> String {
+		self.flags.clone()
+	}
+
+	/// Whether the pattern matches anywhere in `text`.
+	#[napi]
+	pub fn test(&self, text: String) -> bool {
+		self.regex.is_match(&text)
+	}
+
+	/// Find all non-overlapping matches in `text`.
+	///
+	/// # Errors
+	/// Returns an error when the time budget runs out.
+	#[napi(js_name = "findAll")]
+	pub fn find_all(&self, text: String) -> Result<RegexFindResult> {
+		let ct = CancelToken::new(Some(self.timeout_ms), None);
+		let mut cursor = Utf16Cursor { text: &text, byte: 0, utf16: 0 };
+		let mut matches = Vec::new();
+		let mut limit_reached = false;
+		for caps in self.regex.captures_iter(&text) {
+			ct.heartbeat()?;
+			if matches.len() >= self.max_matches as usize {
+				limit_reached = true;
+				break;
+			}
+			let whole = caps.get(0).expect("group 0 always participates");
+			matches.push(RegexMatch {
+				start:  cursor.advance(whole.start()),
+				end:    cursor.advance(whole.end()),
+				text:   whole.as_str().to_string(),
+				groups: caps
+					.iter()
+					.skip(1)
+					.map(|group| group.map(|m| m.as_str().to_string()))
+					.collect(),
+			});
+		}
+		Ok(RegexFindResult { matches, limit_reached })
+	}
+
+	/// Replace every match in `text`.
+	///
+	/// # Arguments
+	/// - `text`: Input text.
+	/// - `replacement`: Replacement with JS patterns: `$&`, `` $` ``, `$'`,
+	///   `$1`-`$99`, `$<name>`, and `$$`.
+	///
+	/// # Errors
+	/// Returns an error when the time budget runs out.
+	#[napi(js_name = "replaceAll")]
+	pub fn replace_all(&self, text: String, replacement: String) -> Result<String> {
+		let ct = CancelToken::new(Some(self.timeout_ms), None);
+		let named = self.regex.capture_names().any(|name| name.is_some());
+		let mut out = String::with_capacity(text.len());
+		let mut last = 0;
+		for caps in self.regex.captures_iter(&text) {
+			ct.heartbeat()?;
+			let whole = caps.get(0).expect("group 0 always participates");
+			out.push_str(&text[last..whole.start()]);
+			expand(&replacement, &caps, named, &text, &mut out);
+			last = whole.end();
+		}
+		out.push_str(&text[last..]);
+		Ok(out)
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Compile a user-supplied pattern into a linear-time regex.
+///
+/// # Arguments
+/// - `pattern`: Rust `regex` syntax, which covers common JS syntax except
+///   lookaround and backreferences.
+/// - `flags`: Any of `g`, `i`, `m`, `s`, `u`, `x` (default: none).
+/// - `options`: Size, time, and match-count budget.
+///
+/// # Errors
+/// Returns an error for invalid syntax, unsupported flags, or a pattern whose
+/// compiled form exceeds the size limit.
+#[napi(js_name = "compileRegex")]
+pub fn compile_regex(
+	pattern: String,
+	flags: Option<String>,
+	options: Option<RegexOptions>,
+) -> Result<SafeRegex> {
+	let flags = flags.unwrap_or_default();
+	let (size_limit, timeout_ms, max_matches) = match options {
+		Some(opts) => (opts.size_limit, opts.timeout_ms, opts.max_matches),
+		None => (None, None, None),
+	};
+	let regex = build(&pattern, &flags, size_limit.unwrap_or(DEFAULT_SIZE_LIMIT) as usize)?;
+	Ok(SafeRegex {
+		regex,
+		flags,
+		timeout_ms: timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS),
+		max_matches: max_matches.unwrap_or(DEFAULT_MAX_MATCHES),
+	})
+}
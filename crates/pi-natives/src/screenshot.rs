@@ -0,0 +1,597 @@
+//! Terminal-line screenshots as SVG or PNG.
+//!
+//! # Overview
+//! `renderToSvg` lays styled terminal lines out on a monospace cell grid and
+//! emits a standalone SVG; `renderToPng` rasterizes the same grid with a
+//! caller-supplied TTF/OTF font. Escape sequences are parsed by the same code
+//! as the `text` width functions, and cells are measured with the same
+//! grapheme widths, so the image lines up with what the terminal showed.
+//!
+//! Basic and 256-color indices resolve through the theme's 16-color palette
+//! and the standard xterm cube; bold, dim, italic, underline, strikethrough,
+//! inverse, and hidden are honored. SVG output relies on the viewer's
+//! monospace font for glyph shapes, while PNG output is pixel-exact for the
+//! given font.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const svg = native.renderToSvg(rows, { padding: 24, font: { size: 13 } })
+//! // JS: const png = await native.renderToPng(rows, { font: { path: "/fonts/JetBrainsMono.ttf" }, scale: 2 })
+//! ```
+
+use std::{fmt::Write as _, io::Cursor};
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont, point};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+	task,
+	text::{self, SgrColor, SpanStyle},
+};
+
+pub(crate) type Rgb = (u8, u8, u8);
+
+const DEFAULT_BACKGROUND: Rgb = (0x1e, 0x1e, 0x1e);
+const DEFAULT_FOREGROUND: Rgb = (0xd4, 0xd4, 0xd4);
+/// Default 16-color palette: normal colors, then bright.
+const DEFAULT_PALETTE: [Rgb; 16] = [
+	(0x00, 0x00, 0x00),
+	(0xcd, 0x31, 0x31),
+	(0x0d, 0xbc, 0x79),
+	(0xe5, 0xe5, 0x10),
+	(0x24, 0x72, 0xc8),
+	(0xbc, 0x3f, 0xbc),
+	(0x11, 0xa8, 0xcd),
+	(0xe5, 0xe5, 0xe5),
+	(0x66, 0x66, 0x66),
+	(0xf1, 0x4c, 0x4c),
+	(0x23, 0xd1, 0x8b),
+	(0xf5, 0xf5, 0x43),
+	(0x3b, 0x8e, 0xea),
+	(0xd6, 0x70, 0xd6),
+	(0x29, 0xb8, 0xdb),
+	(0xff, 0xff, 0xff),
+];
+pub(crate) const DEFAULT_FONT_FAMILY: &str =
+	"ui-monospace, SFMono-Regular, Menlo, Consolas, monospace";
+pub(crate) const DEFAULT_FONT_SIZE: f64 = 14.0;
+pub(crate) const DEFAULT_LINE_HEIGHT: f64 = 1.4;
+/// Cell width relative to font size for SVG output.
+const DEFAULT_CELL_RATIO: f64 = 0.6;
+pub(crate) const DEFAULT_PADDING: u32 = 16;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Colors for a screenshot, as `#rgb` or `#rrggbb`.
+#[napi(object)]
+pub struct ScreenshotTheme {
+	/// Background color (default: "#1e1e1e").
+	pub background: Option<String>,
+	/// Default text color (default: "#d4d4d4").
+	pub foreground: Option<String>,
+	/// The 16 basic colors, normal then bright (default: a VS Code-like dark
+	/// palette).
+	pub palette:    Option<Vec<String>>,
+}
+
+/// Font settings for a screenshot.
+#[napi(object)]
+pub struct ScreenshotFont {
+	/// CSS font family for SVG output (default: a monospace stack).
+	pub family:      Option<String>,
+	/// Font size in pixels (default: 14).
+	pub size:        Option<f64>,
+	/// Line height as a multiple of the font size (default: 1.4).
+	#[napi(js_name = "lineHeight")]
+	pub line_height: Option<f64>,
+	/// Cell width in pixels (default: 0.6 × size for SVG, the font's advance
+	/// width for PNG).
+	#[napi(js_name = "cellWidth")]
+	pub cell_width:  Option<f64>,
+	/// TTF or OTF file to rasterize with; required by `renderToPng`.
+	pub path:        Option<String>,
+}
+
+/// Options for `renderToSvg` and `renderToPng`.
+#[napi(object)]
+pub struct ScreenshotOptions {
+	/// Colors (default: dark theme).
+	pub theme:   Option<ScreenshotTheme>,
+	/// Font settings.
+	pub font:    Option<ScreenshotFont>,
+	/// Space around the text in pixels (default: 16).
+	pub padding: Option<u32>,
+	/// Grid width in cells (default: the widest line).
+	pub columns: Option<u32>,
+	/// Pixel ratio for PNG output, e.g. 2 for high-DPI (default: 1).
+	pub scale:   Option<f64>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Layout
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Resolved colors.
+pub(crate) struct Palette {
+	pub(crate) background: Rgb,
+	pub(crate) foreground: Rgb,
+	basic:                 [Rgb; 16],
+}
+
+/// Colors and attributes of one cell after resolving the theme.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct CellStyle {
+	pub(crate) fg:        Rgb,
+	pub(crate) bg:        Option<Rgb>,
+	pub(crate) bold:      bool,
+	pub(crate) dim:       bool,
+	pub(crate) italic:    bool,
+	pub(crate) underline: bool,
+	pub(crate) strike:    bool,
+	pub(crate) hidden:    bool,
+}
+
+/// A grapheme placed on the grid.
+struct Cell {
+	text:   String,
+	column: usize,
+	width:  usize,
+	style:  CellStyle,
+}
+
+fn parse_hex(color: &str) -> Result<Rgb> {
+	let invalid = || Error::from_reason(format!("Invalid color: {color}"));
+	let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+	let digit = |index: usize| {
+		hex.get(index..=index)
+			.and_then(|d| u8::from_str_radix(d, 16).ok())
+			.ok_or_else(invalid)
+	};
+	match hex.len() {
+		3 => Ok((digit(0)? * 17, digit(1)? * 17, digit(2)? * 17)),
+		6 => Ok((digit(0)? * 16 + digit(1)?, digit(2)? * 16 + digit(3)?, digit(4)? * 16 + digit(5)?)),
+		_ => Err(invalid()),
+	}
+}
+
+impl Palette {
+	pub(crate) fn from_theme(theme: Option<&ScreenshotTheme>) -> Result<Self> {
+		let mut palette = Self {
+			background: DEFAULT_BACKGROUND,
+			foreground: DEFAULT_FOREGROUND,
+			basic:      DEFAULT_PALETTE,
+		};
+		let Some(theme) = theme else {
+			return Ok(palette);
+		};
+		if let Some(color) = &theme.background {
+			palette.background = parse_hex(color)?;
+		}
+		if let Some(color) = &theme.foreground {
+			palette.foreground = parse_hex(color)?;
+		}
+		if let Some(colors) = &theme.palette {
+			if colors.len() != 16 {
+				return Err(Error::from_reason("Theme palette must have 16 colors"));
+			}
+			for (slot, color) in palette.basic.iter_mut().zip(colors) {
+				*slot = parse_hex(color)?;
+			}
+		}
+		Ok(palette)
+	}
+
+	/// Resolve a color through the basic palette, the xterm 6×6×6 cube, and
+	/// the grayscale ramp.
+	fn resolve(&self, color: SgrColor) -> Rgb {
+		let cube = |level: u8| if level == 0 { 0 } else { 55 + level * 40 };
+		match color {
+			SgrColor::Basic(index) => self.basic[usize::from(index & 15)],
+			SgrColor::Indexed(index @ 0..=15) => self.basic[usize::from(index)],
+			SgrColor::Indexed(index @ 16..=231) => {
+				let index = index - 16;
+				(cube(index / 36), cube(index / 6 % 6), cube(index % 6))
+			},
+			SgrColor::Indexed(index) => {
+				let gray = 8 + (index - 232) * 10;
+				(gray, gray, gray)
+			},
+			SgrColor::Rgb(r, g, b) => (r, g, b),
+		}
+	}
+
+	pub(crate) fn cell_style(&self, style: SpanStyle) -> CellStyle {
+		let mut fg = style
+			.fg
+			.map_or(self.foreground, |color| self.resolve(color));
+		let mut bg = style.bg.map(|color| self.resolve(color));
+		if style.inverse {
+			let swapped = bg.unwrap_or(self.background);
+			bg = Some(fg);
+			fg = swapped;
+		}
+		CellStyle {
+			fg,
+			bg,
+			bold: style.bold,
+			dim: style.dim,
+			italic: style.italic,
+			underline: style.underline,
+			strike: style.strike,
+			hidden: style.hidden,
+		}
+	}
+}
+
+/// Place every grapheme of every line on the cell grid.
+fn layout(lines: &[String], palette: &Palette) -> Vec<Vec<Cell>> {
+	lines
+		.iter()
+		.map(|line| {
+			let mut cells = Vec::new();
+			let mut column = 0;
+			for (span, style) in text::styled_spans(line) {
+				let style = palette.cell_style(style);
+				for grapheme in span.graphemes(true) {
+					if grapheme == "\t" {
+						cells.push(Cell { text: String::new(), column, width: text::TAB_WIDTH, style });
+						column += text::TAB_WIDTH;
+						continue;
+					}
+					if grapheme.chars().all(char::is_control) {
+						continue;
+					}
+					let width = text::grapheme_width_str(grapheme);
+					cells.push(Cell { text: grapheme.to_string(), column, width, style });
+					column += width;
+				}
+			}
+			cells
+		})
+		.collect()
+}
+
+fn grid_columns(rows: &[Vec<Cell>], columns: Option<u32>) -> usize {
+	columns.map_or_else(
+		|| {
+			rows
+				.iter()
+				.filter_map(|cells| cells.last().map(|cell| cell.column + cell.width))
+				.max()
+				.unwrap_or(0)
+		},
+		|columns| columns as usize,
+	)
+}
+
+/// Font settings with defaults applied.
+struct Metrics {
+	size:        f64,
+	line_height: f64,
+	cell_width:  Option<f64>,
+}
+
+fn metrics(font: Option<&ScreenshotFont>) -> Metrics {
+	let size = font.and_then(|f| f.size).unwrap_or(DEFAULT_FONT_SIZE);
+	Metrics {
+		size,
+		line_height: size
+			* font
+				.and_then(|f| f.line_height)
+				.unwrap_or(DEFAULT_LINE_HEIGHT),
+		cell_width: font.and_then(|f| f.cell_width),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// SVG
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Round a coordinate to hundredths to keep the markup short.
+fn px(value: f64) -> f64 {
+	(value * 100.0).round() / 100.0
+}
+
+pub(crate) fn hex(color: Rgb) -> String {
+	format!("#{:02x}{:02x}{:02x}", color.0, color.1, color.2)
+}
+
+pub(crate) fn escape_xml(text: &str, out: &mut String) {
+	for ch in text.chars() {
+		match ch {
+			'&' => out.push_str("&amp;"),
+			'<' => out.push_str("&lt;"),
+			'>' => out.push_str("&gt;"),
+			'"' => out.push_str("&quot;"),
+			'\'' => out.push_str("&apos;"),
+			ch => out.push(ch),
+		}
+	}
+}
+
+fn tspan_attributes(style: &CellStyle, out: &mut String) {
+	let _ = write!(out, " fill=\"{}\"", hex(style.fg));
+	if style.dim {
+		out.push_str(" fill-opacity=\"0.5\"");
+	}
+	if style.bold {
+		out.push_str(" font-weight=\"bold\"");
+	}
+	if style.italic {
+		out.push_str(" font-style=\"italic\"");
+	}
+	match (style.underline, style.strike) {
+		(true, true) => out.push_str(" text-decoration=\"underline line-through\""),
+		(true, false) => out.push_str(" text-decoration=\"underline\""),
+		(false, true) => out.push_str(" text-decoration=\"line-through\""),
+		(false, false) => {},
+	}
+}
+
+fn build_svg(lines: &[String], options: Option<&ScreenshotOptions>) -> Result<String> {
+	let palette = Palette::from_theme(options.and_then(|o| o.theme.as_ref()))?;
+	let font = options.and_then(|o| o.font.as_ref());
+	let metrics = metrics(font);
+	let family = font
+		.and_then(|f| f.family.as_deref())
+		.unwrap_or(DEFAULT_FONT_FAMILY);
+	let padding = f64::from(options.and_then(|o| o.padding).unwrap_or(DEFAULT_PADDING));
+	let cell_width = metrics
+		.cell_width
+		.unwrap_or(metrics.size * DEFAULT_CELL_RATIO);
+	let rows = layout(lines, &palette);
+	let columns = grid_columns(&rows, options.and_then(|o| o.columns));
+	let width = (columns as f64).mul_add(cell_width, padding * 2.0).ceil();
+	let height = (rows.len() as f64)
+		.mul_add(metrics.line_height, padding * 2.0)
+		.ceil();
+
+	let mut svg = String::new();
+	let _ = write!(
+		svg,
+		"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+		 viewBox=\"0 0 {width} {height}\">"
+	);
+	let _ =
+		write!(svg, "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>", hex(palette.background));
+	svg.push_str("<g font-family=\"");
+	escape_xml(family, &mut svg);
+	let _ = write!(svg, "\" font-size=\"{}\" xml:space=\"preserve\">", metrics.size);
+
+	for (row, cells) in rows.iter().enumerate() {
+		let top = px((row as f64).mul_add(metrics.line_height, padding));
+		let x = |column: usize| px((column as f64).mul_add(cell_width, padding));
+		let visible = cells
+			.iter()
+			.filter(|cell| cell.column + cell.width <= columns);
+		// Backgrounds, merged across neighboring cells of the same color.
+		let mut runs: Vec<(usize, usize, Rgb)> = Vec::new();
+		for cell in visible.clone() {
+			let Some(bg) = cell.style.bg else {
+				continue;
+			};
+			let end = cell.column + cell.width;
+			match runs.last_mut() {
+				Some((_, stop, color)) if *stop == cell.column && *color == bg => *stop = end,
+				_ => runs.push((cell.column, end, bg)),
+			}
+		}
+		for (start, stop, color) in runs {
+			let _ = write!(
+				svg,
+				"<rect x=\"{}\" y=\"{top}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+				x(start),
+				px((stop - start) as f64 * cell_width),
+				px(metrics.line_height),
+				hex(color)
+			);
+		}
+
+		let middle = px(top + metrics.line_height / 2.0);
+		let _ = write!(svg, "<text y=\"{middle}\" dominant-baseline=\"central\">");
+		let mut open: Option<(CellStyle, usize)> = None;
+		for cell in visible {
+			// Runs of single-width cells share a span; anything wider starts a new
+			// span at its own cell so the font's advance cannot shift later text.
+			let continues =
+				open.is_some_and(|(style, next)| style == cell.style && next == cell.column);
+			if cell.style.hidden || (!continues && cell.text.trim().is_empty()) {
+				continue;
+			}
+			if !continues {
+				if open.is_some() {
+					svg.push_str("</tspan>");
+				}
+				let _ = write!(svg, "<tspan x=\"{}\"", x(cell.column));
+				tspan_attributes(&cell.style, &mut svg);
+				svg.push('>');
+			}
+			escape_xml(&cell.text, &mut svg);
+			open = (cell.width == 1).then_some((cell.style, cell.column + 1));
+			if open.is_none() {
+				svg.push_str("</tspan>");
+			}
+		}
+		if open.is_some() {
+			svg.push_str("</tspan>");
+		}
+		svg.push_str("</text>");
+	}
+	svg.push_str("</g></svg>");
+	Ok(svg)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// PNG
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn blend(image: &mut RgbaImage, x: i64, y: i64, color: Rgb, alpha: f32) {
+	let (Ok(x), Ok(y)) = (u32::try_from(x), u32::try_from(y)) else {
+		return;
+	};
+	if x >= image.width() || y >= image.height() || alpha <= 0.0 {
+		return;
+	}
+	let alpha = alpha.min(1.0);
+	let Rgba([red, green, blue, _]) = *image.get_pixel(x, y);
+	let mix = |under: u8, over: u8| {
+		f32::from(over)
+			.mul_add(alpha, f32::from(under) * (1.0 - alpha))
+			.round() as u8
+	};
+	image.put_pixel(x, y, Rgba([mix(red, color.0), mix(green, color.1), mix(blue, color.2), 255]));
+}
+
+fn fill_rect(image: &mut RgbaImage, x: f64, y: f64, width: f64, height: f64, color: Rgb) {
+	for py in y.round() as i64..(y + height).round() as i64 {
+		for px in x.round() as i64..(x + width).round() as i64 {
+			blend(image, px, py, color, 1.0);
+		}
+	}
+}
+
+fn build_png(
+	lines: &[String],
+	options: Option<&ScreenshotOptions>,
+	font_data: Vec<u8>,
+) -> Result<Vec<u8>> {
+	let font = FontVec::try_from_vec(font_data)
+		.map_err(|err| Error::from_reason(format!("Invalid font: {err}")))?;
+	let palette = Palette::from_theme(options.and_then(|o| o.theme.as_ref()))?;
+	let metrics = metrics(options.and_then(|o| o.font.as_ref()));
+	let scale = options.and_then(|o| o.scale).unwrap_or(1.0);
+	if !(scale > 0.0 && scale <= 8.0) {
+		return Err(Error::from_reason("Scale must be between 0 and 8"));
+	}
+	let px = PxScale::from((metrics.size * scale) as f32);
+	let scaled = font.as_scaled(px);
+	let cell_width = metrics
+		.cell_width
+		.map_or_else(|| f64::from(scaled.h_advance(font.glyph_id('M'))), |width| width * scale);
+	let line_height = metrics.line_height * scale;
+	let padding = f64::from(options.and_then(|o| o.padding).unwrap_or(DEFAULT_PADDING)) * scale;
+	let rows = layout(lines, &palette);
+	let columns = grid_columns(&rows, options.and_then(|o| o.columns));
+	let width = (columns as f64).mul_add(cell_width, padding * 2.0).ceil();
+	let height = (rows.len() as f64)
+		.mul_add(line_height, padding * 2.0)
+		.ceil();
+	if width * height > 100_000_000.0 {
+		return Err(Error::from_reason("Screenshot is too large"));
+	}
+
+	let bg = palette.background;
+	let mut image =
+		RgbaImage::from_pixel(width as u32, height as u32, Rgba([bg.0, bg.1, bg.2, 255]));
+	let ascent = f64::from(scaled.ascent());
+	let glyph_height = ascent - f64::from(scaled.descent());
+	let stroke = scale.max(1.0);
+
+	for (row, cells) in rows.iter().enumerate() {
+		let top = (row as f64).mul_add(line_height, padding);
+		let baseline = top + (line_height - glyph_height) / 2.0 + ascent;
+		for cell in cells
+			.iter()
+			.filter(|cell| cell.column + cell.width <= columns)
+		{
+			let x = (cell.column as f64).mul_add(cell_width, padding);
+			let span = cell.width as f64 * cell_width;
+			if let Some(color) = cell.style.bg {
+				fill_rect(&mut image, x, top, span, line_height, color);
+			}
+			if cell.style.hidden {
+				continue;
+			}
+			let style = cell.style;
+			let alpha = if style.dim { 0.5 } else { 1.0 };
+			if let Some(ch) = cell.text.chars().next() {
+				let glyph = font
+					.glyph_id(ch)
+					.with_scale_and_position(px, point(x as f32, baseline as f32));
+				if let Some(outline) = font.outline_glyph(glyph) {
+					let bounds = outline.px_bounds();
+					// Bold is drawn twice, one pixel apart.
+					let passes: &[i64] = if style.bold { &[0, 1] } else { &[0] };
+					for &shift in passes {
+						outline.draw(|gx, gy, coverage| {
+							// Italic is approximated by shearing rows right.
+							let slant = if style.italic {
+								((f64::from(bounds.max.y) - f64::from(gy) - f64::from(bounds.min.y)) * 0.2)
+									as i64
+							} else {
+								0
+							};
+							blend(
+								&mut image,
+								bounds.min.x as i64 + i64::from(gx) + shift + slant,
+								bounds.min.y as i64 + i64::from(gy),
+								style.fg,
+								coverage * alpha,
+							);
+						});
+					}
+				}
+			}
+			if style.underline {
+				fill_rect(&mut image, x, baseline + stroke, span, stroke, style.fg);
+			}
+			if style.strike {
+				fill_rect(&mut image, x, ascent.mul_add(-0.3, baseline), span, stroke, style.fg);
+			}
+		}
+	}
+
+	let mut buffer = Vec::new();
+	DynamicImage::ImageRgba8(image)
+		.write_to(&mut Cursor::new(&mut buffer), ImageFormat::Png)
+		.map_err(|e| Error::from_reason(format!("Failed to encode PNG: {e}")))?;
+	Ok(buffer)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Render styled terminal lines as a standalone SVG image.
+///
+/// # Arguments
+/// - `lines`: Lines with ANSI escape sequences, one per row.
+/// - `options`: Theme, font, padding, and grid width.
+///
+/// # Errors
+/// Returns an error for malformed theme colors.
+#[napi(js_name = "renderToSvg")]
+pub fn render_to_svg(lines: Vec<String>, options: Option<ScreenshotOptions>) -> Result<String> {
+	build_svg(&lines, options.as_ref())
+}
+
+/// Rasterize styled terminal lines to a PNG with the font at `font.path`.
+///
+/// # Arguments
+/// - `lines`: Lines with ANSI escape sequences, one per row.
+/// - `options`: Theme, font (with `path`), padding, grid width, and scale.
+///
+/// # Errors
+/// Returns an error if no font path is given, the font cannot be read or
+/// parsed, a theme color is malformed, or the image would be too large.
+#[napi(js_name = "renderToPng")]
+pub fn render_to_png(
+	lines: Vec<String>,
+	options: Option<ScreenshotOptions>,
+) -> task::Async<Vec<u8>> {
+	task::blocking("screenshot.png", (), move |_| {
+		let path = options
+			.as_ref()
+			.and_then(|o| o.font.as_ref())
+			.and_then(|f| f.path.as_deref())
+			.ok_or_else(|| Error::from_reason("renderToPng requires font.path"))?;
+		let font_data = std::fs::read(path)
+			.map_err(|err| Error::from_reason(format!("Failed to read font {path}: {err}")))?;
+		build_png(&lines, options.as_ref(), font_data)
+	})
+}
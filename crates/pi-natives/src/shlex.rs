@@ -0,0 +1,306 @@
+//! Shell-style argument splitting and quoting.
+//!
+//! # Overview
+//! Splits a command line into arguments and quotes arguments back into a
+//! command line, for either a POSIX shell or `cmd.exe`. POSIX mode follows
+//! `sh` word rules (single quotes, double quotes with `\` escapes, backslash
+//! escapes, `#` comments). Cmd mode first removes `cmd.exe` caret escapes and
+//! then applies the `CommandLineToArgvW` rules used by the MSVC runtime, so
+//! `shlexSplit(shlexQuote(args, mode), mode)` round-trips in both modes.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.shlexSplit(`git commit -m "it's done"`) // -> ["git", "commit", "-m", "it's done"]
+//! // JS: native.shlexQuote(["echo", "a b", "it's"]) // -> "echo 'a b' 'it'\"'\"'s'"
+//! // JS: native.shlexQuote(["echo", "a&b"], "cmd") // -> "echo ^\"a^&b^\""
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Command-line dialect.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShellMode {
+	Posix,
+	Cmd,
+}
+
+impl ShellMode {
+	/// Parse a JS-facing name (`"posix"` or `"cmd"`); `None` means POSIX.
+	///
+	/// # Errors
+	/// Returns an error for unknown names.
+	pub fn parse(name: Option<&str>) -> Result<Self> {
+		match name {
+			None | Some("posix") => Ok(Self::Posix),
+			Some("cmd") => Ok(Self::Cmd),
+			Some(other) => Err(Error::from_reason(format!("Unknown shell mode: {other}"))),
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// POSIX
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Split a command line using POSIX shell word rules.
+///
+/// # Errors
+/// Returns an error for unterminated quotes or a trailing backslash.
+pub fn split_posix(line: &str) -> Result<Vec<String>> {
+	let mut words = Vec::new();
+	let mut word = String::new();
+	// Distinguishes an empty quoted word (`''`) from no word at all.
+	let mut in_word = false;
+	let mut chars = line.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			' ' | '\t' | '\n' => {
+				if in_word {
+					words.push(std::mem::take(&mut word));
+					in_word = false;
+				}
+			},
+			'#' if !in_word => {
+				// Comment: skip to end of line.
+				for c in chars.by_ref() {
+					if c == '\n' {
+						break;
+					}
+				}
+			},
+			'\\' => match chars.next() {
+				Some('\n') => {},
+				Some(next) => {
+					word.push(next);
+					in_word = true;
+				},
+				None => return Err(Error::from_reason("Trailing backslash in command")),
+			},
+			'\'' => {
+				in_word = true;
+				loop {
+					match chars.next() {
+						Some('\'') => break,
+						Some(c) => word.push(c),
+						None => return Err(Error::from_reason("Unterminated single quote in command")),
+					}
+				}
+			},
+			'"' => {
+				in_word = true;
+				loop {
+					match chars.next() {
+						Some('"') => break,
+						Some('\\') => match chars.next() {
+							Some('\n') => {},
+							Some(next @ ('$' | '`' | '"' | '\\')) => word.push(next),
+							Some(next) => {
+								word.push('\\');
+								word.push(next);
+							},
+							None => {
+								return Err(Error::from_reason("Unterminated double quote in command"));
+							},
+						},
+						Some(c) => word.push(c),
+						None => return Err(Error::from_reason("Unterminated double quote in command")),
+					}
+				}
+			},
+			c => {
+				word.push(c);
+				in_word = true;
+			},
+		}
+	}
+	if in_word {
+		words.push(word);
+	}
+	Ok(words)
+}
+
+/// Quote one argument for a POSIX shell. Safe words are returned unchanged;
+/// everything else is single-quoted.
+pub fn quote_posix(arg: &str) -> String {
+	let safe = |c: char| c.is_ascii_alphanumeric() || "@%+=:,./_-".contains(c);
+	if !arg.is_empty() && arg.chars().all(safe) {
+		return arg.to_string();
+	}
+	format!("'{}'", arg.replace('\'', "'\"'\"'"))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// cmd.exe
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Characters `cmd.exe` interprets outside double quotes.
+const CMD_META: &str = "()%!^\"<>&|";
+
+/// Remove `cmd.exe` caret escapes. Carets inside double quotes are literal.
+fn cmd_unescape(line: &str) -> String {
+	let mut out = String::with_capacity(line.len());
+	let mut in_quote = false;
+	let mut chars = line.chars();
+	while let Some(c) = chars.next() {
+		match c {
+			'^' if !in_quote => {
+				// An escaped quote is passed through without toggling quote state.
+				if let Some(next) = chars.next() {
+					out.push(next);
+				}
+			},
+			'"' => {
+				in_quote = !in_quote;
+				out.push(c);
+			},
+			c => out.push(c),
+		}
+	}
+	out
+}
+
+/// Split a program command line with `CommandLineToArgvW` rules.
+fn split_argv(line: &str) -> Vec<String> {
+	let mut args = Vec::new();
+	let mut arg = String::new();
+	let mut in_arg = false;
+	let mut in_quote = false;
+	let mut chars = line.chars().peekable();
+	while let Some(c) = chars.next() {
+		match c {
+			' ' | '\t' | '\n' if !in_quote => {
+				if in_arg {
+					args.push(std::mem::take(&mut arg));
+					in_arg = false;
+				}
+			},
+			'\\' => {
+				let mut slashes = 1;
+				while chars.next_if_eq(&'\\').is_some() {
+					slashes += 1;
+				}
+				in_arg = true;
+				if chars.peek() == Some(&'"') {
+					// 2n backslashes + quote: n backslashes, quote toggles.
+					// 2n+1 backslashes + quote: n backslashes and a literal quote.
+					arg.extend(std::iter::repeat_n('\\', slashes / 2));
+					if slashes % 2 == 1 {
+						chars.next();
+						arg.push('"');
+					}
+				} else {
+					arg.extend(std::iter::repeat_n('\\', slashes));
+				}
+			},
+			'"' => {
+				in_arg = true;
+				if in_quote && chars.peek() == Some(&'"') {
+					// `""` inside quotes is a literal quote.
+					chars.next();
+					arg.push('"');
+				} else {
+					in_quote = !in_quote;
+				}
+			},
+			c => {
+				arg.push(c);
+				in_arg = true;
+			},
+		}
+	}
+	if in_arg {
+		args.push(arg);
+	}
+	args
+}
+
+/// Split a `cmd.exe` command line into program arguments.
+pub fn split_cmd(line: &str) -> Vec<String> {
+	split_argv(&cmd_unescape(line))
+}
+
+/// Quote one argument so `CommandLineToArgvW` reads it back unchanged, then
+/// caret-escape it if it contains `cmd.exe` metacharacters.
+pub fn quote_cmd(arg: &str) -> String {
+	let needs_quotes = arg.is_empty() || arg.contains([' ', '\t', '\n', '\x0b', '"']);
+	let quoted = if needs_quotes {
+		let mut out = String::with_capacity(arg.len() + 2);
+		out.push('"');
+		let mut slashes = 0;
+		for c in arg.chars() {
+			match c {
+				'\\' => slashes += 1,
+				'"' => {
+					out.extend(std::iter::repeat_n('\\', slashes * 2 + 1));
+					out.push('"');
+					slashes = 0;
+				},
+				c => {
+					out.extend(std::iter::repeat_n('\\', slashes));
+					out.push(c);
+					slashes = 0;
+				},
+			}
+		}
+		// Backslashes before the closing quote must be doubled.
+		out.extend(std::iter::repeat_n('\\', slashes * 2));
+		out.push('"');
+		out
+	} else {
+		arg.to_string()
+	};
+	if !arg.contains(|c: char| CMD_META.contains(c)) {
+		return quoted;
+	}
+	let mut out = String::with_capacity(quoted.len() * 2);
+	for c in quoted.chars() {
+		if CMD_META.contains(c) {
+			out.push('^');
+		}
+		out.push(c);
+	}
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Split a command line into arguments.
+///
+/// # Arguments
+/// - `command`: Command line to split.
+/// - `mode`: `"posix"` (default) or `"cmd"`.
+///
+/// # Errors
+/// Returns an error for an unknown mode, or in POSIX mode for unterminated
+/// quotes and trailing backslashes.
+#[napi(js_name = "shlexSplit")]
+pub fn shlex_split(command: String, mode: Option<String>) -> Result<Vec<String>> {
+	match ShellMode::parse(mode.as_deref())? {
+		ShellMode::Posix => split_posix(&command),
+		ShellMode::Cmd => Ok(split_cmd(&command)),
+	}
+}
+
+/// Quote arguments into a single command line.
+///
+/// # Arguments
+/// - `args`: Arguments to quote, program first.
+/// - `mode`: `"posix"` (default) or `"cmd"`.
+///
+/// # Errors
+/// Returns an error for an unknown mode.
+#[napi(js_name = "shlexQuote")]
+pub fn shlex_quote(args: Vec<String>, mode: Option<String>) -> Result<String> {
+	let quote = match ShellMode::parse(mode.as_deref())? {
+		ShellMode::Posix => quote_posix,
+		ShellMode::Cmd => quote_cmd,
+	};
+	Ok(args.iter().map(|arg| quote(arg)).collect::<Vec<_>>().join(" "))
+}
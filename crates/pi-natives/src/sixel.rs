@@ -0,0 +1,333 @@
+//! Sixel encoding for inline terminal images.
+//!
+//! # Overview
+//! Converts an RGBA buffer into a DEC sixel string for VT340-compatible
+//! terminals. Colors are reduced to at most `maxColors` entries with median
+//! cut over a 15-bit histogram, optionally Floyd-Steinberg dithered, and each
+//! six-row band is emitted per color with run-length encoding. Pixels with
+//! alpha below 128 are left transparent. Encoding runs on the worker pool.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const sixel = await native.encodeSixel(rgba, 320, 200, { maxColors: 64 })
+//! // JS: process.stdout.write(sixel)
+//! ```
+
+use std::{collections::HashMap, fmt::Write};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `encodeSixel`.
+#[napi(object)]
+pub struct SixelOptions {
+	/// Palette size, 2-256 (default: 256).
+	#[napi(js_name = "maxColors")]
+	pub max_colors: Option<u32>,
+	/// Apply Floyd-Steinberg dithering (default: true).
+	pub dither:     Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Quantization
+// ═══════════════════════════════════════════════════════════════════════════
+
+type Rgb = [u8; 3];
+
+/// Alpha below which a pixel is treated as transparent.
+const ALPHA_CUTOFF: u8 = 128;
+
+/// 15-bit histogram key (5 bits per channel).
+const fn key(rgb: Rgb) -> usize {
+	((rgb[0] as usize >> 3) << 10) | ((rgb[1] as usize >> 3) << 5) | (rgb[2] as usize >> 3)
+}
+
+/// A histogram cell: pixel count and channel sums.
+#[derive(Clone, Copy)]
+struct Cell {
+	count: u64,
+	sum:   [u64; 3],
+}
+
+impl Cell {
+	fn mean(&self) -> Rgb {
+		let channel = |i: usize| (self.sum[i] / self.count.max(1)) as u8;
+		[channel(0), channel(1), channel(2)]
+	}
+}
+
+/// Build a palette of at most `max_colors` entries by median cut.
+fn build_palette(rgba: &[u8], max_colors: usize) -> Vec<Rgb> {
+	let mut histogram: HashMap<usize, Cell> = HashMap::new();
+	for px in rgba
+		.as_chunks::<4>()
+		.0
+		.iter()
+		.filter(|px| px[3] >= ALPHA_CUTOFF)
+	{
+		let cell = histogram
+			.entry(key([px[0], px[1], px[2]]))
+			.or_insert(Cell { count: 0, sum: [0; 3] });
+		cell.count += 1;
+		for (sum, &value) in cell.sum.iter_mut().zip(px) {
+			*sum += u64::from(value);
+		}
+	}
+	let cells: Vec<Cell> = histogram.into_values().collect();
+	if cells.len() <= max_colors {
+		return cells.iter().map(Cell::mean).collect();
+	}
+
+	let mut boxes = vec![cells];
+	while boxes.len() < max_colors {
+		// Split the box with the widest channel range.
+		let widest = boxes
+			.iter()
+			.enumerate()
+			.filter(|(_, cells)| cells.len() > 1)
+			.map(|(index, cells)| {
+				let (channel, range) = widest_channel(cells);
+				(index, channel, range)
+			})
+			.max_by_key(|&(_, _, range)| range);
+		let Some((index, channel, _)) = widest else {
+			break;
+		};
+		let mut cells = boxes.swap_remove(index);
+		cells.sort_unstable_by_key(|cell| cell.mean()[channel]);
+		let total: u64 = cells.iter().map(|cell| cell.count).sum();
+		let mut seen = 0;
+		let mut split = cells.len() / 2;
+		for (i, cell) in cells.iter().enumerate() {
+			seen += cell.count;
+			if seen * 2 >= total {
+				split = (i + 1).clamp(1, cells.len() - 1);
+				break;
+			}
+		}
+		let upper = cells.split_off(split);
+		boxes.push(cells);
+		boxes.push(upper);
+	}
+
+	boxes
+		.iter()
+		.map(|cells| {
+			let mut merged = Cell { count: 0, sum: [0; 3] };
+			for cell in cells {
+				merged.count += cell.count;
+				for i in 0..3 {
+					merged.sum[i] += cell.sum[i];
+				}
+			}
+			merged.mean()
+		})
+		.collect()
+}
+
+fn widest_channel(cells: &[Cell]) -> (usize, u8) {
+	let mut lo = [u8::MAX; 3];
+	let mut hi = [0u8; 3];
+	for rgb in cells.iter().map(Cell::mean) {
+		for i in 0..3 {
+			lo[i] = lo[i].min(rgb[i]);
+			hi[i] = hi[i].max(rgb[i]);
+		}
+	}
+	(0..3)
+		.map(|i| (i, hi[i] - lo[i]))
+		.max_by_key(|&(_, range)| range)
+		.unwrap_or((0, 0))
+}
+
+/// Nearest-palette lookup, cached per 15-bit color.
+struct Mapper<'a> {
+	palette: &'a [Rgb],
+	cache:   Vec<u16>,
+}
+
+impl<'a> Mapper<'a> {
+	fn new(palette: &'a [Rgb]) -> Self {
+		Self { palette, cache: vec![u16::MAX; 1 << 15] }
+	}
+
+	fn index(&mut self, rgb: Rgb) -> u16 {
+		let slot = key(rgb);
+		if self.cache[slot] == u16::MAX {
+			let distance = |p: &Rgb| {
+				(0..3)
+					.map(|i| (i32::from(p[i]) - i32::from(rgb[i])).pow(2))
+					.sum::<i32>()
+			};
+			let nearest = (0..self.palette.len())
+				.min_by_key(|&i| distance(&self.palette[i]))
+				.unwrap_or(0);
+			self.cache[slot] = nearest as u16;
+		}
+		self.cache[slot]
+	}
+}
+
+/// Map every pixel to a palette index; `None` marks transparent pixels.
+fn map_pixels(rgba: &[u8], width: usize, palette: &[Rgb], dither: bool) -> Vec<Option<u16>> {
+	let mut mapper = Mapper::new(palette);
+	let mut out = Vec::with_capacity(rgba.len() / 4);
+	if !dither {
+		for px in rgba.as_chunks::<4>().0 {
+			out.push((px[3] >= ALPHA_CUTOFF).then(|| mapper.index([px[0], px[1], px[2]])));
+		}
+		return out;
+	}
+
+	// Floyd-Steinberg with error rows for the current and next scanline.
+	let mut current = vec![[0i32; 3]; width + 2];
+	let mut next = vec![[0i32; 3]; width + 2];
+	for row in rgba.chunks_exact(width * 4) {
+		for (x, px) in row.as_chunks::<4>().0.iter().enumerate() {
+			if px[3] < ALPHA_CUTOFF {
+				out.push(None);
+				continue;
+			}
+			let err = current[x + 1];
+			let wanted: [i32; 3] =
+				std::array::from_fn(|i| (i32::from(px[i]) + err[i] / 16).clamp(0, 255));
+			let index = mapper.index(wanted.map(|v| v as u8));
+			out.push(Some(index));
+			let chosen = palette[usize::from(index)];
+			for i in 0..3 {
+				let delta = wanted[i] - i32::from(chosen[i]);
+				current[x + 2][i] += delta * 7;
+				next[x][i] += delta * 3;
+				next[x + 1][i] += delta * 5;
+				next[x + 2][i] += delta;
+			}
+		}
+		std::mem::swap(&mut current, &mut next);
+		next.fill([0; 3]);
+	}
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Encoding
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Append one run of a sixel character, run-length encoded when worthwhile.
+fn push_run(out: &mut String, sixel: u8, run: usize) {
+	let ch = char::from(0x3f + sixel);
+	if run > 3 {
+		let _ = write!(out, "!{run}{ch}");
+	} else {
+		out.extend(std::iter::repeat_n(ch, run));
+	}
+}
+
+/// Encode RGBA pixels as a sixel string.
+pub fn encode(rgba: &[u8], width: usize, height: usize, max_colors: usize, dither: bool) -> String {
+	// P2=1: pixels left at 0 keep the background (transparency).
+	let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+	if width == 0 || height == 0 {
+		out.push_str("\x1b\\");
+		return out;
+	}
+	let palette = build_palette(rgba, max_colors);
+	let indices = map_pixels(rgba, width, &palette, dither);
+	for (i, rgb) in palette.iter().enumerate() {
+		let pct = |v: u8| (u32::from(v) * 100 + 127) / 255;
+		let _ = write!(out, "#{i};2;{};{};{}", pct(rgb[0]), pct(rgb[1]), pct(rgb[2]));
+	}
+
+	// Per-band sixel bits for each color, and which colors the band uses.
+	let mut bits = vec![0u8; palette.len() * width];
+	let mut used = vec![false; palette.len()];
+	let mut order = Vec::new();
+	for band in 0..height.div_ceil(6) {
+		for dy in 0..6 {
+			let y = band * 6 + dy;
+			if y >= height {
+				break;
+			}
+			for x in 0..width {
+				if let Some(color) = indices[y * width + x] {
+					let color = usize::from(color);
+					bits[color * width + x] |= 1 << dy;
+					if !used[color] {
+						used[color] = true;
+						order.push(color);
+					}
+				}
+			}
+		}
+		for (n, &color) in order.iter().enumerate() {
+			if n > 0 {
+				out.push('$');
+			}
+			let _ = write!(out, "#{color}");
+			let row = &mut bits[color * width..(color + 1) * width];
+			// Trailing empty columns need not be written.
+			let end = row.iter().rposition(|&b| b != 0).map_or(0, |last| last + 1);
+			let mut x = 0;
+			while x < end {
+				let sixel = row[x];
+				let run = row[x..end].iter().take_while(|&&b| b == sixel).count();
+				push_run(&mut out, sixel, run);
+				x += run;
+			}
+			row.fill(0);
+			used[color] = false;
+		}
+		order.clear();
+		out.push('-');
+	}
+	out.push_str("\x1b\\");
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Encode an RGBA image as a sixel string on the worker pool.
+///
+/// # Arguments
+/// - `rgba`: Pixel data, 4 bytes per pixel, row-major.
+/// - `width`, `height`: Image dimensions in pixels.
+/// - `options`: Palette size and dithering.
+///
+/// # Returns
+/// A complete sixel sequence (DCS ... ST) ready to write to the terminal.
+///
+/// # Errors
+/// Returns an error if the buffer length does not match the dimensions.
+#[napi(js_name = "encodeSixel")]
+pub fn encode_sixel(
+	rgba: Uint8Array,
+	width: u32,
+	height: u32,
+	options: Option<SixelOptions>,
+) -> task::Async<String> {
+	let (width, height) = (width as usize, height as usize);
+	let (max_colors, dither) = match options {
+		Some(opts) => (opts.max_colors, opts.dither),
+		None => (None, None),
+	};
+	let max_colors = max_colors.unwrap_or(256).clamp(2, 256) as usize;
+	let dither = dither.unwrap_or(true);
+	let rgba = rgba.to_vec();
+	task::blocking("encode_sixel", (), move |_| {
+		let expected = width.checked_mul(height).and_then(|n| n.checked_mul(4));
+		if expected != Some(rgba.len()) {
+			return Err(Error::from_reason(format!(
+				"RGBA buffer has {} bytes, expected {width}x{height}x4",
+				rgba.len()
+			)));
+		}
+		Ok(encode(&rgba, width, height, max_colors, dither))
+	})
+}
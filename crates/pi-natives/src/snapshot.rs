@@ -0,0 +1,358 @@
+//! File snapshots and session change sets.
+//!
+//! # Overview
+//! `snapshotFiles` records the content hash of every file under a set of
+//! paths and returns an opaque `FileSnapshot` handle. `diffSnapshot` later
+//! re-reads the same paths and reports every file that was modified, created,
+//! or deleted since, with line hunks computed by the histogram diff used by
+//! `diff`.
+//!
+//! Directories are walked with the gitignore-aware scanner used by `grep`
+//! and re-walked on diff, so files created under them are picked up. A listed
+//! file that does not exist yet is recorded as absent and reported as created
+//! once it appears.
+//!
+//! Text content is kept alongside the hash for files up to 2 MiB so that
+//! diffs can be produced later; larger and binary files are tracked by hash
+//! only and reported without hunks.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const snap = await native.snapshotFiles(["src", "README.md"])
+//! // JS: // ... edit files ...
+//! // JS: await native.diffSnapshot(snap) // -> [{ path, status, hunks, added, removed }]
+//! ```
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use rayon::prelude::*;
+
+use crate::{binary, diff, file_filter, fs_cache, hash, task};
+
+/// Text files larger than this are tracked by hash only.
+const MAX_TEXT_BYTES: usize = 2 * 1024 * 1024;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `snapshotFiles`.
+#[napi(object)]
+pub struct SnapshotOptions<'env> {
+	/// Include hidden files when walking directories (default: false).
+	pub hidden:     Option<bool>,
+	/// Respect .gitignore files when walking directories (default: true).
+	pub gitignore:  Option<bool>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Options for `diffSnapshot`.
+#[napi(object)]
+pub struct DiffSnapshotOptions<'env> {
+	/// Unchanged context lines around each hunk (default: 3).
+	pub context:    Option<u32>,
+	/// Diff algorithm: "histogram" (default), "myers", or "myersMinimal".
+	pub algorithm:  Option<String>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// A file recorded in a snapshot.
+#[napi(object)]
+pub struct SnapshotEntry {
+	/// Path as listed, or joined onto the listed directory.
+	pub path: String,
+	/// BLAKE3 hex digest of the content.
+	pub hash: String,
+	/// Size in bytes.
+	pub size: f64,
+}
+
+/// A file that changed since a snapshot was taken.
+#[napi(object)]
+pub struct SnapshotChange {
+	/// Path as listed, or joined onto the listed directory.
+	pub path:    String,
+	/// "modified", "created", or "deleted".
+	pub status:  String,
+	/// Whether either side is binary or too large to diff (no hunks).
+	pub binary:  bool,
+	/// Hunks in ascending line order.
+	pub hunks:   Vec<diff::DiffHunk>,
+	/// Number of added lines.
+	pub added:   u32,
+	/// Number of removed lines.
+	pub removed: u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Snapshot state
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Recorded state of one file.
+struct FileState {
+	hash: String,
+	size: u64,
+	/// Content for text files under the size cap.
+	text: Option<String>,
+}
+
+/// A listed directory, re-walked on diff.
+struct Root {
+	/// Path as listed by the caller.
+	display: String,
+	/// Absolute path.
+	path:    PathBuf,
+}
+
+struct SnapshotData {
+	/// Display path → absolute path and state (`None` when absent).
+	files:          BTreeMap<String, (PathBuf, Option<FileState>)>,
+	/// Directories whose contents are tracked.
+	roots:          Vec<Root>,
+	include_hidden: bool,
+	use_gitignore:  bool,
+}
+
+/// Read a file's current state; `None` when it does not exist or cannot be
+/// read.
+fn read_state(path: &Path) -> Option<FileState> {
+	let bytes = fs::read(path).ok()?;
+	let hash = hash::hash_bytes(&bytes, hash::Algorithm::Blake3);
+	let size = bytes.len() as u64;
+	let text = if bytes.len() <= MAX_TEXT_BYTES && !binary::is_binary(&bytes) {
+		String::from_utf8(bytes).ok()
+	} else {
+		None
+	};
+	Some(FileState { hash, size, text })
+}
+
+/// Join a relative path onto a listed directory for display.
+fn join_display(dir: &str, relative: &str) -> String {
+	let dir = dir.trim_end_matches(['/', '\\']);
+	if dir.is_empty() || dir == "." {
+		relative.to_string()
+	} else {
+		format!("{dir}/{relative}")
+	}
+}
+
+/// Walk the listed directories and return every file under them.
+fn walk_roots(
+	roots: &[Root],
+	include_hidden: bool,
+	use_gitignore: bool,
+	ct: &task::CancelToken,
+) -> Result<Vec<(String, PathBuf)>> {
+	let mut files = Vec::new();
+	for root in roots {
+		if !root.path.is_dir() {
+			continue;
+		}
+		let scanned = fs_cache::force_rescan(&root.path, include_hidden, use_gitignore, false, ct)?;
+		files.extend(
+			file_filter::collect_files(&root.path, &scanned, None, None)
+				.into_iter()
+				.map(|entry| (join_display(&root.display, &entry.relative_path), entry.path)),
+		);
+		ct.heartbeat()?;
+	}
+	Ok(files)
+}
+
+fn take_snapshot(
+	paths: Vec<String>,
+	include_hidden: bool,
+	use_gitignore: bool,
+	ct: &task::CancelToken,
+) -> Result<SnapshotData> {
+	let mut roots = Vec::new();
+	let mut listed = Vec::new();
+	for display in paths {
+		let path = file_filter::resolve_search_path(&display)?;
+		if path.is_dir() {
+			roots.push(Root { display, path });
+		} else {
+			listed.push((display, path));
+		}
+	}
+	listed.extend(walk_roots(&roots, include_hidden, use_gitignore, ct)?);
+
+	let states = listed
+		.into_par_iter()
+		.map(|(display, path)| {
+			ct.heartbeat()?;
+			let state = read_state(&path);
+			Ok((display, (path, state)))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(SnapshotData { files: states.into_iter().collect(), roots, include_hidden, use_gitignore })
+}
+
+/// Text of one side of a change; an absent file diffs as empty.
+fn text_of(state: Option<&FileState>) -> Option<&str> {
+	state.map_or(Some(""), |state| state.text.as_deref())
+}
+
+/// Build the change record for a file whose hash differs between two states.
+fn change_for(
+	path: String,
+	before: Option<&FileState>,
+	after: Option<&FileState>,
+	algorithm: imara_diff::Algorithm,
+	context: usize,
+) -> Option<SnapshotChange> {
+	let status = match (before, after) {
+		(None, None) => return None,
+		(Some(old), Some(new)) if old.hash == new.hash => return None,
+		(Some(_), Some(_)) => "modified",
+		(None, Some(_)) => "created",
+		(Some(_), None) => "deleted",
+	};
+	let (binary, hunks, added, removed) = match (text_of(before), text_of(after)) {
+		(Some(old), Some(new)) => {
+			let result = diff::diff_texts(old, new, algorithm, context);
+			(false, result.hunks, result.added, result.removed)
+		},
+		_ => (true, Vec::new(), 0, 0),
+	};
+	Some(SnapshotChange { path, status: status.to_string(), binary, hunks, added, removed })
+}
+
+fn diff_against(
+	data: &SnapshotData,
+	algorithm: imara_diff::Algorithm,
+	context: usize,
+	ct: &task::CancelToken,
+) -> Result<Vec<SnapshotChange>> {
+	let mut current: BTreeMap<String, PathBuf> = data
+		.files
+		.iter()
+		.map(|(display, (path, _))| (display.clone(), path.clone()))
+		.collect();
+	current.extend(walk_roots(&data.roots, data.include_hidden, data.use_gitignore, ct)?);
+	let displays: BTreeSet<&String> = current.keys().collect();
+
+	let changes = displays
+		.into_par_iter()
+		.map(|display| {
+			ct.heartbeat()?;
+			let after = read_state(&current[display]);
+			let before = data
+				.files
+				.get(display)
+				.and_then(|(_, state)| state.as_ref());
+			Ok(change_for(display.clone(), before, after.as_ref(), algorithm, context))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	Ok(changes.into_iter().flatten().collect())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Content hashes of a set of files at one point in time.
+#[napi]
+pub struct FileSnapshot {
+	data: Arc<SnapshotData>,
+}
+
+#[napi]
+impl FileSnapshot {
+	/// Number of files present when the snapshot was taken.
+	#[napi(getter, js_name = "fileCount")]
+	pub fn file_count(&self) -> u32 {
+		let present = self
+			.data
+			.files
+			.values()
+			.filter(|(_, state)| state.is_some())
+			.count();
+		u32::try_from(present).unwrap_or(u32::MAX)
+	}
+
+	/// Files present when the snapshot was taken, in path order.
+	#[napi]
+	pub fn entries(&self) -> Vec<SnapshotEntry> {
+		self
+			.data
+			.files
+			.iter()
+			.filter_map(|(display, (_, state))| {
+				state.as_ref().map(|state| SnapshotEntry {
+					path: display.clone(),
+					hash: state.hash.clone(),
+					size: state.size as f64,
+				})
+			})
+			.collect()
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Record the content of files and directories for a later `diffSnapshot`.
+///
+/// Directories are walked recursively (gitignore-aware); listed files that do
+/// not exist are recorded as absent.
+///
+/// # Errors
+/// Returns an error when the current directory cannot be resolved or the
+/// operation is cancelled or times out.
+#[napi(js_name = "snapshotFiles")]
+pub fn snapshot_files(
+	paths: Vec<String>,
+	options: Option<SnapshotOptions<'_>>,
+) -> task::Async<FileSnapshot> {
+	let (hidden, gitignore, signal, timeout_ms) = match options {
+		Some(opts) => (opts.hidden, opts.gitignore, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	task::blocking("snapshot.take", ct, move |ct| {
+		let data = take_snapshot(paths, hidden.unwrap_or(false), gitignore.unwrap_or(true), &ct)?;
+		Ok(FileSnapshot { data: Arc::new(data) })
+	})
+}
+
+/// Report files modified, created, or deleted since `snapshot` was taken.
+///
+/// # Returns
+/// Changes in path order, with line hunks for text files.
+///
+/// # Errors
+/// Returns an error for an unknown diff algorithm or when the operation is
+/// cancelled or times out.
+#[napi(js_name = "diffSnapshot")]
+pub fn diff_snapshot(
+	snapshot: &FileSnapshot,
+	options: Option<DiffSnapshotOptions<'_>>,
+) -> Result<task::Async<Vec<SnapshotChange>>> {
+	let (context, algorithm, signal, timeout_ms) = match options {
+		Some(opts) => (opts.context, opts.algorithm, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let algorithm = diff::parse_algorithm(algorithm.as_deref())?;
+	let context = context.unwrap_or(3) as usize;
+	let data = Arc::clone(&snapshot.data);
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	Ok(task::blocking("snapshot.diff", ct, move |ct| diff_against(&data, algorithm, context, &ct)))
+}
@@ -0,0 +1,250 @@
+//! Incremental Server-Sent Events parsing for streamed HTTP responses.
+//!
+//! # Overview
+//! [`SseParser`] consumes raw response bytes in arbitrary chunks and returns
+//! the events completed by each chunk, following the WHATWG event stream
+//! rules: lines end in CRLF, LF, or CR (even when a CRLF is split across
+//! chunks), a leading byte order mark is skipped, `data` fields accumulate
+//! into one newline-joined payload, comment lines are ignored, and a blank
+//! line dispatches the event. Lines are scanned in place in a single byte
+//! buffer, so a chunk costs no per-line string splitting on the JS side.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const sse = new native.SseParser();
+//! // JS: for await (const chunk of response.body) for (const e of sse.push(chunk)) handle(e.event, e.data);
+//! // JS: sse.push("event: delta\r\ndata: {\"a\":1}\r\n\r\n") // -> [{ event: "delta", data: '{"a":1}', ... }]
+//! ```
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// An event dispatched by a blank line.
+#[napi(object)]
+pub struct SseEvent {
+	/// Event type from the `event` field (default: `"message"`).
+	pub event: String,
+	/// `data` fields joined with newlines.
+	pub data:  String,
+	/// Last event ID seen on the stream so far, if any.
+	pub id:    Option<String>,
+	/// Reconnection time in milliseconds, if this event set one.
+	pub retry: Option<u32>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// State machine
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Fields of the event being assembled, plus stream-wide values.
+#[derive(Default)]
+struct Fields {
+	event:         String,
+	data:          String,
+	/// Whether any `data` field was seen for the current event.
+	has_data:      bool,
+	/// `retry` value set for the current event.
+	event_retry:   Option<u32>,
+	last_event_id: String,
+	/// Most recent valid `retry` value on the stream.
+	last_retry:    Option<u32>,
+}
+
+#[derive(Default)]
+struct Parser {
+	/// Bytes not yet terminated by a line ending.
+	buffer:     Vec<u8>,
+	/// Whether the byte-order-mark check at stream start is finished.
+	started:    bool,
+	/// Whether the previous chunk ended with CR, so a leading LF is part of
+	/// that line ending.
+	pending_cr: bool,
+	fields:     Fields,
+}
+
+impl Parser {
+	fn push(&mut self, chunk: &[u8], out: &mut Vec<SseEvent>) {
+		let mut chunk = chunk;
+		if self.pending_cr {
+			self.pending_cr = false;
+			if let Some(rest) = chunk.strip_prefix(b"\n") {
+				chunk = rest;
+			}
+		}
+		self.buffer.extend_from_slice(chunk);
+
+		if !self.started {
+			let head = self.buffer.len().min(BOM.len());
+			if self.buffer[..head] != BOM[..head] {
+				self.started = true;
+			} else if head == BOM.len() {
+				self.buffer.drain(..BOM.len());
+				self.started = true;
+			} else {
+				// Could still be a BOM split across chunks.
+				return;
+			}
+		}
+
+		let mut start = 0;
+		let mut pos = 0;
+		while let Some(offset) = self.buffer[pos..]
+			.iter()
+			.position(|&b| b == b'\n' || b == b'\r')
+		{
+			let end = pos + offset;
+			let mut next = end + 1;
+			if self.buffer[end] == b'\r' {
+				match self.buffer.get(next) {
+					Some(b'\n') => next += 1,
+					Some(_) => {},
+					None => self.pending_cr = true,
+				}
+			}
+			self.fields.line(&self.buffer[start..end], out);
+			start = next;
+			pos = next;
+		}
+		self.buffer.drain(..start);
+	}
+}
+
+impl Fields {
+	fn line(&mut self, line: &[u8], out: &mut Vec<SseEvent>) {
+		if line.is_empty() {
+			self.dispatch(out);
+			return;
+		}
+		if line[0] == b':' {
+			return;
+		}
+		let (field, value) = match line.iter().position(|&b| b == b':') {
+			Some(colon) => {
+				let value = &line[colon + 1..];
+				(&line[..colon], value.strip_prefix(b" ").unwrap_or(value))
+			},
+			None => (line, &[][..]),
+		};
+		let value = String::from_utf8_lossy(value);
+		match field {
+			b"event" => self.event = value.into_owned(),
+			b"data" => {
+				if self.has_data {
+					self.data.push('\n');
+				}
+				self.data.push_str(&value);
+				self.has_data = true;
+			},
+			b"id" if !value.contains('\0') => self.last_event_id = value.into_owned(),
+			b"retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+				let retry = value.parse().unwrap_or(u32::MAX);
+				self.event_retry = Some(retry);
+				self.last_retry = Some(retry);
+			},
+			_ => {},
+		}
+	}
+
+	/// Emit the pending event, if it has data, and start a new one.
+	fn dispatch(&mut self, out: &mut Vec<SseEvent>) {
+		let event = std::mem::take(&mut self.event);
+		let retry = self.event_retry.take();
+		if !std::mem::take(&mut self.has_data) {
+			return;
+		}
+		out.push(SseEvent {
+			event: if event.is_empty() {
+				"message".to_string()
+			} else {
+				event
+			},
+			data: std::mem::take(&mut self.data),
+			id: (!self.last_event_id.is_empty()).then(|| self.last_event_id.clone()),
+			retry,
+		});
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Incremental parser for `text/event-stream` responses.
+#[napi]
+pub struct SseParser {
+	parser: Parser,
+}
+
+#[napi]
+impl SseParser {
+	#[napi(constructor)]
+	pub fn new() -> Self {
+		Self { parser: Parser::default() }
+	}
+
+	/// Feed the next chunk of the response body; strings are processed as
+	/// UTF-8. Lines and multi-byte characters split across calls are handled.
+	///
+	/// # Returns
+	/// Events dispatched within this chunk, in stream order.
+	#[napi]
+	pub fn push(&mut self, chunk: Either<String, Uint8Array>) -> Vec<SseEvent> {
+		let mut events = Vec::new();
+		match chunk {
+			Either::A(text) => self.parser.push(text.as_bytes(), &mut events),
+			Either::B(bytes) => self.parser.push(&bytes, &mut events),
+		}
+		events
+	}
+
+	/// Signal end of the stream. A final line without a line ending is
+	/// processed, and an event still waiting for its blank line is dispatched
+	/// rather than discarded, since many servers omit the last one.
+	///
+	/// # Returns
+	/// The remaining event, if any.
+	#[napi]
+	pub fn end(&mut self) -> Option<SseEvent> {
+		let mut events = Vec::new();
+		let parser = &mut self.parser;
+		parser.started = true;
+		parser.pending_cr = false;
+		if !parser.buffer.is_empty() {
+			parser.fields.line(&parser.buffer, &mut events);
+			parser.buffer.clear();
+		}
+		parser.fields.dispatch(&mut events);
+		events.pop()
+	}
+
+	/// ID of the last event received, as sent in `Last-Event-ID` on reconnect.
+	#[napi(getter, js_name = "lastEventId")]
+	pub fn last_event_id(&self) -> Option<String> {
+		let id = &self.parser.fields.last_event_id;
+		(!id.is_empty()).then(|| id.clone())
+	}
+
+	/// Most recent reconnection time in milliseconds sent by the server.
+	#[napi(getter)]
+	pub const fn retry(&self) -> Option<u32> {
+		self.parser.fields.last_retry
+	}
+
+	/// Discard all buffered input and state, including the last event ID.
+	#[napi]
+	pub fn reset(&mut self) {
+		self.parser = Parser::default();
+	}
+}
+
+impl Default for SseParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,567 @@
+//! Workspace symbol index.
+//!
+//! # Overview
+//! `SymbolIndex` walks a workspace with the shared file walker (honoring
+//! `.gitignore` by default), extracts the symbols of every supported source
+//! file in parallel, and keeps them in memory for instant go-to-symbol
+//! queries. Files are tracked by modification time and size, so `refresh()`
+//! re-extracts only what changed and `update(paths)` applies watcher events
+//! without walking the tree.
+//!
+//! With `cachePath`, the index is persisted to a single text file: `build`
+//! loads it, re-extracts only stale files, and writes it back; `save()`
+//! writes the current state after incremental updates.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const index = await native.SymbolIndex.build(cwd, { cachePath: ".pi/symbols" })
+//! // JS: index.querySymbols("parseArgs") // exact name
+//! // JS: index.querySymbols("prsarg", { fuzzy: true, kind: "function", limit: 20 })
+//! // JS: await index.update(["src/cli.ts"]) // from watcher events
+//! ```
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::Write,
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::UNIX_EPOCH,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::RwLock;
+use rayon::prelude::*;
+
+use crate::{
+	fs_cache::{build_walker, normalize_relative_path, should_skip_path},
+	fuzzy,
+	symbols::{self, CodeSymbol, Language},
+	task,
+};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `SymbolIndex.build`.
+#[napi(object)]
+pub struct SymbolIndexOptions<'env> {
+	/// Include hidden files and directories (default: false).
+	pub hidden:        Option<bool>,
+	/// Respect .gitignore and related ignore files (default: true).
+	pub gitignore:     Option<bool>,
+	/// Skip files larger than this many bytes (default: 1 MiB).
+	#[napi(js_name = "maxFileSize")]
+	pub max_file_size: Option<f64>,
+	/// File the index is loaded from and saved to, relative to the root
+	/// unless absolute.
+	#[napi(js_name = "cachePath")]
+	pub cache_path:    Option<String>,
+	/// Abort signal for cancelling the initial build.
+	pub signal:        Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the initial build.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:    Option<u32>,
+}
+
+/// Options for `SymbolIndex.querySymbols`.
+#[napi(object)]
+pub struct SymbolQueryOptions {
+	/// Only return symbols of this kind (e.g. "function", "class").
+	pub kind:  Option<String>,
+	/// Fuzzy-match the name instead of requiring an exact match (default:
+	/// false). Smart-case: case-sensitive only when the query has an
+	/// uppercase character.
+	pub fuzzy: Option<bool>,
+	/// Maximum number of results (default: 100).
+	pub limit: Option<u32>,
+}
+
+/// A symbol returned by `SymbolIndex.querySymbols`.
+#[napi(object)]
+pub struct SymbolMatch {
+	/// File containing the symbol, relative to the index root with forward
+	/// slashes.
+	pub path:   String,
+	/// The symbol.
+	pub symbol: CodeSymbol,
+	/// Fuzzy score (higher is better); 0 for exact matches.
+	pub score:  i32,
+}
+
+/// Result of `SymbolIndex.update` and `SymbolIndex.refresh`.
+#[napi(object)]
+pub struct SymbolIndexUpdate {
+	/// Files whose symbols were (re-)extracted.
+	pub indexed: u32,
+	/// Files dropped from the index.
+	pub removed: u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Index
+// ═══════════════════════════════════════════════════════════════════════════
+
+const DEFAULT_MAX_FILE_SIZE: u64 = 1024 * 1024;
+const DEFAULT_LIMIT: usize = 100;
+const CACHE_HEADER: &str = "pi-symbol-index 1";
+
+struct FileEntry {
+	mtime:   u64,
+	size:    u64,
+	symbols: Vec<CodeSymbol>,
+}
+
+/// A source file found on disk.
+struct Candidate {
+	relative: String,
+	mtime:    u64,
+	size:     u64,
+	language: Language,
+}
+
+fn io_error(action: &str, path: &Path, err: std::io::Error) -> Error {
+	Error::from_reason(format!("Failed to {action} {}: {err}", path.display()))
+}
+
+struct Index {
+	root:          PathBuf,
+	hidden:        bool,
+	gitignore:     bool,
+	max_file_size: u64,
+	cache_path:    Option<PathBuf>,
+	files:         HashMap<String, FileEntry>,
+}
+
+impl Index {
+	/// Stat `path` and return it as a candidate if it is an indexable source
+	/// file.
+	fn candidate(&self, path: &Path) -> Option<Candidate> {
+		let language = Language::from_path(path)?;
+		let relative = normalize_relative_path(&self.root, path).into_owned();
+		if should_skip_path(Path::new(&relative), false) {
+			return None;
+		}
+		let meta = fs::metadata(path).ok()?;
+		if !meta.is_file() || meta.len() > self.max_file_size {
+			return None;
+		}
+		let mtime = meta
+			.modified()
+			.ok()
+			.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+			.map_or(0, |elapsed| elapsed.as_millis() as u64);
+		Some(Candidate { relative, mtime, size: meta.len(), language })
+	}
+
+	/// Walk `dir` (the root or a directory below it) for candidates.
+	fn walk(&self, dir: &Path, ct: &task::CancelToken) -> Result<Vec<Candidate>> {
+		let mut found = Vec::new();
+		for entry in build_walker(dir, self.hidden, self.gitignore).build() {
+			ct.heartbeat()?;
+			let Ok(entry) = entry else { continue };
+			if entry.file_type().is_some_and(|kind| kind.is_file())
+				&& let Some(candidate) = self.candidate(entry.path())
+			{
+				found.push(candidate);
+			}
+		}
+		Ok(found)
+	}
+
+	fn is_fresh(&self, candidate: &Candidate) -> bool {
+		self
+			.files
+			.get(&candidate.relative)
+			.is_some_and(|entry| entry.mtime == candidate.mtime && entry.size == candidate.size)
+	}
+
+	/// Extract symbols for `candidates` in parallel and store them. Files
+	/// that cannot be read are dropped.
+	fn index(&mut self, candidates: Vec<Candidate>, ct: &task::CancelToken) -> Result<u32> {
+		let root = &self.root;
+		let extracted: Vec<(Candidate, Option<Vec<CodeSymbol>>)> = candidates
+			.into_par_iter()
+			.map(|candidate| {
+				ct.heartbeat()?;
+				let symbols = fs::read(root.join(&candidate.relative))
+					.ok()
+					.and_then(|bytes| {
+						symbols::extract(&String::from_utf8_lossy(&bytes), candidate.language).ok()
+					});
+				Ok((candidate, symbols))
+			})
+			.collect::<Result<_>>()?;
+		let mut indexed = 0;
+		for (candidate, symbols) in extracted {
+			if let Some(symbols) = symbols {
+				let entry = FileEntry { mtime: candidate.mtime, size: candidate.size, symbols };
+				self.files.insert(candidate.relative, entry);
+				indexed += 1;
+			} else {
+				self.files.remove(&candidate.relative);
+			}
+		}
+		Ok(indexed)
+	}
+
+	/// Re-walk the root, re-extracting changed files and dropping missing
+	/// ones.
+	fn refresh(&mut self, ct: &task::CancelToken) -> Result<SymbolIndexUpdate> {
+		let candidates = self.walk(&self.root, ct)?;
+		let before = self.files.len();
+		let live: HashSet<&str> = candidates
+			.iter()
+			.map(|candidate| candidate.relative.as_str())
+			.collect();
+		self.files.retain(|path, _| live.contains(path.as_str()));
+		let removed = (before - self.files.len()) as u32;
+		let stale: Vec<Candidate> = candidates
+			.into_iter()
+			.filter(|candidate| !self.is_fresh(candidate))
+			.collect();
+		let indexed = self.index(stale, ct)?;
+		Ok(SymbolIndexUpdate { indexed, removed })
+	}
+
+	/// Apply changes to `paths` (files or directories, absolute or relative
+	/// to the root): re-extract what exists, drop what is gone.
+	fn update(&mut self, paths: &[String], ct: &task::CancelToken) -> Result<SymbolIndexUpdate> {
+		let mut stale = Vec::new();
+		let mut dropped = HashSet::new();
+		for path in paths {
+			ct.heartbeat()?;
+			let path = self.root.join(path);
+			if !path.starts_with(&self.root) {
+				continue;
+			}
+			let relative = normalize_relative_path(&self.root, &path).into_owned();
+			let prefix = format!("{relative}/");
+			self.files.retain(|file, _| {
+				let keep = !relative.is_empty() && *file != relative && !file.starts_with(&prefix);
+				if !keep {
+					dropped.insert(file.clone());
+				}
+				keep
+			});
+			if path.is_dir() {
+				stale.extend(self.walk(&path, ct)?);
+			} else if let Some(candidate) = self.candidate(&path) {
+				stale.push(candidate);
+			}
+		}
+		let indexed = self.index(stale, ct)?;
+		let removed = dropped
+			.iter()
+			.filter(|file| !self.files.contains_key(*file))
+			.count();
+		Ok(SymbolIndexUpdate { indexed, removed: removed as u32 })
+	}
+
+	fn query(&self, name: &str, kind: Option<&str>, fuzzy: bool, limit: usize) -> Vec<SymbolMatch> {
+		let (query, case_sensitive) = fuzzy::prepare_query(name);
+		let mut matches: Vec<(i32, &str, &CodeSymbol)> = self
+			.files
+			.par_iter()
+			.flat_map_iter(|(path, entry)| entry.symbols.iter().map(move |symbol| (path, symbol)))
+			.filter(|(_, symbol)| kind.is_none_or(|kind| symbol.kind == kind))
+			.filter_map(|(path, symbol)| {
+				let score = if fuzzy {
+					fuzzy::score(&query, &symbol.name, case_sensitive)?
+				} else if symbol.name == name {
+					0
+				} else {
+					return None;
+				};
+				Some((score, path.as_str(), symbol))
+			})
+			.collect();
+		matches.sort_by(|a, b| {
+			b.0.cmp(&a.0)
+				.then_with(|| a.2.name.len().cmp(&b.2.name.len()))
+				.then_with(|| a.1.cmp(b.1))
+				.then_with(|| a.2.start_byte.cmp(&b.2.start_byte))
+		});
+		matches.truncate(limit);
+		matches
+			.into_iter()
+			.map(|(score, path, symbol)| SymbolMatch {
+				path: path.to_string(),
+				symbol: symbol.clone(),
+				score,
+			})
+			.collect()
+	}
+
+	fn symbol_count(&self) -> usize {
+		self.files.values().map(|entry| entry.symbols.len()).sum()
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Persistence
+// ═══════════════════════════════════════════════════════════════════════════
+
+// Cache file layout, one tab-separated record per line:
+// - header line, then `R <root>`;
+// - `F <mtime> <size> <path>`: a file, followed by its symbols;
+// - `S <kind> <startLine> <endLine> <startByte> <endByte> <name> <parent|->
+//   <signature>`.
+// Records whose fields would contain a tab or newline are not written.
+
+fn clean(field: &str) -> bool {
+	!field.contains(['\t', '\n', '\r'])
+}
+
+fn write_symbol(out: &mut String, symbol: &CodeSymbol) {
+	let parent = symbol.parent.as_deref().unwrap_or("-");
+	if ![symbol.name.as_str(), parent, &symbol.signature]
+		.into_iter()
+		.all(clean)
+	{
+		return;
+	}
+	let _ = writeln!(
+		out,
+		"S\t{}\t{}\t{}\t{}\t{}\t{}\t{parent}\t{}",
+		symbol.kind,
+		symbol.start_line,
+		symbol.end_line,
+		symbol.start_byte,
+		symbol.end_byte,
+		symbol.name,
+		symbol.signature,
+	);
+}
+
+fn parse_symbol(line: &str) -> Option<CodeSymbol> {
+	let mut fields = line.strip_prefix("S\t")?.splitn(8, '\t');
+	let kind = fields.next()?.to_string();
+	let start_line = fields.next()?.parse().ok()?;
+	let end_line = fields.next()?.parse().ok()?;
+	let start_byte = fields.next()?.parse().ok()?;
+	let end_byte = fields.next()?.parse().ok()?;
+	let name = fields.next()?.to_string();
+	let parent = match fields.next()? {
+		"-" => None,
+		parent => Some(parent.to_string()),
+	};
+	let signature = fields.next()?.to_string();
+	Some(CodeSymbol { name, kind, parent, signature, start_line, end_line, start_byte, end_byte })
+}
+
+impl Index {
+	/// Load entries from the cache file. A missing, foreign, or corrupt
+	/// cache is ignored; it only saves work.
+	fn load(&mut self) {
+		let Some(cache) = &self.cache_path else {
+			return;
+		};
+		let Ok(text) = fs::read_to_string(cache) else {
+			return;
+		};
+		let mut lines = text.lines();
+		let root = format!("R\t{}", self.root.display());
+		if lines.next() != Some(CACHE_HEADER) || lines.next() != Some(root.as_str()) {
+			return;
+		}
+		let mut files = HashMap::new();
+		let mut current: Option<&mut FileEntry> = None;
+		for line in lines {
+			if let Some(record) = line.strip_prefix("F\t") {
+				let mut fields = record.splitn(3, '\t');
+				let (Some(mtime), Some(size), Some(path)) =
+					(fields.next(), fields.next(), fields.next())
+				else {
+					return;
+				};
+				let (Ok(mtime), Ok(size)) = (mtime.parse(), size.parse()) else {
+					return;
+				};
+				let entry = FileEntry { mtime, size, symbols: Vec::new() };
+				current = Some(files.entry(path.to_string()).insert_entry(entry).into_mut());
+			} else if let (Some(entry), Some(symbol)) = (current.as_deref_mut(), parse_symbol(line)) {
+				entry.symbols.push(symbol);
+			} else {
+				return;
+			}
+		}
+		self.files = files;
+	}
+
+	/// Write all entries to the cache file via a temp file.
+	fn save(&self) -> Result<()> {
+		let Some(cache) = &self.cache_path else {
+			return Err(Error::from_reason("SymbolIndex has no cachePath to save to"));
+		};
+		let mut out = format!("{CACHE_HEADER}\nR\t{}\n", self.root.display());
+		let mut paths: Vec<&String> = self.files.keys().filter(|path| clean(path)).collect();
+		paths.sort();
+		for path in paths {
+			let entry = &self.files[path];
+			let _ = writeln!(out, "F\t{}\t{}\t{path}", entry.mtime, entry.size);
+			for symbol in &entry.symbols {
+				write_symbol(&mut out, symbol);
+			}
+		}
+		if let Some(parent) = cache
+			.parent()
+			.filter(|parent| !parent.as_os_str().is_empty())
+		{
+			fs::create_dir_all(parent).map_err(|err| io_error("create", parent, err))?;
+		}
+		let name = cache
+			.file_name()
+			.map_or_else(Default::default, |name| name.to_string_lossy());
+		let temp = cache.with_file_name(format!(".{name}.{}.tmp", std::process::id()));
+		fs::write(&temp, out)
+			.and_then(|()| fs::rename(&temp, cache))
+			.map_err(|err| {
+				let _ = fs::remove_file(&temp);
+				io_error("write", cache, err)
+			})
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// In-memory index of the symbols defined across a workspace.
+#[napi]
+pub struct SymbolIndex {
+	index: Arc<RwLock<Index>>,
+}
+
+#[napi]
+impl SymbolIndex {
+	/// Walk `root` and extract the symbols of every supported source file.
+	/// With `cachePath`, unchanged files are loaded from the cache and the
+	/// result is saved back.
+	///
+	/// # Errors
+	/// Returns an error for an invalid `maxFileSize`, if the build is
+	/// cancelled, or if the cache cannot be written.
+	#[napi(js_name = "build")]
+	#[allow(clippy::use_self, reason = "napi expands the return type outside the impl")]
+	pub fn build(
+		root: String,
+		options: Option<SymbolIndexOptions<'_>>,
+	) -> Result<task::Async<SymbolIndex>> {
+		let (hidden, gitignore, max_file_size, cache_path, signal, timeout_ms) = match options {
+			Some(opts) => (
+				opts.hidden,
+				opts.gitignore,
+				opts.max_file_size,
+				opts.cache_path,
+				opts.signal,
+				opts.timeout_ms,
+			),
+			None => (None, None, None, None, None, None),
+		};
+		let max_file_size = match max_file_size {
+			Some(max) if !max.is_finite() || max < 0.0 => {
+				return Err(Error::from_reason(format!(
+					"maxFileSize must be a non-negative number, got {max}"
+				)));
+			},
+			Some(max) => max as u64,
+			None => DEFAULT_MAX_FILE_SIZE,
+		};
+		let ct = task::CancelToken::new(timeout_ms, signal);
+		Ok(task::blocking("symbol_index.build", ct, move |ct| {
+			let root = PathBuf::from(root);
+			let root = fs::canonicalize(&root).map_err(|err| io_error("open", &root, err))?;
+			let mut index = Index {
+				cache_path: cache_path.map(|path| root.join(path)),
+				root,
+				hidden: hidden.unwrap_or(false),
+				gitignore: gitignore.unwrap_or(true),
+				max_file_size,
+				files: HashMap::new(),
+			};
+			index.load();
+			let update = index.refresh(&ct)?;
+			if index.cache_path.is_some() && (update.indexed > 0 || update.removed > 0) {
+				index.save()?;
+			}
+			Ok(Self { index: Arc::new(RwLock::new(index)) })
+		}))
+	}
+
+	/// Find symbols by name.
+	///
+	/// # Returns
+	/// Exact name matches, or fuzzy matches when `fuzzy` is set, best first;
+	/// ties are ordered by name length, path, and position.
+	#[napi(js_name = "querySymbols")]
+	pub fn query_symbols(
+		&self,
+		name: String,
+		options: Option<SymbolQueryOptions>,
+	) -> Vec<SymbolMatch> {
+		let (kind, fuzzy, limit) = match options {
+			Some(opts) => (opts.kind, opts.fuzzy, opts.limit),
+			None => (None, None, None),
+		};
+		let limit = limit.map_or(DEFAULT_LIMIT, |limit| limit as usize);
+		self
+			.index
+			.read()
+			.query(&name, kind.as_deref(), fuzzy.unwrap_or(false), limit)
+	}
+
+	/// Symbols of one indexed file, ordered by position.
+	///
+	/// # Returns
+	/// The symbols, or `null` when the file is not indexed.
+	#[napi(js_name = "symbolsIn")]
+	pub fn symbols_in(&self, path: String) -> Option<Vec<CodeSymbol>> {
+		let index = self.index.read();
+		let path = normalize_relative_path(&index.root, &index.root.join(path)).into_owned();
+		index.files.get(&path).map(|entry| entry.symbols.clone())
+	}
+
+	/// Apply watcher events: re-extract the given files (or directories) that
+	/// exist and drop those that no longer do. Paths may be absolute or
+	/// relative to the root; paths outside the root are ignored. Files are
+	/// indexed even when ignore rules would exclude them from a walk.
+	#[napi]
+	pub fn update(&self, paths: Vec<String>) -> task::Async<SymbolIndexUpdate> {
+		let index = Arc::clone(&self.index);
+		task::blocking("symbol_index.update", (), move |ct| index.write().update(&paths, &ct))
+	}
+
+	/// Re-walk the root, re-extracting files whose size or modification time
+	/// changed and dropping files that disappeared.
+	#[napi]
+	pub fn refresh(&self) -> task::Async<SymbolIndexUpdate> {
+		let index = Arc::clone(&self.index);
+		task::blocking("symbol_index.refresh", (), move |ct| index.write().refresh(&ct))
+	}
+
+	/// Write the index to its `cachePath`.
+	///
+	/// # Errors
+	/// Returns an error if the index has no `cachePath` or it cannot be
+	/// written.
+	#[napi]
+	pub fn save(&self) -> task::Async<()> {
+		let index = Arc::clone(&self.index);
+		task::blocking("symbol_index.save", (), move |_| index.read().save())
+	}
+
+	/// Number of indexed files.
+	#[napi(getter, js_name = "fileCount")]
+	pub fn file_count(&self) -> u32 {
+		self.index.read().files.len() as u32
+	}
+
+	/// Number of indexed symbols.
+	#[napi(getter, js_name = "symbolCount")]
+	pub fn symbol_count(&self) -> u32 {
+		self.index.read().symbol_count() as u32
+	}
+}
@@ -0,0 +1,418 @@
+//! Symbol extraction (ctags-like) via tree-sitter.
+//!
+//! # Overview
+//! Parses a source file with the tree-sitter grammar for its language and
+//! lists the definitions in it: functions, methods, classes, structs, enums,
+//! interfaces/traits, type aliases, modules, and top-level constants and
+//! variables. Each symbol carries its line and byte range, a one-line
+//! signature (the definition head up to its body), and the name of the
+//! enclosing definition, which is enough to render an outline tree or give
+//! the agent a cheap structural summary of a file.
+//!
+//! Supported languages: Rust, TypeScript, TSX, JavaScript (including JSX),
+//! Python, and Go. Files with syntax errors still yield the definitions the
+//! parser could recover.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const symbols = await native.extractSymbols("src/main.rs")
+//! // JS: // -> [{ name: "main", kind: "function", startLine: 3, signature: "fn main()", ... }]
+//! // JS: await native.extractSymbols(new TextEncoder().encode(buffer), "typescript")
+//! ```
+
+use std::{cmp::Reverse, collections::HashMap, path::Path, sync::LazyLock};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use tree_sitter::{Node, Parser, Query, QueryCursor, StreamingIterator};
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A definition found in a source file.
+#[napi(object)]
+#[derive(Clone, Debug)]
+pub struct CodeSymbol {
+	/// Symbol name (for Rust `impl` blocks, the implementing type).
+	pub name:       String,
+	/// Kind: "function", "method", "class", "struct", "enum", "interface",
+	/// "type", "module", "impl", "macro", "constant", or "variable".
+	pub kind:       String,
+	/// Name of the nearest enclosing definition, if any.
+	pub parent:     Option<String>,
+	/// Definition head up to its body, whitespace-collapsed.
+	pub signature:  String,
+	/// First line of the definition (1-based).
+	#[napi(js_name = "startLine")]
+	pub start_line: u32,
+	/// Last line of the definition (1-based, inclusive).
+	#[napi(js_name = "endLine")]
+	pub end_line:   u32,
+	/// Start of the definition as a UTF-8 byte offset.
+	#[napi(js_name = "startByte")]
+	pub start_byte: u32,
+	/// End of the definition as a UTF-8 byte offset (exclusive).
+	#[napi(js_name = "endByte")]
+	pub end_byte:   u32,
+}
+
+/// Source language understood by `extractSymbols`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+	Rust,
+	TypeScript,
+	Tsx,
+	JavaScript,
+	Python,
+	Go,
+}
+
+impl Language {
+	/// Parse a language name or common alias.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name.trim().to_ascii_lowercase().as_str() {
+			"rust" | "rs" => Some(Self::Rust),
+			"typescript" | "ts" | "mts" | "cts" => Some(Self::TypeScript),
+			"tsx" => Some(Self::Tsx),
+			"javascript" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+			"python" | "py" | "pyi" => Some(Self::Python),
+			"go" | "golang" => Some(Self::Go),
+			_ => None,
+		}
+	}
+
+	/// Infer the language from a file extension.
+	pub fn from_path(path: &Path) -> Option<Self> {
+		path
+			.extension()
+			.and_then(|ext| ext.to_str())
+			.and_then(Self::from_name)
+	}
+
+	/// The tree-sitter grammar for this language.
+	pub fn grammar(self) -> tree_sitter::Language {
+		match self {
+			Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+			Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+			Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+			Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+			Self::Python => tree_sitter_python::LANGUAGE.into(),
+			Self::Go => tree_sitter_go::LANGUAGE.into(),
+		}
+	}
+
+	fn query(self) -> &'static Query {
+		match self {
+			Self::Rust => &RUST_QUERY,
+			Self::TypeScript => &TYPESCRIPT_QUERY,
+			Self::Tsx => &TSX_QUERY,
+			Self::JavaScript => &JAVASCRIPT_QUERY,
+			Self::Python => &PYTHON_QUERY,
+			Self::Go => &GO_QUERY,
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Queries
+// ═══════════════════════════════════════════════════════════════════════════
+
+// Each pattern captures the definition node under its kind and the name
+// under `@name`. When several patterns match the same node, the first one
+// wins, so specific patterns come before general ones.
+
+const RUST_PATTERNS: &str = r"
+(function_item name: (identifier) @name) @function
+(function_signature_item name: (identifier) @name) @function
+(struct_item name: (type_identifier) @name) @struct
+(union_item name: (type_identifier) @name) @struct
+(enum_item name: (type_identifier) @name) @enum
+(trait_item name: (type_identifier) @name) @interface
+(type_item name: (type_identifier) @name) @type
+(impl_item type: (_) @name) @impl
+(mod_item name: (identifier) @name) @module
+(macro_definition name: (identifier) @name) @macro
+(source_file (const_item name: (identifier) @name) @constant)
+(source_file (static_item name: (identifier) @name) @constant)
+";
+
+/// Patterns shared by JavaScript and TypeScript.
+const JAVASCRIPT_PATTERNS: &str = r#"
+(function_declaration name: (identifier) @name) @function
+(generator_function_declaration name: (identifier) @name) @function
+(class_declaration name: (_) @name) @class
+(method_definition name: (_) @name) @method
+(program (lexical_declaration
+	(variable_declarator name: (identifier) @name
+		value: [(arrow_function) (function_expression)]) @function))
+(program (export_statement (lexical_declaration
+	(variable_declarator name: (identifier) @name
+		value: [(arrow_function) (function_expression)]) @function)))
+(program (lexical_declaration kind: "const"
+	(variable_declarator name: (identifier) @name) @constant))
+(program (export_statement (lexical_declaration kind: "const"
+	(variable_declarator name: (identifier) @name) @constant)))
+(program (lexical_declaration (variable_declarator name: (identifier) @name) @variable))
+(program (export_statement (lexical_declaration
+	(variable_declarator name: (identifier) @name) @variable)))
+(program (variable_declaration (variable_declarator name: (identifier) @name) @variable))
+"#;
+
+const TYPESCRIPT_PATTERNS: &str = r"
+(function_signature name: (identifier) @name) @function
+(abstract_class_declaration name: (_) @name) @class
+(method_signature name: (_) @name) @method
+(abstract_method_signature name: (_) @name) @method
+(interface_declaration name: (_) @name) @interface
+(type_alias_declaration name: (_) @name) @type
+(enum_declaration name: (_) @name) @enum
+(internal_module name: (_) @name) @module
+";
+
+const PYTHON_PATTERNS: &str = r"
+(function_definition name: (identifier) @name) @function
+(class_definition name: (identifier) @name) @class
+(module (expression_statement (assignment left: (identifier) @name) @variable))
+";
+
+const GO_PATTERNS: &str = r"
+(function_declaration name: (identifier) @name) @function
+(method_declaration name: (field_identifier) @name) @method
+(type_spec name: (type_identifier) @name type: (struct_type)) @struct
+(type_spec name: (type_identifier) @name type: (interface_type)) @interface
+(type_spec name: (type_identifier) @name) @type
+(type_alias name: (type_identifier) @name) @type
+(source_file (const_declaration (const_spec name: (identifier) @name) @constant))
+(source_file (var_declaration (var_spec name: (identifier) @name) @variable))
+";
+
+fn compile(language: Language, patterns: &[&str]) -> Query {
+	Query::new(&language.grammar(), &patterns.concat()).expect("symbol query must compile")
+}
+
+static RUST_QUERY: LazyLock<Query> = LazyLock::new(|| compile(Language::Rust, &[RUST_PATTERNS]));
+static TYPESCRIPT_QUERY: LazyLock<Query> =
+	LazyLock::new(|| compile(Language::TypeScript, &[TYPESCRIPT_PATTERNS, JAVASCRIPT_PATTERNS]));
+static TSX_QUERY: LazyLock<Query> =
+	LazyLock::new(|| compile(Language::Tsx, &[TYPESCRIPT_PATTERNS, JAVASCRIPT_PATTERNS]));
+static JAVASCRIPT_QUERY: LazyLock<Query> =
+	LazyLock::new(|| compile(Language::JavaScript, &[JAVASCRIPT_PATTERNS]));
+static PYTHON_QUERY: LazyLock<Query> =
+	LazyLock::new(|| compile(Language::Python, &[PYTHON_PATTERNS]));
+static GO_QUERY: LazyLock<Query> = LazyLock::new(|| compile(Language::Go, &[GO_PATTERNS]));
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Extraction
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Longest signature kept, in bytes.
+const MAX_SIGNATURE: usize = 200;
+
+/// Kinds whose nested functions are reported as methods.
+const CONTAINER_KINDS: &[&str] = &["class", "struct", "enum", "interface", "impl"];
+
+fn node_text<'a>(node: Node<'_>, source: &'a str) -> &'a str {
+	source.get(node.byte_range()).unwrap_or_default()
+}
+
+/// Text from the start of `node` to its body (or first line), with runs of
+/// whitespace collapsed and trailing `{`, `:`, `;`, `=`, and `=>` removed.
+fn signature(node: Node<'_>, source: &str) -> String {
+	// Function-valued declarators (`const f = () => ...`) end at the
+	// function's body.
+	let body = node.child_by_field_name("body").or_else(|| {
+		node
+			.child_by_field_name("value")
+			.and_then(|value| value.child_by_field_name("body"))
+	});
+	let head = match body {
+		Some(body) => source
+			.get(node.start_byte()..body.start_byte())
+			.unwrap_or_default(),
+		None => node_text(node, source).lines().next().unwrap_or_default(),
+	};
+	let mut out = String::with_capacity(head.len().min(MAX_SIGNATURE));
+	for word in head.split_whitespace() {
+		if !out.is_empty() {
+			out.push(' ');
+		}
+		out.push_str(word);
+	}
+	let mut trimmed = out.as_str();
+	while let Some(rest) = trimmed
+		.strip_suffix("=>")
+		.or_else(|| trimmed.strip_suffix(['{', ':', ';', '=']))
+	{
+		trimmed = rest.trim_end();
+	}
+	out.truncate(trimmed.len());
+	if out.len() > MAX_SIGNATURE {
+		let mut cut = MAX_SIGNATURE;
+		while !out.is_char_boundary(cut) {
+			cut -= 1;
+		}
+		out.truncate(cut);
+		out.push('…');
+	}
+	out
+}
+
+/// Receiver type of a Go method, e.g. `Server` for `func (s *Server) Run()`.
+fn go_receiver(node: Node<'_>, source: &str) -> Option<String> {
+	let receiver = node.child_by_field_name("receiver")?;
+	let mut stack = vec![receiver];
+	while let Some(node) = stack.pop() {
+		if node.kind() == "type_identifier" {
+			return Some(node_text(node, source).to_string());
+		}
+		let mut cursor = node.walk();
+		stack.extend(
+			node
+				.named_children(&mut cursor)
+				.collect::<Vec<_>>()
+				.into_iter()
+				.rev(),
+		);
+	}
+	None
+}
+
+/// Extract the definitions in `source`, ordered by position.
+///
+/// # Errors
+/// Returns an error if the parser cannot be initialized or gives up.
+pub fn extract(source: &str, language: Language) -> Result<Vec<CodeSymbol>> {
+	let mut parser = Parser::new();
+	parser
+		.set_language(&language.grammar())
+		.map_err(|err| Error::from_reason(format!("Failed to load {language:?} grammar: {err}")))?;
+	let tree = parser
+		.parse(source, None)
+		.ok_or_else(|| Error::from_reason(format!("Failed to parse {language:?} source")))?;
+
+	let query = language.query();
+	let names = query.capture_names();
+	let mut cursor = QueryCursor::new();
+	let mut matches = cursor.matches(query, tree.root_node(), source.as_bytes());
+
+	// Definition node id -> (pattern index, node, kind, name); the earliest
+	// pattern matching a node wins.
+	let mut found: HashMap<usize, (usize, Node<'_>, &str, &str)> = HashMap::new();
+	while let Some(m) = matches.next() {
+		let mut definition = None;
+		let mut name = None;
+		for capture in m.captures {
+			match names[capture.index as usize] {
+				"name" => name = Some(node_text(capture.node, source)),
+				kind => definition = Some((capture.node, kind)),
+			}
+		}
+		let (Some((node, kind)), Some(name)) = (definition, name) else {
+			continue;
+		};
+		let entry = found
+			.entry(node.id())
+			.or_insert((m.pattern_index, node, kind, name));
+		if m.pattern_index < entry.0 {
+			*entry = (m.pattern_index, node, kind, name);
+		}
+	}
+	let mut found: Vec<_> = found
+		.into_values()
+		.map(|(_, node, kind, name)| (node, kind, name))
+		.collect();
+	// Outer definitions before the ones they contain.
+	found.sort_by_key(|(node, ..)| (node.start_byte(), Reverse(node.end_byte())));
+
+	let mut symbols: Vec<(usize, CodeSymbol)> = Vec::with_capacity(found.len());
+	// Indices into `symbols` of the definitions enclosing the current one.
+	let mut open: Vec<usize> = Vec::new();
+	for (node, kind, name) in found {
+		while let Some(&top) = open.last()
+			&& symbols[top].0 <= node.start_byte()
+		{
+			open.pop();
+		}
+		let parent: Option<&CodeSymbol> = open.last().map(|&index| &symbols[index].1);
+		let mut kind = kind;
+		let parent_name = match parent {
+			Some(parent) => {
+				if kind == "function" && CONTAINER_KINDS.contains(&parent.kind.as_str()) {
+					kind = "method";
+				}
+				Some(parent.name.clone())
+			},
+			None if language == Language::Go && kind == "method" => go_receiver(node, source),
+			None => None,
+		};
+		let symbol = CodeSymbol {
+			name:       name.to_string(),
+			kind:       kind.to_string(),
+			parent:     parent_name,
+			signature:  signature(node, source),
+			start_line: node.start_position().row as u32 + 1,
+			end_line:   node.end_position().row as u32 + 1,
+			start_byte: node.start_byte() as u32,
+			end_byte:   node.end_byte() as u32,
+		};
+		open.push(symbols.len());
+		symbols.push((node.end_byte(), symbol));
+	}
+	Ok(symbols.into_iter().map(|(_, symbol)| symbol).collect())
+}
+
+/// Resolve the language from an explicit name or, failing that, the path.
+fn resolve_language(language: Option<&str>, path: Option<&str>) -> Result<Language> {
+	match (language, path) {
+		(Some(name), _) => Language::from_name(name)
+			.ok_or_else(|| Error::from_reason(format!("Unsupported language: {name}"))),
+		(None, Some(path)) => Language::from_path(Path::new(path)).ok_or_else(|| {
+			Error::from_reason(format!("Cannot infer language for {path}; pass a language"))
+		}),
+		(None, None) => Err(Error::from_reason("A language is required for source bytes")),
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Extract functions, classes, and other definitions from a source file.
+///
+/// # Arguments
+/// - `input`: File path, or the UTF-8 source itself as bytes.
+/// - `language`: Language name ("rust", "typescript", "tsx", "javascript",
+///   "python", "go"); inferred from the path's extension when omitted.
+///
+/// # Returns
+/// Symbols ordered by position, outer definitions before nested ones.
+///
+/// # Errors
+/// Returns an error if the language is unsupported or cannot be inferred,
+/// or the file cannot be read.
+#[napi(js_name = "extractSymbols")]
+pub fn extract_symbols(
+	input: Either<String, Uint8Array>,
+	language: Option<String>,
+) -> Result<task::Async<Vec<CodeSymbol>>> {
+	let path = match &input {
+		Either::A(path) => Some(path.as_str()),
+		Either::B(_) => None,
+	};
+	let language = resolve_language(language.as_deref(), path)?;
+	let source = match input {
+		Either::A(path) => Either::A(path),
+		Either::B(bytes) => Either::B(bytes.to_vec()),
+	};
+	Ok(task::blocking("symbols.extract", (), move |_| {
+		let bytes = match source {
+			Either::A(path) => std::fs::read(&path)
+				.map_err(|err| Error::from_reason(format!("Failed to read {path}: {err}")))?,
+			Either::B(bytes) => bytes,
+		};
+		extract(&String::from_utf8_lossy(&bytes), language)
+	}))
+}
@@ -15,6 +15,8 @@ use std::{fs, path::Path};
 use napi_derive::napi;
 use sysinfo::{Disks, System};
 
+use crate::humanize::format_bytes;
+
 /// Basic system info without shelling out.
 #[napi(object)]
 pub struct SystemInfo {
@@ -170,24 +172,10 @@ fn format_disk_usage(used: u64, total: u64) -> String {
 	} else {
 		((used as f64 / total as f64) * 100.0).round() as u32
 	};
-	format!("{}/{} ({}%)", format_bytes(used), format_bytes(total), pct)
-}
-
-fn format_bytes(bytes: u64) -> String {
-	const KB: f64 = 1024.0;
-	const MB: f64 = KB * 1024.0;
-	const GB: f64 = MB * 1024.0;
-	const TB: f64 = GB * 1024.0;
-	let value = bytes as f64;
-	if value < KB {
-		format!("{bytes}B")
-	} else if value < MB {
-		format!("{:.1}KB", value / KB)
-	} else if value < GB {
-		format!("{:.1}MB", value / MB)
-	} else if value < TB {
-		format!("{:.1}GB", value / GB)
-	} else {
-		format!("{:.1}TB", value / TB)
-	}
+	format!(
+		"{}/{} ({}%)",
+		format_bytes(used as f64, false, 1),
+		format_bytes(total as f64, false, 1),
+		pct
+	)
 }
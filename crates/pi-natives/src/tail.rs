@@ -0,0 +1,234 @@
+//! Follow a growing file, streaming appended lines.
+//!
+//! # Overview
+//! Polls a file from the Tokio runtime and delivers complete lines appended since
+//! the last poll through a threadsafe callback. The file is re-stat'ed each
+//! poll: a shrinking length means it was truncated (reading restarts at 0),
+//! and a changed file identity means it was rotated (the old handle is drained
+//! before switching to the new file). Follow ends when the signal aborts or
+//! the timeout elapses; the promise then resolves with a summary.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.tailFollow("app.log", { pollMs: 200, signal }, ev => render(ev.lines))
+//! ```
+
+use std::{
+	fs::{self, File, Metadata},
+	io::{Read, Seek, SeekFrom},
+	path::{Path, PathBuf},
+	time::Duration,
+};
+
+use napi::{
+	bindgen_prelude::*,
+	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `tailFollow`.
+#[napi(object)]
+pub struct TailFollowOptions<'env> {
+	/// Start at the current end of file instead of the beginning (default: true).
+	#[napi(js_name = "fromEnd")]
+	pub from_end:   Option<bool>,
+	/// Poll interval in milliseconds (default: 250).
+	#[napi(js_name = "pollMs")]
+	pub poll_ms:    Option<u32>,
+	/// Abort signal that stops following.
+	pub signal:     Option<Unknown<'env>>,
+	/// Stop following after this many milliseconds.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// A batch of appended lines.
+#[napi(object)]
+pub struct TailEvent {
+	/// Complete lines appended since the previous event, without terminators.
+	pub lines:     Vec<String>,
+	/// Whether the file was truncated before these lines were read.
+	pub truncated: bool,
+	/// Whether the file was replaced (rotated) before these lines were read.
+	pub rotated:   bool,
+}
+
+/// Summary returned when following stops.
+#[napi(object)]
+pub struct TailFollowResult {
+	/// Total lines delivered.
+	pub lines:       f64,
+	/// Number of truncations observed.
+	pub truncations: u32,
+	/// Number of rotations observed.
+	pub rotations:   u32,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Following
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Identity of the file behind a path, used to detect rotation.
+#[cfg(unix)]
+fn file_identity(meta: &Metadata) -> (u64, u64) {
+	use std::os::unix::fs::MetadataExt;
+	(meta.dev(), meta.ino())
+}
+
+#[cfg(not(unix))]
+fn file_identity(meta: &Metadata) -> (u64, u64) {
+	let created = meta
+		.created()
+		.ok()
+		.and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+		.map_or(0, |elapsed| elapsed.as_nanos() as u64);
+	(created, 0)
+}
+
+struct Follower {
+	path:     PathBuf,
+	file:     File,
+	identity: (u64, u64),
+	pos:      u64,
+	partial:  Vec<u8>,
+}
+
+fn open_error(path: &Path, err: std::io::Error) -> Error {
+	Error::from_reason(format!("Failed to open {}: {err}", path.display()))
+}
+
+impl Follower {
+	fn open(path: PathBuf, from_end: bool) -> Result<Self> {
+		let mut file = File::open(&path).map_err(|err| open_error(&path, err))?;
+		let meta = file.metadata().map_err(|err| open_error(&path, err))?;
+		let pos = if from_end { meta.len() } else { 0 };
+		file
+			.seek(SeekFrom::Start(pos))
+			.map_err(|err| open_error(&path, err))?;
+		Ok(Self { path, file, identity: file_identity(&meta), pos, partial: Vec::new() })
+	}
+
+	/// Read everything available from the current handle, splitting complete
+	/// lines into `out`.
+	fn drain(&mut self, out: &mut Vec<String>) -> Result<()> {
+		let mut buf = vec![0u8; 64 * 1024];
+		loop {
+			let read = self.file.read(&mut buf).map_err(|err| {
+				Error::from_reason(format!("Failed to read {}: {err}", self.path.display()))
+			})?;
+			if read == 0 {
+				return Ok(());
+			}
+			self.pos += read as u64;
+			let mut rest = &buf[..read];
+			while let Some(nl) = rest.iter().position(|&b| b == b'\n') {
+				self.partial.extend_from_slice(&rest[..nl]);
+				if self.partial.last() == Some(&b'\r') {
+					self.partial.pop();
+				}
+				out.push(String::from_utf8_lossy(&self.partial).into_owned());
+				self.partial.clear();
+				rest = &rest[nl + 1..];
+			}
+			self.partial.extend_from_slice(rest);
+		}
+	}
+
+	/// Poll once, returning the event to emit (if any).
+	fn poll(&mut self, summary: &mut TailFollowResult) -> Result<Option<TailEvent>> {
+		let mut event = TailEvent { lines: Vec::new(), truncated: false, rotated: false };
+		match fs::metadata(&self.path) {
+			Ok(meta) if file_identity(&meta) != self.identity => {
+				// Rotated: finish the old file, then follow the new one from the start.
+				self.drain(&mut event.lines)?;
+				if let Ok(file) = File::open(&self.path) {
+					self.file = file;
+					self.identity = file_identity(&meta);
+					self.pos = 0;
+					self.partial.clear();
+					event.rotated = true;
+					summary.rotations += 1;
+				}
+			},
+			Ok(meta) if meta.len() < self.pos => {
+				self
+					.file
+					.seek(SeekFrom::Start(0))
+					.map_err(|err| open_error(&self.path, err))?;
+				self.pos = 0;
+				self.partial.clear();
+				event.truncated = true;
+				summary.truncations += 1;
+			},
+			// Missing mid-rotation or unchanged: keep reading the current handle.
+			_ => {},
+		}
+		self.drain(&mut event.lines)?;
+		summary.lines += event.lines.len() as f64;
+		let changed = !event.lines.is_empty() || event.truncated || event.rotated;
+		Ok(changed.then_some(event))
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Follow a file and stream appended lines until aborted.
+///
+/// # Arguments
+/// - `path`: File to follow.
+/// - `options`: Start position, poll interval, and cancellation.
+/// - `on_lines`: Callback receiving batches of appended lines.
+///
+/// # Returns
+/// A summary once the signal aborts or the timeout elapses.
+///
+/// # Errors
+/// Returns an error if the file cannot be opened or read.
+#[napi(js_name = "tailFollow")]
+pub fn tail_follow<'env>(
+	env: &'env Env,
+	path: String,
+	options: Option<TailFollowOptions<'env>>,
+	#[napi(ts_arg_type = "(event: TailEvent) => void")] on_lines: ThreadsafeFunction<TailEvent>,
+) -> Result<PromiseRaw<'env, TailFollowResult>> {
+	let (from_end, poll_ms, signal, timeout_ms) = match options {
+		Some(opts) => (opts.from_end, opts.poll_ms, opts.signal, opts.timeout_ms),
+		None => (None, None, None, None),
+	};
+	let from_end = from_end.unwrap_or(true);
+	let poll = Duration::from_millis(u64::from(poll_ms.unwrap_or(250).max(10)));
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	let path = PathBuf::from(path);
+	task::future(env, "tail_follow", async move {
+		let mut follower = Follower::open(path, from_end)?;
+		let mut summary = TailFollowResult { lines: 0.0, truncations: 0, rotations: 0 };
+		loop {
+			// Reads can be large on the first poll; keep them off the async workers.
+			let (returned, polled) = tokio::task::spawn_blocking(move || {
+				let mut summary = summary;
+				let event = follower.poll(&mut summary);
+				(follower, event.map(|event| (event, summary)))
+			})
+			.await
+			.map_err(|err| Error::from_reason(format!("Tail poll failed: {err}")))?;
+			follower = returned;
+			let (event, updated) = polled?;
+			summary = updated;
+			if let Some(event) = event {
+				on_lines.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+			}
+			tokio::select! {
+				() = tokio::time::sleep(poll) => {},
+				_ = ct.wait() => return Ok(summary),
+			}
+		}
+	})
+}
@@ -0,0 +1,257 @@
+//! Tracked temporary files and directories.
+//!
+//! # Overview
+//! `createTempFile` and `createTempDir` create uniquely named entries (files
+//! `0600`, directories `0700` on Unix) and register them in a process-wide
+//! table. Each returns a `TempPath` handle whose `dispose()` deletes the entry
+//! immediately; anything still registered is deleted by `cleanupTempPaths`,
+//! which the JS wrapper runs from the process `exit` event and from the
+//! postmortem handlers for signals and fatal errors.
+//!
+//! `keep` creates an untracked entry that the caller owns. `keepOnCrash`
+//! entries are still removed by `dispose()` and on a clean exit, but are left
+//! on disk when the process exits with a failure code, so a crash leaves its
+//! scratch files behind for debugging.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const tmp = native.createTempDir({ prefix: "pi-build-" })
+//! // JS: await build(tmp.path)
+//! // JS: tmp.dispose()
+//! ```
+
+use std::{
+	collections::HashMap,
+	fs,
+	io::ErrorKind,
+	path::{Path, PathBuf},
+	sync::{
+		LazyLock,
+		atomic::{AtomicU64, Ordering},
+	},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::Mutex;
+
+use crate::{file_filter, hash};
+
+/// Attempts at finding an unused name before giving up.
+const MAX_ATTEMPTS: u32 = 64;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `createTempFile` and `createTempDir`.
+#[napi(object)]
+pub struct TempOptions {
+	/// Name prefix (default: "pi-").
+	pub prefix:        Option<String>,
+	/// Name suffix, e.g. an extension (default: none).
+	pub suffix:        Option<String>,
+	/// Parent directory (default: the OS temp directory).
+	pub dir:           Option<String>,
+	/// Leave the entry untracked; it is never deleted automatically (default:
+	/// false).
+	pub keep:          Option<bool>,
+	/// Leave the entry on disk when the process exits with a failure code
+	/// (default: false).
+	#[napi(js_name = "keepOnCrash")]
+	pub keep_on_crash: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Registry
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// A tracked entry awaiting cleanup.
+struct Tracked {
+	is_dir:        bool,
+	keep_on_crash: bool,
+}
+
+/// Entries to delete at exit, keyed by path.
+static TRACKED: LazyLock<Mutex<HashMap<PathBuf, Tracked>>> =
+	LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Distinguishes names generated within the same clock tick.
+static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Delete a file or directory tree, treating an already-missing entry as
+/// success.
+fn remove_entry(path: &Path, is_dir: bool) -> std::io::Result<()> {
+	let result = if is_dir {
+		fs::remove_dir_all(path)
+	} else {
+		fs::remove_file(path)
+	};
+	match result {
+		Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+		other => other,
+	}
+}
+
+/// A short name component unique to this process and call.
+fn unique_token() -> String {
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map_or(0, |duration| duration.as_nanos());
+	let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+	let mut seed = Vec::with_capacity(28);
+	seed.extend_from_slice(&std::process::id().to_le_bytes());
+	seed.extend_from_slice(&nanos.to_le_bytes());
+	seed.extend_from_slice(&count.to_le_bytes());
+	hash::hash_bytes(&seed, hash::Algorithm::Xxh3)[..12].to_string()
+}
+
+fn create_entry(path: &Path, is_dir: bool) -> std::io::Result<()> {
+	if is_dir {
+		let mut builder = fs::DirBuilder::new();
+		#[cfg(unix)]
+		std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+		builder.create(path)
+	} else {
+		let mut options = fs::OpenOptions::new();
+		options.write(true).create_new(true);
+		#[cfg(unix)]
+		std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+		options.open(path).map(drop)
+	}
+}
+
+fn create_temp(options: Option<TempOptions>, is_dir: bool) -> Result<TempPath> {
+	let (prefix, suffix, dir, keep, keep_on_crash) = match options {
+		Some(opts) => (opts.prefix, opts.suffix, opts.dir, opts.keep, opts.keep_on_crash),
+		None => (None, None, None, None, None),
+	};
+	let prefix = prefix.unwrap_or_else(|| "pi-".to_string());
+	let suffix = suffix.unwrap_or_default();
+	if prefix.contains(['/', '\\']) || suffix.contains(['/', '\\']) {
+		return Err(Error::from_reason("Temp name prefix and suffix must not contain separators"));
+	}
+	let dir = match dir {
+		Some(dir) => file_filter::resolve_search_path(&dir)?,
+		None => std::env::temp_dir(),
+	};
+	let kind = if is_dir { "directory" } else { "file" };
+
+	for _ in 0..MAX_ATTEMPTS {
+		let path = dir.join(format!("{prefix}{}{suffix}", unique_token()));
+		match create_entry(&path, is_dir) {
+			Ok(()) => {
+				let keep = keep.unwrap_or(false);
+				if !keep {
+					TRACKED.lock().insert(path.clone(), Tracked {
+						is_dir,
+						keep_on_crash: keep_on_crash.unwrap_or(false),
+					});
+				}
+				return Ok(TempPath { path, is_dir });
+			},
+			Err(err) if err.kind() == ErrorKind::AlreadyExists => {},
+			Err(err) => {
+				return Err(Error::from_reason(format!(
+					"Failed to create temp {kind} in {}: {err}",
+					dir.display()
+				)));
+			},
+		}
+	}
+	Err(Error::from_reason(format!(
+		"Failed to find an unused temp {kind} name in {}",
+		dir.display()
+	)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Handle to a temporary file or directory.
+#[napi]
+pub struct TempPath {
+	path:   PathBuf,
+	is_dir: bool,
+}
+
+#[napi]
+impl TempPath {
+	/// Absolute path of the entry.
+	#[napi(getter)]
+	pub fn path(&self) -> String {
+		self.path.to_string_lossy().into_owned()
+	}
+
+	/// Whether the entry is a directory.
+	#[napi(getter, js_name = "isDir")]
+	pub const fn is_dir(&self) -> bool {
+		self.is_dir
+	}
+
+	/// Delete the entry now (directories recursively) and stop tracking it.
+	/// Disposing twice is a no-op.
+	///
+	/// # Errors
+	/// Returns an error if the entry exists but cannot be deleted.
+	#[napi]
+	pub fn dispose(&self) -> Result<()> {
+		TRACKED.lock().remove(&self.path);
+		remove_entry(&self.path, self.is_dir).map_err(|err| {
+			Error::from_reason(format!("Failed to remove {}: {err}", self.path.display()))
+		})
+	}
+
+	/// Stop tracking the entry so it survives process exit.
+	#[napi]
+	pub fn persist(&self) {
+		TRACKED.lock().remove(&self.path);
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Create an empty temporary file that is deleted on `dispose()` or at exit.
+///
+/// # Errors
+/// Returns an error if the prefix or suffix contains a path separator or the
+/// file cannot be created.
+#[napi(js_name = "createTempFile")]
+pub fn create_temp_file(options: Option<TempOptions>) -> Result<TempPath> {
+	create_temp(options, false)
+}
+
+/// Create an empty temporary directory that is deleted, with its contents,
+/// on `dispose()` or at exit.
+///
+/// # Errors
+/// Returns an error if the prefix or suffix contains a path separator or the
+/// directory cannot be created.
+#[napi(js_name = "createTempDir")]
+pub fn create_temp_dir(options: Option<TempOptions>) -> Result<TempPath> {
+	create_temp(options, true)
+}
+
+/// Delete every tracked temporary entry.
+///
+/// # Arguments
+/// - `crashed`: Whether the process is exiting abnormally; `keepOnCrash`
+///   entries are then left on disk.
+///
+/// # Returns
+/// The number of entries deleted.
+#[napi(js_name = "cleanupTempPaths")]
+pub fn cleanup_temp_paths(crashed: Option<bool>) -> u32 {
+	let crashed = crashed.unwrap_or(false);
+	let tracked = std::mem::take(&mut *TRACKED.lock());
+	let removed = tracked
+		.into_iter()
+		.filter(|(_, entry)| !(crashed && entry.keep_on_crash))
+		.filter(|(path, entry)| remove_entry(path, entry.is_dir).is_ok())
+		.count();
+	u32::try_from(removed).unwrap_or(u32::MAX)
+}
@@ -0,0 +1,519 @@
+//! In-memory terminal screen model.
+//!
+//! # Overview
+//! `TermScreen` consumes raw PTY output and interprets the full VT/ANSI
+//! stream (cursor movement, scroll regions, alternate screen, erase, SGR,
+//! wide characters) into a grid of styled cells, so program output can be
+//! drawn inside a pane instead of being written to the real terminal. The
+//! grid is queryable as plain lines, as SGR-styled lines, or cell by cell.
+//! Lines scrolled off the top are kept in a bounded scrollback buffer.
+//!
+//! `diffScreens` compares two cell grids and emits the minimal update (cursor
+//! moves, SGR changes, text, line erases) for a retained-mode compositor.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const screen = new native.TermScreen(24, 80)
+//! // JS: await pty.spawn("htop", [], { cols: 80, rows: 24 }, (_e, data) => screen.write(data))
+//! // JS: pane.render(screen.styledLines(), screen.cursor)
+//! // JS: process.stdout.write(native.diffScreens(lastFrame, frame = screen.toCells()))
+//! ```
+
+use std::fmt::Write;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// One cell of the screen grid.
+#[napi(object)]
+#[derive(Clone, PartialEq, Eq)]
+pub struct TermCell {
+	/// Cell text: one grapheme, `" "` when blank, `""` for the right half of
+	/// a wide character.
+	pub text:      String,
+	/// Display width: 1, 2 for a wide character, 0 for its right half.
+	pub width:     u32,
+	/// Foreground as SGR parameters (e.g. `"31"`, `"38;5;208"`), or `null`
+	/// for the default color.
+	pub fg:        Option<String>,
+	/// Background as SGR parameters (e.g. `"44"`, `"48;2;0;0;0"`), or `null`
+	/// for the default color.
+	pub bg:        Option<String>,
+	/// Bold / increased intensity (SGR 1).
+	pub bold:      bool,
+	/// Faint / decreased intensity (SGR 2).
+	pub dim:       bool,
+	/// Italic (SGR 3).
+	pub italic:    bool,
+	/// Underline (SGR 4).
+	pub underline: bool,
+	/// Reverse video (SGR 7).
+	pub inverse:   bool,
+}
+
+/// Cursor position and visibility.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct TermCursor {
+	/// Zero-based row.
+	pub row:     u32,
+	/// Zero-based column.
+	pub col:     u32,
+	/// False when the program hid the cursor (DECTCEM).
+	pub visible: bool,
+}
+
+/// Options for `diffScreens`.
+#[napi(object)]
+pub struct DiffScreensOptions {
+	/// Where to leave the cursor, and whether to show it, after the update.
+	pub cursor:       Option<TermCursor>,
+	/// Wrap the update in synchronized-output markers (DEC mode 2026) so the
+	/// terminal presents it atomically (default: false).
+	pub synchronized: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Styling
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// SGR parameters selecting `color`, or `None` for the default color.
+fn color_params(color: vt100::Color, background: bool) -> Option<String> {
+	let base = if background { 40 } else { 30 };
+	match color {
+		vt100::Color::Default => None,
+		vt100::Color::Idx(index @ 0..=7) => Some((base + u32::from(index)).to_string()),
+		vt100::Color::Idx(index @ 8..=15) => Some((base + 60 + u32::from(index) - 8).to_string()),
+		vt100::Color::Idx(index) => Some(format!("{};5;{index}", base + 8)),
+		vt100::Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", base + 8)),
+	}
+}
+
+fn blank_cell() -> TermCell {
+	TermCell {
+		text:      " ".to_string(),
+		width:     1,
+		fg:        None,
+		bg:        None,
+		bold:      false,
+		dim:       false,
+		italic:    false,
+		underline: false,
+		inverse:   false,
+	}
+}
+
+fn to_cell(cell: &vt100::Cell) -> TermCell {
+	let (text, width) = if cell.is_wide_continuation() {
+		(String::new(), 0)
+	} else if cell.has_contents() {
+		(cell.contents().to_string(), if cell.is_wide() { 2 } else { 1 })
+	} else {
+		(" ".to_string(), 1)
+	};
+	TermCell {
+		text,
+		width,
+		fg: color_params(cell.fgcolor(), false),
+		bg: color_params(cell.bgcolor(), true),
+		bold: cell.bold(),
+		dim: cell.dim(),
+		italic: cell.italic(),
+		underline: cell.underline(),
+		inverse: cell.inverse(),
+	}
+}
+
+impl TermCell {
+	const fn is_plain(&self) -> bool {
+		self.fg.is_none()
+			&& self.bg.is_none()
+			&& !(self.bold || self.dim || self.italic || self.underline || self.inverse)
+	}
+
+	/// A blank cell that erasing with default attributes produces.
+	fn is_blank(&self) -> bool {
+		self.text == " " && self.is_plain()
+	}
+
+	fn same_style(&self, other: &Self) -> bool {
+		self.fg == other.fg
+			&& self.bg == other.bg
+			&& self.bold == other.bold
+			&& self.dim == other.dim
+			&& self.italic == other.italic
+			&& self.underline == other.underline
+			&& self.inverse == other.inverse
+	}
+
+	/// Full SGR sequence for this cell's style, starting from a reset.
+	pub fn sgr(&self) -> String {
+		let mut params = String::from("0");
+		for (on, code) in [
+			(self.bold, "1"),
+			(self.dim, "2"),
+			(self.italic, "3"),
+			(self.underline, "4"),
+			(self.inverse, "7"),
+		] {
+			if on {
+				params.push(';');
+				params.push_str(code);
+			}
+		}
+		for color in [&self.fg, &self.bg].into_iter().flatten() {
+			params.push(';');
+			params.push_str(color);
+		}
+		format!("\x1b[{params}m")
+	}
+}
+
+/// Render one row of cells as text with minimal SGR, dropping trailing
+/// unstyled blanks. Styled rows end with a reset.
+pub fn styled_row(cells: &[TermCell]) -> String {
+	let end = cells
+		.iter()
+		.rposition(|cell| !cell.is_blank())
+		.map_or(0, |last| last + 1);
+	let mut out = String::new();
+	let mut current: Option<&TermCell> = None;
+	for cell in &cells[..end] {
+		if cell.width == 0 {
+			continue;
+		}
+		let changed = match current {
+			Some(prev) => !prev.same_style(cell),
+			None => !cell.is_plain(),
+		};
+		if changed {
+			if cell.is_plain() {
+				out.push_str("\x1b[0m");
+			} else {
+				out.push_str(&cell.sgr());
+			}
+		}
+		current = Some(cell);
+		out.push_str(&cell.text);
+	}
+	if current.is_some_and(|cell| !cell.is_plain()) {
+		out.push_str("\x1b[0m");
+	}
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Damage diff
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Unchanged cells between two changes that are cheaper to reprint than to
+/// skip with a cursor move.
+const MAX_REPRINT_GAP: usize = 4;
+
+/// Tracks the real terminal's pen and cursor while emitting an update.
+struct Painter {
+	out:    String,
+	/// Style of the last cell written; `None` until the first SGR.
+	pen:    Option<TermCell>,
+	/// Cursor position, or `None` when unknown (start, or pending wrap).
+	cursor: Option<(usize, usize)>,
+}
+
+impl Painter {
+	fn move_to(&mut self, row: usize, col: usize) {
+		if self.cursor != Some((row, col)) {
+			let _ = write!(self.out, "\x1b[{};{}H", row + 1, col + 1);
+			self.cursor = Some((row, col));
+		}
+	}
+
+	fn paint(&mut self, row: usize, col: usize, cell: &TermCell, row_len: usize) {
+		if !self.pen.as_ref().is_some_and(|pen| pen.same_style(cell)) {
+			self.out.push_str(&cell.sgr());
+			self.pen = Some(cell.clone());
+		}
+		self.out.push_str(&cell.text);
+		let next = col + cell.width.max(1) as usize;
+		// Writing the last column leaves the cursor in a pending-wrap state.
+		self.cursor = (next < row_len).then_some((row, next));
+	}
+
+	/// Erase from `col` to the end of the line with default attributes.
+	fn clear_line_from(&mut self, row: usize, col: usize) {
+		self.move_to(row, col);
+		if !self.pen.as_ref().is_some_and(TermCell::is_plain) {
+			self.out.push_str("\x1b[0m");
+			self.pen = Some(blank_cell());
+		}
+		self.out.push_str("\x1b[K");
+	}
+}
+
+/// Minimal update turning a terminal showing `prev` into one showing `next`.
+///
+/// Changed cells are grouped into runs per row (bridging short unchanged
+/// gaps), each reached with one cursor move; changed trailing blanks are
+/// erased with EL. Rows of `prev` beyond `next` are cleared. An empty `prev`
+/// repaints everything. The pen is reset at the end if left styled.
+pub fn diff_grids(prev: &[Vec<TermCell>], next: &[Vec<TermCell>]) -> String {
+	let mut painter = Painter { out: String::new(), pen: None, cursor: None };
+	for (r, row) in next.iter().enumerate() {
+		let old = prev.get(r).map_or(&[][..], Vec::as_slice);
+		let changed = |col: usize| old.get(col) != Some(&row[col]);
+		let blank_from = row
+			.iter()
+			.rposition(|cell| !cell.is_blank())
+			.map_or(0, |last| last + 1);
+
+		let mut col = 0;
+		while col < blank_from {
+			if !changed(col) {
+				col += 1;
+				continue;
+			}
+			// Start on the left half of a wide character.
+			let mut start = col;
+			while start > 0 && row[start].width == 0 {
+				start -= 1;
+			}
+			let mut end = col + 1;
+			let mut gap = 0;
+			for c in col + 1..blank_from {
+				if changed(c) {
+					end = c + 1;
+					gap = 0;
+				} else {
+					gap += 1;
+					if gap > MAX_REPRINT_GAP {
+						break;
+					}
+				}
+			}
+			// A wide character at the end also covers its right half.
+			while end < row.len() && row[end].width == 0 {
+				end += 1;
+			}
+			painter.move_to(r, start);
+			for (c, cell) in row.iter().enumerate().take(end).skip(start) {
+				if cell.width > 0 {
+					painter.paint(r, c, cell, row.len());
+				}
+			}
+			col = end;
+		}
+
+		let stale_tail =
+			old.len() > row.len() && old[row.len()..].iter().any(|cell| !cell.is_blank());
+		if (blank_from..row.len()).any(changed) || stale_tail {
+			painter.clear_line_from(r, blank_from);
+		}
+	}
+	for (r, old) in prev.iter().enumerate().skip(next.len()) {
+		if old.iter().any(|cell| !cell.is_blank()) {
+			painter.clear_line_from(r, 0);
+		}
+	}
+	if painter.pen.as_ref().is_some_and(|pen| !pen.is_plain()) {
+		painter.out.push_str("\x1b[0m");
+	}
+	painter.out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Screen
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Records OSC events that do not change the grid.
+#[derive(Default)]
+struct Events {
+	title: String,
+}
+
+impl vt100::Callbacks for Events {
+	fn set_window_title(&mut self, _: &mut vt100::Screen, title: &[u8]) {
+		self.title = String::from_utf8_lossy(title).into_owned();
+	}
+}
+
+const DEFAULT_SCROLLBACK: u32 = 1000;
+
+fn new_parser(rows: u16, cols: u16, scrollback: usize) -> vt100::Parser<Events> {
+	vt100::Parser::new_with_callbacks(rows.max(1), cols.max(1), scrollback, Events::default())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Terminal emulator screen fed with raw PTY output.
+#[napi]
+pub struct TermScreen {
+	parser:     vt100::Parser<Events>,
+	scrollback: usize,
+}
+
+#[napi]
+impl TermScreen {
+	/// Create a blank screen.
+	///
+	/// # Arguments
+	/// - `rows`, `cols`: Screen size in cells (at least 1).
+	/// - `scrollback`: Lines kept after scrolling off the top (default: 1000).
+	#[napi(constructor)]
+	pub fn new(rows: u16, cols: u16, scrollback: Option<u32>) -> Self {
+		let scrollback = scrollback.unwrap_or(DEFAULT_SCROLLBACK) as usize;
+		Self { parser: new_parser(rows, cols, scrollback), scrollback }
+	}
+
+	/// Feed program output; strings are processed as UTF-8. Escape sequences
+	/// split across calls are handled.
+	#[napi]
+	pub fn write(&mut self, data: Either<String, Uint8Array>) {
+		match data {
+			Either::A(text) => self.parser.process(text.as_bytes()),
+			Either::B(bytes) => self.parser.process(&bytes),
+		}
+	}
+
+	/// Resize the screen, truncating or padding rows and columns. Call this
+	/// alongside `Pty.resize` so the program and the model agree.
+	#[napi]
+	pub fn resize(&mut self, rows: u16, cols: u16) {
+		self.parser.screen_mut().set_size(rows.max(1), cols.max(1));
+	}
+
+	/// Clear the screen, scrollback, modes, and title.
+	#[napi]
+	pub fn reset(&mut self) {
+		let (rows, cols) = self.parser.screen().size();
+		self.parser = new_parser(rows, cols, self.scrollback);
+	}
+
+	/// Visible rows as plain text, without trailing blanks.
+	#[napi]
+	pub fn lines(&self) -> Vec<String> {
+		let screen = self.parser.screen();
+		let (_, cols) = screen.size();
+		screen
+			.rows(0, cols)
+			.map(|row| row.trim_end().to_string())
+			.collect()
+	}
+
+	/// Visible rows with SGR styling; each styled row ends with a reset.
+	#[napi(js_name = "styledLines")]
+	pub fn styled_lines(&self) -> Vec<String> {
+		self.grid().iter().map(|row| styled_row(row)).collect()
+	}
+
+	/// The visible grid, one array of `cols` cells per row.
+	#[napi(js_name = "toCells")]
+	pub fn to_cells(&self) -> Vec<Vec<TermCell>> {
+		self.grid()
+	}
+
+	/// Scroll the view back into history; 0 shows the live screen. The
+	/// offset is clamped to the available scrollback.
+	///
+	/// # Returns
+	/// The applied offset.
+	#[napi(js_name = "scrollTo")]
+	pub fn scroll_to(&mut self, offset: u32) -> u32 {
+		let screen = self.parser.screen_mut();
+		screen.set_scrollback(offset as usize);
+		screen.scrollback() as u32
+	}
+
+	/// Current scroll offset into history (0 when showing the live screen).
+	#[napi(getter, js_name = "scrollOffset")]
+	pub fn scroll_offset(&self) -> u32 {
+		self.parser.screen().scrollback() as u32
+	}
+
+	/// Screen height in cells.
+	#[napi(getter)]
+	pub fn rows(&self) -> u32 {
+		u32::from(self.parser.screen().size().0)
+	}
+
+	/// Screen width in cells.
+	#[napi(getter)]
+	pub fn cols(&self) -> u32 {
+		u32::from(self.parser.screen().size().1)
+	}
+
+	/// Cursor position and visibility.
+	#[napi(getter)]
+	pub fn cursor(&self) -> TermCursor {
+		let screen = self.parser.screen();
+		let (row, col) = screen.cursor_position();
+		TermCursor { row: u32::from(row), col: u32::from(col), visible: !screen.hide_cursor() }
+	}
+
+	/// Whether the program switched to the alternate screen (full-screen
+	/// apps such as editors and pagers).
+	#[napi(getter, js_name = "alternateScreen")]
+	pub fn alternate_screen(&self) -> bool {
+		self.parser.screen().alternate_screen()
+	}
+
+	/// Window title last set via OSC 0/2 (empty if never set).
+	#[napi(getter)]
+	pub fn title(&self) -> String {
+		self.parser.callbacks().title.clone()
+	}
+}
+
+impl TermScreen {
+	fn grid(&self) -> Vec<Vec<TermCell>> {
+		let screen = self.parser.screen();
+		let (rows, cols) = screen.size();
+		(0..rows)
+			.map(|row| {
+				(0..cols)
+					.map(|col| screen.cell(row, col).map_or_else(blank_cell, to_cell))
+					.collect()
+			})
+			.collect()
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Compute the bytes that update the terminal from one frame to the next.
+///
+/// # Arguments
+/// - `prev`: Grid currently on the terminal (as from `toCells()`); pass `[]`
+///   when unknown to repaint everything.
+/// - `next`: Grid to display.
+/// - `options`: Final cursor placement and synchronized output.
+///
+/// # Returns
+/// Cursor moves, SGR changes, text, and line erases; empty when nothing
+/// changed and no cursor is requested.
+#[napi(js_name = "diffScreens")]
+pub fn diff_screens(
+	prev: Vec<Vec<TermCell>>,
+	next: Vec<Vec<TermCell>>,
+	options: Option<DiffScreensOptions>,
+) -> String {
+	let (cursor, synchronized) = match options {
+		Some(opts) => (opts.cursor, opts.synchronized.unwrap_or(false)),
+		None => (None, false),
+	};
+	let mut out = diff_grids(&prev, &next);
+	if let Some(cursor) = cursor {
+		let _ = write!(out, "\x1b[{};{}H", cursor.row + 1, cursor.col + 1);
+		out.push_str(if cursor.visible { "\x1b[?25h" } else { "\x1b[?25l" });
+	}
+	if synchronized && !out.is_empty() {
+		out = format!("\x1b[?2026h{out}\x1b[?2026l");
+	}
+	out
+}
@@ -0,0 +1,714 @@
+//! Terminal capability detection and notifications.
+//!
+//! # Overview
+//! Builds one capability report for the controlling terminal from three
+//! sources, weakest first:
+//! 1. Environment: `TERM`, `TERM_PROGRAM`, `COLORTERM` and terminal-specific
+//!    variables identify the emulator and any multiplexer.
+//! 2. Terminfo: the compiled entry for `$TERM` supplies the color count and
+//!    the `Tc`/`RGB`/`Sync` extended capabilities.
+//! 3. Live query (opt-in, Unix only): kitty keyboard, DECRQM 2026, XTVERSION,
+//!    kitty graphics, and DA1 requests are written to `/dev/tty` and the
+//!    replies parsed. DA1 is answered by every terminal, so its reply marks
+//!    the end of the exchange. Query before the app starts reading stdin, or
+//!    the replies may be consumed by the input reader.
+//!
+//! `notify` uses the same identification to pick a desktop notification
+//! escape: OSC 99 on kitty (the only one with urgency), OSC 9 on iTerm2,
+//! `WezTerm`, Ghostty, and Windows Terminal, and OSC 777 on foot and
+//! rxvt-unicode. Text is stripped of control characters so it cannot end the
+//! sequence early, and inside tmux the sequence is wrapped for passthrough.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const term = await native.detectTerminal({ query: true })
+//! // JS: if (term.images === "kitty") drawWithKittyGraphics()
+//! // JS: native.notify({ title: "Build finished", body: "All tests passed" }) // -> { protocol: "osc9", sent: true, ... }
+//! ```
+
+use std::{
+	env, fs,
+	io::{IsTerminal, Write},
+	path::{Path, PathBuf},
+	sync::atomic::{AtomicU32, Ordering},
+	time::Duration,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `detectTerminal`.
+#[napi(object)]
+pub struct DetectTerminalOptions {
+	/// Query the terminal over `/dev/tty` for live capability replies
+	/// (default: false).
+	pub query:      Option<bool>,
+	/// How long to wait for query replies in milliseconds (default: 200).
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Terminal capability report.
+#[napi(object)]
+pub struct TerminalInfo {
+	/// Identified emulator (e.g. `"kitty"`, `"wezterm"`, `"iterm2"`,
+	/// `"vscode"`, `"windows-terminal"`), if recognized.
+	pub name:                Option<String>,
+	/// Emulator version, if reported.
+	pub version:             Option<String>,
+	/// Value of `$TERM`.
+	pub term:                Option<String>,
+	/// Enclosing multiplexer: `"tmux"` or `"screen"`.
+	pub multiplexer:         Option<String>,
+	/// Whether stdout is a terminal.
+	#[napi(js_name = "isTty")]
+	pub is_tty:              bool,
+	/// Number of colors supported (0, 8, 16, 256, or 16777216).
+	pub colors:              u32,
+	/// Whether 24-bit color is supported.
+	pub truecolor:           bool,
+	/// Whether the kitty keyboard protocol is supported.
+	#[napi(js_name = "kittyKeyboard")]
+	pub kitty_keyboard:      bool,
+	/// Whether synchronized output (mode 2026) is supported.
+	#[napi(js_name = "synchronizedOutput")]
+	pub synchronized_output: bool,
+	/// Whether OSC 8 hyperlinks are supported.
+	pub hyperlinks:          bool,
+	/// Inline image protocol: `"kitty"`, `"iterm2"`, or `"sixel"`.
+	pub images:              Option<String>,
+	/// Whether a live query was answered by the terminal.
+	pub queried:             bool,
+}
+
+/// Options for `notify`.
+#[napi(object)]
+pub struct NotifyOptions {
+	/// Notification title.
+	pub title:    String,
+	/// Notification body.
+	pub body:     Option<String>,
+	/// `"low"`, `"normal"`, or `"critical"` (default: `"normal"`). Only OSC 99
+	/// carries urgency; other protocols ignore it.
+	pub urgency:  Option<String>,
+	/// Escape to emit: `"osc9"`, `"osc777"`, or `"osc99"` (default: chosen
+	/// from the detected terminal).
+	pub protocol: Option<String>,
+	/// Write the sequence to the terminal (default: true). When false, only
+	/// the sequence is returned.
+	pub write:    Option<bool>,
+}
+
+/// Result of `notify`.
+#[napi(object)]
+pub struct NotifyResult {
+	/// Protocol used; absent when the terminal supports none.
+	pub protocol: Option<String>,
+	/// Escape sequence, including any tmux wrapping (empty when unsupported).
+	pub sequence: String,
+	/// Whether the sequence was written to the terminal.
+	pub sent:     bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Environment
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Capabilities implied by the identified emulator.
+#[derive(Default)]
+struct Known {
+	name:           Option<&'static str>,
+	truecolor:      bool,
+	kitty_keyboard: bool,
+	sync:           bool,
+	hyperlinks:     bool,
+	images:         Option<&'static str>,
+}
+
+fn var(key: &str) -> Option<String> {
+	env::var(key).ok().filter(|value| !value.is_empty())
+}
+
+/// Identify the emulator from environment variables.
+fn identify(term: &str) -> Known {
+	let program = var("TERM_PROGRAM").unwrap_or_default();
+	let full = |name| Known {
+		name: Some(name),
+		truecolor: true,
+		kitty_keyboard: true,
+		sync: true,
+		hyperlinks: true,
+		images: Some("kitty"),
+	};
+	if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" {
+		return full("kitty");
+	}
+	if program == "ghostty" || term == "xterm-ghostty" {
+		return full("ghostty");
+	}
+	if program == "WezTerm" || var("WEZTERM_EXECUTABLE").is_some() {
+		return full("wezterm");
+	}
+	let basic = |name| Known {
+		name: Some(name),
+		truecolor: true,
+		sync: true,
+		hyperlinks: true,
+		..Known::default()
+	};
+	match program.as_str() {
+		"iTerm.app" => return Known { images: Some("iterm2"), ..basic("iterm2") },
+		"vscode" => return basic("vscode"),
+		"Apple_Terminal" => return Known { name: Some("apple-terminal"), ..Known::default() },
+		_ => {},
+	}
+	if var("WT_SESSION").is_some() {
+		return basic("windows-terminal");
+	}
+	if term == "alacritty" || var("ALACRITTY_WINDOW_ID").is_some() {
+		return Known { kitty_keyboard: true, ..basic("alacritty") };
+	}
+	if term.starts_with("foot") {
+		return Known { kitty_keyboard: true, images: Some("sixel"), ..basic("foot") };
+	}
+	if var("KONSOLE_VERSION").is_some() {
+		return Known { sync: false, ..basic("konsole") };
+	}
+	if var("VTE_VERSION").and_then(|v| v.parse::<u32>().ok()) >= Some(5000) {
+		return Known { sync: false, ..basic("vte") };
+	}
+	Known::default()
+}
+
+fn multiplexer(term: &str) -> Option<&'static str> {
+	if var("TMUX").is_some() || term.starts_with("tmux") {
+		Some("tmux")
+	} else if var("STY").is_some() || term.starts_with("screen") {
+		Some("screen")
+	} else {
+		None
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Terminfo
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Capabilities read from a compiled terminfo entry.
+#[derive(Default)]
+struct Terminfo {
+	colors:    Option<u32>,
+	truecolor: bool,
+	sync:      bool,
+}
+
+/// Index of `max_colors` in the standard numeric capabilities.
+const MAX_COLORS: usize = 13;
+
+fn terminfo_dirs() -> Vec<PathBuf> {
+	let mut dirs = Vec::new();
+	if let Some(dir) = var("TERMINFO") {
+		dirs.push(PathBuf::from(dir));
+	}
+	if let Some(home) = var("HOME") {
+		dirs.push(Path::new(&home).join(".terminfo"));
+	}
+	if let Some(list) = var("TERMINFO_DIRS") {
+		dirs.extend(list.split(':').filter(|dir| !dir.is_empty()).map(PathBuf::from));
+	}
+	for dir in ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo", "/usr/lib/terminfo"] {
+		dirs.push(PathBuf::from(dir));
+	}
+	dirs
+}
+
+fn find_terminfo(term: &str) -> Option<Vec<u8>> {
+	let first = term.chars().next()?;
+	// Entries live under their first character, or its hex code on macOS.
+	let buckets = [first.to_string(), format!("{:x}", first as u32)];
+	terminfo_dirs().iter().find_map(|dir| {
+		buckets
+			.iter()
+			.find_map(|bucket| fs::read(dir.join(bucket).join(term)).ok())
+	})
+}
+
+/// Little-endian reader over a terminfo entry.
+struct Cursor<'a> {
+	data: &'a [u8],
+	pos:  usize,
+}
+
+impl<'a> Cursor<'a> {
+	fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+		let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+		self.pos += len;
+		Some(bytes)
+	}
+
+	fn i16(&mut self) -> Option<i16> {
+		self.take(2).map(|b| i16::from_le_bytes([b[0], b[1]]))
+	}
+
+	fn count(&mut self) -> Option<usize> {
+		usize::try_from(self.i16()?.max(0)).ok()
+	}
+
+	const fn align(&mut self) {
+		self.pos += self.pos % 2;
+	}
+
+	fn number(&mut self, wide: bool) -> Option<i32> {
+		if wide {
+			self.take(4).map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+		} else {
+			self.i16().map(i32::from)
+		}
+	}
+}
+
+/// Parse the parts of a compiled terminfo entry used for detection.
+fn parse_terminfo(data: &[u8]) -> Option<Terminfo> {
+	let mut cur = Cursor { data, pos: 0 };
+	let wide = match cur.i16()? {
+		0o432 => false,
+		0o1036 => true,
+		_ => return None,
+	};
+	let names_size = cur.count()?;
+	let bool_count = cur.count()?;
+	let num_count = cur.count()?;
+	let str_count = cur.count()?;
+	let table_size = cur.count()?;
+	cur.take(names_size + bool_count)?;
+	cur.align();
+	let mut info = Terminfo::default();
+	for index in 0..num_count {
+		let value = cur.number(wide)?;
+		if index == MAX_COLORS && value > 0 {
+			info.colors = u32::try_from(value).ok();
+		}
+	}
+	cur.take(str_count * 2 + table_size)?;
+	cur.align();
+
+	// Extended (user-defined) capabilities: only their names matter here.
+	let Some(ext_bools) = cur.count() else {
+		return Some(info);
+	};
+	let ext_nums = cur.count()?;
+	let ext_strs = cur.count()?;
+	let _ext_items = cur.count()?;
+	let ext_table_size = cur.count()?;
+	cur.take(ext_bools)?;
+	cur.align();
+	cur.take(ext_nums * if wide { 4 } else { 2 })?;
+	let total = ext_bools + ext_nums + ext_strs;
+	cur.take((ext_strs + total) * 2)?;
+	let table = cur.take(ext_table_size)?;
+	// Names are the last `total` NUL-terminated strings in the table.
+	let strings: Vec<&[u8]> = table.split(|&b| b == 0).collect();
+	let names = strings.get(strings.len().saturating_sub(total + 1)..)?;
+	for name in names {
+		match *name {
+			b"Tc" | b"RGB" => info.truecolor = true,
+			b"Sync" => info.sync = true,
+			_ => {},
+		}
+	}
+	Some(info)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Live query
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Kitty keyboard flags, DECRQM 2026, XTVERSION, kitty graphics, then DA1.
+const QUERIES: &[u8] =
+	b"\x1b[?u\x1b[?2026$p\x1b[>0q\x1b_Gi=31,s=1,v=1,a=q,t=d,f=24;AAAA\x1b\\\x1b[c";
+
+/// Capabilities reported by the terminal itself.
+#[derive(Default)]
+struct QueryReport {
+	answered:       bool,
+	kitty_keyboard: bool,
+	sync:           Option<bool>,
+	version:        Option<String>,
+	kitty_graphics: bool,
+	sixel:          bool,
+}
+
+/// Parse query replies. Returns a report with `answered` set once the DA1
+/// reply has been seen.
+fn parse_replies(buf: &[u8]) -> QueryReport {
+	let mut report = QueryReport::default();
+	let mut i = 0;
+	while i + 1 < buf.len() {
+		if buf[i] != 0x1b {
+			i += 1;
+			continue;
+		}
+		match buf[i + 1] {
+			b'[' => {
+				let start = i + 2;
+				let Some(len) = buf[start..].iter().position(|b| (0x40..=0x7e).contains(b)) else {
+					break;
+				};
+				let params = String::from_utf8_lossy(&buf[start..start + len]);
+				match buf[start + len] {
+					b'u' if params.starts_with('?') => report.kitty_keyboard = true,
+					b'y' if params.starts_with("?2026;") => {
+						let mode = params.trim_start_matches("?2026;").trim_end_matches('$');
+						report.sync = Some(matches!(mode, "1" | "2"));
+					},
+					b'c' if params.starts_with('?') => {
+						report.answered = true;
+						report.sixel = params[1..].split(';').any(|p| p == "4");
+					},
+					_ => {},
+				}
+				i = start + len + 1;
+			},
+			kind @ (b'P' | b'_') => {
+				let start = i + 2;
+				let Some(len) = buf[start..].windows(2).position(|w| w == b"\x1b\\") else {
+					break;
+				};
+				let body = String::from_utf8_lossy(&buf[start..start + len]);
+				if kind == b'P' {
+					if let Some(version) = body.strip_prefix(">|") {
+						report.version = Some(version.to_string());
+					}
+				} else if body.starts_with("Gi=31;") {
+					report.kitty_graphics = body.ends_with(";OK");
+				}
+				i = start + len + 2;
+			},
+			_ => i += 1,
+		}
+	}
+	report
+}
+
+#[cfg(unix)]
+fn query_terminal(timeout: Duration) -> Option<Vec<u8>> {
+	use std::{fs::OpenOptions, os::fd::AsRawFd};
+
+	let tty = OpenOptions::new().read(true).write(true).open("/dev/tty").ok()?;
+	let fd = tty.as_raw_fd();
+	// SAFETY: termios is plain old data; tcgetattr initializes it on success.
+	let mut original: libc::termios = unsafe { std::mem::zeroed() };
+	// SAFETY: `fd` is an open terminal descriptor owned by `tty`.
+	if unsafe { libc::tcgetattr(fd, &raw mut original) } != 0 {
+		return None;
+	}
+	let mut raw = original;
+	raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+	// Reads return after at most 100ms so the deadline is honored.
+	raw.c_cc[libc::VMIN] = 0;
+	raw.c_cc[libc::VTIME] = 1;
+	// SAFETY: `fd` is valid and `raw` is a fully initialized termios.
+	if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw const raw) } != 0 {
+		return None;
+	}
+	let replies = exchange(&tty, timeout);
+	// SAFETY: restores the settings read above on the same descriptor.
+	unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw const original) };
+	replies
+}
+
+#[cfg(unix)]
+fn exchange(tty: &fs::File, timeout: Duration) -> Option<Vec<u8>> {
+	use std::{
+		io::{Read, Write},
+		time::Instant,
+	};
+
+	let mut writer = tty;
+	writer.write_all(QUERIES).ok()?;
+	writer.flush().ok()?;
+	let deadline = Instant::now() + timeout;
+	let mut replies = Vec::new();
+	let mut buf = [0u8; 1024];
+	let mut reader = tty;
+	while Instant::now() < deadline {
+		match reader.read(&mut buf) {
+			Ok(n) => replies.extend_from_slice(&buf[..n]),
+			Err(_) => break,
+		}
+		if parse_replies(&replies).answered {
+			break;
+		}
+	}
+	Some(replies)
+}
+
+#[cfg(not(unix))]
+const fn query_terminal(_timeout: Duration) -> Option<Vec<u8>> {
+	None
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Detection
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn detect(query: bool, timeout: Duration) -> TerminalInfo {
+	let term = var("TERM");
+	let term_str = term.as_deref().unwrap_or_default();
+	let known = identify(term_str);
+	let mux = multiplexer(term_str);
+	let terminfo = find_terminfo(term_str)
+		.and_then(|data| parse_terminfo(&data))
+		.unwrap_or_default();
+	let report = if query {
+		query_terminal(timeout).map_or_default(|replies| parse_replies(&replies))
+	} else {
+		QueryReport::default()
+	};
+
+	let colorterm = var("COLORTERM").unwrap_or_default();
+	let truecolor = known.truecolor
+		|| terminfo.truecolor
+		|| matches!(colorterm.as_str(), "truecolor" | "24bit");
+	let colors = if truecolor {
+		1 << 24
+	} else if let Some(colors) = terminfo.colors {
+		colors
+	} else if term_str.contains("256color") {
+		256
+	} else if term_str.is_empty() || term_str == "dumb" {
+		0
+	} else {
+		16
+	};
+
+	// A multiplexer sits between the app and the emulator, so only trust
+	// emulator-specific protocols when the terminal itself answered.
+	let direct = mux.is_none();
+	let (kitty_keyboard, sync, images) = if report.answered {
+		let images = if report.kitty_graphics {
+			Some("kitty")
+		} else if report.sixel {
+			Some("sixel")
+		} else {
+			known.images.filter(|&protocol| protocol == "iterm2" && direct)
+		};
+		(report.kitty_keyboard, report.sync.unwrap_or(terminfo.sync), images)
+	} else {
+		let images = known.images.filter(|_| direct);
+		(known.kitty_keyboard && direct, known.sync || terminfo.sync, images)
+	};
+
+	// XTVERSION replies look like "kitty(0.35.2)" or "WezTerm 20240203".
+	let (reported_name, version) = match report.version.as_deref() {
+		Some(text) => {
+			let split = text.find(['(', ' ']).unwrap_or(text.len());
+			let version = text[split..].trim_matches(|c| c == '(' || c == ')' || c == ' ');
+			let version = (!version.is_empty()).then(|| version.to_string());
+			(Some(text[..split].to_ascii_lowercase()), version)
+		},
+		None => (None, var("TERM_PROGRAM_VERSION")),
+	};
+
+	TerminalInfo {
+		name: known.name.map(str::to_string).or(reported_name),
+		version,
+		term,
+		multiplexer: mux.map(str::to_string),
+		is_tty: std::io::stdout().is_terminal(),
+		colors,
+		truecolor,
+		kitty_keyboard,
+		synchronized_output: sync,
+		hyperlinks: known.hyperlinks,
+		images: images.map(str::to_string),
+		queried: report.answered,
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Notifications
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy)]
+enum NotifyProtocol {
+	Osc9,
+	Osc777,
+	Osc99,
+}
+
+impl NotifyProtocol {
+	fn parse(name: &str) -> Result<Self> {
+		match name {
+			"osc9" => Ok(Self::Osc9),
+			"osc777" => Ok(Self::Osc777),
+			"osc99" => Ok(Self::Osc99),
+			_ => Err(Error::from_reason(format!("Unknown notification protocol: {name}"))),
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Osc9 => "osc9",
+			Self::Osc777 => "osc777",
+			Self::Osc99 => "osc99",
+		}
+	}
+
+	/// Protocol understood by the terminal identified from the environment.
+	fn detect(term: &str) -> Option<Self> {
+		match identify(term).name {
+			Some("kitty") => Some(Self::Osc99),
+			Some("iterm2" | "wezterm" | "ghostty" | "windows-terminal") => Some(Self::Osc9),
+			Some("foot") => Some(Self::Osc777),
+			_ if term.starts_with("rxvt-unicode") => Some(Self::Osc777),
+			_ => None,
+		}
+	}
+}
+
+/// Notification IDs, so kitty joins the title and body chunks.
+static NEXT_NOTIFICATION: AtomicU32 = AtomicU32::new(1);
+
+/// Replace line breaks and tabs with spaces and drop other control
+/// characters, any of which could terminate the sequence.
+fn notification_text(text: &str) -> String {
+	text
+		.chars()
+		.filter_map(|ch| match ch {
+			'\n' | '\r' | '\t' => Some(' '),
+			ch if ch.is_control() => None,
+			ch => Some(ch),
+		})
+		.collect()
+}
+
+fn notification_sequence(
+	protocol: NotifyProtocol,
+	title: &str,
+	body: Option<&str>,
+	urgency: u8,
+) -> String {
+	let title = notification_text(title);
+	let body = body.map(notification_text).filter(|body| !body.is_empty());
+	match protocol {
+		NotifyProtocol::Osc9 => {
+			let mut message = match body {
+				Some(body) => format!("{title}: {body}"),
+				None => title,
+			};
+			// ConEmu and Windows Terminal read `9;<digits>;` as a subcommand.
+			if message.starts_with(|ch: char| ch.is_ascii_digit()) {
+				message.insert(0, ' ');
+			}
+			format!("\x1b]9;{message}\x1b\\")
+		},
+		NotifyProtocol::Osc777 => {
+			// The title is a `;`-separated field; the body runs to the end.
+			let title = title.replace(';', ",");
+			format!("\x1b]777;notify;{title};{}\x1b\\", body.unwrap_or_default())
+		},
+		NotifyProtocol::Osc99 => match body {
+			Some(body) => {
+				let id = NEXT_NOTIFICATION.fetch_add(1, Ordering::Relaxed);
+				format!(
+					"\x1b]99;i={id}:d=0:u={urgency};{title}\x1b\\\x1b]99;i={id}:d=1:p=body;{body}\x1b\\"
+				)
+			},
+			None => format!("\x1b]99;u={urgency};{title}\x1b\\"),
+		},
+	}
+}
+
+/// Wrap a sequence in a tmux DCS passthrough. tmux 3.3 and later forward it
+/// only with `allow-passthrough` enabled.
+fn tmux_passthrough(sequence: &str) -> String {
+	format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+}
+
+/// Write to stdout when it is a terminal, else to the controlling terminal.
+fn write_to_terminal(sequence: &str) -> bool {
+	let stdout = std::io::stdout();
+	if stdout.is_terminal() {
+		let mut out = stdout.lock();
+		return out
+			.write_all(sequence.as_bytes())
+			.and_then(|()| out.flush())
+			.is_ok();
+	}
+	#[cfg(unix)]
+	{
+		fs::OpenOptions::new()
+			.write(true)
+			.open("/dev/tty")
+			.and_then(|mut tty| tty.write_all(sequence.as_bytes()))
+			.is_ok()
+	}
+	#[cfg(not(unix))]
+	{
+		false
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Detect the capabilities of the controlling terminal.
+///
+/// # Arguments
+/// - `options`: Whether to query the terminal live, and the reply timeout.
+///
+/// # Returns
+/// A capability report combining environment, terminfo, and query replies.
+#[napi(js_name = "detectTerminal")]
+pub fn detect_terminal(options: Option<DetectTerminalOptions>) -> task::Async<TerminalInfo> {
+	let (query, timeout_ms) = match options {
+		Some(opts) => (opts.query.unwrap_or(false), opts.timeout_ms),
+		None => (false, None),
+	};
+	let timeout = Duration::from_millis(u64::from(timeout_ms.unwrap_or(200)));
+	task::blocking("detect_terminal", (), move |_| Ok(detect(query, timeout)))
+}
+
+/// Show a desktop notification through the terminal.
+///
+/// # Arguments
+/// - `options`: Title, body, urgency, and an optional protocol override.
+///
+/// # Returns
+/// The protocol and sequence used, and whether it was written. Terminals
+/// with no known notification support get nothing.
+///
+/// # Errors
+/// Returns an error for an unknown urgency or protocol name.
+#[napi]
+pub fn notify(options: NotifyOptions) -> Result<NotifyResult> {
+	let urgency = match options.urgency.as_deref().unwrap_or("normal") {
+		"low" => 0,
+		"normal" => 1,
+		"critical" => 2,
+		other => return Err(Error::from_reason(format!("Unknown urgency: {other}"))),
+	};
+	let term = var("TERM").unwrap_or_default();
+	let protocol = match options.protocol.as_deref() {
+		Some(name) => Some(NotifyProtocol::parse(name)?),
+		None => NotifyProtocol::detect(&term),
+	};
+	let Some(protocol) = protocol else {
+		return Ok(NotifyResult { protocol: None, sequence: String::new(), sent: false });
+	};
+	let mut sequence =
+		notification_sequence(protocol, &options.title, options.body.as_deref(), urgency);
+	if multiplexer(&term) == Some("tmux") {
+		sequence = tmux_passthrough(&sequence);
+	}
+	let sent = options.write.unwrap_or(true) && write_to_terminal(&sequence);
+	Ok(NotifyResult { protocol: Some(protocol.name().to_string()), sequence, sent })
+}
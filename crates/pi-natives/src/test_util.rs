@@ -0,0 +1,52 @@
+//! Fixtures shared by unit tests.
+
+use std::{
+	fs,
+	ops::Deref,
+	path::{Path, PathBuf},
+};
+
+/// A scratch directory under the system temp directory, removed on drop so
+/// a failed assertion does not leave it behind.
+pub struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+	/// Create an empty, resolved directory named after `name` and this process.
+	/// Anything left at that path by an earlier run is removed first.
+	pub fn new(name: &str) -> Self {
+		let path = std::env::temp_dir().join(format!("pi-{name}-{}", std::process::id()));
+		let _ = fs::remove_dir_all(&path);
+		fs::create_dir_all(&path).unwrap();
+		Self(fs::canonicalize(&path).unwrap())
+	}
+}
+
+impl Deref for ScratchDir {
+	type Target = Path;
+
+	fn deref(&self) -> &Path {
+		&self.0
+	}
+}
+
+impl AsRef<Path> for ScratchDir {
+	fn as_ref(&self) -> &Path {
+		&self.0
+	}
+}
+
+impl Drop for ScratchDir {
+	fn drop(&mut self) {
+		let _ = fs::remove_dir_all(&self.0);
+	}
+}
+
+/// Sorted names of the entries in `dir`.
+pub fn dir_names(dir: &Path) -> Vec<String> {
+	let mut names: Vec<_> = fs::read_dir(dir)
+		.unwrap()
+		.map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+		.collect();
+	names.sort();
+	names
+}
@@ -8,15 +8,20 @@
 //! - Ellipsis decoded lazily
 //! - truncateToWidth returns the original `JsString` when possible
 
-use std::cell::RefCell;
+use std::{
+	cell::RefCell,
+	sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
 
 use napi::{JsString, bindgen_prelude::*};
 use napi_derive::napi;
+use rayon::prelude::*;
 use smallvec::{SmallVec, smallvec};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-const TAB_WIDTH: usize = 3;
+/// Cells a tab occupies unless changed with `setTextOptions`.
+pub const TAB_WIDTH: usize = 3;
 const ESC: u16 = 0x1b;
 
 fn build_utf16_string(mut data: Vec<u16>) -> Utf16String {
@@ -311,9 +316,9 @@ fn ansi_seq_len_u16(data: &[u16], pos: usize) -> Option<usize> {
 			None
 		},
 		0x5d => {
-			// ']' OSC
+			// ']' OSC, terminated by BEL, ST, or the 8-bit ST
 			for (i, &b) in data[pos + 2..].iter().enumerate() {
-				if b == 0x07 {
+				if b == 0x07 || b == 0x9c {
 					return Some(i + 3);
 				}
 				if b == ESC && data.get(pos + 2 + i + 1) == Some(&0x5c) {
@@ -346,37 +351,156 @@ fn ansi_seq_len_u16(data: &[u16], pos: usize) -> Option<usize> {
 }
 
 #[inline]
-fn is_sgr_u16(seq: &[u16]) -> bool {
+const fn is_sgr_u16(seq: &[u16]) -> bool {
 	seq.len() >= 3 && seq[1] == b'[' as u16 && *seq.last().unwrap() == b'm' as u16
 }
 
+/// OSC 8 with an empty URI, ending the current hyperlink.
+const HYPERLINK_CLOSE: &[u16] =
+	&[ESC, b']' as u16, b'8' as u16, b';' as u16, b';' as u16, ESC, b'\\' as u16];
+
+/// Whether `seq` opens or closes an OSC 8 hyperlink.
+fn is_hyperlink_u16(seq: &[u16]) -> bool {
+	seq.starts_with(&[ESC, b']' as u16, b'8' as u16, b';' as u16])
+}
+
+/// Whether `seq` is an OSC 8 hyperlink with a URI, i.e. one that opens a link
+/// rather than closing it.
+fn opens_hyperlink_u16(seq: &[u16]) -> bool {
+	if !is_hyperlink_u16(seq) {
+		return false;
+	}
+	let terminator = if matches!(seq.last(), Some(&0x07 | &0x9c)) {
+		1
+	} else {
+		2
+	};
+	// `ESC ] 8 ; params ; URI`
+	let body = &seq[4..seq.len() - terminator];
+	body
+		.iter()
+		.position(|&u| u == b';' as u16)
+		.is_some_and(|semi| semi + 1 < body.len())
+}
+
+/// Whether a hyperlink is still open at the end of `data`.
+fn ends_in_hyperlink_u16(data: &[u16]) -> bool {
+	let mut open = false;
+	let mut i = 0usize;
+	while i < data.len() {
+		if data[i] == ESC
+			&& let Some(seq_len) = ansi_seq_len_u16(data, i)
+		{
+			let seq = &data[i..i + seq_len];
+			if is_hyperlink_u16(seq) {
+				open = opens_hyperlink_u16(seq);
+			}
+			i += seq_len;
+			continue;
+		}
+		i += 1;
+	}
+	open
+}
+
+/// Close a hyperlink left open at the end of `out`, so it doesn't extend
+/// over whatever is printed next.
+fn close_hyperlink_u16(out: &mut Vec<u16>) {
+	if ends_in_hyperlink_u16(out) {
+		out.extend_from_slice(HYPERLINK_CLOSE);
+	}
+}
+
 // ============================================================================
 // Grapheme / Width
 // ============================================================================
 
+static TAB_CELLS: AtomicUsize = AtomicUsize::new(TAB_WIDTH);
+static AMBIGUOUS_WIDE: AtomicBool = AtomicBool::new(false);
+static EMOJI_WIDE: AtomicBool = AtomicBool::new(true);
+
+/// Terminal width settings for the width-aware text functions.
+#[napi(object)]
+pub struct TextOptions {
+	#[napi(js_name = "tabWidth")]
+	/// Cells a tab occupies (default: 3).
+	pub tab_width:      Option<u32>,
+	#[napi(js_name = "ambiguousWide")]
+	/// Draw East Asian ambiguous-width characters in two cells (default: false).
+	pub ambiguous_wide: Option<bool>,
+	#[napi(js_name = "emojiWide")]
+	/// Draw emoji in two cells (default: true).
+	pub emoji_wide:     Option<bool>,
+}
+
+/// Change how the width-aware text functions measure tabs, East Asian
+/// ambiguous-width characters, and emoji, to match the terminal.
+///
+/// Omitted fields keep their current value. The settings are process-wide.
+#[napi(js_name = "setTextOptions")]
+pub fn set_text_options(options: TextOptions) {
+	if let Some(tab_width) = options.tab_width {
+		TAB_CELLS.store(tab_width as usize, Ordering::Relaxed);
+	}
+	if let Some(wide) = options.ambiguous_wide {
+		AMBIGUOUS_WIDE.store(wide, Ordering::Relaxed);
+	}
+	if let Some(wide) = options.emoji_wide {
+		EMOJI_WIDE.store(wide, Ordering::Relaxed);
+	}
+}
+
+/// Cells a tab currently occupies.
+#[inline]
+pub fn tab_width() -> usize {
+	TAB_CELLS.load(Ordering::Relaxed)
+}
+
 #[inline]
-const fn ascii_cell_width_u16(u: u16) -> usize {
+fn ascii_cell_width_u16(u: u16) -> usize {
 	let b = u as u8;
 	match b {
-		b'\t' => TAB_WIDTH,
+		b'\t' => tab_width(),
 		0x20..=0x7e => 1,
 		_ => 0,
 	}
 }
 
+/// Cell width of one grapheme cluster.
 #[inline]
-fn grapheme_width_str(g: &str) -> usize {
+pub fn grapheme_width_str(g: &str) -> usize {
 	if g == "\t" {
-		return TAB_WIDTH;
+		return tab_width();
 	}
+	cluster_width(g, AMBIGUOUS_WIDE.load(Ordering::Relaxed), EMOJI_WIDE.load(Ordering::Relaxed))
+}
+
+/// Cell width of a cluster other than a tab under the given settings.
+fn cluster_width(g: &str, ambiguous_wide: bool, emoji_wide: bool) -> usize {
 	let mut it = g.chars();
 	let Some(c0) = it.next() else {
 		return 0;
 	};
-	if it.next().is_none() {
-		return UnicodeWidthChar::width(c0).unwrap_or(0);
+	let width = match (it.next().is_none(), ambiguous_wide) {
+		(true, false) => UnicodeWidthChar::width(c0).unwrap_or(0),
+		(true, true) => UnicodeWidthChar::width_cjk(c0).unwrap_or(0),
+		(false, false) => UnicodeWidthStr::width(g),
+		(false, true) => UnicodeWidthStr::width_cjk(g),
+	};
+	if width == 2 && !emoji_wide && is_emoji_cluster(g) {
+		1
+	} else {
+		width
 	}
-	UnicodeWidthStr::width(g)
+}
+
+/// Whether a cluster is drawn as an emoji: it carries an emoji presentation
+/// selector or starts in one of the pictograph blocks.
+fn is_emoji_cluster(g: &str) -> bool {
+	g.chars().any(|c| c == '\u{FE0F}')
+		|| g.chars().next().is_some_and(
+			|c| matches!(c, '\u{2600}'..='\u{27BF}' | '\u{2B00}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}'),
+		)
 }
 
 thread_local! {
@@ -471,6 +595,138 @@ fn visible_width_u16(data: &[u16]) -> usize {
 	visible_width_u16_up_to(data, usize::MAX).0
 }
 
+/// Visible width of a Rust string, excluding ANSI escape sequences.
+pub fn visible_width(text: &str) -> usize {
+	let data: Vec<u16> = text.encode_utf16().collect();
+	visible_width_u16(&data)
+}
+
+// ============================================================================
+// Styled spans
+// ============================================================================
+
+/// A color set by an SGR sequence.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SgrColor {
+	/// One of the 16 basic colors: 0-7 normal, 8-15 bright.
+	Basic(u8),
+	/// An entry of the 256-color palette.
+	Indexed(u8),
+	/// A 24-bit color.
+	Rgb(u8, u8, u8),
+}
+
+impl SgrColor {
+	const fn from_val(color: ColorVal) -> Option<Self> {
+		if color == COLOR_NONE {
+			None
+		} else if color < 0x100 {
+			Some(Self::Basic((color - 1) as u8))
+		} else if color < 0x1000000 {
+			Some(Self::Indexed((color & 0xff) as u8))
+		} else {
+			Some(Self::Rgb((color >> 16) as u8, (color >> 8) as u8, color as u8))
+		}
+	}
+}
+
+/// Attributes and colors in effect for a span of text.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SpanStyle {
+	pub bold:      bool,
+	pub dim:       bool,
+	pub italic:    bool,
+	pub underline: bool,
+	pub inverse:   bool,
+	pub hidden:    bool,
+	pub strike:    bool,
+	pub fg:        Option<SgrColor>,
+	pub bg:        Option<SgrColor>,
+}
+
+impl From<AnsiState> for SpanStyle {
+	fn from(state: AnsiState) -> Self {
+		let has = |attr: u16| state.attrs & attr != 0;
+		Self {
+			bold:      has(ATTR_BOLD),
+			dim:       has(ATTR_DIM),
+			italic:    has(ATTR_ITALIC),
+			underline: has(ATTR_UNDERLINE),
+			inverse:   has(ATTR_INVERSE),
+			hidden:    has(ATTR_HIDDEN),
+			strike:    has(ATTR_STRIKE),
+			fg:        SgrColor::from_val(state.fg),
+			bg:        SgrColor::from_val(state.bg),
+		}
+	}
+}
+
+/// A piece of a line reported by [`for_each_span`].
+pub enum SpanEvent {
+	/// A run of text with the style in effect.
+	Text(String, SpanStyle),
+	/// A non-SGR escape sequence, e.g. an OSC 8 hyperlink.
+	Escape(String),
+}
+
+/// Walk a line parsing escapes exactly as the width functions do, passing
+/// each text run and non-SGR escape sequence to `on_event` in order.
+pub fn for_each_span(line: &str, mut on_event: impl FnMut(SpanEvent)) {
+	let data: Vec<u16> = line.encode_utf16().collect();
+	let mut state = AnsiState::new();
+	let mut start = 0usize;
+	let mut i = 0usize;
+	while i < data.len() {
+		if data[i] == ESC
+			&& let Some(seq_len) = ansi_seq_len_u16(&data, i)
+		{
+			if start < i {
+				on_event(SpanEvent::Text(
+					String::from_utf16_lossy(&data[start..i]),
+					SpanStyle::from(state),
+				));
+			}
+			let seq = &data[i..i + seq_len];
+			if is_sgr_u16(seq) {
+				state.apply_sgr_u16(&seq[2..seq_len - 1]);
+			} else {
+				on_event(SpanEvent::Escape(String::from_utf16_lossy(seq)));
+			}
+			i += seq_len;
+			start = i;
+			continue;
+		}
+		i += 1;
+	}
+	if start < data.len() {
+		on_event(SpanEvent::Text(String::from_utf16_lossy(&data[start..]), SpanStyle::from(state)));
+	}
+}
+
+/// Split a line into runs of text sharing one style, parsing escapes exactly
+/// as the width functions do. Non-SGR sequences are dropped.
+pub fn styled_spans(line: &str) -> Vec<(String, SpanStyle)> {
+	let mut spans: Vec<(String, SpanStyle)> = Vec::new();
+	for_each_span(line, |event| {
+		if let SpanEvent::Text(text, style) = event {
+			match spans.last_mut() {
+				Some((last, last_style)) if *last_style == style => last.push_str(&text),
+				_ => spans.push((text, style)),
+			}
+		}
+	});
+	spans
+}
+
+/// Remove every escape sequence the width functions recognize.
+pub fn strip_ansi(text: &str) -> String {
+	if !text.contains('\x1b') {
+		return text.to_string();
+	}
+	let data: Vec<u16> = text.encode_utf16().collect();
+	strip_ansi_u16(&data).map_or_else(|| text.to_string(), |out| String::from_utf16_lossy(&out))
+}
+
 // ============================================================================
 // wrapTextWithAnsi
 // ============================================================================
@@ -577,11 +833,17 @@ fn split_into_tokens_with_ansi(line: &[u16]) -> SmallVec<[Vec<u16>; 4]> {
 	tokens
 }
 
-fn break_long_word(word: &[u16], width: usize, state: &mut AnsiState) -> SmallVec<[Vec<u16>; 4]> {
+fn break_long_word(
+	word: &[u16],
+	first_width: usize,
+	rest_width: usize,
+	state: &mut AnsiState,
+) -> SmallVec<[Vec<u16>; 4]> {
 	let mut lines = SmallVec::<[Vec<u16>; 4]>::new();
 	let mut current_line = Vec::<u16>::new();
 	write_active_codes(state, &mut current_line);
 	let mut current_width = 0usize;
+	let mut width = first_width;
 	let mut i = 0usize;
 
 	while i < word.len() {
@@ -616,6 +878,7 @@ fn break_long_word(word: &[u16], width: usize, state: &mut AnsiState) -> SmallVe
 					current_line = Vec::new();
 					write_active_codes(state, &mut current_line);
 					current_width = 0;
+					width = rest_width;
 				}
 				current_line.push(u);
 				current_width += gw;
@@ -627,6 +890,7 @@ fn break_long_word(word: &[u16], width: usize, state: &mut AnsiState) -> SmallVe
 					lines.push(std::mem::take(&mut current_line));
 					write_active_codes(state, &mut current_line);
 					current_width = 0;
+					width = rest_width;
 				}
 				current_line.extend_from_slice(gu16);
 				current_width += gw;
@@ -642,15 +906,37 @@ fn break_long_word(word: &[u16], width: usize, state: &mut AnsiState) -> SmallVe
 	lines
 }
 
-fn wrap_single_line(line: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
+/// Line widths and overflow handling for wrapping.
+#[derive(Clone, Copy)]
+struct WrapConfig<'a> {
+	/// Width of the first line of each paragraph.
+	width:  usize,
+	/// Prefix for continuation lines; they get `width` minus its width.
+	indent: &'a [u16],
+	/// Break words longer than the line; otherwise they overflow.
+	hard:   bool,
+}
+
+impl WrapConfig<'_> {
+	fn rest_width(&self) -> usize {
+		self
+			.width
+			.saturating_sub(visible_width_u16(self.indent))
+			.max(1)
+	}
+}
+
+fn wrap_single_line(line: &[u16], config: WrapConfig<'_>) -> SmallVec<[Vec<u16>; 4]> {
 	if line.is_empty() {
 		return smallvec![Vec::new()];
 	}
 
-	if visible_width_u16(line) <= width {
+	let first_width = config.width;
+	if visible_width_u16(line) <= first_width {
 		return smallvec![line.to_vec()];
 	}
 
+	let rest_width = config.rest_width();
 	let tokens = split_into_tokens_with_ansi(line);
 	let mut wrapped = SmallVec::<[Vec<u16>; 4]>::new();
 	let mut current_line = Vec::<u16>::new();
@@ -660,6 +946,11 @@ fn wrap_single_line(line: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
 	for token in tokens {
 		let token_width = visible_width_u16(&token);
 		let is_whitespace = token_is_whitespace(&token);
+		let width = if wrapped.is_empty() {
+			first_width
+		} else {
+			rest_width
+		};
 
 		if token_width > width && !is_whitespace {
 			if !current_line.is_empty() {
@@ -669,7 +960,24 @@ fn wrap_single_line(line: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
 				current_width = 0;
 			}
 
-			let mut broken = break_long_word(&token, width, &mut state);
+			if !config.hard {
+				// Give the word a line of its own and let it overflow.
+				if !wrapped.is_empty() {
+					write_active_codes(&state, &mut current_line);
+				}
+				current_line.extend_from_slice(&token);
+				current_width = token_width;
+				update_state_from_text(&token, &mut state);
+				continue;
+			}
+
+			// The word starts a continuation line if a line was just pushed.
+			let width = if wrapped.is_empty() {
+				first_width
+			} else {
+				rest_width
+			};
+			let mut broken = break_long_word(&token, width, rest_width, &mut state);
 			if let Some(last) = broken.pop() {
 				wrapped.extend(broken);
 				current_line = last;
@@ -686,13 +994,17 @@ fn wrap_single_line(line: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
 			wrapped.push(line_to_wrap);
 
 			current_line = Vec::new();
-			write_active_codes(&state, &mut current_line);
 			if is_whitespace {
+				// The dropped whitespace can carry codes, e.g. the reset ending
+				// a styled word, which must not leak onto the next line.
+				update_state_from_text(&token, &mut state);
+				write_active_codes(&state, &mut current_line);
 				current_width = 0;
-			} else {
-				current_line.extend_from_slice(&token);
-				current_width = token_width;
+				continue;
 			}
+			write_active_codes(&state, &mut current_line);
+			current_line.extend_from_slice(&token);
+			current_width = token_width;
 		} else {
 			current_line.extend_from_slice(&token);
 			current_width += token_width;
@@ -713,10 +1025,16 @@ fn wrap_single_line(line: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
 		wrapped.push(Vec::new());
 	}
 
+	if !config.indent.is_empty() {
+		for line in wrapped.iter_mut().skip(1) {
+			line.splice(0..0, config.indent.iter().copied());
+		}
+	}
+
 	wrapped
 }
 
-fn wrap_text_with_ansi_impl(text: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4]> {
+fn wrap_text_with_ansi_impl(text: &[u16], config: WrapConfig<'_>) -> SmallVec<[Vec<u16>; 4]> {
 	if text.is_empty() {
 		return smallvec![Vec::new()];
 	}
@@ -734,47 +1052,608 @@ fn wrap_text_with_ansi_impl(text: &[u16], width: usize) -> SmallVec<[Vec<u16>; 4
 			}
 			line_with_prefix.extend_from_slice(line);
 
-			let wrapped = wrap_single_line(&line_with_prefix, width);
-			result.extend(wrapped);
-			update_state_from_text(line, &mut state);
-			line_start = i + 1;
+			let wrapped = wrap_single_line(&line_with_prefix, config);
+			result.extend(wrapped);
+			update_state_from_text(line, &mut state);
+			line_start = i + 1;
+		}
+	}
+
+	if result.is_empty() {
+		result.push(Vec::new());
+	}
+
+	result
+}
+
+/// Wrap text to a visible width, preserving ANSI escape codes across line
+/// breaks.
+///
+/// Returns UTF-16 lines with active SGR codes carried across line boundaries.
+#[napi(js_name = "wrapTextWithAnsi")]
+pub fn wrap_text_with_ansi(text: JsString, width: u32) -> Result<Vec<Utf16String>> {
+	let text_u16 = text.into_utf16()?;
+	let config = WrapConfig { width: width as usize, indent: &[], hard: true };
+	let lines = wrap_text_with_ansi_impl(text_u16.as_slice(), config);
+	Ok(lines.into_iter().map(build_utf16_string).collect())
+}
+
+/// Options for `wrapToWidth`.
+#[napi(object)]
+pub struct WrapOptions {
+	/// Prefix for continuation lines, counted toward the width (default: none).
+	pub indent: Option<String>,
+	/// Break words longer than the width; when false they overflow on a line
+	/// of their own (default: true).
+	pub hard:   Option<bool>,
+}
+
+/// Wrap text to a visible width, carrying active SGR codes onto each
+/// continuation line.
+///
+/// Like `wrapTextWithAnsi`, with a hanging indent for continuation lines and
+/// control over words longer than the width.
+#[napi(js_name = "wrapToWidth")]
+pub fn wrap_to_width(
+	text: JsString,
+	width: u32,
+	options: Option<WrapOptions>,
+) -> Result<Vec<Utf16String>> {
+	let text_u16 = text.into_utf16()?;
+	let (indent, hard) = options.map_or((None, None), |opts| (opts.indent, opts.hard));
+	let indent: Vec<u16> = indent.unwrap_or_default().encode_utf16().collect();
+	let config =
+		WrapConfig { width: width as usize, indent: &indent, hard: hard.unwrap_or(true) };
+	let lines = wrap_text_with_ansi_impl(text_u16.as_slice(), config);
+	Ok(lines.into_iter().map(build_utf16_string).collect())
+}
+
+// ============================================================================
+// layoutLine
+// ============================================================================
+
+/// One row of a wrapped line, as UTF-8 byte offsets into the input.
+#[napi(object)]
+pub struct LayoutSegment {
+	#[napi(js_name = "startByte")]
+	/// Byte offset where the row starts.
+	pub start_byte: u32,
+	#[napi(js_name = "endByte")]
+	/// Byte offset where the row ends (exclusive). Whitespace dropped at a
+	/// break belongs to the row before it, so rows cover the whole line.
+	pub end_byte:   u32,
+	/// Visible width of the row, excluding the continuation indent and
+	/// whitespace dropped at the break.
+	pub width:      u32,
+}
+
+/// A run of spaces or non-spaces, including the escapes just before it.
+struct LayoutToken {
+	start: usize,
+	end:   usize,
+	width: usize,
+	space: bool,
+}
+
+/// Split a line into tokens the same way `split_into_tokens_with_ansi` does,
+/// as ranges instead of copies.
+fn layout_tokens(line: &[u16]) -> Vec<LayoutToken> {
+	let mut tokens = Vec::new();
+	let mut token_start = 0usize;
+	let mut has_text = false;
+	let mut in_whitespace = false;
+	let mut pending_ansi: Option<usize> = None;
+	let mut i = 0usize;
+
+	while i < line.len() {
+		if line[i] == ESC
+			&& let Some(seq_len) = ansi_seq_len_u16(line, i)
+		{
+			pending_ansi.get_or_insert(i);
+			i += seq_len;
+			continue;
+		}
+
+		let start = pending_ansi.take().unwrap_or(i);
+		let char_is_space = line[i] == b' ' as u16;
+		if char_is_space != in_whitespace && has_text {
+			tokens.push(LayoutToken {
+				start: token_start,
+				end:   start,
+				width: 0,
+				space: in_whitespace,
+			});
+			token_start = start;
+		}
+		has_text = true;
+		in_whitespace = char_is_space;
+		i += 1;
+	}
+
+	if has_text || token_start < line.len() {
+		tokens.push(LayoutToken {
+			start: token_start,
+			end:   line.len(),
+			width: 0,
+			space: in_whitespace,
+		});
+	}
+	for token in &mut tokens {
+		token.width = visible_width_u16(&line[token.start..token.end]);
+	}
+	tokens
+}
+
+/// Call `f(start, cluster, width)` for each grapheme in `line[start..end]`,
+/// skipping escape sequences. `f` returns `false` to stop early.
+fn for_each_cell_u16(
+	line: &[u16],
+	start: usize,
+	end: usize,
+	mut f: impl FnMut(usize, &[u16], usize) -> bool,
+) {
+	let mut i = start;
+	while i < end {
+		if line[i] == ESC {
+			i += ansi_seq_len_u16(line, i).unwrap_or(1);
+			continue;
+		}
+
+		let seg_start = i;
+		let mut is_ascii = true;
+		while i < end && line[i] != ESC {
+			if line[i] > 0x7f {
+				is_ascii = false;
+			}
+			i += 1;
+		}
+
+		if is_ascii {
+			for pos in seg_start..i {
+				if !f(pos, &line[pos..=pos], ascii_cell_width_u16(line[pos])) {
+					return;
+				}
+			}
+		} else {
+			let mut pos = seg_start;
+			let done = !for_each_grapheme_u16_slow(&line[seg_start..i], |g, w| {
+				let more = f(pos, g, w);
+				pos += g.len();
+				more
+			});
+			if done {
+				return;
+			}
+		}
+	}
+}
+
+/// Rows of a single line as `(start, end, width)` UTF-16 ranges, breaking
+/// where `wrap_single_line` does.
+fn layout_line_u16(line: &[u16], config: WrapConfig<'_>) -> Vec<(usize, usize, usize)> {
+	let first_width = config.width;
+	let total = visible_width_u16(line);
+	if total <= first_width {
+		return vec![(0, line.len(), total)];
+	}
+
+	let rest_width = config.rest_width();
+	let mut rows: Vec<(usize, usize, usize)> = Vec::new();
+	let mut row_start = 0usize;
+	let mut row_width = 0usize;
+	// Width of the spaces ending the row, trimmed when it is pushed.
+	let mut trailing = 0usize;
+
+	for token in layout_tokens(line) {
+		let width = if rows.is_empty() {
+			first_width
+		} else {
+			rest_width
+		};
+
+		if token.width > width && !token.space {
+			if token.start > row_start {
+				rows.push((row_start, token.start, row_width - trailing));
+				row_start = token.start;
+			}
+			trailing = 0;
+
+			if !config.hard {
+				// The word gets a row of its own and overflows.
+				row_width = token.width;
+				continue;
+			}
+
+			let mut width = if rows.is_empty() {
+				first_width
+			} else {
+				rest_width
+			};
+			row_width = 0;
+			for_each_cell_u16(line, token.start, token.end, |pos, _, cell_width| {
+				if row_width + cell_width > width && row_width > 0 {
+					rows.push((row_start, pos, row_width));
+					row_start = pos;
+					row_width = 0;
+					width = rest_width;
+				}
+				row_width += cell_width;
+				true
+			});
+			continue;
+		}
+
+		if row_width + token.width > width && row_width > 0 {
+			let end = if token.space { token.end } else { token.start };
+			rows.push((row_start, end, row_width - trailing));
+			row_start = end;
+			row_width = if token.space { 0 } else { token.width };
+			trailing = 0;
+			continue;
+		}
+
+		row_width += token.width;
+		trailing = if token.space {
+			trailing + token.width
+		} else {
+			0
+		};
+	}
+
+	if row_start < line.len() || rows.is_empty() {
+		rows.push((row_start, line.len(), row_width - trailing));
+	}
+	rows
+}
+
+/// Converts increasing UTF-16 offsets into UTF-8 byte offsets.
+struct Utf8Offsets<'a> {
+	data: &'a [u16],
+	unit: usize,
+	byte: usize,
+}
+
+impl Utf8Offsets<'_> {
+	fn advance(&mut self, to: usize) -> usize {
+		while self.unit < to {
+			let u = self.data[self.unit];
+			self.byte += match u {
+				0..=0x7f => 1,
+				0x80..=0x7ff => 2,
+				0xd800..=0xdbff if matches!(self.data.get(self.unit + 1), Some(0xdc00..=0xdfff)) => {
+					self.unit += 1;
+					4
+				},
+				// Lone surrogates encode as U+FFFD.
+				_ => 3,
+			};
+			self.unit += 1;
+		}
+		self.byte
+	}
+}
+
+/// Compute where a line wraps at a visible width without building the
+/// wrapped strings.
+///
+/// Uses the word-breaking rules of `wrapToWidth` with the same options. The
+/// text is treated as a single line; split on newlines first.
+#[napi(js_name = "layoutLine")]
+pub fn layout_line(
+	text: JsString,
+	width: u32,
+	options: Option<WrapOptions>,
+) -> Result<Vec<LayoutSegment>> {
+	let text_u16 = text.into_utf16()?;
+	let data = text_u16.as_slice();
+	let (indent, hard) = options.map_or((None, None), |opts| (opts.indent, opts.hard));
+	let indent: Vec<u16> = indent.unwrap_or_default().encode_utf16().collect();
+	let config =
+		WrapConfig { width: width as usize, indent: &indent, hard: hard.unwrap_or(true) };
+
+	let mut offsets = Utf8Offsets { data, unit: 0, byte: 0 };
+	Ok(layout_line_u16(data, config)
+		.into_iter()
+		.map(|(start, end, width)| LayoutSegment {
+			start_byte: clamp_u32(offsets.advance(start)),
+			end_byte:   clamp_u32(offsets.advance(end)),
+			width:      clamp_u32(width),
+		})
+		.collect())
+}
+
+// ============================================================================
+// graphemes / graphemeAt
+// ============================================================================
+
+/// A grapheme cluster with its position and cell width.
+#[napi(object)]
+pub struct GraphemeInfo {
+	/// The cluster text.
+	pub cluster:     String,
+	#[napi(js_name = "byteOffset")]
+	/// UTF-8 byte offset of the cluster in the input.
+	pub byte_offset: u32,
+	/// Column where the cluster starts, in terminal cells.
+	pub column:      u32,
+	/// Width of the cluster in terminal cells.
+	pub width:       u32,
+}
+
+/// Visit each grapheme cluster with its position, skipping escape sequences.
+/// `f` returns `false` to stop early.
+fn for_each_grapheme_info(data: &[u16], mut f: impl FnMut(GraphemeInfo) -> bool) {
+	let mut offsets = Utf8Offsets { data, unit: 0, byte: 0 };
+	let mut column = 0usize;
+	for_each_cell_u16(data, 0, data.len(), |pos, cluster, width| {
+		let info = GraphemeInfo {
+			cluster:     String::from_utf16_lossy(cluster),
+			byte_offset: clamp_u32(offsets.advance(pos)),
+			column:      clamp_u32(column),
+			width:       clamp_u32(width),
+		};
+		column += width;
+		f(info)
+	});
+}
+
+fn graphemes_u16(data: &[u16]) -> Vec<GraphemeInfo> {
+	let mut clusters = Vec::new();
+	for_each_grapheme_info(data, |info| {
+		clusters.push(info);
+		true
+	});
+	clusters
+}
+
+fn grapheme_at_u16(data: &[u16], column: u32) -> Option<GraphemeInfo> {
+	let mut found = None;
+	for_each_grapheme_info(data, |info| {
+		if column < info.column + info.width {
+			found = Some(info);
+			return false;
+		}
+		true
+	});
+	found
+}
+
+/// Split text into grapheme clusters with their cell widths.
+///
+/// Clusters and widths match the width functions; escape sequences are
+/// skipped.
+#[napi(js_name = "graphemes")]
+pub fn graphemes(text: JsString) -> Result<Vec<GraphemeInfo>> {
+	let text_u16 = text.into_utf16()?;
+	Ok(graphemes_u16(text_u16.as_slice()))
+}
+
+/// Find the grapheme cluster covering a visible column.
+///
+/// Both cells of a wide character resolve to it. Returns None past the end of
+/// the text; zero-width clusters are never returned.
+#[napi(js_name = "graphemeAt")]
+pub fn grapheme_at(text: JsString, column: u32) -> Result<Option<GraphemeInfo>> {
+	let text_u16 = text.into_utf16()?;
+	Ok(grapheme_at_u16(text_u16.as_slice(), column))
+}
+
+// ============================================================================
+// truncateToWidth
+// ============================================================================
+
+/// Which part of the text `truncateToWidth` keeps.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum TruncateMode {
+	/// Keep the head; the ellipsis goes at the end.
+	End,
+	/// Keep the tail; the ellipsis goes at the start.
+	Start,
+	/// Keep head and tail; the ellipsis goes in the middle.
+	Middle,
+	/// Like `End`, but cut at the last word boundary that fits.
+	Word,
+}
+
+impl TruncateMode {
+	fn parse(name: Option<&str>) -> Result<Self> {
+		match name {
+			None | Some("end") => Ok(Self::End),
+			Some("start") => Ok(Self::Start),
+			Some("middle") => Ok(Self::Middle),
+			Some("word") => Ok(Self::Word),
+			Some(other) => Err(Error::from_reason(format!("Unknown truncate mode: {other}"))),
+		}
+	}
+}
+
+#[inline]
+fn is_space_u16(unit: &[u16]) -> bool {
+	matches!(unit, [u] if char::from_u32(u32::from(*u)).is_some_and(char::is_whitespace))
+}
+
+/// Head of `text` that fits in `target_w` cells, with its ANSI codes.
+///
+/// Returns the kept units, their width, and whether an SGR code was copied.
+/// With `word`, the cut moves back to the end of the last word that fits,
+/// unless the first word alone is too wide.
+fn truncate_head_u16(text: &[u16], target_w: usize, word: bool) -> (Vec<u16>, usize, bool) {
+	let mut out = Vec::with_capacity(text.len().min(target_w * 2) + 8);
+	let mut w = 0usize;
+	let mut i = 0usize;
+	let text_len = text.len();
+
+	let mut saw_sgr = false;
+	// End of the last word seen, as (output length, width).
+	let mut boundary: Option<(usize, usize)> = None;
+	let mut prev_space = true;
+	let mut cut = false;
+
+	let mut push = |out: &mut Vec<u16>, unit: &[u16], gw: usize| {
+		let space = word && is_space_u16(unit);
+		if space && !prev_space {
+			boundary = Some((out.len(), w));
+		}
+		prev_space = space;
+		if w + gw > target_w {
+			return false;
+		}
+		out.extend_from_slice(unit);
+		w += gw;
+		true
+	};
+
+	while i < text_len && !cut {
+		if text[i] == ESC {
+			if let Some(seq_len) = ansi_seq_len_u16(text, i) {
+				let seq = &text[i..i + seq_len];
+				out.extend_from_slice(seq);
+				if is_sgr_u16(seq) {
+					saw_sgr = true;
+				}
+				i += seq_len;
+				continue;
+			}
+			out.push(ESC);
+			i += 1;
+			continue;
+		}
+
+		let start = i;
+		let mut is_ascii = true;
+		while i < text_len && text[i] != ESC {
+			if text[i] > 0x7f {
+				is_ascii = false;
+			}
+			i += 1;
+		}
+		let seg = &text[start..i];
+
+		if is_ascii {
+			for u in seg {
+				if !push(&mut out, std::slice::from_ref(u), ascii_cell_width_u16(*u)) {
+					cut = true;
+					break;
+				}
+			}
+		} else {
+			cut = !for_each_grapheme_u16_slow(seg, |gu16, gw| push(&mut out, gu16, gw));
+		}
+	}
+
+	if cut
+		&& word
+		&& let Some((len, boundary_w)) = boundary
+	{
+		out.truncate(len);
+		w = boundary_w;
+	}
+	(out, w, saw_sgr)
+}
+
+/// Tail of `text` that fits in `target_w` cells, led by the ANSI codes that
+/// precede it so its styling is unchanged.
+fn truncate_tail_u16(text: &[u16], target_w: usize) -> (Vec<u16>, usize) {
+	if target_w == 0 {
+		return (Vec::new(), 0);
+	}
+	let text_w = visible_width_u16(text);
+	slice_with_width_impl(text, text_w.saturating_sub(target_w), target_w, true)
+}
+
+/// Truncate `text`, which is known to be wider than `max_width`.
+fn truncate_u16(
+	text: &[u16],
+	max_width: usize,
+	ellipsis: &[u16],
+	ellipsis_w: usize,
+	pad: bool,
+	mode: TruncateMode,
+) -> Vec<u16> {
+	const RESET: &[u16] = &[ESC, b'[' as u16, b'0' as u16, b'm' as u16];
+
+	let target_w = max_width.saturating_sub(ellipsis_w);
+
+	// If ellipsis alone doesn't fit, return ellipsis cut to max_width
+	if target_w == 0 {
+		let mut out = Vec::with_capacity(ellipsis.len().min(max_width * 2));
+		let mut w = 0usize;
+		let _ = for_each_grapheme_u16_slow(ellipsis, |gu16, gw| {
+			if w + gw > max_width {
+				return false;
+			}
+			out.extend_from_slice(gu16);
+			w += gw;
+			true
+		});
+
+		if pad && w < max_width {
+			out.resize(out.len() + (max_width - w), b' ' as u16);
 		}
+		return out;
 	}
 
-	if result.is_empty() {
-		result.push(Vec::new());
-	}
-
-	result
-}
+	let (mut out, w) = match mode {
+		TruncateMode::End | TruncateMode::Word => {
+			let (mut out, w, saw_sgr) = truncate_head_u16(text, target_w, mode == TruncateMode::Word);
+			close_hyperlink_u16(&mut out);
+			// Only reset if we actually copied SGR codes into the output.
+			if saw_sgr {
+				out.extend_from_slice(RESET);
+			}
+			out.extend_from_slice(ellipsis);
+			(out, w)
+		},
+		TruncateMode::Start => {
+			let (tail, tail_w) = truncate_tail_u16(text, target_w);
+			let mut out = Vec::with_capacity(ellipsis.len() + tail.len());
+			out.extend_from_slice(ellipsis);
+			out.extend_from_slice(&tail);
+			(out, tail_w)
+		},
+		TruncateMode::Middle => {
+			let head_target = target_w - target_w / 2;
+			let (mut out, head_w, saw_sgr) = truncate_head_u16(text, head_target, false);
+			close_hyperlink_u16(&mut out);
+			// The tail replays earlier codes, so the ellipsis starts unstyled.
+			if saw_sgr {
+				out.extend_from_slice(RESET);
+			}
+			out.extend_from_slice(ellipsis);
+			let (tail, tail_w) = truncate_tail_u16(text, target_w - head_w);
+			out.extend_from_slice(&tail);
+			(out, head_w + tail_w)
+		},
+	};
 
-/// Wrap text to a visible width, preserving ANSI escape codes across line
-/// breaks.
-///
-/// Returns UTF-16 lines with active SGR codes carried across line boundaries.
-#[napi(js_name = "wrapTextWithAnsi")]
-pub fn wrap_text_with_ansi(text: JsString, width: u32) -> Result<Vec<Utf16String>> {
-	let text_u16 = text.into_utf16()?;
-	let lines = wrap_text_with_ansi_impl(text_u16.as_slice(), width as usize);
-	Ok(lines.into_iter().map(build_utf16_string).collect())
+	if pad {
+		let out_w = w + ellipsis_w;
+		if out_w < max_width {
+			out.resize(out.len() + (max_width - out_w), b' ' as u16);
+		}
+	}
+	out
 }
 
-// ============================================================================
-// truncateToWidth
-// ============================================================================
-
 /// Truncate text to a visible width, preserving ANSI codes.
 ///
 /// `ellipsis_kind`: 0 = "…", 1 = "...", 2 = "" (omit); pads with spaces when
-/// requested.
+/// requested. `mode` picks what is kept: `"end"` (default) keeps the head,
+/// `"start"` the tail, `"middle"` both ends, and `"word"` the head cut at a
+/// word boundary.
+///
+/// # Errors
+/// Returns an error for an unknown mode.
 #[napi(js_name = "truncateToWidth")]
 pub fn truncate_to_width(
 	text: JsString<'_>,
 	max_width: u32,
 	ellipsis_kind: u8,
 	pad: bool,
+	mode: Option<String>,
 ) -> Result<Either<JsString<'_>, Utf16String>> {
 	let max_width = max_width as usize;
+	let mode = TruncateMode::parse(mode.as_deref())?;
 
 	// Keep original handle so we can return it without allocating.
 	let original = text;
@@ -813,102 +1692,228 @@ pub fn truncate_to_width(
 		_ => (ELLIPSIS_UNICODE, 1), // Default to Unicode for invalid values
 	};
 
-	let target_w = max_width.saturating_sub(ellipsis_w);
+	let out = truncate_u16(text, max_width, ellipsis, ellipsis_w, pad, mode);
+	Ok(Either::B(build_utf16_string(out)))
+}
 
-	// If ellipsis alone doesn't fit, return ellipsis cut to max_width
-	if target_w == 0 {
-		let mut out = Vec::with_capacity(ellipsis.len().min(max_width * 2));
-		let mut w = 0usize;
-		let _ = for_each_grapheme_u16_slow(ellipsis, |gu16, gw| {
-			if w + gw > max_width {
-				return false;
-			}
-			out.extend_from_slice(gu16);
-			w += gw;
-			true
-		});
+// ============================================================================
+// padToWidth
+// ============================================================================
 
-		if pad && w < max_width {
-			out.resize(out.len() + (max_width - w), b' ' as u16);
+/// Where `padToWidth` places the text within the padded width.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Align {
+	Left,
+	Right,
+	Center,
+}
+
+impl Align {
+	fn parse(name: Option<&str>) -> Result<Self> {
+		match name {
+			None | Some("left") => Ok(Self::Left),
+			Some("right") => Ok(Self::Right),
+			Some("center") => Ok(Self::Center),
+			Some(other) => Err(Error::from_reason(format!("Unknown alignment: {other}"))),
 		}
-		return Ok(Either::B(build_utf16_string(out)));
 	}
+}
 
-	// Main truncation
-	let mut out = Vec::with_capacity(text.len().min(max_width * 2) + ellipsis.len() + 8);
-	let mut w = 0usize;
-	let mut i = 0usize;
-	let text_len = text.len();
+/// Append `gap` cells of `fill` (`fill_w` cells wide). Cells a wide fill
+/// cannot cover become spaces, placed on the side next to the text.
+fn push_fill_u16(out: &mut Vec<u16>, gap: usize, fill: &[u16], fill_w: usize, spaces_first: bool) {
+	let spaces = gap % fill_w;
+	if spaces_first {
+		out.resize(out.len() + spaces, b' ' as u16);
+	}
+	for _ in 0..gap / fill_w {
+		out.extend_from_slice(fill);
+	}
+	if !spaces_first {
+		out.resize(out.len() + spaces, b' ' as u16);
+	}
+}
 
-	let mut saw_sgr = false;
+/// Pad `text`, `text_w` cells wide, to `width` cells; `None` when it already
+/// fills the width.
+fn pad_u16(
+	text: &[u16],
+	text_w: usize,
+	width: usize,
+	align: Align,
+	fill: &[u16],
+	fill_w: usize,
+) -> Option<Vec<u16>> {
+	let gap = width.checked_sub(text_w).filter(|&gap| gap > 0)?;
+	let (left, right) = match align {
+		Align::Left => (0, gap),
+		Align::Right => (gap, 0),
+		Align::Center => (gap / 2, gap - gap / 2),
+	};
+	let mut out = Vec::with_capacity(text.len() + gap * fill.len());
+	push_fill_u16(&mut out, left, fill, fill_w, false);
+	out.extend_from_slice(text);
+	push_fill_u16(&mut out, right, fill, fill_w, true);
+	Some(out)
+}
 
-	while i < text_len {
-		if text[i] == ESC {
-			if let Some(seq_len) = ansi_seq_len_u16(text, i) {
-				let seq = &text[i..i + seq_len];
-				out.extend_from_slice(seq);
-				if is_sgr_u16(seq) {
-					saw_sgr = true;
-				}
-				i += seq_len;
-				continue;
-			}
-			out.push(ESC);
-			i += 1;
-			continue;
-		}
+/// Pad text with `fill_char` to a visible width, preserving ANSI codes.
+///
+/// The text is measured once; text already `width` cells wide or wider is
+/// returned unchanged. A double-width fill covers two cells per copy, with a
+/// space making up an odd remainder.
+///
+/// # Errors
+/// Returns an error for an unknown alignment, or a fill that is not a single
+/// visible character.
+#[napi(js_name = "padToWidth")]
+pub fn pad_to_width(
+	text: JsString<'_>,
+	width: u32,
+	align: Option<String>,
+	fill_char: Option<String>,
+) -> Result<Either<JsString<'_>, Utf16String>> {
+	let align = Align::parse(align.as_deref())?;
+	let fill = fill_char.as_deref().unwrap_or(" ");
+	let mut graphemes = fill.graphemes(true);
+	let (Some(grapheme), None) = (graphemes.next(), graphemes.next()) else {
+		return Err(Error::from_reason(format!("Fill must be a single character: {fill:?}")));
+	};
+	let fill_w = grapheme_width_str(grapheme);
+	if fill_w == 0 || grapheme == "\t" {
+		return Err(Error::from_reason(format!("Fill must be a visible character: {fill:?}")));
+	}
+	let fill: Vec<u16> = grapheme.encode_utf16().collect();
 
-		let start = i;
-		let mut is_ascii = true;
-		while i < text_len && text[i] != ESC {
-			if text[i] > 0x7f {
-				is_ascii = false;
-			}
-			i += 1;
-		}
-		let seg = &text[start..i];
+	// Keep original handle so we can return it without allocating.
+	let original = text;
 
-		if is_ascii {
-			for &u in seg {
-				let gw = ascii_cell_width_u16(u);
-				if w + gw > target_w {
-					break;
-				}
-				out.push(u);
-				w += gw;
-			}
-			if w >= target_w {
-				break;
-			}
-		} else {
-			let keep_going = for_each_grapheme_u16_slow(seg, |gu16, gw| {
-				if w + gw > target_w {
-					return false;
-				}
-				out.extend_from_slice(gu16);
-				w += gw;
-				true
-			});
-			if !keep_going {
-				break;
+	let text_u16 = text.into_utf16()?;
+	let text = text_u16.as_slice();
+	let (text_w, _) = visible_width_u16_up_to(text, width as usize);
+	Ok(match pad_u16(text, text_w, width as usize, align, &fill, fill_w) {
+		Some(out) => Either::B(build_utf16_string(out)),
+		None => Either::A(original),
+	})
+}
+
+// ============================================================================
+// joinStyled
+// ============================================================================
+
+/// Whether SGR codes in `data` leave a style active at its end: the last SGR
+/// is anything but a full reset.
+fn ends_styled_u16(data: &[u16]) -> bool {
+	let mut styled = false;
+	let mut i = 0usize;
+	while i < data.len() {
+		if data[i] == ESC
+			&& let Some(seq_len) = ansi_seq_len_u16(data, i)
+		{
+			let seq = &data[i..i + seq_len];
+			if is_sgr_u16(seq) {
+				styled = !seq[2..seq_len - 1].iter().all(|&u| u == b'0' as u16);
 			}
+			i += seq_len;
+			continue;
 		}
+		i += 1;
 	}
+	styled
+}
 
-	// Only reset if we actually copied SGR codes into the output.
-	if saw_sgr {
+/// Append `data`, then a reset when it leaves a style active and a close
+/// when it leaves a hyperlink open.
+fn push_isolated_u16(out: &mut Vec<u16>, data: &[u16]) {
+	out.extend_from_slice(data);
+	if ends_styled_u16(data) {
 		out.extend_from_slice(&[ESC, b'[' as u16, b'0' as u16, b'm' as u16]);
 	}
-	out.extend_from_slice(ellipsis);
+	if ends_in_hyperlink_u16(data) {
+		out.extend_from_slice(HYPERLINK_CLOSE);
+	}
+}
 
-	if pad {
-		let out_w = w + ellipsis_w;
-		if out_w < max_width {
-			out.resize(out.len() + (max_width - out_w), b' ' as u16);
+fn join_styled_u16<'a>(
+	segments: impl IntoIterator<Item = &'a [u16]>,
+	separator: &[u16],
+) -> Vec<u16> {
+	let mut out = Vec::new();
+	for (index, segment) in segments.into_iter().enumerate() {
+		if index > 0 {
+			push_isolated_u16(&mut out, separator);
 		}
+		push_isolated_u16(&mut out, segment);
 	}
+	out
+}
 
-	Ok(Either::B(build_utf16_string(out)))
+/// Join styled segments so no segment's styling reaches the next.
+///
+/// Each segment and separator that leaves an SGR style active is followed by
+/// a reset, and one that leaves a hyperlink open by a close; nothing is
+/// restored afterwards, so every piece starts unstyled.
+#[napi(js_name = "joinStyled")]
+pub fn join_styled(segments: Vec<String>, separator: Option<String>) -> Utf16String {
+	let separator: Vec<u16> = separator.unwrap_or_default().encode_utf16().collect();
+	let segments: Vec<Vec<u16>> = segments
+		.iter()
+		.map(|segment| segment.encode_utf16().collect())
+		.collect();
+	build_utf16_string(join_styled_u16(segments.iter().map(Vec::as_slice), &separator))
+}
+
+// ============================================================================
+// wrapWithHyperlink
+// ============================================================================
+
+/// Wrap `text` in an OSC 8 link to `uri`. Links already inside `text` are
+/// dropped, since links cannot nest.
+fn hyperlink_u16(text: &[u16], uri: &str) -> Vec<u16> {
+	const HEX: &[u8; 16] = b"0123456789ABCDEF";
+
+	let mut out = Vec::with_capacity(text.len() + uri.len() + 2 * HYPERLINK_CLOSE.len());
+	out.extend_from_slice(&[ESC, b']' as u16, b'8' as u16, b';' as u16, b';' as u16]);
+	// The URI may only hold printable ASCII; escape everything else.
+	for &b in uri.as_bytes() {
+		if (0x21..=0x7e).contains(&b) {
+			out.push(u16::from(b));
+		} else {
+			out.extend_from_slice(&[
+				b'%' as u16,
+				u16::from(HEX[usize::from(b >> 4)]),
+				u16::from(HEX[usize::from(b & 0xf)]),
+			]);
+		}
+	}
+	out.extend_from_slice(&[ESC, b'\\' as u16]);
+
+	let stripped = filter_u16(text, |i| {
+		if text[i] == ESC
+			&& let Some(seq_len) = ansi_seq_len_u16(text, i)
+		{
+			return (seq_len, !is_hyperlink_u16(&text[i..i + seq_len]));
+		}
+		(1, true)
+	});
+	out.extend_from_slice(stripped.as_deref().unwrap_or(text));
+	out.extend_from_slice(HYPERLINK_CLOSE);
+	out
+}
+
+/// Wrap text in an OSC 8 hyperlink to `uri`.
+///
+/// The link sequences are zero-width for the width and slicing functions.
+///
+/// # Errors
+/// Returns an error for an empty URI.
+#[napi(js_name = "wrapWithHyperlink")]
+pub fn wrap_with_hyperlink(text: JsString, uri: String) -> Result<Utf16String> {
+	if uri.is_empty() {
+		return Err(Error::from_reason("Hyperlink URI must not be empty"));
+	}
+	let text_u16 = text.into_utf16()?;
+	Ok(build_utf16_string(hyperlink_u16(text_u16.as_slice(), &uri)))
 }
 
 // ============================================================================
@@ -936,8 +1941,15 @@ fn slice_with_width_impl(
 	while i < line_len && current_col < end_col {
 		if line[i] == ESC {
 			if let Some(seq_len) = ansi_seq_len_u16(line, i) {
+				let seq = &line[i..i + seq_len];
 				if current_col >= start_col {
-					out.extend_from_slice(&line[i..i + seq_len]);
+					out.extend_from_slice(seq);
+				} else if is_hyperlink_u16(seq) {
+					// Only the link open where the slice starts carries over.
+					pending_ansi.retain(|&mut (p, l)| !is_hyperlink_u16(&line[p..p + l]));
+					if opens_hyperlink_u16(seq) {
+						pending_ansi.push((i, seq_len));
+					}
 				} else {
 					pending_ansi.push((i, seq_len));
 				}
@@ -1019,6 +2031,7 @@ fn slice_with_width_impl(
 		}
 		break;
 	}
+	close_hyperlink_u16(&mut out);
 
 	(out, out_w)
 }
@@ -1026,7 +2039,8 @@ fn slice_with_width_impl(
 /// Slice a range of visible columns from a line.
 ///
 /// Counts terminal cells, skipping ANSI escapes, and optionally enforces strict
-/// width.
+/// width. A hyperlink cut by the slice is reopened at its start and closed at
+/// its end.
 #[napi(js_name = "sliceWithWidth")]
 pub fn slice_with_width(
 	line: JsString,
@@ -1070,6 +2084,8 @@ fn extract_segments_impl(
 
 	let mut after_started = false;
 	let mut state = AnsiState::new();
+	// Hyperlink open at the current column, as (pos, len) of its opener.
+	let mut link: Option<(usize, usize)> = None;
 
 	let done_col = if after_len == 0 {
 		before_end
@@ -1083,6 +2099,8 @@ fn extract_segments_impl(
 				let seq = &line[i..i + seq_len];
 				if is_sgr_u16(seq) {
 					state.apply_sgr_u16(&seq[2..seq_len - 1]);
+				} else if is_hyperlink_u16(seq) {
+					link = opens_hyperlink_u16(seq).then_some((i, seq_len));
 				}
 
 				if current_col < before_end {
@@ -1135,6 +2153,9 @@ fn extract_segments_impl(
 					if fits {
 						if !after_started {
 							state.write_restore_u16(&mut after);
+							if let Some((p, l)) = link {
+								after.extend_from_slice(&line[p..p + l]);
+							}
 							after_started = true;
 						}
 						after.push(u);
@@ -1163,6 +2184,9 @@ fn extract_segments_impl(
 					if fits {
 						if !after_started {
 							state.write_restore_u16(&mut after);
+							if let Some((p, l)) = link {
+								after.extend_from_slice(&line[p..p + l]);
+							}
 							after_started = true;
 						}
 						after.extend_from_slice(gu16);
@@ -1176,13 +2200,17 @@ fn extract_segments_impl(
 		}
 	}
 
+	close_hyperlink_u16(&mut before);
+	close_hyperlink_u16(&mut after);
+
 	(before, before_w, after, after_w)
 }
 
 /// Extract the before/after slices around an overlay region.
 ///
 /// Preserves ANSI state so the `after` segment renders correctly after
-/// truncation.
+/// truncation, including a hyperlink open where it starts; hyperlinks left
+/// open by either segment are closed.
 #[napi(js_name = "extractSegments")]
 pub fn extract_segments(
 	line: JsString,
@@ -1293,17 +2321,167 @@ pub fn sanitize_text(text: JsString<'_>) -> Result<Either<JsString<'_>, Utf16Str
 	Ok(Either::B(build_utf16_string(out)))
 }
 
+// ============================================================================
+// stripAnsi / sanitizeAnsi
+// ============================================================================
+
+/// Which escape sequences `sanitizeAnsi` keeps.
+#[napi(object)]
+pub struct AnsiPolicy {
+	/// Keep SGR sequences: colors and text attributes (default: true).
+	#[napi(js_name = "keepColors")]
+	pub keep_colors:     Option<bool>,
+	/// Keep OSC 8 hyperlinks (default: false).
+	#[napi(js_name = "keepHyperlinks")]
+	pub keep_hyperlinks: Option<bool>,
+}
+
+/// Copy `data` without the units `unit` rejects. `unit(i)` returns the length
+/// of the unit starting at `i` and whether to keep it.
+///
+/// Returns `None` when everything is kept, so callers can reuse the input.
+fn filter_u16(data: &[u16], mut unit: impl FnMut(usize) -> (usize, bool)) -> Option<Vec<u16>> {
+	let mut out: Option<Vec<u16>> = None;
+	let mut last = 0usize;
+	let mut i = 0usize;
+	while i < data.len() {
+		let (len, keep) = unit(i);
+		if !keep {
+			let out = out.get_or_insert_with(|| Vec::with_capacity(data.len()));
+			out.extend_from_slice(&data[last..i]);
+			last = i + len;
+		}
+		i += len;
+	}
+	let mut out = out?;
+	out.extend_from_slice(&data[last..]);
+	Some(out)
+}
+
+fn strip_ansi_u16(data: &[u16]) -> Option<Vec<u16>> {
+	filter_u16(data, |i| match ansi_seq_len_u16(data, i) {
+		Some(seq_len) => (seq_len, false),
+		None => (1, true),
+	})
+}
+
+/// Whether `seq` is an SGR sequence with only numeric parameters. Private
+/// forms such as `CSI > 4 ; 1 m` (modifyOtherKeys) also end in `m`.
+fn is_plain_sgr_u16(seq: &[u16]) -> bool {
+	is_sgr_u16(seq)
+		&& seq[2..seq.len() - 1]
+			.iter()
+			.all(|&u| u == b';' as u16 || u == b':' as u16 || (0x30..=0x39).contains(&u))
+}
+
+fn sanitize_ansi_u16(data: &[u16], keep_colors: bool, keep_hyperlinks: bool) -> Option<Vec<u16>> {
+	filter_u16(data, |i| {
+		let u = data[i];
+		if u == ESC {
+			let Some(seq_len) = ansi_seq_len_u16(data, i) else {
+				// Unterminated or unknown: drop the ESC so the rest is inert text.
+				return (1, false);
+			};
+			let seq = &data[i..i + seq_len];
+			let keep =
+				(keep_colors && is_plain_sgr_u16(seq)) || (keep_hyperlinks && is_hyperlink_u16(seq));
+			return (seq_len, keep);
+		}
+		// C0 (except tab and newline), DEL, and C1 controls. C1 includes the
+		// 8-bit CSI and OSC introducers; CR would let output overwrite itself.
+		let control = (u <= 0x1f && u != 0x09 && u != 0x0a) || (0x7f..=0x9f).contains(&u);
+		(1, !control)
+	})
+}
+
+/// Remove ANSI escape sequences, keeping all other text.
+#[napi(js_name = "stripAnsi")]
+pub fn strip_ansi_napi(text: JsString<'_>) -> Result<Either<JsString<'_>, Utf16String>> {
+	let original = text;
+	let text_u16 = text.into_utf16()?;
+	Ok(match strip_ansi_u16(text_u16.as_slice()) {
+		Some(out) => Either::B(build_utf16_string(out)),
+		None => Either::A(original),
+	})
+}
+
+/// Make untrusted terminal output safe to render inside the TUI.
+///
+/// Removes every escape sequence except SGR colors and, when allowed, OSC 8
+/// hyperlinks, so output cannot move the cursor, clear the screen, change
+/// the window title, or switch modes. Control characters other than tab and
+/// newline are removed too, including carriage returns and 8-bit C1
+/// introducers.
+#[napi(js_name = "sanitizeAnsi")]
+pub fn sanitize_ansi(
+	text: JsString<'_>,
+	policy: Option<AnsiPolicy>,
+) -> Result<Either<JsString<'_>, Utf16String>> {
+	let policy = policy.unwrap_or(AnsiPolicy { keep_colors: None, keep_hyperlinks: None });
+	let original = text;
+	let text_u16 = text.into_utf16()?;
+	let sanitized = sanitize_ansi_u16(
+		text_u16.as_slice(),
+		policy.keep_colors.unwrap_or(true),
+		policy.keep_hyperlinks.unwrap_or(false),
+	);
+	Ok(match sanitized {
+		Some(out) => Either::B(build_utf16_string(out)),
+		None => Either::A(original),
+	})
+}
+
 // ============================================================================
 // visibleWidth
 // ============================================================================
 
 /// Calculate visible width of text, excluding ANSI escape sequences.
 ///
-/// Tabs count as a fixed-width cell.
+/// Tabs count as a fixed-width cell. Byte arrays are decoded as UTF-8, with
+/// invalid sequences measured as U+FFFD.
 #[napi(js_name = "visibleWidth")]
-pub fn visible_width_napi(text: JsString) -> Result<u32> {
-	let text_u16 = text.into_utf16()?;
-	Ok(clamp_u32(visible_width_u16(text_u16.as_slice())))
+pub fn visible_width_napi(text: Either<JsString, Uint8Array>) -> Result<u32> {
+	match text {
+		Either::A(text) => {
+			let text_u16 = text.into_utf16()?;
+			Ok(clamp_u32(visible_width_u16(text_u16.as_slice())))
+		},
+		Either::B(bytes) => Ok(clamp_u32(visible_width(&String::from_utf8_lossy(&bytes)))),
+	}
+}
+
+// ============================================================================
+// visibleWidths
+// ============================================================================
+
+/// Line count below which `visibleWidths` stays on the calling thread; for a
+/// screenful of lines the rayon hand-off costs more than it saves.
+const PARALLEL_LINES: usize = 512;
+
+fn measure_all<T: Sync>(lines: &[T], measure: impl Fn(&T) -> usize + Sync) -> Vec<u32> {
+	if lines.len() < PARALLEL_LINES {
+		lines.iter().map(|line| clamp_u32(measure(line))).collect()
+	} else {
+		lines
+			.par_iter()
+			.map(|line| clamp_u32(measure(line)))
+			.collect()
+	}
+}
+
+/// Calculate the visible width of many lines in one call.
+///
+/// Byte arrays are decoded as UTF-8, with invalid sequences measured as
+/// U+FFFD. Large batches are measured in parallel.
+#[napi(js_name = "visibleWidths")]
+pub fn visible_widths(lines: Either<Vec<String>, Vec<Uint8Array>>) -> Vec<u32> {
+	match lines {
+		Either::A(lines) => measure_all(&lines, |line| visible_width(line)),
+		Either::B(lines) => {
+			let lines: Vec<&[u8]> = lines.iter().map(|line| &line[..]).collect();
+			measure_all(&lines, |line| visible_width(&String::from_utf8_lossy(line)))
+		},
+	}
 }
 
 #[cfg(test)]
@@ -1322,6 +2500,18 @@ mod tests {
 		assert_eq!(visible_width_u16(&to_u16("a\tb")), 1 + TAB_WIDTH + 1);
 	}
 
+	#[test]
+	fn test_cluster_width_settings() {
+		// U+00B1 is East Asian ambiguous
+		assert_eq!(cluster_width("\u{B1}", false, true), 1);
+		assert_eq!(cluster_width("\u{B1}", true, true), 2);
+		assert_eq!(cluster_width("\u{4E2D}", false, false), 2);
+		assert_eq!(cluster_width("👍🏽", false, true), 2);
+		assert_eq!(cluster_width("👍🏽", false, false), 1);
+		assert_eq!(cluster_width("\u{2764}\u{FE0F}", false, false), 1);
+		assert_eq!(cluster_width("a", true, false), 1);
+	}
+
 	#[test]
 	fn test_ansi_detection() {
 		let data = to_u16("\x1b[31mred\x1b[0m");
@@ -1345,6 +2535,123 @@ mod tests {
 		assert_eq!(width, 5);
 	}
 
+	fn truncate(text: &str, max_width: usize, mode: TruncateMode) -> String {
+		let out = truncate_u16(&to_u16(text), max_width, &[0x2026], 1, false, mode);
+		String::from_utf16_lossy(&out)
+	}
+
+	#[test]
+	fn test_truncate_modes() {
+		let path = "src/components/status-line.ts";
+		assert_eq!(truncate(path, 10, TruncateMode::End), "src/compo\u{2026}");
+		assert_eq!(truncate(path, 10, TruncateMode::Start), "\u{2026}s-line.ts");
+		assert_eq!(truncate(path, 10, TruncateMode::Middle), "src/c\u{2026}e.ts");
+		assert_eq!(truncate("hello brave new world", 14, TruncateMode::Word), "hello brave\u{2026}");
+		assert_eq!(truncate("hello brave new world", 12, TruncateMode::Word), "hello brave\u{2026}");
+		// A single word too wide for the width is cut mid-word.
+		assert_eq!(truncate("extraordinary", 6, TruncateMode::Word), "extra\u{2026}");
+	}
+
+	#[test]
+	fn test_truncate_modes_keep_ansi() {
+		let text = "\x1b[31mred text\x1b[0m and \x1b[32mgreen text\x1b[0m";
+		assert_eq!(truncate(text, 6, TruncateMode::End), "\x1b[31mred t\x1b[0m\u{2026}");
+		// The tail is led by the codes that were active where it starts.
+		assert_eq!(
+			truncate(text, 6, TruncateMode::Start),
+			"\u{2026}\x1b[31m\x1b[0m\x1b[32m text\x1b[0m"
+		);
+		assert_eq!(
+			truncate(text, 7, TruncateMode::Middle),
+			"\x1b[31mred\x1b[0m\u{2026}\x1b[31m\x1b[0m\x1b[32mext\x1b[0m"
+		);
+		// Wide characters never straddle the cut.
+		assert_eq!(truncate("\u{4E2D}\u{6587}\u{5B57}", 4, TruncateMode::Start), "\u{2026}\u{5B57}");
+	}
+
+	#[test]
+	fn test_pad_align() {
+		let pad = |text: &str, width: usize, align: Align, fill: &str, fill_w: usize| {
+			let text = to_u16(text);
+			let text_w = visible_width_u16(&text);
+			pad_u16(&text, text_w, width, align, &to_u16(fill), fill_w)
+				.map(|out| String::from_utf16_lossy(&out))
+		};
+		assert_eq!(pad("\x1b[1mab\x1b[0m", 5, Align::Left, " ", 1).unwrap(), "\x1b[1mab\x1b[0m   ");
+		assert_eq!(pad("ab", 5, Align::Right, ".", 1).unwrap(), "...ab");
+		assert_eq!(pad("ab", 7, Align::Center, "-", 1).unwrap(), "--ab---");
+		assert_eq!(
+			pad("\u{4E2D}", 7, Align::Center, "\u{2550}", 1).unwrap(),
+			"\u{2550}\u{2550}\u{4E2D}\u{2550}\u{2550}\u{2550}"
+		);
+		// A wide fill leaves a space next to the text for an odd gap.
+		assert_eq!(pad("ab", 7, Align::Left, "\u{FF0D}", 2).unwrap(), "ab \u{FF0D}\u{FF0D}");
+		assert_eq!(pad("ab", 5, Align::Right, "\u{FF0D}", 2).unwrap(), "\u{FF0D} ab");
+		assert_eq!(pad("abc", 3, Align::Left, " ", 1), None);
+		assert_eq!(pad("abcd", 3, Align::Center, " ", 1), None);
+	}
+
+	#[test]
+	fn test_join_styled() {
+		let join = |segments: &[&str], separator: &str| {
+			let segments: Vec<Vec<u16>> = segments.iter().map(|s| to_u16(s)).collect();
+			let out = join_styled_u16(segments.iter().map(Vec::as_slice), &to_u16(separator));
+			String::from_utf16_lossy(&out)
+		};
+		assert_eq!(join(&["a", "b"], " | "), "a | b");
+		assert_eq!(join(&["\x1b[31ma", "b"], " "), "\x1b[31ma\x1b[0m b");
+		// Segments that close their own styles are left alone.
+		assert_eq!(
+			join(&["\x1b[1ma\x1b[0m", "\x1b[32mb\x1b[m"], ""),
+			"\x1b[1ma\x1b[0m\x1b[32mb\x1b[m"
+		);
+		assert_eq!(
+			join(&["\x1b[0;1ma", "b"], "\x1b[2m\u{2502}"),
+			"\x1b[0;1ma\x1b[0m\x1b[2m\u{2502}\x1b[0mb"
+		);
+		assert_eq!(join(&[], ","), "");
+	}
+
+	#[test]
+	fn test_hyperlinks() {
+		let link =
+			|text: &str, uri: &str| String::from_utf16_lossy(&hyperlink_u16(&to_u16(text), uri));
+		let text = format!("see {} now", link("the docs", "https://example.com/a b"));
+		assert_eq!(text, "see \x1b]8;;https://example.com/a%20b\x1b\\the docs\x1b]8;;\x1b\\ now");
+		assert_eq!(visible_width_u16(&to_u16(&text)), 16);
+		assert_eq!(link("\x1b]8;;x\x07a\x1b]8;;\x07", "y"), "\x1b]8;;y\x1b\\a\x1b]8;;\x1b\\");
+
+		// A slice through the link reopens it and closes it again.
+		let data = to_u16(&text);
+		let (out, width) = slice_with_width_impl(&data, 8, 3, false);
+		assert_eq!(
+			String::from_utf16_lossy(&out),
+			"\x1b]8;;https://example.com/a%20b\x1b\\doc\x1b]8;;\x1b\\"
+		);
+		assert_eq!(width, 3);
+		// Links closed before the slice are not replayed.
+		let (out, _) = slice_with_width_impl(&data, 13, 3, false);
+		assert_eq!(String::from_utf16_lossy(&out), "now");
+
+		let (before, _, after, _) = extract_segments_impl(&data, 6, 9, 3, false);
+		assert_eq!(
+			String::from_utf16_lossy(&before),
+			"see \x1b]8;;https://example.com/a%20b\x1b\\th\x1b]8;;\x1b\\"
+		);
+		assert_eq!(
+			String::from_utf16_lossy(&after),
+			"\x1b]8;;https://example.com/a%20b\x1b\\ocs\x1b]8;;\x1b\\"
+		);
+
+		assert_eq!(
+			truncate(&link("abcdef", "u"), 4, TruncateMode::End),
+			"\x1b]8;;u\x1b\\abc\x1b]8;;\x1b\\\u{2026}"
+		);
+		let segments = [to_u16("\x1b]8;;u\x1b\\a"), to_u16("b")];
+		let joined = join_styled_u16(segments.iter().map(Vec::as_slice), &[]);
+		assert_eq!(String::from_utf16_lossy(&joined), "\x1b]8;;u\x1b\\a\x1b]8;;\x1b\\b");
+	}
+
 	#[test]
 	fn test_ascii_fast_path() {
 		fn is_ascii(seg: &[u16]) -> bool {
@@ -1369,7 +2676,8 @@ mod tests {
 	#[test]
 	fn test_wrap_text_with_ansi_preserves_color() {
 		let data = to_u16("\x1b[38;2;156;163;176mhello world\x1b[0m");
-		let lines = wrap_text_with_ansi_impl(&data, 5);
+		let config = WrapConfig { width: 5, indent: &[], hard: true };
+		let lines = wrap_text_with_ansi_impl(&data, config);
 		assert_eq!(lines.len(), 2);
 		let first = String::from_utf16_lossy(&lines[0]);
 		let second = String::from_utf16_lossy(&lines[1]);
@@ -1377,4 +2685,97 @@ mod tests {
 		assert!(second.starts_with("\x1b[38;2;156;163;176m"));
 		assert!(second.contains("world"));
 	}
+
+	#[test]
+	fn test_wrap_to_width_indent_and_overflow() {
+		let indent = to_u16("  ");
+		let data = to_u16("\x1b[31maaa bbb ccc\x1b[0m");
+		let config = WrapConfig { width: 7, indent: &indent, hard: true };
+		let lines: Vec<String> = wrap_text_with_ansi_impl(&data, config)
+			.iter()
+			.map(|line| String::from_utf16_lossy(line))
+			.collect();
+		assert_eq!(lines, ["\x1b[31maaa bbb", "  \x1b[31mccc\x1b[0m"]);
+
+		let data = to_u16("ab abcdefghij cd");
+		let config = WrapConfig { width: 5, indent: &[], hard: false };
+		let lines: Vec<String> = wrap_text_with_ansi_impl(&data, config)
+			.iter()
+			.map(|line| String::from_utf16_lossy(line))
+			.collect();
+		assert_eq!(lines, ["ab", "abcdefghij", "cd"]);
+	}
+
+	#[test]
+	fn test_layout_line_matches_wrap() {
+		let indent = to_u16("  ");
+		for (text, width, hard) in [
+			("\x1b[31maaa bbb ccc\x1b[0m", 7, true),
+			("ab abcdefghij cd", 5, false),
+			("ab abcdefghij cd", 5, true),
+			("中文中文 x  y", 3, true),
+			("", 4, true),
+		] {
+			let data = to_u16(text);
+			let config = WrapConfig { width, indent: &indent, hard };
+			let rows = layout_line_u16(&data, config);
+			let lines = wrap_text_with_ansi_impl(&data, config);
+			assert_eq!(rows.len(), lines.len(), "{text:?}");
+			assert_eq!(rows.first().map(|row| row.0), Some(0));
+			assert_eq!(rows.last().map(|row| row.1), Some(data.len()));
+			for (i, ((start, end, row_width), line)) in rows.iter().zip(&lines).enumerate() {
+				let line = if i > 0 {
+					&line[indent.len()..]
+				} else {
+					&line[..]
+				};
+				let row = strip_ansi(&String::from_utf16_lossy(&data[*start..*end]));
+				assert_eq!(row.trim_end_matches(' '), strip_ansi(&String::from_utf16_lossy(line)));
+				assert_eq!(*row_width, visible_width_u16(line));
+			}
+		}
+	}
+
+	#[test]
+	fn test_graphemes() {
+		let text = "a\x1b[31m中\u{301}e\u{301}\t👍🏽";
+		let data = to_u16(text);
+		let clusters = graphemes_u16(&data);
+		let summary: Vec<(&str, u32, u32, u32)> = clusters
+			.iter()
+			.map(|g| (g.cluster.as_str(), g.byte_offset, g.column, g.width))
+			.collect();
+		assert_eq!(summary, [
+			("a", 0, 0, 1),
+			("中\u{301}", 6, 1, 2),
+			("e\u{301}", 11, 3, 1),
+			("\t", 14, 4, TAB_WIDTH as u32),
+			("👍🏽", 15, 4 + TAB_WIDTH as u32, 2),
+		]);
+
+		let at = |column| grapheme_at_u16(&data, column).map(|g| g.cluster);
+		assert_eq!(at(2).as_deref(), Some("中\u{301}"));
+		assert_eq!(at(3).as_deref(), Some("e\u{301}"));
+		assert_eq!(at(4 + TAB_WIDTH as u32 + 2), None);
+	}
+
+	#[test]
+	fn test_strip_and_sanitize_ansi() {
+		let sanitize = |text: &str, colors: bool, links: bool| {
+			let data = to_u16(text);
+			sanitize_ansi_u16(&data, colors, links).map(|out| String::from_utf16_lossy(&out))
+		};
+		assert_eq!(strip_ansi_u16(&to_u16("plain")), None);
+		assert_eq!(strip_ansi("\x1b[1;31mred\x1b[0m \x1b]0;title\x07x"), "red x");
+
+		let text = "\x1b[2J\x1b[H\x1b[31mred\x1b[0m\x1b]0;pwned\x07\x1b[5A\r\nok\x1b[>4;1m";
+		assert_eq!(sanitize(text, true, false).as_deref(), Some("\x1b[31mred\x1b[0m\nok"));
+		assert_eq!(sanitize(text, false, false).as_deref(), Some("red\nok"));
+
+		let link = "\x1b]8;;https://example.com\x1b\\site\x1b]8;;\x1b\\";
+		assert_eq!(sanitize(link, true, true), None);
+		assert_eq!(sanitize(link, true, false).as_deref(), Some("site"));
+		assert_eq!(sanitize("a\u{9b}2Jb\x1b", true, false).as_deref(), Some("a2Jb"));
+		assert_eq!(sanitize("tab\there", true, false), None);
+	}
 }
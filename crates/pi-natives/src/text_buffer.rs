@@ -0,0 +1,605 @@
+//! Rope-backed text buffer with transactional undo.
+//!
+//! # Overview
+//! `TextBuffer` keeps editor text in a rope, so inserts and deletes stay
+//! cheap regardless of document size. Offsets are UTF-16 code units, matching
+//! JS string indices; lines are split on `\n` (a preceding `\r` is dropped
+//! from line text).
+//!
+//! Every edit is recorded as a reversible change. Edits made between
+//! `beginGroup()` and `endGroup()` form one undo step (groups nest; only the
+//! outermost counts), and any other edit is a step of its own. `undo()` and
+//! `redo()` replay whole steps and put the cursor back where it was before or
+//! after the step. History keeps at most `historyLimit` steps, dropping the
+//! oldest, and a new edit after an undo discards the redo steps.
+//!
+//! `search()` scans a snapshot of the rope on the worker pool in line-aligned
+//! windows, so the whole text is never copied out. Matches stream to a
+//! callback as they are found, and the scan honors abort signals and
+//! timeouts. Matches may span lines but not exceed one window (64 KiB).
+//!
+//! # Example
+//! ```ignore
+//! // JS: const buf = new native.TextBuffer("hello", { historyLimit: 500 })
+//! // JS: buf.beginGroup(); buf.insert(5, " world"); buf.delete(0, 1); buf.endGroup()
+//! // JS: buf.undo() // -> true; buf.text() === "hello", buf.cursor === 5
+//! // JS: await buf.search("l+", { regex: true }, (_e, m) => highlight(m.start, m.end))
+//! ```
+
+use std::collections::VecDeque;
+
+use grep_matcher::Matcher;
+use grep_regex::{RegexMatcher, RegexMatcherBuilder};
+use napi::{
+	bindgen_prelude::*,
+	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+use ropey::Rope;
+
+use crate::task;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for creating a `TextBuffer`.
+#[napi(object)]
+pub struct TextBufferOptions {
+	/// Maximum number of undo steps kept (default: 1000; 0 disables undo).
+	#[napi(js_name = "historyLimit")]
+	pub history_limit: Option<u32>,
+}
+
+/// Line and column of an offset.
+#[napi(object)]
+pub struct TextPosition {
+	/// Zero-based line.
+	pub line: u32,
+	/// Zero-based column in UTF-16 code units.
+	pub col:  u32,
+}
+
+/// Offset range in UTF-16 code units.
+#[napi(object)]
+pub struct TextRange {
+	/// Start offset (inclusive).
+	pub start: u32,
+	/// End offset (exclusive).
+	pub end:   u32,
+}
+
+/// Options for `TextBuffer.search`.
+#[napi(object)]
+pub struct TextBufferSearchOptions<'env> {
+	/// Treat `pattern` as a regex instead of literal text (default: false).
+	pub regex:          Option<bool>,
+	/// Match letter case exactly (default: true).
+	#[napi(js_name = "caseSensitive")]
+	pub case_sensitive: Option<bool>,
+	/// Only search within this range (default: the whole buffer).
+	pub range:          Option<TextRange>,
+	/// Stop after this many matches.
+	#[napi(js_name = "maxCount")]
+	pub max_count:      Option<u32>,
+	/// Abort signal for cancelling the search.
+	pub signal:         Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the search.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:     Option<u32>,
+}
+
+/// A match found by `TextBuffer.search`.
+#[napi(object)]
+#[derive(Clone)]
+pub struct TextBufferMatch {
+	/// Start offset in UTF-16 code units.
+	pub start: u32,
+	/// End offset in UTF-16 code units (exclusive).
+	pub end:   u32,
+	/// Zero-based line containing `start`.
+	pub line:  u32,
+	/// Matched text.
+	pub text:  String,
+}
+
+/// Result of `TextBuffer.search`.
+#[napi(object)]
+pub struct TextBufferSearchResult {
+	/// Matches in buffer order.
+	pub matches:       Vec<TextBufferMatch>,
+	/// Whether the search stopped at `maxCount`.
+	#[napi(js_name = "limitReached")]
+	pub limit_reached: bool,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// History
+// ═══════════════════════════════════════════════════════════════════════════
+
+const DEFAULT_HISTORY_LIMIT: u32 = 1000;
+
+/// One replacement at char offset `at`.
+struct Change {
+	at:       usize,
+	removed:  String,
+	inserted: String,
+}
+
+/// One undo step: changes in application order, with the cursor (char
+/// offset) around them.
+struct Group {
+	changes:       Vec<Change>,
+	cursor_before: usize,
+	cursor_after:  usize,
+}
+
+struct History {
+	undo:  VecDeque<Group>,
+	redo:  Vec<Group>,
+	/// Group collecting edits between `beginGroup()` and `endGroup()`.
+	open:  Option<Group>,
+	depth: u32,
+	limit: usize,
+}
+
+impl History {
+	const fn new(limit: usize) -> Self {
+		Self { undo: VecDeque::new(), redo: Vec::new(), open: None, depth: 0, limit }
+	}
+
+	fn record(&mut self, change: Change, cursor_before: usize, cursor_after: usize) {
+		self.redo.clear();
+		if let Some(open) = &mut self.open {
+			open.changes.push(change);
+			open.cursor_after = cursor_after;
+		} else {
+			self.push(Group { changes: vec![change], cursor_before, cursor_after });
+		}
+	}
+
+	fn push(&mut self, group: Group) {
+		self.undo.push_back(group);
+		while self.undo.len() > self.limit {
+			self.undo.pop_front();
+		}
+	}
+
+	fn ensure_closed(&self, action: &str) -> Result<()> {
+		if self.open.is_some() {
+			return Err(Error::from_reason(format!("Cannot {action} inside an open edit group")));
+		}
+		Ok(())
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Search
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Text searched at once; windows end at a line boundary and hold at least
+/// two lines.
+const SEARCH_WINDOW_BYTES: usize = 64 * 1024;
+
+/// Scan chars `start..end` of `rope`, passing each match to `emit`.
+///
+/// Each window is searched whole, but only matches starting before its last
+/// line are taken; the next window starts at that line, so a match is always
+/// searched with the text following it.
+///
+/// # Returns
+/// Whether `max_count` was reached.
+fn search_rope(
+	rope: &Rope,
+	start: usize,
+	end: usize,
+	matcher: &RegexMatcher,
+	max_count: Option<usize>,
+	ct: &task::CancelToken,
+	mut emit: impl FnMut(TextBufferMatch),
+) -> Result<bool> {
+	let slice = rope.slice(start..end);
+	let base = rope.char_to_byte(start);
+	let total_lines = slice.len_lines();
+	let to_utf16 = |byte: usize| rope.char_to_utf16_cu(rope.byte_to_char(base + byte)) as u32;
+
+	let mut window = String::new();
+	let mut first_line = 0;
+	// Slice byte offset before which matches have been reported.
+	let mut resume: usize = 0;
+	let mut count = 0;
+	loop {
+		ct.heartbeat()?;
+		let window_start = slice.line_to_byte(first_line);
+		window.clear();
+		let mut next_line = first_line;
+		while next_line < total_lines
+			&& (next_line - first_line < 2 || window.len() < SEARCH_WINDOW_BYTES)
+		{
+			window.extend(slice.line(next_line).chunks());
+			next_line += 1;
+		}
+		let last = next_line >= total_lines;
+		let commit_end = if last {
+			window.len() + 1
+		} else {
+			slice.line_to_byte(next_line - 1) - window_start
+		};
+
+		let haystack = window.as_bytes();
+		let mut at = resume.saturating_sub(window_start);
+		while at <= haystack.len() {
+			let found = matcher
+				.find_at(haystack, at)
+				.map_err(|err| Error::from_reason(format!("Regex error: {err}")))?;
+			let Some(found) = found.filter(|found| found.start() < commit_end) else {
+				break;
+			};
+			emit(TextBufferMatch {
+				start: to_utf16(window_start + found.start()),
+				end:   to_utf16(window_start + found.end()),
+				line:  rope.byte_to_line(base + window_start + found.start()) as u32,
+				text:  String::from_utf8_lossy(&haystack[found]).into_owned(),
+			});
+			count += 1;
+			if max_count.is_some_and(|max| count >= max) {
+				return Ok(true);
+			}
+			resume = window_start + found.end();
+			at = if found.is_empty() {
+				// Step over the character after an empty match.
+				found.end() + window[found.end()..].chars().next().map_or(1, char::len_utf8)
+			} else {
+				found.end()
+			};
+		}
+		if last {
+			return Ok(false);
+		}
+		resume = resume.max(window_start + commit_end);
+		first_line = next_line - 1;
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Editable text backed by a rope, with grouped undo/redo.
+#[napi]
+pub struct TextBuffer {
+	rope:    Rope,
+	/// Cursor as a char offset.
+	cursor:  usize,
+	history: History,
+}
+
+#[napi]
+impl TextBuffer {
+	/// Create a buffer holding `text` (default: empty), with the cursor at
+	/// the start.
+	#[napi(constructor)]
+	pub fn new(text: Option<String>, options: Option<TextBufferOptions>) -> Self {
+		let limit = options
+			.and_then(|opts| opts.history_limit)
+			.unwrap_or(DEFAULT_HISTORY_LIMIT);
+		Self {
+			rope:    Rope::from_str(text.as_deref().unwrap_or("")),
+			cursor:  0,
+			history: History::new(limit as usize),
+		}
+	}
+
+	/// Length in UTF-16 code units.
+	#[napi(getter)]
+	pub fn length(&self) -> u32 {
+		self.rope.len_utf16_cu() as u32
+	}
+
+	/// Number of lines (a trailing newline starts an empty last line).
+	#[napi(getter, js_name = "lineCount")]
+	pub fn line_count(&self) -> u32 {
+		self.rope.len_lines() as u32
+	}
+
+	/// The whole text.
+	#[napi]
+	pub fn text(&self) -> String {
+		self.rope.to_string()
+	}
+
+	/// Text between two offsets.
+	///
+	/// # Errors
+	/// Returns an error if the range is out of bounds or reversed.
+	#[napi]
+	pub fn slice(&self, start: u32, end: u32) -> Result<String> {
+		let (start, end) = self.char_range(start, end)?;
+		Ok(self.rope.slice(start..end).to_string())
+	}
+
+	/// Text of one line, without its line ending.
+	///
+	/// # Errors
+	/// Returns an error if `index` is not below `lineCount`.
+	#[napi]
+	pub fn line(&self, index: u32) -> Result<String> {
+		let line = self.rope.get_line(index as usize).ok_or_else(|| {
+			Error::from_reason(format!(
+				"Line {index} is out of range ({} lines)",
+				self.rope.len_lines()
+			))
+		})?;
+		let mut text = line.to_string();
+		if text.ends_with('\n') {
+			text.pop();
+			if text.ends_with('\r') {
+				text.pop();
+			}
+		}
+		Ok(text)
+	}
+
+	/// Line and column of an offset.
+	///
+	/// # Errors
+	/// Returns an error if `offset` is past the end.
+	#[napi(js_name = "positionAt")]
+	pub fn position_at(&self, offset: u32) -> Result<TextPosition> {
+		let index = self.char_index(offset)?;
+		let line = self.rope.char_to_line(index);
+		let line_start = self.utf16(self.rope.line_to_char(line));
+		Ok(TextPosition { line: line as u32, col: self.utf16(index) - line_start })
+	}
+
+	/// Offset of a line and column; the column is clamped to the line's
+	/// length.
+	///
+	/// # Errors
+	/// Returns an error if `line` is not below `lineCount`.
+	#[napi(js_name = "offsetAt")]
+	pub fn offset_at(&self, line: u32, col: u32) -> Result<u32> {
+		let length = self.line(line)?.encode_utf16().count() as u32;
+		let line_start = self.utf16(self.rope.line_to_char(line as usize));
+		Ok(line_start + col.min(length))
+	}
+
+	/// Insert `text` at `offset`; the cursor moves to the end of the insert.
+	///
+	/// # Errors
+	/// Returns an error if `offset` is past the end.
+	#[napi]
+	pub fn insert(&mut self, offset: u32, text: String) -> Result<()> {
+		let at = self.char_index(offset)?;
+		self.edit(at, at, &text);
+		Ok(())
+	}
+
+	/// Delete the text between two offsets; the cursor moves to `start`.
+	///
+	/// # Errors
+	/// Returns an error if the range is out of bounds or reversed.
+	#[napi]
+	pub fn delete(&mut self, start: u32, end: u32) -> Result<()> {
+		let (start, end) = self.char_range(start, end)?;
+		self.edit(start, end, "");
+		Ok(())
+	}
+
+	/// Replace the text between two offsets; the cursor moves to the end of
+	/// the new text.
+	///
+	/// # Errors
+	/// Returns an error if the range is out of bounds or reversed.
+	#[napi]
+	pub fn replace(&mut self, start: u32, end: u32, text: String) -> Result<()> {
+		let (start, end) = self.char_range(start, end)?;
+		self.edit(start, end, &text);
+		Ok(())
+	}
+
+	/// Cursor offset, restored by `undo()` and `redo()`.
+	#[napi(getter)]
+	pub fn cursor(&self) -> u32 {
+		self.utf16(self.cursor)
+	}
+
+	/// Move the cursor without recording history.
+	///
+	/// # Errors
+	/// Returns an error if `offset` is past the end.
+	#[napi(js_name = "setCursor")]
+	pub fn set_cursor(&mut self, offset: u32) -> Result<()> {
+		self.cursor = self.char_index(offset)?;
+		Ok(())
+	}
+
+	/// Start an undo group; edits until the matching `endGroup()` undo as one
+	/// step. Groups nest.
+	#[napi(js_name = "beginGroup")]
+	pub fn begin_group(&mut self) {
+		if self.history.depth == 0 {
+			self.history.open = Some(Group {
+				changes:       Vec::new(),
+				cursor_before: self.cursor,
+				cursor_after:  self.cursor,
+			});
+		}
+		self.history.depth += 1;
+	}
+
+	/// Close the innermost undo group.
+	///
+	/// # Errors
+	/// Returns an error if no group is open.
+	#[napi(js_name = "endGroup")]
+	pub fn end_group(&mut self) -> Result<()> {
+		if self.history.depth == 0 {
+			return Err(Error::from_reason("endGroup() called without a matching beginGroup()"));
+		}
+		self.history.depth -= 1;
+		if self.history.depth == 0
+			&& let Some(group) = self.history.open.take()
+			&& !group.changes.is_empty()
+		{
+			self.history.push(group);
+		}
+		Ok(())
+	}
+
+	/// Revert the last undo step.
+	///
+	/// # Returns
+	/// False when there is nothing to undo.
+	///
+	/// # Errors
+	/// Returns an error while a group is open.
+	#[napi]
+	pub fn undo(&mut self) -> Result<bool> {
+		self.history.ensure_closed("undo")?;
+		let Some(group) = self.history.undo.pop_back() else {
+			return Ok(false);
+		};
+		for change in group.changes.iter().rev() {
+			let end = change.at + change.inserted.chars().count();
+			self.rope.remove(change.at..end);
+			self.rope.insert(change.at, &change.removed);
+		}
+		self.cursor = group.cursor_before;
+		self.history.redo.push(group);
+		Ok(true)
+	}
+
+	/// Re-apply the last undone step.
+	///
+	/// # Returns
+	/// False when there is nothing to redo.
+	///
+	/// # Errors
+	/// Returns an error while a group is open.
+	#[napi]
+	pub fn redo(&mut self) -> Result<bool> {
+		self.history.ensure_closed("redo")?;
+		let Some(group) = self.history.redo.pop() else {
+			return Ok(false);
+		};
+		for change in &group.changes {
+			let end = change.at + change.removed.chars().count();
+			self.rope.remove(change.at..end);
+			self.rope.insert(change.at, &change.inserted);
+		}
+		self.cursor = group.cursor_after;
+		self.history.push(group);
+		Ok(true)
+	}
+
+	/// Whether `undo()` would change the text.
+	#[napi(getter, js_name = "canUndo")]
+	pub fn can_undo(&self) -> bool {
+		!self.history.undo.is_empty()
+	}
+
+	/// Whether `redo()` would change the text.
+	#[napi(getter, js_name = "canRedo")]
+	pub const fn can_redo(&self) -> bool {
+		!self.history.redo.is_empty()
+	}
+
+	/// Drop all undo and redo steps.
+	#[napi(js_name = "clearHistory")]
+	pub fn clear_history(&mut self) {
+		self.history.undo.clear();
+		self.history.redo.clear();
+	}
+
+	/// Find matches of `pattern` on the worker pool, streaming each to
+	/// `onMatch` as it is found. The search runs over a snapshot, so edits
+	/// made while it runs do not affect it.
+	///
+	/// # Errors
+	/// Returns an error for an invalid regex or range, or when the search is
+	/// aborted or times out.
+	#[napi]
+	pub fn search(
+		&self,
+		pattern: String,
+		options: Option<TextBufferSearchOptions<'_>>,
+		#[napi(ts_arg_type = "((match: TextBufferMatch) => void) | undefined | null")]
+		on_match: Option<ThreadsafeFunction<TextBufferMatch>>,
+	) -> Result<task::Async<TextBufferSearchResult>> {
+		let (regex, case_sensitive, range, max_count, timeout_ms, signal) = match options {
+			Some(opts) => (
+				opts.regex,
+				opts.case_sensitive,
+				opts.range,
+				opts.max_count,
+				opts.timeout_ms,
+				opts.signal,
+			),
+			None => (None, None, None, None, None, None),
+		};
+		let matcher = RegexMatcherBuilder::new()
+			.case_insensitive(!case_sensitive.unwrap_or(true))
+			.multi_line(true)
+			.fixed_strings(!regex.unwrap_or(false))
+			.build(&pattern)
+			.map_err(|err| Error::from_reason(format!("Regex error: {err}")))?;
+		let (start, end) = match range {
+			Some(range) => self.char_range(range.start, range.end)?,
+			None => (0, self.rope.len_chars()),
+		};
+		let max_count = max_count.map(|max| max as usize);
+		let rope = self.rope.clone();
+		let ct = task::CancelToken::new(timeout_ms, signal);
+		Ok(task::blocking("text_buffer.search", ct, move |ct| {
+			let mut matches = Vec::new();
+			let limit_reached = search_rope(&rope, start, end, &matcher, max_count, &ct, |found| {
+				if let Some(callback) = &on_match {
+					callback.call(Ok(found.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+				}
+				matches.push(found);
+			})?;
+			Ok(TextBufferSearchResult { matches, limit_reached })
+		}))
+	}
+}
+
+impl TextBuffer {
+	fn utf16(&self, index: usize) -> u32 {
+		self.rope.char_to_utf16_cu(index) as u32
+	}
+
+	/// Char offset of a UTF-16 offset. An offset inside a surrogate pair
+	/// resolves to the start of that character.
+	fn char_index(&self, offset: u32) -> Result<usize> {
+		let length = self.rope.len_utf16_cu();
+		if offset as usize > length {
+			return Err(Error::from_reason(format!(
+				"Offset {offset} is out of range (length {length})"
+			)));
+		}
+		Ok(self.rope.utf16_cu_to_char(offset as usize))
+	}
+
+	fn char_range(&self, start: u32, end: u32) -> Result<(usize, usize)> {
+		if start > end {
+			return Err(Error::from_reason(format!("Invalid range: {start} > {end}")));
+		}
+		Ok((self.char_index(start)?, self.char_index(end)?))
+	}
+
+	/// Replace chars `start..end` with `text`, move the cursor after it, and
+	/// record the change.
+	fn edit(&mut self, start: usize, end: usize, text: &str) {
+		if start == end && text.is_empty() {
+			return;
+		}
+		let removed = self.rope.slice(start..end).to_string();
+		self.rope.remove(start..end);
+		self.rope.insert(start, text);
+		let cursor_before = self.cursor;
+		self.cursor = start + text.chars().count();
+		let change = Change { at: start, removed, inserted: text.to_string() };
+		self.history.record(change, cursor_before, self.cursor);
+	}
+}
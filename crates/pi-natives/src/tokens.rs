@@ -0,0 +1,191 @@
+//! Streaming BPE token counting for files.
+//!
+//! # Overview
+//! Reads a file in fixed-size chunks on the worker pool and feeds each chunk
+//! through a tiktoken encoder. Chunks are cut just after a newline that is
+//! followed by non-whitespace, which is also a pre-tokenizer split point, so
+//! the streamed count matches encoding the whole file at once.
+//!
+//! Content with no such newline (minified code, one-line JSON) is buffered up
+//! to a cap and then cut before the last whitespace run, or failing that at a
+//! character boundary; such a cut may split a pre-tokenizer word, so the count
+//! can drift by a token per cut.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.countFileTokens("src/lib.rs", "o200k_base")
+//! ```
+
+use std::{fs::File, io::Read, path::PathBuf};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use tiktoken_rs::CoreBPE;
+
+use crate::task;
+
+/// Bytes read per chunk.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Buffered bytes at which a chunk is cut even without a [`split_point`].
+const MAX_PENDING: usize = 16 * CHUNK_SIZE;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `countFileTokens`.
+#[napi(object)]
+pub struct CountTokensOptions<'env> {
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// Token and byte counts for a file.
+#[napi(object)]
+pub struct TokenCount {
+	/// Number of BPE tokens.
+	pub tokens: u32,
+	/// Number of bytes read.
+	pub bytes:  f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Counting
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Lazily-initialized encoder accessor; the rank tables load on first use.
+pub type EncoderFn = fn() -> &'static CoreBPE;
+
+/// Resolve an encoding name (default `o200k_base`) to its encoder.
+pub fn resolve_encoding(name: Option<&str>) -> Result<EncoderFn> {
+	match name.unwrap_or("o200k_base") {
+		"o200k_base" | "o200k" => Ok(tiktoken_rs::o200k_base_singleton),
+		"cl100k_base" | "cl100k" => Ok(tiktoken_rs::cl100k_base_singleton),
+		"p50k_base" | "p50k" => Ok(tiktoken_rs::p50k_base_singleton),
+		"r50k_base" | "r50k" | "gpt2" => Ok(tiktoken_rs::r50k_base_singleton),
+		other => Err(Error::from_reason(format!("Unknown token encoding: {other}"))),
+	}
+}
+
+/// Index just past the last `\n` that is followed by non-whitespace, if any.
+fn split_point(buf: &[u8]) -> Option<usize> {
+	(1..buf.len())
+		.rev()
+		.find(|&i| buf[i - 1] == b'\n' && !buf[i].is_ascii_whitespace())
+}
+
+/// Cut for a buffer without a [`split_point`]: just before the last whitespace
+/// run that follows non-whitespace (the pre-tokenizer keeps a space with the
+/// next word), else the last UTF-8 character boundary.
+fn forced_split_point(buf: &[u8]) -> usize {
+	(1..buf.len())
+		.rev()
+		.find(|&i| !buf[i - 1].is_ascii_whitespace() && buf[i].is_ascii_whitespace())
+		.or_else(|| (1..buf.len()).rev().find(|&i| buf[i] & 0xc0 != 0x80))
+		.unwrap_or(buf.len())
+}
+
+fn count_tokens(bpe: &CoreBPE, bytes: &[u8]) -> usize {
+	match std::str::from_utf8(bytes) {
+		Ok(text) => bpe.encode_ordinary(text).len(),
+		Err(_) => bpe.encode_ordinary(&String::from_utf8_lossy(bytes)).len(),
+	}
+}
+
+fn count_file_tokens_sync(
+	path: PathBuf,
+	bpe: &CoreBPE,
+	ct: &task::CancelToken,
+) -> Result<TokenCount> {
+	let mut file = File::open(&path)
+		.map_err(|err| Error::from_reason(format!("Failed to open {}: {err}", path.display())))?;
+	let mut pending: Vec<u8> = Vec::with_capacity(CHUNK_SIZE * 2);
+	let mut chunk = vec![0u8; CHUNK_SIZE];
+	let mut tokens = 0usize;
+	let mut bytes = 0u64;
+
+	loop {
+		ct.heartbeat()?;
+		let read = file
+			.read(&mut chunk)
+			.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+		if read == 0 {
+			break;
+		}
+		bytes += read as u64;
+		pending.extend_from_slice(&chunk[..read]);
+		if pending.len() < CHUNK_SIZE {
+			continue;
+		}
+		// Without a usable split point, keep buffering up to `MAX_PENDING`.
+		let cut = split_point(&pending)
+			.or_else(|| (pending.len() >= MAX_PENDING).then(|| forced_split_point(&pending)));
+		if let Some(cut) = cut {
+			tokens += count_tokens(bpe, &pending[..cut]);
+			pending.drain(..cut);
+		}
+	}
+
+	if !pending.is_empty() {
+		tokens += count_tokens(bpe, &pending);
+	}
+	Ok(TokenCount { tokens: tokens.min(u32::MAX as usize) as u32, bytes: bytes as f64 })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Count BPE tokens in a file without loading it into JS.
+///
+/// # Arguments
+/// - `path`: File to count.
+/// - `encoding`: `"o200k_base"` (default), `"cl100k_base"`, `"p50k_base"`, or
+///   `"r50k_base"`.
+///
+/// # Errors
+/// Returns an error for unknown encodings, unreadable files, or cancellation.
+#[napi(js_name = "countFileTokens")]
+pub fn count_file_tokens(
+	path: String,
+	encoding: Option<String>,
+	options: Option<CountTokensOptions<'_>>,
+) -> Result<task::Async<TokenCount>> {
+	let encoder = resolve_encoding(encoding.as_deref())?;
+	let (signal, timeout_ms) = options.map_or((None, None), |opts| (opts.signal, opts.timeout_ms));
+	let ct = task::CancelToken::new(timeout_ms, signal);
+	let path = PathBuf::from(path);
+	Ok(task::blocking("count_file_tokens", ct, move |ct| {
+		count_file_tokens_sync(path, encoder(), &ct)
+	}))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_forced_split_point() {
+		assert_eq!(forced_split_point(b"foo bar  baz"), 7);
+		// No whitespace: cut before the last character, never inside it.
+		assert_eq!(forced_split_point("abcé".as_bytes()), 3);
+		assert_eq!(forced_split_point("éé".as_bytes()), 2);
+		assert_eq!(forced_split_point(b"x"), 1);
+	}
+
+	#[test]
+	fn test_forced_split_keeps_whitespace_cut_count() {
+		let bpe = tiktoken_rs::o200k_base_singleton();
+		let text = "const a = [1, 2, 3];  let   b = a.map(x => x * 2);".repeat(4);
+		let cut = forced_split_point(text.as_bytes());
+		let (head, tail) = text.as_bytes().split_at(cut);
+		assert_eq!(
+			count_tokens(bpe, head) + count_tokens(bpe, tail),
+			count_tokens(bpe, text.as_bytes())
+		);
+	}
+}
@@ -0,0 +1,452 @@
+//! Session transcript export as plain text, HTML, or Markdown.
+//!
+//! # Overview
+//! `exportTranscript` turns the styled lines of a session (as written to the
+//! terminal) into a file a user can save or share. Lines are parsed with the
+//! same span parser as the width functions, so colors and attributes match
+//! what was on screen:
+//! - `text`: escapes removed; hyperlinks become `label (uri)`.
+//! - `html`: a `<pre>` with inline styles, colors resolved through the
+//!   screenshot theme, and hyperlinks as `<a>` elements.
+//! - `markdown`: bold, italic, and strikethrough as emphasis, hyperlinks as
+//!   Markdown links, and everything else escaped so terminal output cannot turn
+//!   into headings, lists, or raw HTML.
+//!
+//! OSC 8 hyperlinks are tracked per line; links with script-capable schemes
+//! are exported as plain text in HTML and Markdown. Inline images (kitty
+//! graphics placements, sixel, and iTerm2 inline files) become an `[image]`
+//! placeholder in every format.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const html = native.exportTranscript(session.lines, "html", { title: "Session 42" })
+//! // JS: await Bun.write("session.html", html)
+//! // JS: native.exportTranscript(["\x1b[1mdone\x1b[0m"], "markdown") // -> "**done**\n"
+//! ```
+
+use std::fmt::Write as _;
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{
+	screenshot::{self, Palette, ScreenshotTheme},
+	text::{self, SpanEvent, SpanStyle},
+};
+
+const IMAGE_PLACEHOLDER: &str = "[image]";
+const DEFAULT_TITLE: &str = "Transcript";
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `exportTranscript`.
+#[napi(object)]
+pub struct TranscriptOptions {
+	/// Document title for HTML output (default: "Transcript").
+	pub title:      Option<String>,
+	/// Colors for HTML output (default: the screenshot theme defaults).
+	pub theme:      Option<ScreenshotTheme>,
+	/// Wrap HTML output in a full document; when false, only the `<pre>`
+	/// element is returned (default: true).
+	pub standalone: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Parsing
+// ═══════════════════════════════════════════════════════════════════════════
+
+enum Content {
+	Text(String),
+	Image,
+}
+
+/// A run of a line sharing one style and hyperlink.
+struct Piece {
+	content: Content,
+	style:   SpanStyle,
+	link:    Option<String>,
+}
+
+impl Piece {
+	/// Text as it reads on screen: concealed characters become spaces.
+	fn plain(&self) -> String {
+		match &self.content {
+			Content::Text(text) if self.style.hidden => " ".repeat(text.chars().count()),
+			Content::Text(text) => text.clone(),
+			Content::Image => IMAGE_PLACEHOLDER.to_string(),
+		}
+	}
+}
+
+/// Body of an OSC sequence, without the introducer and terminator.
+fn osc_body(seq: &str) -> Option<&str> {
+	let body = seq.strip_prefix("\x1b]")?;
+	Some(
+		body
+			.strip_suffix('\x07')
+			.or_else(|| body.strip_suffix("\x1b\\"))
+			.unwrap_or(body),
+	)
+}
+
+/// Target of an OSC 8 sequence; an empty target closes the current link.
+fn hyperlink_target(seq: &str) -> Option<&str> {
+	let rest = osc_body(seq)?.strip_prefix("8;")?;
+	rest.split_once(';').map(|(_, uri)| uri)
+}
+
+/// Whether `seq` draws an inline image.
+fn is_image(seq: &str) -> bool {
+	if let Some(control) = seq.strip_prefix("\x1b_G") {
+		// Kitty graphics: only transmit-and-display and put commands draw;
+		// continuation chunks and queries do not.
+		let control = control.split(';').next().unwrap_or_default();
+		return control.split(',').any(|key| key == "a=T" || key == "a=p");
+	}
+	if let Some(body) = seq.strip_prefix("\x1bP") {
+		// Sixel: DCS with numeric parameters followed by `q`.
+		return body
+			.trim_start_matches(|ch: char| ch.is_ascii_digit() || ch == ';')
+			.starts_with('q');
+	}
+	osc_body(seq)
+		.and_then(|body| body.strip_prefix("1337;File="))
+		.is_some_and(|args| args.split([';', ':']).any(|arg| arg == "inline=1"))
+}
+
+fn pieces(line: &str) -> Vec<Piece> {
+	let mut pieces: Vec<Piece> = Vec::new();
+	let mut link: Option<String> = None;
+	text::for_each_span(line, |event| match event {
+		SpanEvent::Text(text, style) => {
+			let text: String = text
+				.chars()
+				.filter(|&ch| ch == '\t' || !ch.is_control())
+				.collect();
+			if text.is_empty() {
+				return;
+			}
+			match pieces.last_mut() {
+				Some(Piece { content: Content::Text(last), style: last_style, link: last_link })
+					if *last_style == style && *last_link == link =>
+				{
+					last.push_str(&text);
+				},
+				_ => pieces.push(Piece { content: Content::Text(text), style, link: link.clone() }),
+			}
+		},
+		SpanEvent::Escape(seq) => {
+			if let Some(uri) = hyperlink_target(&seq) {
+				link = (!uri.is_empty()).then(|| uri.to_string());
+			} else if is_image(&seq) {
+				pieces.push(Piece {
+					content: Content::Image,
+					style:   SpanStyle::default(),
+					link:    link.clone(),
+				});
+			}
+		},
+	});
+	pieces
+}
+
+/// Whether a link can be exported without running script when clicked.
+fn is_safe_uri(uri: &str) -> bool {
+	let scheme = uri
+		.split_once(':')
+		.map_or("", |(scheme, _)| scheme)
+		.trim()
+		.to_ascii_lowercase();
+	!matches!(scheme.as_str(), "javascript" | "vbscript" | "data")
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Plain text
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn render_text(lines: &[Vec<Piece>]) -> String {
+	let mut out = String::new();
+	for line in lines {
+		for group in line.chunk_by(|a, b| a.link == b.link) {
+			let label: String = group.iter().map(Piece::plain).collect();
+			out.push_str(&label);
+			if let Some(uri) = &group[0].link
+				&& label != *uri
+			{
+				let _ = write!(out, " ({uri})");
+			}
+		}
+		out.push('\n');
+	}
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Markdown
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn escape_markdown(text: &str, out: &mut String) {
+	for ch in text.chars() {
+		if matches!(ch, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '|' | '&') {
+			out.push('\\');
+		}
+		out.push(ch);
+	}
+}
+
+/// Percent-encode the characters that would end a Markdown link destination.
+fn encode_link(uri: &str) -> String {
+	let mut out = String::with_capacity(uri.len());
+	for ch in uri.chars() {
+		match ch {
+			' ' => out.push_str("%20"),
+			'(' => out.push_str("%28"),
+			')' => out.push_str("%29"),
+			'<' => out.push_str("%3C"),
+			'>' => out.push_str("%3E"),
+			ch => out.push(ch),
+		}
+	}
+	out
+}
+
+/// Emphasis for a run of pieces, keeping surrounding whitespace outside the
+/// delimiters so they stay valid emphasis.
+fn emphasis(group: &[Piece], out: &mut String) {
+	let mut body = String::new();
+	for piece in group {
+		escape_markdown(&piece.plain(), &mut body);
+	}
+	let style = group[0].style;
+	let mut delimiter = String::new();
+	if style.strike {
+		delimiter.push_str("~~");
+	}
+	if style.bold {
+		delimiter.push_str("**");
+	}
+	if style.italic {
+		delimiter.push('*');
+	}
+	wrap(&body, &delimiter, out);
+}
+
+fn wrap(body: &str, open: &str, out: &mut String) {
+	let core = body.trim_matches([' ', '\t']);
+	if open.is_empty() || core.is_empty() {
+		out.push_str(body);
+		return;
+	}
+	let start = body.len() - body.trim_start_matches([' ', '\t']).len();
+	let close: String = open.chars().rev().collect();
+	out.push_str(&body[..start]);
+	out.push_str(open);
+	out.push_str(core);
+	out.push_str(&close);
+	out.push_str(&body[start + core.len()..]);
+}
+
+/// Keep a line from starting a Markdown block: leading indentation would
+/// become a code block, and `#`, `>`, `-`, `+`, `=`, or `1.` a heading, quote,
+/// list, or setext underline.
+fn escape_line_start(line: &str) -> String {
+	let mut out = String::with_capacity(line.len());
+	let rest = line.trim_start_matches([' ', '\t']);
+	for ch in line[..line.len() - rest.len()].chars() {
+		let count = if ch == '\t' { text::TAB_WIDTH } else { 1 };
+		out.push_str(&"&nbsp;".repeat(count));
+	}
+	let digits = rest.len()
+		- rest
+			.trim_start_matches(|ch: char| ch.is_ascii_digit())
+			.len();
+	if rest.starts_with(['#', '>', '-', '+', '=']) {
+		out.push('\\');
+	} else if (1..=9).contains(&digits) && rest[digits..].starts_with(['.', ')']) {
+		out.push_str(&rest[..digits]);
+		out.push('\\');
+		out.push_str(&rest[digits..]);
+		return out;
+	}
+	out.push_str(rest);
+	out
+}
+
+fn render_markdown(lines: &[Vec<Piece>]) -> String {
+	let rendered: Vec<String> = lines
+		.iter()
+		.map(|line| {
+			let mut out = String::new();
+			for group in line.chunk_by(|a, b| a.link == b.link) {
+				let mut label = String::new();
+				for run in group.chunk_by(|a, b| {
+					(a.style.bold, a.style.italic, a.style.strike)
+						== (b.style.bold, b.style.italic, b.style.strike)
+				}) {
+					emphasis(run, &mut label);
+				}
+				match &group[0].link {
+					Some(uri) if is_safe_uri(uri) => {
+						let _ = write!(out, "[{label}]({})", encode_link(uri));
+					},
+					_ => out.push_str(&label),
+				}
+			}
+			escape_line_start(&out)
+		})
+		.collect();
+	let mut out = rendered.join("  \n");
+	out.push('\n');
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// HTML
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn span_css(palette: &Palette, style: SpanStyle) -> String {
+	let cell = palette.cell_style(style);
+	let mut css = String::new();
+	if cell.fg != palette.foreground {
+		let _ = write!(css, "color:{};", screenshot::hex(cell.fg));
+	}
+	if let Some(bg) = cell.bg {
+		let _ = write!(css, "background-color:{};", screenshot::hex(bg));
+	}
+	if cell.bold {
+		css.push_str("font-weight:bold;");
+	}
+	if cell.dim {
+		css.push_str("opacity:0.5;");
+	}
+	if cell.italic {
+		css.push_str("font-style:italic;");
+	}
+	match (cell.underline, cell.strike) {
+		(true, true) => css.push_str("text-decoration:underline line-through;"),
+		(true, false) => css.push_str("text-decoration:underline;"),
+		(false, true) => css.push_str("text-decoration:line-through;"),
+		(false, false) => {},
+	}
+	if cell.hidden {
+		css.push_str("visibility:hidden;");
+	}
+	css.pop();
+	css
+}
+
+fn render_piece(piece: &Piece, palette: &Palette, out: &mut String) {
+	let Content::Text(text) = &piece.content else {
+		out.push_str("<span style=\"opacity:0.5;font-style:italic\">");
+		screenshot::escape_xml(IMAGE_PLACEHOLDER, out);
+		out.push_str("</span>");
+		return;
+	};
+	let css = span_css(palette, piece.style);
+	if css.is_empty() {
+		screenshot::escape_xml(text, out);
+		return;
+	}
+	let _ = write!(out, "<span style=\"{css}\">");
+	screenshot::escape_xml(text, out);
+	out.push_str("</span>");
+}
+
+fn render_html(lines: &[Vec<Piece>], palette: &Palette) -> String {
+	let mut out = String::new();
+	let _ = write!(
+		out,
+		"<pre style=\"margin:0;padding:{}px;background-color:{};color:{};font-family:",
+		screenshot::DEFAULT_PADDING,
+		screenshot::hex(palette.background),
+		screenshot::hex(palette.foreground),
+	);
+	screenshot::escape_xml(screenshot::DEFAULT_FONT_FAMILY, &mut out);
+	let _ = write!(
+		out,
+		";font-size:{}px;line-height:{};tab-size:{}\">",
+		screenshot::DEFAULT_FONT_SIZE,
+		screenshot::DEFAULT_LINE_HEIGHT,
+		text::TAB_WIDTH,
+	);
+	for (index, line) in lines.iter().enumerate() {
+		if index > 0 {
+			out.push('\n');
+		}
+		for group in line.chunk_by(|a, b| a.link == b.link) {
+			let link = group[0].link.as_deref().filter(|uri| is_safe_uri(uri));
+			if let Some(uri) = link {
+				out.push_str("<a href=\"");
+				screenshot::escape_xml(uri, &mut out);
+				out.push_str("\" style=\"color:inherit\">");
+			}
+			for piece in group {
+				render_piece(piece, palette, &mut out);
+			}
+			if link.is_some() {
+				out.push_str("</a>");
+			}
+		}
+	}
+	out.push_str("</pre>\n");
+	out
+}
+
+fn html_document(title: &str, body: &str, palette: &Palette) -> String {
+	let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+	screenshot::escape_xml(title, &mut out);
+	let _ = write!(
+		out,
+		"</title>\n</head>\n<body style=\"margin:0;background-color:{}\">\n{body}</body>\n</html>\n",
+		screenshot::hex(palette.background),
+	);
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Export styled terminal lines as a shareable transcript.
+///
+/// # Arguments
+/// - `lines`: Lines as written to the terminal, with ANSI escapes.
+/// - `format`: `"text"`, `"html"`, or `"markdown"`.
+/// - `options`: HTML title, theme, and whether to emit a full document.
+///
+/// # Returns
+/// The transcript, ending with a newline.
+///
+/// # Errors
+/// Returns an error for an unknown format or an invalid theme color.
+#[napi(js_name = "exportTranscript")]
+pub fn export_transcript(
+	lines: Vec<String>,
+	format: String,
+	options: Option<TranscriptOptions>,
+) -> Result<String> {
+	let parsed: Vec<Vec<Piece>> = lines.iter().map(|line| pieces(line)).collect();
+	match format.as_str() {
+		"text" => Ok(render_text(&parsed)),
+		"markdown" => Ok(render_markdown(&parsed)),
+		"html" => {
+			let options = options.unwrap_or(TranscriptOptions {
+				title:      None,
+				theme:      None,
+				standalone: None,
+			});
+			let palette = Palette::from_theme(options.theme.as_ref())?;
+			let pre = render_html(&parsed, &palette);
+			if !options.standalone.unwrap_or(true) {
+				return Ok(pre);
+			}
+			let title = options.title.as_deref().unwrap_or(DEFAULT_TITLE);
+			Ok(html_document(title, &pre, &palette))
+		},
+		other => Err(Error::from_reason(format!(
+			"Unknown transcript format: {other} (expected text, html, or markdown)"
+		))),
+	}
+}
@@ -0,0 +1,452 @@
+//! Exact nearest-neighbour search over embedding vectors.
+//!
+//! # Overview
+//! `VectorIndex` keeps fixed-dimension `f32` vectors under string ids in one
+//! contiguous buffer and answers top-k queries by scanning every row on the
+//! worker pool in parallel. A flat scan is exact and fast enough for the
+//! tens of thousands of chunks in a workspace; it also makes `add` and
+//! `remove` constant time with no graph to rebuild.
+//!
+//! With the `cosine` metric (default) vectors are normalized on insert, so
+//! queries reduce to dot products; `dot` scores raw inner products.
+//!
+//! Indexes persist to a single little-endian binary file written through a
+//! temp file and rename, so a crash never leaves a truncated index.
+//!
+//! # Example
+//! ```ignore
+//! // JS: const index = new native.VectorIndex(384)
+//! // JS: index.add("src/lib.rs#3", embedding)
+//! // JS: await index.search(queryEmbedding, 10) // -> [{ id, score }]
+//! // JS: await index.save(".cache/vectors.bin")
+//! ```
+
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::RwLock;
+use rayon::prelude::*;
+
+use crate::task;
+
+/// File signature of a saved index.
+const MAGIC: &[u8; 4] = b"PIVX";
+/// Version of the on-disk format.
+const FORMAT_VERSION: u32 = 1;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for creating a `VectorIndex`.
+#[napi(object)]
+pub struct VectorIndexOptions {
+	/// Similarity metric: "cosine" (default) or "dot".
+	pub metric: Option<String>,
+}
+
+/// Options for `VectorIndex.search`.
+#[napi(object)]
+pub struct VectorSearchOptions<'env> {
+	/// Drop matches scoring below this value.
+	#[napi(js_name = "minScore")]
+	pub min_score:  Option<f64>,
+	/// Abort signal for cancelling the operation.
+	pub signal:     Option<Unknown<'env>>,
+	/// Timeout in milliseconds for the operation.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms: Option<u32>,
+}
+
+/// A search hit.
+#[napi(object)]
+pub struct VectorMatch {
+	/// Id the vector was added under.
+	pub id:    String,
+	/// Cosine similarity or dot product with the query (higher is closer).
+	pub score: f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Storage
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Metric {
+	Cosine,
+	Dot,
+}
+
+impl Metric {
+	fn parse(name: Option<&str>) -> Result<Self> {
+		match name.unwrap_or("cosine") {
+			"cosine" => Ok(Self::Cosine),
+			"dot" => Ok(Self::Dot),
+			other => Err(Error::from_reason(format!("Unknown vector metric: {other}"))),
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Cosine => "cosine",
+			Self::Dot => "dot",
+		}
+	}
+
+	const fn tag(self) -> u8 {
+		match self {
+			Self::Cosine => 0,
+			Self::Dot => 1,
+		}
+	}
+
+	const fn from_tag(tag: u8) -> Option<Self> {
+		match tag {
+			0 => Some(Self::Cosine),
+			1 => Some(Self::Dot),
+			_ => None,
+		}
+	}
+}
+
+/// Row-major vector storage with an id lookup table.
+struct Store {
+	dimensions: usize,
+	metric:     Metric,
+	ids:        Vec<String>,
+	/// `ids.len() * dimensions` components; row `i` belongs to `ids[i]`.
+	data:       Vec<f32>,
+	rows:       HashMap<String, usize>,
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+	a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+impl Store {
+	fn new(dimensions: usize, metric: Metric) -> Self {
+		Self { dimensions, metric, ids: Vec::new(), data: Vec::new(), rows: HashMap::new() }
+	}
+
+	/// Validate `vector` and bring it into the stored form for this metric.
+	fn prepare(&self, vector: &[f32], what: &str) -> Result<Vec<f32>> {
+		if vector.len() != self.dimensions {
+			return Err(Error::from_reason(format!(
+				"{what} has {} dimensions, expected {}",
+				vector.len(),
+				self.dimensions
+			)));
+		}
+		if vector.iter().any(|value| !value.is_finite()) {
+			return Err(Error::from_reason(format!("{what} contains a non-finite value")));
+		}
+		let mut vector = vector.to_vec();
+		if self.metric == Metric::Cosine {
+			let norm = dot(&vector, &vector).sqrt();
+			if norm > 0.0 {
+				for value in &mut vector {
+					*value /= norm;
+				}
+			}
+		}
+		Ok(vector)
+	}
+
+	fn row(&self, row: usize) -> &[f32] {
+		&self.data[row * self.dimensions..(row + 1) * self.dimensions]
+	}
+
+	fn insert(&mut self, id: String, vector: &[f32]) {
+		if let Some(&row) = self.rows.get(&id) {
+			let dims = self.dimensions;
+			self.data[row * dims..(row + 1) * dims].copy_from_slice(vector);
+			return;
+		}
+		self.rows.insert(id.clone(), self.ids.len());
+		self.ids.push(id);
+		self.data.extend_from_slice(vector);
+	}
+
+	/// Remove `id` by moving the last row into its slot.
+	fn remove(&mut self, id: &str) -> bool {
+		let Some(row) = self.rows.remove(id) else {
+			return false;
+		};
+		let last = self.ids.len() - 1;
+		let dims = self.dimensions;
+		if row != last {
+			self
+				.data
+				.copy_within(last * dims..(last + 1) * dims, row * dims);
+			self.ids.swap(row, last);
+			self.rows.insert(self.ids[row].clone(), row);
+		}
+		self.ids.pop();
+		self.data.truncate(last * dims);
+		true
+	}
+
+	fn search(
+		&self,
+		query: &[f32],
+		k: usize,
+		min_score: Option<f64>,
+		ct: &task::CancelToken,
+	) -> Result<Vec<VectorMatch>> {
+		if k == 0 || self.ids.is_empty() {
+			return Ok(Vec::new());
+		}
+		let mut scored = (0..self.ids.len())
+			.into_par_iter()
+			.with_min_len(1024)
+			.map(|row| {
+				if row % 4096 == 0 {
+					ct.heartbeat()?;
+				}
+				Ok((dot(query, self.row(row)), row))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		if let Some(min) = min_score {
+			scored.retain(|&(score, _)| f64::from(score) >= min);
+		}
+		let by_score = |a: &(f32, usize), b: &(f32, usize)| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1));
+		if scored.len() > k {
+			scored.select_nth_unstable_by(k - 1, by_score);
+			scored.truncate(k);
+		}
+		scored.sort_unstable_by(by_score);
+		Ok(scored
+			.into_iter()
+			.map(|(score, row)| VectorMatch { id: self.ids[row].clone(), score: f64::from(score) })
+			.collect())
+	}
+
+	// ─────────────────────────────────────────────────────────────────────────
+	// Persistence
+	// ─────────────────────────────────────────────────────────────────────────
+
+	fn encode(&self) -> Vec<u8> {
+		let ids_len: usize = self.ids.iter().map(|id| 4 + id.len()).sum();
+		let mut out = Vec::with_capacity(17 + ids_len + self.data.len() * 4);
+		out.extend_from_slice(MAGIC);
+		out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+		out.push(self.metric.tag());
+		out.extend_from_slice(&(self.dimensions as u32).to_le_bytes());
+		out.extend_from_slice(&(self.ids.len() as u32).to_le_bytes());
+		for id in &self.ids {
+			out.extend_from_slice(&(id.len() as u32).to_le_bytes());
+			out.extend_from_slice(id.as_bytes());
+		}
+		for value in &self.data {
+			out.extend_from_slice(&value.to_le_bytes());
+		}
+		out
+	}
+
+	fn decode(bytes: &[u8]) -> Option<Self> {
+		let mut reader = Reader { bytes };
+		if reader.take(4)? != MAGIC || reader.u32()? != FORMAT_VERSION {
+			return None;
+		}
+		let metric = Metric::from_tag(reader.take(1)?[0])?;
+		let dimensions = reader.u32().filter(|&dims| dims > 0)? as usize;
+		let count = reader.u32()? as usize;
+		let mut store = Self::new(dimensions, metric);
+		for row in 0..count {
+			let len = reader.u32()? as usize;
+			let id = std::str::from_utf8(reader.take(len)?).ok()?.to_string();
+			store.rows.insert(id.clone(), row);
+			store.ids.push(id);
+		}
+		let data = reader.take(count.checked_mul(dimensions)?.checked_mul(4)?)?;
+		if !reader.bytes.is_empty() || store.rows.len() != count {
+			return None;
+		}
+		store.data = data
+			.as_chunks::<4>()
+			.0
+			.iter()
+			.map(|&chunk| f32::from_le_bytes(chunk))
+			.collect();
+		Some(store)
+	}
+
+	fn load(path: &Path) -> Result<Self> {
+		let bytes = fs::read(path)
+			.map_err(|err| Error::from_reason(format!("Failed to read {}: {err}", path.display())))?;
+		Self::decode(&bytes).ok_or_else(|| {
+			Error::from_reason(format!("{} is not a valid vector index", path.display()))
+		})
+	}
+
+	/// Write the index to `path` via a temp file.
+	fn save(&self, path: &Path) -> Result<()> {
+		let io_error = |action: &str, err: std::io::Error| {
+			Error::from_reason(format!("Failed to {action} {}: {err}", path.display()))
+		};
+		if let Some(parent) = path
+			.parent()
+			.filter(|parent| !parent.as_os_str().is_empty())
+		{
+			fs::create_dir_all(parent).map_err(|err| io_error("create parent of", err))?;
+		}
+		let name = path
+			.file_name()
+			.map_or_else(Default::default, |name| name.to_string_lossy());
+		let temp = path.with_file_name(format!(".{name}.{}.tmp", std::process::id()));
+		fs::write(&temp, self.encode())
+			.and_then(|()| fs::rename(&temp, path))
+			.map_err(|err| {
+				let _ = fs::remove_file(&temp);
+				io_error("write", err)
+			})
+	}
+}
+
+/// Cursor over a saved index.
+struct Reader<'a> {
+	bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	const fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+		if self.bytes.len() < len {
+			return None;
+		}
+		let (head, tail) = self.bytes.split_at(len);
+		self.bytes = tail;
+		Some(head)
+	}
+
+	fn u32(&mut self) -> Option<u32> {
+		let bytes = self.take(4)?;
+		Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API class
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// In-memory vector index with exact top-k search and file persistence.
+#[napi]
+pub struct VectorIndex {
+	store: Arc<RwLock<Store>>,
+}
+
+#[napi]
+impl VectorIndex {
+	/// Create an empty index for vectors of `dimensions` components.
+	///
+	/// # Errors
+	/// Returns an error for zero dimensions or an unknown metric.
+	#[napi(constructor)]
+	pub fn new(dimensions: u32, options: Option<VectorIndexOptions>) -> Result<Self> {
+		if dimensions == 0 {
+			return Err(Error::from_reason("dimensions must be greater than 0"));
+		}
+		let metric = Metric::parse(options.and_then(|opts| opts.metric).as_deref())?;
+		Ok(Self { store: Arc::new(RwLock::new(Store::new(dimensions as usize, metric))) })
+	}
+
+	/// Load an index written by `save`.
+	///
+	/// # Errors
+	/// Returns an error if the file cannot be read or is not a saved index.
+	#[napi(js_name = "load")]
+	#[allow(clippy::use_self, reason = "napi expands the return type outside the impl")]
+	pub fn load(path: String) -> task::Async<VectorIndex> {
+		task::blocking("vector_index.load", (), move |_| {
+			let store = Store::load(&PathBuf::from(path))?;
+			Ok(Self { store: Arc::new(RwLock::new(store)) })
+		})
+	}
+
+	/// Add `vector` under `id`, replacing any vector already stored there.
+	///
+	/// # Errors
+	/// Returns an error if the vector has the wrong length or a non-finite
+	/// component.
+	#[napi]
+	pub fn add(&self, id: String, vector: Float32Array) -> Result<()> {
+		let vector = self.store.read().prepare(&vector, "Vector")?;
+		self.store.write().insert(id, &vector);
+		Ok(())
+	}
+
+	/// Remove the vector stored under `id`.
+	///
+	/// # Returns
+	/// Whether the id was present.
+	#[napi]
+	pub fn remove(&self, id: String) -> bool {
+		self.store.write().remove(&id)
+	}
+
+	/// Whether a vector is stored under `id`.
+	#[napi]
+	pub fn has(&self, id: String) -> bool {
+		self.store.read().rows.contains_key(&id)
+	}
+
+	/// Find the `k` stored vectors closest to `query`.
+	///
+	/// # Returns
+	/// Matches sorted by descending score.
+	///
+	/// # Errors
+	/// Returns an error if the query has the wrong length or a non-finite
+	/// component, or the search is cancelled.
+	#[napi]
+	pub fn search(
+		&self,
+		query: Float32Array,
+		k: u32,
+		options: Option<VectorSearchOptions<'_>>,
+	) -> Result<task::Async<Vec<VectorMatch>>> {
+		let (min_score, signal, timeout_ms) = match options {
+			Some(opts) => (opts.min_score, opts.signal, opts.timeout_ms),
+			None => (None, None, None),
+		};
+		let query = self.store.read().prepare(&query, "Query")?;
+		let store = Arc::clone(&self.store);
+		let ct = task::CancelToken::new(timeout_ms, signal);
+		Ok(task::blocking("vector_index.search", ct, move |ct| {
+			store.read().search(&query, k as usize, min_score, &ct)
+		}))
+	}
+
+	/// Write the index to `path`, replacing the file atomically.
+	///
+	/// # Errors
+	/// Returns an error if the file cannot be written.
+	#[napi]
+	pub fn save(&self, path: String) -> task::Async<()> {
+		let store = Arc::clone(&self.store);
+		task::blocking("vector_index.save", (), move |_| store.read().save(&PathBuf::from(path)))
+	}
+
+	/// Number of stored vectors.
+	#[napi(getter)]
+	pub fn size(&self) -> u32 {
+		self.store.read().ids.len() as u32
+	}
+
+	/// Components per vector.
+	#[napi(getter)]
+	pub fn dimensions(&self) -> u32 {
+		self.store.read().dimensions as u32
+	}
+
+	/// Similarity metric, "cosine" or "dot".
+	#[napi(getter)]
+	pub fn metric(&self) -> String {
+		self.store.read().metric.name().to_string()
+	}
+}
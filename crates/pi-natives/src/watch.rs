@@ -0,0 +1,450 @@
+//! Filesystem watching with quiet-period triggers.
+//!
+//! # Overview
+//! `watchAndTrigger` watches a directory tree recursively and folds raw
+//! filesystem events into one "changed set" callback per burst: the callback
+//! fires only once the tree has been quiet for `quietMs`, so an editor save
+//! (write temp, rename, chmod) or a formatter touching dozens of files
+//! triggers downstream re-indexing once instead of per event.
+//!
+//! Events are filtered like the `glob`/`grep` walker before they count toward
+//! a burst: `.git` and `node_modules` are skipped, hidden paths are skipped
+//! unless requested, ignore files are honored exactly as `IgnoreMatcher`
+//! does, and an optional list of globs narrows the set further. Ignored churn
+//! such as build output therefore never delays a trigger.
+//!
+//! Reported paths also drop stale entries from the filesystem scan cache.
+//!
+//! `Watcher.watch` applies the same filtering and bursts to several
+//! directories at once and keeps watching until `close()` is called, for
+//! long-lived watches that would otherwise need an abort signal each.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.watchAndTrigger({ path: ".", globs: ["*.ts"], signal }, ev => reindex(ev.changed))
+//! // JS: const watcher = native.Watcher.watch(["src", "test"], { quietMs: 50 }, ev => rebuild(ev.changed))
+//! // JS: watcher.close()
+//! ```
+
+use std::{
+	collections::BTreeSet,
+	path::{Path, PathBuf},
+	sync::mpsc::{self, RecvTimeoutError},
+	thread::JoinHandle,
+	time::{Duration, Instant},
+};
+
+use globset::GlobSet;
+use napi::{
+	bindgen_prelude::*,
+	threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode},
+};
+use napi_derive::napi;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+use crate::{file_filter, fs_cache, ignore_matcher::IgnoreMatcher, task};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `watchAndTrigger`.
+#[napi(object)]
+pub struct WatchTriggerOptions<'env> {
+	/// Directory to watch recursively (default: current directory).
+	pub path:        Option<String>,
+	/// Only report files matching one of these globs (default: all files).
+	pub globs:       Option<Vec<String>>,
+	/// Minimum time from the first event of a burst to the callback, so
+	/// trailing events are folded in (default: 100).
+	#[napi(js_name = "debounceMs")]
+	pub debounce_ms: Option<u32>,
+	/// Time without events before the callback fires (default: 200).
+	#[napi(js_name = "quietMs")]
+	pub quiet_ms:    Option<u32>,
+	/// Include hidden files (default: false).
+	pub hidden:      Option<bool>,
+	/// Respect .gitignore files (default: true).
+	pub gitignore:   Option<bool>,
+	/// Abort signal that stops watching.
+	pub signal:      Option<Unknown<'env>>,
+	/// Stop watching after this many milliseconds.
+	#[napi(js_name = "timeoutMs")]
+	pub timeout_ms:  Option<u32>,
+}
+
+/// Options for `Watcher.watch`.
+#[napi(object)]
+pub struct WatcherOptions {
+	/// Only report files matching one of these globs, relative to the watched
+	/// directory (default: all files).
+	pub globs:       Option<Vec<String>>,
+	/// Minimum time from the first event of a burst to the callback, so
+	/// trailing events are folded in (default: 100).
+	#[napi(js_name = "debounceMs")]
+	pub debounce_ms: Option<u32>,
+	/// Time without events before the callback fires (default: 200).
+	#[napi(js_name = "quietMs")]
+	pub quiet_ms:    Option<u32>,
+	/// Include hidden files (default: false).
+	pub hidden:      Option<bool>,
+	/// Respect .gitignore files (default: true).
+	pub gitignore:   Option<bool>,
+	/// Watch subdirectories too (default: true).
+	pub recursive:   Option<bool>,
+}
+
+/// Files that changed during one burst of activity.
+#[napi(object)]
+pub struct WatchTriggerEvent {
+	/// Created or modified files, relative to the watched directory
+	/// (absolute for `Watcher`).
+	pub changed: Vec<String>,
+	/// Deleted files, relative to the watched directory (absolute for
+	/// `Watcher`).
+	pub deleted: Vec<String>,
+}
+
+/// Summary returned when watching stops.
+#[napi(object)]
+pub struct WatchTriggerResult {
+	/// Number of callbacks delivered.
+	pub triggers: u32,
+	/// Total paths reported across all callbacks.
+	pub paths:    f64,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Event filtering
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Decides which event paths count, mirroring the walker's visibility rules.
+struct EventFilter {
+	root:    PathBuf,
+	globs:   Vec<GlobSet>,
+	ignores: IgnoreMatcher,
+}
+
+impl EventFilter {
+	fn new(
+		root: PathBuf,
+		globs: &[String],
+		include_hidden: bool,
+		use_gitignore: bool,
+	) -> Result<Self> {
+		let globs = globs
+			.iter()
+			.filter_map(|glob| file_filter::compile_glob(Some(glob)).transpose())
+			.collect::<Result<Vec<_>>>()?;
+		let ignores =
+			IgnoreMatcher::with_settings(root.clone(), include_hidden, use_gitignore, false);
+		Ok(Self { root, globs, ignores })
+	}
+
+	/// Whether a change at `path` should be reported.
+	fn accepts(&mut self, path: &Path) -> bool {
+		let Ok(relative) = path.strip_prefix(&self.root) else {
+			return false;
+		};
+		if relative.as_os_str().is_empty() {
+			return false;
+		}
+		if path
+			.file_name()
+			.is_some_and(|name| name == ".gitignore" || name == ".ignore")
+		{
+			// Rules changed; reload them on the next lookup.
+			if let Some(dir) = path.parent() {
+				self.ignores.forget(dir);
+			}
+		}
+		if !self.globs.is_empty() && !self.globs.iter().any(|glob| glob.is_match(relative)) {
+			return false;
+		}
+		!self.ignores.is_ignored_path(path, path.is_dir())
+	}
+}
+
+/// Split pending paths into changed and deleted files, named by `label`.
+fn classify(pending: &BTreeSet<PathBuf>, label: impl Fn(&Path) -> String) -> WatchTriggerEvent {
+	let mut event = WatchTriggerEvent { changed: Vec::new(), deleted: Vec::new() };
+	for path in pending {
+		match std::fs::metadata(path) {
+			Ok(metadata) if metadata.is_file() => event.changed.push(label(path)),
+			Ok(_) => continue,
+			Err(_) => event.deleted.push(label(path)),
+		}
+		fs_cache::invalidate_path(path);
+	}
+	event
+}
+
+/// Fold paths from `rx` into bursts and deliver each once the quiet period
+/// ends. Returns when the sending watcher is dropped; pending paths are
+/// discarded.
+fn deliver_bursts(
+	rx: &mpsc::Receiver<PathBuf>,
+	debounce: Duration,
+	quiet: Duration,
+	on_change: &ThreadsafeFunction<WatchTriggerEvent>,
+) {
+	let mut pending = BTreeSet::new();
+	let mut first_event = Instant::now();
+	let mut last_event = Instant::now();
+	loop {
+		let received = if pending.is_empty() {
+			rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+		} else {
+			let fire_at = (first_event + debounce).max(last_event + quiet);
+			rx.recv_timeout(fire_at.saturating_duration_since(Instant::now()))
+		};
+		match received {
+			Ok(path) => {
+				let now = Instant::now();
+				if pending.is_empty() {
+					first_event = now;
+				}
+				last_event = now;
+				pending.insert(path);
+			},
+			Err(RecvTimeoutError::Timeout) => {
+				let event =
+					classify(&std::mem::take(&mut pending), |path| path.to_string_lossy().into_owned());
+				if !event.changed.is_empty() || !event.deleted.is_empty() {
+					on_change.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+				}
+			},
+			Err(RecvTimeoutError::Disconnected) => return,
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Watch a directory and report changed files once per burst of activity.
+///
+/// # Arguments
+/// - `options`: Directory, filters, quiet period, and cancellation.
+/// - `on_change`: Callback receiving the changed and deleted files of each
+///   burst.
+///
+/// # Returns
+/// A summary once the signal aborts or the timeout elapses.
+///
+/// # Errors
+/// Returns an error if the directory does not exist, a glob is invalid, or
+/// the watcher cannot be started.
+#[napi(js_name = "watchAndTrigger")]
+pub fn watch_and_trigger<'env>(
+	env: &'env Env,
+	options: Option<WatchTriggerOptions<'env>>,
+	#[napi(ts_arg_type = "(event: WatchTriggerEvent) => void")] on_change: ThreadsafeFunction<
+		WatchTriggerEvent,
+	>,
+) -> Result<PromiseRaw<'env, WatchTriggerResult>> {
+	let (path, globs, debounce_ms, quiet_ms, hidden, gitignore, signal, timeout_ms) = match options {
+		Some(opts) => (
+			opts.path,
+			opts.globs,
+			opts.debounce_ms,
+			opts.quiet_ms,
+			opts.hidden,
+			opts.gitignore,
+			opts.signal,
+			opts.timeout_ms,
+		),
+		None => (None, None, None, None, None, None, None, None),
+	};
+	let debounce = Duration::from_millis(u64::from(debounce_ms.unwrap_or(100)));
+	let quiet = Duration::from_millis(u64::from(quiet_ms.unwrap_or(200)));
+	let root = fs_cache::resolve_search_path(path.as_deref().unwrap_or("."))?;
+	let mut filter = EventFilter::new(
+		root.clone(),
+		&globs.unwrap_or_default(),
+		hidden.unwrap_or(false),
+		gitignore.unwrap_or(true),
+	)?;
+	let ct = task::CancelToken::new(timeout_ms, signal);
+
+	let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PathBuf>();
+	let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+		let Ok(event) = res else { return };
+		if matches!(event.kind, EventKind::Access(_)) {
+			return;
+		}
+		for path in event.paths {
+			if filter.accepts(&path) {
+				let _ = tx.send(path);
+			}
+		}
+	})
+	.map_err(|err| Error::from_reason(format!("Failed to start watcher: {err}")))?;
+	watcher
+		.watch(&root, RecursiveMode::Recursive)
+		.map_err(|err| Error::from_reason(format!("Failed to watch {}: {err}", root.display())))?;
+
+	task::future(env, "watch_and_trigger", async move {
+		// Dropping the watcher stops event delivery; keep it for the whole loop.
+		let _watcher = watcher;
+		let mut summary = WatchTriggerResult { triggers: 0, paths: 0.0 };
+		let mut pending = BTreeSet::new();
+		let mut first_event = Instant::now();
+		let mut last_event = Instant::now();
+		loop {
+			let fire_at = (first_event + debounce).max(last_event + quiet);
+			tokio::select! {
+				received = rx.recv() => {
+					let Some(path) = received else { return Ok(summary) };
+					let now = Instant::now();
+					if pending.is_empty() {
+						first_event = now;
+					}
+					last_event = now;
+					pending.insert(path);
+				},
+				() = tokio::time::sleep_until(fire_at.into()), if !pending.is_empty() => {
+					let event = classify(&std::mem::take(&mut pending), |path| {
+						fs_cache::normalize_relative_path(&root, path).into_owned()
+					});
+					let count = event.changed.len() + event.deleted.len();
+					if count > 0 {
+						summary.triggers = summary.triggers.saturating_add(1);
+						summary.paths += count as f64;
+						on_change.call(Ok(event), ThreadsafeFunctionCallMode::NonBlocking);
+					}
+				},
+				_ = ct.wait() => return Ok(summary),
+			}
+		}
+	})
+}
+
+/// Running watch owned by a `Watcher`.
+struct ActiveWatch {
+	watcher: RecommendedWatcher,
+	worker:  JoinHandle<()>,
+}
+
+/// Long-lived watch over one or more directories that reports changed files
+/// once per burst of activity until closed.
+#[napi]
+pub struct Watcher {
+	active: Option<ActiveWatch>,
+}
+
+#[napi]
+impl Watcher {
+	/// Start watching directories.
+	///
+	/// # Arguments
+	/// - `paths`: Directories to watch; relative paths resolve against the
+	///   current directory.
+	/// - `options`: Filters, burst timing, and recursion.
+	/// - `on_change`: Callback receiving the changed and deleted files of each
+	///   burst, as absolute paths.
+	///
+	/// # Errors
+	/// Returns an error if no paths are given, a directory does not exist, a
+	/// glob is invalid, or the watcher cannot be started.
+	#[napi(factory)]
+	pub fn watch(
+		paths: Vec<String>,
+		options: Option<WatcherOptions>,
+		#[napi(ts_arg_type = "(event: WatchTriggerEvent) => void")] on_change: ThreadsafeFunction<
+			WatchTriggerEvent,
+		>,
+	) -> Result<Self> {
+		let (globs, debounce_ms, quiet_ms, hidden, gitignore, recursive) = match options {
+			Some(opts) => (
+				opts.globs,
+				opts.debounce_ms,
+				opts.quiet_ms,
+				opts.hidden,
+				opts.gitignore,
+				opts.recursive,
+			),
+			None => (None, None, None, None, None, None),
+		};
+		if paths.is_empty() {
+			return Err(Error::from_reason("No paths to watch"));
+		}
+		let debounce = Duration::from_millis(u64::from(debounce_ms.unwrap_or(100)));
+		let quiet = Duration::from_millis(u64::from(quiet_ms.unwrap_or(200)));
+		let mode = if recursive.unwrap_or(true) {
+			RecursiveMode::Recursive
+		} else {
+			RecursiveMode::NonRecursive
+		};
+		let globs = globs.unwrap_or_default();
+		let roots = paths
+			.iter()
+			.map(|path| fs_cache::resolve_search_path(path))
+			.collect::<Result<Vec<_>>>()?;
+		let mut filters = roots
+			.iter()
+			.map(|root| {
+				EventFilter::new(
+					root.clone(),
+					&globs,
+					hidden.unwrap_or(false),
+					gitignore.unwrap_or(true),
+				)
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let (tx, rx) = mpsc::channel::<PathBuf>();
+		let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+			let Ok(event) = res else { return };
+			if matches!(event.kind, EventKind::Access(_)) {
+				return;
+			}
+			for path in event.paths {
+				// Nested roots: the innermost one decides.
+				let filter = filters
+					.iter_mut()
+					.filter(|filter| path.starts_with(&filter.root))
+					.max_by_key(|filter| filter.root.as_os_str().len());
+				if filter.is_some_and(|filter| filter.accepts(&path)) {
+					let _ = tx.send(path);
+				}
+			}
+		})
+		.map_err(|err| Error::from_reason(format!("Failed to start watcher: {err}")))?;
+		for root in &roots {
+			watcher.watch(root, mode).map_err(|err| {
+				Error::from_reason(format!("Failed to watch {}: {err}", root.display()))
+			})?;
+		}
+
+		let worker = std::thread::Builder::new()
+			.name("pi-watcher".to_string())
+			.spawn(move || deliver_bursts(&rx, debounce, quiet, &on_change))
+			.map_err(|err| Error::from_reason(format!("Failed to start watcher thread: {err}")))?;
+		Ok(Self { active: Some(ActiveWatch { watcher, worker }) })
+	}
+
+	/// Stop watching. Changes not yet delivered are discarded; calling it
+	/// again does nothing.
+	#[napi]
+	pub fn close(&mut self) {
+		if let Some(active) = self.active.take() {
+			// Dropping the watcher disconnects the channel and ends the worker.
+			drop(active.watcher);
+			let _ = active.worker.join();
+		}
+	}
+
+	/// Whether `close()` has been called.
+	#[napi(getter)]
+	pub const fn closed(&self) -> bool {
+		self.active.is_none()
+	}
+}
+
+impl Drop for Watcher {
+	fn drop(&mut self) {
+		self.close();
+	}
+}
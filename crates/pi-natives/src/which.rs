@@ -0,0 +1,155 @@
+//! Executable resolution across `PATH`.
+//!
+//! # Overview
+//! Resolves a command name to every matching executable, in `PATH` order, the
+//! way a shell would pick it. On Windows the current directory is searched
+//! first and each `PATHEXT` extension is tried; on Unix a candidate must be a
+//! regular file with an execute bit. Names containing a path separator are
+//! resolved against the working directory only.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.whichAll("node") // -> ["/usr/local/bin/node", "/usr/bin/node"]
+//! // JS: native.whichAll("rg", { env: { PATH: "/opt/bin" } }) // -> []
+//! ```
+
+use std::{
+	collections::{HashMap, HashSet},
+	env,
+	ffi::OsString,
+	path::{Path, PathBuf},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `whichAll`.
+#[napi(object)]
+pub struct WhichOptions {
+	/// Directory used for relative names and relative `PATH` entries
+	/// (default: process cwd).
+	pub cwd: Option<String>,
+	/// Environment to read `PATH`/`PATHEXT` from (default: process env).
+	pub env: Option<HashMap<String, String>>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Resolution
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Look up an environment variable, from `vars` if given. Keys are
+/// case-insensitive on Windows.
+fn lookup_var(vars: Option<&HashMap<String, String>>, key: &str) -> Option<OsString> {
+	let Some(vars) = vars else {
+		return env::var_os(key);
+	};
+	if cfg!(windows) {
+		vars
+			.iter()
+			.find(|(name, _)| name.eq_ignore_ascii_case(key))
+			.map(|(_, value)| value.into())
+	} else {
+		vars.get(key).map(Into::into)
+	}
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+	use std::os::unix::fs::PermissionsExt;
+	path
+		.metadata()
+		.is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+	path.is_file()
+}
+
+/// File names to try for `name`: the name itself, plus (on Windows) the name
+/// with each `PATHEXT` extension unless it already has one of them.
+fn candidate_names(name: &str, vars: Option<&HashMap<String, String>>) -> Vec<String> {
+	if !cfg!(windows) {
+		return vec![name.to_string()];
+	}
+	let pathext = lookup_var(vars, "PATHEXT").map_or_else(
+		|| ".COM;.EXE;.BAT;.CMD".to_string(),
+		|value| value.to_string_lossy().into_owned(),
+	);
+	let exts: Vec<&str> = pathext.split(';').filter(|ext| !ext.is_empty()).collect();
+	let lower = name.to_ascii_lowercase();
+	if exts.iter().any(|ext| lower.ends_with(&ext.to_ascii_lowercase())) {
+		return vec![name.to_string()];
+	}
+	exts.iter().map(|ext| format!("{name}{ext}")).collect()
+}
+
+/// Resolve `name` to every matching executable, in search order.
+fn resolve(name: &str, cwd: &Path, vars: Option<&HashMap<String, String>>) -> Vec<PathBuf> {
+	if name.is_empty() {
+		return Vec::new();
+	}
+	let names = candidate_names(name, vars);
+	let has_separator = name.contains('/') || (cfg!(windows) && name.contains('\\'));
+	let mut dirs = Vec::new();
+	if has_separator {
+		dirs.push(cwd.to_path_buf());
+	} else {
+		if cfg!(windows) {
+			dirs.push(cwd.to_path_buf());
+		}
+		if let Some(path) = lookup_var(vars, "PATH") {
+			// Relative entries (including an empty one) resolve against `cwd`.
+			dirs.extend(env::split_paths(&path).map(|dir| cwd.join(dir)));
+		}
+	}
+
+	let mut seen = HashSet::new();
+	let mut found = Vec::new();
+	for dir in dirs {
+		for file in &names {
+			let candidate = dir.join(file);
+			if is_executable(&candidate) && seen.insert(candidate.clone()) {
+				found.push(candidate);
+			}
+		}
+	}
+	found
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Resolve a command name to every matching executable on `PATH`.
+///
+/// # Arguments
+/// - `name`: Command name, or a path containing a separator.
+/// - `options`: Working directory and environment overrides.
+///
+/// # Returns
+/// Paths of all matches in search order; the first is the one a shell would
+/// run. Empty when nothing matches.
+///
+/// # Errors
+/// Returns an error if no `cwd` is given and the process cwd is unavailable.
+#[napi(js_name = "whichAll")]
+pub fn which_all(name: String, options: Option<WhichOptions>) -> Result<Vec<String>> {
+	let (cwd, vars) = match options {
+		Some(opts) => (opts.cwd, opts.env),
+		None => (None, None),
+	};
+	let cwd = match cwd {
+		Some(cwd) => PathBuf::from(cwd),
+		None => env::current_dir()
+			.map_err(|err| Error::from_reason(format!("Failed to read current directory: {err}")))?,
+	};
+	Ok(resolve(&name, &cwd, vars.as_ref())
+		.into_iter()
+		.map(|path| path.to_string_lossy().into_owned())
+		.collect())
+}
@@ -0,0 +1,171 @@
+//! Identifier segmentation and word boundaries for cursor motions.
+//!
+//! # Overview
+//! `splitIdentifier` breaks an identifier into its subwords: separators such
+//! as `_`, `-`, and `.` split it, and so do camelCase humps, with acronyms
+//! kept together (`parseHTMLResponse` is `parse`, `HTML`, `Response`).
+//! Digits stay attached to the subword before them (`base64Encode` is
+//! `base64`, `Encode`).
+//!
+//! `wordBoundaries` lists the columns where words and punctuation runs start
+//! and end on a line, for word-wise cursor motion. With `codeAware`, words are
+//! further split into subwords the same way identifiers are, so motions stop
+//! inside `snake_case` and `camelCase` names like a code editor's word-part
+//! navigation. Columns are UTF-16 code units, matching JS string indices.
+//!
+//! # Example
+//! ```ignore
+//! // JS: native.splitIdentifier("XMLHttpRequest_v2") // -> ["XML", "Http", "Request", "v2"]
+//! // JS: native.wordBoundaries("let fooBar = 1;", { codeAware: true }) // -> [0, 3, 4, 7, 10, 11, 12, 13, 14, 15]
+//! ```
+
+use std::ops::Range;
+
+use napi_derive::napi;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `wordBoundaries`.
+#[napi(object)]
+pub struct WordBoundaryOptions {
+	/// Also stop at subword boundaries inside identifiers: camelCase humps and
+	/// underscores (default: false).
+	#[napi(js_name = "codeAware")]
+	pub code_aware: Option<bool>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Segmentation
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+	Space,
+	Word,
+	Punct,
+}
+
+fn classify(ch: char) -> CharClass {
+	if ch.is_whitespace() {
+		CharClass::Space
+	} else if ch.is_alphanumeric() || ch == '_' {
+		CharClass::Word
+	} else {
+		CharClass::Punct
+	}
+}
+
+/// Whether a camelCase subword starts at `chars[index]`.
+fn is_hump(chars: &[char], index: usize) -> bool {
+	let (prev, cur) = (chars[index - 1], chars[index]);
+	if !cur.is_uppercase() {
+		return false;
+	}
+	// `fooBar`, `base64Encode`
+	if prev.is_lowercase() || prev.is_numeric() {
+		return true;
+	}
+	// The last capital of an acronym starts the next word: `HTMLParser`.
+	prev.is_uppercase() && chars.get(index + 1).is_some_and(|next| next.is_lowercase())
+}
+
+/// Char ranges of the subwords in `chars`; anything not alphanumeric
+/// separates subwords.
+fn subwords(chars: &[char]) -> Vec<Range<usize>> {
+	let mut ranges = Vec::new();
+	let mut start = None;
+	for index in 0..chars.len() {
+		if !chars[index].is_alphanumeric() {
+			if let Some(begin) = start.take() {
+				ranges.push(begin..index);
+			}
+			continue;
+		}
+		match start {
+			Some(begin) if is_hump(chars, index) => {
+				ranges.push(begin..index);
+				start = Some(index);
+			},
+			Some(_) => {},
+			None => start = Some(index),
+		}
+	}
+	if let Some(begin) = start {
+		ranges.push(begin..chars.len());
+	}
+	ranges
+}
+
+/// Start and end char indices of each word, subword, or punctuation run.
+fn boundaries(chars: &[char], code_aware: bool) -> Vec<usize> {
+	let mut out = Vec::new();
+	let mut index = 0;
+	while index < chars.len() {
+		let class = classify(chars[index]);
+		let start = index;
+		while index < chars.len() && classify(chars[index]) == class {
+			index += 1;
+		}
+		match class {
+			CharClass::Space => {},
+			CharClass::Word if code_aware => {
+				let parts = subwords(&chars[start..index]);
+				if parts.is_empty() {
+					// Only underscores: treat the run as one word.
+					out.extend([start, index]);
+				}
+				for part in parts {
+					out.extend([start + part.start, start + part.end]);
+				}
+			},
+			CharClass::Word | CharClass::Punct => out.extend([start, index]),
+		}
+	}
+	out.dedup();
+	out
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Split an identifier into subwords at separators and camelCase humps.
+///
+/// # Returns
+/// Subwords in order with their original case; separators are dropped.
+#[napi(js_name = "splitIdentifier")]
+pub fn split_identifier(word: String) -> Vec<String> {
+	let chars: Vec<char> = word.chars().collect();
+	subwords(&chars)
+		.into_iter()
+		.map(|range| chars[range].iter().collect())
+		.collect()
+}
+
+/// Find word boundaries on a line for word-wise cursor motion.
+///
+/// # Arguments
+/// - `line`: A single line of text.
+/// - `options`: Whether to stop at subwords inside identifiers.
+///
+/// # Returns
+/// Ascending UTF-16 columns where a word, subword, or punctuation run starts
+/// or ends. Whitespace is never a word.
+#[napi(js_name = "wordBoundaries")]
+pub fn word_boundaries(line: String, options: Option<WordBoundaryOptions>) -> Vec<u32> {
+	let code_aware = options.and_then(|opts| opts.code_aware).unwrap_or(false);
+	let chars: Vec<char> = line.chars().collect();
+	let mut columns = Vec::with_capacity(chars.len() + 1);
+	let mut column = 0u32;
+	for ch in &chars {
+		columns.push(column);
+		column += ch.len_utf16() as u32;
+	}
+	columns.push(column);
+	boundaries(&chars, code_aware)
+		.into_iter()
+		.map(|index| columns[index])
+		.collect()
+}
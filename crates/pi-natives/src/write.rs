@@ -0,0 +1,349 @@
+//! Atomic, durable file writes.
+//!
+//! # Overview
+//! Writes go to a sibling temp file that is renamed over the target, so
+//! readers and crashes only ever see the old or the new content, never a
+//! partial write. With `fsync` (default) the temp file is flushed before the
+//! rename and the parent directory after it, so the new content and the
+//! rename itself survive power loss.
+//!
+//! The replacement keeps the target's permissions unless `mode` is given,
+//! and with `preserveOwner` its owner and group (Unix). Writing through a
+//! symlink replaces the file it points to, leaving the link intact. With
+//! `preserveEol`, text is converted to the existing file's line ending.
+//!
+//! # Example
+//! ```ignore
+//! // JS: await native.writeFileAtomic("session.json", JSON.stringify(state))
+//! // JS: await native.writeFileAtomic("run.sh", script, { mode: 0o755 })
+//! ```
+
+use std::{
+	fs::{self, File, OpenOptions},
+	io::{ErrorKind, Write},
+	path::{Path, PathBuf},
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+
+use crate::{eol, file_filter, task};
+
+/// Distinguishes temp files of concurrent writes from this process.
+static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Symlinks followed before giving up on a dangling chain (as `ELOOP`).
+const MAX_LINK_HOPS: usize = 40;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Public types
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Options for `writeFileAtomic`.
+#[napi(object)]
+pub struct WriteFileAtomicOptions {
+	/// Flush the file and its directory to disk before resolving (default:
+	/// true).
+	pub fsync:          Option<bool>,
+	/// Permission bits for the file, e.g. `0o644` (default: keep the existing
+	/// file's, or 0o666 minus the umask for new files). Unix only.
+	pub mode:           Option<u32>,
+	/// Keep the existing file's owner and group (default: false). Unix only.
+	#[napi(js_name = "preserveOwner")]
+	pub preserve_owner: Option<bool>,
+	/// Create missing parent directories (default: false).
+	#[napi(js_name = "createDirs")]
+	pub create_dirs:    Option<bool>,
+	/// Convert text `data` to the existing file's dominant line ending
+	/// (default: false). Ignored for bytes and new files.
+	#[napi(js_name = "preserveEol")]
+	pub preserve_eol:   Option<bool>,
+}
+
+/// Settings for `write_atomic`.
+#[derive(Clone, Copy, Debug)]
+pub struct AtomicWrite {
+	pub fsync:          bool,
+	pub mode:           Option<u32>,
+	pub preserve_owner: bool,
+	pub create_dirs:    bool,
+}
+
+impl Default for AtomicWrite {
+	fn default() -> Self {
+		Self {
+			fsync:          true,
+			mode:           None,
+			preserve_owner: false,
+			create_dirs:    false,
+		}
+	}
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Writing
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Copy ownership and permissions onto the temp file before it replaces the
+/// target.
+fn apply_metadata(
+	temp: &Path,
+	existing: Option<&fs::Metadata>,
+	settings: &AtomicWrite,
+) -> std::io::Result<()> {
+	#[cfg(unix)]
+	{
+		use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+		if settings.preserve_owner
+			&& let Some(existing) = existing
+		{
+			std::os::unix::fs::chown(temp, Some(existing.uid()), Some(existing.gid()))?;
+		}
+		// Applied after chown, which may clear setuid/setgid bits.
+		let mode = settings
+			.mode
+			.or_else(|| existing.map(|meta| meta.permissions().mode()));
+		if let Some(mode) = mode {
+			fs::set_permissions(temp, fs::Permissions::from_mode(mode & 0o7777))?;
+		}
+	}
+	#[cfg(not(unix))]
+	if let Some(existing) = existing {
+		fs::set_permissions(temp, existing.permissions())?;
+	}
+	Ok(())
+}
+
+/// Flush a directory entry so a rename inside it is durable.
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+	#[cfg(unix)]
+	{
+		File::open(dir)?.sync_all()
+	}
+	#[cfg(not(unix))]
+	{
+		// Directories cannot be opened for syncing here; the rename is already
+		// flushed with the file on these platforms.
+		let _ = dir;
+		Ok(())
+	}
+}
+
+/// The file a write to `path` replaces: the final target of a symlink chain,
+/// even a dangling one, or `path` itself.
+fn resolve_target(path: &Path) -> PathBuf {
+	if let Ok(target) = fs::canonicalize(path) {
+		return target;
+	}
+	// `canonicalize` fails on a dangling link; follow it to the missing file.
+	let mut target = path.to_path_buf();
+	for _ in 0..MAX_LINK_HOPS {
+		let Ok(link) = fs::read_link(&target) else {
+			break;
+		};
+		target = match target.parent() {
+			Some(parent) => parent.join(link),
+			None => link,
+		};
+	}
+	target
+}
+
+/// Write `data` to `path` via a temp file and rename.
+///
+/// The temp file is removed if any step fails, leaving the target untouched.
+pub fn write_atomic(path: &Path, data: &[u8], settings: &AtomicWrite) -> Result<()> {
+	// Replace the file a symlink points to rather than the link itself.
+	let target = resolve_target(path);
+	let existing = fs::metadata(&target).ok();
+	if existing.as_ref().is_some_and(fs::Metadata::is_dir) {
+		return Err(Error::from_reason(format!("{} is a directory", path.display())));
+	}
+	let parent = match target.parent() {
+		Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+		_ => PathBuf::from("."),
+	};
+	if settings.create_dirs {
+		fs::create_dir_all(&parent).map_err(|err| {
+			Error::from_reason(format!("Failed to create {}: {err}", parent.display()))
+		})?;
+	}
+	let name = target
+		.file_name()
+		.map(|name| name.to_string_lossy().into_owned())
+		.ok_or_else(|| Error::from_reason(format!("Invalid file path: {}", path.display())))?;
+
+	let (temp, mut file) = loop {
+		let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+		let temp = parent.join(format!(".{name}.{}.{count}.tmp", std::process::id()));
+		match OpenOptions::new().write(true).create_new(true).open(&temp) {
+			Ok(file) => break (temp, file),
+			Err(err) if err.kind() == ErrorKind::AlreadyExists => {},
+			Err(err) => {
+				return Err(Error::from_reason(format!("Failed to write {}: {err}", path.display())));
+			},
+		}
+	};
+
+	let written = file
+		.write_all(data)
+		.and_then(|()| apply_metadata(&temp, existing.as_ref(), settings))
+		.and_then(|()| {
+			if settings.fsync {
+				file.sync_all()
+			} else {
+				Ok(())
+			}
+		})
+		.and_then(|()| {
+			drop(file);
+			fs::rename(&temp, &target)
+		});
+	if let Err(err) = written {
+		let _ = fs::remove_file(&temp);
+		return Err(Error::from_reason(format!("Failed to write {}: {err}", path.display())));
+	}
+	if settings.fsync {
+		sync_dir(&parent).map_err(|err| {
+			Error::from_reason(format!("Failed to sync {}: {err}", parent.display()))
+		})?;
+	}
+	Ok(())
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// N-API exports
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Replace a file's contents atomically via a temp file and rename.
+///
+/// # Arguments
+/// - `path`: File to write; created if missing.
+/// - `data`: Text (written as UTF-8) or bytes.
+/// - `options`: Durability, permissions, ownership, and line endings.
+///
+/// # Errors
+/// Returns an error if the path is a directory, its parent does not exist
+/// (without `createDirs`), or any write, permission, or rename step fails; the
+/// original file is then left unchanged.
+#[napi(js_name = "writeFileAtomic")]
+pub fn write_file_atomic(
+	path: String,
+	data: Either<String, Uint8Array>,
+	options: Option<WriteFileAtomicOptions>,
+) -> task::Async<()> {
+	let preserve_eol = options
+		.as_ref()
+		.and_then(|opts| opts.preserve_eol)
+		.unwrap_or(false);
+	let data = match data {
+		Either::A(text) => Either::A(text),
+		Either::B(buffer) => Either::B(buffer.as_ref().to_vec()),
+	};
+	let settings = options.map_or_else(AtomicWrite::default, |opts| AtomicWrite {
+		fsync:          opts.fsync.unwrap_or(true),
+		mode:           opts.mode,
+		preserve_owner: opts.preserve_owner.unwrap_or(false),
+		create_dirs:    opts.create_dirs.unwrap_or(false),
+	});
+	task::blocking("write.atomic", (), move |_| {
+		let path = file_filter::resolve_search_path(&path)?;
+		let bytes = match data {
+			Either::A(text) if preserve_eol => match fs::read_to_string(&path) {
+				Ok(existing) => eol::preserve_eol(&existing, &text)
+					.into_owned()
+					.into_bytes(),
+				Err(_) => text.into_bytes(),
+			},
+			Either::A(text) => text.into_bytes(),
+			Either::B(bytes) => bytes,
+		};
+		write_atomic(&path, &bytes, &settings)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::test_util::{ScratchDir, dir_names};
+
+	#[test]
+	fn test_write_replaces_without_leaving_temp_files() {
+		let root = ScratchDir::new("write-replace");
+		let path = root.join("a.txt");
+		fs::write(&path, "old").unwrap();
+		write_atomic(&path, b"new", &AtomicWrite::default()).unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+		assert_eq!(dir_names(&root), ["a.txt"]);
+	}
+
+	#[test]
+	fn test_write_creates_missing_parents_only_on_request() {
+		let root = ScratchDir::new("write-parents");
+		let path = root.join("a/b/c.txt");
+		assert!(write_atomic(&path, b"x", &AtomicWrite::default()).is_err());
+		assert_eq!(dir_names(&root), Vec::<String>::new());
+
+		let settings = AtomicWrite { create_dirs: true, ..AtomicWrite::default() };
+		write_atomic(&path, b"x", &settings).unwrap();
+		assert_eq!(fs::read_to_string(&path).unwrap(), "x");
+	}
+
+	#[test]
+	fn test_write_refuses_directories() {
+		let root = ScratchDir::new("write-dir");
+		fs::create_dir(root.join("d")).unwrap();
+		let err = write_atomic(&root.join("d"), b"x", &AtomicWrite::default()).unwrap_err();
+		assert!(err.reason.ends_with("is a directory"), "{}", err.reason);
+		assert!(root.join("d").is_dir());
+		assert_eq!(dir_names(&root), ["d"]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_write_keeps_mode_and_symlinks() {
+		use std::os::unix::fs::PermissionsExt;
+
+		let root = ScratchDir::new("write-unix");
+		let target = root.join("run.sh");
+		fs::write(&target, "old").unwrap();
+		fs::set_permissions(&target, fs::Permissions::from_mode(0o750)).unwrap();
+		std::os::unix::fs::symlink("run.sh", root.join("link")).unwrap();
+
+		write_atomic(&root.join("link"), b"new", &AtomicWrite::default()).unwrap();
+		assert!(
+			fs::symlink_metadata(root.join("link"))
+				.unwrap()
+				.file_type()
+				.is_symlink()
+		);
+		assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+		assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o7777, 0o750);
+
+		let settings = AtomicWrite { mode: Some(0o600), ..AtomicWrite::default() };
+		write_atomic(&target, b"newer", &settings).unwrap();
+		assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o7777, 0o600);
+		assert_eq!(dir_names(&root), ["link", "run.sh"]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn test_write_through_dangling_symlink_creates_target() {
+		let root = ScratchDir::new("write-dangling");
+		fs::create_dir(root.join("sub")).unwrap();
+		std::os::unix::fs::symlink("sub/next", root.join("link")).unwrap();
+		std::os::unix::fs::symlink("missing.txt", root.join("sub/next")).unwrap();
+
+		write_atomic(&root.join("link"), b"new", &AtomicWrite::default()).unwrap();
+		assert!(
+			fs::symlink_metadata(root.join("link"))
+				.unwrap()
+				.file_type()
+				.is_symlink()
+		);
+		assert_eq!(fs::read_to_string(root.join("sub/missing.txt")).unwrap(), "new");
+		assert_eq!(dir_names(&root.join("sub")), ["missing.txt", "next"]);
+	}
+}
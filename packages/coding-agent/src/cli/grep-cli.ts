@@ -87,7 +87,10 @@ export async function runGrepCommand(cmd: GrepCommandArgs): Promise<void> {
 			hidden: true,
 		});
 
-		console.log(chalk.green(`Total matches: ${result.totalMatches}`));
+		// filesWithMatches stops at each file's first match, so its total is only a file count.
+		if (cmd.mode !== "filesWithMatches") {
+			console.log(chalk.green(`Total matches: ${result.totalMatches}`));
+		}
 		console.log(chalk.green(`Files with matches: ${result.filesWithMatches}`));
 		console.log(chalk.green(`Files searched: ${result.filesSearched}`));
 		if (result.limitReached) {
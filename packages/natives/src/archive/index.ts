@@ -0,0 +1,9 @@
+/**
+ * Archive listing and extraction for zip and tar (plain, gzip, zstd).
+ */
+
+import { native } from "../native";
+
+export type { ArchiveEntry, ExtractArchiveOptions, ExtractArchiveResult, ListArchiveOptions } from "./types";
+
+export const { extractArchive, listArchive } = native;
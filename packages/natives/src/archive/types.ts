@@ -0,0 +1,62 @@
+/**
+ * Types for archive listing and extraction.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Options for listing an archive. */
+export interface ListArchiveOptions extends Cancellable {}
+
+/** An entry of an archive. */
+export interface ArchiveEntry {
+	/** Path inside the archive, with forward slashes. */
+	path: string;
+	/** Entry kind. */
+	kind: "file" | "dir" | "symlink" | "hardlink" | "other";
+	/** Uncompressed size in bytes. */
+	size: number;
+	/** Unix permission bits, when recorded. */
+	mode?: number;
+	/** Link target for symlinks and hard links. */
+	linkTarget?: string;
+}
+
+/** Options for extracting an archive. */
+export interface ExtractArchiveOptions extends Cancellable {
+	/**
+	 * Globs selecting entries to extract, matched against paths after `stripComponents` (default: all).
+	 * Patterns without a `/` match names at any depth.
+	 */
+	include?: string[];
+	/** Leading path components to drop from each entry (default: 0). */
+	stripComponents?: number;
+}
+
+/** Result of extracting an archive. */
+export interface ExtractArchiveResult {
+	/** Files, symlinks, and hard links written. */
+	files: number;
+	/** Bytes of file content written. */
+	bytes: number;
+	/** Archive paths skipped because they would escape the destination. */
+	skipped: string[];
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * List the entries of a zip or tar archive (plain, gzip, or zstd; detected from the content).
+		 * @param path Archive file.
+		 * @param options Cancellation.
+		 */
+		listArchive(path: string, options?: ListArchiveOptions): Promise<ArchiveEntry[]>;
+		/**
+		 * Extract a zip or tar archive (plain, gzip, or zstd) into `dest`.
+		 * Entries that would escape `dest` (absolute paths, `..`, symlinks pointing outside) are skipped and reported.
+		 * @param path Archive file.
+		 * @param dest Destination directory, created if missing.
+		 * @param options Entry filter, leading components to strip, cancellation.
+		 */
+		extractArchive(path: string, dest: string, options?: ExtractArchiveOptions): Promise<ExtractArchiveResult>;
+	}
+}
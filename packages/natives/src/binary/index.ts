@@ -0,0 +1,9 @@
+/**
+ * Binary content detection and hexdump rendering.
+ */
+
+import { native } from "../native";
+
+export type { HexdumpOptions } from "./types";
+
+export const { isBinary, hexdump } = native;
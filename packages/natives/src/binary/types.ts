@@ -0,0 +1,33 @@
+/**
+ * Types for binary detection and hexdumps.
+ */
+
+/** Options for rendering a hexdump. */
+export interface HexdumpOptions {
+	/** First byte to render (default: 0). */
+	offset?: number;
+	/** Number of bytes to render (default: rest of the buffer). */
+	length?: number;
+	/** Bytes per line (default: 16). */
+	width?: number;
+	/** Color bytes by class with ANSI escapes (default: true). */
+	color?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Check whether a file or buffer holds binary content (NUL bytes or dense control characters).
+		 * @param input File path, or the content itself.
+		 * @returns True for binary content.
+		 */
+		isBinary(input: string | Uint8Array): boolean;
+		/**
+		 * Render a byte range as hexdump lines with offset, hex, and ASCII columns.
+		 * @param buffer Bytes to render.
+		 * @param options Range, line width, and coloring.
+		 * @returns One line per `width` bytes.
+		 */
+		hexdump(buffer: Uint8Array, options?: HexdumpOptions): string[];
+	}
+}
@@ -0,0 +1,13 @@
+/**
+ * Content-addressed disk cache with TTLs and size-bounded LRU eviction.
+ */
+
+import { native } from "../native";
+
+export type { CacheEvictResult, CachePutOptions, CacheStoreConstructor, CacheStoreOptions } from "./types";
+
+/** CacheStore class. Use CacheStore.open() to create instances. */
+export const CacheStore = native.CacheStore;
+
+/** CacheStore instance type. */
+export type CacheStore = import("./types").CacheStore;
@@ -0,0 +1,61 @@
+/**
+ * Types for the content-addressed disk cache.
+ */
+
+/** Options for opening a cache store. */
+export interface CacheStoreOptions {
+	/** Size bound for stored blobs in bytes (default: 256 MiB). */
+	maxBytes?: number;
+}
+
+/** Options for storing a value. */
+export interface CachePutOptions {
+	/** Time to live in milliseconds (default: no expiry). */
+	ttl?: number;
+}
+
+/** Result of evicting entries. */
+export interface CacheEvictResult {
+	/** Entries removed (expired or over the size bound). */
+	removed: number;
+	/** Bytes of blob storage freed. */
+	freedBytes: number;
+}
+
+/** Content-addressed blob cache persisted in a directory. */
+export interface CacheStore {
+	/** Number of entries, including expired ones not yet evicted. */
+	get count(): number;
+	/** Bytes of blob storage in use. */
+	get size(): number;
+	/**
+	 * Store `data` under `key`, replacing any previous value.
+	 * Evicts other least recently used entries if the store exceeds `maxBytes`; rejects values larger than `maxBytes`.
+	 * @returns The BLAKE3 digest the blob is stored under.
+	 */
+	put(key: string, data: string | Uint8Array, options?: CachePutOptions): Promise<string>;
+	/** Read the value stored under `key`; `null` when missing or expired. */
+	get(key: string): Promise<Uint8Array | null>;
+	/** Remove the value stored under `key`; resolves to whether it was present. */
+	delete(key: string): Promise<boolean>;
+	/**
+	 * Drop expired entries and least recently used entries over `maxBytes`,
+	 * delete orphaned blobs, and compact the journal.
+	 */
+	evict(): Promise<CacheEvictResult>;
+}
+
+/** Static entrypoints for opening `CacheStore` instances. */
+export interface CacheStoreConstructor {
+	/** Open (or create) a cache in `dir`, replaying its journal. */
+	open(dir: string, options?: CacheStoreOptions): Promise<CacheStore>;
+	/** Instance prototype reference. */
+	prototype: CacheStore;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Content-addressed disk cache constructor exposed by the native module. */
+		CacheStore: CacheStoreConstructor;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Sparkline and bar chart rendering with block characters.
+ */
+
+import { native } from "../native";
+
+export type { BarsOptions, SparklineOptions } from "./types";
+
+export const { renderSparkline, renderBars } = native;
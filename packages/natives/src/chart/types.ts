@@ -0,0 +1,44 @@
+/**
+ * Types for mini-chart rendering.
+ */
+
+/** Options for rendering a sparkline. */
+export interface SparklineOptions {
+	/** Maximum number of characters; longer series are averaged into buckets. */
+	width?: number;
+	/** Value mapped to the lowest block (default: series minimum). */
+	min?: number;
+	/** Value mapped to the full block (default: series maximum). */
+	max?: number;
+	/** SGR parameters applied to the line (e.g. "36" or "38;5;208"). */
+	color?: string;
+}
+
+/** Options for rendering bar charts. */
+export interface BarsOptions {
+	/** SGR parameters applied to the bars (e.g. "32"). */
+	color?: string;
+	/** Omit the value after each bar (default: false). */
+	hideValues?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Render a numeric series as a one-line sparkline (▁▂▃▄▅▆▇█).
+		 * @param values Series to plot; non-finite values render as gaps.
+		 * @param options Width limit, value range, and color.
+		 * @returns The sparkline.
+		 */
+		renderSparkline(values: number[], options?: SparklineOptions): string;
+		/**
+		 * Render labelled horizontal bars with eighth-block precision.
+		 * @param values Bar values; negative and non-finite values render empty.
+		 * @param labels Label for each bar, padded to a common width.
+		 * @param width Total line width in columns.
+		 * @param options Bar color and value display.
+		 * @returns One line per value.
+		 */
+		renderBars(values: number[], labels: string[], width: number, options?: BarsOptions): string[];
+	}
+}
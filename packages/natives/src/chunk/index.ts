@@ -0,0 +1,9 @@
+/**
+ * Token-aware file chunking for context building.
+ */
+
+import { native } from "../native";
+
+export type { ChunkFileOptions, ChunkLanguage, FileChunk } from "./types";
+
+export const { chunkFile } = native;
@@ -0,0 +1,51 @@
+/**
+ * Types for token-aware file chunking.
+ */
+
+import type { Cancellable } from "../bindings";
+import type { SymbolLanguage } from "../symbols/types";
+import type { TokenEncoding } from "../tokens/types";
+
+/** Structure used to find split points. */
+export type ChunkLanguage = SymbolLanguage | "markdown" | "text";
+
+/** Options for chunking a file. */
+export interface ChunkFileOptions extends Cancellable {
+	/** Maximum tokens per chunk (default: 512). */
+	maxTokens?: number;
+	/** Tokens of trailing context repeated at the start of the next chunk (default: 0). Rounded down to whole lines. */
+	overlap?: number;
+	/** Prefer definition, heading, and paragraph boundaries (default: true); when false, pack chunks line by line. */
+	respectBoundaries?: boolean;
+	/** Tokenizer encoding (default: "o200k_base"). */
+	encoding?: TokenEncoding;
+	/** Structure to split on; inferred from the extension when omitted. */
+	language?: ChunkLanguage;
+}
+
+/** A contiguous, line-aligned slice of a file. */
+export interface FileChunk {
+	/** Chunk text. */
+	text: string;
+	/** First line (1-based). */
+	startLine: number;
+	/** Last line (1-based, inclusive). */
+	endLine: number;
+	/** Start byte offset in the file (UTF-8). */
+	startByte: number;
+	/** End byte offset in the file (UTF-8, exclusive). */
+	endByte: number;
+	/** Number of BPE tokens in `text`. */
+	tokens: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Split a file into token-bounded chunks, preferring definition (tree-sitter) and heading boundaries.
+		 * @param path File to chunk.
+		 * @returns Chunks in file order; without overlap they tile the file exactly.
+		 */
+		chunkFile(path: string, options?: ChunkFileOptions): Promise<FileChunk[]>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * zstd and gzip compression for buffers and files.
+ */
+
+import { native } from "../native";
+
+export type { CompressFileResult, CompressionAlgorithm, CompressOptions } from "./types";
+
+export const { compress, compressFile, decompress, decompressFile } = native;
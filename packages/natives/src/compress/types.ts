@@ -0,0 +1,50 @@
+/**
+ * Types for zstd and gzip compression.
+ */
+
+/** Compression format. */
+export type CompressionAlgorithm = "zstd" | "gzip";
+
+/** Options for compressing buffers and files. */
+export interface CompressOptions {
+	/** Format (default: "zstd"). */
+	algo?: CompressionAlgorithm;
+	/** Compression level: 1-22 for zstd (default 3), 0-9 for gzip (default 6). */
+	level?: number;
+}
+
+/** Result of compressing or decompressing a file. */
+export interface CompressFileResult {
+	/** Bytes read from the source file. */
+	bytesIn: number;
+	/** Bytes written to the destination file. */
+	bytesOut: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Compress a buffer or string (as UTF-8) on the worker pool.
+		 * @param data Bytes or text to compress.
+		 * @param options Format and level.
+		 * @returns The compressed bytes.
+		 */
+		compress(data: string | Uint8Array, options?: CompressOptions): Promise<Uint8Array>;
+		/**
+		 * Decompress zstd or gzip data on the worker pool; the format is detected from the magic bytes.
+		 * @param data Compressed bytes; concatenated frames are decoded in sequence.
+		 * @returns The decompressed bytes.
+		 */
+		decompress(data: Uint8Array): Promise<Uint8Array>;
+		/**
+		 * Compress `src` into `dst`, streaming on the worker pool.
+		 * The output is written to a temp file and renamed into place.
+		 */
+		compressFile(src: string, dst: string, options?: CompressOptions): Promise<CompressFileResult>;
+		/**
+		 * Decompress `src` (zstd or gzip, detected) into `dst`, streaming on the worker pool.
+		 * The output is written to a temp file and renamed into place.
+		 */
+		decompressFile(src: string, dst: string): Promise<CompressFileResult>;
+	}
+}
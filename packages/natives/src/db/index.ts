@@ -0,0 +1,23 @@
+/**
+ * Embedded SQLite storage with migrations.
+ */
+
+import { native } from "../native";
+
+export type {
+	DatabaseConstructor,
+	DbExecResult,
+	DbOpenOptions,
+	DbQueryResult,
+	DbStatement,
+	SqlParam,
+	SqlValue,
+} from "./types";
+
+/** Database class. Use openDb() to create instances. */
+export const Database = native.Database;
+
+/** Database instance type. */
+export type Database = import("./types").Database;
+
+export const { openDb } = native;
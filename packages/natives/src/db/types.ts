@@ -0,0 +1,79 @@
+/**
+ * Types for the embedded SQLite store.
+ */
+
+/** A value read from a column. Integers beyond 2^53 lose precision. */
+export type SqlValue = number | string | Uint8Array | null;
+
+/** A value bound to a `?` parameter; whole numbers bind as integers and booleans as 0/1. */
+export type SqlParam = number | string | Uint8Array | boolean | null;
+
+/** Options for opening a database. */
+export interface DbOpenOptions {
+	/** Open without write access; the file must exist (default: false). */
+	readonly?: boolean;
+	/** Milliseconds to wait on a locked database before failing (default: 5000). */
+	busyTimeoutMs?: number;
+	/** Schema migrations to apply on open; see `Database.migrate()`. */
+	migrations?: string[];
+}
+
+/** One statement of a transaction batch. */
+export interface DbStatement {
+	/** SQL text of a single statement. */
+	sql: string;
+	/** Values for the statement's `?` parameters. */
+	params?: SqlParam[];
+}
+
+/** Outcome of a write. */
+export interface DbExecResult {
+	/** Rows inserted, updated, or deleted. */
+	changes: number;
+	/** Rowid of the most recent successful insert on this connection. */
+	lastInsertRowid: number;
+}
+
+/** Rows returned by a query. */
+export interface DbQueryResult {
+	/** Column names in select order. */
+	columns: string[];
+	/** Row values, one entry per column. */
+	rows: SqlValue[][];
+}
+
+/** Handle to an open SQLite database (WAL mode, foreign keys on). */
+export interface Database {
+	/** Path the database was opened with. */
+	get path(): string;
+	/** Run SQL that returns no rows. Without `params`, `sql` may hold several `;`-separated statements. */
+	exec(sql: string, params?: SqlParam[]): Promise<DbExecResult>;
+	/** Run a single statement and collect every row it returns. */
+	query(sql: string, params?: SqlParam[]): Promise<DbQueryResult>;
+	/** Run `statements` in one transaction; if any fails, none take effect. */
+	transaction(statements: DbStatement[]): Promise<DbExecResult>;
+	/**
+	 * Apply the migrations past the database's `PRAGMA user_version`.
+	 * Migration `i` upgrades version `i` to `i + 1`, each in its own transaction.
+	 * @returns The resulting schema version.
+	 */
+	migrate(migrations: string[]): Promise<number>;
+	/** Close the database. Later calls fail; closing twice is a no-op. */
+	close(): Promise<void>;
+}
+
+export interface DatabaseConstructor {
+	prototype: Database;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** SQLite database handle class; create instances with `openDb()`. */
+		Database: DatabaseConstructor;
+		/**
+		 * Open (or create) a SQLite database and apply `migrations`.
+		 * @param path Database file; parent directories are created. `":memory:"` opens an in-memory database.
+		 */
+		openDb(path: string, options?: DbOpenOptions): Promise<Database>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Styled diff rendering combining line diffs, syntax highlighting, and width-aware layout.
+ */
+
+import { native } from "../native";
+
+export type { RenderDiffOptions, RenderedDiffRow, RenderedDiffRowKind } from "./types";
+
+export const { renderDiff } = native;
@@ -0,0 +1,65 @@
+/**
+ * Types for styled diff rendering.
+ */
+
+import type { DiffHunk } from "../diff/types";
+import type { HighlightColors } from "../highlight/types";
+
+/** Options for rendering diff hunks. SGR styles are raw escape sequences such as "\x1b[48;5;22m". */
+export interface RenderDiffOptions {
+	/** Width of every rendered row in terminal cells. */
+	width: number;
+	/** Language for syntax highlighting, e.g. "ts" or "rust" (default: none). */
+	syntaxLanguage?: string;
+	/** Show old and new line numbers in a gutter (default: true). */
+	lineNumbers?: boolean;
+	/** Wrap long lines onto continuation rows instead of truncating them (default: false). */
+	wrap?: boolean;
+	/** Emit a `@@ -a,b +c,d @@` row before each hunk (default: true). */
+	hunkHeaders?: boolean;
+	/** Emphasize changed words within replaced line pairs (default: true). */
+	refine?: boolean;
+	/** Syntax colors (default: content is not syntax highlighted). */
+	colors?: HighlightColors;
+	/** SGR style for added rows, e.g. a background (default: green text). */
+	addedStyle?: string;
+	/** SGR style for removed rows (default: red text). */
+	removedStyle?: string;
+	/** SGR style for context rows (default: none). */
+	contextStyle?: string;
+	/** SGR style added to changed words in added rows (default: bold). */
+	addedEmphasis?: string;
+	/** SGR style added to changed words in removed rows (default: bold). */
+	removedEmphasis?: string;
+	/** SGR style for the line-number gutter and hunk headers (default: dim). */
+	gutterStyle?: string;
+}
+
+/** Kinds of rendered diff rows. */
+export type RenderedDiffRowKind = "header" | "context" | "added" | "removed";
+
+/** One rendered diff row. */
+export interface RenderedDiffRow {
+	/** Styled row text, exactly `width` cells wide. */
+	text: string;
+	/** Whether the row is a hunk header or a context, added, or removed line. */
+	kind: RenderedDiffRowKind;
+	/** Line number in the old text (unset for headers and added lines). */
+	oldLine?: number;
+	/** Line number in the new text (unset for headers and removed lines). */
+	newLine?: number;
+	/** Whether the row continues a wrapped line. */
+	continuation: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Render diff hunks as syntax-highlighted, width-fitted terminal rows.
+		 * @param hunks Hunks from `diffLines` or `gitDiff`.
+		 * @param options Width, highlighting, gutter, wrapping, and row styles.
+		 * @returns Rows in display order, each exactly `width` cells wide.
+		 */
+		renderDiff(hunks: DiffHunk[], options: RenderDiffOptions): RenderedDiffRow[];
+	}
+}
@@ -0,0 +1,19 @@
+/**
+ * Line diffing powered by imara-diff.
+ */
+
+import { native } from "../native";
+
+export type {
+	ChangedRange,
+	DiffHunk,
+	DiffLine,
+	DiffOptions,
+	DiffResult,
+	LineRefinement,
+	RefineOptions,
+	WordDiffPart,
+} from "./types";
+export { DiffLineKind } from "./types";
+
+export const { diffLines, diffWords, refineHunk } = native;
@@ -0,0 +1,118 @@
+/**
+ * Types for native line diffing.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Kind of a single line inside a diff hunk. */
+export const enum DiffLineKind {
+	/** Line present on both sides. */
+	Context = 0,
+	/** Line only present in the new text. */
+	Added = 1,
+	/** Line only present in the old text. */
+	Removed = 2,
+}
+
+/** Options for computing a line diff. */
+export interface DiffOptions extends Cancellable {
+	/** Diff algorithm (default: "histogram"). */
+	algorithm?: "histogram" | "myers" | "myersMinimal";
+	/** Number of unchanged context lines around each change (default: 3). */
+	context?: number;
+}
+
+/** A single line of a diff hunk. */
+export interface DiffLine {
+	/** Whether the line is context, added, or removed. */
+	kind: DiffLineKind;
+	/** Line content without its line terminator. */
+	content: string;
+	/** 1-indexed line number in the old text (absent for added lines). */
+	oldLine?: number;
+	/** 1-indexed line number in the new text (absent for removed lines). */
+	newLine?: number;
+}
+
+/** A group of nearby changes with surrounding context. */
+export interface DiffHunk {
+	/** 1-indexed start line in the old text (line before the hunk when `oldLines` is 0). */
+	oldStart: number;
+	/** Number of old-text lines covered by the hunk. */
+	oldLines: number;
+	/** 1-indexed start line in the new text (line before the hunk when `newLines` is 0). */
+	newStart: number;
+	/** Number of new-text lines covered by the hunk. */
+	newLines: number;
+	/** Lines of the hunk in display order. */
+	lines: DiffLine[];
+}
+
+/** Result of a line diff. */
+export interface DiffResult {
+	/** Hunks in ascending line order (empty when the texts are identical). */
+	hunks: DiffHunk[];
+	/** Total number of added lines. */
+	added: number;
+	/** Total number of removed lines. */
+	removed: number;
+}
+
+/** Options for refining replaced line pairs. */
+export interface RefineOptions {
+	/** Token granularity (default: "word"). */
+	granularity?: "word" | "char";
+}
+
+/** A changed span inside a line, in UTF-16 code units. */
+export interface ChangedRange {
+	/** Start offset (inclusive). */
+	start: number;
+	/** End offset (exclusive). */
+	end: number;
+}
+
+/** Intra-line changes for one replaced line pair. */
+export interface LineRefinement {
+	/** Changed spans in the old line. */
+	oldRanges: ChangedRange[];
+	/** Changed spans in the new line. */
+	newRanges: ChangedRange[];
+	/** Fraction of tokens shared by both lines (0 = disjoint, 1 = identical). */
+	similarity: number;
+}
+
+/** A run of text in a word diff. */
+export interface WordDiffPart {
+	/** Whether the text is shared, added, or removed. */
+	kind: DiffLineKind;
+	/** The text of the run. */
+	text: string;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Diff two texts line by line and group the changes into hunks.
+		 * @param oldText Original text.
+		 * @param newText Updated text.
+		 * @param options Algorithm, context-line, and cancellation settings.
+		 */
+		diffLines(oldText: string, newText: string, options?: DiffOptions): Promise<DiffResult>;
+		/**
+		 * Compute word- or character-level changed ranges for replaced line pairs.
+		 * Lines are paired by index; unpaired extra lines are not refined.
+		 * @param oldLines Removed lines of a hunk.
+		 * @param newLines Added lines of a hunk.
+		 * @param options Token granularity.
+		 */
+		refineHunk(oldLines: string[], newLines: string[], options?: RefineOptions): LineRefinement[];
+		/**
+		 * Diff two short texts word by word, using the word tokens of `refineHunk`.
+		 * @param oldText Original text.
+		 * @param newText Updated text.
+		 * @returns Shared, removed, and added runs covering both texts in order.
+		 */
+		diffWords(oldText: string, newText: string): WordDiffPart[];
+	}
+}
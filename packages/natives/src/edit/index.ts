@@ -0,0 +1,9 @@
+/**
+ * Validated string-replacement file edits.
+ */
+
+import { native } from "../native";
+
+export type { ApplyEditOptions, ApplyEditResult, ApplyEditStatus, EditCandidate } from "./types";
+
+export const { applyEdit } = native;
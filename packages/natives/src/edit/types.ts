@@ -0,0 +1,67 @@
+/**
+ * Types for validated string-replacement file edits.
+ */
+
+import type { DiffHunk } from "../diff/types";
+
+/** Options for editing a file. */
+export interface ApplyEditOptions {
+	/** Exact text to replace. Line endings are matched to the file's. */
+	oldText: string;
+	/** Replacement text. */
+	newText: string;
+	/** Replace only this match, 1-indexed (default: `oldText` must be unique). */
+	occurrence?: number;
+	/** Replace every match (default: false). */
+	replaceAll?: boolean;
+	/** Compute the result without writing the file (default: false). */
+	dryRun?: boolean;
+	/** Unchanged context lines around each hunk (default: 3). */
+	context?: number;
+}
+
+/** A region of the file resembling `oldText`. */
+export interface EditCandidate {
+	/** First line of the region, 1-indexed. */
+	startLine: number;
+	/** Last line of the region, 1-indexed and inclusive. */
+	endLine: number;
+	/** Text of the region. */
+	text: string;
+	/** Similarity to `oldText`, from 0 to 1. */
+	similarity: number;
+}
+
+/** Outcome of an edit; "notFound" covers both a missing text and an out-of-range `occurrence`. */
+export type ApplyEditStatus = "applied" | "dryRun" | "notFound" | "ambiguous";
+
+/** Result of editing a file. */
+export interface ApplyEditResult {
+	/** Outcome; the file is only written when "applied". */
+	status: ApplyEditStatus;
+	/** Number of matches of `oldText` in the file. */
+	matches: number;
+	/** Number of matches replaced (or that would be, for dry runs). */
+	replacements: number;
+	/** Line hunks describing the change; empty unless applied or dry run. */
+	hunks: DiffHunk[];
+	/** Number of added lines. */
+	added: number;
+	/** Number of removed lines. */
+	removed: number;
+	/** Closest regions when `oldText` was not found, best first. */
+	candidates: EditCandidate[];
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Replace exact text in a file after verifying it matches, writing the file atomically.
+		 * A missing or ambiguous match leaves the file untouched; a missing one reports the closest regions.
+		 * @param path File to edit.
+		 * @param options Text to replace, replacement, and match selection.
+		 * @returns The outcome with line hunks.
+		 */
+		applyEdit(path: string, options: ApplyEditOptions): Promise<ApplyEditResult>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Text encoding detection and transcoding.
+ */
+
+import { native } from "../native";
+
+export type { DetectedEncoding, FileText, ReadFileTextOptions, TextEncoding } from "./types";
+
+export const { detectEncoding, readFileText } = native;
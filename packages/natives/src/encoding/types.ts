@@ -0,0 +1,50 @@
+/**
+ * Types for text encoding detection.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Text encodings recognized by the native detector. */
+export type TextEncoding = "utf-8" | "utf-16le" | "utf-16be" | "latin1";
+
+/** Detected encoding of a byte buffer. */
+export interface DetectedEncoding {
+	/** Detected encoding. */
+	encoding: TextEncoding;
+	/** Whether the content starts with a byte-order mark. */
+	bom: boolean;
+}
+
+/** Options for reading a file as text. */
+export interface ReadFileTextOptions extends Cancellable {
+	/** Convert detected non-UTF-8 content to UTF-8 (default: true). */
+	transcode?: boolean;
+}
+
+/** Decoded file contents. */
+export interface FileText {
+	/** File contents as UTF-8, without a BOM. */
+	text: string;
+	/** Encoding detected on disk. */
+	encoding: TextEncoding;
+	/** Whether the file started with a byte-order mark. */
+	bom: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect UTF-8, UTF-16LE/BE, or Latin-1 content from its BOM or byte distribution.
+		 * @param buffer Bytes to inspect.
+		 * @returns Detected encoding and whether a BOM is present.
+		 */
+		detectEncoding(buffer: Uint8Array): DetectedEncoding;
+		/**
+		 * Read a file as text, converting it to UTF-8 and reporting the original encoding.
+		 * @param path File to read.
+		 * @param options Transcoding and cancellation options.
+		 * @returns Decoded text with the detected encoding.
+		 */
+		readFileText(path: string, options?: ReadFileTextOptions): Promise<FileText>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Environment snapshots, diffs, and shell export parsing.
+ */
+
+import { native } from "../native";
+
+export type { DiffEnvOptions, EnvChange, EnvDiff, EnvVar } from "./types";
+
+export const { snapshotEnv, diffEnv, parseShellExports } = native;
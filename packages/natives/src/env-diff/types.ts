@@ -0,0 +1,62 @@
+/**
+ * Types for environment snapshots and diffs.
+ */
+
+/** Options for diffing environments. */
+export interface DiffEnvOptions {
+	/** Variable names to leave out of the diff (default: `_` and `SHLVL`). */
+	ignore?: string[];
+}
+
+/** A variable present only in the later environment. */
+export interface EnvVar {
+	/** Variable name. */
+	name: string;
+	/** Value in the later environment. */
+	value: string;
+}
+
+/** A variable whose value differs between the two environments. */
+export interface EnvChange {
+	/** Variable name. */
+	name: string;
+	/** Value in the earlier environment. */
+	before: string;
+	/** Value in the later environment. */
+	after: string;
+}
+
+/** Differences between two environments, each list sorted by name. */
+export interface EnvDiff {
+	/** Variables set only in `after`. */
+	added: EnvVar[];
+	/** Variables set in both with different values. */
+	changed: EnvChange[];
+	/** Names of variables set only in `before`. */
+	removed: string[];
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Capture the environment of the current process.
+		 * @returns Variable names mapped to values.
+		 */
+		snapshotEnv(): Record<string, string>;
+		/**
+		 * Compare two environments.
+		 * @param before Earlier environment, e.g. from `snapshotEnv`.
+		 * @param after Later environment, e.g. from `parseShellExports`.
+		 * @param options Names to ignore.
+		 * @returns Added, changed, and removed variables.
+		 */
+		diffEnv(before: Record<string, string>, after: Record<string, string>, options?: DiffEnvOptions): EnvDiff;
+		/**
+		 * Parse exported variables from `export -p` output (bash, dash, zsh) or `NAME=value` lines.
+		 * @param text Shell output; later assignments to a name win.
+		 * @returns Exported variable names mapped to their unquoted values.
+		 * @throws On unterminated quotes.
+		 */
+		parseShellExports(text: string): Record<string, string>;
+	}
+}
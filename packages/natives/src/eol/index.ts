@@ -0,0 +1,9 @@
+/**
+ * Line-ending detection and normalization.
+ */
+
+import { native } from "../native";
+
+export type { EolInfo, LineEnding } from "./types";
+
+export const { detectEol, normalizeEol } = native;
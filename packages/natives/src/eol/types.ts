@@ -0,0 +1,38 @@
+/**
+ * Types for line-ending helpers.
+ */
+
+/** Line terminator kinds. */
+export type LineEnding = "lf" | "crlf" | "cr";
+
+/** Line-ending statistics for a text. */
+export interface EolInfo {
+	/** Dominant line ending ("lf" when the text has none). */
+	eol: LineEnding;
+	/** Number of LF terminators not preceded by CR. */
+	lf: number;
+	/** Number of CRLF terminators. */
+	crlf: number;
+	/** Number of CR terminators not followed by LF. */
+	cr: number;
+	/** Whether more than one kind of terminator occurs. */
+	mixed: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect the dominant line ending of a text.
+		 * @param text Text to inspect.
+		 * @returns Dominant ending and per-kind counts.
+		 */
+		detectEol(text: string): EolInfo;
+		/**
+		 * Rewrite every line ending in a text to one kind.
+		 * @param text Text to normalize.
+		 * @param target Line ending to use.
+		 * @returns The normalized text.
+		 */
+		normalizeEol(text: string, target: LineEnding): string;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Upward config file discovery.
+ */
+
+import { native } from "../native";
+
+export type { FindUpOptions } from "./types";
+
+export const { findUp } = native;
@@ -0,0 +1,27 @@
+/**
+ * Types for upward config file discovery.
+ */
+
+/** Options for finding files in a directory and its ancestors. */
+export interface FindUpOptions {
+	/** Names to look for in each directory, in priority order; may contain separators (e.g. ".pi/config.json"). */
+	names: string[];
+	/** Starting directory, or a file whose directory is used (default: process cwd). */
+	from?: string;
+	/** Last directory to search (default: filesystem root). */
+	stopAt?: string;
+	/** Return every hit instead of only the nearest (default: false). */
+	multiple?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Find config files by walking from a directory up through its ancestors.
+		 * The walk follows the path as written, so symlinked project paths find the configs around the link.
+		 * @param options Names to look for, start and stop directories, and whether to collect every hit.
+		 * @returns Absolute paths, nearest directory first and in `names` order within a directory.
+		 */
+		findUp(options: FindUpOptions): string[];
+	}
+}
@@ -0,0 +1,15 @@
+/**
+ * Fuzzy scoring for command palettes and did-you-mean suggestions.
+ */
+
+import { native } from "../native";
+
+export type {
+	FuzzyScoreMatch,
+	FuzzyScoreOptions,
+	FuzzySpan,
+	SuggestOptions,
+	Suggestion,
+} from "./types";
+
+export const { fuzzyScore, suggest } = native;
@@ -0,0 +1,66 @@
+/**
+ * Types for native fuzzy scoring and suggestions.
+ */
+
+/** Options for fuzzy scoring. */
+export interface FuzzyScoreOptions {
+	/** Maximum number of results to return (default: all matches). */
+	limit?: number;
+	/** Also report the matched characters merged into highlight spans (default: false). */
+	spans?: boolean;
+}
+
+/** A scored candidate. */
+export interface FuzzyScoreMatch {
+	/** Index of the candidate in the input array. */
+	index: number;
+	/** Match quality score (higher is better). */
+	score: number;
+	/** UTF-16 offsets of the matched characters in the candidate. */
+	positions: number[];
+	/** Matched character runs in the candidate, in order; only set when `spans` is requested. */
+	spans?: FuzzySpan[];
+}
+
+/** A run of consecutive matched characters. */
+export interface FuzzySpan {
+	/** UTF-16 offset of the first matched character. */
+	start: number;
+	/** UTF-16 offset just past the last matched character. */
+	end: number;
+}
+
+/** Options for did-you-mean suggestions. */
+export interface SuggestOptions {
+	/** Maximum edit distance to accept (default: 2, or a third of the input length for longer inputs). */
+	maxDistance?: number;
+}
+
+/** A candidate close to the input. */
+export interface Suggestion {
+	/** The candidate string. */
+	value: string;
+	/** Edit distance from the input (case-insensitive). */
+	distance: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Fuzzy-score candidates against a query (fzf-v2 style, smart-case).
+		 * @param query Query characters that must appear in order.
+		 * @param candidates Strings to rank.
+		 * @param options Result limit and whether to merge matched characters into spans.
+		 * @returns Matches sorted by score, then candidate length and input order.
+		 */
+		fuzzyScore(query: string, candidates: string[], options?: FuzzyScoreOptions): FuzzyScoreMatch[];
+		/**
+		 * Suggest candidates within a small Damerau-Levenshtein distance of the input.
+		 * @param input Misspelled command, flag, or file name.
+		 * @param candidates Known names to compare against.
+		 * @param options Distance budget.
+		 * @returns Close candidates sorted by distance, then input order.
+		 */
+		suggest(input: string, candidates: string[], options?: SuggestOptions): Suggestion[];
+	}
+}
@@ -0,0 +1,20 @@
+/**
+ * Native git queries via libgit2.
+ */
+
+import { native } from "../native";
+
+export type {
+	GitCommit,
+	GitDiffOptions,
+	GitDiffResult,
+	GitFileDiff,
+	GitFileStatus,
+	GitHunkOptions,
+	GitLogOptions,
+	GitRepoInfo,
+	GitRepoInfoOptions,
+	GitRepoState,
+} from "./types";
+
+export const { gitDiff, gitLog, gitRepoInfo, gitStageHunks, gitUnstageHunks } = native;
@@ -0,0 +1,172 @@
+/**
+ * Types for native git queries.
+ */
+
+import type { Cancellable } from "../bindings";
+import type { DiffHunk } from "../diff/types";
+
+/** Change status of a file in a git diff. */
+export type GitFileStatus = "added" | "deleted" | "modified" | "renamed" | "copied" | "typechange" | "untracked";
+
+/** Options for diffing a repository. */
+export interface GitDiffOptions extends Cancellable {
+	/** Path inside the repository (default: current directory). */
+	repoPath?: string;
+	/** Limit the diff to these pathspecs, relative to the repository root. */
+	paths?: string[];
+	/** Diff the index against HEAD instead of the working tree against the index (default: false). */
+	staged?: boolean;
+	/** Unchanged context lines around each hunk (default: 3). */
+	contextLines?: number;
+}
+
+/** Diff of a single file. */
+export interface GitFileDiff {
+	/** Path relative to the repository root (the new path for renames). */
+	path: string;
+	/** Previous path for renames and copies. */
+	oldPath?: string;
+	/** Change status. */
+	status: GitFileStatus;
+	/** Whether either side is binary (binary files have no hunks). */
+	binary: boolean;
+	/** Hunks in ascending line order. */
+	hunks: DiffHunk[];
+	/** Stable identifier of each hunk, for `gitStageHunks`/`gitUnstageHunks`. */
+	hunkIds: string[];
+	/** Number of added lines. */
+	added: number;
+	/** Number of removed lines. */
+	removed: number;
+}
+
+/** Result of a repository diff. */
+export interface GitDiffResult {
+	/** Changed files in path order. */
+	files: GitFileDiff[];
+	/** Total number of added lines. */
+	added: number;
+	/** Total number of removed lines. */
+	removed: number;
+}
+
+/** Options for listing a path's history. */
+export interface GitLogOptions extends Cancellable {
+	/** File or directory whose history to list (absolute, cwd-relative, or repository-relative). */
+	path: string;
+	/** Path inside the repository (default: the parent of `path`). */
+	repoPath?: string;
+	/** Maximum number of commits to return (default: 20). */
+	maxCount?: number;
+	/** Include each commit's diff for `path` (default: false). */
+	patch?: boolean;
+	/** Unchanged context lines around each patch hunk (default: 3). */
+	contextLines?: number;
+}
+
+/** A commit touching the requested path. */
+export interface GitCommit {
+	/** Full commit hash. */
+	hash: string;
+	/** Author name. */
+	author: string;
+	/** Author email. */
+	email: string;
+	/** Author date in milliseconds since the Unix epoch. */
+	date: number;
+	/** First line of the commit message. */
+	subject: string;
+	/** Changes to the requested path, when `patch` is set. */
+	patch?: GitFileDiff[];
+}
+
+/** In-progress repository operation. */
+export type GitRepoState =
+	| "clean"
+	| "merge"
+	| "revert"
+	| "cherryPick"
+	| "bisect"
+	| "rebase"
+	| "rebaseInteractive"
+	| "rebaseMerge"
+	| "applyMailbox";
+
+/** Repository location, branch, and state. */
+export interface GitRepoInfo {
+	/** Working tree root (the git directory for bare repositories). */
+	root: string;
+	/** Path of the git directory. */
+	gitDir: string;
+	/** Current branch name; absent when HEAD is detached. */
+	branch?: string;
+	/** Commit hash HEAD points at; absent on an unborn branch. */
+	head?: string;
+	/** Whether HEAD is detached. */
+	detached: boolean;
+	/** Upstream branch (e.g. "origin/main"), if configured. */
+	upstream?: string;
+	/** Commits on the branch that are not on its upstream. */
+	ahead: number;
+	/** Commits on the upstream that are not on the branch. */
+	behind: number;
+	/** In-progress operation. */
+	state: GitRepoState;
+	/** Whether this is a linked worktree (`git worktree add`). */
+	worktree: boolean;
+	/** Whether this repository is a submodule of another. */
+	submodule: boolean;
+	/** Whether the repository is bare. */
+	bare: boolean;
+}
+
+/** Options for describing a repository. */
+export interface GitRepoInfoOptions extends Cancellable {}
+
+/** Options for staging or unstaging hunks. */
+export interface GitHunkOptions extends Cancellable {
+	/** Path inside the repository (default: the parent of `path`). */
+	repoPath?: string;
+	/** Context lines used when the hunk ids were computed (default: 3). */
+	contextLines?: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Diff the working tree (or the index with `staged`) into structured hunks without spawning git.
+		 * @param options Repository, pathspecs, and diff mode.
+		 * @returns Per-file hunks in the same shape as `diffLines`.
+		 */
+		gitDiff(options?: GitDiffOptions): Promise<GitDiffResult>;
+		/**
+		 * List recent commits touching a file or directory, newest first.
+		 * @param options Path, commit limit, and whether to include patches.
+		 * @returns Commits with hash, author, date, subject, and optional patch.
+		 */
+		gitLog(options: GitLogOptions): Promise<GitCommit[]>;
+		/**
+		 * Describe the repository containing a path.
+		 * @param path Path inside the repository (default: current directory).
+		 * @param options Cancellation.
+		 * @returns Root, branch, upstream tracking, and state, or null outside a repository.
+		 */
+		gitRepoInfo(path?: string, options?: GitRepoInfoOptions): Promise<GitRepoInfo | null>;
+		/**
+		 * Stage individual working-tree hunks of a file.
+		 * @param path File whose hunks to stage.
+		 * @param hunkIds Ids from the file's `hunkIds` in a working-tree `gitDiff` result.
+		 * @param options Repository, the context lines the ids were computed with, and cancellation.
+		 * @returns Number of hunks staged.
+		 */
+		gitStageHunks(path: string, hunkIds: string[], options?: GitHunkOptions): Promise<number>;
+		/**
+		 * Unstage individual hunks of a file.
+		 * @param path File whose hunks to unstage.
+		 * @param hunkIds Ids from the file's `hunkIds` in a staged `gitDiff` result.
+		 * @param options Repository, the context lines the ids were computed with, and cancellation.
+		 * @returns Number of hunks unstaged.
+		 */
+		gitUnstageHunks(path: string, hunkIds: string[], options?: GitHunkOptions): Promise<number>;
+	}
+}
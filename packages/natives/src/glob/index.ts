@@ -6,19 +6,23 @@ import * as path from "node:path";
 import { native } from "../native";
 import type { GlobMatch, GlobOptions, GlobResult } from "./types";
 
-export type { GlobMatch, GlobOptions, GlobResult } from "./types";
+export type { GlobExplanation, GlobMatch, GlobOptions, GlobResult, GlobStream } from "./types";
 export { FileType } from "./types";
 
+export const { explainGlob, globStream, globToRegex } = native;
+
 /**
  * Find files matching a glob pattern.
  * Respects .gitignore by default.
  */
 export async function glob(options: GlobOptions, onMatch?: (match: GlobMatch) => void): Promise<GlobResult> {
 	const searchPath = path.resolve(options.path);
-	const pattern = options.pattern || "*";
+	// Match everything only when no other patterns are given
+	const pattern = options.pattern || (options.patterns?.length ? undefined : "*");
 
 	// Convert simple patterns to recursive globs if needed
-	const globPattern = pattern.includes("/") || pattern.startsWith("**") ? pattern : `**/${pattern}`;
+	const globPattern =
+		pattern === undefined || pattern.includes("/") || pattern.startsWith("**") ? pattern : `**/${pattern}`;
 
 	// napi-rs ThreadsafeFunction passes (error, value) - skip callback on error
 	const cb = onMatch ? (err: Error | null, m: GlobMatch) => !err && onMatch(m) : undefined;
@@ -16,7 +16,11 @@ export const enum FileType {
 /** Options for discovering files and directories. */
 export interface GlobOptions extends Cancellable {
 	/** Glob pattern to match (e.g., `*.ts`). */
-	pattern: string;
+	pattern?: string;
+	/** More patterns to match in the same walk; entries starting with `!` are exclusions. */
+	patterns?: string[];
+	/** Patterns to skip even when another pattern matches; an excluded directory excludes everything under it. */
+	exclude?: string[];
 	/** Directory to search. */
 	path: string;
 	/** Filter by file type: "file", "dir", or "symlink". */
@@ -31,6 +35,31 @@ export interface GlobOptions extends Cancellable {
 	cache?: boolean;
 	/** Sort results by mtime (most recent first) before applying limit. */
 	sortByMtime?: boolean;
+	/** Sort results by path before applying limit, so a parallel walk returns the same matches every time. */
+	sortByPath?: boolean;
+	/**
+	 * Number of walker threads; 0 picks one from the available parallelism (default: 1).
+	 * Parallel walks return matches in the order found; a single thread walks in path order. Ignored with `cache`.
+	 */
+	threads?: number;
+	/**
+	 * Walk into symlinked directories (default: false). Each directory is entered once, so cycles terminate and a
+	 * directory reached again, through a link or directly, is left out. `fileType` then also matches a link by its
+	 * target's type.
+	 * Always walks without the cache.
+	 */
+	followSymlinks?: boolean;
+	/** Deepest level to return and walk, counting the root's children as 1. */
+	maxDepth?: number;
+	/** Shallowest level to return; shallower entries are still walked. */
+	minDepth?: number;
+	/**
+	 * Called with each directory's root-relative path before walking into it; returning `false` skips the directory
+	 * and everything under it. Runs synchronously on the walk, so keep it cheap. Always walks without the cache.
+	 */
+	shouldDescend?: (path: string) => boolean;
+	/** Fill in `size`, `depth`, and `isBinary` on each match; sniffing reads the first block of every matched file. */
+	withMetadata?: boolean;
 	/** Include node_modules entries even when pattern does not mention node_modules. */
 	includeNodeModules?: boolean;
 }
@@ -43,6 +72,14 @@ export interface GlobMatch {
 	fileType: FileType;
 	/** Modification time in milliseconds since epoch, if available. */
 	mtime?: number;
+	/** File size in bytes; set for files when `withMetadata` is requested. */
+	size?: number;
+	/** Number of path components below the search root; set when `withMetadata` is requested. */
+	depth?: number;
+	/** Whether the file's first block looks binary; set for readable files when `withMetadata` is requested. */
+	isBinary?: boolean;
+	/** Type of a symlink's target; unset for other entries and dangling links. */
+	targetType?: FileType;
 }
 
 /** Result of a find operation. */
@@ -53,6 +90,38 @@ export interface GlobResult {
 	totalMatches: number;
 }
 
+/** Glob matches produced by a background walk and pulled in batches. */
+export interface GlobStream {
+	/**
+	 * Resolve with up to `batchSize` matches once at least one is available.
+	 * Resolves with an empty array when the walk is finished or cancelled, and
+	 * rejects if it was aborted or timed out.
+	 */
+	next(batchSize: number): Promise<GlobMatch[]>;
+	/** Stop walking; buffered matches can still be pulled. */
+	pause(): void;
+	/** Continue a paused walk. */
+	resume(): void;
+	/** Stop the walk and discard buffered matches. */
+	cancel(): void;
+}
+
+/** How `glob` interprets a pattern. */
+export interface GlobExplanation {
+	/** Pattern as given. */
+	input: string;
+	/** Pattern after rewriting, as matched against root-relative paths. */
+	pattern: string;
+	/** Regular expression source the pattern compiles to, usable as a JS RegExp. */
+	regex: string;
+	/** Whether backslashes were converted to `/` (Windows only). */
+	normalizedSeparators: boolean;
+	/** Whether a `**` directory prefix was prepended because the pattern has no `/`. */
+	anyDirectory: boolean;
+	/** Human-readable notes on the rewrites and matching rules that apply. */
+	notes: string[];
+}
+
 declare module "../bindings" {
 	interface NativeBindings {
 		/**
@@ -61,7 +130,25 @@ declare module "../bindings" {
 		 * @param onMatch Optional callback for streaming matches as they are found.
 		 */
 		glob(options: GlobOptions, onMatch?: TsFunc<GlobMatch>): Promise<GlobResult>;
+		/**
+		 * Start walking for glob matches in the background, buffering ahead of the caller.
+		 * @param options Same as `glob`, except `sortByMtime` is rejected and `cache` and `threads` are ignored.
+		 * @returns Handle for pulling matches in batches.
+		 */
+		globStream(options: GlobOptions): GlobStream;
 		/** Invalidate the filesystem scan cache for the given path (or all caches if omitted). */
 		invalidateFsScanCache(path?: string): void;
+		/**
+		 * Compile a glob pattern to the regular expression `glob` matches root-relative paths with.
+		 * @param pattern Glob pattern, rewritten exactly as `glob` does.
+		 * @returns JS RegExp source; non-ASCII characters are written as `\uXXXX` escapes.
+		 */
+		globToRegex(pattern: string): string;
+		/**
+		 * Explain how `glob` rewrites and matches a pattern.
+		 * @param pattern Glob pattern to explain.
+		 * @returns The rewritten pattern, its regex, and notes on the rules applied.
+		 */
+		explainGlob(pattern: string): GlobExplanation;
 	}
 }
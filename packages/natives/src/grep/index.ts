@@ -5,6 +5,8 @@
 import { native } from "../native";
 import type {
 	ContextLine,
+	CountMatchesOptions,
+	CountMatchesResult,
 	FuzzyFindMatch,
 	FuzzyFindOptions,
 	FuzzyFindResult,
@@ -18,6 +20,8 @@ import type {
 
 export type {
 	ContextLine,
+	CountMatchesOptions,
+	CountMatchesResult,
 	FuzzyFindMatch,
 	FuzzyFindOptions,
 	FuzzyFindResult,
@@ -38,6 +42,17 @@ export async function grep(options: GrepOptions, onMatch?: (match: GrepMatch) =>
 	return native.grep(options, cb);
 }
 
+/**
+ * Count regex matches under a path. Cheaper than `grep()` in count mode when only totals are needed.
+ */
+export async function countMatches(
+	pattern: string,
+	path: string,
+	options?: CountMatchesOptions,
+): Promise<CountMatchesResult> {
+	return native.countMatches(pattern, path, options);
+}
+
 /**
  * Search a single file's content for a pattern.
  * Lower-level API for when you already have file content.
@@ -16,8 +16,12 @@ export interface GrepOptions extends Cancellable {
 	type?: string;
 	/** Case-insensitive search */
 	ignoreCase?: boolean;
-	/** Enable multiline matching */
+	/** Let matches span lines; `^`/`$` still match at line boundaries */
 	multiline?: boolean;
+	/** Treat the pattern as a literal string */
+	fixedStrings?: boolean;
+	/** Only match whole words */
+	wordBoundary?: boolean;
 	/** Include hidden files (default: true) */
 	hidden?: boolean;
 	/** Enable shared filesystem scan cache (default: false). */
@@ -34,7 +38,7 @@ export interface GrepOptions extends Cancellable {
 	context?: number;
 	/** Truncate lines longer than this (characters) */
 	maxColumns?: number;
-	/** Output mode */
+	/** Output mode; `filesWithMatches` stops reading each file at its first match, so totals count one per file */
 	mode?: "content" | "filesWithMatches" | "count";
 }
 
@@ -60,13 +64,17 @@ export interface GrepMatch {
 	contextAfter?: ContextLine[];
 	/** Whether the line was truncated. */
 	truncated?: boolean;
+	/** 1-indexed column of the first match on the line, in UTF-16 code units (content mode only). */
+	column?: number;
+	/** Text of the first match on the line (content mode only). */
+	matchText?: string;
 	/** Per-file match count (count mode only). */
 	matchCount?: number;
 }
 
 /** Summary stats for a grep run. */
 export interface GrepSummary {
-	/** Total matches across all files. */
+	/** Matching lines across all files (one per file in `filesWithMatches` mode). */
 	totalMatches: number;
 	/** Number of files with at least one match. */
 	filesWithMatches: number;
@@ -76,6 +84,36 @@ export interface GrepSummary {
 	limitReached?: boolean;
 }
 
+/** Options for counting matches in files. */
+export interface CountMatchesOptions extends Cancellable {
+	/** Glob filter for filenames (e.g., "*.ts") */
+	glob?: string;
+	/** Filter by file type (e.g., "js", "py", "rust") */
+	type?: string;
+	/** Case-insensitive search */
+	ignoreCase?: boolean;
+	/** Let matches span lines; `^`/`$` still match at line boundaries */
+	multiline?: boolean;
+	/** Treat the pattern as a literal string */
+	fixedStrings?: boolean;
+	/** Only match whole words */
+	wordBoundary?: boolean;
+	/** Include hidden files (default: true) */
+	hidden?: boolean;
+	/** Enable shared filesystem scan cache (default: false). */
+	cache?: boolean;
+}
+
+/** Match totals across files. */
+export interface CountMatchesResult {
+	/** Matching lines across all files; a line with several occurrences counts once. */
+	totalMatches: number;
+	/** Number of files with at least one match. */
+	filesWithMatches: number;
+	/** Number of files searched. */
+	filesSearched: number;
+}
+
 /** Full grep result including matches and summary counts. */
 export interface GrepResult extends GrepSummary {
 	/** Matches or per-file counts, depending on mode. */
@@ -88,8 +126,12 @@ export interface SearchOptions {
 	pattern: string;
 	/** Case-insensitive search */
 	ignoreCase?: boolean;
-	/** Enable multiline matching */
+	/** Let matches span lines; `^`/`$` still match at line boundaries */
 	multiline?: boolean;
+	/** Treat the pattern as a literal string */
+	fixedStrings?: boolean;
+	/** Only match whole words */
+	wordBoundary?: boolean;
 	/** Maximum number of matches to return */
 	maxCount?: number;
 	/** Skip first N matches */
@@ -118,6 +160,10 @@ export interface SearchMatch {
 	contextAfter?: ContextLine[];
 	/** Whether the line was truncated. */
 	truncated?: boolean;
+	/** 1-indexed column of the first match on the line, in UTF-16 code units. */
+	column?: number;
+	/** Text of the first match on the line. */
+	matchText?: string;
 }
 
 /** Result of searching in-memory content. */
@@ -172,6 +218,8 @@ declare module "../bindings" {
 		fuzzyFind(options: FuzzyFindOptions): Promise<FuzzyFindResult>;
 		/** Search files for a regex pattern. */
 		grep(options: GrepOptions, onMatch?: TsFunc<GrepMatch>): Promise<GrepResult>;
+		/** Count regex matches in files without building per-match or per-file results. */
+		countMatches(pattern: string, path: string, options?: CountMatchesOptions): Promise<CountMatchesResult>;
 		/** Search in-memory content for a regex pattern. */
 		search(content: string | Uint8Array, options: SearchOptions): SearchResult;
 		/** Quick check if content matches a pattern. */
@@ -0,0 +1,9 @@
+/**
+ * Content hashing with BLAKE3 and XXH3/XXH64.
+ */
+
+import { native } from "../native";
+
+export type { ChangeEntry, DetectChangesOptions, HashAlgorithm } from "./types";
+
+export const { hashBuffer, hashFile, detectChanges } = native;
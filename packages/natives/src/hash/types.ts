@@ -0,0 +1,53 @@
+/**
+ * Types for content hashing.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Hash algorithm: BLAKE3 (256-bit, cryptographic) or XXH3/XXH64 (fast fingerprints). */
+export type HashAlgorithm = "blake3" | "xxh3" | "xxh3-128" | "xxh64";
+
+/** A file and the state it was last seen in, for `detectChanges()`. */
+export interface ChangeEntry {
+	/** File to check. */
+	path: string;
+	/** Digest of the content when last seen, in the requested algorithm. */
+	expectedHash?: string;
+	/** Modification time in milliseconds since the Unix epoch when last seen (e.g. `Stats.mtimeMs`). */
+	expectedMtime?: number;
+	/** Size in bytes when last seen. */
+	expectedSize?: number;
+}
+
+/** Options for `detectChanges()`. */
+export interface DetectChangesOptions extends Cancellable {
+	/** Algorithm of `expectedHash` (default: "blake3"). */
+	algorithm?: HashAlgorithm;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Hash a buffer or string (as UTF-8) on the worker pool.
+		 * @param data Bytes or text to hash.
+		 * @param algo Algorithm (default: "blake3").
+		 * @returns The lowercase hex digest.
+		 */
+		hashBuffer(data: string | Uint8Array, algo?: HashAlgorithm): Promise<string>;
+		/**
+		 * Hash a file's contents on the worker pool, streaming it in chunks.
+		 * Large files are hashed with multithreaded BLAKE3.
+		 * @param path File to hash.
+		 * @param algo Algorithm (default: "blake3").
+		 * @returns The lowercase hex digest.
+		 */
+		hashFile(path: string, algo?: HashAlgorithm): Promise<string>;
+		/**
+		 * Find the files that changed since they were last seen, checking entries in parallel.
+		 * Size and mtime are compared first; a file is hashed only when its mtime moved.
+		 * Missing or unreadable files count as changed.
+		 * @returns Paths of changed entries, in input order.
+		 */
+		detectChanges(entries: ChangeEntry[], options?: DetectChangesOptions): Promise<string[]>;
+	}
+}
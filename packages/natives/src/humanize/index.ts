@@ -0,0 +1,15 @@
+/**
+ * Human-readable sizes, durations, and relative times.
+ */
+
+import { native } from "../native";
+
+export type {
+	DurationStyle,
+	FormatAlign,
+	FormatBytesOptions,
+	FormatDurationOptions,
+	FormatRelativeTimeOptions,
+} from "./types";
+
+export const { formatBytes, formatDuration, formatRelativeTime } = native;
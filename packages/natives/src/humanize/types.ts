@@ -0,0 +1,67 @@
+/**
+ * Types for human-readable formatters.
+ */
+
+/** Side to pad on when a fixed width is set. */
+export type FormatAlign = "left" | "right";
+
+/** Options for formatting a byte count. */
+export interface FormatBytesOptions {
+	/** Use powers of 1000 instead of 1024 (default: false). */
+	si?: boolean;
+	/** Digits after the decimal point for KB and above (default: 1). */
+	decimals?: number;
+	/** Pad to this many columns. */
+	width?: number;
+	/** Padding side when `width` is set (default: "right"). */
+	align?: FormatAlign;
+}
+
+/** Duration style: "compact" (`3m 05s`) or "clock" (`3:05`). */
+export type DurationStyle = "compact" | "clock";
+
+/** Options for formatting a duration. */
+export interface FormatDurationOptions {
+	/** Output style (default: "compact"). */
+	style?: DurationStyle;
+	/** Pad to this many columns. */
+	width?: number;
+	/** Padding side when `width` is set (default: "right"). */
+	align?: FormatAlign;
+}
+
+/** Options for formatting a relative time. */
+export interface FormatRelativeTimeOptions {
+	/** Reference time in milliseconds since the Unix epoch (default: now). */
+	now?: number;
+	/** Pad to this many columns. */
+	width?: number;
+	/** Padding side when `width` is set (default: "right"). */
+	align?: FormatAlign;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Format a byte count, e.g. "512B", "1.5KB", or "3.2GB".
+		 * @param bytes Byte count.
+		 * @param options Unit base, decimals, and column width.
+		 * @returns The formatted size, padded when `width` is set.
+		 */
+		formatBytes(bytes: number, options?: FormatBytesOptions): string;
+		/**
+		 * Format a duration, e.g. "850ms", "4.2s", "3m 05s", or "2h 05m".
+		 * @param ms Duration in milliseconds; negative values are formatted as zero.
+		 * @param options Style and column width.
+		 * @returns The formatted duration, padded when `width` is set.
+		 */
+		formatDuration(ms: number, options?: FormatDurationOptions): string;
+		/**
+		 * Format a timestamp relative to now, e.g. "just now", "3m ago", or "in 5m".
+		 * @param timestamp Milliseconds since the Unix epoch.
+		 * @param options Reference time and column width.
+		 * @returns The formatted relative time, padded when `width` is set.
+		 */
+		formatRelativeTime(timestamp: number, options?: FormatRelativeTimeOptions): string;
+	}
+}
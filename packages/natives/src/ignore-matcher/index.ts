@@ -0,0 +1,10 @@
+/**
+ * Ignore checks with the same semantics as the glob walker.
+ */
+
+import { native } from "../native";
+
+export type { IgnoreMatcherConstructor, IgnoreMatcherOptions } from "./types";
+
+export const { IgnoreMatcher } = native;
+export type IgnoreMatcher = import("./types").IgnoreMatcher;
@@ -0,0 +1,42 @@
+/**
+ * Types for standalone ignore checks matching the glob walker.
+ */
+
+/** Options for creating an ignore matcher. */
+export interface IgnoreMatcherOptions {
+	/** Treat hidden files as visible (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore, .ignore, and git exclude files (default: true). */
+	gitignore?: boolean;
+	/** Treat `node_modules` entries as visible (default: false). */
+	includeNodeModules?: boolean;
+}
+
+/** Answers whether `glob` would skip a path, without walking the tree. */
+export interface IgnoreMatcher {
+	/** Resolved root directory. */
+	get root(): string;
+	/**
+	 * Check whether `glob` would skip `path` or one of its parents. Paths outside the root are never ignored.
+	 * @param path Path relative to the root, or absolute.
+	 * @param isDir Whether the path is a directory (default: checked on disk; pass it for deleted paths).
+	 */
+	isIgnored(path: string, isDir?: boolean): boolean;
+	/** Forget cached ignore files, so edits to them take effect. */
+	reload(): void;
+}
+
+export interface IgnoreMatcherConstructor {
+	/**
+	 * Create a matcher for paths under `root`; relative roots resolve against the current directory.
+	 * @throws When `root` cannot be resolved or is not a directory.
+	 */
+	new (root: string, options?: IgnoreMatcherOptions): IgnoreMatcher;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Ignore matcher constructor applying the glob walker's ignore rules to arbitrary paths. */
+		IgnoreMatcher: IgnoreMatcherConstructor;
+	}
+}
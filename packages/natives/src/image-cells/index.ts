@@ -0,0 +1,9 @@
+/**
+ * Half-block and braille image rendering for terminals without graphics protocols.
+ */
+
+import { native } from "../native";
+
+export type { ImageCellColor, ImageCellMode, RenderImageCellsOptions } from "./types";
+
+export const { renderImageCells } = native;
@@ -0,0 +1,33 @@
+/**
+ * Types for character-cell image rendering.
+ */
+
+/** Cell rendering mode. */
+export type ImageCellMode = "halfblock" | "braille";
+
+/** Color depth for styled cells. */
+export type ImageCellColor = "truecolor" | "256" | "16";
+
+/** Options for rendering an image as character cells. */
+export interface RenderImageCellsOptions {
+	/** Cell mode (default: "halfblock"). */
+	mode?: ImageCellMode;
+	/** Color depth (default: "truecolor"). */
+	color?: ImageCellColor;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Render an RGBA image as rows of styled characters for terminals without
+		 * graphics protocols. Scale the image to `cols x rows*2` pixels for
+		 * "halfblock" or `cols*2 x rows*4` for "braille" first.
+		 * @param rgba Pixel data, 4 bytes per pixel, row-major.
+		 * @param width Image width in pixels.
+		 * @param height Image height in pixels.
+		 * @param options Cell mode and color depth.
+		 * @returns One string per terminal row.
+		 */
+		renderImageCells(rgba: Uint8Array, width: number, height: number, options?: RenderImageCellsOptions): string[];
+	}
+}
@@ -4,10 +4,19 @@
 
 import { native } from "../native";
 
-export { ImageFormat, type PhotonImageConstructor, SamplingFilter } from "./types";
+export {
+	type CellPixels,
+	ImageFormat,
+	type PhotonImageConstructor,
+	type PreparedImage,
+	type PrepareImageOptions,
+	SamplingFilter,
+} from "./types";
 
 /** PhotonImage class for image manipulation. Use PhotonImage.parse() to create instances. */
 export const PhotonImage = native.PhotonImage;
 
 /** PhotonImage instance type. */
 export type PhotonImage = import("./types").PhotonImage;
+
+export const { prepareImage } = native;
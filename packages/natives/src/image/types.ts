@@ -54,6 +54,42 @@ export interface PhotonImageConstructor {
 	prototype: PhotonImage;
 }
 
+/** Terminal cell size in pixels. */
+export interface CellPixels {
+	/** Cell width in pixels. */
+	width: number;
+	/** Cell height in pixels. */
+	height: number;
+}
+
+/** Options for fitting an image to a terminal cell box. */
+export interface PrepareImageOptions {
+	/** Maximum width in terminal columns. */
+	maxCols?: number;
+	/** Maximum height in terminal rows. */
+	maxRows?: number;
+	/** Cell size in pixels (default: 8x16). */
+	cellPixels?: CellPixels;
+}
+
+/** Decoded image scaled to fit a cell box. */
+export interface PreparedImage {
+	/** RGBA pixel data, 4 bytes per pixel, row-major. */
+	data: Uint8Array;
+	/** Scaled width in pixels. */
+	width: number;
+	/** Scaled height in pixels. */
+	height: number;
+	/** Columns covered by the scaled image. */
+	cols: number;
+	/** Rows covered by the scaled image. */
+	rows: number;
+	/** Original width in pixels. */
+	originalWidth: number;
+	/** Original height in pixels. */
+	originalHeight: number;
+}
+
 declare module "../bindings" {
 	/** Native bindings for image operations. */
 	interface NativeBindings {
@@ -61,5 +97,13 @@ declare module "../bindings" {
 		SamplingFilter: typeof SamplingFilter;
 		/** Photon image constructor exposed by the native module. */
 		PhotonImage: PhotonImageConstructor;
+		/**
+		 * Decode an image (PNG, JPEG, GIF, WebP) and downscale it to fit a cell box.
+		 * Images are never upscaled; animated GIFs use the first frame.
+		 * @param input File path or encoded bytes.
+		 * @param options Cell box limits and cell size.
+		 * @returns RGBA pixels with the scaled size and the cells they cover.
+		 */
+		prepareImage(input: string | Uint8Array, options?: PrepareImageOptions): Promise<PreparedImage>;
 	}
 }
@@ -0,0 +1,9 @@
+/**
+ * Import extraction and file-level dependency graphs.
+ */
+
+import { native } from "../native";
+
+export type { ImportGraphNode, ImportGraphOptions, ImportKind, ImportRef } from "./types";
+
+export const { buildImportGraph, extractImports } = native;
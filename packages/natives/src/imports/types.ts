@@ -0,0 +1,59 @@
+/**
+ * Types for import extraction and dependency graphs.
+ */
+
+import type { Cancellable } from "../bindings";
+import type { SymbolLanguage } from "../symbols/types";
+
+/** Kind of import statement. */
+export type ImportKind = "import" | "export" | "require" | "dynamic" | "use" | "mod" | "extern";
+
+/** An import statement found in a source file. */
+export interface ImportRef {
+	/** Module as written (`./args`, `os.path`, `crate::fs::read`, `fmt`). */
+	specifier: string;
+	/** Statement kind; "export" is a re-export and "dynamic" an `import()` call. */
+	kind: ImportKind;
+	/** Line of the statement (1-based). */
+	line: number;
+	/** Resolved file (a directory for Go packages), or `null` for external or unresolvable modules. */
+	resolved: string | null;
+}
+
+/** Options for building an import graph. */
+export interface ImportGraphOptions extends Cancellable {
+	/** Include hidden files and directories (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore and related ignore files (default: true). */
+	gitignore?: boolean;
+}
+
+/** A file in an import graph. Paths are relative to the graph root with forward slashes. */
+export interface ImportGraphNode {
+	/** The file. */
+	path: string;
+	/** Files this file imports. */
+	imports: string[];
+	/** Files that import this file. */
+	importedBy: string[];
+	/** Specifiers that did not resolve to a file in the graph (packages, standard library, missing files). */
+	external: string[];
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Extract and resolve the imports of a source file (Rust, TypeScript/TSX, JavaScript, Python, Go).
+		 * @param path Source file; resolved paths are built from it, so they are relative when it is.
+		 * @param language Language of the file; inferred from the extension when omitted.
+		 * @returns Imports in source order.
+		 */
+		extractImports(path: string, language?: SymbolLanguage): Promise<ImportRef[]>;
+		/**
+		 * Build the file-level import graph of a workspace.
+		 * @param root Directory to walk.
+		 * @returns One node per supported source file, sorted by path.
+		 */
+		buildImportGraph(root: string, options?: ImportGraphOptions): Promise<ImportGraphNode[]>;
+	}
+}
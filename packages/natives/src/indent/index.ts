@@ -0,0 +1,9 @@
+/**
+ * Indentation style detection.
+ */
+
+import { native } from "../native";
+
+export type { IndentInfo, IndentStyle } from "./types";
+
+export const { detectIndent } = native;
@@ -0,0 +1,37 @@
+/**
+ * Types for indentation style detection.
+ */
+
+import type { LineEnding } from "../eol/types";
+
+/** Indentation kinds ("unknown" when no line is indented). */
+export type IndentStyle = "tabs" | "spaces" | "unknown";
+
+/** Indentation and line-ending style of a text. */
+export interface IndentInfo {
+	/** Whether lines indent with tabs or spaces. */
+	style: IndentStyle;
+	/** Characters per indentation level: 1 for tabs, 0 when unknown. */
+	width: number;
+	/** One level of indentation, e.g. "\t" or "  " (empty when unknown). */
+	indent: string;
+	/** How consistently the text follows the detected style, from 0 to 1. */
+	confidence: number;
+	/** Dominant line ending ("lf" when the text has none). */
+	eol: LineEnding;
+	/** Whether more than one kind of line ending occurs. */
+	mixedEol: boolean;
+	/** Whether the text ends with a line ending. */
+	finalNewline: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect the indentation style and line ending of a text.
+		 * @param text Text to inspect, typically a whole file.
+		 * @returns Tabs or spaces with level width and confidence, plus line-ending info.
+		 */
+		detectIndent(text: string): IndentInfo;
+	}
+}
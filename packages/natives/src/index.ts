@@ -12,8 +12,18 @@ export { type ClipboardImage, copyToClipboard, readImageFromClipboard } from "./
 // Grep (ripgrep-based regex search)
 // =============================================================================
 
+export {
+	type ApplyEditOptions,
+	type ApplyEditResult,
+	type ApplyEditStatus,
+	applyEdit,
+	type EditCandidate,
+} from "./edit";
 export {
 	type ContextLine,
+	type CountMatchesOptions,
+	type CountMatchesResult,
+	countMatches,
 	type FuzzyFindMatch,
 	type FuzzyFindOptions,
 	type FuzzyFindResult,
@@ -26,41 +36,373 @@ export {
 	hasMatch,
 	searchContent,
 } from "./grep";
+export { type MultiMatch, MultiMatcher, type MultiMatcherConstructor, type MultiMatcherOptions } from "./multi-match";
+export { type FileReplacement, type ReplaceOptions, type ReplaceResult, replaceInFiles } from "./replace";
+export {
+	compileRegex,
+	type RegexFindResult,
+	type RegexMatch,
+	type RegexOptions,
+	type SafeRegex,
+} from "./safe-regex";
+
+// =============================================================================
+// Diff (line diffing)
+// =============================================================================
+
+export {
+	type ChangedRange,
+	type DiffHunk,
+	type DiffLine,
+	DiffLineKind,
+	type DiffOptions,
+	type DiffResult,
+	diffLines,
+	diffWords,
+	type LineRefinement,
+	type RefineOptions,
+	refineHunk,
+	type WordDiffPart,
+} from "./diff";
+export { type RenderDiffOptions, type RenderedDiffRow, type RenderedDiffRowKind, renderDiff } from "./diff-render";
+export {
+	type ApplyPatchOptions,
+	type ApplyPatchResult,
+	applyPatch,
+	type PatchConflict,
+	type PatchFile,
+	type PatchHunkResult,
+	parsePatch,
+} from "./patch";
+
+// =============================================================================
+// Merge (three-way merge)
+// =============================================================================
+
+export { type MergeConflict, type MergeOptions, type MergeResult, merge3 } from "./merge";
+
+// =============================================================================
+// Git (libgit2 queries)
+// =============================================================================
+
+export {
+	type GitCommit,
+	type GitDiffOptions,
+	type GitDiffResult,
+	type GitFileDiff,
+	type GitFileStatus,
+	type GitHunkOptions,
+	type GitLogOptions,
+	type GitRepoInfo,
+	type GitRepoInfoOptions,
+	type GitRepoState,
+	gitDiff,
+	gitLog,
+	gitRepoInfo,
+	gitStageHunks,
+	gitUnstageHunks,
+} from "./git";
+
+// =============================================================================
+// Fuzzy (command palette scoring, suggestions)
+// =============================================================================
+
+export {
+	type FuzzyScoreMatch,
+	type FuzzyScoreOptions,
+	type FuzzySpan,
+	fuzzyScore,
+	type SuggestOptions,
+	type Suggestion,
+	suggest,
+} from "./fuzzy";
 
 // =============================================================================
 // Glob (file discovery)
 // =============================================================================
 
+export { type FindUpOptions, findUp } from "./find-up";
 export {
+	explainGlob,
 	FileType,
+	type GlobExplanation,
 	type GlobMatch,
 	type GlobOptions,
 	type GlobResult,
+	type GlobStream,
 	glob,
+	globStream,
+	globToRegex,
 	invalidateFsScanCache,
 } from "./glob";
+export { IgnoreMatcher, type IgnoreMatcherConstructor, type IgnoreMatcherOptions } from "./ignore-matcher";
 
 // =============================================================================
 // Image processing (photon-compatible API)
 // =============================================================================
 
-export { ImageFormat, PhotonImage, SamplingFilter } from "./image";
+export {
+	type CellPixels,
+	ImageFormat,
+	PhotonImage,
+	type PreparedImage,
+	type PrepareImageOptions,
+	prepareImage,
+	SamplingFilter,
+} from "./image";
+export {
+	type ImageCellColor,
+	type ImageCellMode,
+	type RenderImageCellsOptions,
+	renderImageCells,
+} from "./image-cells";
+export {
+	renderToPng,
+	renderToSvg,
+	type ScreenshotFont,
+	type ScreenshotOptions,
+	type ScreenshotTheme,
+} from "./screenshot";
+export { encodeSixel, type SixelOptions } from "./sixel";
+export { exportTranscript, type TranscriptFormat, type TranscriptOptions } from "./transcript";
 
 // =============================================================================
 // Text utilities
 // =============================================================================
 
+export { detectEol, type EolInfo, type LineEnding, normalizeEol } from "./eol";
 export {
+	type DurationStyle,
+	type FormatAlign,
+	type FormatBytesOptions,
+	type FormatDurationOptions,
+	type FormatRelativeTimeOptions,
+	formatBytes,
+	formatDuration,
+	formatRelativeTime,
+} from "./humanize";
+export { detectIndent, type IndentInfo, type IndentStyle } from "./indent";
+export { type NormalizeLogOptions, normalizeLog } from "./log-normalize";
+export { compareNatural, naturalSort } from "./natural";
+export { offsetMapUtf16, type Utf16OffsetMap } from "./offset-map";
+export {
+	type AnsiPolicy,
 	Ellipsis,
 	type ExtractSegmentsResult,
 	extractSegments,
+	type GraphemeInfo,
+	graphemeAt,
+	graphemes,
+	joinStyled,
+	type LayoutSegment,
+	layoutLine,
+	type PadAlign,
+	padToWidth,
 	type SliceWithWidthResult,
+	sanitizeAnsi,
 	sanitizeText,
+	setTextOptions,
 	sliceWithWidth,
+	stripAnsi,
+	type TextOptions,
+	type TruncateMode,
 	truncateToWidth,
 	visibleWidth,
+	visibleWidths,
+	type WrapOptions,
 	wrapTextWithAnsi,
+	wrapToWidth,
+	wrapWithHyperlink,
 } from "./text";
+export { splitIdentifier, type WordBoundaryOptions, wordBoundaries } from "./words";
+
+// =============================================================================
+// Text buffer
+// =============================================================================
+
+export {
+	TextBuffer,
+	type TextBufferConstructor,
+	type TextBufferMatch,
+	type TextBufferOptions,
+	type TextBufferSearchOptions,
+	type TextBufferSearchResult,
+	type TextPosition,
+	type TextRange,
+} from "./text-buffer";
+
+// =============================================================================
+// Secret redaction
+// =============================================================================
+
+export { type RedactOptions, type RedactResult, redactSecrets, type SecretMatch } from "./redact";
+
+// =============================================================================
+// Link detection
+// =============================================================================
+
+export {
+	type DetectedLink,
+	type DetectLinksOptions,
+	detectLinks,
+	type FileLinkOptions,
+	type FileLinkScheme,
+	type HyperlinkOptions,
+	hyperlink,
+	makeFileLink,
+} from "./links";
+
+// =============================================================================
+// Hashing
+// =============================================================================
+
+export {
+	type ChangeEntry,
+	type DetectChangesOptions,
+	detectChanges,
+	type HashAlgorithm,
+	hashBuffer,
+	hashFile,
+} from "./hash";
+
+// =============================================================================
+// File snapshots
+// =============================================================================
+
+export {
+	type DiffSnapshotOptions,
+	diffSnapshot,
+	FileSnapshot,
+	type FileSnapshotConstructor,
+	type SnapshotChange,
+	type SnapshotChangeStatus,
+	type SnapshotEntry,
+	type SnapshotOptions,
+	snapshotFiles,
+} from "./snapshot";
+
+// =============================================================================
+// Caching
+// =============================================================================
+
+export {
+	type CacheEvictResult,
+	type CachePutOptions,
+	CacheStore,
+	type CacheStoreConstructor,
+	type CacheStoreOptions,
+} from "./cache-store";
+
+// =============================================================================
+// Storage
+// =============================================================================
+
+export {
+	Database,
+	type DatabaseConstructor,
+	type DbExecResult,
+	type DbOpenOptions,
+	type DbQueryResult,
+	type DbStatement,
+	openDb,
+	type SqlParam,
+	type SqlValue,
+} from "./db";
+export { LruMap, type LruMapConstructor, type LruMapOptions, type LruSetOptions } from "./lru";
+
+// =============================================================================
+// Compression
+// =============================================================================
+
+export {
+	type CompressFileResult,
+	type CompressionAlgorithm,
+	type CompressOptions,
+	compress,
+	compressFile,
+	decompress,
+	decompressFile,
+} from "./compress";
+
+// =============================================================================
+// Archives
+// =============================================================================
+
+export {
+	type ArchiveEntry,
+	type ExtractArchiveOptions,
+	type ExtractArchiveResult,
+	extractArchive,
+	type ListArchiveOptions,
+	listArchive,
+} from "./archive";
+
+// =============================================================================
+// Symbols
+// =============================================================================
+
+export {
+	type CodeSymbol,
+	extractSymbols,
+	SymbolIndex,
+	type SymbolIndexConstructor,
+	type SymbolIndexOptions,
+	type SymbolIndexUpdate,
+	type SymbolKind,
+	type SymbolLanguage,
+	type SymbolMatch,
+	type SymbolQueryOptions,
+} from "./symbols";
+
+// =============================================================================
+// Imports
+// =============================================================================
+
+export {
+	buildImportGraph,
+	extractImports,
+	type ImportGraphNode,
+	type ImportGraphOptions,
+	type ImportKind,
+	type ImportRef,
+} from "./imports";
+
+// =============================================================================
+// Line counts
+// =============================================================================
+
+export { type CountLinesOptions, type CountLinesResult, countLines, type LanguageLineCount } from "./line-count";
+
+// =============================================================================
+// Charts
+// =============================================================================
+
+export { type BarsOptions, renderBars, renderSparkline, type SparklineOptions } from "./chart";
+
+// =============================================================================
+// Token counting
+// =============================================================================
+
+export { type CountTokensOptions, countFileTokens, type TokenCount, type TokenEncoding } from "./tokens";
+
+// =============================================================================
+// Chunking
+// =============================================================================
+
+export { type ChunkFileOptions, type ChunkLanguage, chunkFile, type FileChunk } from "./chunk";
+
+// =============================================================================
+// Vector search
+// =============================================================================
+
+export {
+	VectorIndex,
+	type VectorIndexConstructor,
+	type VectorIndexOptions,
+	type VectorMatch,
+	type VectorMetric,
+	type VectorSearchOptions,
+} from "./vector-index";
 
 // =============================================================================
 // Syntax highlighting
@@ -73,19 +415,101 @@ export {
 	supportsLanguage,
 } from "./highlight";
 
+// =============================================================================
+// Terminal capabilities
+// =============================================================================
+
+export {
+	type DetectTerminalOptions,
+	detectTerminal,
+	type NotifyOptions,
+	type NotifyProtocol,
+	type NotifyResult,
+	type NotifyUrgency,
+	notify,
+	type TerminalImageProtocol,
+	type TerminalInfo,
+} from "./terminal";
+
 // =============================================================================
 // Keyboard sequence helpers
 // =============================================================================
 
+export { InputDecoder, type InputDecoderConstructor, type InputEvent, type InputEventKind } from "./input-decoder";
 export {
+	type KeyEvent,
 	type KeyEventType,
 	matchesKey,
 	matchesKittySequence,
 	matchesLegacySequence,
 	type ParsedKittyResult,
 	parseKey,
+	parseKeyEvent,
 	parseKittySequence,
 } from "./keys";
+export { type MouseEvent, type MouseEventKind, parseMouseEvent } from "./mouse";
+export { openRawInput, type RawInputChunk, type RawInputOptions, type RawInputResult } from "./raw-input";
+
+// =============================================================================
+// File reading and writing
+// =============================================================================
+
+export { type HexdumpOptions, hexdump, isBinary } from "./binary";
+export {
+	type DetectedEncoding,
+	detectEncoding,
+	type FileText,
+	type ReadFileTextOptions,
+	readFileText,
+	type TextEncoding,
+} from "./encoding";
+export { LineIndex, type LineIndexConstructor } from "./line-index";
+export { type ReadFileSliceOptions, type ReadFileSliceResult, readFileSlice } from "./read";
+export { type TailEvent, type TailFollowOptions, type TailFollowResult, tailFollow } from "./tail";
+export {
+	cleanupTempPaths,
+	createTempDir,
+	createTempFile,
+	type TempOptions,
+	TempPath,
+	type TempPathConstructor,
+} from "./temp";
+export { type WriteFileAtomicOptions, writeFileAtomic } from "./write";
+
+// =============================================================================
+// File watching
+// =============================================================================
+
+export {
+	Watcher,
+	type WatcherConstructor,
+	type WatcherOptions,
+	type WatchTriggerEvent,
+	type WatchTriggerOptions,
+	type WatchTriggerResult,
+	watch,
+	watchAndTrigger,
+} from "./watch";
+
+// =============================================================================
+// JSON and event streaming
+// =============================================================================
+
+export { JsonStream, type JsonStreamConstructor, type JsonStreamValue } from "./json";
+export { type JsonlFile, openJsonl } from "./jsonl";
+export { type SseEvent, SseParser, type SseParserConstructor } from "./sse";
+
+// =============================================================================
+// Markdown structure
+// =============================================================================
+
+export {
+	type CodeBlock,
+	extractCodeBlocks,
+	type MarkdownBlock,
+	MarkdownBlockKind,
+	parseMarkdownBlocks,
+} from "./markdown";
 
 // =============================================================================
 // HTML to Markdown
@@ -103,6 +527,15 @@ export { getSystemInfo, type SystemInfo } from "./system-info";
 // Shell execution (brush-core)
 // =============================================================================
 
+export {
+	type DiffEnvOptions,
+	diffEnv,
+	type EnvChange,
+	type EnvDiff,
+	type EnvVar,
+	parseShellExports,
+	snapshotEnv,
+} from "./env-diff";
 export {
 	executeShell,
 	Shell,
@@ -112,12 +545,30 @@ export {
 	type ShellRunOptions,
 	type ShellRunResult,
 } from "./shell";
+export { type ShellMode, shlexQuote, shlexSplit } from "./shlex";
+export { type WhichOptions, whichAll } from "./which";
 
 // =============================================================================
 // PTY execution
 // =============================================================================
 
-export { type PtyRunResult, PtySession, type PtyStartOptions } from "./pty";
+export {
+	Pty,
+	type PtyConstructor,
+	type PtyRunResult,
+	PtySession,
+	type PtySpawnOptions,
+	type PtyStartOptions,
+} from "./pty";
+export {
+	type DiffScreensOptions,
+	diffScreens,
+	type TermCell,
+	type TermCursor,
+	TermScreen,
+	type TermScreenConstructor,
+} from "./term-screen";
+
 // =============================================================================
 // Process management
 // =============================================================================
@@ -0,0 +1,10 @@
+/**
+ * Incremental terminal input decoding powered by native bindings.
+ */
+
+import { native } from "../native";
+
+export type { InputDecoderConstructor, InputEvent, InputEventKind } from "./types";
+
+export const { InputDecoder } = native;
+export type InputDecoder = import("./types").InputDecoder;
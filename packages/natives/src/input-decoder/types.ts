@@ -0,0 +1,55 @@
+/**
+ * Types for incremental terminal input decoding.
+ */
+
+import type { KeyEvent } from "../keys/types";
+import type { MouseEvent } from "../mouse/types";
+
+/** Input event kinds. */
+export type InputEventKind = "key" | "mouse" | "paste-start" | "paste" | "paste-end" | "focus" | "unknown";
+
+/** A decoded input event. */
+export interface InputEvent {
+	/** Event type. */
+	kind: InputEventKind;
+	/** Raw input for the event, or the pasted text for "paste" events. */
+	data: string;
+	/** Decoded key for "key" events. */
+	key?: KeyEvent;
+	/** Decoded report for "mouse" events. */
+	mouse?: MouseEvent;
+	/** Whether the terminal gained focus, for "focus" events. */
+	focused?: boolean;
+}
+
+/** Stateful decoder turning raw terminal input into typed events. */
+export interface InputDecoder {
+	/**
+	 * Feed the next chunk of input; strings are processed as UTF-8. Sequences split across calls are held until
+	 * complete. Returns the events completed by this chunk, in input order.
+	 */
+	feed(chunk: string | Uint8Array): InputEvent[];
+	/**
+	 * Emit buffered input as a single event without waiting for the rest of the sequence, e.g. a lone ESC after
+	 * an escape timeout. Does nothing inside a bracketed paste.
+	 */
+	flush(): InputEvent[];
+	/** Whether input is buffered waiting for the rest of a sequence (outside a bracketed paste). */
+	get hasPending(): boolean;
+	/** Whether the decoder is inside a bracketed paste. */
+	get inPaste(): boolean;
+	/** Discard buffered input and paste state. */
+	reset(): void;
+}
+
+/** Native input decoder constructor. */
+export interface InputDecoderConstructor {
+	new (): InputDecoder;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Incremental tokenizer for raw terminal input (keys, mouse, paste, focus). */
+		InputDecoder: InputDecoderConstructor;
+	}
+}
@@ -0,0 +1,10 @@
+/**
+ * Incremental JSON parsing and repair for streamed LLM output.
+ */
+
+import { native } from "../native";
+
+export type { JsonStreamConstructor, JsonStreamValue } from "./types";
+
+export const { JsonStream } = native;
+export type JsonStream = import("./types").JsonStream;
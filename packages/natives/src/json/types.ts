@@ -0,0 +1,41 @@
+/**
+ * Types for native incremental JSON parsing.
+ */
+
+/** A value that finished parsing during a `push`. */
+export interface JsonStreamValue {
+	/** JSON Pointer to the value (`""` for the root, e.g. `/edits/0/old`). */
+	path: string;
+	/** Raw JSON text of the value. */
+	raw: string;
+}
+
+/** Incremental JSON parser instance. */
+export interface JsonStream {
+	/**
+	 * Feed the next chunk of JSON text.
+	 * Returns values that closed within this chunk, innermost first.
+	 * Throws on invalid JSON; the stream stays failed until `reset()`.
+	 */
+	push(chunk: string): JsonStreamValue[];
+	/** Signal end of input, completing a trailing root-level number. */
+	end(): JsonStreamValue | null;
+	/** Best-effort valid JSON for the text received so far, or null if nothing is salvageable. */
+	repair(): string | null;
+	/** Whether the root value has been fully received. */
+	get done(): boolean;
+	/** Discard all received text and state. */
+	reset(): void;
+}
+
+/** Native JSON stream constructor. */
+export interface JsonStreamConstructor {
+	new (): JsonStream;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Incremental JSON parser for streamed tool-call arguments. */
+		JsonStream: JsonStreamConstructor;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Indexed JSONL files for session history.
+ */
+
+import { native } from "../native";
+
+export type { JsonlFile } from "./types";
+
+export const { openJsonl } = native;
@@ -0,0 +1,29 @@
+/**
+ * Types for indexed JSONL files.
+ */
+
+/** Handle to an indexed JSONL file. Records are exchanged as raw JSON text. */
+export interface JsonlFile {
+	/** Number of indexed records. */
+	count(): number;
+	/** Read records `[start, end)` as raw JSON text (bounds are clamped). */
+	readRange(start: number, end: number): Promise<string[]>;
+	/**
+	 * Append records (one JSON document each, without newlines) and index them.
+	 * Resolves to the new record count.
+	 */
+	append(records: string[]): Promise<number>;
+	/** Index records written by other processes; resolves to the new record count. */
+	refresh(): Promise<number>;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Open (creating if missing) and index a JSONL file by byte offset.
+		 * @param path JSONL file path.
+		 * @returns Handle for counting, range reads, and appends.
+		 */
+		openJsonl(path: string): Promise<JsonlFile>;
+	}
+}
@@ -4,6 +4,13 @@
 
 import { native } from "../native";
 
-export type { KeyEventType, ParsedKittyResult } from "./types";
+export type { KeyEvent, KeyEventType, ParsedKittyResult } from "./types";
 
-export const { matchesKittySequence, parseKey, matchesLegacySequence, parseKittySequence, matchesKey } = native;
+export const {
+	matchesKittySequence,
+	parseKey,
+	matchesLegacySequence,
+	parseKittySequence,
+	parseKeyEvent,
+	matchesKey,
+} = native;
@@ -29,6 +29,24 @@ export interface ParsedKittyResult {
 	eventType?: KeyEventType;
 }
 
+/** Structured key event decoded from Kitty, modifyOtherKeys or legacy input. */
+export interface KeyEvent {
+	/** Key name without modifiers (e.g. "a", "enter", "up", "f5"), when known. */
+	key?: string;
+	/** Unicode or Kitty functional codepoint; absent for keys reported by name only. */
+	codepoint?: number;
+	/** Shifted key codepoint reported by the sequence. */
+	shiftedKey?: number;
+	/** Base layout key codepoint reported by the sequence. */
+	baseLayoutKey?: number;
+	/** Modifier bitmask (shift=1, alt=2, ctrl=4, super=8, hyper=16, meta=32, capsLock=64, numLock=128). */
+	modifiers: number;
+	/** Event type (press when the sequence does not report one). */
+	eventType: KeyEventType;
+	/** Text the key inserts, if any. */
+	text?: string;
+}
+
 declare module "../bindings" {
 	interface NativeBindings {
 		/**
@@ -61,6 +79,13 @@ declare module "../bindings" {
 		 * @returns Parsed sequence info or null if not a Kitty sequence.
 		 */
 		parseKittySequence(data: string): ParsedKittyResult | null;
+		/**
+		 * Parse terminal input into a structured key event.
+		 * Handles Kitty, xterm modifyOtherKeys and legacy sequences.
+		 * @param data Raw terminal input data.
+		 * @returns The decoded key event or null when unrecognized.
+		 */
+		parseKeyEvent(data: string): KeyEvent | null;
 		/**
 		 * Match input data against a key identifier string.
 		 * Supports: escape, tab, enter, backspace, delete, home, end, space,
@@ -0,0 +1,9 @@
+/**
+ * Language-aware line statistics.
+ */
+
+import { native } from "../native";
+
+export type { CountLinesOptions, CountLinesResult, LanguageLineCount } from "./types";
+
+export const { countLines } = native;
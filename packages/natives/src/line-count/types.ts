@@ -0,0 +1,57 @@
+/**
+ * Types for language-aware line counting.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Options for counting lines. */
+export interface CountLinesOptions extends Cancellable {
+	/** Directory or file to count (default: current directory). */
+	path?: string;
+	/** Glob filter for filenames (e.g., "*.ts"). */
+	glob?: string;
+	/** Include hidden files (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore files (default: true). */
+	gitignore?: boolean;
+}
+
+/** Line counts for one language. */
+export interface LanguageLineCount {
+	/** Language name (e.g., "Rust", "TypeScript"). */
+	language: string;
+	/** Number of files counted. */
+	files: number;
+	/** Lines containing code. */
+	code: number;
+	/** Lines containing only comments. */
+	comment: number;
+	/** Whitespace-only lines. */
+	blank: number;
+}
+
+/** Result of counting lines. */
+export interface CountLinesResult {
+	/** Per-language counts, sorted by code lines (most first). */
+	languages: LanguageLineCount[];
+	/** Total files counted. */
+	files: number;
+	/** Total code lines. */
+	code: number;
+	/** Total comment lines. */
+	comment: number;
+	/** Total blank lines. */
+	blank: number;
+	/** Files skipped as binary, oversized, unreadable, or of unknown language. */
+	skipped: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Count code, comment, and blank lines per language, cloc-style.
+		 * Files are selected like `grep` and counted in parallel.
+		 */
+		countLines(options: CountLinesOptions): Promise<CountLinesResult>;
+	}
+}
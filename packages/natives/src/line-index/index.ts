@@ -0,0 +1,13 @@
+/**
+ * Newline-offset indexes over files and buffers.
+ */
+
+import { native } from "../native";
+
+export type { LineIndexConstructor } from "./types";
+
+/** LineIndex class. Use LineIndex.fromFile() or LineIndex.fromBuffer() to create instances. */
+export const LineIndex = native.LineIndex;
+
+/** LineIndex instance type. */
+export type LineIndex = import("./types").LineIndex;
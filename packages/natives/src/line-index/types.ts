@@ -0,0 +1,32 @@
+/**
+ * Types for newline-offset indexes.
+ */
+
+/** Line-start index over a file or buffer. Lines are 1-indexed; offsets are UTF-8 bytes. */
+export interface LineIndex {
+	/** Total indexed length in bytes. */
+	get byteLength(): number;
+	/** Number of lines (0 for empty input; a trailing newline does not add a line). */
+	lineCount(): number;
+	/** Byte offset where a 1-indexed line starts, or null when out of range. */
+	offsetOfLine(line: number): number | null;
+	/** 1-indexed line containing a byte offset, or null when out of range. */
+	lineOfOffset(offset: number): number | null;
+}
+
+/** Static entrypoints for creating `LineIndex` instances. */
+export interface LineIndexConstructor {
+	/** Index a file on the worker pool. */
+	fromFile(path: string): Promise<LineIndex>;
+	/** Index an in-memory buffer synchronously. */
+	fromBuffer(buffer: Uint8Array): LineIndex;
+	/** Instance prototype reference. */
+	prototype: LineIndex;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Line index constructor shared by the read tool, grep context, and editor gutter. */
+		LineIndex: LineIndexConstructor;
+	}
+}
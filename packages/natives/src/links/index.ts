@@ -0,0 +1,9 @@
+/**
+ * URL and file-reference detection, and OSC 8 hyperlinks for clickable terminal output.
+ */
+
+import { native } from "../native";
+
+export type { DetectedLink, DetectLinksOptions, FileLinkOptions, FileLinkScheme, HyperlinkOptions } from "./types";
+
+export const { detectLinks, makeFileLink, hyperlink } = native;
@@ -0,0 +1,91 @@
+/**
+ * Types for link detection in terminal output.
+ */
+
+/** Options for detecting links. */
+export interface DetectLinksOptions {
+	/** Directory relative paths resolve against (default: process cwd). */
+	cwd?: string;
+}
+
+/** A link found in the text. */
+export interface DetectedLink {
+	/** Link kind. */
+	kind: "url" | "file";
+	/** Zero-based line of the input containing the link. */
+	row: number;
+	/** Start display column within the line (ANSI escapes excluded). */
+	start: number;
+	/** End display column (exclusive). */
+	end: number;
+	/** The link as it appears in the text. */
+	text: string;
+	/** Target URL, for "url" links. */
+	url?: string;
+	/** Absolute path of the existing file or directory, for "file" links. */
+	path?: string;
+	/** 1-based line number from the reference, if any. */
+	line?: number;
+	/** 1-based column number from the reference, if any. */
+	column?: number;
+}
+
+/** Link target kind for file links: a file URI or an editor URI scheme. */
+export type FileLinkScheme =
+	| "file"
+	| "vscode"
+	| "vscode-insiders"
+	| "vscodium"
+	| "cursor"
+	| "windsurf"
+	| "zed"
+	| "idea"
+	| "subl";
+
+/** Options for building a file hyperlink. */
+export interface FileLinkOptions {
+	/** URI scheme (default: "file"). */
+	scheme?: FileLinkScheme;
+	/** Visible text, which may be styled (default: `path:line:col` as given). */
+	text?: string;
+	/** Link id (default: derived from the URI). */
+	id?: string;
+	/** Directory relative paths resolve against (default: process cwd). */
+	cwd?: string;
+}
+
+/** Options for wrapping text in a hyperlink. */
+export interface HyperlinkOptions {
+	/** Link id (default: derived from the URI). */
+	id?: string;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect URLs and `path:line:col` style file references in terminal output.
+		 * File references are returned only when the path exists.
+		 * @param text Output to scan; may contain ANSI escapes and multiple lines.
+		 * @param options Directory for resolving relative paths.
+		 */
+		detectLinks(text: string, options?: DetectLinksOptions): DetectedLink[];
+		/**
+		 * Build a clickable OSC 8 hyperlink for a file reference.
+		 * `file://` URIs carry no position; editor URIs default to line and column 1.
+		 * @param path File path; relative paths resolve against `cwd`, `~/` against the home directory.
+		 * @param line 1-based line number.
+		 * @param column 1-based column number.
+		 * @param options URI scheme, visible text, link id, and base directory.
+		 * @returns The visible text wrapped in an OSC 8 sequence.
+		 */
+		makeFileLink(path: string, line?: number, column?: number, options?: FileLinkOptions): string;
+		/**
+		 * Wrap existing, possibly styled, text in an OSC 8 hyperlink.
+		 * Links already inside the text are removed, and each line is linked separately under the same id.
+		 * @param text Visible text; SGR styling is kept.
+		 * @param uri Link target; bytes outside printable ASCII are percent-encoded.
+		 * @param options Link id.
+		 */
+		hyperlink(text: string, uri: string, options?: HyperlinkOptions): string;
+	}
+}
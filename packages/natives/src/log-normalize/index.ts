@@ -0,0 +1,9 @@
+/**
+ * Log cleanup before display or summarization.
+ */
+
+import { native } from "../native";
+
+export type { NormalizeLogOptions } from "./types";
+
+export const { normalizeLog } = native;
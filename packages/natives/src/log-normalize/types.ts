@@ -0,0 +1,25 @@
+/**
+ * Types for log normalization.
+ */
+
+/** Options for normalizing log lines. */
+export interface NormalizeLogOptions {
+	/** Remove timestamps and process IDs (default: true). */
+	stripTimestamps?: boolean;
+	/** Replace runs of identical lines with one line ending in "(×N)" (default: true). */
+	collapseRepeats?: boolean;
+	/** Remove ANSI escape sequences (default: true). */
+	stripAnsi?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Clean up noisy log lines: strip timestamps, PIDs, and ANSI escapes, and collapse repeated lines.
+		 * @param lines Log lines; entries containing newlines are split.
+		 * @param options Which cleanups to apply (all on by default).
+		 * @returns Cleaned lines, with repeats collapsed when enabled.
+		 */
+		normalizeLog(lines: string[], options?: NormalizeLogOptions): string[];
+	}
+}
@@ -0,0 +1,10 @@
+/**
+ * Bounded in-process LRU map with byte accounting and TTLs.
+ */
+
+import { native } from "../native";
+
+export type { LruMapConstructor, LruMapOptions, LruSetOptions } from "./types";
+
+export const { LruMap } = native;
+export type LruMap = import("./types").LruMap;
@@ -0,0 +1,57 @@
+/**
+ * Types for the bounded in-process LRU map.
+ */
+
+/** Options for creating an LRU map. */
+export interface LruMapOptions {
+	/** Maximum number of entries (default: unlimited). */
+	maxEntries?: number;
+	/** Maximum total bytes of keys and values (default: 64 MiB). */
+	maxBytes?: number;
+	/** Default time to live in milliseconds (default: no expiry). */
+	ttl?: number;
+}
+
+/** Options for storing a value. */
+export interface LruSetOptions {
+	/** Time to live in milliseconds, overriding the map's default. */
+	ttl?: number;
+}
+
+/** Bounded LRU map of string or byte values with TTL support. */
+export interface LruMap {
+	/** Number of entries, including expired ones not yet pruned. */
+	get size(): number;
+	/** Total bytes of keys and values held (strings counted as UTF-8). */
+	get bytes(): number;
+	/**
+	 * Store `value` under `key` as the most recently used entry, evicting older entries to stay within bounds.
+	 * @returns `false` when the entry alone exceeds `maxBytes` and was not stored; any previous value is kept.
+	 */
+	set(key: string, value: string | Uint8Array, options?: LruSetOptions): boolean;
+	/** Read the value under `key`, marking it as recently used; `null` when missing or expired. */
+	get(key: string): string | Uint8Array | null;
+	/** Read the value under `key` without changing its recency. */
+	peek(key: string): string | Uint8Array | null;
+	/** Whether a live entry exists under `key` (does not change recency). */
+	has(key: string): boolean;
+	/** Remove the entry under `key`, returning whether it existed. */
+	delete(key: string): boolean;
+	/** Remove all entries. */
+	clear(): void;
+	/** Drop expired entries, returning how many were removed. */
+	prune(): number;
+	/** Keys from least to most recently used, including expired entries not yet pruned. */
+	keys(): string[];
+}
+
+export interface LruMapConstructor {
+	new (options?: LruMapOptions): LruMap;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Bounded LRU map constructor for hot in-process caches. */
+		LruMap: LruMapConstructor;
+	}
+}
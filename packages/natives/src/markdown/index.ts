@@ -0,0 +1,10 @@
+/**
+ * Markdown structure helpers.
+ */
+
+import { native } from "../native";
+
+export type { CodeBlock, MarkdownBlock } from "./types";
+export { MarkdownBlockKind } from "./types";
+
+export const { extractCodeBlocks, parseMarkdownBlocks } = native;
@@ -0,0 +1,78 @@
+/**
+ * Types for native Markdown block parsing.
+ */
+
+/** Kind of a top-level Markdown block. */
+export const enum MarkdownBlockKind {
+	/** Plain paragraph. */
+	Paragraph = 1,
+	/** ATX or setext heading. */
+	Heading = 2,
+	/** Fenced or indented code block. */
+	CodeBlock = 3,
+	/** Ordered or unordered list. */
+	List = 4,
+	/** GFM table. */
+	Table = 5,
+	/** Block quote. */
+	BlockQuote = 6,
+	/** Thematic break (`---`). */
+	Rule = 7,
+	/** Raw HTML block. */
+	Html = 8,
+	/** Footnote definition. */
+	FootnoteDefinition = 9,
+	/** Anything else (definition lists, metadata blocks, math). */
+	Other = 10,
+}
+
+/** A top-level Markdown block and its source range. */
+export interface MarkdownBlock {
+	/** Block kind. */
+	kind: MarkdownBlockKind;
+	/** Start byte offset in the source (UTF-8). */
+	start: number;
+	/** End byte offset in the source (UTF-8, exclusive). */
+	end: number;
+	/** Heading level (1-6), for headings. */
+	level?: number;
+	/** Info-string language, for fenced code blocks with one. */
+	language?: string;
+	/** Whether the code block is fenced (vs indented), for code blocks. */
+	fenced?: boolean;
+	/** Whether the list is ordered, for lists. */
+	ordered?: boolean;
+}
+
+/** A fenced code block extracted from Markdown. */
+export interface CodeBlock {
+	/** First word of the info string (e.g. "ts"), if any. */
+	language?: string;
+	/** Full info string after the opening fence. */
+	info: string;
+	/** Code content without fences or container indentation. */
+	content: string;
+	/** Start byte offset of the opening fence (UTF-8). */
+	start: number;
+	/** End byte offset of the block (UTF-8, exclusive). */
+	end: number;
+	/** Whether the closing fence is present (false while still streaming). */
+	closed: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Split Markdown into top-level blocks with byte ranges.
+		 * @param text Markdown source (may be a partial, still-streaming message).
+		 * @returns Blocks in source order.
+		 */
+		parseMarkdownBlocks(text: string): MarkdownBlock[];
+		/**
+		 * Extract every fenced code block from Markdown, at any nesting depth.
+		 * @param markdown Markdown source (may be a partial, still-streaming message).
+		 * @returns Code blocks in source order; unterminated fences report `closed: false`.
+		 */
+		extractCodeBlocks(markdown: string): CodeBlock[];
+	}
+}
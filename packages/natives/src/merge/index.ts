@@ -0,0 +1,9 @@
+/**
+ * Three-way text merging built on the native line differ.
+ */
+
+import { native } from "../native";
+
+export type { MergeConflict, MergeOptions, MergeResult } from "./types";
+
+export const { merge3 } = native;
@@ -0,0 +1,70 @@
+/**
+ * Types for native three-way merging.
+ */
+
+/** Options for a three-way merge. */
+export interface MergeOptions {
+	/** Conflict style: "merge" (default) or "diff3" (includes the base section). */
+	style?: "merge" | "diff3";
+	/** Diff algorithm used against the base (default: "histogram"). */
+	algorithm?: "histogram" | "myers" | "myersMinimal";
+	/** Label after `<<<<<<<` (default: "ours"). */
+	oursLabel?: string;
+	/** Label after `|||||||` in diff3 style (default: "base"). */
+	baseLabel?: string;
+	/** Label after `>>>>>>>` (default: "theirs"). */
+	theirsLabel?: string;
+}
+
+/**
+ * A conflicting region of a three-way merge.
+ * Line numbers are 1-indexed; a side with 0 lines is an empty insertion point.
+ */
+export interface MergeConflict {
+	/** First line of the conflict block (the `<<<<<<<` marker) in the output. */
+	mergedStart: number;
+	/** Number of output lines occupied by the block, markers included. */
+	mergedLines: number;
+	/** Start line of the region in the base text. */
+	baseStart: number;
+	/** Number of base lines in the region. */
+	baseLines: number;
+	/** Start line of the region in our text. */
+	oursStart: number;
+	/** Number of our lines in the region. */
+	oursLines: number;
+	/** Start line of the region in their text. */
+	theirsStart: number;
+	/** Number of their lines in the region. */
+	theirsLines: number;
+	/** Base content of the region. */
+	base: string;
+	/** Our content of the region. */
+	ours: string;
+	/** Their content of the region. */
+	theirs: string;
+}
+
+/** Result of a three-way merge. */
+export interface MergeResult {
+	/** Merged text, with conflict markers around unresolved regions. */
+	merged: string;
+	/** Unresolved regions in output order. */
+	conflicts: MergeConflict[];
+	/** Whether the merge completed without conflicts. */
+	clean: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Three-way merge of `ours` and `theirs` against their common `base`.
+		 * @param base Common ancestor text.
+		 * @param ours Our revision (e.g. the user's edits).
+		 * @param theirs Their revision (e.g. the agent's edits).
+		 * @param options Conflict style, labels, and diff algorithm.
+		 * @returns Merged text with conflict markers and a structured conflict list.
+		 */
+		merge3(base: string, ours: string, theirs: string, options?: MergeOptions): MergeResult;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Terminal mouse report decoding powered by native bindings.
+ */
+
+import { native } from "../native";
+
+export type { MouseEvent, MouseEventKind } from "./types";
+
+export const { parseMouseEvent } = native;
@@ -0,0 +1,31 @@
+/**
+ * Types for terminal mouse report decoding.
+ */
+
+/** Mouse event kinds. */
+export type MouseEventKind = "press" | "release" | "drag" | "move" | "wheel";
+
+/** Decoded mouse report. */
+export interface MouseEvent {
+	/** Button: 0 left, 1 middle, 2 right, 3 none, 4-7 wheel up/down/left/right, 8-11 extra buttons. */
+	button: number;
+	/** 1-based column. */
+	x: number;
+	/** 1-based row. */
+	y: number;
+	/** Modifier bitmask (shift=1, alt=2, ctrl=4). */
+	modifiers: number;
+	/** Event kind. */
+	kind: MouseEventKind;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Decode a single SGR (1006), urxvt (1015), or X10 mouse report.
+		 * @param data Raw terminal input data.
+		 * @returns The decoded event or null when the input is not one mouse report.
+		 */
+		parseMouseEvent(data: string): MouseEvent | null;
+	}
+}
@@ -0,0 +1,10 @@
+/**
+ * Multi-pattern literal scanning with an Aho-Corasick automaton.
+ */
+
+import { native } from "../native";
+
+export type { MultiMatch, MultiMatcherConstructor, MultiMatcherOptions } from "./types";
+
+export const { MultiMatcher } = native;
+export type MultiMatcher = import("./types").MultiMatcher;
@@ -0,0 +1,56 @@
+/**
+ * Types for multi-pattern literal scanning.
+ */
+
+/** Options for a multi-pattern matcher. */
+export interface MultiMatcherOptions {
+	/** Match ASCII letters case-insensitively (default: false). */
+	caseInsensitive?: boolean;
+	/** Only report matches not adjacent to word characters (default: false). */
+	wholeWords?: boolean;
+	/** Report overlapping matches, including patterns inside longer ones (default: false). */
+	overlapping?: boolean;
+}
+
+/** A pattern occurrence. */
+export interface MultiMatch {
+	/** Index of the pattern in the constructor's list. */
+	patternId: number;
+	/** Start offset in UTF-16 code units; for `push`, relative to the chunk and negative when the match began earlier. */
+	start: number;
+	/** End offset in UTF-16 code units (exclusive). */
+	end: number;
+}
+
+/** Aho-Corasick scanner over many literal patterns. */
+export interface MultiMatcher {
+	/** Number of compiled patterns. */
+	get patternCount(): number;
+	/** Find pattern occurrences in `text`, ordered by start offset. */
+	scan(text: string): MultiMatch[];
+	/** Whether any pattern occurs in `text`. */
+	isMatch(text: string): boolean;
+	/**
+	 * Scan the next chunk of a stream. Matches spanning chunk boundaries are found and each is reported once;
+	 * a match near the chunk's end that more input could still change waits for the next chunk or `end()`.
+	 * Offsets are relative to this chunk's start.
+	 */
+	push(chunk: string): MultiMatch[];
+	/** Signal end of the stream; returns matches that were pending at the end of the last chunk. */
+	end(): MultiMatch[];
+	/** Forget the carried-over end of the stream. */
+	reset(): void;
+}
+
+/** Native multi-pattern matcher constructor. */
+export interface MultiMatcherConstructor {
+	/** Compile `patterns` into one automaton; throws if a pattern is empty. */
+	new (patterns: string[], options?: MultiMatcherOptions): MultiMatcher;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Scanner for hundreds of literal patterns in one pass, for classifying PTY output. */
+		MultiMatcher: MultiMatcherConstructor;
+	}
+}
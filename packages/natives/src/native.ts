@@ -16,19 +16,79 @@ import type { NativeBindings } from "./bindings";
 import { embeddedAddon } from "./embedded-addon";
 
 // Import types to trigger declaration merging
+import "./archive/types";
+import "./binary/types";
+import "./cache-store/types";
+import "./chart/types";
+import "./chunk/types";
 import "./clipboard/types";
+import "./compress/types";
+import "./db/types";
+import "./diff/types";
+import "./diff-render/types";
+import "./edit/types";
+import "./encoding/types";
+import "./env-diff/types";
+import "./eol/types";
+import "./find-up/types";
+import "./fuzzy/types";
+import "./git/types";
 import "./glob/types";
 import "./grep/types";
+import "./hash/types";
 import "./highlight/types";
 import "./html/types";
+import "./humanize/types";
+import "./ignore-matcher/types";
 import "./image/types";
+import "./image-cells/types";
+import "./imports/types";
+import "./indent/types";
+import "./input-decoder/types";
+import "./json/types";
+import "./jsonl/types";
 import "./keys/types";
+import "./line-count/types";
+import "./line-index/types";
+import "./links/types";
+import "./log-normalize/types";
+import "./lru/types";
+import "./markdown/types";
+import "./merge/types";
+import "./mouse/types";
+import "./multi-match/types";
+import "./natural/types";
+import "./offset-map/types";
+import "./patch/types";
 import "./ps/types";
 import "./pty/types";
+import "./raw-input/types";
+import "./read/types";
+import "./redact/types";
+import "./replace/types";
+import "./safe-regex/types";
+import "./screenshot/types";
 import "./shell/types";
+import "./shlex/types";
+import "./sixel/types";
+import "./snapshot/types";
+import "./sse/types";
+import "./symbols/types";
 import "./system-info/types";
+import "./tail/types";
+import "./temp/types";
+import "./term-screen/types";
+import "./terminal/types";
 import "./text/types";
+import "./text-buffer/types";
+import "./tokens/types";
+import "./transcript/types";
+import "./vector-index/types";
+import "./watch/types";
+import "./which/types";
+import "./words/types";
 import "./work/types";
+import "./write/types";
 
 export type { NativeBindings, TsFunc } from "./bindings";
 
@@ -178,7 +238,12 @@ function validateNative(bindings: NativeBindings, source: string): void {
 	checkFn("matchesKittySequence");
 	checkFn("executeShell");
 	checkFn("PtySession");
+	checkFn("Pty");
+	checkFn("TermScreen");
 	checkFn("Shell");
+	checkFn("JsonStream");
+	checkFn("LineIndex");
+	checkFn("TextBuffer");
 	checkFn("parseKey");
 	checkFn("matchesLegacySequence");
 	checkFn("parseKittySequence");
@@ -189,6 +254,119 @@ function validateNative(bindings: NativeBindings, source: string): void {
 	checkFn("getSystemInfo");
 	checkFn("getWorkProfile");
 	checkFn("invalidateFsScanCache");
+	checkFn("diffLines");
+	checkFn("refineHunk");
+	checkFn("merge3");
+	checkFn("fuzzyScore");
+	checkFn("suggest");
+	checkFn("countFileTokens");
+	checkFn("parseMarkdownBlocks");
+	checkFn("extractCodeBlocks");
+	checkFn("openJsonl");
+	checkFn("readFileSlice");
+	checkFn("tailFollow");
+	checkFn("detectEncoding");
+	checkFn("readFileText");
+	checkFn("detectEol");
+	checkFn("normalizeEol");
+	checkFn("isBinary");
+	checkFn("hexdump");
+	checkFn("replaceInFiles");
+	checkFn("gitDiff");
+	checkFn("gitLog");
+	checkFn("gitRepoInfo");
+	checkFn("gitStageHunks");
+	checkFn("gitUnstageHunks");
+	checkFn("shlexSplit");
+	checkFn("shlexQuote");
+	checkFn("whichAll");
+	checkFn("detectTerminal");
+	checkFn("encodeSixel");
+	checkFn("prepareImage");
+	checkFn("renderImageCells");
+	checkFn("renderSparkline");
+	checkFn("renderBars");
+	checkFn("diffScreens");
+	checkFn("redactSecrets");
+	checkFn("detectLinks");
+	checkFn("hashBuffer");
+	checkFn("hashFile");
+	checkFn("CacheStore");
+	checkFn("LruMap");
+	checkFn("compress");
+	checkFn("decompress");
+	checkFn("compressFile");
+	checkFn("decompressFile");
+	checkFn("listArchive");
+	checkFn("extractArchive");
+	checkFn("compareNatural");
+	checkFn("naturalSort");
+	checkFn("extractSymbols");
+	checkFn("SymbolIndex");
+	checkFn("extractImports");
+	checkFn("buildImportGraph");
+	checkFn("countLines");
+	checkFn("chunkFile");
+	checkFn("VectorIndex");
+	checkFn("openDb");
+	checkFn("Database");
+	checkFn("snapshotFiles");
+	checkFn("diffSnapshot");
+	checkFn("FileSnapshot");
+	checkFn("detectChanges");
+	checkFn("watchAndTrigger");
+	checkFn("createTempFile");
+	checkFn("createTempDir");
+	checkFn("cleanupTempPaths");
+	checkFn("TempPath");
+	checkFn("writeFileAtomic");
+	checkFn("applyEdit");
+	checkFn("applyPatch");
+	checkFn("detectIndent");
+	checkFn("globToRegex");
+	checkFn("explainGlob");
+	checkFn("renderDiff");
+	checkFn("splitIdentifier");
+	checkFn("wordBoundaries");
+	checkFn("renderToSvg");
+	checkFn("renderToPng");
+	checkFn("makeFileLink");
+	checkFn("hyperlink");
+	checkFn("SseParser");
+	checkFn("compileRegex");
+	checkFn("MultiMatcher");
+	checkFn("normalizeLog");
+	checkFn("openRawInput");
+	checkFn("notify");
+	checkFn("findUp");
+	checkFn("offsetMapUtf16");
+	checkFn("exportTranscript");
+	checkFn("snapshotEnv");
+	checkFn("diffEnv");
+	checkFn("parseShellExports");
+	checkFn("formatBytes");
+	checkFn("formatDuration");
+	checkFn("formatRelativeTime");
+	checkFn("wrapToWidth");
+	checkFn("visibleWidths");
+	checkFn("globStream");
+	checkFn("stripAnsi");
+	checkFn("sanitizeAnsi");
+	checkFn("parseKeyEvent");
+	checkFn("parseMouseEvent");
+	checkFn("InputDecoder");
+	checkFn("layoutLine");
+	checkFn("graphemes");
+	checkFn("graphemeAt");
+	checkFn("setTextOptions");
+	checkFn("diffWords");
+	checkFn("parsePatch");
+	checkFn("Watcher");
+	checkFn("countMatches");
+	checkFn("IgnoreMatcher");
+	checkFn("padToWidth");
+	checkFn("joinStyled");
+	checkFn("wrapWithHyperlink");
 
 	if (missing.length) {
 		throw new Error(
@@ -0,0 +1,7 @@
+/**
+ * Natural-order (numeric-aware, case-folding) string sorting.
+ */
+
+import { native } from "../native";
+
+export const { compareNatural, naturalSort } = native;
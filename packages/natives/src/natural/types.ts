@@ -0,0 +1,18 @@
+/**
+ * Types for natural-order string comparison.
+ */
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Compare two strings in natural order: digit runs by numeric value, letters case-folded, locale-independent.
+		 * @returns -1, 0, or 1; usable directly as an `Array.prototype.sort` comparator.
+		 */
+		compareNatural(a: string, b: string): number;
+		/**
+		 * Sort strings in natural order (`file2` before `file10`).
+		 * @returns A new sorted array; the input is not modified.
+		 */
+		naturalSort(strings: string[]): string[];
+	}
+}
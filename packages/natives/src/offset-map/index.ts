@@ -0,0 +1,9 @@
+/**
+ * UTF-8 byte to UTF-16 offset conversion for JS interop.
+ */
+
+import { native } from "../native";
+
+export type { Utf16OffsetMap } from "./types";
+
+export const { offsetMapUtf16 } = native;
@@ -0,0 +1,35 @@
+/**
+ * Types for UTF-8 byte to UTF-16 offset conversion.
+ */
+
+/**
+ * Offset conversion between UTF-8 bytes (as reported by `chunkFile`, symbols, and `LineIndex`) and UTF-16 code
+ * units (as used by JS string APIs) for one text.
+ */
+export interface Utf16OffsetMap {
+	/** Length of the text in UTF-8 bytes. */
+	get byteLength(): number;
+	/** Length of the text in UTF-16 code units, i.e. the JS string length. */
+	get utf16Length(): number;
+	/** Whether the text is ASCII, so both offsets are always equal. */
+	get isAscii(): boolean;
+	/** Convert a byte offset to a UTF-16 offset; an offset inside a character maps to its start. Throws past the end. */
+	toUtf16(offset: number): number;
+	/** Convert a UTF-16 offset to a byte offset; an offset inside a surrogate pair maps to its start. Throws past end. */
+	toByte(offset: number): number;
+	/** Convert many byte offsets in one call. Throws if any is past the end. */
+	toUtf16Many(offsets: number[]): number[];
+	/** Convert a 0-indexed byte column on a 1-indexed line to a UTF-16 column on the same line. */
+	columnToUtf16(line: number, column: number): number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Build a map converting UTF-8 byte offsets in `text` to UTF-16 offsets and back.
+		 * @param text The text the offsets were computed on; invalid UTF-8 is measured as `TextDecoder` decodes it.
+		 * @returns The offset map.
+		 */
+		offsetMapUtf16(text: string | Uint8Array): Utf16OffsetMap;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Unified diff parsing and application with fuzz and whitespace-tolerant hunk matching.
+ */
+
+import { native } from "../native";
+
+export type { ApplyPatchOptions, ApplyPatchResult, PatchConflict, PatchFile, PatchHunkResult } from "./types";
+
+export const { applyPatch, parsePatch } = native;
@@ -0,0 +1,92 @@
+/**
+ * Types for native unified diff parsing and application.
+ */
+
+import type { DiffHunk } from "../diff/types";
+
+/** Options for applying a patch. */
+export interface ApplyPatchOptions {
+	/** Context lines that may be dropped from each end of a hunk when it does not match exactly (default: 0). */
+	fuzz?: number;
+	/** Compare lines ignoring leading and trailing whitespace when exact matching fails (default: false). */
+	tolerant?: boolean;
+	/** Furthest a hunk may move from the line its header names (default: unlimited). */
+	maxOffset?: number;
+}
+
+/** Why a hunk did not apply. Line numbers are 1-indexed and refer to the original text. */
+export interface PatchConflict {
+	/** Context and removed lines the hunk expected, in order. */
+	expected: string[];
+	/** File lines at the position matching the most expected lines (nearest the header on ties). */
+	actual: string[];
+	/** Line where `actual` starts. */
+	startLine: number;
+	/** First line that differs from what the hunk expected. */
+	mismatchLine: number;
+}
+
+/** How one hunk was applied. Line numbers are 1-indexed and refer to the original text. */
+export interface PatchHunkResult {
+	/** Start line of the hunk's old side as written in its header. */
+	oldStart: number;
+	/** Whether the hunk was applied. */
+	applied: boolean;
+	/** First matched line (the line after which text was inserted for pure insertions); unset when not applied. */
+	startLine?: number;
+	/** Last matched line, inclusive. */
+	endLine?: number;
+	/** Lines between the matched position and the one the header names (positive when the hunk moved down). */
+	offset: number;
+	/** Context lines dropped from each end to make the hunk match. */
+	fuzz: number;
+	/** Lines that matched only with whitespace ignored. */
+	whitespaceLines: number[];
+	/** Expected and actual lines when the hunk did not apply. */
+	conflict?: PatchConflict;
+}
+
+/** Result of applying a patch. */
+export interface ApplyPatchResult {
+	/** Patched text (hunks that did not apply are skipped). */
+	text: string;
+	/** Whether every hunk was applied. */
+	applied: boolean;
+	/** Whether every hunk applied at its stated position without fuzz or whitespace tolerance. */
+	exact: boolean;
+	/** Per-hunk outcome in patch order. */
+	hunks: PatchHunkResult[];
+}
+
+/** One file of a parsed patch. */
+export interface PatchFile {
+	/** Path from the `---` header as written (unset for `/dev/null` or when the patch has no file headers). */
+	oldPath?: string;
+	/** Path from the `+++` header as written (unset for `/dev/null` or when the patch has no file headers). */
+	newPath?: string;
+	/** Hunks in patch order, with line numbers from their headers. */
+	hunks: DiffHunk[];
+	/** Whether the old file's last line has no trailing newline. */
+	oldNoNewlineAtEnd: boolean;
+	/** Whether the new file's last line has no trailing newline. */
+	newNoNewlineAtEnd: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Parse a unified diff touching one or more files into per-file hunks.
+		 * @param patch Unified diff; file headers are optional.
+		 * @returns One entry per file in patch order, with hunks shaped like those of `diffLines`.
+		 */
+		parsePatch(patch: string): PatchFile[];
+		/**
+		 * Apply a single-file unified diff to text, tolerating drift, fuzz, and whitespace changes.
+		 * @param content Text to patch.
+		 * @param patch Unified diff; file headers are optional.
+		 * @param options Fuzz, whitespace tolerance, and maximum drift.
+		 * @returns Patched text and how each hunk matched, so inexact applications can be confirmed.
+		 */
+		applyPatch(content: string, patch: string, options?: ApplyPatchOptions): ApplyPatchResult;
+	}
+}
@@ -4,7 +4,14 @@
 
 import { native } from "../native";
 
-export type { PtyRunResult, PtySessionConstructor, PtyStartOptions } from "./types";
+export type {
+	PtyConstructor,
+	PtyRunResult,
+	PtySessionConstructor,
+	PtySpawnOptions,
+	PtyStartOptions,
+} from "./types";
 
-export const { PtySession } = native;
+export const { Pty, PtySession } = native;
+export type Pty = import("./types").Pty;
 export type PtySession = import("./types").PtySession;
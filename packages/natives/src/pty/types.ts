@@ -49,9 +49,47 @@ export interface PtySessionConstructor {
 	new (): PtySession;
 }
 
+/**
+ * Options for spawning a program in a pseudo-terminal.
+ */
+export interface PtySpawnOptions extends Cancellable {
+	/** PTY column count (default: 120). */
+	cols?: number;
+	/** PTY row count (default: 40). */
+	rows?: number;
+	/** Working directory for the program. */
+	cwd?: string;
+	/** Environment variables for the program. */
+	env?: Record<string, string>;
+}
+
+/** Pseudo-terminal running a program with raw byte output. */
+export interface Pty {
+	/** Spawn `cmd` with `args` and stream output bytes until it exits. */
+	spawn(
+		cmd: string,
+		args?: string[],
+		options?: PtySpawnOptions,
+		onData?: TsFunc<Uint8Array>,
+	): Promise<PtyRunResult>;
+	/** Write input to the program; strings are sent as UTF-8. */
+	write(data: string | Uint8Array): void;
+	/** Resize the PTY. */
+	resize(cols: number, rows: number): void;
+	/** Force-kill the running program. */
+	kill(): void;
+}
+
+/** Native PTY constructor. */
+export interface PtyConstructor {
+	new (): Pty;
+}
+
 declare module "../bindings" {
 	interface NativeBindings {
 		/** Stateful PTY session constructor for interactive terminal passthrough. */
 		PtySession: PtySessionConstructor;
+		/** Pseudo-terminal constructor for spawning programs with raw byte output. */
+		Pty: PtyConstructor;
 	}
 }
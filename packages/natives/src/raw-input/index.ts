@@ -0,0 +1,9 @@
+/**
+ * Raw terminal input read natively from stdin.
+ */
+
+import { native } from "../native";
+
+export type { RawInputChunk, RawInputOptions, RawInputResult } from "./types";
+
+export const { openRawInput } = native;
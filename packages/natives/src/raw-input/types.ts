@@ -0,0 +1,44 @@
+/**
+ * Types for raw terminal input.
+ */
+
+import type { Cancellable, TsFunc } from "../bindings";
+
+/** Options for reading raw input. `signal`/`timeoutMs` stop reading. */
+export interface RawInputOptions extends Cancellable {
+	/** Minimum bytes a read waits for (default: 1); values above 1 can delay cancellation until more input arrives. */
+	vmin?: number;
+	/** Inter-byte timeout in tenths of a second (default: 0, no timeout). */
+	vtime?: number;
+}
+
+/** Bytes returned by one read of stdin. */
+export interface RawInputChunk {
+	/** Raw input bytes. */
+	data: Uint8Array;
+	/** Milliseconds since the Unix epoch, with microsecond precision, taken when the read returned. */
+	timestamp: number;
+}
+
+/** Summary returned when reading stops. */
+export interface RawInputResult {
+	/** Number of chunks delivered. */
+	chunks: number;
+	/** Total bytes delivered. */
+	bytes: number;
+	/** Whether reading stopped because stdin reached end of file. */
+	eof: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Put the terminal in raw mode and stream stdin chunks, stamped on arrival, until the signal aborts, the
+		 * timeout elapses, or stdin ends. Terminal settings are restored afterwards.
+		 * @param options termios read batching (`vmin`, `vtime`) and cancellation.
+		 * @param onInput Callback receiving each chunk as it is read.
+		 * @returns Summary of delivered chunks.
+		 */
+		openRawInput(options: RawInputOptions | undefined, onInput: TsFunc<RawInputChunk>): Promise<RawInputResult>;
+	}
+}
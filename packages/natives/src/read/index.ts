@@ -0,0 +1,9 @@
+/**
+ * Bounded file reads by line range.
+ */
+
+import { native } from "../native";
+
+export type { ReadFileSliceOptions, ReadFileSliceResult } from "./types";
+
+export const { readFileSlice } = native;
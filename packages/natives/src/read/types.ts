@@ -0,0 +1,45 @@
+/**
+ * Types for bounded file reads.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Options for reading a line range of a file. */
+export interface ReadFileSliceOptions extends Cancellable {
+	/** First line to return, 1-indexed (default: 1). */
+	startLine?: number;
+	/** Last line to return, 1-indexed and inclusive (default: end of file). */
+	endLine?: number;
+	/** Maximum bytes of line content to return (default: unlimited). */
+	maxBytes?: number;
+	/** Decoding for lines that are not valid UTF-8 (default: "lossy"). */
+	encodingFallback?: "lossy" | "latin1";
+}
+
+/** Result of reading a line range of a file. */
+export interface ReadFileSliceResult {
+	/** Lines without their terminators. */
+	lines: string[];
+	/** Visible terminal width of each line. */
+	widths: number[];
+	/** 1-indexed number of the first returned line. */
+	startLine: number;
+	/** Whether `maxBytes` cut the read short. */
+	truncated: boolean;
+	/** Whether the file has lines after the last returned one. */
+	hasMore: boolean;
+	/** Whether any line needed the encoding fallback. */
+	fallbackUsed: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Read a line range of a file with a byte budget, without loading the whole file.
+		 * @param path File to read.
+		 * @param options Line range, byte budget, and encoding fallback.
+		 * @returns The requested lines, their visible widths, and truncation flags.
+		 */
+		readFileSlice(path: string, options?: ReadFileSliceOptions): Promise<ReadFileSliceResult>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Secret detection and redaction for outbound text.
+ */
+
+import { native } from "../native";
+
+export type { RedactOptions, RedactResult, SecretMatch } from "./types";
+
+export const { redactSecrets } = native;
@@ -0,0 +1,43 @@
+/**
+ * Types for secret redaction.
+ */
+
+/** Options for redacting secrets. */
+export interface RedactOptions {
+	/** Extra regexes to redact (kind "custom"); a pattern with a capture group redacts only the first group. */
+	patterns?: string[];
+	/** Also redact long high-entropy tokens not matched by any rule (default: false). */
+	entropyCheck?: boolean;
+	/** Replacement text; "{kind}" expands to the finding's kind (default: "[REDACTED:{kind}]"). */
+	replacement?: string;
+}
+
+/** A secret found in the input. */
+export interface SecretMatch {
+	/** Rule that matched (e.g. "aws_access_key", "private_key", "high_entropy", "custom"). */
+	kind: string;
+	/** Start offset in the input, in UTF-16 code units. */
+	start: number;
+	/** End offset in the input, in UTF-16 code units (exclusive). */
+	end: number;
+}
+
+/** Result of redacting secrets. */
+export interface RedactResult {
+	/** Input with every finding replaced. */
+	text: string;
+	/** Findings in input order. */
+	secrets: SecretMatch[];
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect and redact API keys, tokens, private keys, and (optionally) high-entropy strings.
+		 * @param text Text to scan.
+		 * @param options Extra patterns, entropy check, and replacement text.
+		 * @returns Redacted text and the location of each finding in the input.
+		 */
+		redactSecrets(text: string, options?: RedactOptions): RedactResult;
+	}
+}
@@ -0,0 +1,19 @@
+/**
+ * Workspace-wide search and replace.
+ */
+
+import { native } from "../native";
+import type { FileReplacement, ReplaceOptions, ReplaceResult } from "./types";
+
+export type { FileReplacement, ReplaceOptions, ReplaceResult };
+
+/**
+ * Replace a pattern across files with optional per-file progress callback.
+ */
+export async function replaceInFiles(
+	options: ReplaceOptions,
+	onFile?: (file: FileReplacement) => void,
+): Promise<ReplaceResult> {
+	const cb = onFile ? (err: Error | null, f: FileReplacement) => !err && onFile(f) : undefined;
+	return native.replaceInFiles(options, cb);
+}
@@ -0,0 +1,70 @@
+/**
+ * Types for workspace-wide search and replace.
+ */
+
+import type { Cancellable, TsFunc } from "../bindings";
+import type { DiffHunk } from "../diff/types";
+
+/** Options for replacing a pattern across files. */
+export interface ReplaceOptions extends Cancellable {
+	/** Text or regex to search for. */
+	pattern: string;
+	/** Replacement text. With `regex`, `$1`/`${name}` expand capture groups and `$$` is a literal `$`. */
+	replacement: string;
+	/** Directory or file to rewrite (default: current directory). */
+	path?: string;
+	/** Glob filter for filenames (e.g., "*.ts"). */
+	glob?: string;
+	/** Filter by file type (e.g., "js", "py", "rust"). */
+	type?: string;
+	/** Treat `pattern` as a regex instead of literal text (default: false). */
+	regex?: boolean;
+	/** Case-insensitive matching. */
+	ignoreCase?: boolean;
+	/** Include hidden files (default: true). */
+	hidden?: boolean;
+	/** Compute hunks without writing any file (default: false). */
+	dryRun?: boolean;
+	/** Copy each original to `<file>.bak` before replacing it (default: false). */
+	backup?: boolean;
+	/** Unchanged context lines around each hunk (default: 3). */
+	context?: number;
+}
+
+/** Changes made (or previewed) in one file. */
+export interface FileReplacement {
+	/** Path relative to the search root, using forward slashes. */
+	path: string;
+	/** Number of matches replaced. */
+	replacements: number;
+	/** Line hunks describing the change. */
+	hunks: DiffHunk[];
+	/** Backup file written before replacing, if any. */
+	backupPath?: string;
+}
+
+/** Result of a search-and-replace run. */
+export interface ReplaceResult {
+	/** Changed files, sorted by path. */
+	files: FileReplacement[];
+	/** Total matches replaced across all files. */
+	totalReplacements: number;
+	/** Number of files considered. */
+	filesSearched: number;
+	/** Whether changes were written to disk (false for dry runs). */
+	applied: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Replace a pattern across files, or preview the change with `dryRun`.
+		 * Writes are staged and renamed into place, rolling back (and removing backups) on failure;
+		 * files modified since they were read abort the replacement before any write.
+		 * @param options Pattern, replacement, file filters, and write mode.
+		 * @param onFile Optional callback invoked per changed (or previewed) file, once all files are written.
+		 * @returns Per-file hunks and replacement counts.
+		 */
+		replaceInFiles(options: ReplaceOptions, onFile?: TsFunc<FileReplacement>): Promise<ReplaceResult>;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Linear-time regular expressions for user-supplied search patterns.
+ */
+
+import { native } from "../native";
+
+export type { RegexFindResult, RegexMatch, RegexOptions, SafeRegex } from "./types";
+
+export const { compileRegex } = native;
@@ -0,0 +1,66 @@
+/**
+ * Types for linear-time regular expressions.
+ */
+
+/** Resource budget for a compiled regex. */
+export interface RegexOptions {
+	/** Maximum compiled program size in bytes (default: 1 MiB). */
+	sizeLimit?: number;
+	/** Time limit for each `findAll` or `replaceAll` call in milliseconds (default: 250). */
+	timeoutMs?: number;
+	/** Maximum matches `findAll` returns (default: 10000). */
+	maxMatches?: number;
+}
+
+/** One match found by `findAll`. */
+export interface RegexMatch {
+	/** Start offset in UTF-16 code units. */
+	start: number;
+	/** End offset in UTF-16 code units (exclusive). */
+	end: number;
+	/** Matched text. */
+	text: string;
+	/** Capture groups 1..n; null for groups that did not participate. */
+	groups: (string | null)[];
+}
+
+/** Result of `findAll`. */
+export interface RegexFindResult {
+	/** Matches in order of position. */
+	matches: RegexMatch[];
+	/** Whether `maxMatches` stopped the search early. */
+	limitReached: boolean;
+}
+
+/** A compiled linear-time regular expression with a matching budget. */
+export interface SafeRegex {
+	/** The pattern source. */
+	get source(): string;
+	/** The flags the regex was compiled with. */
+	get flags(): string;
+	/** Whether the pattern matches anywhere in `text`. */
+	test(text: string): boolean;
+	/**
+	 * Find all non-overlapping matches in `text`.
+	 * Throws when the time budget runs out.
+	 */
+	findAll(text: string): RegexFindResult;
+	/**
+	 * Replace every match in `text`. The replacement uses JS patterns: `$&`, `` $` ``, `$'`, `$1`-`$99`,
+	 * `$<name>`, and `$$`. Throws when the time budget runs out.
+	 */
+	replaceAll(text: string, replacement: string): string;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Compile a user-supplied pattern into a linear-time regex that cannot hang on hostile input.
+		 * @param pattern Rust `regex` syntax: common JS syntax without lookaround or backreferences.
+		 * @param flags Any of `g`, `i`, `m`, `s`, `u`, `x`; matching is always global.
+		 * @param options Size, time, and match-count budget.
+		 * @throws On invalid syntax, unsupported flags, or a pattern over the size limit.
+		 */
+		compileRegex(pattern: string, flags?: string, options?: RegexOptions): SafeRegex;
+	}
+}
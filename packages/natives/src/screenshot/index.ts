@@ -0,0 +1,9 @@
+/**
+ * Terminal screenshots: styled lines rendered to SVG or PNG.
+ */
+
+import { native } from "../native";
+
+export type { ScreenshotFont, ScreenshotOptions, ScreenshotTheme } from "./types";
+
+export const { renderToSvg, renderToPng } = native;
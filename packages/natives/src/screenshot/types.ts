@@ -0,0 +1,60 @@
+/**
+ * Types for terminal screenshots.
+ */
+
+/** Colors for a screenshot, as "#rgb" or "#rrggbb". */
+export interface ScreenshotTheme {
+	/** Background color (default: "#1e1e1e"). */
+	background?: string;
+	/** Default text color (default: "#d4d4d4"). */
+	foreground?: string;
+	/** The 16 basic colors, normal then bright (default: a VS Code-like dark palette). */
+	palette?: string[];
+}
+
+/** Font settings for a screenshot. */
+export interface ScreenshotFont {
+	/** CSS font family for SVG output (default: a monospace stack). */
+	family?: string;
+	/** Font size in pixels (default: 14). */
+	size?: number;
+	/** Line height as a multiple of the font size (default: 1.4). */
+	lineHeight?: number;
+	/** Cell width in pixels (default: 0.6 × size for SVG, the font's advance width for PNG). */
+	cellWidth?: number;
+	/** TTF or OTF file to rasterize with; required by `renderToPng`. */
+	path?: string;
+}
+
+/** Options for rendering terminal lines to an image. */
+export interface ScreenshotOptions {
+	/** Colors (default: dark theme). */
+	theme?: ScreenshotTheme;
+	/** Font settings. */
+	font?: ScreenshotFont;
+	/** Space around the text in pixels (default: 16). */
+	padding?: number;
+	/** Grid width in cells (default: the widest line). */
+	columns?: number;
+	/** Pixel ratio for PNG output, e.g. 2 for high-DPI (default: 1). */
+	scale?: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Render styled terminal lines as a standalone SVG image.
+		 * @param lines Lines with ANSI escape sequences, one per row.
+		 * @param options Theme, font, padding, and grid width.
+		 * @returns SVG markup.
+		 */
+		renderToSvg(lines: string[], options?: ScreenshotOptions): string;
+		/**
+		 * Rasterize styled terminal lines to a PNG on the worker pool.
+		 * @param lines Lines with ANSI escape sequences, one per row.
+		 * @param options Theme, font (`font.path` is required), padding, grid width, and scale.
+		 * @returns The encoded PNG bytes.
+		 */
+		renderToPng(lines: string[], options?: ScreenshotOptions): Promise<Uint8Array>;
+	}
+}
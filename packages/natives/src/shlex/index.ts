@@ -0,0 +1,9 @@
+/**
+ * Shell-style argument splitting and quoting (POSIX and cmd.exe).
+ */
+
+import { native } from "../native";
+
+export type { ShellMode } from "./types";
+
+export const { shlexSplit, shlexQuote } = native;
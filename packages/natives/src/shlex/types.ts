@@ -0,0 +1,26 @@
+/**
+ * Types for shell-style argument splitting and quoting.
+ */
+
+/** Command-line dialect: POSIX shell or cmd.exe. */
+export type ShellMode = "posix" | "cmd";
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Split a command line into arguments.
+		 * @param command Command line to split.
+		 * @param mode Dialect (default: "posix").
+		 * @returns The arguments, with quotes and escapes removed.
+		 * @throws In POSIX mode, on unterminated quotes or a trailing backslash.
+		 */
+		shlexSplit(command: string, mode?: ShellMode): string[];
+		/**
+		 * Quote arguments into a single command line.
+		 * @param args Arguments to quote, program first.
+		 * @param mode Dialect (default: "posix").
+		 * @returns A command line that `shlexSplit` reads back as `args`.
+		 */
+		shlexQuote(args: string[], mode?: ShellMode): string;
+	}
+}
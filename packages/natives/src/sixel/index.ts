@@ -0,0 +1,9 @@
+/**
+ * Sixel encoding for inline terminal images.
+ */
+
+import { native } from "../native";
+
+export type { SixelOptions } from "./types";
+
+export const { encodeSixel } = native;
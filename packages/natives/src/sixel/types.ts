@@ -0,0 +1,26 @@
+/**
+ * Types for sixel image encoding.
+ */
+
+/** Options for sixel encoding. */
+export interface SixelOptions {
+	/** Palette size, 2-256 (default: 256). */
+	maxColors?: number;
+	/** Apply Floyd-Steinberg dithering (default: true). */
+	dither?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Encode an RGBA image as a sixel string on the worker pool.
+		 * Pixels with alpha below 128 are left transparent.
+		 * @param rgba Pixel data, 4 bytes per pixel, row-major.
+		 * @param width Image width in pixels.
+		 * @param height Image height in pixels.
+		 * @param options Palette size and dithering.
+		 * @returns A complete sixel sequence ready to write to the terminal.
+		 */
+		encodeSixel(rgba: Uint8Array, width: number, height: number, options?: SixelOptions): Promise<string>;
+	}
+}
@@ -0,0 +1,22 @@
+/**
+ * File snapshots and session change sets.
+ */
+
+import { native } from "../native";
+
+export type {
+	DiffSnapshotOptions,
+	FileSnapshotConstructor,
+	SnapshotChange,
+	SnapshotChangeStatus,
+	SnapshotEntry,
+	SnapshotOptions,
+} from "./types";
+
+/** FileSnapshot class. Use snapshotFiles() to create instances. */
+export const FileSnapshot = native.FileSnapshot;
+
+/** FileSnapshot instance type. */
+export type FileSnapshot = import("./types").FileSnapshot;
+
+export const { snapshotFiles, diffSnapshot } = native;
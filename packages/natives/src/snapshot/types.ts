@@ -0,0 +1,80 @@
+/**
+ * Types for file snapshots and session change sets.
+ */
+
+import type { Cancellable } from "../bindings";
+import type { DiffHunk } from "../diff/types";
+
+/** Options for taking a file snapshot. */
+export interface SnapshotOptions extends Cancellable {
+	/** Include hidden files when walking directories (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore files when walking directories (default: true). */
+	gitignore?: boolean;
+}
+
+/** Options for diffing against a snapshot. */
+export interface DiffSnapshotOptions extends Cancellable {
+	/** Unchanged context lines around each hunk (default: 3). */
+	context?: number;
+	/** Diff algorithm (default: "histogram"). */
+	algorithm?: "histogram" | "myers" | "myersMinimal";
+}
+
+/** A file recorded in a snapshot. */
+export interface SnapshotEntry {
+	/** Path as listed, or joined onto the listed directory. */
+	path: string;
+	/** BLAKE3 hex digest of the content. */
+	hash: string;
+	/** Size in bytes. */
+	size: number;
+}
+
+/** How a file changed since the snapshot. */
+export type SnapshotChangeStatus = "modified" | "created" | "deleted";
+
+/** A file that changed since a snapshot was taken. */
+export interface SnapshotChange {
+	/** Path as listed, or joined onto the listed directory. */
+	path: string;
+	/** Change status. */
+	status: SnapshotChangeStatus;
+	/** Whether either side is binary or too large to diff (no hunks). */
+	binary: boolean;
+	/** Hunks in ascending line order. */
+	hunks: DiffHunk[];
+	/** Number of added lines. */
+	added: number;
+	/** Number of removed lines. */
+	removed: number;
+}
+
+/** Content hashes of a set of files at one point in time. */
+export interface FileSnapshot {
+	/** Number of files present when the snapshot was taken. */
+	get fileCount(): number;
+	/** Files present when the snapshot was taken, in path order. */
+	entries(): SnapshotEntry[];
+}
+
+export interface FileSnapshotConstructor {
+	prototype: FileSnapshot;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** File snapshot handle class; create instances with `snapshotFiles()`. */
+		FileSnapshot: FileSnapshotConstructor;
+		/**
+		 * Record the content of files and directories for a later `diffSnapshot()`.
+		 * Directories are walked recursively; listed files that do not exist yet are recorded as absent.
+		 */
+		snapshotFiles(paths: string[], options?: SnapshotOptions): Promise<FileSnapshot>;
+		/**
+		 * Report files modified, created, or deleted since `snapshot` was taken.
+		 * @returns Changes in path order, with line hunks for text files up to 2 MiB.
+		 */
+		diffSnapshot(snapshot: FileSnapshot, options?: DiffSnapshotOptions): Promise<SnapshotChange[]>;
+	}
+}
@@ -0,0 +1,10 @@
+/**
+ * Incremental Server-Sent Events parsing for streamed LLM responses.
+ */
+
+import { native } from "../native";
+
+export type { SseEvent, SseParserConstructor } from "./types";
+
+export const { SseParser } = native;
+export type SseParser = import("./types").SseParser;
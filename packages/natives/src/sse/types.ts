@@ -0,0 +1,47 @@
+/**
+ * Types for native Server-Sent Events parsing.
+ */
+
+/** An event dispatched by a blank line. */
+export interface SseEvent {
+	/** Event type from the `event` field (default: "message"). */
+	event: string;
+	/** `data` fields joined with newlines. */
+	data: string;
+	/** Last event ID seen on the stream so far, if any. */
+	id?: string;
+	/** Reconnection time in milliseconds, if this event set one. */
+	retry?: number;
+}
+
+/** Incremental `text/event-stream` parser instance. */
+export interface SseParser {
+	/**
+	 * Feed the next chunk of the response body; strings are processed as UTF-8.
+	 * Returns the events dispatched within this chunk, in stream order.
+	 */
+	push(chunk: string | Uint8Array): SseEvent[];
+	/**
+	 * Signal end of the stream. A final unterminated line is processed, and an event still waiting for its
+	 * blank line is returned rather than discarded.
+	 */
+	end(): SseEvent | null;
+	/** ID of the last event received, as sent in `Last-Event-ID` on reconnect. */
+	get lastEventId(): string | null;
+	/** Most recent reconnection time in milliseconds sent by the server. */
+	get retry(): number | null;
+	/** Discard all buffered input and state, including the last event ID. */
+	reset(): void;
+}
+
+/** Native SSE parser constructor. */
+export interface SseParserConstructor {
+	new (): SseParser;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Incremental parser for streamed `text/event-stream` responses. */
+		SseParser: SseParserConstructor;
+	}
+}
@@ -0,0 +1,25 @@
+/**
+ * Symbol extraction (functions, classes, top-level definitions) via tree-sitter,
+ * and a workspace symbol index for go-to-symbol.
+ */
+
+import { native } from "../native";
+
+export type {
+	CodeSymbol,
+	SymbolIndexConstructor,
+	SymbolIndexOptions,
+	SymbolIndexUpdate,
+	SymbolKind,
+	SymbolLanguage,
+	SymbolMatch,
+	SymbolQueryOptions,
+} from "./types";
+
+export const { extractSymbols } = native;
+
+/** SymbolIndex class. Use SymbolIndex.build() to create instances. */
+export const SymbolIndex = native.SymbolIndex;
+
+/** SymbolIndex instance type. */
+export type SymbolIndex = import("./types").SymbolIndex;
@@ -0,0 +1,132 @@
+/**
+ * Types for symbol extraction and the workspace symbol index.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Language understood by symbol extraction (aliases such as "ts", "py", "rs" are also accepted). */
+export type SymbolLanguage = "rust" | "typescript" | "tsx" | "javascript" | "python" | "go";
+
+/** Kind of definition reported by `extractSymbols`. */
+export type SymbolKind =
+	| "function"
+	| "method"
+	| "class"
+	| "struct"
+	| "enum"
+	| "interface"
+	| "type"
+	| "module"
+	| "impl"
+	| "macro"
+	| "constant"
+	| "variable";
+
+/** A definition found in a source file. */
+export interface CodeSymbol {
+	/** Symbol name (for Rust `impl` blocks, the implementing type). */
+	name: string;
+	/** Definition kind. */
+	kind: SymbolKind;
+	/** Name of the nearest enclosing definition, if any. */
+	parent?: string;
+	/** Definition head up to its body, whitespace-collapsed (e.g. `fn parse(input: &str) -> Result<Ast>`). */
+	signature: string;
+	/** First line of the definition (1-based). */
+	startLine: number;
+	/** Last line of the definition (1-based, inclusive). */
+	endLine: number;
+	/** Start of the definition as a UTF-8 byte offset. */
+	startByte: number;
+	/** End of the definition as a UTF-8 byte offset (exclusive). */
+	endByte: number;
+}
+
+/** Options for building a symbol index. */
+export interface SymbolIndexOptions extends Cancellable {
+	/** Include hidden files and directories (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore and related ignore files (default: true). */
+	gitignore?: boolean;
+	/** Skip files larger than this many bytes (default: 1 MiB). */
+	maxFileSize?: number;
+	/** File the index is loaded from and saved to, relative to the root unless absolute. */
+	cachePath?: string;
+}
+
+/** Options for querying a symbol index. */
+export interface SymbolQueryOptions {
+	/** Only return symbols of this kind. */
+	kind?: SymbolKind;
+	/** Fuzzy-match the name instead of requiring an exact match (default: false). Smart-case. */
+	fuzzy?: boolean;
+	/** Maximum number of results (default: 100). */
+	limit?: number;
+}
+
+/** A symbol found by `SymbolIndex.querySymbols`. */
+export interface SymbolMatch {
+	/** File containing the symbol, relative to the index root with forward slashes. */
+	path: string;
+	/** The symbol. */
+	symbol: CodeSymbol;
+	/** Fuzzy score (higher is better); 0 for exact matches. */
+	score: number;
+}
+
+/** Result of applying updates to a symbol index. */
+export interface SymbolIndexUpdate {
+	/** Files whose symbols were (re-)extracted. */
+	indexed: number;
+	/** Files dropped from the index. */
+	removed: number;
+}
+
+/** In-memory index of the symbols defined across a workspace. */
+export interface SymbolIndex {
+	/** Number of indexed files. */
+	get fileCount(): number;
+	/** Number of indexed symbols. */
+	get symbolCount(): number;
+	/**
+	 * Find symbols by name: exact matches, or fuzzy matches when `fuzzy` is set.
+	 * @returns Matches best first; ties ordered by name length, path, and position.
+	 */
+	querySymbols(name: string, options?: SymbolQueryOptions): SymbolMatch[];
+	/** Symbols of one indexed file, ordered by position; `null` when the file is not indexed. */
+	symbolsIn(path: string): CodeSymbol[] | null;
+	/**
+	 * Apply watcher events: re-extract the given files or directories that exist and drop those that no longer do.
+	 * Paths may be absolute or relative to the root; paths outside the root are ignored.
+	 */
+	update(paths: string[]): Promise<SymbolIndexUpdate>;
+	/** Re-walk the root, re-extracting changed files and dropping files that disappeared. */
+	refresh(): Promise<SymbolIndexUpdate>;
+	/** Write the index to its `cachePath`; rejects when none was given. */
+	save(): Promise<void>;
+}
+
+/** Static entrypoints for building `SymbolIndex` instances. */
+export interface SymbolIndexConstructor {
+	/**
+	 * Walk `root` and extract the symbols of every supported source file.
+	 * With `cachePath`, unchanged files are loaded from the cache and the result is saved back.
+	 */
+	build(root: string, options?: SymbolIndexOptions): Promise<SymbolIndex>;
+	/** Instance prototype reference. */
+	prototype: SymbolIndex;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Extract functions, classes, and other definitions from a source file using tree-sitter.
+		 * @param input File path, or the UTF-8 source itself as bytes.
+		 * @param language Language of the source; inferred from the path's extension when omitted.
+		 * @returns Symbols ordered by position, outer definitions before nested ones.
+		 */
+		extractSymbols(input: string | Uint8Array, language?: SymbolLanguage): Promise<CodeSymbol[]>;
+		/** Workspace symbol index constructor exposed by the native module. */
+		SymbolIndex: SymbolIndexConstructor;
+	}
+}
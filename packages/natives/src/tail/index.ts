@@ -0,0 +1,20 @@
+/**
+ * Follow growing files (log viewer).
+ */
+
+import { native } from "../native";
+import type { TailEvent, TailFollowOptions, TailFollowResult } from "./types";
+
+export type { TailEvent, TailFollowOptions, TailFollowResult };
+
+/**
+ * Stream lines appended to a file until `options.signal` aborts or `options.timeoutMs` elapses.
+ * Truncation and rotation are detected and reported on the next event.
+ */
+export function tailFollow(
+	path: string,
+	onLines: (event: TailEvent) => void,
+	options?: TailFollowOptions,
+): Promise<TailFollowResult> {
+	return native.tailFollow(path, options, (err, event) => !err && onLines(event));
+}
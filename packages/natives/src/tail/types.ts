@@ -0,0 +1,50 @@
+/**
+ * Types for following growing files.
+ */
+
+import type { Cancellable, TsFunc } from "../bindings";
+
+/** Options for following a file. `signal`/`timeoutMs` stop following. */
+export interface TailFollowOptions extends Cancellable {
+	/** Start at the current end of file instead of the beginning (default: true). */
+	fromEnd?: boolean;
+	/** Poll interval in milliseconds (default: 250). */
+	pollMs?: number;
+}
+
+/** A batch of appended lines. */
+export interface TailEvent {
+	/** Complete lines appended since the previous event, without terminators. */
+	lines: string[];
+	/** Whether the file was truncated before these lines were read. */
+	truncated: boolean;
+	/** Whether the file was replaced (rotated) before these lines were read. */
+	rotated: boolean;
+}
+
+/** Summary returned when following stops. */
+export interface TailFollowResult {
+	/** Total lines delivered. */
+	lines: number;
+	/** Number of truncations observed. */
+	truncations: number;
+	/** Number of rotations observed. */
+	rotations: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Follow a file and stream appended lines until the signal aborts or the timeout elapses.
+		 * @param path File to follow.
+		 * @param options Start position, poll interval, and cancellation.
+		 * @param onLines Callback receiving batches of appended lines.
+		 * @returns Summary of delivered lines, truncations, and rotations.
+		 */
+		tailFollow(
+			path: string,
+			options: TailFollowOptions | undefined,
+			onLines: TsFunc<TailEvent>,
+		): Promise<TailFollowResult>;
+	}
+}
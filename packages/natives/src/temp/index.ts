@@ -0,0 +1,48 @@
+/**
+ * Tracked temporary files and directories, cleaned up on dispose or process exit.
+ */
+
+import { postmortem } from "@oh-my-pi/pi-utils";
+import { native } from "../native";
+import type { TempOptions } from "./types";
+
+export type { TempOptions, TempPathConstructor } from "./types";
+
+/** TempPath class. Use createTempFile() or createTempDir() to create instances. */
+export const TempPath = native.TempPath;
+
+/** TempPath instance type. */
+export type TempPath = import("./types").TempPath;
+
+let exitHooked = false;
+
+/**
+ * Delete tracked entries when the process exits, keeping `keepOnCrash` entries after a failure exit.
+ * The `exit` event alone misses signals and fatal errors, so those run through the postmortem handlers.
+ */
+function hookExit(): void {
+	if (exitHooked) return;
+	exitHooked = true;
+	postmortem.register("temp-paths", reason => {
+		// Normal and manual exits still reach the `exit` listener below with their exit code.
+		if (reason === postmortem.Reason.EXIT || reason === postmortem.Reason.MANUAL) return;
+		native.cleanupTempPaths(true);
+	});
+	process.once("exit", code => {
+		native.cleanupTempPaths(code !== 0);
+	});
+}
+
+/** Create an empty temporary file, deleted on `dispose()` or when the process exits. */
+export function createTempFile(options?: TempOptions): TempPath {
+	hookExit();
+	return native.createTempFile(options);
+}
+
+/** Create an empty temporary directory, deleted with its contents on `dispose()` or when the process exits. */
+export function createTempDir(options?: TempOptions): TempPath {
+	hookExit();
+	return native.createTempDir(options);
+}
+
+export const { cleanupTempPaths } = native;
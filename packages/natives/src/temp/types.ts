@@ -0,0 +1,50 @@
+/**
+ * Types for tracked temporary files and directories.
+ */
+
+/** Options for creating a temporary file or directory. */
+export interface TempOptions {
+	/** Name prefix (default: "pi-"). */
+	prefix?: string;
+	/** Name suffix, e.g. an extension (default: none). */
+	suffix?: string;
+	/** Parent directory (default: the OS temp directory). */
+	dir?: string;
+	/** Leave the entry untracked; it is never deleted automatically (default: false). */
+	keep?: boolean;
+	/** Leave the entry on disk when the process exits with a failure code, for debugging (default: false). */
+	keepOnCrash?: boolean;
+}
+
+/** Handle to a temporary file or directory. */
+export interface TempPath {
+	/** Absolute path of the entry. */
+	get path(): string;
+	/** Whether the entry is a directory. */
+	get isDir(): boolean;
+	/** Delete the entry now (directories recursively) and stop tracking it. Disposing twice is a no-op. */
+	dispose(): void;
+	/** Stop tracking the entry so it survives process exit. */
+	persist(): void;
+}
+
+export interface TempPathConstructor {
+	prototype: TempPath;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Temporary entry handle class; create instances with `createTempFile()` or `createTempDir()`. */
+		TempPath: TempPathConstructor;
+		/** Create an empty temporary file (mode 0600) tracked for cleanup. */
+		createTempFile(options?: TempOptions): TempPath;
+		/** Create an empty temporary directory (mode 0700) tracked for cleanup. */
+		createTempDir(options?: TempOptions): TempPath;
+		/**
+		 * Delete every tracked temporary entry.
+		 * @param crashed Whether the process is exiting abnormally; `keepOnCrash` entries are then left on disk.
+		 * @returns The number of entries deleted.
+		 */
+		cleanupTempPaths(crashed?: boolean): number;
+	}
+}
@@ -0,0 +1,10 @@
+/**
+ * In-memory terminal screen model for PTY output and frame diffing.
+ */
+
+import { native } from "../native";
+
+export type { DiffScreensOptions, TermCell, TermCursor, TermScreenConstructor } from "./types";
+
+export const { TermScreen, diffScreens } = native;
+export type TermScreen = import("./types").TermScreen;
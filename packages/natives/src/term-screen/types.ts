@@ -0,0 +1,94 @@
+/**
+ * Types for the terminal screen model.
+ */
+
+/** One cell of the screen grid. */
+export interface TermCell {
+	/** Cell text: one grapheme, " " when blank, "" for the right half of a wide character. */
+	text: string;
+	/** Display width: 1, 2 for a wide character, 0 for its right half. */
+	width: number;
+	/** Foreground as SGR parameters (e.g. "31", "38;5;208"), or null for the default color. */
+	fg?: string | null;
+	/** Background as SGR parameters (e.g. "44", "48;2;0;0;0"), or null for the default color. */
+	bg?: string | null;
+	/** Bold / increased intensity (SGR 1). */
+	bold: boolean;
+	/** Faint / decreased intensity (SGR 2). */
+	dim: boolean;
+	/** Italic (SGR 3). */
+	italic: boolean;
+	/** Underline (SGR 4). */
+	underline: boolean;
+	/** Reverse video (SGR 7). */
+	inverse: boolean;
+}
+
+/** Cursor position and visibility. */
+export interface TermCursor {
+	/** Zero-based row. */
+	row: number;
+	/** Zero-based column. */
+	col: number;
+	/** False when the program hid the cursor. */
+	visible: boolean;
+}
+
+/** Options for diffing screen grids. */
+export interface DiffScreensOptions {
+	/** Where to leave the cursor, and whether to show it, after the update. */
+	cursor?: TermCursor;
+	/** Wrap the update in synchronized-output markers (DEC mode 2026) (default: false). */
+	synchronized?: boolean;
+}
+
+/** Terminal emulator screen fed with raw PTY output. */
+export interface TermScreen {
+	/** Feed program output; strings are processed as UTF-8. */
+	write(data: string | Uint8Array): void;
+	/** Resize the screen; call alongside `Pty.resize`. */
+	resize(rows: number, cols: number): void;
+	/** Clear the screen, scrollback, modes, and title. */
+	reset(): void;
+	/** Visible rows as plain text, without trailing blanks. */
+	lines(): string[];
+	/** Visible rows with SGR styling; each styled row ends with a reset. */
+	styledLines(): string[];
+	/** The visible grid, one array of `cols` cells per row. */
+	toCells(): TermCell[][];
+	/** Scroll the view back into history (0 = live screen); returns the applied offset. */
+	scrollTo(offset: number): number;
+	/** Current scroll offset into history. */
+	get scrollOffset(): number;
+	/** Screen height in cells. */
+	get rows(): number;
+	/** Screen width in cells. */
+	get cols(): number;
+	/** Cursor position and visibility. */
+	get cursor(): TermCursor;
+	/** Whether the program switched to the alternate screen. */
+	get alternateScreen(): boolean;
+	/** Window title last set via OSC 0/2 (empty if never set). */
+	get title(): string;
+}
+
+/** Terminal screen constructor. */
+export interface TermScreenConstructor {
+	/** Create a blank screen with `scrollback` history lines (default: 1000). */
+	new (rows: number, cols: number, scrollback?: number): TermScreen;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Terminal screen model constructor for rendering PTY output in a pane. */
+		TermScreen: TermScreenConstructor;
+		/**
+		 * Compute the minimal ANSI update (cursor moves, SGR changes, text, line erases)
+		 * turning a terminal showing `prev` into one showing `next`.
+		 * @param prev Grid currently displayed; `[]` repaints everything.
+		 * @param next Grid to display.
+		 * @param options Final cursor placement and synchronized output.
+		 */
+		diffScreens(prev: TermCell[][], next: TermCell[][], options?: DiffScreensOptions): string;
+	}
+}
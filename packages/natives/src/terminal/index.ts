@@ -0,0 +1,17 @@
+/**
+ * Terminal capability detection and notifications.
+ */
+
+import { native } from "../native";
+
+export type {
+	DetectTerminalOptions,
+	NotifyOptions,
+	NotifyProtocol,
+	NotifyResult,
+	NotifyUrgency,
+	TerminalImageProtocol,
+	TerminalInfo,
+} from "./types";
+
+export const { detectTerminal, notify } = native;
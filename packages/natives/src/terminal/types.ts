@@ -0,0 +1,92 @@
+/**
+ * Types for terminal capability detection and notifications.
+ */
+
+/** Options for detecting terminal capabilities. */
+export interface DetectTerminalOptions {
+	/** Query the terminal over /dev/tty for live replies (default: false, Unix only). */
+	query?: boolean;
+	/** How long to wait for query replies in milliseconds (default: 200). */
+	timeoutMs?: number;
+}
+
+/** Inline image protocols. */
+export type TerminalImageProtocol = "kitty" | "iterm2" | "sixel";
+
+/** Terminal capability report. */
+export interface TerminalInfo {
+	/** Identified emulator (e.g. "kitty", "wezterm", "iterm2", "vscode"), if recognized. */
+	name?: string;
+	/** Emulator version, if reported. */
+	version?: string;
+	/** Value of $TERM. */
+	term?: string;
+	/** Enclosing multiplexer. */
+	multiplexer?: "tmux" | "screen";
+	/** Whether stdout is a terminal. */
+	isTty: boolean;
+	/** Number of colors supported (0, 8, 16, 256, or 16777216). */
+	colors: number;
+	/** Whether 24-bit color is supported. */
+	truecolor: boolean;
+	/** Whether the kitty keyboard protocol is supported. */
+	kittyKeyboard: boolean;
+	/** Whether synchronized output (mode 2026) is supported. */
+	synchronizedOutput: boolean;
+	/** Whether OSC 8 hyperlinks are supported. */
+	hyperlinks: boolean;
+	/** Inline image protocol, if any. */
+	images?: TerminalImageProtocol;
+	/** Whether a live query was answered by the terminal. */
+	queried: boolean;
+}
+
+/** Notification urgency; only OSC 99 (kitty) carries it. */
+export type NotifyUrgency = "low" | "normal" | "critical";
+
+/** Terminal notification escapes. */
+export type NotifyProtocol = "osc9" | "osc777" | "osc99";
+
+/** Options for showing a notification. */
+export interface NotifyOptions {
+	/** Notification title. */
+	title: string;
+	/** Notification body. */
+	body?: string;
+	/** Urgency (default: "normal"). */
+	urgency?: NotifyUrgency;
+	/** Escape to emit (default: chosen from the detected terminal). */
+	protocol?: NotifyProtocol;
+	/** Write the sequence to the terminal (default: true); when false, only the sequence is returned. */
+	write?: boolean;
+}
+
+/** Result of showing a notification. */
+export interface NotifyResult {
+	/** Protocol used; absent when the terminal supports none. */
+	protocol?: NotifyProtocol;
+	/** Escape sequence, including any tmux wrapping (empty when unsupported). */
+	sequence: string;
+	/** Whether the sequence was written to the terminal. */
+	sent: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Detect the capabilities of the controlling terminal from the environment,
+		 * terminfo, and optionally live query replies. Query before reading stdin,
+		 * or the replies may be consumed by the input reader.
+		 * @param options Whether to query the terminal, and the reply timeout.
+		 * @returns The capability report.
+		 */
+		detectTerminal(options?: DetectTerminalOptions): Promise<TerminalInfo>;
+		/**
+		 * Show a desktop notification through the terminal with OSC 9, OSC 777, or OSC 99, wrapped for tmux
+		 * passthrough when needed. Control characters in the text are removed.
+		 * @param options Title, body, urgency, and an optional protocol override.
+		 * @returns The protocol and sequence used (none when unsupported), and whether it was written.
+		 */
+		notify(options: NotifyOptions): NotifyResult;
+	}
+}
@@ -0,0 +1,18 @@
+/**
+ * Rope-backed text buffer with grouped undo/redo.
+ */
+
+import { native } from "../native";
+
+export type {
+	TextBufferConstructor,
+	TextBufferMatch,
+	TextBufferOptions,
+	TextBufferSearchOptions,
+	TextBufferSearchResult,
+	TextPosition,
+	TextRange,
+} from "./types";
+
+export const { TextBuffer } = native;
+export type TextBuffer = import("./types").TextBuffer;
@@ -0,0 +1,122 @@
+/**
+ * Types for the rope-backed text buffer.
+ */
+
+import type { Cancellable, TsFunc } from "../bindings";
+
+/** Options for creating a text buffer. */
+export interface TextBufferOptions {
+	/** Maximum number of undo steps kept (default: 1000; 0 disables undo). */
+	historyLimit?: number;
+}
+
+/** Line and column of an offset. */
+export interface TextPosition {
+	/** Zero-based line. */
+	line: number;
+	/** Zero-based column in UTF-16 code units. */
+	col: number;
+}
+
+/** Offset range in UTF-16 code units. */
+export interface TextRange {
+	/** Start offset (inclusive). */
+	start: number;
+	/** End offset (exclusive). */
+	end: number;
+}
+
+/** Options for searching a text buffer. */
+export interface TextBufferSearchOptions extends Cancellable {
+	/** Treat `pattern` as a regex instead of literal text (default: false). */
+	regex?: boolean;
+	/** Match letter case exactly (default: true). */
+	caseSensitive?: boolean;
+	/** Only search within this range (default: the whole buffer). */
+	range?: TextRange;
+	/** Stop after this many matches. */
+	maxCount?: number;
+}
+
+/** A match found by `TextBuffer.search`. */
+export interface TextBufferMatch {
+	/** Start offset in UTF-16 code units. */
+	start: number;
+	/** End offset in UTF-16 code units (exclusive). */
+	end: number;
+	/** Zero-based line containing `start`. */
+	line: number;
+	/** Matched text. */
+	text: string;
+}
+
+/** Result of `TextBuffer.search`. */
+export interface TextBufferSearchResult {
+	/** Matches in buffer order. */
+	matches: TextBufferMatch[];
+	/** Whether the search stopped at `maxCount`. */
+	limitReached: boolean;
+}
+
+/** Editable text backed by a rope, with grouped undo/redo. Offsets are UTF-16 code units. */
+export interface TextBuffer {
+	/** Length in UTF-16 code units. */
+	get length(): number;
+	/** Number of lines (a trailing newline starts an empty last line). */
+	get lineCount(): number;
+	/** Cursor offset, restored by `undo()` and `redo()`. */
+	get cursor(): number;
+	/** Whether `undo()` would change the text. */
+	get canUndo(): boolean;
+	/** Whether `redo()` would change the text. */
+	get canRedo(): boolean;
+	/** The whole text. */
+	text(): string;
+	/** Text between two offsets. */
+	slice(start: number, end: number): string;
+	/** Text of one line, without its line ending. */
+	line(index: number): string;
+	/** Line and column of an offset. */
+	positionAt(offset: number): TextPosition;
+	/** Offset of a line and column; the column is clamped to the line's length. */
+	offsetAt(line: number, col: number): number;
+	/** Insert text; the cursor moves to the end of the insert. */
+	insert(offset: number, text: string): void;
+	/** Delete a range; the cursor moves to `start`. */
+	delete(start: number, end: number): void;
+	/** Replace a range; the cursor moves to the end of the new text. */
+	replace(start: number, end: number, text: string): void;
+	/** Move the cursor without recording history. */
+	setCursor(offset: number): void;
+	/** Start an undo group; edits until the matching `endGroup()` undo as one step. Groups nest. */
+	beginGroup(): void;
+	/** Close the innermost undo group. */
+	endGroup(): void;
+	/** Revert the last undo step; false when there is nothing to undo. */
+	undo(): boolean;
+	/** Re-apply the last undone step; false when there is nothing to redo. */
+	redo(): boolean;
+	/** Drop all undo and redo steps. */
+	clearHistory(): void;
+	/**
+	 * Find matches over a snapshot of the buffer on the worker pool, streaming each to `onMatch`.
+	 * Matches may span lines but not exceed 64 KiB.
+	 */
+	search(
+		pattern: string,
+		options?: TextBufferSearchOptions,
+		onMatch?: TsFunc<TextBufferMatch>,
+	): Promise<TextBufferSearchResult>;
+}
+
+/** Text buffer constructor. */
+export interface TextBufferConstructor {
+	new (text?: string, options?: TextBufferOptions): TextBuffer;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Rope-backed text buffer constructor for editor state with native undo history. */
+		TextBuffer: TextBufferConstructor;
+	}
+}
@@ -2,10 +2,20 @@
  * ANSI-aware text utilities powered by native bindings.
  */
 
-import { Ellipsis, type SliceWithWidthResult } from "@oh-my-pi/pi-natives";
+import { Ellipsis, type SliceWithWidthResult, type TruncateMode } from "@oh-my-pi/pi-natives";
 import { native } from "../native";
 
-export type { ExtractSegmentsResult, SliceWithWidthResult } from "./types";
+export type {
+	AnsiPolicy,
+	ExtractSegmentsResult,
+	GraphemeInfo,
+	LayoutSegment,
+	PadAlign,
+	SliceWithWidthResult,
+	TextOptions,
+	TruncateMode,
+	WrapOptions,
+} from "./types";
 export { Ellipsis } from "./types";
 
 /**
@@ -17,6 +27,8 @@ export { Ellipsis } from "./types";
  * @param maxWidth - Maximum visible width
  * @param ellipsis - Ellipsis kind to append when truncating (default: Unicode "…")
  * @param pad - If true, pad result with spaces to exactly maxWidth (default: false)
+ * @param mode - Which part to keep: head ("end"), tail ("start"), both ends ("middle"), or head cut at a word
+ *   boundary ("word") (default: "end")
  * @returns Truncated text, optionally padded to exactly maxWidth
  */
 export function truncateToWidth(
@@ -24,8 +36,9 @@ export function truncateToWidth(
 	maxWidth: number,
 	ellipsis: Ellipsis = Ellipsis.Unicode,
 	pad = false,
+	mode: TruncateMode = "end",
 ): string {
-	return native.truncateToWidth(text, maxWidth, ellipsis, pad);
+	return native.truncateToWidth(text, maxWidth, ellipsis, pad, mode);
 }
 
 /**
@@ -41,4 +54,20 @@ export function sliceWithWidth(line: string, startCol: number, length: number, s
 	return native.sliceWithWidth(line, startCol, length, strict);
 }
 
-export const { wrapTextWithAnsi, visibleWidth, extractSegments, sanitizeText } = native;
+export const {
+	wrapTextWithAnsi,
+	wrapToWidth,
+	layoutLine,
+	graphemes,
+	graphemeAt,
+	setTextOptions,
+	visibleWidth,
+	visibleWidths,
+	padToWidth,
+	joinStyled,
+	wrapWithHyperlink,
+	extractSegments,
+	sanitizeText,
+	stripAnsi,
+	sanitizeAnsi,
+} = native;
@@ -22,6 +22,54 @@ export interface ExtractSegmentsResult {
 	afterWidth: number;
 }
 
+/** Options for wrapping text to a width. */
+export interface WrapOptions {
+	/** Prefix for continuation lines, counted toward the width (default: none). */
+	indent?: string;
+	/** Break words longer than the width; when false they overflow on a line of their own (default: true). */
+	hard?: boolean;
+}
+
+/** One row of a wrapped line, as UTF-8 byte offsets into the input. */
+export interface LayoutSegment {
+	/** Byte offset where the row starts. */
+	startByte: number;
+	/** Byte offset where the row ends (exclusive); whitespace dropped at a break belongs to the row before it. */
+	endByte: number;
+	/** Visible width of the row, excluding the continuation indent and whitespace dropped at the break. */
+	width: number;
+}
+
+/** A grapheme cluster with its position and cell width. */
+export interface GraphemeInfo {
+	/** The cluster text. */
+	cluster: string;
+	/** UTF-8 byte offset of the cluster in the input. */
+	byteOffset: number;
+	/** Column where the cluster starts, in terminal cells. */
+	column: number;
+	/** Width of the cluster in terminal cells. */
+	width: number;
+}
+
+/** Terminal width settings for the width-aware text functions. */
+export interface TextOptions {
+	/** Cells a tab occupies (default: 3). */
+	tabWidth?: number;
+	/** Draw East Asian ambiguous-width characters in two cells (default: false). */
+	ambiguousWide?: boolean;
+	/** Draw emoji in two cells (default: true). */
+	emojiWide?: boolean;
+}
+
+/** Which escape sequences `sanitizeAnsi` keeps. */
+export interface AnsiPolicy {
+	/** Keep SGR sequences: colors and text attributes (default: true). */
+	keepColors?: boolean;
+	/** Keep OSC 8 hyperlinks (default: false). */
+	keepHyperlinks?: boolean;
+}
+
 /** Ellipsis strategy for truncation. */
 export const enum Ellipsis {
 	/** Use a single Unicode ellipsis character ("…"). */
@@ -32,6 +80,15 @@ export const enum Ellipsis {
 	Omit = 2,
 }
 
+/**
+ * Which part of the text `truncateToWidth` keeps: the head (`"end"`), the tail (`"start"`), both ends (`"middle"`),
+ * or the head cut at a word boundary (`"word"`).
+ */
+export type TruncateMode = "end" | "start" | "middle" | "word";
+
+/** Where `padToWidth` places the text within the padded width. */
+export type PadAlign = "left" | "right" | "center";
+
 declare module "../bindings" {
 	interface NativeBindings {
 		/**
@@ -40,12 +97,51 @@ declare module "../bindings" {
 		 * @param maxWidth Maximum visible width in terminal cells.
 		 * @param ellipsisKind Ellipsis strategy (see {@link Ellipsis}).
 		 * @param pad Whether to pad the output to `maxWidth`.
+		 * @param mode Which part of the text to keep (default: `"end"`).
+		 */
+		truncateToWidth(text: string, maxWidth: number, ellipsisKind: number, pad: boolean, mode?: TruncateMode): string;
+		/**
+		 * Pad text to a visible width in one pass, measuring it without counting ANSI codes.
+		 * Text already `width` cells wide or wider is returned unchanged.
+		 * @param text UTF-16 input text with optional ANSI escapes.
+		 * @param width Target width in terminal cells.
+		 * @param align Where the text goes (default: `"left"`).
+		 * @param fillChar Single visible character to pad with (default: space); a double-width fill covers two cells,
+		 *   with a space making up an odd remainder next to the text.
+		 * @throws For an unknown alignment or a fill that is not a single visible character.
+		 */
+		padToWidth(text: string, width: number, align?: PadAlign, fillChar?: string): string;
+		/**
+		 * Wrap text in an OSC 8 hyperlink. Links already in `text` are dropped, since links cannot nest.
+		 * The link sequences count as zero width for measuring, slicing, and truncation.
+		 * @param text UTF-16 link text with optional ANSI escapes.
+		 * @param uri Link target; characters outside printable ASCII are percent-encoded.
+		 * @throws For an empty URI.
+		 */
+		wrapWithHyperlink(text: string, uri: string): string;
+		/**
+		 * Join styled segments so no segment's colors or attributes reach the next: each segment and separator that
+		 * leaves a style active is followed by a reset, and one that leaves a hyperlink open by a close.
+		 * @param segments UTF-16 segments with optional ANSI escapes.
+		 * @param separator Text placed between segments (default: empty), isolated the same way.
 		 */
-		truncateToWidth(text: string, maxWidth: number, ellipsisKind: number, pad: boolean): string;
+		joinStyled(segments: string[], separator?: string): string;
 		/**
 		 * Sanitize text output: strip ANSI codes, remove binary garbage, normalize line endings.
 		 */
 		sanitizeText(text: string): string;
+		/**
+		 * Remove ANSI escape sequences, keeping all other text.
+		 * @param text UTF-16 input text.
+		 */
+		stripAnsi(text: string): string;
+		/**
+		 * Make untrusted terminal output safe to render: remove cursor movement, screen clears, title changes, and
+		 * other escapes, plus control characters other than tab and newline, while keeping SGR colors.
+		 * @param text UTF-16 input text.
+		 * @param policy Whether to keep colors and OSC 8 hyperlinks.
+		 */
+		sanitizeAnsi(text: string, policy?: AnsiPolicy): string;
 
 		/**
 		 * Wrap text to a visible width, preserving ANSI codes across line breaks.
@@ -54,7 +150,41 @@ declare module "../bindings" {
 		 */
 		wrapTextWithAnsi(text: string, width: number): string[];
 		/**
-		 * Slice a range of visible columns from a line.
+		 * Wrap text to a visible width, re-emitting active ANSI styles at the start of each continuation line.
+		 * @param text UTF-16 input text with optional ANSI escapes.
+		 * @param width Maximum visible width per line.
+		 * @param options Continuation indent and handling of words longer than the width.
+		 */
+		wrapToWidth(text: string, width: number, options?: WrapOptions): string[];
+		/**
+		 * Compute where a single line wraps at a visible width without building the wrapped strings.
+		 * @param text UTF-16 input line with optional ANSI escapes.
+		 * @param width Maximum visible width per row.
+		 * @param options Continuation indent and handling of words longer than the width, as for `wrapToWidth`.
+		 * @returns Rows covering the whole line, in order.
+		 */
+		layoutLine(text: string, width: number, options?: WrapOptions): LayoutSegment[];
+		/**
+		 * Split text into grapheme clusters with their cell widths; ANSI escapes are skipped.
+		 * @param text UTF-16 input text with optional ANSI escapes.
+		 * @returns Clusters in order, including zero-width ones.
+		 */
+		graphemes(text: string): GraphemeInfo[];
+		/**
+		 * Change how the width-aware text functions measure tabs, ambiguous-width characters, and emoji.
+		 * Omitted fields keep their current value; the settings are process-wide.
+		 * @param options Width settings matching the terminal.
+		 */
+		setTextOptions(options: TextOptions): void;
+		/**
+		 * Find the grapheme cluster covering a visible column; both cells of a wide character resolve to it.
+		 * @param text UTF-16 input text with optional ANSI escapes.
+		 * @param column Zero-based column in terminal cells.
+		 * @returns The cluster, or null past the end of the text.
+		 */
+		graphemeAt(text: string, column: number): GraphemeInfo | null;
+		/**
+		 * Slice a range of visible columns from a line. A hyperlink cut by the slice is reopened and closed within it.
 		 * @param line UTF-16 input line with optional ANSI escapes.
 		 * @param startCol Starting column in terminal cells.
 		 * @param length Number of visible cells to include.
@@ -63,9 +193,15 @@ declare module "../bindings" {
 		sliceWithWidth(line: string, startCol: number, length: number, strict: boolean): SliceWithWidthResult;
 		/**
 		 * Measure the visible width of text (excluding ANSI codes).
-		 * @param text UTF-16 input text with optional ANSI escapes.
+		 * @param text UTF-16 input text, or UTF-8 bytes (invalid sequences count as U+FFFD), with optional ANSI escapes.
+		 */
+		visibleWidth(text: string | Uint8Array): number;
+		/**
+		 * Calculate the visible width of many lines in one call, in parallel for large batches.
+		 * @param lines UTF-16 strings, or UTF-8 byte arrays (invalid sequences count as U+FFFD).
+		 * @returns Visible width of each line, in input order.
 		 */
-		visibleWidth(text: string): number;
+		visibleWidths(lines: string[] | Uint8Array[]): number[];
 		/** Extract before/after segments around an overlay region.
 		 * @param line UTF-16 input line with optional ANSI escapes.
 		 * @param beforeEnd Column where the "before" segment ends.
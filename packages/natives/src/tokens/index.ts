@@ -0,0 +1,9 @@
+/**
+ * Streaming token counting for files.
+ */
+
+import { native } from "../native";
+
+export type { CountTokensOptions, TokenCount, TokenEncoding } from "./types";
+
+export const { countFileTokens } = native;
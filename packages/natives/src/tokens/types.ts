@@ -0,0 +1,32 @@
+/**
+ * Types for native token counting.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** BPE encodings supported by the native token counter. */
+export type TokenEncoding = "o200k_base" | "cl100k_base" | "p50k_base" | "r50k_base";
+
+/** Options for counting file tokens. */
+export interface CountTokensOptions extends Cancellable {}
+
+/** Token and byte counts for a file. */
+export interface TokenCount {
+	/** Number of BPE tokens. */
+	tokens: number;
+	/** Number of bytes read. */
+	bytes: number;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Count BPE tokens in a file, streaming it on the worker pool.
+		 * @param path File to count.
+		 * @param encoding Tokenizer encoding (default: "o200k_base").
+		 * @param options Cancellation options.
+		 * @returns Token and byte counts.
+		 */
+		countFileTokens(path: string, encoding?: TokenEncoding, options?: CountTokensOptions): Promise<TokenCount>;
+	}
+}
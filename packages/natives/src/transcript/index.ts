@@ -0,0 +1,9 @@
+/**
+ * Session transcripts: styled lines exported as text, HTML, or Markdown.
+ */
+
+import { native } from "../native";
+
+export type { TranscriptFormat, TranscriptOptions } from "./types";
+
+export const { exportTranscript } = native;
@@ -0,0 +1,31 @@
+/**
+ * Types for session transcript export.
+ */
+
+import type { ScreenshotTheme } from "../screenshot/types";
+
+/** Output format of an exported transcript. */
+export type TranscriptFormat = "text" | "html" | "markdown";
+
+/** Options for exporting a transcript. */
+export interface TranscriptOptions {
+	/** Document title for HTML output (default: "Transcript"). */
+	title?: string;
+	/** Colors for HTML output (default: the screenshot theme defaults). */
+	theme?: ScreenshotTheme;
+	/** Wrap HTML output in a full document; when false, only the `<pre>` element is returned (default: true). */
+	standalone?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Export styled terminal lines as plain text, HTML, or Markdown, keeping styling, hyperlinks, and image placeholders.
+		 * @param lines Lines as written to the terminal, with ANSI escapes.
+		 * @param format Output format.
+		 * @param options HTML title, theme, and whether to emit a full document.
+		 * @returns The transcript, ending with a newline.
+		 */
+		exportTranscript(lines: string[], format: TranscriptFormat, options?: TranscriptOptions): string;
+	}
+}
@@ -0,0 +1,19 @@
+/**
+ * Exact nearest-neighbour search over embedding vectors.
+ */
+
+import { native } from "../native";
+
+export type {
+	VectorIndexConstructor,
+	VectorIndexOptions,
+	VectorMatch,
+	VectorMetric,
+	VectorSearchOptions,
+} from "./types";
+
+/** VectorIndex class. Construct with `new VectorIndex(dimensions)` or use VectorIndex.load(). */
+export const VectorIndex = native.VectorIndex;
+
+/** VectorIndex instance type. */
+export type VectorIndex = import("./types").VectorIndex;
@@ -0,0 +1,65 @@
+/**
+ * Types for embedding vector search.
+ */
+
+import type { Cancellable } from "../bindings";
+
+/** Similarity metric; "cosine" normalizes vectors on insert. */
+export type VectorMetric = "cosine" | "dot";
+
+/** Options for creating a vector index. */
+export interface VectorIndexOptions {
+	/** Similarity metric (default: "cosine"). */
+	metric?: VectorMetric;
+}
+
+/** Options for searching a vector index. */
+export interface VectorSearchOptions extends Cancellable {
+	/** Drop matches scoring below this value. */
+	minScore?: number;
+}
+
+/** A search hit. */
+export interface VectorMatch {
+	/** Id the vector was added under. */
+	id: string;
+	/** Cosine similarity or dot product with the query (higher is closer). */
+	score: number;
+}
+
+/** In-memory vector index with exact top-k search and file persistence. */
+export interface VectorIndex {
+	/** Number of stored vectors. */
+	get size(): number;
+	/** Components per vector. */
+	get dimensions(): number;
+	/** Similarity metric. */
+	get metric(): VectorMetric;
+	/** Add `vector` under `id`, replacing any vector already stored there. Throws on a length mismatch. */
+	add(id: string, vector: Float32Array): void;
+	/** Remove the vector stored under `id`; returns whether it was present. */
+	remove(id: string): boolean;
+	/** Whether a vector is stored under `id`. */
+	has(id: string): boolean;
+	/**
+	 * Find the `k` stored vectors closest to `query`, scanning all rows in parallel.
+	 * @returns Matches sorted by descending score.
+	 */
+	search(query: Float32Array, k: number, options?: VectorSearchOptions): Promise<VectorMatch[]>;
+	/** Write the index to `path`, replacing the file atomically. */
+	save(path: string): Promise<void>;
+}
+
+export interface VectorIndexConstructor {
+	new (dimensions: number, options?: VectorIndexOptions): VectorIndex;
+	/** Load an index written by `save()`. */
+	load(path: string): Promise<VectorIndex>;
+	prototype: VectorIndex;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/** Embedding vector index for local retrieval. */
+		VectorIndex: VectorIndexConstructor;
+	}
+}
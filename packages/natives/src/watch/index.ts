@@ -0,0 +1,38 @@
+/**
+ * Filesystem watching with debounced rebuild triggers.
+ */
+
+import { native } from "../native";
+import type { WatcherOptions, WatchTriggerEvent, WatchTriggerOptions, WatchTriggerResult } from "./types";
+
+export type { WatcherConstructor } from "./types";
+export type { WatcherOptions, WatchTriggerEvent, WatchTriggerOptions, WatchTriggerResult };
+
+/** Watcher class. Use watch() or Watcher.watch() to create instances. */
+export const Watcher = native.Watcher;
+
+/** Watcher instance type. */
+export type Watcher = import("./types").Watcher;
+
+/**
+ * Watch a directory until `options.signal` aborts or `options.timeoutMs` elapses, calling `onChange` once per
+ * burst of filesystem activity with the files that changed.
+ */
+export function watchAndTrigger(
+	options: WatchTriggerOptions | undefined,
+	onChange: (event: WatchTriggerEvent) => void,
+): Promise<WatchTriggerResult> {
+	return native.watchAndTrigger(options, (err, event) => !err && onChange(event));
+}
+
+/**
+ * Watch directories until `close()` is called on the returned watcher, calling `onChange` once per burst of
+ * filesystem activity with the absolute paths of the files that changed.
+ */
+export function watch(
+	paths: string[],
+	options: WatcherOptions | undefined,
+	onChange: (event: WatchTriggerEvent) => void,
+): Watcher {
+	return native.Watcher.watch(paths, options, (err, event) => !err && onChange(event));
+}
@@ -0,0 +1,92 @@
+/**
+ * Types for filesystem watching.
+ */
+
+import type { Cancellable, TsFunc } from "../bindings";
+
+/** Options for watching a directory. `signal`/`timeoutMs` stop watching. */
+export interface WatchTriggerOptions extends Cancellable {
+	/** Directory to watch recursively (default: current directory). */
+	path?: string;
+	/** Only report files matching one of these globs (default: all files). */
+	globs?: string[];
+	/** Minimum time from the first event of a burst to the callback, so trailing events are folded in (default: 100). */
+	debounceMs?: number;
+	/** Time without events before the callback fires (default: 200). */
+	quietMs?: number;
+	/** Include hidden files (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore files (default: true). */
+	gitignore?: boolean;
+}
+
+/** Options for `Watcher.watch`. */
+export interface WatcherOptions {
+	/** Only report files matching one of these globs, relative to the watched directory (default: all files). */
+	globs?: string[];
+	/** Minimum time from the first event of a burst to the callback, so trailing events are folded in (default: 100). */
+	debounceMs?: number;
+	/** Time without events before the callback fires (default: 200). */
+	quietMs?: number;
+	/** Include hidden files (default: false). */
+	hidden?: boolean;
+	/** Respect .gitignore files (default: true). */
+	gitignore?: boolean;
+	/** Watch subdirectories too (default: true). */
+	recursive?: boolean;
+}
+
+/** Files that changed during one burst of activity. */
+export interface WatchTriggerEvent {
+	/** Created or modified files, relative to the watched directory (absolute for `Watcher`). */
+	changed: string[];
+	/** Deleted files, relative to the watched directory (absolute for `Watcher`). */
+	deleted: string[];
+}
+
+/** Summary returned when watching stops. */
+export interface WatchTriggerResult {
+	/** Number of callbacks delivered. */
+	triggers: number;
+	/** Total paths reported across all callbacks. */
+	paths: number;
+}
+
+/** Long-lived watch over one or more directories; keeps the process alive until closed. */
+export interface Watcher {
+	/** Whether `close()` has been called. */
+	get closed(): boolean;
+	/** Stop watching; changes not yet delivered are discarded. Calling it again does nothing. */
+	close(): void;
+}
+
+/** Static entrypoints for creating `Watcher` instances. */
+export interface WatcherConstructor {
+	/**
+	 * Start watching directories, reporting changed files once per burst of activity.
+	 * @param paths Directories to watch; relative paths resolve against the current directory.
+	 * @param options Filters, burst timing, and recursion.
+	 * @param onChange Callback receiving the changed and deleted files of each burst, as absolute paths.
+	 */
+	watch(paths: string[], options: WatcherOptions | undefined, onChange: TsFunc<WatchTriggerEvent>): Watcher;
+	/** Instance prototype reference. */
+	prototype: Watcher;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Watch a directory and report changed files once per burst, after the tree has been quiet.
+		 * Ignored, hidden, and non-matching paths never count toward a burst.
+		 * @param options Directory, filters, quiet period, and cancellation.
+		 * @param onChange Callback receiving the changed and deleted files of each burst.
+		 * @returns Summary of delivered callbacks once the signal aborts or the timeout elapses.
+		 */
+		watchAndTrigger(
+			options: WatchTriggerOptions | undefined,
+			onChange: TsFunc<WatchTriggerEvent>,
+		): Promise<WatchTriggerResult>;
+		/** Watcher constructor for long-lived watches over several directories. */
+		Watcher: WatcherConstructor;
+	}
+}
@@ -0,0 +1,9 @@
+/**
+ * Executable resolution across PATH.
+ */
+
+import { native } from "../native";
+
+export type { WhichOptions } from "./types";
+
+export const { whichAll } = native;
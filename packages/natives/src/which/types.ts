@@ -0,0 +1,24 @@
+/**
+ * Types for executable resolution.
+ */
+
+/** Options for resolving executables. */
+export interface WhichOptions {
+	/** Directory for relative names and relative PATH entries (default: process cwd). */
+	cwd?: string;
+	/** Environment to read PATH/PATHEXT from (default: process env). */
+	env?: Record<string, string>;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Resolve a command name to every matching executable on PATH.
+		 * Applies PATHEXT and searches the cwd first on Windows.
+		 * @param name Command name, or a path containing a separator.
+		 * @param options Working directory and environment overrides.
+		 * @returns Matches in search order; the first is what a shell would run.
+		 */
+		whichAll(name: string, options?: WhichOptions): string[];
+	}
+}
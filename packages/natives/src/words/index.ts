@@ -0,0 +1,9 @@
+/**
+ * Identifier segmentation and code-aware word boundaries.
+ */
+
+import { native } from "../native";
+
+export type { WordBoundaryOptions } from "./types";
+
+export const { splitIdentifier, wordBoundaries } = native;
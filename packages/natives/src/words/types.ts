@@ -0,0 +1,27 @@
+/**
+ * Types for identifier segmentation and word boundaries.
+ */
+
+/** Options for finding word boundaries. */
+export interface WordBoundaryOptions {
+	/** Also stop at subword boundaries inside identifiers: camelCase humps and underscores (default: false). */
+	codeAware?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Split an identifier into subwords at separators and camelCase humps.
+		 * @param word Identifier such as "parseHTMLResponse" or "snake_case".
+		 * @returns Subwords with their original case; separators are dropped.
+		 */
+		splitIdentifier(word: string): string[];
+		/**
+		 * Find word boundaries on a line for word-wise cursor motion.
+		 * @param line A single line of text.
+		 * @param options Whether to stop at subwords inside identifiers.
+		 * @returns Ascending UTF-16 columns where a word, subword, or punctuation run starts or ends.
+		 */
+		wordBoundaries(line: string, options?: WordBoundaryOptions): number[];
+	}
+}
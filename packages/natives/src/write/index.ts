@@ -0,0 +1,9 @@
+/**
+ * Atomic, durable file writes.
+ */
+
+import { native } from "../native";
+
+export type { WriteFileAtomicOptions } from "./types";
+
+export const { writeFileAtomic } = native;
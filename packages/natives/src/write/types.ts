@@ -0,0 +1,30 @@
+/**
+ * Types for atomic file writes.
+ */
+
+/** Options for writing a file atomically. */
+export interface WriteFileAtomicOptions {
+	/** Flush the file and its directory to disk before resolving (default: true). */
+	fsync?: boolean;
+	/** Permission bits, e.g. `0o644` (default: keep the existing file's, or 0o666 minus the umask). Unix only. */
+	mode?: number;
+	/** Keep the existing file's owner and group (default: false). Unix only. */
+	preserveOwner?: boolean;
+	/** Create missing parent directories (default: false). */
+	createDirs?: boolean;
+	/** Convert text `data` to the existing file's dominant line ending (default: false). Ignored for bytes and new files. */
+	preserveEol?: boolean;
+}
+
+declare module "../bindings" {
+	interface NativeBindings {
+		/**
+		 * Replace a file's contents atomically: write a sibling temp file, then rename it over the target.
+		 * Writing through a symlink replaces the file it points to. On failure the original file is unchanged.
+		 * @param path File to write; created if missing.
+		 * @param data Text (written as UTF-8) or bytes.
+		 * @param options Durability, permissions, ownership, and line endings.
+		 */
+		writeFileAtomic(path: string, data: string | Uint8Array, options?: WriteFileAtomicOptions): Promise<void>;
+	}
+}
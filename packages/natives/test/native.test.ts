@@ -3,14 +3,26 @@ import * as fs from "node:fs/promises";
 import * as os from "node:os";
 import * as path from "node:path";
 import {
+	DiffLineKind,
+	diffLines,
+	extractCodeBlocks,
 	FileType,
 	fuzzyFind,
+	fuzzyScore,
 	type GlobMatch,
 	glob,
+	globToRegex,
 	grep,
 	htmlToMarkdown,
 	invalidateFsScanCache,
+	JsonStream,
+	LruMap,
+	naturalSort,
+	readFileSlice,
 	sanitizeText,
+	shlexQuote,
+	shlexSplit,
+	TextBuffer,
 } from "../src/index";
 
 let testDir: string;
@@ -175,6 +187,14 @@ describe("pi-natives", () => {
 			expect(second.totalMatches).toBe(1);
 		});
 	});
+	describe("globToRegex", () => {
+		it("should compile non-ASCII patterns to JS-compatible sources", () => {
+			const regex = new RegExp(globToRegex("docs/café/*.md"), "u");
+			expect(regex.test("docs/café/menu.md")).toBe(true);
+			expect(regex.test("docs/cafe/menu.md")).toBe(false);
+		});
+	});
+
 	describe("htmlToMarkdown", () => {
 		it("should convert basic HTML to markdown", async () => {
 			const html = "<h1>Hello World</h1><p>This is a paragraph.</p>";
@@ -242,4 +262,121 @@ describe("pi-natives", () => {
 			expect(sanitizeText(input)).toBe("hello");
 		});
 	});
+	describe("diffLines", () => {
+		it("should produce hunks with line numbers", async () => {
+			const result = await diffLines("a\nb\nc\n", "a\nB\nc\nd\n", { context: 1 });
+
+			expect(result.added).toBe(2);
+			expect(result.removed).toBe(1);
+			expect(result.hunks).toHaveLength(1);
+			const [hunk] = result.hunks;
+			expect(hunk.oldStart).toBe(1);
+			expect(hunk.newStart).toBe(1);
+			expect(hunk.lines.find(line => line.kind === DiffLineKind.Removed)?.oldLine).toBe(2);
+			expect(hunk.lines.filter(line => line.kind === DiffLineKind.Added).map(line => line.content)).toEqual([
+				"B",
+				"d",
+			]);
+		});
+
+		it("should return no hunks for identical text", async () => {
+			expect((await diffLines("same\n", "same\n")).hunks).toHaveLength(0);
+		});
+	});
+
+	describe("fuzzyScore", () => {
+		it("should rank boundary matches first and report positions", () => {
+			const results = fuzzyScore("gco", ["go", "gecko", "git checkout"]);
+			expect(results.map(r => r.index)).toEqual([2, 1]);
+			expect(results[0].positions).toEqual([0, 4, 9]);
+			expect(results[0].spans).toBeUndefined();
+		});
+
+		it("should merge consecutive matches into spans on request", () => {
+			const [match] = fuzzyScore("gch", ["git checkout"], { spans: true });
+			expect(match.spans).toEqual([
+				{ start: 0, end: 1 },
+				{ start: 4, end: 6 },
+			]);
+		});
+	});
+
+	describe("extractCodeBlocks", () => {
+		it("should report language, content, and unterminated fences", () => {
+			const blocks = extractCodeBlocks("```ts\nconst a = 1;\n```\n\n```py\nprint(1)\n");
+			expect(blocks.map(b => [b.language, b.content, b.closed])).toEqual([
+				["ts", "const a = 1;\n", true],
+				["py", "print(1)\n", false],
+			]);
+		});
+	});
+
+	describe("JsonStream", () => {
+		it("should report closed values and repair truncated input", () => {
+			const stream = new JsonStream();
+			expect(stream.push('{"path": "a.ts", "edits": [{"old')).toEqual([{ path: "/path", raw: '"a.ts"' }]);
+			expect(JSON.parse(stream.repair()!)).toEqual({ path: "a.ts", edits: [{}] });
+			stream.push('": "x"}]}');
+			expect(stream.done).toBe(true);
+		});
+	});
+
+	describe("LruMap", () => {
+		it("should keep the existing value when an oversized replacement is rejected", () => {
+			const map = new LruMap({ maxBytes: 8 });
+			expect(map.set("k", "abc")).toBe(true);
+			expect(map.set("k", "too long to fit")).toBe(false);
+			expect(map.get("k")).toBe("abc");
+			expect(map.bytes).toBe(4);
+		});
+	});
+
+	describe("readFileSlice", () => {
+		it("should return the requested line range", async () => {
+			const result = await readFileSlice(path.join(testDir, "file1.ts"), { startLine: 2, endLine: 3 });
+			expect(result.lines).toEqual(["    // TODO: implement", '    return "hello";']);
+			expect(result.widths).toEqual([22, 19]);
+			expect(result.hasMore).toBe(true);
+			expect(result.truncated).toBe(false);
+		});
+	});
+
+	describe("shlexSplit/shlexQuote", () => {
+		it("should round-trip arguments in both modes", () => {
+			expect(shlexSplit(`git commit -m "it's done" 'a b'`)).toEqual(["git", "commit", "-m", "it's done", "a b"]);
+			const args = ["echo", "a b", "it's", "", 'q"z', "c:\\p a\\", "a&b"];
+			for (const mode of ["posix", "cmd"] as const) {
+				expect(shlexSplit(shlexQuote(args, mode), mode)).toEqual(args);
+			}
+		});
+	});
+
+	describe("naturalSort", () => {
+		it("should order digit runs numerically and ignore case", () => {
+			expect(naturalSort(["file10.txt", "File2.txt", "file1.txt", "file01.txt"])).toEqual([
+				"file1.txt",
+				"file01.txt",
+				"File2.txt",
+				"file10.txt",
+			]);
+		});
+	});
+
+	describe("TextBuffer", () => {
+		it("should undo grouped edits and restore the cursor", () => {
+			const buf = new TextBuffer("hello");
+			buf.setCursor(5);
+			buf.beginGroup();
+			buf.insert(5, " world");
+			buf.delete(0, 1);
+			buf.endGroup();
+			expect(buf.text()).toBe("ello world");
+			expect(buf.undo()).toBe(true);
+			expect(buf.text()).toBe("hello");
+			expect(buf.cursor).toBe(5);
+			expect(buf.redo()).toBe(true);
+			expect(buf.text()).toBe("ello world");
+			expect(buf.canRedo).toBe(false);
+		});
+	});
 });